@@ -49,9 +49,14 @@ impl AskPassDelegate {
     }
 }
 
+/// How long a session waits for the master process to ask for a password, unless told otherwise.
+#[cfg(unix)]
+const DEFAULT_CONNECTION_TIMEOUT: Duration = Duration::from_secs(10);
+
 #[cfg(unix)]
 pub struct AskPassSession {
     script_path: PathBuf,
+    connection_timeout: Option<Duration>,
     _askpass_task: Task<()>,
     askpass_opened_rx: Option<oneshot::Receiver<()>>,
     askpass_kill_master_rx: Option<oneshot::Receiver<()>>,
@@ -134,12 +139,20 @@ impl AskPassSession {
 
         Ok(Self {
             script_path: askpass_script_path,
+            connection_timeout: Some(DEFAULT_CONNECTION_TIMEOUT),
             _askpass_task: askpass_task,
             askpass_kill_master_rx: Some(askpass_kill_master_rx),
             askpass_opened_rx: Some(askpass_opened_rx),
         })
     }
 
+    /// Makes [`Self::run`] wait for a password prompt for as long as the master process runs, for processes that
+    /// may take arbitrarily long before prompting, e.g. commits running hooks before signing.
+    pub fn without_connection_timeout(mut self) -> Self {
+        self.connection_timeout = None;
+        self
+    }
+
     pub fn script_path(&self) -> &Path {
         &self.script_path
     }
@@ -149,7 +162,7 @@ impl AskPassSession {
     // future when this is no longer needed. Note that this can only be called once, but due to the
     // drop order this takes an &mut, so you can `drop()` it after you're done with the master process.
     pub async fn run(&mut self) -> AskPassResult {
-        let connection_timeout = Duration::from_secs(10);
+        let connection_timeout = self.connection_timeout;
         let askpass_opened_rx = self.askpass_opened_rx.take().expect("Only call run once");
         let askpass_kill_master_rx = self
             .askpass_kill_master_rx
@@ -163,16 +176,26 @@ impl AskPassSession {
                 return AskPassResult::CancelledByUser
             }
 
-            _ = futures::FutureExt::fuse(smol::Timer::after(connection_timeout)) => {
+            _ = futures::FutureExt::fuse(connection_timer(connection_timeout)) => {
                 return AskPassResult::Timedout
             }
         }
     }
 }
 
+async fn connection_timer(connection_timeout: Option<Duration>) {
+    match connection_timeout {
+        Some(connection_timeout) => {
+            smol::Timer::after(connection_timeout).await;
+        }
+        None => futures::future::pending::<()>().await,
+    }
+}
+
 #[cfg(not(unix))]
 pub struct AskPassSession {
     path: PathBuf,
+    connection_timeout: Option<Duration>,
 }
 
 #[cfg(not(unix))]
@@ -180,15 +203,21 @@ impl AskPassSession {
     pub async fn new(_: &BackgroundExecutor, _: AskPassDelegate) -> anyhow::Result<Self> {
         Ok(Self {
             path: PathBuf::new(),
+            connection_timeout: Some(Duration::from_secs(20)),
         })
     }
 
+    pub fn without_connection_timeout(mut self) -> Self {
+        self.connection_timeout = None;
+        self
+    }
+
     pub fn script_path(&self) -> &Path {
         &self.path
     }
 
     pub async fn run(&mut self) -> AskPassResult {
-        futures::FutureExt::fuse(smol::Timer::after(Duration::from_secs(20))).await;
+        connection_timer(self.connection_timeout).await;
         AskPassResult::Timedout
     }
 }
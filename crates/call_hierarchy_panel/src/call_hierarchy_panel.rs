@@ -0,0 +1,881 @@
+mod call_hierarchy_panel_settings;
+
+use std::{ops::Range, sync::Arc};
+
+use anyhow::Context as _;
+use call_hierarchy_panel_settings::{CallHierarchyPanelDockPosition, CallHierarchyPanelSettings};
+use collections::HashMap;
+use db::kvp::KEY_VALUE_STORE;
+use editor::{Editor, scroll::Autoscroll};
+use gpui::{
+    Action, AnyElement, App, AsyncWindowContext, ClickEvent, Context, Entity, EventEmitter,
+    FocusHandle, Focusable, KeyContext, MouseButton, Pixels, Render, ScrollStrategy, Task,
+    UniformListScrollHandle, WeakEntity, Window, actions, px, uniform_list,
+};
+use language::{Anchor, Bias, Buffer, PointUtf16, Unclipped};
+use menu::{Confirm, SelectFirst, SelectLast, SelectNext, SelectPrevious};
+use project::{CallHierarchyItem, Fs, Project};
+use serde::{Deserialize, Serialize};
+use settings::Settings;
+use ui::{ListItem, Tooltip, prelude::*};
+use util::ResultExt;
+use workspace::{
+    Workspace,
+    dock::{DockPosition, Panel, PanelEvent},
+};
+
+actions!(
+    call_hierarchy_panel,
+    [
+        CollapseSelectedEntry,
+        ExpandSelectedEntry,
+        ShowIncomingCalls,
+        ShowOutgoingCalls,
+        ToggleDirection,
+        ToggleFocus,
+    ]
+);
+
+const CALL_HIERARCHY_PANEL_KEY: &str = "CallHierarchyPanel";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CallHierarchyDirection {
+    Incoming,
+    Outgoing,
+}
+
+struct CallEntry {
+    item: CallHierarchyItem,
+    /// Call sites: within this entry's item for incoming calls, within the parent's item for outgoing calls.
+    ranges: Vec<Range<Unclipped<PointUtf16>>>,
+    depth: usize,
+    parent: Option<usize>,
+    /// `None` until the calls of this entry are fetched, which happens when it is first expanded.
+    children: Option<Vec<usize>>,
+    expanded: bool,
+}
+
+pub struct CallHierarchyPanel {
+    fs: Arc<dyn Fs>,
+    project: Entity<Project>,
+    workspace: WeakEntity<Workspace>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    width: Option<Pixels>,
+    active: bool,
+    pending_serialization: Task<Option<()>>,
+    direction: CallHierarchyDirection,
+    /// The buffer the hierarchy was prepared in; follow-up requests are routed through it.
+    buffer: Option<Entity<Buffer>>,
+    entries: Vec<CallEntry>,
+    roots: Vec<usize>,
+    /// Indices into `entries`, in display order.
+    visible_entries: Vec<usize>,
+    selected_entry: Option<usize>,
+    prepare_task: Task<()>,
+    fetch_tasks: HashMap<usize, Task<()>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedCallHierarchyPanel {
+    width: Option<Pixels>,
+    active: Option<bool>,
+}
+
+pub fn init(cx: &mut App) {
+    CallHierarchyPanelSettings::register(cx);
+
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace
+            .register_action(|workspace, _: &ToggleFocus, window, cx| {
+                workspace.toggle_panel_focus::<CallHierarchyPanel>(window, cx);
+            })
+            .register_action(|workspace, _: &ShowIncomingCalls, window, cx| {
+                show_call_hierarchy(workspace, CallHierarchyDirection::Incoming, window, cx);
+            })
+            .register_action(|workspace, _: &ShowOutgoingCalls, window, cx| {
+                show_call_hierarchy(workspace, CallHierarchyDirection::Outgoing, window, cx);
+            });
+    })
+    .detach();
+}
+
+fn show_call_hierarchy(
+    workspace: &mut Workspace,
+    direction: CallHierarchyDirection,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+        return;
+    };
+    let Some((buffer, position)) = editor.update(cx, |editor, cx| {
+        let head = editor.selections.newest_anchor().head();
+        editor.buffer().read(cx).text_anchor_for_position(head, cx)
+    }) else {
+        return;
+    };
+    let Some(panel) = workspace.focus_panel::<CallHierarchyPanel>(window, cx) else {
+        return;
+    };
+    panel.update(cx, |panel, cx| {
+        panel.show_call_hierarchy(buffer, position, direction, cx);
+    });
+}
+
+impl CallHierarchyPanel {
+    pub async fn load(
+        workspace: WeakEntity<Workspace>,
+        mut cx: AsyncWindowContext,
+    ) -> anyhow::Result<Entity<Self>> {
+        let serialized_panel = cx
+            .background_spawn(async move { KEY_VALUE_STORE.read_kvp(CALL_HIERARCHY_PANEL_KEY) })
+            .await
+            .context("loading call hierarchy panel")
+            .log_err()
+            .flatten()
+            .map(|panel| serde_json::from_str::<SerializedCallHierarchyPanel>(&panel))
+            .transpose()
+            .log_err()
+            .flatten();
+
+        workspace.update_in(&mut cx, |workspace, _, cx| {
+            let panel = cx.new(|cx| Self::new(workspace, cx));
+            if let Some(serialized_panel) = serialized_panel {
+                panel.update(cx, |panel, cx| {
+                    panel.width = serialized_panel.width.map(|px| px.round());
+                    panel.active = serialized_panel.active.unwrap_or(false);
+                    cx.notify();
+                });
+            }
+            panel
+        })
+    }
+
+    fn new(workspace: &Workspace, cx: &mut Context<Self>) -> Self {
+        Self {
+            fs: workspace.app_state().fs.clone(),
+            project: workspace.project().clone(),
+            workspace: workspace.weak_handle(),
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            width: None,
+            active: false,
+            pending_serialization: Task::ready(None),
+            direction: CallHierarchyDirection::Incoming,
+            buffer: None,
+            entries: Vec::new(),
+            roots: Vec::new(),
+            visible_entries: Vec::new(),
+            selected_entry: None,
+            prepare_task: Task::ready(()),
+            fetch_tasks: HashMap::default(),
+        }
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let width = self.width;
+        let active = Some(self.active);
+        self.pending_serialization = cx.background_spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        CALL_HIERARCHY_PANEL_KEY.into(),
+                        serde_json::to_string(&SerializedCallHierarchyPanel { width, active })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    /// Shows the calls to or from the function at `position`, replacing the current hierarchy.
+    pub fn show_call_hierarchy(
+        &mut self,
+        buffer: Entity<Buffer>,
+        position: Anchor,
+        direction: CallHierarchyDirection,
+        cx: &mut Context<Self>,
+    ) {
+        let items = self.project.update(cx, |project, cx| {
+            project.prepare_call_hierarchy(&buffer, position, cx)
+        });
+        self.direction = direction;
+        self.buffer = Some(buffer);
+        self.prepare_task = cx.spawn(async move |panel, cx| {
+            let items = items.await.log_err().unwrap_or_default();
+            panel
+                .update(cx, |panel, cx| panel.set_roots(items, cx))
+                .ok();
+        });
+    }
+
+    fn set_roots(&mut self, items: Vec<CallHierarchyItem>, cx: &mut Context<Self>) {
+        self.entries.clear();
+        self.fetch_tasks.clear();
+        self.roots = items
+            .into_iter()
+            .map(|item| {
+                self.entries.push(CallEntry {
+                    item,
+                    ranges: Vec::new(),
+                    depth: 0,
+                    parent: None,
+                    children: None,
+                    expanded: true,
+                });
+                self.entries.len() - 1
+            })
+            .collect();
+        for root in self.roots.clone() {
+            self.fetch_calls(root, cx);
+        }
+        self.selected_entry = self.roots.first().copied();
+        self.update_visible_entries();
+        cx.notify();
+    }
+
+    fn fetch_calls(&mut self, entry_ix: usize, cx: &mut Context<Self>) {
+        let Some(buffer) = self.buffer.clone() else {
+            return;
+        };
+        let item = self.entries[entry_ix].item.clone();
+        let calls = self.project.update(cx, |project, cx| match self.direction {
+            CallHierarchyDirection::Incoming => project.incoming_calls(&buffer, item, cx),
+            CallHierarchyDirection::Outgoing => project.outgoing_calls(&buffer, item, cx),
+        });
+        let task = cx.spawn(async move |panel, cx| {
+            let calls = calls.await.log_err().unwrap_or_default();
+            panel
+                .update(cx, |panel, cx| {
+                    panel.fetch_tasks.remove(&entry_ix);
+                    let depth = panel.entries[entry_ix].depth + 1;
+                    let children = calls
+                        .into_iter()
+                        .map(|call| {
+                            panel.entries.push(CallEntry {
+                                item: call.item,
+                                ranges: call.ranges,
+                                depth,
+                                parent: Some(entry_ix),
+                                children: None,
+                                expanded: false,
+                            });
+                            panel.entries.len() - 1
+                        })
+                        .collect();
+                    panel.entries[entry_ix].children = Some(children);
+                    panel.update_visible_entries();
+                    cx.notify();
+                })
+                .ok();
+        });
+        self.fetch_tasks.insert(entry_ix, task);
+    }
+
+    fn update_visible_entries(&mut self) {
+        self.visible_entries.clear();
+        let mut stack = self.roots.iter().rev().copied().collect::<Vec<_>>();
+        while let Some(entry_ix) = stack.pop() {
+            self.visible_entries.push(entry_ix);
+            let entry = &self.entries[entry_ix];
+            if let Some(children) = entry.children.as_ref().filter(|_| entry.expanded) {
+                stack.extend(children.iter().rev());
+            }
+        }
+    }
+
+    fn toggle_expanded(&mut self, entry_ix: usize, cx: &mut Context<Self>) {
+        let entry = &mut self.entries[entry_ix];
+        entry.expanded = !entry.expanded;
+        if entry.expanded && entry.children.is_none() && !self.fetch_tasks.contains_key(&entry_ix) {
+            self.fetch_calls(entry_ix, cx);
+        }
+        self.update_visible_entries();
+        cx.notify();
+    }
+
+    fn toggle_direction(&mut self, _: &ToggleDirection, _: &mut Window, cx: &mut Context<Self>) {
+        self.direction = match self.direction {
+            CallHierarchyDirection::Incoming => CallHierarchyDirection::Outgoing,
+            CallHierarchyDirection::Outgoing => CallHierarchyDirection::Incoming,
+        };
+        let items = self
+            .roots
+            .iter()
+            .map(|&root| self.entries[root].item.clone())
+            .collect();
+        self.set_roots(items, cx);
+    }
+
+    fn expand_selected_entry(
+        &mut self,
+        _: &ExpandSelectedEntry,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(entry_ix) = self.selected_entry else {
+            return;
+        };
+        let entry = &self.entries[entry_ix];
+        if !entry.expanded {
+            self.toggle_expanded(entry_ix, cx);
+        } else if let Some(&child) = entry.children.as_ref().and_then(|c| c.first()) {
+            self.select_entry(child, cx);
+        }
+    }
+
+    fn collapse_selected_entry(
+        &mut self,
+        _: &CollapseSelectedEntry,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(entry_ix) = self.selected_entry else {
+            return;
+        };
+        let entry = &self.entries[entry_ix];
+        if entry.expanded {
+            self.toggle_expanded(entry_ix, cx);
+        } else if let Some(parent) = entry.parent {
+            self.select_entry(parent, cx);
+        }
+    }
+
+    fn select_entry(&mut self, entry_ix: usize, cx: &mut Context<Self>) {
+        self.selected_entry = Some(entry_ix);
+        if let Some(visible_ix) = self.visible_entries.iter().position(|&ix| ix == entry_ix) {
+            self.scroll_handle
+                .scroll_to_item(visible_ix, ScrollStrategy::Center);
+        }
+        cx.notify();
+    }
+
+    fn selected_visible_ix(&self) -> Option<usize> {
+        let selected = self.selected_entry?;
+        self.visible_entries.iter().position(|&ix| ix == selected)
+    }
+
+    fn select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        let next_ix = self.selected_visible_ix().map_or(0, |ix| ix + 1);
+        if let Some(&entry_ix) = self.visible_entries.get(next_ix) {
+            self.select_entry(entry_ix, cx);
+        }
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, _: &mut Window, cx: &mut Context<Self>) {
+        let previous_ix = self
+            .selected_visible_ix()
+            .map_or(0, |ix| ix.saturating_sub(1));
+        if let Some(&entry_ix) = self.visible_entries.get(previous_ix) {
+            self.select_entry(entry_ix, cx);
+        }
+    }
+
+    fn select_first(&mut self, _: &SelectFirst, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(&entry_ix) = self.visible_entries.first() {
+            self.select_entry(entry_ix, cx);
+        }
+    }
+
+    fn select_last(&mut self, _: &SelectLast, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(&entry_ix) = self.visible_entries.last() {
+            self.select_entry(entry_ix, cx);
+        }
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(entry_ix) = self.selected_entry {
+            self.open_entry(entry_ix, true, window, cx);
+        }
+    }
+
+    fn open_entry(
+        &mut self,
+        entry_ix: usize,
+        focus_editor: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let entry = &self.entries[entry_ix];
+        let symbol = entry.item.symbol.clone();
+        // Incoming call sites are within the caller, so jump to the call instead of the declaration.
+        let position = match (self.direction, entry.ranges.first()) {
+            (CallHierarchyDirection::Incoming, Some(range)) => range.start,
+            _ => entry.item.selection_range.start,
+        };
+        let buffer = self.project.update(cx, |project, cx| {
+            project.open_buffer_for_symbol(&symbol, cx)
+        });
+        let workspace = self.workspace.clone();
+        cx.spawn_in(window, async move |_, cx| {
+            let buffer = buffer.await?;
+            workspace.update_in(cx, |workspace, window, cx| {
+                let position = buffer.read(cx).clip_point_utf16(position, Bias::Left);
+                let editor = workspace.open_project_item::<Editor>(
+                    workspace.active_pane().clone(),
+                    buffer,
+                    true,
+                    focus_editor,
+                    window,
+                    cx,
+                );
+                editor.update(cx, |editor, cx| {
+                    editor.change_selections(Some(Autoscroll::center()), window, cx, |s| {
+                        s.select_ranges([position..position])
+                    });
+                });
+            })?;
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn dispatch_context(&self) -> KeyContext {
+        let mut dispatch_context = KeyContext::new_with_defaults();
+        dispatch_context.add("CallHierarchyPanel");
+        dispatch_context.add("menu");
+        dispatch_context
+    }
+
+    fn render_entry(&self, entry_ix: usize, cx: &mut Context<Self>) -> AnyElement {
+        let entry = &self.entries[entry_ix];
+        let indent_size = CallHierarchyPanelSettings::get_global(cx).indent_size;
+        let has_calls = entry
+            .children
+            .as_ref()
+            .map_or(true, |children| !children.is_empty());
+        let file_name = entry
+            .item
+            .symbol
+            .path
+            .path
+            .file_name()
+            .map(|file_name| file_name.to_string_lossy().into_owned());
+        let call_count = entry.ranges.len();
+
+        ListItem::new(("call-hierarchy-entry", entry_ix))
+            .indent_level(entry.depth)
+            .indent_step_size(px(indent_size))
+            .toggle_state(self.selected_entry == Some(entry_ix))
+            .toggle(has_calls.then_some(entry.expanded))
+            .on_toggle(cx.listener(move |panel, _, _, cx| {
+                panel.toggle_expanded(entry_ix, cx);
+            }))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(Label::new(entry.item.symbol.name.clone()))
+                    .when_some(entry.item.detail.clone(), |this, detail| {
+                        this.child(
+                            Label::new(detail)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted)
+                                .truncate(),
+                        )
+                    })
+                    .when_some(file_name, |this, file_name| {
+                        this.child(
+                            Label::new(file_name)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                    })
+                    .when(call_count > 1, |this| {
+                        this.child(
+                            Label::new(format!("×{call_count}"))
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                    }),
+            )
+            .on_click(cx.listener(move |panel, event: &ClickEvent, window, cx| {
+                if event.down.button == MouseButton::Right || event.down.first_mouse {
+                    return;
+                }
+                panel.selected_entry = Some(entry_ix);
+                panel.open_entry(entry_ix, event.down.click_count > 1, window, cx);
+                cx.notify();
+            }))
+            .into_any_element()
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let (title, toggle_label) = match self.direction {
+            CallHierarchyDirection::Incoming => ("Incoming Calls", "Show Outgoing Calls"),
+            CallHierarchyDirection::Outgoing => ("Outgoing Calls", "Show Incoming Calls"),
+        };
+        let focus_handle = self.focus_handle.clone();
+
+        h_flex()
+            .h(px(32.))
+            .px_2()
+            .flex_shrink_0()
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().colors().border)
+            .child(Label::new(title).size(LabelSize::Small).color(Color::Muted))
+            .child(
+                Button::new("call-hierarchy-toggle-direction", toggle_label)
+                    .label_size(LabelSize::Small)
+                    .disabled(self.roots.is_empty())
+                    .tooltip(move |window, cx| {
+                        Tooltip::for_action_in(
+                            "Toggle Call Direction",
+                            &ToggleDirection,
+                            &focus_handle,
+                            window,
+                            cx,
+                        )
+                    })
+                    .on_click(cx.listener(|panel, _, window, cx| {
+                        panel.toggle_direction(&ToggleDirection, window, cx);
+                    })),
+            )
+    }
+}
+
+#[cfg(any(test, feature = "test-support"))]
+impl CallHierarchyPanel {
+    /// The names of the displayed entries, indented by their depth.
+    pub fn visible_entry_names(&self) -> Vec<String> {
+        self.visible_entries
+            .iter()
+            .map(|&ix| {
+                let entry = &self.entries[ix];
+                format!("{}{}", "  ".repeat(entry.depth), entry.item.symbol.name)
+            })
+            .collect()
+    }
+}
+
+impl Panel for CallHierarchyPanel {
+    fn persistent_name() -> &'static str {
+        "Call Hierarchy Panel"
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        match CallHierarchyPanelSettings::get_global(cx).dock {
+            CallHierarchyPanelDockPosition::Left => DockPosition::Left,
+            CallHierarchyPanelDockPosition::Right => DockPosition::Right,
+        }
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file::<CallHierarchyPanelSettings>(
+            self.fs.clone(),
+            cx,
+            move |settings, _| {
+                let dock = match position {
+                    DockPosition::Left | DockPosition::Bottom => {
+                        CallHierarchyPanelDockPosition::Left
+                    }
+                    DockPosition::Right => CallHierarchyPanelDockPosition::Right,
+                };
+                settings.dock = Some(dock);
+            },
+        );
+    }
+
+    fn size(&self, _: &Window, cx: &App) -> Pixels {
+        self.width
+            .unwrap_or_else(|| CallHierarchyPanelSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        CallHierarchyPanelSettings::get_global(cx)
+            .button
+            .then_some(IconName::Route)
+    }
+
+    fn icon_tooltip(&self, _window: &Window, _: &App) -> Option<&'static str> {
+        Some("Call Hierarchy Panel")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn starts_open(&self, _window: &Window, _: &App) -> bool {
+        self.active
+    }
+
+    fn set_active(&mut self, active: bool, _: &mut Window, cx: &mut Context<Self>) {
+        if self.active != active {
+            self.active = active;
+            self.serialize(cx);
+        }
+    }
+
+    fn activation_priority(&self) -> u32 {
+        10
+    }
+}
+
+impl Focusable for CallHierarchyPanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for CallHierarchyPanel {}
+
+impl Render for CallHierarchyPanel {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .id("call-hierarchy-panel")
+            .size_full()
+            .overflow_hidden()
+            .key_context(self.dispatch_context())
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::expand_selected_entry))
+            .on_action(cx.listener(Self::collapse_selected_entry))
+            .on_action(cx.listener(Self::toggle_direction))
+            .child(self.render_header(cx))
+            .map(|this| {
+                if self.visible_entries.is_empty() {
+                    let keystroke = window.keystroke_text_for(&ShowIncomingCalls);
+                    this.child(
+                        v_flex()
+                            .flex_1()
+                            .p_4()
+                            .gap_1()
+                            .justify_center()
+                            .child(
+                                h_flex()
+                                    .justify_center()
+                                    .child(Label::new("No call hierarchy to show")),
+                            )
+                            .child(
+                                h_flex().justify_center().child(
+                                    Label::new(format!(
+                                        "Place the cursor on a function and press {keystroke}"
+                                    ))
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                                ),
+                            ),
+                    )
+                } else {
+                    this.child(
+                        uniform_list(
+                            cx.entity().clone(),
+                            "call-hierarchy-entries",
+                            self.visible_entries.len(),
+                            |panel, range, _, cx| {
+                                panel.visible_entries[range]
+                                    .to_vec()
+                                    .into_iter()
+                                    .map(|entry_ix| panel.render_entry(entry_ix, cx))
+                                    .collect()
+                            },
+                        )
+                        .size_full()
+                        .track_scroll(self.scroll_handle.clone()),
+                    )
+                }
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use futures::StreamExt as _;
+    use gpui::{TestAppContext, VisualTestContext};
+    use language::{FakeLspAdapter, Language, LanguageConfig, LanguageMatcher};
+    use pretty_assertions::assert_eq;
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+    use workspace::OpenOptions;
+
+    use super::*;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings = SettingsStore::test(cx);
+            cx.set_global(settings);
+
+            theme::init(theme::LoadThemes::JustBase, cx);
+
+            language::init(cx);
+            editor::init(cx);
+            workspace::init_settings(cx);
+            Project::init_settings(cx);
+            super::init(cx);
+        });
+    }
+
+    fn lsp_item(name: &str, path: &str, line: u32) -> lsp::CallHierarchyItem {
+        lsp::CallHierarchyItem {
+            name: name.to_string(),
+            kind: lsp::SymbolKind::FUNCTION,
+            tags: None,
+            detail: None,
+            uri: lsp::Url::from_file_path(path).unwrap(),
+            range: lsp::Range::new(lsp::Position::new(line, 0), lsp::Position::new(line + 1, 0)),
+            selection_range: lsp::Range::new(
+                lsp::Position::new(line, 3),
+                lsp::Position::new(line, 3 + name.len() as u32),
+            ),
+            data: None,
+        }
+    }
+
+    fn incoming_call(name: &str, line: u32) -> lsp::CallHierarchyIncomingCall {
+        lsp::CallHierarchyIncomingCall {
+            from: lsp_item(name, path!("/dir/main.rs"), line),
+            from_ranges: vec![lsp::Range::new(
+                lsp::Position::new(line, 12),
+                lsp::Position::new(line, 16),
+            )],
+        }
+    }
+
+    #[gpui::test]
+    async fn test_call_hierarchy_panel(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/dir"),
+            json!({
+                "main.rs": "fn leaf() {}\nfn a() { leaf() }\nfn b() { a() }\nfn c() { leaf() }\n",
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+        let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+        language_registry.add(Arc::new(Language::new(
+            LanguageConfig {
+                name: "Rust".into(),
+                matcher: LanguageMatcher {
+                    path_suffixes: vec!["rs".to_string()],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            None,
+        )));
+        let mut fake_servers = language_registry.register_fake_lsp(
+            "Rust",
+            FakeLspAdapter {
+                capabilities: lsp::ServerCapabilities {
+                    call_hierarchy_provider: Some(lsp::CallHierarchyServerCapability::Simple(true)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+
+        let window = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let panel = window
+            .update(cx, |_, window, cx| {
+                cx.spawn_in(window, async |this, cx| {
+                    CallHierarchyPanel::load(this, cx.clone()).await
+                })
+            })
+            .unwrap()
+            .await
+            .expect("Failed to load call hierarchy panel");
+        window
+            .update(cx, |workspace, window, cx| {
+                workspace.add_panel(panel.clone(), window, cx);
+            })
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(*window, cx);
+
+        let editor = window
+            .update(cx, |workspace, window, cx| {
+                workspace.open_abs_path(
+                    Path::new(path!("/dir/main.rs")).to_path_buf(),
+                    OpenOptions::default(),
+                    window,
+                    cx,
+                )
+            })
+            .unwrap()
+            .await
+            .unwrap()
+            .downcast::<Editor>()
+            .unwrap();
+
+        let fake_server = fake_servers.next().await.unwrap();
+        fake_server.set_request_handler::<lsp::request::CallHierarchyPrepare, _, _>(
+            |params, _| async move {
+                assert_eq!(
+                    params.text_document_position_params.position,
+                    lsp::Position::new(0, 4)
+                );
+                Ok(Some(vec![lsp_item("leaf", path!("/dir/main.rs"), 0)]))
+            },
+        );
+        fake_server.set_request_handler::<lsp::request::CallHierarchyIncomingCalls, _, _>(
+            |params, _| async move {
+                Ok(Some(match params.item.name.as_str() {
+                    "leaf" => vec![incoming_call("a", 1), incoming_call("c", 3)],
+                    "a" => vec![incoming_call("b", 2)],
+                    _ => Vec::new(),
+                }))
+            },
+        );
+        fake_server.set_request_handler::<lsp::request::CallHierarchyOutgoingCalls, _, _>(
+            |_, _| async move { Ok(Some(Vec::new())) },
+        );
+
+        editor.update_in(cx, |editor, window, cx| {
+            editor.change_selections(None, window, cx, |s| {
+                s.select_ranges([PointUtf16::new(0, 4)..PointUtf16::new(0, 4)])
+            });
+        });
+        cx.dispatch_action(ShowIncomingCalls);
+        cx.run_until_parked();
+
+        panel.update(cx, |panel, _| {
+            assert_eq!(panel.visible_entry_names(), vec!["leaf", "  a", "  c"]);
+        });
+
+        // Callers are fetched lazily, when their entry is expanded.
+        cx.dispatch_action(SelectNext);
+        cx.dispatch_action(ExpandSelectedEntry);
+        cx.run_until_parked();
+        panel.update(cx, |panel, _| {
+            assert_eq!(
+                panel.visible_entry_names(),
+                vec!["leaf", "  a", "    b", "  c"]
+            );
+        });
+
+        cx.dispatch_action(CollapseSelectedEntry);
+        panel.update(cx, |panel, _| {
+            assert_eq!(panel.visible_entry_names(), vec!["leaf", "  a", "  c"]);
+        });
+
+        cx.dispatch_action(ToggleDirection);
+        cx.run_until_parked();
+        panel.update(cx, |panel, _| {
+            assert_eq!(panel.direction, CallHierarchyDirection::Outgoing);
+            assert_eq!(panel.visible_entry_names(), vec!["leaf"]);
+        });
+    }
+}
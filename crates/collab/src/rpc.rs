@@ -305,6 +305,13 @@ impl Server {
             .add_request_handler(forward_find_search_candidates_request)
             .add_request_handler(forward_read_only_project_request::<proto::GetDocumentHighlights>)
             .add_request_handler(forward_read_only_project_request::<proto::GetDocumentSymbols>)
+            .add_request_handler(forward_read_only_project_request::<proto::PrepareCallHierarchy>)
+            .add_request_handler(forward_read_only_project_request::<proto::GetIncomingCalls>)
+            .add_request_handler(forward_read_only_project_request::<proto::GetOutgoingCalls>)
+            .add_request_handler(forward_read_only_project_request::<proto::PrepareTypeHierarchy>)
+            .add_request_handler(forward_read_only_project_request::<proto::GetSupertypes>)
+            .add_request_handler(forward_read_only_project_request::<proto::GetSubtypes>)
+            .add_request_handler(forward_read_only_project_request::<proto::GetSemanticTokens>)
             .add_request_handler(forward_read_only_project_request::<proto::GetProjectSymbols>)
             .add_request_handler(forward_read_only_project_request::<proto::OpenBufferForSymbol>)
             .add_request_handler(forward_read_only_project_request::<proto::OpenBufferById>)
@@ -361,6 +368,9 @@ impl Server {
             .add_request_handler(update_buffer)
             .add_message_handler(broadcast_project_message_from_host::<proto::RefreshInlayHints>)
             .add_message_handler(broadcast_project_message_from_host::<proto::RefreshCodeLens>)
+            .add_message_handler(
+                broadcast_project_message_from_host::<proto::RefreshSemanticTokens>,
+            )
             .add_message_handler(broadcast_project_message_from_host::<proto::UpdateBufferFile>)
             .add_message_handler(broadcast_project_message_from_host::<proto::BufferReloaded>)
             .add_message_handler(broadcast_project_message_from_host::<proto::BufferSaved>)
@@ -419,9 +429,38 @@ impl Server {
             .add_request_handler(forward_mutating_project_request::<proto::SetIndexText>)
             .add_request_handler(forward_mutating_project_request::<proto::ToggleBreakpoint>)
             .add_message_handler(broadcast_project_message_from_host::<proto::BreakpointsForFile>)
+            .add_request_handler(
+                forward_mutating_project_request::<proto::UpdateExceptionBreakpoint>,
+            )
+            .add_message_handler(broadcast_project_message_from_host::<proto::ExceptionBreakpoints>)
+            .add_request_handler(
+                forward_mutating_project_request::<proto::UpdateFunctionBreakpoint>,
+            )
+            .add_message_handler(broadcast_project_message_from_host::<proto::FunctionBreakpoints>)
+            .add_request_handler(forward_mutating_project_request::<proto::UpdateDataBreakpoint>)
+            .add_message_handler(broadcast_project_message_from_host::<proto::DataBreakpoints>)
             .add_request_handler(forward_mutating_project_request::<proto::OpenCommitMessageBuffer>)
             .add_request_handler(forward_mutating_project_request::<proto::GitDiff>)
             .add_request_handler(forward_mutating_project_request::<proto::GitCreateBranch>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitStashPush>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitStashList>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitLog>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitRebase>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitRebaseContinue>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitRebaseSkip>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitRebaseAbort>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitRebaseStatus>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitSubmodules>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitSubmoduleUpdate>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitWorktrees>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitWorktreeAdd>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitWorktreeRemove>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitBlameAtRevision>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitLoadTextAtRevision>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitStashApply>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitStashPop>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitStashDrop>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitStashShow>)
             .add_request_handler(forward_mutating_project_request::<proto::GitChangeBranch>)
             .add_request_handler(forward_mutating_project_request::<proto::CheckForPushedCommits>)
             .add_message_handler(broadcast_project_message_from_host::<proto::AdvertiseContexts>)
@@ -105,6 +105,7 @@ impl CommandPalette {
                 Some(Command {
                     name: humanize_action_name(action.name()),
                     action,
+                    intercepted: false,
                 })
             })
             .collect();
@@ -157,6 +158,8 @@ pub struct CommandPaletteDelegate {
 struct Command {
     name: String,
     action: Box<dyn Action>,
+    /// Whether the command was produced by the [`CommandPaletteInterceptor`].
+    intercepted: bool,
 }
 
 impl Clone for Command {
@@ -164,6 +167,7 @@ impl Clone for Command {
         Self {
             name: self.name.clone(),
             action: self.action.boxed_clone(),
+            intercepted: self.intercepted,
         }
     }
 }
@@ -200,7 +204,9 @@ impl CommandPaletteDelegate {
             .map(|interceptor| interceptor.intercept(&query, cx))
             .unwrap_or_default();
 
+        let mut intercepted = true;
         if parse_zed_link(&query, cx).is_some() {
+            intercepted = false;
             intercept_results = vec![CommandInterceptResult {
                 action: OpenZedUrl { url: query.clone() }.boxed_clone(),
                 string: query.clone(),
@@ -225,6 +231,7 @@ impl CommandPaletteDelegate {
             commands.push(Command {
                 name: string.clone(),
                 action,
+                intercepted,
             });
             new_matches.push(StringMatch {
                 candidate_id: commands.len() - 1,
@@ -408,6 +415,13 @@ impl PickerDelegate for CommandPaletteDelegate {
                 .await
         })
         .detach_and_log_err(cx);
+        if command.intercepted {
+            if let Some(observer) = CommandPaletteInterceptor::try_global(cx)
+                .and_then(|interceptor| interceptor.confirm_observer())
+            {
+                observer(&latest_query, cx);
+            }
+        }
         let action = command.action;
         window.focus(&self.previous_focus_handle);
         self.dismissed(window, cx);
@@ -2,7 +2,7 @@
 
 #![deny(missing_docs)]
 
-use std::any::TypeId;
+use std::{any::TypeId, rc::Rc};
 
 use collections::HashSet;
 use derive_more::{Deref, DerefMut};
@@ -107,9 +107,10 @@ pub struct CommandInterceptResult {
 
 /// An interceptor for the command palette.
 #[derive(Default)]
-pub struct CommandPaletteInterceptor(
-    Option<Box<dyn Fn(&str, &App) -> Vec<CommandInterceptResult>>>,
-);
+pub struct CommandPaletteInterceptor {
+    handler: Option<Box<dyn Fn(&str, &App) -> Vec<CommandInterceptResult>>>,
+    confirm_observer: Option<Rc<dyn Fn(&str, &mut App)>>,
+}
 
 #[derive(Default)]
 struct GlobalCommandPaletteInterceptor(CommandPaletteInterceptor);
@@ -133,7 +134,7 @@ impl CommandPaletteInterceptor {
 
     /// Intercepts the given query from the command palette.
     pub fn intercept(&self, query: &str, cx: &App) -> Vec<CommandInterceptResult> {
-        if let Some(handler) = self.0.as_ref() {
+        if let Some(handler) = self.handler.as_ref() {
             (handler)(query, cx)
         } else {
             Vec::new()
@@ -142,13 +143,25 @@ impl CommandPaletteInterceptor {
 
     /// Clears the global interceptor.
     pub fn clear(&mut self) {
-        self.0 = None;
+        self.handler = None;
+        self.confirm_observer = None;
     }
 
     /// Sets the global interceptor.
     ///
     /// This will override the previous interceptor, if it exists.
     pub fn set(&mut self, handler: Box<dyn Fn(&str, &App) -> Vec<CommandInterceptResult>>) {
-        self.0 = Some(handler);
+        self.handler = Some(handler);
+    }
+
+    /// Sets a callback to run with the query whenever one of the interceptor's results is
+    /// confirmed, e.g. to keep a history of the intercepted commands.
+    pub fn set_confirm_observer(&mut self, observer: Rc<dyn Fn(&str, &mut App)>) {
+        self.confirm_observer = Some(observer);
+    }
+
+    /// Returns the callback to run when one of the interceptor's results is confirmed.
+    pub fn confirm_observer(&self) -> Option<Rc<dyn Fn(&str, &mut App)>> {
+        self.confirm_observer.clone()
     }
 }
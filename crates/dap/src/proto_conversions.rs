@@ -589,3 +589,118 @@ impl ProtoConversion for dap_types::Thread {
         }
     }
 }
+
+impl ProtoConversion for dap_types::DataBreakpointAccessType {
+    type ProtoType = proto::DapDataBreakpointAccessType;
+    type Output = Self;
+
+    fn to_proto(&self) -> Self::ProtoType {
+        match self {
+            dap_types::DataBreakpointAccessType::Read => proto::DapDataBreakpointAccessType::Read,
+            dap_types::DataBreakpointAccessType::Write => proto::DapDataBreakpointAccessType::Write,
+            dap_types::DataBreakpointAccessType::ReadWrite => {
+                proto::DapDataBreakpointAccessType::ReadWrite
+            }
+        }
+    }
+
+    fn from_proto(payload: Self::ProtoType) -> Self {
+        match payload {
+            proto::DapDataBreakpointAccessType::Read => dap_types::DataBreakpointAccessType::Read,
+            proto::DapDataBreakpointAccessType::Write => dap_types::DataBreakpointAccessType::Write,
+            proto::DapDataBreakpointAccessType::ReadWrite => {
+                dap_types::DataBreakpointAccessType::ReadWrite
+            }
+        }
+    }
+}
+
+impl ProtoConversion for dap_types::DisassembledInstruction {
+    type ProtoType = proto::DapDisassembledInstruction;
+    type Output = Self;
+
+    fn to_proto(&self) -> Self::ProtoType {
+        proto::DapDisassembledInstruction {
+            address: self.address.clone(),
+            instruction_bytes: self.instruction_bytes.clone(),
+            instruction: self.instruction.clone(),
+            symbol: self.symbol.clone(),
+            location: self.location.as_ref().map(|location| location.to_proto()),
+            line: self.line,
+            column: self.column,
+            end_line: self.end_line,
+            end_column: self.end_column,
+        }
+    }
+
+    fn from_proto(payload: Self::ProtoType) -> Self {
+        Self {
+            address: payload.address,
+            instruction_bytes: payload.instruction_bytes,
+            instruction: payload.instruction,
+            symbol: payload.symbol,
+            location: payload.location.map(Source::from_proto),
+            line: payload.line,
+            column: payload.column,
+            end_line: payload.end_line,
+            end_column: payload.end_column,
+            presentation_hint: None, // TODO Debugger Collab
+        }
+    }
+}
+
+impl ProtoConversion for dap_types::StepInTarget {
+    type ProtoType = proto::DapStepInTarget;
+    type Output = Self;
+
+    fn to_proto(&self) -> Self::ProtoType {
+        proto::DapStepInTarget {
+            id: self.id,
+            label: self.label.clone(),
+            line: self.line,
+            column: self.column,
+            end_line: self.end_line,
+            end_column: self.end_column,
+        }
+    }
+
+    fn from_proto(payload: Self::ProtoType) -> Self {
+        Self {
+            id: payload.id,
+            label: payload.label,
+            line: payload.line,
+            column: payload.column,
+            end_line: payload.end_line,
+            end_column: payload.end_column,
+        }
+    }
+}
+
+impl ProtoConversion for dap_types::GotoTarget {
+    type ProtoType = proto::DapGotoTarget;
+    type Output = Self;
+
+    fn to_proto(&self) -> Self::ProtoType {
+        proto::DapGotoTarget {
+            id: self.id,
+            label: self.label.clone(),
+            line: self.line,
+            column: self.column,
+            end_line: self.end_line,
+            end_column: self.end_column,
+            instruction_pointer_reference: self.instruction_pointer_reference.clone(),
+        }
+    }
+
+    fn from_proto(payload: Self::ProtoType) -> Self {
+        Self {
+            id: payload.id,
+            label: payload.label,
+            line: payload.line,
+            column: payload.column,
+            end_line: payload.end_line,
+            end_column: payload.end_column,
+            instruction_pointer_reference: payload.instruction_pointer_reference,
+        }
+    }
+}
@@ -24,8 +24,9 @@ use terminal_view::terminal_panel::TerminalPanel;
 use ui::prelude::*;
 use util::ResultExt;
 use workspace::{
-    ClearAllBreakpoints, Continue, Disconnect, Pane, Pause, Restart, StepBack, StepInto, StepOut,
-    StepOver, Stop, ToggleIgnoreBreakpoints, Workspace,
+    ClearAllBreakpoints, Continue, Disconnect, JumpToCursor, Pane, Pause, Restart, StepBack,
+    StepInto, StepIntoInstruction, StepIntoTarget, StepOut, StepOver, StepOverInstruction, Stop,
+    ToggleIgnoreBreakpoints, Workspace,
     dock::{DockPosition, Panel, PanelEvent},
     pane,
 };
@@ -186,32 +187,42 @@ impl DebugPanel {
                 });
 
                 cx.observe(&debug_panel, |_, debug_panel, cx| {
-                    let (has_active_session, supports_restart, support_step_back) = debug_panel
-                        .update(cx, |this, cx| {
-                            this.active_session(cx)
-                                .map(|item| {
-                                    let running = item.read(cx).mode().as_running().cloned();
-
-                                    match running {
-                                        Some(running) => {
-                                            let caps = running.read(cx).capabilities(cx);
-                                            (
-                                                true,
-                                                caps.supports_restart_request.unwrap_or_default(),
-                                                caps.supports_step_back.unwrap_or_default(),
-                                            )
-                                        }
-                                        None => (false, false, false),
+                    let (
+                        has_active_session,
+                        supports_restart,
+                        support_step_back,
+                        supports_step_in_targets,
+                        supports_goto_targets,
+                    ) = debug_panel.update(cx, |this, cx| {
+                        this.active_session(cx)
+                            .map(|item| {
+                                let running = item.read(cx).mode().as_running().cloned();
+
+                                match running {
+                                    Some(running) => {
+                                        let caps = running.read(cx).capabilities(cx);
+                                        (
+                                            true,
+                                            caps.supports_restart_request.unwrap_or_default(),
+                                            caps.supports_step_back.unwrap_or_default(),
+                                            caps.supports_step_in_targets_request
+                                                .unwrap_or_default(),
+                                            caps.supports_goto_targets_request.unwrap_or_default(),
+                                        )
                                     }
-                                })
-                                .unwrap_or((false, false, false))
-                        });
+                                    None => (false, false, false, false, false),
+                                }
+                            })
+                            .unwrap_or((false, false, false, false, false))
+                    });
 
                     let filter = CommandPaletteFilter::global_mut(cx);
                     let debugger_action_types = [
                         TypeId::of::<Continue>(),
                         TypeId::of::<StepOver>(),
                         TypeId::of::<StepInto>(),
+                        TypeId::of::<StepOverInstruction>(),
+                        TypeId::of::<StepIntoInstruction>(),
                         TypeId::of::<StepOut>(),
                         TypeId::of::<Stop>(),
                         TypeId::of::<Disconnect>(),
@@ -221,6 +232,8 @@ impl DebugPanel {
 
                     let step_back_action_type = [TypeId::of::<StepBack>()];
                     let restart_action_type = [TypeId::of::<Restart>()];
+                    let step_into_target_action_type = [TypeId::of::<StepIntoTarget>()];
+                    let jump_to_cursor_action_type = [TypeId::of::<JumpToCursor>()];
 
                     if has_active_session {
                         filter.show_action_types(debugger_action_types.iter());
@@ -236,11 +249,25 @@ impl DebugPanel {
                         } else {
                             filter.hide_action_types(&step_back_action_type);
                         }
+
+                        if supports_step_in_targets {
+                            filter.show_action_types(step_into_target_action_type.iter());
+                        } else {
+                            filter.hide_action_types(&step_into_target_action_type);
+                        }
+
+                        if supports_goto_targets {
+                            filter.show_action_types(jump_to_cursor_action_type.iter());
+                        } else {
+                            filter.hide_action_types(&jump_to_cursor_action_type);
+                        }
                     } else {
                         // show only the `debug: start`
                         filter.hide_action_types(&debugger_action_types);
                         filter.hide_action_types(&step_back_action_type);
                         filter.hide_action_types(&restart_action_type);
+                        filter.hide_action_types(&step_into_target_action_type);
+                        filter.hide_action_types(&jump_to_cursor_action_type);
                     }
                 })
                 .detach();
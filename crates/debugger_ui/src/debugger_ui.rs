@@ -1,17 +1,21 @@
 use dap::debugger_settings::DebuggerSettings;
 use debugger_panel::{DebugPanel, ToggleFocus};
+use editor::Editor;
 use feature_flags::{Debugger, FeatureFlagViewExt};
 use gpui::App;
+use language::ToPoint as _;
+use project::debugger::breakpoint_store::BreakpointStore;
 use session::DebugSession;
 use settings::Settings;
 use workspace::{
-    Pause, Restart, ShutdownDebugAdapters, StepBack, StepInto, StepOver, Stop,
-    ToggleIgnoreBreakpoints, Workspace,
+    JumpToCursor, Pause, Restart, ShutdownDebugAdapters, StepBack, StepInto, StepIntoInstruction,
+    StepIntoTarget, StepOver, StepOverInstruction, Stop, ToggleIgnoreBreakpoints, Workspace,
 };
 
 pub mod attach_modal;
 pub mod debugger_panel;
 pub mod session;
+pub mod step_in_target_modal;
 
 #[cfg(test)]
 mod tests;
@@ -74,6 +78,67 @@ pub fn init(cx: &mut App) {
                         active_item.update(cx, |item, cx| item.step_over(cx))
                     }
                 })
+                .register_action(|workspace, _: &StepIntoInstruction, _, cx| {
+                    let debug_panel = workspace.panel::<DebugPanel>(cx).unwrap();
+
+                    if let Some(active_item) = debug_panel.read_with(cx, |panel, cx| {
+                        panel
+                            .active_session(cx)
+                            .and_then(|session| session.read(cx).mode().as_running().cloned())
+                    }) {
+                        active_item.update(cx, |item, cx| item.step_in_instruction(cx))
+                    }
+                })
+                .register_action(|workspace, _: &StepOverInstruction, _, cx| {
+                    let debug_panel = workspace.panel::<DebugPanel>(cx).unwrap();
+
+                    if let Some(active_item) = debug_panel.read_with(cx, |panel, cx| {
+                        panel
+                            .active_session(cx)
+                            .and_then(|session| session.read(cx).mode().as_running().cloned())
+                    }) {
+                        active_item.update(cx, |item, cx| item.step_over_instruction(cx))
+                    }
+                })
+                .register_action(|workspace, _: &StepIntoTarget, window, cx| {
+                    let debug_panel = workspace.panel::<DebugPanel>(cx).unwrap();
+
+                    if let Some(active_item) = debug_panel.read_with(cx, |panel, cx| {
+                        panel
+                            .active_session(cx)
+                            .and_then(|session| session.read(cx).mode().as_running().cloned())
+                    }) {
+                        active_item.update(cx, |item, cx| item.step_in_targets(window, cx))
+                    }
+                })
+                .register_action(|workspace, _: &JumpToCursor, _, cx| {
+                    let debug_panel = workspace.panel::<DebugPanel>(cx).unwrap();
+
+                    let Some(active_item) = debug_panel.read_with(cx, |panel, cx| {
+                        panel
+                            .active_session(cx)
+                            .and_then(|session| session.read(cx).mode().as_running().cloned())
+                    }) else {
+                        return;
+                    };
+                    let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+                        return;
+                    };
+                    let Some((buffer, position)) = editor.update(cx, |editor, cx| {
+                        let head = editor.selections.newest_anchor().head();
+                        editor.buffer().read(cx).text_anchor_for_position(head, cx)
+                    }) else {
+                        return;
+                    };
+                    let Some(abs_path) = BreakpointStore::abs_path_from_buffer(&buffer, cx) else {
+                        return;
+                    };
+                    let row = position.to_point(&buffer.read(cx).snapshot()).row;
+
+                    active_item.update(cx, |item, cx| {
+                        item.jump_to_line(abs_path, row as u64 + 1, cx)
+                    })
+                })
                 .register_action(|workspace, _: &StepBack, _, cx| {
                     let debug_panel = workspace.panel::<DebugPanel>(cx).unwrap();
 
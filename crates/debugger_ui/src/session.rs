@@ -62,8 +62,11 @@ pub enum DebugPanelItemEvent {
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum ThreadItem {
+    Breakpoints,
     Console,
+    Disassembly,
     LoadedSource,
+    Memory,
     Modules,
     Variables,
 }
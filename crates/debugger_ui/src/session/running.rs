@@ -1,19 +1,30 @@
+mod breakpoint_list;
 mod console;
+mod disassembly_view;
 mod loaded_source_list;
+mod memory_view;
 mod module_list;
 pub mod stack_frame_list;
 pub mod variable_list;
 
 use super::{DebugPanelItemEvent, ThreadItem};
+use crate::step_in_target_modal::StepInTargetModal;
+use breakpoint_list::BreakpointList;
 use console::Console;
-use dap::{Capabilities, Thread, client::SessionId, debugger_settings::DebuggerSettings};
+use dap::{
+    Capabilities, SteppingGranularity, Thread, client::SessionId,
+    debugger_settings::DebuggerSettings,
+};
+use disassembly_view::DisassemblyView;
 use gpui::{AppContext, Entity, EventEmitter, FocusHandle, Focusable, Subscription, WeakEntity};
 use loaded_source_list::LoadedSourceList;
+use memory_view::{MemoryView, MemoryViewEvent};
 use module_list::ModuleList;
 use project::debugger::session::{Session, SessionEvent, ThreadId, ThreadStatus};
 use rpc::proto::ViewId;
 use settings::Settings;
 use stack_frame_list::StackFrameList;
+use std::{path::Path, sync::Arc};
 use ui::{
     ActiveTheme, AnyElement, App, Button, ButtonCommon, Clickable, Context, ContextMenu,
     Disableable, Divider, DropdownMenu, FluentBuilder, IconButton, IconName, IconSize, Indicator,
@@ -39,6 +50,9 @@ pub struct RunningState {
     _subscriptions: Vec<Subscription>,
     stack_frame_list: Entity<stack_frame_list::StackFrameList>,
     loaded_source_list: Entity<loaded_source_list::LoadedSourceList>,
+    breakpoint_list: Entity<breakpoint_list::BreakpointList>,
+    disassembly_view: Entity<disassembly_view::DisassemblyView>,
+    memory_view: Entity<memory_view::MemoryView>,
 }
 
 impl Render for RunningState {
@@ -213,6 +227,27 @@ impl Render for RunningState {
                                                 Tooltip::text("Step in")(window, cx)
                                             }),
                                     )
+                                    .when(
+                                        capabilities
+                                            .supports_step_in_targets_request
+                                            .unwrap_or_default(),
+                                        |this| {
+                                            this.child(
+                                                IconButton::new(
+                                                    "debug-step-in-target",
+                                                    IconName::ChevronDown,
+                                                )
+                                                .icon_size(IconSize::XSmall)
+                                                .on_click(cx.listener(|this, _, window, cx| {
+                                                    this.step_in_targets(window, cx);
+                                                }))
+                                                .disabled(thread_status != ThreadStatus::Stopped)
+                                                .tooltip(move |window, cx| {
+                                                    Tooltip::text("Step into target")(window, cx)
+                                                }),
+                                            )
+                                        },
+                                    )
                                     .child(
                                         IconButton::new("debug-step-out", IconName::DebugStepOut)
                                             .icon_size(IconSize::XSmall)
@@ -339,6 +374,47 @@ impl Render for RunningState {
                                     ))
                                 },
                             )
+                            .when(
+                                capabilities
+                                    .exception_breakpoint_filters
+                                    .as_ref()
+                                    .is_some_and(|filters| !filters.is_empty())
+                                    || capabilities
+                                        .supports_function_breakpoints
+                                        .unwrap_or_default()
+                                    || capabilities.supports_data_breakpoints.unwrap_or_default(),
+                                |this| {
+                                    this.child(self.render_entry_button(
+                                        &SharedString::from("Breakpoints"),
+                                        ThreadItem::Breakpoints,
+                                        cx,
+                                    ))
+                                },
+                            )
+                            .when(
+                                capabilities
+                                    .supports_disassemble_request
+                                    .unwrap_or_default(),
+                                |this| {
+                                    this.child(self.render_entry_button(
+                                        &SharedString::from("Disassembly"),
+                                        ThreadItem::Disassembly,
+                                        cx,
+                                    ))
+                                },
+                            )
+                            .when(
+                                capabilities
+                                    .supports_read_memory_request
+                                    .unwrap_or_default(),
+                                |this| {
+                                    this.child(self.render_entry_button(
+                                        &SharedString::from("Memory"),
+                                        ThreadItem::Memory,
+                                        cx,
+                                    ))
+                                },
+                            )
                             .child(self.render_entry_button(
                                 &SharedString::from("Console"),
                                 ThreadItem::Console,
@@ -354,6 +430,15 @@ impl Render for RunningState {
                     .when(*active_thread_item == ThreadItem::LoadedSource, |this| {
                         this.size_full().child(self.loaded_source_list.clone())
                     })
+                    .when(*active_thread_item == ThreadItem::Breakpoints, |this| {
+                        this.size_full().child(self.breakpoint_list.clone())
+                    })
+                    .when(*active_thread_item == ThreadItem::Disassembly, |this| {
+                        this.size_full().child(self.disassembly_view.clone())
+                    })
+                    .when(*active_thread_item == ThreadItem::Memory, |this| {
+                        this.size_full().child(self.memory_view.clone())
+                    })
                     .when(*active_thread_item == ThreadItem::Console, |this| {
                         this.child(self.console.clone())
                     }),
@@ -372,11 +457,34 @@ impl RunningState {
         let session_id = session.read(cx).session_id();
         let weak_state = cx.weak_entity();
         let stack_frame_list = cx.new(|cx| {
-            StackFrameList::new(workspace.clone(), session.clone(), weak_state, window, cx)
+            StackFrameList::new(
+                workspace.clone(),
+                session.clone(),
+                weak_state.clone(),
+                window,
+                cx,
+            )
+        });
+
+        let breakpoint_list =
+            cx.new(|cx| BreakpointList::new(session.clone(), workspace.clone(), window, cx));
+
+        let memory_view = cx.new(|cx| MemoryView::new(session.clone(), window, cx));
+
+        let disassembly_view = cx.new(|cx| {
+            DisassemblyView::new(session.clone(), weak_state, stack_frame_list.clone(), cx)
         });
 
-        let variable_list =
-            cx.new(|cx| VariableList::new(session.clone(), stack_frame_list.clone(), window, cx));
+        let variable_list = cx.new(|cx| {
+            VariableList::new(
+                session.clone(),
+                stack_frame_list.clone(),
+                breakpoint_list.clone(),
+                memory_view.clone(),
+                window,
+                cx,
+            )
+        });
 
         let module_list = cx.new(|cx| ModuleList::new(session.clone(), workspace.clone(), cx));
 
@@ -394,6 +502,12 @@ impl RunningState {
 
         let _subscriptions = vec![
             cx.observe(&module_list, |_, _, cx| cx.notify()),
+            cx.subscribe(&memory_view, |this, _, event, cx| match event {
+                MemoryViewEvent::Opened => {
+                    this.active_thread_item = ThreadItem::Memory;
+                    cx.notify();
+                }
+            }),
             cx.subscribe_in(&session, window, |this, _, event, window, cx| {
                 match event {
                     SessionEvent::Stopped(thread_id) => {
@@ -429,6 +543,9 @@ impl RunningState {
             _remote_id: None,
             stack_frame_list,
             loaded_source_list,
+            breakpoint_list,
+            disassembly_view,
+            memory_view,
             session_id,
             show_console_indicator: false,
             active_thread_item: ThreadItem::Variables,
@@ -471,6 +588,21 @@ impl RunningState {
         &self.module_list
     }
 
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn breakpoint_list(&self) -> &Entity<BreakpointList> {
+        &self.breakpoint_list
+    }
+
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn disassembly_view(&self) -> &Entity<DisassemblyView> {
+        &self.disassembly_view
+    }
+
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn memory_view(&self) -> &Entity<MemoryView> {
+        &self.memory_view
+    }
+
     #[cfg(any(test, feature = "test-support"))]
     pub fn variable_list(&self) -> &Entity<VariableList> {
         &self.variable_list
@@ -595,6 +727,100 @@ impl RunningState {
         });
     }
 
+    pub fn step_over_instruction(&mut self, cx: &mut Context<Self>) {
+        let Some(thread_id) = self.thread_id else {
+            return;
+        };
+
+        self.session().update(cx, |state, cx| {
+            state.step_over(thread_id, SteppingGranularity::Instruction, cx);
+        });
+    }
+
+    pub fn step_in_instruction(&mut self, cx: &mut Context<Self>) {
+        let Some(thread_id) = self.thread_id else {
+            return;
+        };
+
+        self.session().update(cx, |state, cx| {
+            state.step_in(thread_id, SteppingGranularity::Instruction, cx);
+        });
+    }
+
+    /// Asks the adapter for the calls on the current line and lets the user pick
+    /// which one to step into.
+    pub fn step_in_targets(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(thread_id) = self.thread_id else {
+            return;
+        };
+        let Some(stack_frame_id) = self.stack_frame_list.read(cx).current_stack_frame_id() else {
+            return;
+        };
+
+        let granularity = DebuggerSettings::get_global(cx).stepping_granularity;
+        let targets = self.session.update(cx, |session, cx| {
+            session.step_in_targets(stack_frame_id, cx)
+        });
+
+        cx.spawn_in(window, async move |this, cx| {
+            let targets = targets.await.unwrap_or_default();
+
+            this.update_in(cx, |this, window, cx| {
+                if targets.len() <= 1 {
+                    let target_id = targets.first().map(|target| target.id);
+                    this.session.update(cx, |session, cx| {
+                        session.step_in_target(thread_id, target_id, granularity, cx);
+                    });
+                    return;
+                }
+
+                let session = this.session.clone();
+                this.workspace
+                    .update(cx, |workspace, cx| {
+                        workspace.toggle_modal(window, cx, |window, cx| {
+                            StepInTargetModal::new(
+                                session,
+                                thread_id,
+                                granularity,
+                                targets,
+                                window,
+                                cx,
+                            )
+                        });
+                    })
+                    .log_err();
+            })
+            .log_err();
+        })
+        .detach();
+    }
+
+    /// Moves the execution point to the given (1-based) line without executing
+    /// the code in between.
+    pub fn jump_to_line(&mut self, abs_path: Arc<Path>, line: u64, cx: &mut Context<Self>) {
+        let Some(thread_id) = self.thread_id else {
+            return;
+        };
+
+        let targets = self
+            .session
+            .update(cx, |session, cx| session.goto_targets(abs_path, line, cx));
+
+        cx.spawn(async move |this, cx| {
+            let Some(target) = targets.await.and_then(|targets| targets.into_iter().next()) else {
+                return;
+            };
+
+            this.update(cx, |this, cx| {
+                this.session.update(cx, |session, cx| {
+                    session.goto(thread_id, target.id, cx);
+                });
+            })
+            .log_err();
+        })
+        .detach();
+    }
+
     pub fn step_out(&mut self, cx: &mut Context<Self>) {
         let Some(thread_id) = self.thread_id else {
             return;
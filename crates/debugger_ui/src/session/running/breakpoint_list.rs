@@ -0,0 +1,673 @@
+use dap::ExceptionBreakpointsFilter;
+use editor::Editor;
+use gpui::{
+    AnyElement, ClickEvent, Entity, FocusHandle, Focusable, Subscription, TextStyleRefinement,
+    WeakEntity,
+};
+use project::debugger::{
+    breakpoint_store::{
+        BreakpointState, BreakpointStore, DataBreakpoint, DataBreakpointAccessType,
+        ExceptionBreakpoint, FunctionBreakpoint,
+    },
+    session::{Session, SessionEvent},
+};
+use ui::{Checkbox, Tooltip, prelude::*};
+use util::ResultExt;
+use workspace::Workspace;
+
+/// Lists the breakpoints of a session that aren't tied to a source line.
+pub struct BreakpointList {
+    session: Entity<Session>,
+    workspace: WeakEntity<Workspace>,
+    focus_handle: FocusHandle,
+    /// Exception filter whose condition is currently being edited.
+    edited_filter: Option<(SharedString, Entity<Editor>)>,
+    /// Editor for the name of a function breakpoint that's being added.
+    new_function_breakpoint: Option<Entity<Editor>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl BreakpointList {
+    pub fn new(
+        session: Entity<Session>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let focus_handle = cx.focus_handle();
+
+        let mut _subscriptions = vec![
+            cx.subscribe(&session, |_, _, event, cx| match event {
+                SessionEvent::Stopped(_) => cx.notify(),
+                _ => {}
+            }),
+            cx.on_focus_out(&focus_handle, window, |this, _, _, cx| {
+                this.edited_filter.take();
+                this.new_function_breakpoint.take();
+                cx.notify();
+            }),
+        ];
+
+        if let Some(breakpoint_store) = workspace
+            .read_with(cx, |workspace, cx| {
+                workspace.project().read(cx).breakpoint_store()
+            })
+            .log_err()
+        {
+            _subscriptions.push(cx.observe(&breakpoint_store, |_, _, cx| cx.notify()));
+        }
+
+        Self {
+            session,
+            workspace,
+            focus_handle,
+            edited_filter: None,
+            new_function_breakpoint: None,
+            _subscriptions,
+        }
+    }
+
+    fn breakpoint_store(&self, cx: &App) -> Option<Entity<BreakpointStore>> {
+        self.workspace
+            .read_with(cx, |workspace, cx| {
+                workspace.project().read(cx).breakpoint_store()
+            })
+            .ok()
+    }
+
+    fn exception_filters(&self, cx: &App) -> Vec<ExceptionBreakpointsFilter> {
+        self.session
+            .read(cx)
+            .capabilities()
+            .exception_breakpoint_filters
+            .clone()
+            .unwrap_or_default()
+    }
+
+    fn supports_filter_conditions(&self, filter: &ExceptionBreakpointsFilter, cx: &App) -> bool {
+        self.session
+            .read(cx)
+            .capabilities()
+            .supports_exception_filter_options
+            .unwrap_or_default()
+            && filter.supports_condition.unwrap_or_default()
+    }
+
+    pub(crate) fn toggle_exception_breakpoint(
+        &mut self,
+        filter: &ExceptionBreakpointsFilter,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(breakpoint_store) = self.breakpoint_store(cx) {
+            breakpoint_store.update(cx, |store, cx| {
+                store.toggle_exception_breakpoint(filter, cx);
+            });
+        }
+        cx.notify();
+    }
+
+    pub(crate) fn add_function_breakpoint(&mut self, name: String, cx: &mut Context<Self>) {
+        let name = name.trim();
+        if name.is_empty() {
+            return;
+        }
+
+        if let Some(breakpoint_store) = self.breakpoint_store(cx) {
+            breakpoint_store.update(cx, |store, cx| {
+                store.update_function_breakpoint(FunctionBreakpoint::new(name), cx);
+            });
+        }
+        cx.notify();
+    }
+
+    /// Asks the adapter for a data id for the variable and watches it for writes when possible.
+    pub(crate) fn add_data_breakpoint(
+        &mut self,
+        variables_reference: Option<u64>,
+        name: String,
+        cx: &mut Context<Self>,
+    ) {
+        let info = self.session.update(cx, |session, cx| {
+            session.data_breakpoint_info(variables_reference, name.clone(), None, cx)
+        });
+
+        cx.spawn(async move |this, cx| {
+            let Some(info) = info.await else {
+                return;
+            };
+            let Some(data_id) = info.data_id else {
+                log::warn!(
+                    "Can't set a data breakpoint on {name}: {}",
+                    info.description
+                );
+                return;
+            };
+            let access_types = info.access_types.unwrap_or_default();
+            let access_type = access_types
+                .iter()
+                .any(|access_type| matches!(access_type, dap::DataBreakpointAccessType::Write))
+                .then_some(DataBreakpointAccessType::Write)
+                .or_else(|| access_types.into_iter().next().map(Into::into))
+                .unwrap_or_default();
+
+            this.update(cx, |this, cx| {
+                if let Some(breakpoint_store) = this.breakpoint_store(cx) {
+                    breakpoint_store.update(cx, |store, cx| {
+                        store.update_data_breakpoint(
+                            DataBreakpoint {
+                                can_persist: info.can_persist.unwrap_or_default(),
+                                ..DataBreakpoint::new(data_id, info.description, access_type)
+                            },
+                            cx,
+                        );
+                    });
+                }
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    fn toggle_function_breakpoint(
+        &mut self,
+        breakpoint: FunctionBreakpoint,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(breakpoint_store) = self.breakpoint_store(cx) {
+            breakpoint_store.update(cx, |store, cx| {
+                store.update_function_breakpoint(
+                    FunctionBreakpoint {
+                        state: toggled_state(breakpoint.state),
+                        ..breakpoint
+                    },
+                    cx,
+                );
+            });
+        }
+    }
+
+    fn toggle_data_breakpoint(&mut self, breakpoint: DataBreakpoint, cx: &mut Context<Self>) {
+        if let Some(breakpoint_store) = self.breakpoint_store(cx) {
+            breakpoint_store.update(cx, |store, cx| {
+                store.update_data_breakpoint(
+                    DataBreakpoint {
+                        state: toggled_state(breakpoint.state),
+                        ..breakpoint
+                    },
+                    cx,
+                );
+            });
+        }
+    }
+
+    fn start_new_function_breakpoint(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Function name", cx);
+            editor
+        });
+        editor.focus_handle(cx).focus(window);
+
+        self.edited_filter.take();
+        self.new_function_breakpoint = Some(editor);
+        cx.notify();
+    }
+
+    fn edit_condition(
+        &mut self,
+        filter: &ExceptionBreakpointsFilter,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let condition = self
+            .breakpoint_store(cx)
+            .and_then(|store| {
+                store
+                    .read(cx)
+                    .exception_breakpoint_for_filter(filter)
+                    .condition
+            })
+            .unwrap_or_default();
+
+        let editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_text_style_refinement(TextStyleRefinement {
+                font_size: Some(
+                    TextSize::XSmall
+                        .rems(cx)
+                        .to_pixels(window.rem_size())
+                        .into(),
+                ),
+                ..Default::default()
+            });
+            if let Some(description) = &filter.condition_description {
+                editor.set_placeholder_text(description.clone(), cx);
+            }
+            editor.set_text(condition.as_ref(), window, cx);
+            editor.select_all(&editor::actions::SelectAll, window, cx);
+            editor
+        });
+        editor.focus_handle(cx).focus(window);
+
+        self.new_function_breakpoint.take();
+        self.edited_filter = Some((filter.filter.clone().into(), editor));
+        cx.notify();
+    }
+
+    fn cancel_condition_edit(
+        &mut self,
+        _: &menu::Cancel,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.edited_filter.take();
+        self.new_function_breakpoint.take();
+        self.focus_handle.focus(window);
+        cx.notify();
+    }
+
+    fn confirm_condition_edit(
+        &mut self,
+        _: &menu::Confirm,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(editor) = self.new_function_breakpoint.take() {
+            let name = editor.read(cx).text(cx);
+            self.add_function_breakpoint(name, cx);
+            self.focus_handle.focus(window);
+            return;
+        }
+
+        let Some((filter_id, editor)) = self.edited_filter.take() else {
+            return;
+        };
+        let Some(filter) = self
+            .exception_filters(cx)
+            .into_iter()
+            .find(|filter| filter.filter.as_str() == filter_id.as_ref())
+        else {
+            return;
+        };
+        let condition = editor.read(cx).text(cx);
+
+        if let Some(breakpoint_store) = self.breakpoint_store(cx) {
+            breakpoint_store.update(cx, |store, cx| {
+                let breakpoint = store.exception_breakpoint_for_filter(&filter);
+                store.update_exception_breakpoint(
+                    ExceptionBreakpoint {
+                        condition: (!condition.is_empty()).then(|| condition.into()),
+                        ..breakpoint
+                    },
+                    cx,
+                );
+            });
+        }
+
+        self.focus_handle.focus(window);
+        cx.notify();
+    }
+
+    fn render_exception_filter(
+        &self,
+        ix: usize,
+        filter: ExceptionBreakpointsFilter,
+        breakpoint: ExceptionBreakpoint,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let supports_condition = self.supports_filter_conditions(&filter, cx);
+        let edited_editor = self
+            .edited_filter
+            .as_ref()
+            .filter(|(id, _)| id.as_ref() == filter.filter.as_str())
+            .map(|(_, editor)| editor.clone());
+
+        v_flex()
+            .id(("exception-breakpoint", ix))
+            .w_full()
+            .p_1()
+            .rounded_md()
+            .hover(|s| s.bg(cx.theme().colors().element_hover))
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        Checkbox::new(
+                            ("exception-breakpoint-toggle", ix),
+                            breakpoint.state.is_enabled().into(),
+                        )
+                        .on_click({
+                            let this = cx.entity();
+                            let filter = filter.clone();
+                            move |_, _, cx| {
+                                this.update(cx, |this, cx| {
+                                    this.toggle_exception_breakpoint(&filter, cx);
+                                })
+                            }
+                        }),
+                    )
+                    .child(
+                        div()
+                            .id(("exception-breakpoint-label", ix))
+                            .text_ui_sm(cx)
+                            .child(filter.label.clone())
+                            .when_some(filter.description.clone(), |this, description| {
+                                this.tooltip(Tooltip::text(description))
+                            }),
+                    ),
+            )
+            .when(supports_condition, |this| {
+                this.child(
+                    div()
+                        .id(("exception-breakpoint-condition", ix))
+                        .pl_5()
+                        .w_full()
+                        .map(|this| {
+                            if let Some(editor) = edited_editor {
+                                this.child(editor)
+                            } else {
+                                let condition = breakpoint
+                                    .condition
+                                    .as_ref()
+                                    .map(|condition| SharedString::from(condition.to_string()));
+                                this.child(
+                                    Label::new(
+                                        condition.unwrap_or_else(|| "Add condition...".into()),
+                                    )
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                                )
+                                .on_click(cx.listener(
+                                    move |this, click: &ClickEvent, window, cx| {
+                                        if click.down.click_count < 2 {
+                                            return;
+                                        }
+                                        this.edit_condition(&filter, window, cx);
+                                    },
+                                ))
+                            }
+                        }),
+                )
+            })
+            .into_any()
+    }
+
+    fn render_function_breakpoint(
+        &self,
+        ix: usize,
+        breakpoint: FunctionBreakpoint,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let name = SharedString::from(breakpoint.name.to_string());
+
+        h_flex()
+            .id(("function-breakpoint", ix))
+            .w_full()
+            .p_1()
+            .gap_1()
+            .rounded_md()
+            .hover(|s| s.bg(cx.theme().colors().element_hover))
+            .child(
+                Checkbox::new(
+                    ("function-breakpoint-toggle", ix),
+                    breakpoint.state.is_enabled().into(),
+                )
+                .on_click({
+                    let this = cx.entity();
+                    let breakpoint = breakpoint.clone();
+                    move |_, _, cx| {
+                        this.update(cx, |this, cx| {
+                            this.toggle_function_breakpoint(breakpoint.clone(), cx);
+                        })
+                    }
+                }),
+            )
+            .child(
+                h_flex()
+                    .flex_1()
+                    .gap_1()
+                    .child(Label::new(name).size(LabelSize::Small))
+                    .when_some(breakpoint.condition.clone(), |this, condition| {
+                        this.child(
+                            Label::new(SharedString::from(condition.to_string()))
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                    }),
+            )
+            .child(
+                IconButton::new(("remove-function-breakpoint", ix), IconName::Close)
+                    .icon_size(IconSize::XSmall)
+                    .tooltip(Tooltip::text("Remove Function Breakpoint"))
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        if let Some(breakpoint_store) = this.breakpoint_store(cx) {
+                            breakpoint_store.update(cx, |store, cx| {
+                                store.remove_function_breakpoint(&breakpoint.name, cx);
+                            });
+                        }
+                    })),
+            )
+            .into_any()
+    }
+
+    fn render_data_breakpoint(
+        &self,
+        ix: usize,
+        breakpoint: DataBreakpoint,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let description = SharedString::from(breakpoint.description.to_string());
+
+        h_flex()
+            .id(("data-breakpoint", ix))
+            .w_full()
+            .p_1()
+            .gap_1()
+            .rounded_md()
+            .hover(|s| s.bg(cx.theme().colors().element_hover))
+            .child(
+                Checkbox::new(
+                    ("data-breakpoint-toggle", ix),
+                    breakpoint.state.is_enabled().into(),
+                )
+                .on_click({
+                    let this = cx.entity();
+                    let breakpoint = breakpoint.clone();
+                    move |_, _, cx| {
+                        this.update(cx, |this, cx| {
+                            this.toggle_data_breakpoint(breakpoint.clone(), cx);
+                        })
+                    }
+                }),
+            )
+            .child(
+                h_flex()
+                    .flex_1()
+                    .gap_1()
+                    .child(Label::new(description).size(LabelSize::Small))
+                    .child(
+                        Label::new(breakpoint.access_type.label())
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    ),
+            )
+            .child(
+                IconButton::new(("remove-data-breakpoint", ix), IconName::Close)
+                    .icon_size(IconSize::XSmall)
+                    .tooltip(Tooltip::text("Remove Data Breakpoint"))
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        if let Some(breakpoint_store) = this.breakpoint_store(cx) {
+                            breakpoint_store.update(cx, |store, cx| {
+                                store.remove_data_breakpoint(&breakpoint.data_id, cx);
+                            });
+                        }
+                    })),
+            )
+            .into_any()
+    }
+}
+
+fn toggled_state(state: BreakpointState) -> BreakpointState {
+    if state.is_enabled() {
+        BreakpointState::Disabled
+    } else {
+        BreakpointState::Enabled
+    }
+}
+
+#[cfg(any(test, feature = "test-support"))]
+impl BreakpointList {
+    pub fn exception_breakpoints(&self, cx: &App) -> Vec<(String, bool)> {
+        let Some(breakpoint_store) = self.breakpoint_store(cx) else {
+            return Vec::new();
+        };
+        let store = breakpoint_store.read(cx);
+
+        self.exception_filters(cx)
+            .iter()
+            .map(|filter| {
+                (
+                    filter.filter.clone(),
+                    store
+                        .exception_breakpoint_for_filter(filter)
+                        .state
+                        .is_enabled(),
+                )
+            })
+            .collect()
+    }
+
+    pub fn function_breakpoints(&self, cx: &App) -> Vec<(String, bool)> {
+        self.breakpoint_store(cx)
+            .map(|store| {
+                store
+                    .read(cx)
+                    .all_function_breakpoints()
+                    .into_iter()
+                    .map(|bp| (bp.name.to_string(), bp.state.is_enabled()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn data_breakpoints(&self, cx: &App) -> Vec<(String, bool)> {
+        self.breakpoint_store(cx)
+            .map(|store| {
+                store
+                    .read(cx)
+                    .all_data_breakpoints()
+                    .into_iter()
+                    .map(|bp| (bp.description.to_string(), bp.state.is_enabled()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl Focusable for BreakpointList {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for BreakpointList {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let (breakpoints, function_breakpoints, data_breakpoints) = self
+            .breakpoint_store(cx)
+            .map(|store| {
+                let store = store.read(cx);
+                let exception_breakpoints = self
+                    .exception_filters(cx)
+                    .into_iter()
+                    .map(|filter| {
+                        let breakpoint = store.exception_breakpoint_for_filter(&filter);
+                        (filter, breakpoint)
+                    })
+                    .collect::<Vec<_>>();
+                (
+                    exception_breakpoints,
+                    store.all_function_breakpoints(),
+                    store.all_data_breakpoints(),
+                )
+            })
+            .unwrap_or_default();
+        let capabilities = self.session.read(cx).capabilities();
+        let supports_function_breakpoints = capabilities
+            .supports_function_breakpoints
+            .unwrap_or_default();
+        let supports_data_breakpoints = capabilities.supports_data_breakpoints.unwrap_or_default();
+
+        v_flex()
+            .key_context("BreakpointList")
+            .id("breakpoint-list")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::cancel_condition_edit))
+            .on_action(cx.listener(Self::confirm_condition_edit))
+            .size_full()
+            .p_1()
+            .overflow_y_scroll()
+            .when(!breakpoints.is_empty(), |this| {
+                this.child(
+                    Label::new("Exception Breakpoints")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+                .children(
+                    breakpoints
+                        .into_iter()
+                        .enumerate()
+                        .map(|(ix, (filter, breakpoint))| {
+                            self.render_exception_filter(ix, filter, breakpoint, cx)
+                        }),
+                )
+            })
+            .when(supports_function_breakpoints, |this| {
+                this.child(
+                    h_flex()
+                        .justify_between()
+                        .child(
+                            Label::new("Function Breakpoints")
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                        .child(
+                            IconButton::new("add-function-breakpoint", IconName::Plus)
+                                .icon_size(IconSize::XSmall)
+                                .tooltip(Tooltip::text("Add Function Breakpoint"))
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.start_new_function_breakpoint(window, cx);
+                                })),
+                        ),
+                )
+                .when_some(self.new_function_breakpoint.clone(), |this, editor| {
+                    this.child(div().pl_5().w_full().child(editor))
+                })
+                .children(
+                    function_breakpoints
+                        .into_iter()
+                        .enumerate()
+                        .map(|(ix, breakpoint)| {
+                            self.render_function_breakpoint(ix, breakpoint, cx)
+                        }),
+                )
+            })
+            .when(
+                supports_data_breakpoints || !data_breakpoints.is_empty(),
+                |this| {
+                    this.child(
+                        Label::new("Data Breakpoints")
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .children(
+                        data_breakpoints
+                            .into_iter()
+                            .enumerate()
+                            .map(|(ix, breakpoint)| {
+                                self.render_data_breakpoint(ix, breakpoint, cx)
+                            }),
+                    )
+                },
+            )
+    }
+}
@@ -0,0 +1,282 @@
+use super::{
+    RunningState,
+    stack_frame_list::{StackFrameList, StackFrameListEvent},
+};
+use gpui::{
+    Entity, FocusHandle, Focusable, ScrollStrategy, Subscription, Task, UniformListScrollHandle,
+    WeakEntity, uniform_list,
+};
+use project::debugger::session::{Session, SessionEvent, ThreadStatus};
+use std::ops::Range;
+use ui::{Tooltip, prelude::*};
+use util::ResultExt;
+
+/// Number of instructions fetched before the instruction pointer, so the
+/// instructions leading up to it are visible as well.
+const INSTRUCTIONS_BEFORE_POINTER: i64 = 32;
+const INSTRUCTION_COUNT: u64 = 128;
+
+/// Shows the machine instructions around the selected stack frame's `instructionPointerReference`.
+pub struct DisassemblyView {
+    session: Entity<Session>,
+    state: WeakEntity<RunningState>,
+    stack_frame_list: Entity<StackFrameList>,
+    focus_handle: FocusHandle,
+    list_handle: UniformListScrollHandle,
+    instruction_pointer: Option<String>,
+    instructions: Vec<dap::DisassembledInstruction>,
+    _fetch_task: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl DisassemblyView {
+    pub fn new(
+        session: Entity<Session>,
+        state: WeakEntity<RunningState>,
+        stack_frame_list: Entity<StackFrameList>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let _subscriptions = vec![
+            cx.subscribe(&stack_frame_list, |this, _, event, cx| match event {
+                StackFrameListEvent::SelectedStackFrameChanged(_) => this.refresh(cx),
+            }),
+            cx.subscribe(&session, |this, _, event, cx| match event {
+                SessionEvent::Memory => {
+                    this.instruction_pointer.take();
+                    this.refresh(cx);
+                }
+                _ => {}
+            }),
+        ];
+
+        let mut this = Self {
+            session,
+            state,
+            stack_frame_list,
+            focus_handle: cx.focus_handle(),
+            list_handle: UniformListScrollHandle::default(),
+            instruction_pointer: None,
+            instructions: Vec::new(),
+            _fetch_task: Task::ready(()),
+            _subscriptions,
+        };
+        this.refresh(cx);
+        this
+    }
+
+    fn refresh(&mut self, cx: &mut Context<Self>) {
+        let instruction_pointer = self
+            .stack_frame_list
+            .read(cx)
+            .current_stack_frame()
+            .and_then(|stack_frame| stack_frame.instruction_pointer_reference.clone());
+
+        if instruction_pointer == self.instruction_pointer {
+            return;
+        }
+        self.instruction_pointer = instruction_pointer.clone();
+
+        let Some(instruction_pointer) = instruction_pointer else {
+            self.instructions.clear();
+            cx.notify();
+            return;
+        };
+
+        let instructions = self.session.update(cx, |session, cx| {
+            session.disassemble(
+                instruction_pointer.clone(),
+                -INSTRUCTIONS_BEFORE_POINTER,
+                INSTRUCTION_COUNT,
+                cx,
+            )
+        });
+
+        self._fetch_task = cx.spawn(async move |this, cx| {
+            let instructions = instructions.await.unwrap_or_default();
+
+            this.update(cx, |this, cx| {
+                if this.instruction_pointer.as_ref() != Some(&instruction_pointer) {
+                    return;
+                }
+
+                this.instructions = instructions;
+                if let Some(ix) = this.current_instruction_ix() {
+                    this.list_handle.scroll_to_item(ix, ScrollStrategy::Center);
+                }
+                cx.notify();
+            })
+            .log_err();
+        });
+    }
+
+    fn current_instruction_ix(&self) -> Option<usize> {
+        let instruction_pointer = self.instruction_pointer.as_ref()?;
+        let instruction_pointer = parse_address(instruction_pointer);
+
+        self.instructions
+            .iter()
+            .position(|instruction| parse_address(&instruction.address) == instruction_pointer)
+    }
+
+    fn step_over_instruction(&mut self, cx: &mut Context<Self>) {
+        self.state
+            .update(cx, |state, cx| state.step_over_instruction(cx))
+            .log_err();
+    }
+
+    fn step_in_instruction(&mut self, cx: &mut Context<Self>) {
+        self.state
+            .update(cx, |state, cx| state.step_in_instruction(cx))
+            .log_err();
+    }
+
+    fn render_instructions(
+        &mut self,
+        range: Range<usize>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Vec<AnyElement> {
+        let current_ix = self.current_instruction_ix();
+
+        range
+            .filter_map(|ix| {
+                let instruction = self.instructions.get(ix)?;
+                let is_current = current_ix == Some(ix);
+
+                Some(
+                    h_flex()
+                        .id(("disassembly-instruction", ix))
+                        .w_full()
+                        .px_1()
+                        .gap_2()
+                        .when(is_current, |this| {
+                            this.bg(cx.theme().colors().editor_highlighted_line_background)
+                        })
+                        .child(
+                            Label::new(SharedString::from(instruction.address.clone()))
+                                .buffer_font(cx)
+                                .size(LabelSize::Small)
+                                .color(if is_current {
+                                    Color::Accent
+                                } else {
+                                    Color::Muted
+                                }),
+                        )
+                        .when_some(instruction.instruction_bytes.clone(), |this, bytes| {
+                            this.child(
+                                Label::new(bytes)
+                                    .buffer_font(cx)
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            )
+                        })
+                        .child(
+                            Label::new(SharedString::from(instruction.instruction.clone()))
+                                .buffer_font(cx)
+                                .size(LabelSize::Small),
+                        )
+                        .when_some(instruction.symbol.clone(), |this, symbol| {
+                            this.child(
+                                Label::new(symbol)
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted)
+                                    .single_line()
+                                    .truncate(),
+                            )
+                        })
+                        .into_any_element(),
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(any(test, feature = "test-support"))]
+impl DisassemblyView {
+    pub fn instructions(&self) -> Vec<String> {
+        self.instructions
+            .iter()
+            .map(|instruction| instruction.instruction.clone())
+            .collect()
+    }
+
+    pub fn current_instruction(&self) -> Option<String> {
+        self.current_instruction_ix()
+            .map(|ix| self.instructions[ix].instruction.clone())
+    }
+}
+
+/// Adapters don't agree on the formatting of addresses (e.g. `0x0000ff` vs `0xff`),
+/// so compare them numerically when possible.
+fn parse_address(address: &str) -> Result<u64, &str> {
+    let trimmed = address.trim();
+    trimmed
+        .strip_prefix("0x")
+        .or_else(|| trimmed.strip_prefix("0X"))
+        .and_then(|hex| u64::from_str_radix(hex, 16).ok())
+        .ok_or(trimmed)
+}
+
+impl Focusable for DisassemblyView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for DisassemblyView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let is_stopped = self
+            .state
+            .read_with(cx, |state, cx| state.thread_status(cx))
+            .ok()
+            .flatten()
+            .is_some_and(|status| status == ThreadStatus::Stopped);
+
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .p_1()
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        IconButton::new("disassembly-step-over", IconName::DebugStepOver)
+                            .icon_size(IconSize::XSmall)
+                            .disabled(!is_stopped)
+                            .tooltip(Tooltip::text("Step Over Instruction"))
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.step_over_instruction(cx);
+                            })),
+                    )
+                    .child(
+                        IconButton::new("disassembly-step-in", IconName::DebugStepInto)
+                            .icon_size(IconSize::XSmall)
+                            .disabled(!is_stopped)
+                            .tooltip(Tooltip::text("Step Into Instruction"))
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.step_in_instruction(cx);
+                            })),
+                    ),
+            )
+            .map(|this| {
+                if self.instructions.is_empty() {
+                    this.child(
+                        Label::new("No disassembly available for the selected stack frame")
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                } else {
+                    this.child(
+                        uniform_list(
+                            cx.entity(),
+                            "disassembly",
+                            self.instructions.len(),
+                            |this, range, window, cx| this.render_instructions(range, window, cx),
+                        )
+                        .track_scroll(self.list_handle.clone())
+                        .size_full(),
+                    )
+                }
+            })
+    }
+}
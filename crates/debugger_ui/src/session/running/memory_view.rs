@@ -0,0 +1,341 @@
+use editor::Editor;
+use gpui::{
+    ClickEvent, Entity, EventEmitter, FocusHandle, Focusable, Subscription, Task,
+    TextStyleRefinement,
+};
+use project::debugger::session::{Memory, Session, SessionEvent};
+use ui::{Tooltip, prelude::*};
+use util::ResultExt;
+
+const BYTES_PER_ROW: usize = 16;
+const PAGE_SIZE: u64 = 256;
+
+pub enum MemoryViewEvent {
+    Opened,
+}
+
+/// Hex view of the debuggee's memory around a variable's `memoryReference`.
+pub struct MemoryView {
+    session: Entity<Session>,
+    focus_handle: FocusHandle,
+    /// Memory reference and the name of the variable it was opened from.
+    target: Option<(String, SharedString)>,
+    offset: i64,
+    memory: Option<Memory>,
+    edited_byte: Option<(usize, Entity<Editor>)>,
+    _fetch_task: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl EventEmitter<MemoryViewEvent> for MemoryView {}
+
+impl MemoryView {
+    pub fn new(session: Entity<Session>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let focus_handle = cx.focus_handle();
+
+        let _subscriptions = vec![
+            cx.subscribe(&session, |this, _, event, cx| match event {
+                SessionEvent::Stopped(_) | SessionEvent::Memory => this.fetch(cx),
+                _ => {}
+            }),
+            cx.on_focus_out(&focus_handle, window, |this, _, _, cx| {
+                this.edited_byte.take();
+                cx.notify();
+            }),
+        ];
+
+        Self {
+            session,
+            focus_handle,
+            target: None,
+            offset: 0,
+            memory: None,
+            edited_byte: None,
+            _fetch_task: Task::ready(()),
+            _subscriptions,
+        }
+    }
+
+    pub(crate) fn open(
+        &mut self,
+        memory_reference: String,
+        name: impl Into<SharedString>,
+        cx: &mut Context<Self>,
+    ) {
+        self.target = Some((memory_reference, name.into()));
+        self.offset = 0;
+        self.memory = None;
+        self.edited_byte = None;
+        self.fetch(cx);
+        cx.emit(MemoryViewEvent::Opened);
+    }
+
+    fn fetch(&mut self, cx: &mut Context<Self>) {
+        let Some((memory_reference, _)) = self.target.clone() else {
+            return;
+        };
+        let offset = self.offset;
+
+        let memory = self.session.update(cx, |session, cx| {
+            session.read_memory(memory_reference.clone(), offset, PAGE_SIZE, cx)
+        });
+
+        self._fetch_task = cx.spawn(async move |this, cx| {
+            let memory = memory.await;
+
+            this.update(cx, |this, cx| {
+                let is_current = this
+                    .target
+                    .as_ref()
+                    .is_some_and(|(reference, _)| *reference == memory_reference)
+                    && this.offset == offset;
+                if is_current {
+                    this.memory = memory;
+                    cx.notify();
+                }
+            })
+            .log_err();
+        });
+    }
+
+    fn go_to_page(&mut self, delta: i64, cx: &mut Context<Self>) {
+        self.offset += delta * PAGE_SIZE as i64;
+        self.edited_byte.take();
+        self.fetch(cx);
+        cx.notify();
+    }
+
+    fn edit_byte(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(byte) = self
+            .memory
+            .as_ref()
+            .and_then(|memory| memory.data.get(ix).copied())
+        else {
+            return;
+        };
+
+        let editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_text_style_refinement(TextStyleRefinement {
+                font_size: Some(
+                    TextSize::XSmall
+                        .rems(cx)
+                        .to_pixels(window.rem_size())
+                        .into(),
+                ),
+                ..Default::default()
+            });
+            editor.set_text(format!("{byte:02x}"), window, cx);
+            editor.select_all(&editor::actions::SelectAll, window, cx);
+            editor
+        });
+        editor.focus_handle(cx).focus(window);
+
+        self.edited_byte = Some((ix, editor));
+        cx.notify();
+    }
+
+    fn cancel_byte_edit(&mut self, _: &menu::Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        self.edited_byte.take();
+        self.focus_handle.focus(window);
+        cx.notify();
+    }
+
+    fn confirm_byte_edit(
+        &mut self,
+        _: &menu::Confirm,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some((ix, editor)) = self.edited_byte.take() else {
+            return;
+        };
+        self.focus_handle.focus(window);
+
+        let text = editor.read(cx).text(cx);
+        let Some(byte) = u8::from_str_radix(text.trim().trim_start_matches("0x"), 16).log_err()
+        else {
+            cx.notify();
+            return;
+        };
+        self.write_byte(ix, byte, cx);
+    }
+
+    pub(crate) fn write_byte(&mut self, ix: usize, byte: u8, cx: &mut Context<Self>) {
+        let Some((memory_reference, _)) = self.target.clone() else {
+            return;
+        };
+        let offset = self.offset + ix as i64;
+
+        self.session
+            .update(cx, |session, cx| {
+                session.write_memory(memory_reference, offset, &[byte], cx)
+            })
+            .detach();
+        cx.notify();
+    }
+
+    fn render_row(&self, row: usize, memory: &Memory, cx: &mut Context<Self>) -> AnyElement {
+        let start = row * BYTES_PER_ROW;
+        let end = (start + BYTES_PER_ROW).min(memory.data.len());
+        let bytes = &memory.data[start..end];
+        let supports_write = self
+            .session
+            .read(cx)
+            .capabilities()
+            .supports_write_memory_request
+            .unwrap_or_default();
+
+        let address = parse_address(&memory.address)
+            .map(|address| format!("{:#018x}", address + start as u64))
+            .unwrap_or_else(|| format!("{}+{start:#x}", memory.address));
+        let ascii = bytes
+            .iter()
+            .map(|byte| {
+                if byte.is_ascii_graphic() || *byte == b' ' {
+                    *byte as char
+                } else {
+                    '.'
+                }
+            })
+            .collect::<String>();
+
+        h_flex()
+            .id(("memory-row", row))
+            .gap_2()
+            .px_1()
+            .hover(|s| s.bg(cx.theme().colors().element_hover))
+            .child(
+                Label::new(address)
+                    .buffer_font(cx)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(
+                h_flex()
+                    .gap_1()
+                    .children(bytes.iter().enumerate().map(|(column, byte)| {
+                        let ix = start + column;
+                        let edited_editor = self
+                            .edited_byte
+                            .as_ref()
+                            .filter(|(edited_ix, _)| *edited_ix == ix)
+                            .map(|(_, editor)| editor.clone());
+
+                        div()
+                            .id(("memory-byte", ix))
+                            .map(|this| {
+                                if let Some(editor) = edited_editor {
+                                    this.w_6().child(editor)
+                                } else {
+                                    this.child(
+                                        Label::new(format!("{byte:02x}"))
+                                            .buffer_font(cx)
+                                            .size(LabelSize::Small),
+                                    )
+                                }
+                            })
+                            .when(supports_write, |this| {
+                                this.on_click(cx.listener(
+                                    move |this, click: &ClickEvent, window, cx| {
+                                        if click.down.click_count < 2 {
+                                            return;
+                                        }
+                                        this.edit_byte(ix, window, cx);
+                                    },
+                                ))
+                            })
+                    })),
+            )
+            .child(
+                Label::new(ascii)
+                    .buffer_font(cx)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .into_any()
+    }
+}
+
+#[cfg(any(test, feature = "test-support"))]
+impl MemoryView {
+    pub fn memory(&self) -> Option<&Memory> {
+        self.memory.as_ref()
+    }
+}
+
+fn parse_address(address: &str) -> Option<u64> {
+    let address = address.trim();
+    let hex = address
+        .strip_prefix("0x")
+        .or_else(|| address.strip_prefix("0X"))?;
+    u64::from_str_radix(hex, 16).ok()
+}
+
+impl Focusable for MemoryView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for MemoryView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let title = self.target.as_ref().map(|(_, name)| name.clone());
+        let rows = self
+            .memory
+            .as_ref()
+            .map(|memory| memory.data.len().div_ceil(BYTES_PER_ROW))
+            .unwrap_or_default();
+
+        v_flex()
+            .key_context("MemoryView")
+            .id("memory-view")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::cancel_byte_edit))
+            .on_action(cx.listener(Self::confirm_byte_edit))
+            .size_full()
+            .p_1()
+            .overflow_y_scroll()
+            .child(
+                h_flex()
+                    .gap_1()
+                    .justify_between()
+                    .child(
+                        Label::new(title.unwrap_or_else(|| "No memory reference selected".into()))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .when(self.target.is_some(), |this| {
+                        this.child(
+                            h_flex()
+                                .child(
+                                    IconButton::new("memory-previous-page", IconName::ChevronLeft)
+                                        .icon_size(IconSize::XSmall)
+                                        .tooltip(Tooltip::text("Previous Page"))
+                                        .on_click(cx.listener(|this, _, _, cx| {
+                                            this.go_to_page(-1, cx);
+                                        })),
+                                )
+                                .child(
+                                    IconButton::new("memory-next-page", IconName::ChevronRight)
+                                        .icon_size(IconSize::XSmall)
+                                        .tooltip(Tooltip::text("Next Page"))
+                                        .on_click(cx.listener(|this, _, _, cx| {
+                                            this.go_to_page(1, cx);
+                                        })),
+                                ),
+                        )
+                    }),
+            )
+            .when_some(self.memory.clone(), |this, memory| {
+                this.children((0..rows).map(|row| self.render_row(row, &memory, cx)))
+                    .when(memory.unreadable_bytes > 0, |this| {
+                        this.child(
+                            Label::new(format!("{} unreadable bytes", memory.unreadable_bytes))
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                    })
+            })
+    }
+}
@@ -1,4 +1,8 @@
-use super::stack_frame_list::{StackFrameList, StackFrameListEvent};
+use super::{
+    breakpoint_list::BreakpointList,
+    memory_view::MemoryView,
+    stack_frame_list::{StackFrameList, StackFrameListEvent},
+};
 use dap::{ScopePresentationHint, StackFrameId, VariablePresentationHintKind, VariableReference};
 use editor::Editor;
 use gpui::{
@@ -135,6 +139,8 @@ pub struct VariableList {
     list_handle: UniformListScrollHandle,
     scrollbar_state: ScrollbarState,
     session: Entity<Session>,
+    breakpoint_list: Entity<BreakpointList>,
+    memory_view: Entity<MemoryView>,
     selection: Option<EntryPath>,
     open_context_menu: Option<(Entity<ContextMenu>, Point<Pixels>, Subscription)>,
     focus_handle: FocusHandle,
@@ -147,6 +153,8 @@ impl VariableList {
     pub fn new(
         session: Entity<Session>,
         stack_frame_list: Entity<StackFrameList>,
+        breakpoint_list: Entity<BreakpointList>,
+        memory_view: Entity<MemoryView>,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Self {
@@ -174,6 +182,8 @@ impl VariableList {
             scrollbar_state: ScrollbarState::new(list_state.clone()),
             list_handle: list_state,
             session,
+            breakpoint_list,
+            memory_view,
             focus_handle,
             _subscriptions,
             selected_stack_frame_id: None,
@@ -499,6 +509,26 @@ impl VariableList {
 
         let variable_value = dap_var.value.clone();
         let variable_name = dap_var.name.clone();
+        let function_name = dap_var
+            .evaluate_name
+            .clone()
+            .unwrap_or_else(|| dap_var.name.clone());
+        let parent_reference = self
+            .entry_states
+            .get(&variable.path)
+            .map(|state| state.parent_reference);
+        let capabilities = self.session.read(cx).capabilities();
+        let supports_function_breakpoints = capabilities
+            .supports_function_breakpoints
+            .unwrap_or_default();
+        let supports_data_breakpoints = capabilities.supports_data_breakpoints.unwrap_or_default();
+        let memory_reference = dap_var.memory_reference.clone().filter(|_| {
+            capabilities
+                .supports_read_memory_request
+                .unwrap_or_default()
+        });
+        let breakpoint_list = self.breakpoint_list.clone();
+        let memory_view = self.memory_view.clone();
         let this = cx.entity().clone();
 
         let context_menu = ContextMenu::build(window, cx, |menu, _, _| {
@@ -511,13 +541,48 @@ impl VariableList {
                     cx.write_to_clipboard(ClipboardItem::new_string(variable_value.clone()))
                 }
             })
-            .entry("Set value", None, move |window, cx| {
-                this.update(cx, |variable_list, cx| {
-                    let editor = Self::create_variable_editor(&variable_value, window, cx);
-                    variable_list.edited_path = Some((variable.path.clone(), editor));
-
-                    cx.notify();
-                });
+            .entry("Set value", None, {
+                let variable_path = variable.path.clone();
+                move |window, cx| {
+                    this.update(cx, |variable_list, cx| {
+                        let editor = Self::create_variable_editor(&variable_value, window, cx);
+                        variable_list.edited_path = Some((variable_path.clone(), editor));
+
+                        cx.notify();
+                    });
+                }
+            })
+            .when_some(memory_reference, |menu, memory_reference| {
+                let variable_name = dap_var.name.clone();
+                menu.entry("View Memory", None, move |_, cx| {
+                    memory_view.update(cx, |memory_view, cx| {
+                        memory_view.open(memory_reference.clone(), variable_name.clone(), cx);
+                    });
+                })
+            })
+            .when(
+                supports_function_breakpoints || supports_data_breakpoints,
+                |menu| menu.separator(),
+            )
+            .when(supports_data_breakpoints, |menu| {
+                let breakpoint_list = breakpoint_list.clone();
+                let variable_name = dap_var.name.clone();
+                menu.entry("Break on Value Change", None, move |_, cx| {
+                    breakpoint_list.update(cx, |breakpoint_list, cx| {
+                        breakpoint_list.add_data_breakpoint(
+                            parent_reference,
+                            variable_name.clone(),
+                            cx,
+                        );
+                    });
+                })
+            })
+            .when(supports_function_breakpoints, |menu| {
+                menu.entry("Add Function Breakpoint", None, move |_, cx| {
+                    breakpoint_list.update(cx, |breakpoint_list, cx| {
+                        breakpoint_list.add_function_breakpoint(function_name.clone(), cx);
+                    });
+                })
             })
         });
 
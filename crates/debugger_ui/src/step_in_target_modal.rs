@@ -0,0 +1,224 @@
+use dap::SteppingGranularity;
+use fuzzy::{StringMatch, StringMatchCandidate};
+use gpui::Subscription;
+use gpui::{DismissEvent, Entity, EventEmitter, Focusable, Render};
+use picker::{Picker, PickerDelegate};
+use project::debugger::session::{Session, ThreadId};
+
+use std::sync::Arc;
+use ui::{Context, prelude::*};
+use ui::{ListItem, ListItemSpacing};
+use workspace::ModalView;
+
+pub(crate) struct StepInTargetModalDelegate {
+    selected_index: usize,
+    matches: Vec<StringMatch>,
+    session: Entity<Session>,
+    thread_id: ThreadId,
+    granularity: SteppingGranularity,
+    targets: Arc<[dap::StepInTarget]>,
+}
+
+impl StepInTargetModalDelegate {
+    fn new(
+        session: Entity<Session>,
+        thread_id: ThreadId,
+        granularity: SteppingGranularity,
+        targets: Arc<[dap::StepInTarget]>,
+    ) -> Self {
+        Self {
+            session,
+            thread_id,
+            granularity,
+            targets,
+            selected_index: 0,
+            matches: Vec::default(),
+        }
+    }
+}
+
+/// Lets the user pick which of the calls on the current line to step into.
+pub struct StepInTargetModal {
+    _subscription: Subscription,
+    pub(crate) picker: Entity<Picker<StepInTargetModalDelegate>>,
+}
+
+impl StepInTargetModal {
+    pub fn new(
+        session: Entity<Session>,
+        thread_id: ThreadId,
+        granularity: SteppingGranularity,
+        targets: Vec<dap::StepInTarget>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let picker = cx.new(|cx| {
+            Picker::uniform_list(
+                StepInTargetModalDelegate::new(session, thread_id, granularity, targets.into()),
+                window,
+                cx,
+            )
+        });
+        Self {
+            _subscription: cx.subscribe(&picker, |_, _, _, cx| {
+                cx.emit(DismissEvent);
+            }),
+            picker,
+        }
+    }
+}
+
+impl Render for StepInTargetModal {
+    fn render(&mut self, _window: &mut Window, _: &mut Context<Self>) -> impl ui::IntoElement {
+        v_flex()
+            .key_context("StepInTargetModal")
+            .w(rems(34.))
+            .child(self.picker.clone())
+    }
+}
+
+impl EventEmitter<DismissEvent> for StepInTargetModal {}
+
+impl Focusable for StepInTargetModal {
+    fn focus_handle(&self, cx: &App) -> gpui::FocusHandle {
+        self.picker.read(cx).focus_handle(cx)
+    }
+}
+
+impl ModalView for StepInTargetModal {}
+
+impl PickerDelegate for StepInTargetModalDelegate {
+    type ListItem = ListItem;
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Select the call to step into".into()
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> gpui::Task<()> {
+        let candidates = self
+            .targets
+            .iter()
+            .enumerate()
+            .map(|(id, target)| StringMatchCandidate::new(id, &target.label))
+            .collect::<Vec<_>>();
+
+        cx.spawn(async move |this, cx| {
+            let matches = fuzzy::match_strings(
+                &candidates,
+                &query,
+                true,
+                100,
+                &Default::default(),
+                cx.background_executor().clone(),
+            )
+            .await;
+
+            this.update(cx, |this, _| {
+                let delegate = &mut this.delegate;
+
+                delegate.matches = matches;
+
+                if delegate.matches.is_empty() {
+                    delegate.selected_index = 0;
+                } else {
+                    delegate.selected_index =
+                        delegate.selected_index.min(delegate.matches.len() - 1);
+                }
+            })
+            .ok();
+        })
+    }
+
+    fn confirm(&mut self, _: bool, _window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let target = self
+            .matches
+            .get(self.selected_index())
+            .and_then(|current_match| self.targets.get(current_match.candidate_id));
+
+        if let Some(target) = target {
+            let (thread_id, target_id, granularity) = (self.thread_id, target.id, self.granularity);
+            self.session.update(cx, |session, cx| {
+                session.step_in_target(thread_id, Some(target_id), granularity, cx);
+            });
+        }
+
+        cx.emit(DismissEvent);
+    }
+
+    fn dismissed(&mut self, _window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.selected_index = 0;
+
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let hit = &self.matches[ix];
+        let target = self.targets.get(hit.candidate_id)?;
+
+        Some(
+            ListItem::new(SharedString::from(format!("step-in-target-{ix}")))
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(
+                    h_flex()
+                        .gap_2()
+                        .child(Label::new(target.label.clone()))
+                        .when_some(target.line, |this, line| {
+                            let position = match target.column {
+                                Some(column) => format!("{line}:{column}"),
+                                None => line.to_string(),
+                            };
+                            this.child(
+                                Label::new(position)
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            )
+                        }),
+                ),
+        )
+    }
+}
+
+#[cfg(any(test, feature = "test-support"))]
+pub(crate) fn target_labels(
+    modal: &StepInTargetModal,
+    cx: &mut Context<StepInTargetModal>,
+) -> Vec<String> {
+    modal.picker.update(cx, |picker, _| {
+        picker
+            .delegate
+            .matches
+            .iter()
+            .map(|hit| hit.string.clone())
+            .collect::<Vec<_>>()
+    })
+}
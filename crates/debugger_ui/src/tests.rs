@@ -7,10 +7,14 @@ use workspace::Workspace;
 use crate::{debugger_panel::DebugPanel, session::DebugSession};
 
 mod attach_modal;
+mod breakpoint_list;
 mod console;
 mod debugger_panel;
+mod disassembly_view;
+mod memory_view;
 mod module_list;
 mod stack_frame_list;
+mod step_in_targets;
 mod variable_list;
 
 pub fn init_test(cx: &mut gpui::TestAppContext) {
@@ -0,0 +1,326 @@
+use crate::{
+    debugger_panel::DebugPanel,
+    session::ThreadItem,
+    tests::{active_debug_session_panel, init_test, init_test_workspace},
+};
+use dap::{
+    ExceptionBreakpointsFilter,
+    requests::{
+        DataBreakpointInfo, SetDataBreakpoints, SetExceptionBreakpoints, SetFunctionBreakpoints,
+    },
+};
+use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
+use project::{
+    FakeFs, Project,
+    debugger::breakpoint_store::{BreakpointState, ExceptionBreakpoint},
+};
+use std::sync::{Arc, Mutex};
+use task::LaunchConfig;
+
+#[gpui::test]
+async fn test_exception_breakpoints(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    let project = Project::test(fs, ["/project".as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let raised = ExceptionBreakpointsFilter {
+        filter: "raised".into(),
+        label: "Raised Exceptions".into(),
+        description: None,
+        default: Some(false),
+        supports_condition: Some(true),
+        condition_description: None,
+    };
+    let uncaught = ExceptionBreakpointsFilter {
+        filter: "uncaught".into(),
+        label: "Uncaught Exceptions".into(),
+        description: None,
+        default: Some(true),
+        supports_condition: Some(false),
+        condition_description: None,
+    };
+
+    let task = project.update(cx, |project, cx| {
+        project.fake_debug_session(
+            dap::DebugRequestType::Launch(LaunchConfig::default()),
+            Some(dap::Capabilities {
+                exception_breakpoint_filters: Some(vec![raised.clone(), uncaught.clone()]),
+                supports_exception_filter_options: Some(true),
+                ..Default::default()
+            }),
+            false,
+            cx,
+        )
+    });
+
+    let session = task.await.unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    let last_request = Arc::new(Mutex::new(None));
+    client
+        .on_request::<SetExceptionBreakpoints, _>({
+            let last_request = last_request.clone();
+            move |_, args| {
+                *last_request.lock().unwrap() = Some(args);
+                Ok(dap::SetExceptionBreakpointsResponse { breakpoints: None })
+            }
+        })
+        .await;
+
+    cx.run_until_parked();
+
+    let running_state =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            item.mode()
+                .as_running()
+                .expect("Session should be running by this point")
+                .clone()
+        });
+
+    running_state.update(cx, |state, cx| {
+        state.set_thread_item(ThreadItem::Breakpoints, cx);
+        cx.refresh_windows();
+    });
+
+    cx.run_until_parked();
+
+    running_state.update(cx, |state, cx| {
+        let breakpoints = state.breakpoint_list().read(cx).exception_breakpoints(cx);
+        assert_eq!(
+            vec![
+                ("raised".to_string(), false),
+                ("uncaught".to_string(), true)
+            ],
+            breakpoints,
+            "Untouched filters should use the adapter's defaults"
+        );
+
+        state.breakpoint_list().update(cx, |list, cx| {
+            list.toggle_exception_breakpoint(&raised, cx);
+        });
+    });
+
+    cx.run_until_parked();
+
+    let args = last_request.lock().unwrap().take().unwrap();
+    assert_eq!(
+        vec!["raised".to_string(), "uncaught".to_string()],
+        args.filters
+    );
+    assert_eq!(None, args.filter_options);
+
+    project.update(cx, |project, cx| {
+        project.breakpoint_store().update(cx, |store, cx| {
+            store.update_exception_breakpoint(
+                ExceptionBreakpoint {
+                    id: "raised".into(),
+                    condition: Some("isinstance(e, KeyError)".into()),
+                    state: BreakpointState::Enabled,
+                },
+                cx,
+            );
+        })
+    });
+
+    cx.run_until_parked();
+
+    let args = last_request.lock().unwrap().take().unwrap();
+    assert_eq!(vec!["uncaught".to_string()], args.filters);
+    let filter_options = args.filter_options.unwrap();
+    assert_eq!(1, filter_options.len());
+    assert_eq!("raised", filter_options[0].filter_id);
+    assert_eq!(
+        Some("isinstance(e, KeyError)".to_string()),
+        filter_options[0].condition
+    );
+
+    session.update(cx, |session, cx| {
+        session.toggle_ignore_breakpoints(cx).detach();
+    });
+
+    cx.run_until_parked();
+
+    let args = last_request.lock().unwrap().take().unwrap();
+    assert!(
+        args.filters.is_empty() && args.filter_options.is_none(),
+        "Ignoring breakpoints should clear exception breakpoints as well"
+    );
+
+    let shutdown_session = project.update(cx, |project, cx| {
+        project.dap_store().update(cx, |dap_store, cx| {
+            dap_store.shutdown_session(session.read(cx).session_id(), cx)
+        })
+    });
+
+    shutdown_session.await.unwrap();
+}
+
+#[gpui::test]
+async fn test_function_and_data_breakpoints(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    let project = Project::test(fs, ["/project".as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let task = project.update(cx, |project, cx| {
+        project.fake_debug_session(
+            dap::DebugRequestType::Launch(LaunchConfig::default()),
+            Some(dap::Capabilities {
+                supports_function_breakpoints: Some(true),
+                supports_data_breakpoints: Some(true),
+                ..Default::default()
+            }),
+            false,
+            cx,
+        )
+    });
+
+    let session = task.await.unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    let function_breakpoints = Arc::new(Mutex::new(None));
+    client
+        .on_request::<SetFunctionBreakpoints, _>({
+            let function_breakpoints = function_breakpoints.clone();
+            move |_, args| {
+                *function_breakpoints.lock().unwrap() = Some(args.breakpoints);
+                Ok(dap::SetFunctionBreakpointsResponse {
+                    breakpoints: Vec::new(),
+                })
+            }
+        })
+        .await;
+
+    client
+        .on_request::<DataBreakpointInfo, _>(move |_, args| {
+            assert_eq!(Some(2), args.variables_reference);
+            assert_eq!("retries", args.name);
+
+            Ok(dap::DataBreakpointInfoResponse {
+                data_id: Some("0x7ffe1234".into()),
+                description: "config.retries".into(),
+                access_types: Some(vec![
+                    dap::DataBreakpointAccessType::Read,
+                    dap::DataBreakpointAccessType::Write,
+                ]),
+                can_persist: None,
+            })
+        })
+        .await;
+
+    let data_breakpoints = Arc::new(Mutex::new(None));
+    client
+        .on_request::<SetDataBreakpoints, _>({
+            let data_breakpoints = data_breakpoints.clone();
+            move |_, args| {
+                *data_breakpoints.lock().unwrap() = Some(args.breakpoints);
+                Ok(dap::SetDataBreakpointsResponse {
+                    breakpoints: Vec::new(),
+                })
+            }
+        })
+        .await;
+
+    cx.run_until_parked();
+
+    let running_state =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            item.mode()
+                .as_running()
+                .expect("Session should be running by this point")
+                .clone()
+        });
+
+    running_state.update(cx, |state, cx| {
+        state.breakpoint_list().update(cx, |list, cx| {
+            list.add_function_breakpoint("main::run".into(), cx);
+            list.add_data_breakpoint(Some(2), "retries".into(), cx);
+        });
+    });
+
+    cx.run_until_parked();
+
+    let breakpoints = function_breakpoints.lock().unwrap().take().unwrap();
+    assert_eq!(1, breakpoints.len());
+    assert_eq!("main::run", breakpoints[0].name);
+
+    let breakpoints = data_breakpoints.lock().unwrap().take().unwrap();
+    assert_eq!(1, breakpoints.len());
+    assert_eq!("0x7ffe1234", breakpoints[0].data_id);
+    assert!(matches!(
+        breakpoints[0].access_type,
+        Some(dap::DataBreakpointAccessType::Write)
+    ));
+
+    running_state.update(cx, |state, cx| {
+        let list = state.breakpoint_list().read(cx);
+        assert_eq!(
+            vec![("main::run".to_string(), true)],
+            list.function_breakpoints(cx)
+        );
+        assert_eq!(
+            vec![("config.retries".to_string(), true)],
+            list.data_breakpoints(cx)
+        );
+    });
+
+    project.update(cx, |project, cx| {
+        project.breakpoint_store().update(cx, |store, cx| {
+            store.remove_data_breakpoint("0x7ffe1234", cx);
+        })
+    });
+
+    cx.run_until_parked();
+
+    let breakpoints = data_breakpoints.lock().unwrap().take().unwrap();
+    assert!(
+        breakpoints.is_empty(),
+        "Removed data breakpoints should be cleared in the adapter"
+    );
+
+    running_state.update(cx, |state, cx| {
+        state.breakpoint_list().update(cx, |list, cx| {
+            list.add_data_breakpoint(Some(2), "retries".into(), cx);
+        });
+    });
+
+    cx.run_until_parked();
+
+    let shutdown_session = project.update(cx, |project, cx| {
+        project.dap_store().update(cx, |dap_store, cx| {
+            dap_store.shutdown_session(session.read(cx).session_id(), cx)
+        })
+    });
+
+    shutdown_session.await.unwrap();
+
+    project.update(cx, |project, cx| {
+        assert!(
+            project
+                .breakpoint_store()
+                .read(cx)
+                .all_data_breakpoints()
+                .is_empty(),
+            "Data breakpoints that can't persist should be dropped with their session"
+        );
+    });
+}
@@ -0,0 +1,185 @@
+use crate::{
+    debugger_panel::DebugPanel,
+    session::ThreadItem,
+    tests::{active_debug_session_panel, init_test, init_test_workspace},
+};
+use dap::{
+    StackFrame, SteppingGranularity,
+    requests::{Disassemble, Next, StackTrace, Threads},
+};
+use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
+use project::{FakeFs, Project};
+use std::sync::{Arc, Mutex};
+use task::LaunchConfig;
+
+fn instruction(address: &str, instruction: &str) -> dap::DisassembledInstruction {
+    dap::DisassembledInstruction {
+        address: address.into(),
+        instruction_bytes: None,
+        instruction: instruction.into(),
+        symbol: None,
+        location: None,
+        line: None,
+        column: None,
+        end_line: None,
+        end_column: None,
+        presentation_hint: None,
+    }
+}
+
+#[gpui::test]
+async fn test_disassembly_view(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    let project = Project::test(fs, ["/project".as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let task = project.update(cx, |project, cx| {
+        project.fake_debug_session(
+            dap::DebugRequestType::Launch(LaunchConfig::default()),
+            Some(dap::Capabilities {
+                supports_disassemble_request: Some(true),
+                supports_stepping_granularity: Some(true),
+                ..Default::default()
+            }),
+            false,
+            cx,
+        )
+    });
+
+    let session = task.await.unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client
+        .on_request::<Threads, _>(move |_, _| {
+            Ok(dap::ThreadsResponse {
+                threads: vec![dap::Thread {
+                    id: 1,
+                    name: "Thread 1".into(),
+                }],
+            })
+        })
+        .await;
+
+    client
+        .on_request::<StackTrace, _>(move |_, args| {
+            assert_eq!(1, args.thread_id);
+
+            Ok(dap::StackTraceResponse {
+                stack_frames: vec![StackFrame {
+                    id: 1,
+                    name: "main".into(),
+                    source: None,
+                    line: 0,
+                    column: 0,
+                    end_line: None,
+                    end_column: None,
+                    can_restart: None,
+                    instruction_pointer_reference: Some("0x1008".into()),
+                    module_id: None,
+                    presentation_hint: None,
+                }],
+                total_frames: None,
+            })
+        })
+        .await;
+
+    client
+        .on_request::<Disassemble, _>(move |_, args| {
+            assert_eq!("0x1008", args.memory_reference);
+            assert_eq!(Some(-32), args.instruction_offset);
+
+            Ok(dap::DisassembleResponse {
+                instructions: vec![
+                    instruction("0x0000000000001000", "push rbp"),
+                    instruction("0x0000000000001004", "mov rbp, rsp"),
+                    instruction("0x0000000000001008", "sub rsp, 16"),
+                    instruction("0x000000000000100c", "call 0x2000"),
+                ],
+            })
+        })
+        .await;
+
+    let step_granularity = Arc::new(Mutex::new(None));
+    client
+        .on_request::<Next, _>({
+            let step_granularity = step_granularity.clone();
+            move |_, args| {
+                *step_granularity.lock().unwrap() = Some(args.granularity);
+                Ok(())
+            }
+        })
+        .await;
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+
+    let running_state =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            item.mode()
+                .as_running()
+                .expect("Session should be running by this point")
+                .clone()
+        });
+
+    running_state.update(cx, |state, cx| {
+        let threads = state
+            .session()
+            .update(cx, |session, cx| session.threads(cx));
+        state.select_current_thread(&threads, cx);
+        state.set_thread_item(ThreadItem::Disassembly, cx);
+        cx.refresh_windows();
+    });
+
+    cx.run_until_parked();
+
+    running_state.update(cx, |state, cx| {
+        let disassembly_view = state.disassembly_view().read(cx);
+        assert_eq!(
+            vec!["push rbp", "mov rbp, rsp", "sub rsp, 16", "call 0x2000"],
+            disassembly_view.instructions()
+        );
+        assert_eq!(
+            Some("sub rsp, 16".to_string()),
+            disassembly_view.current_instruction(),
+            "Addresses should be matched regardless of their zero padding"
+        );
+
+        state.step_over_instruction(cx);
+    });
+
+    cx.run_until_parked();
+
+    assert!(matches!(
+        step_granularity.lock().unwrap().take(),
+        Some(Some(SteppingGranularity::Instruction))
+    ));
+
+    let shutdown_session = project.update(cx, |project, cx| {
+        project.dap_store().update(cx, |dap_store, cx| {
+            dap_store.shutdown_session(session.read(cx).session_id(), cx)
+        })
+    });
+
+    shutdown_session.await.unwrap();
+}
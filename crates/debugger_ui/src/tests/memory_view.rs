@@ -0,0 +1,115 @@
+use crate::{
+    debugger_panel::DebugPanel,
+    tests::{active_debug_session_panel, init_test, init_test_workspace},
+};
+use dap::requests::{ReadMemory, WriteMemory};
+use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
+use project::{FakeFs, Project};
+use std::sync::{Arc, Mutex};
+use task::LaunchConfig;
+
+#[gpui::test]
+async fn test_memory_view(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    let project = Project::test(fs, ["/project".as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let task = project.update(cx, |project, cx| {
+        project.fake_debug_session(
+            dap::DebugRequestType::Launch(LaunchConfig::default()),
+            Some(dap::Capabilities {
+                supports_read_memory_request: Some(true),
+                supports_write_memory_request: Some(true),
+                ..Default::default()
+            }),
+            false,
+            cx,
+        )
+    });
+
+    let session = task.await.unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client
+        .on_request::<ReadMemory, _>(move |_, args| {
+            assert_eq!("0x7ffe0000", args.memory_reference);
+            assert_eq!(Some(0), args.offset);
+
+            Ok(dap::ReadMemoryResponse {
+                address: "0x7ffe0000".into(),
+                unreadable_bytes: Some(240),
+                // Bytes 0x00 through 0x0f
+                data: Some("AAECAwQFBgcICQoLDA0ODw==".into()),
+            })
+        })
+        .await;
+
+    let written = Arc::new(Mutex::new(None));
+    client
+        .on_request::<WriteMemory, _>({
+            let written = written.clone();
+            move |_, args| {
+                *written.lock().unwrap() = Some((args.offset, args.data));
+                Ok(dap::WriteMemoryResponse {
+                    offset: None,
+                    bytes_written: Some(1),
+                })
+            }
+        })
+        .await;
+
+    cx.run_until_parked();
+
+    let running_state =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            item.mode()
+                .as_running()
+                .expect("Session should be running by this point")
+                .clone()
+        });
+
+    running_state.update(cx, |state, cx| {
+        state.memory_view().update(cx, |memory_view, cx| {
+            memory_view.open("0x7ffe0000".into(), "buffer", cx);
+        });
+        cx.refresh_windows();
+    });
+
+    cx.run_until_parked();
+
+    running_state.update(cx, |state, cx| {
+        let memory_view = state.memory_view().read(cx);
+        let memory = memory_view.memory().expect("Memory should be loaded");
+        assert_eq!("0x7ffe0000", memory.address);
+        assert_eq!((0..16).collect::<Vec<u8>>(), memory.data);
+        assert_eq!(240, memory.unreadable_bytes);
+
+        state.memory_view().update(cx, |memory_view, cx| {
+            memory_view.write_byte(4, 0xff, cx);
+        });
+    });
+
+    cx.run_until_parked();
+
+    let (offset, data) = written.lock().unwrap().take().unwrap();
+    assert_eq!(Some(4), offset);
+    assert_eq!("/w==", data);
+
+    let shutdown_session = project.update(cx, |project, cx| {
+        project.dap_store().update(cx, |dap_store, cx| {
+            dap_store.shutdown_session(session.read(cx).session_id(), cx)
+        })
+    });
+
+    shutdown_session.await.unwrap();
+}
@@ -0,0 +1,289 @@
+use crate::{
+    debugger_panel::DebugPanel,
+    step_in_target_modal::{self, StepInTargetModal},
+    tests::{active_debug_session_panel, init_test, init_test_workspace},
+};
+use dap::{
+    StackFrame,
+    requests::{Goto, GotoTargets, StackTrace, StepIn, StepInTargets, Threads},
+};
+use gpui::{BackgroundExecutor, Entity, TestAppContext, VisualTestContext, WindowHandle};
+use menu::Confirm;
+use project::{FakeFs, Project};
+use std::{
+    path::Path,
+    sync::{Arc, Mutex},
+};
+use task::LaunchConfig;
+use workspace::Workspace;
+
+/// Starts a fake session that is stopped in `main` on thread 1.
+async fn start_stopped_session(
+    project: &Entity<Project>,
+    workspace: WindowHandle<Workspace>,
+    capabilities: dap::Capabilities,
+    cx: &mut VisualTestContext,
+) -> Entity<project::debugger::session::Session> {
+    let task = project.update(cx, |project, cx| {
+        project.fake_debug_session(
+            dap::DebugRequestType::Launch(LaunchConfig::default()),
+            Some(capabilities),
+            false,
+            cx,
+        )
+    });
+
+    let session = task.await.unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client
+        .on_request::<Threads, _>(move |_, _| {
+            Ok(dap::ThreadsResponse {
+                threads: vec![dap::Thread {
+                    id: 1,
+                    name: "Thread 1".into(),
+                }],
+            })
+        })
+        .await;
+
+    client
+        .on_request::<StackTrace, _>(move |_, _| {
+            Ok(dap::StackTraceResponse {
+                stack_frames: vec![StackFrame {
+                    id: 7,
+                    name: "main".into(),
+                    source: None,
+                    line: 3,
+                    column: 1,
+                    end_line: None,
+                    end_column: None,
+                    can_restart: None,
+                    instruction_pointer_reference: None,
+                    module_id: None,
+                    presentation_hint: None,
+                }],
+                total_frames: None,
+            })
+        })
+        .await;
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+
+    active_debug_session_panel(workspace, cx).update(cx, |item, cx| {
+        item.mode()
+            .as_running()
+            .unwrap()
+            .update(cx, |running_state, cx| {
+                let threads = running_state
+                    .session()
+                    .update(cx, |session, cx| session.threads(cx));
+                running_state.select_current_thread(&threads, cx);
+            });
+    });
+
+    cx.run_until_parked();
+
+    session
+}
+
+#[gpui::test]
+async fn test_step_in_targets(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    let project = Project::test(fs, ["/project".as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_stopped_session(
+        &project,
+        workspace,
+        dap::Capabilities {
+            supports_step_in_targets_request: Some(true),
+            ..Default::default()
+        },
+        cx,
+    )
+    .await;
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client
+        .on_request::<StepInTargets, _>(move |_, args| {
+            assert_eq!(7, args.frame_id);
+
+            Ok(dap::StepInTargetsResponse {
+                targets: vec![
+                    dap::StepInTarget {
+                        id: 1,
+                        label: "bar()".into(),
+                        line: Some(3),
+                        column: Some(5),
+                        end_line: None,
+                        end_column: None,
+                    },
+                    dap::StepInTarget {
+                        id: 2,
+                        label: "baz()".into(),
+                        line: Some(3),
+                        column: Some(12),
+                        end_line: None,
+                        end_column: None,
+                    },
+                ],
+            })
+        })
+        .await;
+
+    let stepped_target = Arc::new(Mutex::new(None));
+    client
+        .on_request::<StepIn, _>({
+            let stepped_target = stepped_target.clone();
+            move |_, args| {
+                *stepped_target.lock().unwrap() = Some(args.target_id);
+                Ok(())
+            }
+        })
+        .await;
+
+    let running_state =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            item.mode()
+                .as_running()
+                .expect("Session should be running by this point")
+                .clone()
+        });
+
+    running_state.update_in(cx, |state, window, cx| {
+        state.step_in_targets(window, cx);
+    });
+
+    cx.run_until_parked();
+
+    let modal = workspace
+        .update(cx, |workspace, _, cx| {
+            workspace.active_modal::<StepInTargetModal>(cx)
+        })
+        .unwrap()
+        .expect("Multiple targets should open the picker");
+
+    let labels = modal.update(cx, |modal, cx| {
+        step_in_target_modal::target_labels(modal, cx)
+    });
+    assert_eq!(vec!["bar()", "baz()"], labels);
+
+    cx.dispatch_action(menu::SelectNext);
+    cx.dispatch_action(Confirm);
+
+    cx.run_until_parked();
+
+    assert_eq!(Some(Some(2)), stepped_target.lock().unwrap().take());
+
+    let shutdown_session = project.update(cx, |project, cx| {
+        project.dap_store().update(cx, |dap_store, cx| {
+            dap_store.shutdown_session(session.read(cx).session_id(), cx)
+        })
+    });
+
+    shutdown_session.await.unwrap();
+}
+
+#[gpui::test]
+async fn test_jump_to_line(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    let project = Project::test(fs, ["/project".as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_stopped_session(
+        &project,
+        workspace,
+        dap::Capabilities {
+            supports_goto_targets_request: Some(true),
+            ..Default::default()
+        },
+        cx,
+    )
+    .await;
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client
+        .on_request::<GotoTargets, _>(move |_, args| {
+            assert_eq!(Some("/project/main.rs".to_string()), args.source.path);
+            assert_eq!(5, args.line);
+
+            Ok(dap::GotoTargetsResponse {
+                targets: vec![dap::GotoTarget {
+                    id: 42,
+                    label: "main.rs:5".into(),
+                    line: 5,
+                    column: None,
+                    end_line: None,
+                    end_column: None,
+                    instruction_pointer_reference: None,
+                }],
+            })
+        })
+        .await;
+
+    let goto_request = Arc::new(Mutex::new(None));
+    client
+        .on_request::<Goto, _>({
+            let goto_request = goto_request.clone();
+            move |_, args| {
+                *goto_request.lock().unwrap() = Some((args.thread_id, args.target_id));
+                Ok(())
+            }
+        })
+        .await;
+
+    let running_state = active_debug_session_panel(workspace, cx).update(cx, |item, _| {
+        item.mode()
+            .as_running()
+            .expect("Session should be running by this point")
+            .clone()
+    });
+
+    running_state.update(cx, |state, cx| {
+        state.jump_to_line(Arc::from(Path::new("/project/main.rs")), 5, cx);
+    });
+
+    cx.run_until_parked();
+
+    assert_eq!(Some((1, 42)), goto_request.lock().unwrap().take());
+
+    let shutdown_session = project.update(cx, |project, cx| {
+        project.dap_store().update(cx, |dap_store, cx| {
+            dap_store.shutdown_session(session.read(cx).session_id(), cx)
+        })
+    });
+
+    shutdown_session.await.unwrap();
+}
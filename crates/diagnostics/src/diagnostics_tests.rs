@@ -6,7 +6,8 @@ use editor::{
 };
 use gpui::{AvailableSpace, Stateful, TestAppContext, VisualTestContext, px};
 use language::{
-    Diagnostic, DiagnosticEntry, DiagnosticSeverity, OffsetRangeExt, PointUtf16, Rope, Unclipped,
+    Diagnostic, DiagnosticEntry, DiagnosticSeverity, DiagnosticSourceKind, OffsetRangeExt,
+    PointUtf16, Rope, Unclipped,
 };
 use pretty_assertions::assert_eq;
 use project::FakeFs;
@@ -1016,6 +1017,7 @@ fn random_diagnostic(
             is_primary,
             is_disk_based: false,
             is_unnecessary: false,
+            source_kind: DiagnosticSourceKind::Other,
             data: None,
         },
     }
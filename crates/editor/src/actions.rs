@@ -422,6 +422,7 @@ actions!(
         ToggleAutoSignatureHelp,
         ToggleGitBlameInline,
         OpenGitBlameCommit,
+        BlamePreviousRevision,
         ToggleIndentGuides,
         ToggleInlayHints,
         ToggleInlineDiagnostics,
@@ -33,10 +33,12 @@ mod lsp_ext;
 mod mouse_context_menu;
 pub mod movement;
 mod persistence;
+mod persistent_undo;
 mod proposed_changes_editor;
 mod rust_analyzer_ext;
 pub mod scroll;
 mod selections_collection;
+mod semantic_tokens;
 pub mod tasks;
 
 #[cfg(test)]
@@ -76,7 +78,7 @@ use futures::{
 };
 use fuzzy::StringMatchCandidate;
 
-use ::git::Restore;
+use ::git::{Restore, blame::BlameEntry, repository::RepoPath};
 use code_context_menus::{
     AvailableCodeAction, CodeActionContents, CodeActionsItem, CodeActionsMenu, CodeContextMenu,
     CompletionsMenu, ContextMenuOrigin,
@@ -125,7 +127,7 @@ use project::{
     },
 };
 
-pub use git::blame::BlameRenderer;
+pub use git::blame::{BlameRenderer, BlameRevision};
 pub use proposed_changes_editor::{
     ProposedChangeLocation, ProposedChangesEditor, ProposedChangesEditorToolbar,
 };
@@ -651,6 +653,10 @@ pub trait Addon: 'static {
     }
 
     fn to_any(&self) -> &dyn std::any::Any;
+
+    fn to_any_mut(&mut self) -> Option<&mut dyn std::any::Any> {
+        None
+    }
 }
 
 /// Zed's primary implementation of text input, allowing users to edit a [`MultiBuffer`].
@@ -729,6 +735,7 @@ pub struct Editor {
     document_highlights_task: Option<Task<()>>,
     linked_editing_range_task: Option<Task<Option<()>>>,
     linked_edit_ranges: linked_editing_ranges::LinkedEditingRanges,
+    semantic_tokens: semantic_tokens::SemanticTokens,
     pending_rename: Option<RenameState>,
     searchable: bool,
     cursor_shape: CursorShape,
@@ -780,6 +787,8 @@ pub struct Editor {
     serialize_dirty_buffers: bool,
     show_selection_menu: Option<bool>,
     blame: Option<Entity<GitBlame>>,
+    /// The past revision whose blame is shown instead of the working copy's.
+    blame_revision: Option<BlameRevision>,
     blame_subscription: Option<Subscription>,
     custom_context_menu: Option<
         Box<
@@ -815,6 +824,7 @@ pub struct Editor {
     _scroll_cursor_center_top_bottom_task: Task<()>,
     serialize_selections: Task<()>,
     serialize_folds: Task<()>,
+    persistent_undo_task: Task<()>,
     mouse_cursor_hidden: bool,
     hide_mouse_mode: HideMouseMode,
 }
@@ -1340,6 +1350,9 @@ impl Editor {
                             editor
                                 .refresh_inlay_hints(InlayHintRefreshReason::RefreshRequested, cx);
                         }
+                        project::Event::RefreshSemanticTokens => {
+                            semantic_tokens::refresh_semantic_tokens(editor, None, cx);
+                        }
                         project::Event::SnippetEdit(id, snippet_edits) => {
                             if let Some(buffer) = editor.buffer.read(cx).buffer(*id) {
                                 let focus_handle = editor.focus_handle(cx);
@@ -1555,6 +1568,7 @@ impl Editor {
                 .session
                 .restore_unsaved_buffers,
             blame: None,
+            blame_revision: None,
             blame_subscription: None,
             tasks: Default::default(),
 
@@ -1580,6 +1594,7 @@ impl Editor {
             ],
             tasks_update_task: None,
             linked_edit_ranges: Default::default(),
+            semantic_tokens: Default::default(),
             in_project_search: false,
             previous_search_ranges: None,
             breadcrumb_header: None,
@@ -1592,6 +1607,7 @@ impl Editor {
             toggle_fold_multiple_buffers: Task::ready(()),
             serialize_selections: Task::ready(()),
             serialize_folds: Task::ready(()),
+            persistent_undo_task: Task::ready(()),
             text_style_refinement: None,
             load_diff_task: load_uncommitted_diff,
             mouse_cursor_hidden: false,
@@ -1643,6 +1659,8 @@ impl Editor {
                         .insert(buffer.read(cx).remote_id(), handle);
                 }
             }
+            semantic_tokens::refresh_semantic_tokens(&mut this, None, cx);
+            persistent_undo::restore_undo_history(&mut this, cx);
         }
 
         this.report_editor_event("Editor Opened", None, cx);
@@ -15949,16 +15967,47 @@ impl Editor {
         self.open_git_blame_commit_internal(window, cx);
     }
 
-    fn open_git_blame_commit_internal(
+    pub fn blame_previous_revision(
         &mut self,
+        _: &BlamePreviousRevision,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(blame_entry) = self.blame_entry_at_cursor(window, cx) {
+            self.open_blame_previous_revision(blame_entry, window, cx);
+        }
+    }
+
+    /// Re-blames the file at the parent of the entry's commit, keeping the entry's line in view.
+    pub fn open_blame_previous_revision(
+        &mut self,
+        blame_entry: BlameEntry,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Option<()> {
+        let (sha, path) = blame_entry.previous_revision()?;
+        let revision = BlameRevision {
+            repository: self.blame.as_ref()?.read(cx).repository(cx)?,
+            path: RepoPath::from_str(path),
+            sha: sha.to_string().into(),
+        };
+        let row = blame_entry.original_line_number.saturating_sub(1);
+        let renderer = cx.global::<GlobalBlameRenderer>().0.clone();
+        let workspace = self.workspace()?.downgrade();
+        renderer.open_blame_revision(revision, row, cx.entity(), workspace, window, cx);
+        None
+    }
+
+    fn blame_entry_at_cursor(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<BlameEntry> {
         let blame = self.blame.as_ref()?;
         let snapshot = self.snapshot(window, cx);
         let cursor = self.selections.newest::<Point>(cx).head();
         let (buffer, point, _) = snapshot.buffer_snapshot.point_to_buffer_point(cursor)?;
-        let blame_entry = blame
+        blame
             .update(cx, |blame, cx| {
                 blame
                     .blame_for_rows(
@@ -15971,9 +16020,17 @@ impl Editor {
                     )
                     .next()
             })
-            .flatten()?;
+            .flatten()
+    }
+
+    fn open_git_blame_commit_internal(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<()> {
+        let blame_entry = self.blame_entry_at_cursor(window, cx)?;
         let renderer = cx.global::<GlobalBlameRenderer>().0.clone();
-        let repo = blame.read(cx).repository(cx)?;
+        let repo = self.blame.as_ref()?.read(cx).repository(cx)?;
         let workspace = self.workspace()?.downgrade();
         renderer.open_blame_commit(blame_entry, repo, workspace, window, cx);
         None
@@ -16020,7 +16077,10 @@ impl Editor {
             let focused = self.focus_handle(cx).contains_focused(window, cx);
 
             let project = project.clone();
-            let blame = cx.new(|cx| GitBlame::new(buffer, project, user_triggered, focused, cx));
+            let blame = cx.new(|cx| match self.blame_revision.clone() {
+                Some(revision) => GitBlame::for_revision(buffer, project, revision, focused, cx),
+                None => GitBlame::new(buffer, project, user_triggered, focused, cx),
+            });
             self.blame_subscription =
                 Some(cx.observe_in(&blame, window, |_, _, _, cx| cx.notify()));
             self.blame = Some(blame);
@@ -16064,6 +16124,20 @@ impl Editor {
         }
     }
 
+    /// Shows the blame of the file at the given revision in the gutter, for editors whose buffer
+    /// holds the file's contents at that revision.
+    pub fn set_blame_revision(
+        &mut self,
+        revision: BlameRevision,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.blame_revision = Some(revision);
+        self.show_git_blame_gutter = true;
+        self.start_git_blame(true, window, cx);
+        cx.notify();
+    }
+
     pub fn blame(&self) -> Option<&Entity<GitBlame>> {
         self.blame.as_ref()
     }
@@ -16825,6 +16899,19 @@ impl Editor {
         cx.notify();
     }
 
+    pub fn highlight_text_key<T: 'static>(
+        &mut self,
+        key: usize,
+        ranges: Vec<Range<Anchor>>,
+        style: HighlightStyle,
+        cx: &mut Context<Self>,
+    ) {
+        self.display_map.update(cx, |map, _| {
+            map.highlight_text_key(TypeId::of::<T>(), key, ranges, style)
+        });
+        cx.notify();
+    }
+
     pub(crate) fn highlight_inlays<T: 'static>(
         &mut self,
         highlights: Vec<InlayHighlight>,
@@ -16888,6 +16975,7 @@ impl Editor {
                 }
                 if let Some(buffer) = buffer_edited {
                     let buffer_id = buffer.read(cx).remote_id();
+                    semantic_tokens::refresh_semantic_tokens(self, Some(buffer_id), cx);
                     if !self.registered_buffers.contains_key(&buffer_id) {
                         if let Some(project) = self.project.as_ref() {
                             project.update(cx, |project, cx| {
@@ -16963,9 +17051,11 @@ impl Editor {
                     excerpts: excerpts.clone(),
                 });
                 self.refresh_inlay_hints(InlayHintRefreshReason::NewLinesShown, cx);
+                semantic_tokens::refresh_semantic_tokens(self, Some(buffer_id), cx);
             }
             multi_buffer::Event::ExcerptsRemoved { ids } => {
                 self.refresh_inlay_hints(InlayHintRefreshReason::ExcerptsRemoved(ids.clone()), cx);
+                semantic_tokens::update_semantic_token_highlights(self, cx);
                 let buffer = self.buffer.read(cx);
                 self.registered_buffers
                     .retain(|buffer_id, _| buffer.buffer(*buffer_id).is_some());
@@ -16998,12 +17088,16 @@ impl Editor {
             }
             multi_buffer::Event::LanguageChanged(buffer_id) => {
                 linked_editing_ranges::refresh_linked_ranges(self, window, cx);
+                semantic_tokens::refresh_semantic_tokens(self, Some(*buffer_id), cx);
                 jsx_tag_auto_close::refresh_enabled_in_any_buffer(self, multibuffer, cx);
                 cx.emit(EditorEvent::Reparsed(*buffer_id));
                 cx.notify();
             }
             multi_buffer::Event::DirtyChanged => cx.emit(EditorEvent::DirtyChanged),
-            multi_buffer::Event::Saved => cx.emit(EditorEvent::Saved),
+            multi_buffer::Event::Saved => {
+                persistent_undo::save_undo_history(self, cx);
+                cx.emit(EditorEvent::Saved);
+            }
             multi_buffer::Event::FileHandleChanged
             | multi_buffer::Event::Reloaded
             | multi_buffer::Event::BufferDiffChanged => cx.emit(EditorEvent::TitleChanged),
@@ -17039,6 +17133,7 @@ impl Editor {
             )),
             cx,
         );
+        semantic_tokens::refresh_semantic_tokens(self, None, cx);
 
         let old_cursor_shape = self.cursor_shape;
 
@@ -17782,6 +17877,13 @@ impl Editor {
             .and_then(|item| item.to_any().downcast_ref::<T>())
     }
 
+    pub fn addon_mut<T: Addon>(&mut self) -> Option<&mut T> {
+        let type_id = std::any::TypeId::of::<T>();
+        self.addons
+            .get_mut(&type_id)
+            .and_then(|item| item.to_any_mut()?.downcast_mut::<T>())
+    }
+
     fn character_size(&self, window: &mut Window) -> gpui::Size<Pixels> {
         let text_layout_details = self.text_layout_details(window);
         let style = &text_layout_details.editor_style;
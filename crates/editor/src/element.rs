@@ -23,7 +23,7 @@ use crate::{
     inlay_hint_settings,
     items::BufferSearchHighlights,
     mouse_context_menu::{self, MenuPosition},
-    scroll::scroll_amount::ScrollAmount,
+    scroll::{Autoscroll, scroll_amount::ScrollAmount},
 };
 use buffer_diff::{DiffHunkStatus, DiffHunkStatusKind};
 use client::ParticipantIndex;
@@ -38,13 +38,13 @@ use gpui::{
     InteractiveElement, IntoElement, Keystroke, Length, ModifiersChangedEvent, MouseButton,
     MouseDownEvent, MouseMoveEvent, MouseUpEvent, PaintQuad, ParentElement, Pixels, ScrollDelta,
     ScrollWheelEvent, ShapedLine, SharedString, Size, StatefulInteractiveElement, Style, Styled,
-    TextRun, TextStyleRefinement, WeakEntity, Window, anchored, deferred, div, fill,
+    StyledText, TextRun, TextStyleRefinement, WeakEntity, Window, anchored, deferred, div, fill,
     linear_color_stop, linear_gradient, outline, point, px, quad, relative, size, solid_background,
     transparent_black,
 };
 use itertools::Itertools;
 use language::{
-    ChunkRendererContext,
+    ChunkRendererContext, OutlineItem,
     language_settings::{
         IndentGuideBackgroundColoring, IndentGuideColoring, IndentGuideSettings,
         ShowWhitespaceSetting,
@@ -428,6 +428,7 @@ impl EditorElement {
         register_action(editor, window, Editor::toggle_git_blame);
         register_action(editor, window, Editor::toggle_git_blame_inline);
         register_action(editor, window, Editor::open_git_blame_commit);
+        register_action(editor, window, Editor::blame_previous_revision);
         register_action(editor, window, Editor::toggle_selected_diff_hunks);
         register_action(editor, window, Editor::toggle_staged_selected_diff_hunks);
         register_action(editor, window, Editor::stage_and_next);
@@ -3256,6 +3257,123 @@ impl EditorElement {
         header
     }
 
+    fn layout_sticky_scroll(
+        &self,
+        snapshot: &EditorSnapshot,
+        scroll_position: f32,
+        line_height: Pixels,
+        hitbox: &Hitbox,
+        gutter_dimensions: &GutterDimensions,
+        has_sticky_buffer_header: bool,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<AnyElement> {
+        let settings = EditorSettings::get_global(cx);
+        if !settings.sticky_scroll.enabled || snapshot.mode != EditorMode::Full {
+            return None;
+        }
+        // In multibuffers, the scopes are pinned below the header of the excerpt at the top.
+        let header_rows = if has_sticky_buffer_header {
+            FILE_HEADER_HEIGHT
+        } else {
+            0
+        };
+        let top_row = DisplayRow(scroll_position as u32 + header_rows);
+        let scopes = sticky_scroll_scopes(snapshot, top_row, settings.sticky_scroll.max_depth);
+        if scopes.is_empty() {
+            return None;
+        }
+
+        let show_line_numbers = snapshot
+            .show_line_numbers
+            .unwrap_or(settings.gutter.line_numbers);
+        let colors = cx.theme().colors();
+        let mut line_number_style = self.style.text.clone();
+        line_number_style.color = colors.editor_line_number;
+
+        let rows = scopes.into_iter().enumerate().map(|(ix, scope)| {
+            let header_row = scope
+                .range
+                .start
+                .to_display_point(&snapshot.display_snapshot)
+                .row();
+            let mut line = String::new();
+            let mut highlights = Vec::new();
+            for chunk in
+                snapshot.highlighted_chunks(header_row..header_row.next_row(), true, &self.style)
+            {
+                let text = chunk.text.split('\n').next().unwrap_or_default();
+                if let Some(style) = chunk.style {
+                    highlights.push((line.len()..line.len() + text.len(), style));
+                }
+                line.push_str(text);
+                if text.len() < chunk.text.len() {
+                    break;
+                }
+            }
+            let line_number = show_line_numbers
+                .then(|| {
+                    let start = scope.range.start.to_point(&snapshot.buffer_snapshot);
+                    let (_, point, _) = snapshot.buffer_snapshot.point_to_buffer_point(start)?;
+                    Some((point.row + 1).to_string())
+                })
+                .flatten();
+
+            let editor = self.editor.clone();
+            let anchor = scope.range.start;
+            h_flex()
+                .h(line_height)
+                .w_full()
+                .whitespace_nowrap()
+                .overflow_hidden()
+                .cursor_pointer()
+                .hover(|style| style.bg(colors.editor_active_line_background))
+                .on_mouse_down(MouseButton::Left, move |_, window, cx| {
+                    cx.stop_propagation();
+                    editor.update(cx, |editor, cx| {
+                        // Scroll the header to just below the scopes still pinned above it.
+                        editor.change_selections(
+                            Some(Autoscroll::top_relative(ix)),
+                            window,
+                            cx,
+                            |selections| selections.select_anchor_ranges([anchor..anchor]),
+                        );
+                    });
+                })
+                .child(
+                    h_flex()
+                        .flex_none()
+                        .justify_end()
+                        .w(gutter_dimensions.width)
+                        .pr(gutter_dimensions.right_padding)
+                        .when_some(line_number, |this, line_number| {
+                            this.child(
+                                StyledText::new(line_number)
+                                    .with_default_highlights(&line_number_style, []),
+                            )
+                        }),
+                )
+                .child(div().flex_none().w(gutter_dimensions.margin))
+                .child(StyledText::new(line).with_default_highlights(&self.style.text, highlights))
+        });
+
+        let mut element = v_flex()
+            .w(hitbox.size.width)
+            .bg(colors.editor_background)
+            .border_b_1()
+            .border_color(colors.border_variant)
+            .children(rows)
+            .into_any_element();
+
+        let origin = hitbox.origin + point(Pixels::ZERO, header_rows as f32 * line_height);
+        let available_space = size(
+            AvailableSpace::Definite(hitbox.size.width),
+            AvailableSpace::MinContent,
+        );
+        element.prepaint_as_root(origin, available_space, window, cx);
+        Some(element)
+    }
+
     fn layout_cursor_popovers(
         &self,
         line_height: Pixels,
@@ -6974,6 +7092,19 @@ impl Element for EditorElement {
                         })
                     });
 
+                    let sticky_scroll = window.with_element_namespace("sticky_scroll", |window| {
+                        self.layout_sticky_scroll(
+                            &snapshot,
+                            scroll_position.y,
+                            line_height,
+                            &hitbox,
+                            &gutter_dimensions,
+                            sticky_buffer_header.is_some(),
+                            window,
+                            cx,
+                        )
+                    });
+
                     let start_buffer_row =
                         MultiBufferRow(start_anchor.to_point(&snapshot.buffer_snapshot).row);
                     let end_buffer_row =
@@ -7472,6 +7603,7 @@ impl Element for EditorElement {
                         tab_invisible,
                         space_invisible,
                         sticky_buffer_header,
+                        sticky_scroll,
                         expand_toggles,
                     }
                 })
@@ -7539,6 +7671,12 @@ impl Element for EditorElement {
                         }
                     });
 
+                    window.with_element_namespace("sticky_scroll", |window| {
+                        if let Some(mut sticky_scroll) = layout.sticky_scroll.take() {
+                            sticky_scroll.paint(window, cx)
+                        }
+                    });
+
                     self.paint_scrollbars(layout, window, cx);
                     self.paint_inline_completion_popover(layout, window, cx);
                     self.paint_mouse_context_menu(layout, window, cx);
@@ -7548,6 +7686,89 @@ impl Element for EditorElement {
     }
 }
 
+/// The outline items enclosing the top of the viewport whose headers have scrolled above it,
+/// outermost first, which sticky scroll pins to the top of the editor.
+///
+/// Each pinned header covers one more row, so the item at each depth is looked up at the row
+/// below the headers pinned so far, and the headers stop where an enclosing scope ends. In
+/// multibuffers, only the items whose headers are inside the excerpt are pinned.
+fn sticky_scroll_scopes(
+    snapshot: &EditorSnapshot,
+    top_row: DisplayRow,
+    max_depth: usize,
+) -> Vec<OutlineItem<Anchor>> {
+    let mut scopes: Vec<OutlineItem<Anchor>> = Vec::new();
+    let max_row = snapshot.max_point().row();
+    while scopes.len() < max_depth {
+        let row = DisplayRow(top_row.0 + scopes.len() as u32);
+        if row > max_row {
+            break;
+        }
+        let point = DisplayPoint::new(row, 0).to_point(&snapshot.display_snapshot);
+        let anchor = snapshot.buffer_snapshot.anchor_before(point);
+        let excerpt_id = anchor.excerpt_id;
+        let (Some(buffer), Some(context)) = (
+            snapshot.buffer_snapshot.buffer_for_excerpt(excerpt_id),
+            snapshot
+                .buffer_snapshot
+                .context_range_for_excerpt(excerpt_id),
+        ) else {
+            break;
+        };
+        let Some(items) = buffer.symbols_containing(anchor.text_anchor, None) else {
+            break;
+        };
+        let to_anchor = |text_anchor| {
+            snapshot
+                .buffer_snapshot
+                .anchor_in_excerpt(excerpt_id, text_anchor)
+        };
+        let mut items = items
+            .into_iter()
+            // Items ending just before the row are included too, but no longer enclose it. Items
+            // starting above the excerpt are left out, as their headers aren't in the multibuffer.
+            .filter(|item| {
+                item.range.end.cmp(&anchor.text_anchor, buffer).is_ge()
+                    && item.range.start.cmp(&context.start, buffer).is_ge()
+            })
+            .filter_map(|item| {
+                Some(OutlineItem {
+                    depth: item.depth,
+                    range: to_anchor(item.range.start)?..to_anchor(item.range.end)?,
+                    text: item.text,
+                    highlight_ranges: item.highlight_ranges,
+                    name_ranges: item.name_ranges,
+                    body_range: item
+                        .body_range
+                        .and_then(|range| Some(to_anchor(range.start)?..to_anchor(range.end)?)),
+                    annotation_range: item
+                        .annotation_range
+                        .and_then(|range| Some(to_anchor(range.start)?..to_anchor(range.end)?)),
+                })
+            })
+            .collect::<Vec<_>>();
+        if items.len() <= scopes.len()
+            || items
+                .iter()
+                .zip(&scopes)
+                .any(|(item, scope)| item.range != scope.range)
+        {
+            break;
+        }
+        let item = items.swap_remove(scopes.len());
+        let header_row = item
+            .range
+            .start
+            .to_display_point(&snapshot.display_snapshot)
+            .row();
+        if header_row >= row {
+            break;
+        }
+        scopes.push(item);
+    }
+    scopes
+}
+
 pub(super) fn gutter_bounds(
     editor_bounds: Bounds<Pixels>,
     gutter_dimensions: GutterDimensions,
@@ -7649,6 +7870,8 @@ pub struct EditorLayout {
     tab_invisible: ShapedLine,
     space_invisible: ShapedLine,
     sticky_buffer_header: Option<AnyElement>,
+    /// The headers of the scopes enclosing the top visible line, pinned to the top of the editor.
+    sticky_scroll: Option<AnyElement>,
 }
 
 impl EditorLayout {
@@ -8405,12 +8628,13 @@ fn compute_auto_height_layout(
 mod tests {
     use super::*;
     use crate::{
-        Editor, MultiBuffer,
+        Editor, ExcerptRange, MultiBuffer,
         display_map::{BlockPlacement, BlockProperties},
         editor_tests::{init_test, update_test_language_settings},
     };
     use gpui::{TestAppContext, VisualTestContext};
-    use language::language_settings;
+    use indoc::indoc;
+    use language::{Capability, Language, LanguageConfig, language_settings};
     use log::info;
     use std::num::NonZeroU32;
     use util::test::sample_text;
@@ -8715,6 +8939,100 @@ mod tests {
         }
     }
 
+    fn sticky_scroll_test_buffer(cx: &mut TestAppContext) -> Entity<language::Buffer> {
+        let language = Arc::new(
+            Language::new(
+                LanguageConfig {
+                    name: "Rust".into(),
+                    ..Default::default()
+                },
+                Some(tree_sitter_rust::LANGUAGE.into()),
+            )
+            .with_outline_query(
+                r#"
+                (impl_item "impl" @context type: (_) @name) @item
+                (function_item "fn" @context name: (_) @name) @item
+                "#,
+            )
+            .unwrap(),
+        );
+        let text = indoc! {"
+            impl Foo {
+                fn bar() {
+                    let a = 1;
+                    let b = 2;
+                }
+            }
+            fn baz() {}
+        "};
+        cx.new(|cx| language::Buffer::local(text, cx).with_language(language, cx))
+    }
+
+    #[gpui::test]
+    fn test_sticky_scroll_scopes(cx: &mut TestAppContext) {
+        init_test(cx, |_| {});
+        let buffer = sticky_scroll_test_buffer(cx);
+        cx.executor().run_until_parked();
+        let window = cx.add_window(|window, cx| {
+            let buffer = cx.new(|cx| MultiBuffer::singleton(buffer, cx));
+            Editor::new(EditorMode::Full, buffer, None, window, cx)
+        });
+        let snapshot = window
+            .update(cx, |editor, window, cx| editor.snapshot(window, cx))
+            .unwrap();
+        let scopes = |top_row, max_depth| {
+            sticky_scroll_scopes(&snapshot, DisplayRow(top_row), max_depth)
+                .into_iter()
+                .map(|scope| scope.text)
+                .collect::<Vec<_>>()
+        };
+
+        // Nothing is pinned while the headers are visible.
+        assert_eq!(scopes(0, 5), Vec::<String>::new());
+        // Pinning the impl covers the function header, so it is pinned too.
+        assert_eq!(scopes(1, 5), ["impl Foo", "fn bar"]);
+        assert_eq!(scopes(2, 5), ["impl Foo", "fn bar"]);
+        assert_eq!(scopes(2, 1), ["impl Foo"]);
+        // The function ends on the row the second header would cover.
+        assert_eq!(scopes(4, 5), ["impl Foo"]);
+        assert_eq!(scopes(6, 5), Vec::<String>::new());
+    }
+
+    #[gpui::test]
+    fn test_sticky_scroll_scopes_in_multibuffer(cx: &mut TestAppContext) {
+        init_test(cx, |_| {});
+        let buffer = sticky_scroll_test_buffer(cx);
+        cx.executor().run_until_parked();
+        let window = cx.add_window(|window, cx| {
+            let multibuffer = cx.new(|cx| {
+                let mut multibuffer = MultiBuffer::new(Capability::ReadWrite);
+                // The excerpt starts inside the impl, below its header.
+                multibuffer.push_excerpts(
+                    buffer,
+                    [ExcerptRange::new(Point::new(1, 0)..Point::new(5, 0))],
+                    cx,
+                );
+                multibuffer
+            });
+            Editor::new(EditorMode::Full, multibuffer, None, window, cx)
+        });
+        let snapshot = window
+            .update(cx, |editor, window, cx| editor.snapshot(window, cx))
+            .unwrap();
+        let scopes = |row| {
+            let top_row = Point::new(row, 0).to_display_point(&snapshot).row();
+            sticky_scroll_scopes(&snapshot, top_row, 5)
+                .into_iter()
+                .map(|scope| scope.text)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(scopes(0), Vec::<String>::new());
+        // The impl's header isn't in the excerpt, so only the function is pinned.
+        assert_eq!(scopes(1), ["fn bar"]);
+        assert_eq!(scopes(2), ["fn bar"]);
+    }
+
     #[gpui::test]
     fn test_wrapped_invisibles_drawing(cx: &mut TestAppContext) {
         let tab_size = 4;
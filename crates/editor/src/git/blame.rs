@@ -5,10 +5,11 @@ use git::{
     GitHostingProviderRegistry, GitRemote, Oid,
     blame::{Blame, BlameEntry, ParsedCommitMessage},
     parse_git_remote_url,
+    repository::RepoPath,
 };
 use gpui::{
-    AnyElement, App, AppContext as _, Context, Entity, Hsla, Subscription, Task, TextStyle,
-    WeakEntity, Window,
+    AnyElement, App, AppContext as _, Context, Entity, Hsla, SharedString, Subscription, Task,
+    TextStyle, WeakEntity, Window,
 };
 use language::{Bias, Buffer, BufferSnapshot, Edit};
 use multi_buffer::RowInfo;
@@ -62,9 +63,18 @@ impl<'a> sum_tree::Dimension<'a, GitBlameEntrySummary> for u32 {
     }
 }
 
+/// A file as it was at a past commit, blamed instead of the buffer's working copy.
+#[derive(Clone, Debug)]
+pub struct BlameRevision {
+    pub repository: Entity<Repository>,
+    pub path: RepoPath,
+    pub sha: SharedString,
+}
+
 pub struct GitBlame {
     project: Entity<Project>,
     buffer: Entity<Buffer>,
+    revision: Option<BlameRevision>,
     entries: SumTree<GitBlameEntry>,
     commit_details: HashMap<Oid, ParsedCommitMessage>,
     buffer_snapshot: BufferSnapshot,
@@ -113,6 +123,17 @@ pub trait BlameRenderer {
         _: &mut Window,
         _: &mut App,
     );
+
+    /// Opens the file at the given revision with its own blame, scrolled to the given row.
+    fn open_blame_revision(
+        &self,
+        _: BlameRevision,
+        _: u32,
+        _: Entity<Editor>,
+        _: WeakEntity<Workspace>,
+        _: &mut Window,
+        _: &mut App,
+    );
 }
 
 impl BlameRenderer for () {
@@ -157,6 +178,17 @@ impl BlameRenderer for () {
         _: &mut App,
     ) {
     }
+
+    fn open_blame_revision(
+        &self,
+        _: BlameRevision,
+        _: u32,
+        _: Entity<Editor>,
+        _: WeakEntity<Workspace>,
+        _: &mut Window,
+        _: &mut App,
+    ) {
+    }
 }
 
 pub(crate) struct GlobalBlameRenderer(pub Arc<dyn BlameRenderer>);
@@ -170,6 +202,28 @@ impl GitBlame {
         user_triggered: bool,
         focused: bool,
         cx: &mut Context<Self>,
+    ) -> Self {
+        Self::new_internal(buffer, project, None, user_triggered, focused, cx)
+    }
+
+    /// Blames the buffer's contents as those of the file at the given revision.
+    pub fn for_revision(
+        buffer: Entity<Buffer>,
+        project: Entity<Project>,
+        revision: BlameRevision,
+        focused: bool,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        Self::new_internal(buffer, project, Some(revision), true, focused, cx)
+    }
+
+    fn new_internal(
+        buffer: Entity<Buffer>,
+        project: Entity<Project>,
+        revision: Option<BlameRevision>,
+        user_triggered: bool,
+        focused: bool,
+        cx: &mut Context<Self>,
     ) -> Self {
         let entries = SumTree::from_item(
             GitBlameEntry {
@@ -227,6 +281,7 @@ impl GitBlame {
         let mut this = Self {
             project,
             buffer,
+            revision,
             buffer_snapshot,
             entries,
             buffer_edits,
@@ -248,6 +303,9 @@ impl GitBlame {
     }
 
     pub fn repository(&self, cx: &App) -> Option<Entity<Repository>> {
+        if let Some(revision) = &self.revision {
+            return Some(revision.repository.clone());
+        }
         self.project
             .read(cx)
             .git_store()
@@ -256,6 +314,10 @@ impl GitBlame {
             .map(|(repo, _)| repo)
     }
 
+    pub fn revision(&self) -> Option<&BlameRevision> {
+        self.revision.as_ref()
+    }
+
     pub fn has_generated_entries(&self) -> bool {
         self.generated
     }
@@ -436,7 +498,16 @@ impl GitBlame {
         }
         let buffer_edits = self.buffer.update(cx, |buffer, _| buffer.subscribe());
         let snapshot = self.buffer.read(cx).snapshot();
-        let blame = self.project.read(cx).blame_buffer(&self.buffer, None, cx);
+        let blame = match &self.revision {
+            Some(revision) => {
+                let blame = revision
+                    .repository
+                    .read(cx)
+                    .blame_at_revision(revision.path.clone(), revision.sha.to_string());
+                cx.background_spawn(async move { anyhow::Ok(Some(blame.await??)) })
+            }
+            None => self.project.read(cx).blame_buffer(&self.buffer, None, cx),
+        };
         let provider_registry = GitHostingProviderRegistry::default_global(cx);
 
         self.task = cx.spawn(async move |this, cx| {
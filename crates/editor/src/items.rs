@@ -1511,6 +1511,7 @@ impl SearchableItem for Editor {
     ) {
         let text = self.buffer.read(cx);
         let text = text.snapshot(cx);
+        let language = text.language_at(identifier.start).cloned();
         let text = text.text_for_range(identifier.clone()).collect::<Vec<_>>();
         let text: Cow<_> = if text.len() == 1 {
             text.first().cloned().unwrap().into()
@@ -1519,7 +1520,7 @@ impl SearchableItem for Editor {
             joined_chunks.into()
         };
 
-        if let Some(replacement) = query.replacement_for(&text) {
+        if let Some(replacement) = query.replacement_for(&text, language.as_ref()) {
             self.transact(window, cx, |this, _, cx| {
                 this.edit([(identifier.clone(), Arc::from(&*replacement))], cx);
             });
@@ -1536,6 +1537,7 @@ impl SearchableItem for Editor {
         let text = text.snapshot(cx);
         let mut edits = vec![];
         for m in matches {
+            let language = text.language_at(m.start);
             let text = text.text_for_range(m.clone()).collect::<Vec<_>>();
             let text: Cow<_> = if text.len() == 1 {
                 text.first().cloned().unwrap().into()
@@ -1544,7 +1546,7 @@ impl SearchableItem for Editor {
                 joined_chunks.into()
             };
 
-            if let Some(replacement) = query.replacement_for(&text) {
+            if let Some(replacement) = query.replacement_for(&text, language) {
                 edits.push((m.clone(), Arc::from(&*replacement)));
             }
         }
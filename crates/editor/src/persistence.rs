@@ -113,6 +113,12 @@ define_connection!(
     //   start: usize,
     //   end: usize,
     // )
+    //
+    // buffer_undo_histories(
+    //   path: PathBuf,
+    //   content_hash: Vec<u8>,
+    //   history: String,
+    // )
     pub static ref DB: EditorDb<WorkspaceDb> = &[
         sql! (
             CREATE TABLE editors(
@@ -188,6 +194,14 @@ define_connection!(
                 ON DELETE CASCADE
             ) STRICT;
         ),
+        sql! (
+            CREATE TABLE buffer_undo_histories (
+                path BLOB NOT NULL,
+                content_hash BLOB NOT NULL,
+                history TEXT NOT NULL,
+                PRIMARY KEY(path)
+            ) STRICT;
+        ),
     ];
 );
 
@@ -270,6 +284,27 @@ impl EditorDb {
         }
     }
 
+    query! {
+        pub fn get_undo_history(path: PathBuf) -> Result<Option<(Vec<u8>, String)>> {
+            SELECT content_hash, history
+            FROM buffer_undo_histories
+            WHERE path = ?
+        }
+    }
+
+    // Replacing a row gives it a new rowid, so only the most recently saved histories are kept.
+    query! {
+        pub async fn save_undo_history(path: PathBuf, content_hash: Vec<u8>, history: String) -> Result<()> {
+            INSERT OR REPLACE INTO buffer_undo_histories (path, content_hash, history)
+            VALUES (?1, ?2, ?3);
+
+            DELETE FROM buffer_undo_histories
+            WHERE rowid NOT IN (
+                SELECT rowid FROM buffer_undo_histories ORDER BY rowid DESC LIMIT 500
+            );
+        }
+    }
+
     pub async fn save_editor_selections(
         &self,
         editor_id: ItemId,
@@ -478,4 +513,21 @@ mod tests {
             .unwrap();
         assert_eq!(have, serialized_editor);
     }
+
+    #[gpui::test]
+    async fn test_save_and_get_undo_history() {
+        let path = PathBuf::from("/undo_history.txt");
+        assert_eq!(DB.get_undo_history(path.clone()).unwrap(), None);
+
+        DB.save_undo_history(path.clone(), vec![1, 2, 3], "first".to_owned())
+            .await
+            .unwrap();
+        DB.save_undo_history(path.clone(), vec![4, 5, 6], "second".to_owned())
+            .await
+            .unwrap();
+        assert_eq!(
+            DB.get_undo_history(path).unwrap(),
+            Some((vec![4, 5, 6], "second".to_owned()))
+        );
+    }
 }
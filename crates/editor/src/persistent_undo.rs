@@ -0,0 +1,96 @@
+use std::path::PathBuf;
+
+use gpui::{Context, Entity};
+use language::{Buffer, SerializedHistory};
+use settings::Settings as _;
+use sha2::{Digest as _, Sha256};
+use util::ResultExt as _;
+
+use crate::{Editor, EditorMode, EditorSettings, persistence::DB};
+
+/// The most text a buffer's persisted undo history holds, counting both the replaced and the
+/// inserted text of every edit.
+const MAX_PERSISTED_HISTORY_LEN: usize = 1024 * 1024;
+
+/// Persists the undo history of the editor's buffer once it's saved, so that the history can be
+/// restored when the file is reopened with the same contents.
+pub(super) fn save_undo_history(editor: &mut Editor, cx: &mut Context<Editor>) {
+    let Some((buffer, abs_path)) = local_singleton_buffer(editor, cx) else {
+        return;
+    };
+    let buffer = buffer.read(cx);
+    if buffer.is_dirty() {
+        return;
+    }
+    let history = buffer.history_snapshot();
+    let snapshot = buffer.text_snapshot();
+    editor.persistent_undo_task = cx.background_spawn(async move {
+        let content_hash = content_hash(&snapshot);
+        let history = history.serialize(MAX_PERSISTED_HISTORY_LEN);
+        let Some(history) = serde_json::to_string(&history).log_err() else {
+            return;
+        };
+        DB.save_undo_history(abs_path, content_hash, history)
+            .await
+            .log_err();
+    });
+}
+
+/// Restores the persisted undo history of the editor's buffer, unless the buffer already has a
+/// history or its contents differ from those the history was saved with.
+pub(super) fn restore_undo_history(editor: &mut Editor, cx: &mut Context<Editor>) {
+    let Some((buffer, abs_path)) = local_singleton_buffer(editor, cx) else {
+        return;
+    };
+    if has_history(buffer.read(cx)) || buffer.read(cx).is_dirty() {
+        return;
+    }
+    let snapshot = buffer.read(cx).text_snapshot();
+    let version = snapshot.version().clone();
+    editor.persistent_undo_task = cx.spawn(async move |_, cx| {
+        let history = cx
+            .background_spawn(async move {
+                let (saved_hash, history) = DB.get_undo_history(abs_path).log_err()??;
+                if saved_hash != content_hash(&snapshot) {
+                    return None;
+                }
+                serde_json::from_str::<SerializedHistory>(&history).log_err()
+            })
+            .await;
+        let Some(history) = history.filter(|history| !history.is_empty()) else {
+            return;
+        };
+        buffer
+            .update(cx, |buffer, cx| {
+                // The buffer may have been edited while the history was loading.
+                if buffer.version() == version && !has_history(buffer) {
+                    buffer.restore_history(history, cx).log_err();
+                }
+            })
+            .ok();
+    });
+}
+
+fn local_singleton_buffer(
+    editor: &Editor,
+    cx: &mut Context<Editor>,
+) -> Option<(Entity<Buffer>, PathBuf)> {
+    if editor.mode != EditorMode::Full || !EditorSettings::get_global(cx).persistent_undo {
+        return None;
+    }
+    let buffer = editor.buffer.read(cx).as_singleton()?;
+    let abs_path = buffer.read(cx).file()?.as_local()?.abs_path(cx);
+    Some((buffer, abs_path))
+}
+
+fn has_history(buffer: &Buffer) -> bool {
+    buffer.peek_undo_stack().is_some() || buffer.peek_redo_stack().is_some()
+}
+
+fn content_hash(snapshot: &text::BufferSnapshot) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    for chunk in snapshot.as_rope().chunks() {
+        hasher.update(chunk.as_bytes());
+    }
+    hasher.finalize().to_vec()
+}
@@ -0,0 +1,229 @@
+use std::{ops::Range, time::Duration};
+
+use collections::HashMap;
+use gpui::{Context, HighlightStyle, Task};
+use language::{Bias, Buffer, Unclipped, language_settings::language_settings};
+use multi_buffer::Anchor;
+use project::{BufferSemanticTokens, SemanticToken};
+use text::BufferId;
+use theme::{ActiveTheme as _, SyntaxTheme};
+use util::ResultExt as _;
+
+use crate::{Editor, EditorMode};
+
+const UPDATE_DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// Marker type for the text highlights produced from semantic tokens.
+enum SemanticTokenHighlight {}
+
+#[derive(Default)]
+pub(super) struct SemanticTokens {
+    tokens: HashMap<BufferId, AnchoredSemanticTokens>,
+    update_tasks: HashMap<BufferId, Task<()>>,
+}
+
+struct AnchoredSemanticTokens {
+    tokens: BufferSemanticTokens,
+    /// The buffer range of each item yielded by [`BufferSemanticTokens::tokens`], in order.
+    ranges: Vec<Range<text::Anchor>>,
+}
+
+/// Requests semantic tokens for the given buffer, or for every buffer of the editor when `None`,
+/// dropping the tokens of buffers that have semantic tokens disabled.
+pub(super) fn refresh_semantic_tokens(
+    editor: &mut Editor,
+    buffer_id: Option<BufferId>,
+    cx: &mut Context<Editor>,
+) {
+    if editor.mode != EditorMode::Full {
+        return;
+    }
+    let Some(project) = editor.project.clone() else {
+        return;
+    };
+
+    let multi_buffer = editor.buffer.read(cx);
+    let buffers = match buffer_id {
+        Some(buffer_id) => multi_buffer
+            .buffer(buffer_id)
+            .into_iter()
+            .collect::<Vec<_>>(),
+        None => multi_buffer.all_buffers().into_iter().collect(),
+    };
+
+    let mut tokens_removed = false;
+    for buffer in buffers {
+        let buffer_id = buffer.read(cx).remote_id();
+        if !semantic_tokens_enabled(&buffer, cx) {
+            editor.semantic_tokens.update_tasks.remove(&buffer_id);
+            tokens_removed |= editor.semantic_tokens.tokens.remove(&buffer_id).is_some();
+            continue;
+        }
+
+        let project = project.clone();
+        let task = cx.spawn(async move |editor, cx| {
+            cx.background_executor().timer(UPDATE_DEBOUNCE).await;
+            let Some(tokens) = project
+                .update(cx, |project, cx| project.semantic_tokens(&buffer, cx))
+                .log_err()
+            else {
+                return;
+            };
+            let Some(tokens) = tokens.await.log_err() else {
+                return;
+            };
+            editor
+                .update(cx, |editor, cx| {
+                    match tokens {
+                        Some(tokens) => {
+                            let buffer = buffer.read(cx);
+                            // Tokens computed for an older version can't be positioned in the
+                            // buffer; the edits since then have scheduled another refresh.
+                            if buffer.version() != tokens.version {
+                                return;
+                            }
+                            let ranges = tokens
+                                .tokens()
+                                .map(|token| {
+                                    let start = buffer
+                                        .clip_point_utf16(Unclipped(token.range.start), Bias::Left);
+                                    let end = buffer
+                                        .clip_point_utf16(Unclipped(token.range.end), Bias::Right);
+                                    buffer.anchor_after(start)..buffer.anchor_before(end)
+                                })
+                                .collect();
+                            editor
+                                .semantic_tokens
+                                .tokens
+                                .insert(buffer_id, AnchoredSemanticTokens { tokens, ranges });
+                        }
+                        None => {
+                            editor.semantic_tokens.tokens.remove(&buffer_id);
+                        }
+                    }
+                    update_semantic_token_highlights(editor, cx);
+                })
+                .ok();
+        });
+        editor.semantic_tokens.update_tasks.insert(buffer_id, task);
+    }
+
+    if tokens_removed {
+        update_semantic_token_highlights(editor, cx);
+    }
+}
+
+/// Re-applies the known semantic tokens as text highlights, e.g. after excerpts were removed.
+pub(super) fn update_semantic_token_highlights(editor: &mut Editor, cx: &mut Context<Editor>) {
+    let multi_buffer = editor.buffer.read(cx);
+    editor
+        .semantic_tokens
+        .tokens
+        .retain(|buffer_id, _| multi_buffer.buffer(*buffer_id).is_some());
+
+    let snapshot = multi_buffer.snapshot(cx);
+    let syntax_theme = cx.theme().syntax().clone();
+    let mut highlight_ids = HashMap::default();
+    let mut highlights = HashMap::<u32, (HighlightStyle, Vec<Range<Anchor>>)>::default();
+    for (buffer_id, tokens) in &editor.semantic_tokens.tokens {
+        let Some(buffer) = multi_buffer.buffer(*buffer_id) else {
+            continue;
+        };
+        let buffer = buffer.read(cx);
+        let excerpts = multi_buffer.excerpts_for_buffer(*buffer_id, cx);
+        for (token, range) in tokens.tokens.tokens().zip(&tokens.ranges) {
+            let highlight = *highlight_ids
+                .entry((token.token_type, token.modifiers.clone()))
+                .or_insert_with(|| highlight_for_token(&token, &syntax_theme));
+            let Some((highlight_id, style)) = highlight else {
+                continue;
+            };
+            for (excerpt_id, excerpt_range) in &excerpts {
+                if range.end.cmp(&excerpt_range.context.start, buffer).is_lt()
+                    || range.start.cmp(&excerpt_range.context.end, buffer).is_gt()
+                {
+                    continue;
+                }
+                if let Some((start, end)) = snapshot
+                    .anchor_in_excerpt(*excerpt_id, range.start)
+                    .zip(snapshot.anchor_in_excerpt(*excerpt_id, range.end))
+                {
+                    highlights
+                        .entry(highlight_id)
+                        .or_insert_with(|| (style, Vec::new()))
+                        .1
+                        .push(start..end);
+                }
+            }
+        }
+    }
+
+    editor.clear_highlights::<SemanticTokenHighlight>(cx);
+    for (highlight_id, (style, mut ranges)) in highlights {
+        ranges.sort_by(|a, b| a.start.cmp(&b.start, &snapshot));
+        editor.highlight_text_key::<SemanticTokenHighlight>(
+            highlight_id as usize,
+            ranges,
+            style,
+            cx,
+        );
+    }
+}
+
+fn semantic_tokens_enabled(buffer: &gpui::Entity<Buffer>, cx: &gpui::App) -> bool {
+    let buffer = buffer.read(cx);
+    language_settings(
+        buffer.language().map(|language| language.name()),
+        buffer.file(),
+        cx,
+    )
+    .semantic_tokens
+}
+
+/// Resolves the theme style of a token. For each syntax key the token type maps to, keys
+/// refined by one of the token's modifiers (e.g. `variable.mutable`) take precedence over
+/// the plain key. Token types without a known mapping are looked up by their own name.
+fn highlight_for_token(
+    token: &SemanticToken,
+    syntax_theme: &SyntaxTheme,
+) -> Option<(u32, HighlightStyle)> {
+    let own_name = [token.token_type];
+    let keys = match theme_keys_for_token_type(token.token_type) {
+        [] => &own_name[..],
+        keys => keys,
+    };
+    let highlight_id = keys.iter().find_map(|key| {
+        token
+            .modifiers
+            .iter()
+            .find_map(|modifier| syntax_theme.highlight_id(&format!("{key}.{modifier}")))
+            .or_else(|| syntax_theme.highlight_id(key))
+    })?;
+    let (_, style) = syntax_theme.highlights.get(highlight_id as usize)?;
+    Some((highlight_id, *style))
+}
+
+fn theme_keys_for_token_type(token_type: &str) -> &'static [&'static str] {
+    match token_type {
+        "namespace" => &["namespace"],
+        "type" | "class" | "struct" | "interface" | "typeParameter" | "typeAlias"
+        | "builtinType" => &["type"],
+        "enum" => &["enum", "type"],
+        "enumMember" => &["variant"],
+        "parameter" => &["variable.parameter", "variable"],
+        "variable" => &["variable"],
+        "property" => &["property"],
+        "function" => &["function"],
+        "method" => &["function.method", "function"],
+        "macro" => &["function.macro", "function.special", "function"],
+        "keyword" | "modifier" => &["keyword"],
+        "comment" => &["comment"],
+        "string" => &["string"],
+        "number" => &["number"],
+        "regexp" => &["string.regex"],
+        "operator" => &["operator"],
+        "decorator" => &["attribute"],
+        "label" => &["label"],
+        _ => &[],
+    }
+}
@@ -1,16 +1,18 @@
-use crate::FakeFs;
+use crate::{FakeFs, Fs as _, RemoveOptions};
 use anyhow::{Context as _, Result, anyhow};
 use collections::{HashMap, HashSet};
 use futures::future::{self, BoxFuture};
 use git::{
     blame::Blame,
     repository::{
-        AskPassDelegate, Branch, CommitDetails, GitRepository, GitRepositoryCheckpoint,
-        PushOptions, Remote, RepoPath, ResetMode,
+        AskPassDelegate, Branch, CommitDetails, CommitDiff, CommitFile, CommitOptions,
+        GitRepository, GitRepositoryCheckpoint, GitWorktree, LogEntry, LogOptions, PushOptions,
+        RebaseAction, RebaseStatus, RebaseStep, Remote, RepoPath, ResetMode, StashEntry, Submodule,
+        SubmoduleStatus,
     },
     status::{FileStatus, GitStatus, StatusCode, TrackedStatus, UnmergedStatus},
 };
-use gpui::{AsyncApp, BackgroundExecutor};
+use gpui::{AsyncApp, BackgroundExecutor, SharedString};
 use ignore::gitignore::GitignoreBuilder;
 use rope::Rope;
 use smol::future::FutureExt as _;
@@ -31,9 +33,44 @@ pub struct FakeGitRepositoryState {
     pub head_contents: HashMap<RepoPath, String>,
     pub index_contents: HashMap<RepoPath, String>,
     pub blames: HashMap<RepoPath, Blame>,
+    /// The blames of files at past revisions, keyed by the revision as it is requested.
+    pub revision_blames: HashMap<(String, RepoPath), Blame>,
+    /// The contents of files at past revisions, keyed by the revision as it is requested.
+    pub revision_contents: HashMap<(String, RepoPath), String>,
     pub current_branch_name: Option<String>,
     pub branches: HashSet<String>,
     pub simulated_index_write_error_message: Option<String>,
+    /// The stash entries, the most recent one first.
+    pub stash_entries: Vec<FakeStashEntry>,
+    pub next_stash_id: usize,
+    /// The commits reachable from HEAD, the most recent first, each with the paths it changed.
+    pub log_entries: Vec<(LogEntry, Vec<RepoPath>)>,
+    /// The commits on which a rebase stops as if they conflicted.
+    pub rebase_conflicts: HashSet<SharedString>,
+    pub rebase: Option<FakeRebase>,
+    pub submodules: Vec<Submodule>,
+    /// The linked worktrees, excluding the main one.
+    pub worktrees: Vec<GitWorktree>,
+}
+
+/// A rebase that stopped at the first of its remaining steps.
+#[derive(Debug, Clone)]
+pub struct FakeRebase {
+    /// The log before the rebase started, restored when it is aborted.
+    original_log_entries: Vec<(LogEntry, Vec<RepoPath>)>,
+    base_sha: SharedString,
+    /// The commits applied so far, oldest first.
+    applied: Vec<(LogEntry, Vec<RepoPath>)>,
+    remaining_steps: Vec<RebaseStep>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FakeStashEntry {
+    pub sha: String,
+    pub message: String,
+    pub head_contents: HashMap<RepoPath, String>,
+    /// The stashed working copy contents of each changed path, `None` for deleted files.
+    pub worktree_contents: HashMap<RepoPath, Option<String>>,
 }
 
 impl FakeGitRepositoryState {
@@ -45,10 +82,115 @@ impl FakeGitRepositoryState {
             index_contents: Default::default(),
             unmerged_paths: Default::default(),
             blames: Default::default(),
+            revision_blames: Default::default(),
+            revision_contents: Default::default(),
             current_branch_name: Default::default(),
             branches: Default::default(),
             simulated_index_write_error_message: Default::default(),
+            stash_entries: Default::default(),
+            next_stash_id: Default::default(),
+            log_entries: Default::default(),
+            rebase_conflicts: Default::default(),
+            rebase: Default::default(),
+            submodules: Default::default(),
+            worktrees: Default::default(),
+        }
+    }
+}
+
+impl FakeGitRepositoryState {
+    /// Resolves `HEAD`, a ref or a sha prefix to a commit of the log, returning the commit and
+    /// all of its ancestors.
+    fn ancestors(&self, revision: &str) -> Result<HashSet<SharedString>> {
+        let start = self.resolve(revision)?;
+        let mut ancestors = HashSet::default();
+        let mut pending = vec![start.sha.clone()];
+        while let Some(sha) = pending.pop() {
+            if !ancestors.insert(sha.clone()) {
+                continue;
+            }
+            if let Some((entry, _)) = self.log_entries.iter().find(|(entry, _)| entry.sha == sha) {
+                pending.extend(entry.parent_shas.iter().cloned());
+            }
+        }
+        Ok(ancestors)
+    }
+
+    fn resolve(&self, revision: &str) -> Result<&LogEntry> {
+        let entry = if revision == "HEAD" {
+            self.log_entries.first()
+        } else {
+            self.log_entries.iter().find(|(entry, _)| {
+                entry.refs.iter().any(|name| name.as_ref() == revision)
+                    || entry.sha.starts_with(revision)
+            })
+        };
+        entry
+            .map(|(entry, _)| entry)
+            .with_context(|| format!("unknown revision {revision:?}"))
+    }
+
+    /// Applies the remaining steps of the rebase until one of them conflicts. When `resolved` is
+    /// true, the first step is applied even if it conflicts.
+    fn run_rebase(&mut self, mut rebase: FakeRebase, mut resolved: bool) -> Result<RebaseStatus> {
+        while let Some(step) = rebase.remaining_steps.first().cloned() {
+            if !resolved
+                && step.action != RebaseAction::Drop
+                && self.rebase_conflicts.contains(&step.sha)
+            {
+                self.rebase = Some(rebase);
+                return Ok(RebaseStatus::Stopped {
+                    commit: Some(step.sha),
+                });
+            }
+            resolved = false;
+            rebase.remaining_steps.remove(0);
+
+            let (entry, changed_paths) = rebase
+                .original_log_entries
+                .iter()
+                .find(|(entry, _)| entry.sha == step.sha)
+                .cloned()
+                .with_context(|| format!("unknown commit {}", step.sha))?;
+            match step.action {
+                RebaseAction::Pick => rebase.applied.push((entry, changed_paths)),
+                RebaseAction::Reword => {
+                    let mut entry = entry;
+                    if let Some(message) = &step.message {
+                        entry.subject = message
+                            .lines()
+                            .next()
+                            .unwrap_or_default()
+                            .to_string()
+                            .into();
+                    }
+                    rebase.applied.push((entry, changed_paths));
+                }
+                RebaseAction::Squash | RebaseAction::Fixup => {
+                    let (_, previous_paths) = rebase
+                        .applied
+                        .last_mut()
+                        .context("cannot squash without a previous commit")?;
+                    previous_paths.extend(changed_paths);
+                }
+                RebaseAction::Drop => {}
+            }
         }
+
+        let base_index = self
+            .log_entries
+            .iter()
+            .position(|(entry, _)| entry.sha == rebase.base_sha)
+            .context("unknown base commit")?;
+        let mut parent_sha = rebase.base_sha;
+        for (entry, _) in &mut rebase.applied {
+            entry.parent_shas = vec![parent_sha.clone()];
+            parent_sha = entry.sha.clone();
+        }
+        rebase.applied.reverse();
+        rebase.applied.extend(self.log_entries.drain(base_index..));
+        self.log_entries = rebase.applied;
+        Ok(RebaseStatus::Idle)
     }
 }
 
@@ -111,6 +253,16 @@ impl GitRepository for FakeGitRepository {
         .boxed()
     }
 
+    fn load_text_at_revision(
+        &self,
+        path: RepoPath,
+        revision: String,
+    ) -> BoxFuture<Result<Option<String>>> {
+        self.with_state_async(false, move |state| {
+            Ok(state.revision_contents.get(&(revision, path)).cloned())
+        })
+    }
+
     fn load_commit(
         &self,
         _commit: String,
@@ -119,6 +271,46 @@ impl GitRepository for FakeGitRepository {
         unimplemented!()
     }
 
+    fn log(&self, options: LogOptions) -> BoxFuture<Result<Vec<LogEntry>>> {
+        self.with_state_async(false, move |state| {
+            let revision = options.revision.as_deref().unwrap_or("HEAD");
+            let (excluded, included) = match revision.split_once("..") {
+                Some((excluded, included)) => (Some(excluded), included),
+                None => (None, revision),
+            };
+            let excluded = excluded
+                .map(|revision| state.ancestors(revision))
+                .transpose()?
+                .unwrap_or_default();
+            let included = state.ancestors(included)?;
+            let message = options.message.map(|message| message.to_lowercase());
+
+            Ok(state
+                .log_entries
+                .iter()
+                .filter(|(entry, changed_paths)| {
+                    included.contains(&entry.sha)
+                        && !excluded.contains(&entry.sha)
+                        && options.path.as_ref().is_none_or(|path| {
+                            changed_paths
+                                .iter()
+                                .any(|changed_path| changed_path.starts_with(path))
+                        })
+                        && options.author.as_ref().is_none_or(|author| {
+                            entry.author_name.contains(author.as_str())
+                                || entry.author_email.contains(author.as_str())
+                        })
+                        && message.as_ref().is_none_or(|message| {
+                            entry.subject.to_lowercase().contains(message.as_str())
+                        })
+                })
+                .skip(options.skip)
+                .take(options.limit)
+                .map(|(entry, _)| entry.clone())
+                .collect())
+        })
+    }
+
     fn set_index_text(
         &self,
         path: RepoPath,
@@ -353,12 +545,47 @@ impl GitRepository for FakeGitRepository {
         })
     }
 
+    fn blame_at_revision(
+        &self,
+        path: RepoPath,
+        revision: String,
+    ) -> BoxFuture<Result<git::blame::Blame>> {
+        self.with_state_async(false, move |state| {
+            state
+                .revision_blames
+                .get(&(revision.clone(), path.clone()))
+                .with_context(|| format!("failed to get blame for {:?} at {revision}", path.0))
+                .cloned()
+        })
+    }
+
     fn stage_paths(
         &self,
-        _paths: Vec<RepoPath>,
+        paths: Vec<RepoPath>,
         _env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<Result<()>> {
-        unimplemented!()
+        let workdir_path = self.dot_git_path.parent().unwrap().to_path_buf();
+        let contents = paths
+            .into_iter()
+            .map(|path| {
+                let content = self
+                    .fs
+                    .read_file_sync(workdir_path.join(&path))
+                    .ok()
+                    .map(|content| String::from_utf8(content).unwrap());
+                (path, content)
+            })
+            .collect::<Vec<_>>();
+        self.with_state_async(true, move |state| {
+            for (path, content) in contents {
+                state.unmerged_paths.remove(&path);
+                match content {
+                    Some(content) => state.index_contents.insert(path, content),
+                    None => state.index_contents.remove(&path),
+                };
+            }
+            Ok(())
+        })
     }
 
     fn unstage_paths(
@@ -373,6 +600,8 @@ impl GitRepository for FakeGitRepository {
         &self,
         _message: gpui::SharedString,
         _name_and_email: Option<(gpui::SharedString, gpui::SharedString)>,
+        _options: CommitOptions,
+        _askpass: AskPassDelegate,
         _env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<Result<()>> {
         unimplemented!()
@@ -422,6 +651,383 @@ impl GitRepository for FakeGitRepository {
         unimplemented!()
     }
 
+    fn stash_push(
+        &self,
+        message: Option<String>,
+        include_untracked: bool,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        let workdir_path = self.dot_git_path.parent().unwrap().to_path_buf();
+        async move {
+            let status = self.status(&[RepoPath::default()]).await?;
+            let worktree_contents = status
+                .entries
+                .iter()
+                .filter(|(_, status)| include_untracked || !status.is_untracked())
+                .map(|(path, _)| {
+                    let content = self
+                        .fs
+                        .read_file_sync(workdir_path.join(path))
+                        .ok()
+                        .map(|content| String::from_utf8(content).unwrap());
+                    (path.clone(), content)
+                })
+                .collect::<HashMap<_, _>>();
+
+            let head_contents = self
+                .with_state_async(true, move |state| {
+                    if worktree_contents.is_empty() {
+                        return Err(anyhow!("No local changes to save"));
+                    }
+                    let id = state.next_stash_id;
+                    state.next_stash_id += 1;
+                    let branch = state
+                        .current_branch_name
+                        .as_deref()
+                        .unwrap_or("(no branch)");
+                    let message = match message {
+                        Some(message) => format!("On {branch}: {message}"),
+                        None => format!("WIP on {branch}"),
+                    };
+                    let head_contents = worktree_contents
+                        .keys()
+                        .map(|path| (path.clone(), state.head_contents.get(path).cloned()))
+                        .collect::<Vec<_>>();
+                    state.stash_entries.insert(
+                        0,
+                        FakeStashEntry {
+                            sha: format!("{id:040x}"),
+                            message,
+                            head_contents: state.head_contents.clone(),
+                            worktree_contents,
+                        },
+                    );
+                    state.index_contents = state.head_contents.clone();
+                    Ok(head_contents)
+                })
+                .await?;
+
+            for (path, content) in head_contents {
+                let abs_path = workdir_path.join(&path);
+                match content {
+                    Some(content) => self.fs.atomic_write(abs_path, content).await?,
+                    None => {
+                        self.fs
+                            .remove_file(
+                                &abs_path,
+                                RemoveOptions {
+                                    recursive: false,
+                                    ignore_if_not_exists: true,
+                                },
+                            )
+                            .await?
+                    }
+                }
+            }
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn stash_list(&self) -> BoxFuture<Result<Vec<StashEntry>>> {
+        self.with_state_async(false, |state| {
+            Ok(state
+                .stash_entries
+                .iter()
+                .enumerate()
+                .map(|(index, entry)| StashEntry {
+                    index,
+                    sha: entry.sha.clone().into(),
+                    message: entry.message.clone().into(),
+                    commit_timestamp: 0,
+                })
+                .collect())
+        })
+    }
+
+    fn stash_apply(
+        &self,
+        index: usize,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        let workdir_path = self.dot_git_path.parent().unwrap().to_path_buf();
+        async move {
+            let entry = self
+                .with_state_async(false, move |state| {
+                    state
+                        .stash_entries
+                        .get(index)
+                        .cloned()
+                        .with_context(|| format!("stash@{{{index}}} is not a valid reference"))
+                })
+                .await?;
+            for (path, content) in entry.worktree_contents {
+                let abs_path = workdir_path.join(&path);
+                match content {
+                    Some(content) => self.fs.atomic_write(abs_path, content).await?,
+                    None => {
+                        self.fs
+                            .remove_file(
+                                &abs_path,
+                                RemoveOptions {
+                                    recursive: false,
+                                    ignore_if_not_exists: true,
+                                },
+                            )
+                            .await?
+                    }
+                }
+            }
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn stash_pop(&self, index: usize, env: Arc<HashMap<String, String>>) -> BoxFuture<Result<()>> {
+        async move {
+            self.stash_apply(index, env.clone()).await?;
+            self.stash_drop(index, env).await
+        }
+        .boxed()
+    }
+
+    fn stash_drop(
+        &self,
+        index: usize,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        self.with_state_async(true, move |state| {
+            anyhow::ensure!(
+                index < state.stash_entries.len(),
+                "stash@{{{index}}} is not a valid reference"
+            );
+            state.stash_entries.remove(index);
+            Ok(())
+        })
+    }
+
+    fn stash_show(&self, index: usize) -> BoxFuture<Result<CommitDiff>> {
+        self.with_state_async(false, move |state| {
+            let entry = state
+                .stash_entries
+                .get(index)
+                .with_context(|| format!("stash@{{{index}}} is not a valid reference"))?;
+            let mut files = entry
+                .worktree_contents
+                .iter()
+                .map(|(path, new_text)| CommitFile {
+                    path: path.clone(),
+                    old_text: entry.head_contents.get(path).cloned(),
+                    new_text: new_text.clone(),
+                })
+                .collect::<Vec<_>>();
+            files.sort_by(|a, b| a.path.cmp(&b.path));
+            Ok(CommitDiff { files })
+        })
+    }
+
+    fn rebase_interactive(
+        &self,
+        base: String,
+        steps: Vec<RebaseStep>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<RebaseStatus>> {
+        self.with_state_async(true, move |state| {
+            anyhow::ensure!(state.rebase.is_none(), "a rebase is already in progress");
+            let base_sha = state.resolve(&base)?.sha.clone();
+            let base_ancestors = state.ancestors(&base)?;
+            for step in &steps {
+                anyhow::ensure!(
+                    state
+                        .log_entries
+                        .iter()
+                        .any(|(entry, _)| entry.sha == step.sha
+                            && !base_ancestors.contains(&entry.sha)),
+                    "commit {} is not being rebased",
+                    step.sha
+                );
+            }
+            if let Some(first_step) = steps.iter().find(|step| step.action != RebaseAction::Drop) {
+                anyhow::ensure!(
+                    !matches!(
+                        first_step.action,
+                        RebaseAction::Squash | RebaseAction::Fixup
+                    ),
+                    "cannot squash without a previous commit"
+                );
+            }
+
+            let rebase = FakeRebase {
+                original_log_entries: state.log_entries.clone(),
+                base_sha,
+                applied: Vec::new(),
+                remaining_steps: steps,
+            };
+            state.run_rebase(rebase, false)
+        })
+    }
+
+    fn rebase_continue(
+        &self,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<RebaseStatus>> {
+        self.with_state_async(true, |state| {
+            let rebase = state.rebase.take().context("no rebase in progress")?;
+            state.run_rebase(rebase, true)
+        })
+    }
+
+    fn rebase_skip(&self, _env: Arc<HashMap<String, String>>) -> BoxFuture<Result<RebaseStatus>> {
+        self.with_state_async(true, |state| {
+            let mut rebase = state.rebase.take().context("no rebase in progress")?;
+            rebase.remaining_steps.remove(0);
+            state.run_rebase(rebase, false)
+        })
+    }
+
+    fn rebase_abort(&self, _env: Arc<HashMap<String, String>>) -> BoxFuture<Result<()>> {
+        self.with_state_async(true, |state| {
+            let rebase = state.rebase.take().context("no rebase in progress")?;
+            state.log_entries = rebase.original_log_entries;
+            Ok(())
+        })
+    }
+
+    fn rebase_status(&self) -> BoxFuture<Result<RebaseStatus>> {
+        self.with_state_async(false, |state| {
+            Ok(match &state.rebase {
+                Some(rebase) => RebaseStatus::Stopped {
+                    commit: rebase.remaining_steps.first().map(|step| step.sha.clone()),
+                },
+                None => RebaseStatus::Idle,
+            })
+        })
+    }
+
+    fn submodules(&self) -> BoxFuture<Result<Vec<Submodule>>> {
+        self.with_state_async(false, |state| Ok(state.submodules.clone()))
+    }
+
+    fn submodule_update(
+        &self,
+        paths: Vec<RepoPath>,
+        init: bool,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        self.with_state_async(true, move |state| {
+            for submodule in &mut state.submodules {
+                if !paths.is_empty() && !paths.contains(&submodule.path) {
+                    continue;
+                }
+                match submodule.status {
+                    SubmoduleStatus::Uninitialized if !init => {}
+                    SubmoduleStatus::Conflicted => {
+                        anyhow::bail!("submodule {:?} has merge conflicts", submodule.path)
+                    }
+                    _ => submodule.status = SubmoduleStatus::UpToDate,
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn worktrees(&self) -> BoxFuture<Result<Vec<GitWorktree>>> {
+        let workdir_path = self.dot_git_path.parent().unwrap().to_path_buf();
+        self.with_state_async(false, move |state| {
+            let main_worktree = GitWorktree {
+                path: workdir_path,
+                sha: state
+                    .log_entries
+                    .first()
+                    .map(|(entry, _)| entry.sha.clone()),
+                branch: state.current_branch_name.clone().map(SharedString::from),
+                is_main: true,
+            };
+            Ok(std::iter::once(main_worktree)
+                .chain(state.worktrees.iter().cloned())
+                .collect())
+        })
+    }
+
+    fn worktree_add(
+        &self,
+        path: PathBuf,
+        revision: String,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        let workdir_path = self.dot_git_path.parent().unwrap().to_path_buf();
+        async move {
+            let worktree_path = path.clone();
+            self.with_state_async(true, move |state| {
+                if path == workdir_path || state.worktrees.iter().any(|w| w.path == path) {
+                    anyhow::bail!("{path:?} is already a worktree");
+                }
+                let worktree = if state.branches.contains(&revision) {
+                    if state.current_branch_name.as_ref() == Some(&revision)
+                        || state
+                            .worktrees
+                            .iter()
+                            .any(|w| w.branch.as_deref() == Some(revision.as_str()))
+                    {
+                        anyhow::bail!("branch {revision} is already checked out");
+                    }
+                    GitWorktree {
+                        path,
+                        sha: state
+                            .log_entries
+                            .first()
+                            .map(|(entry, _)| entry.sha.clone()),
+                        branch: Some(revision.into()),
+                        is_main: false,
+                    }
+                } else {
+                    GitWorktree {
+                        path,
+                        sha: Some(state.resolve(&revision)?.sha.clone()),
+                        branch: None,
+                        is_main: false,
+                    }
+                };
+                state.worktrees.push(worktree);
+                Ok(())
+            })
+            .await?;
+            self.fs.create_dir(&worktree_path).await
+        }
+        .boxed()
+    }
+
+    fn worktree_remove(
+        &self,
+        path: PathBuf,
+        _force: bool,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        async move {
+            let worktree_path = path.clone();
+            self.with_state_async(true, move |state| {
+                let ix = state
+                    .worktrees
+                    .iter()
+                    .position(|w| w.path == path)
+                    .with_context(|| format!("{path:?} is not a linked worktree"))?;
+                state.worktrees.remove(ix);
+                Ok(())
+            })
+            .await?;
+            self.fs
+                .remove_dir(
+                    &worktree_path,
+                    RemoveOptions {
+                        recursive: true,
+                        ignore_if_not_exists: true,
+                    },
+                )
+                .await
+        }
+        .boxed()
+    }
+
     fn checkpoint(&self) -> BoxFuture<'static, Result<GitRepositoryCheckpoint>> {
         unimplemented!()
     }
@@ -1419,6 +1419,49 @@ impl FakeFs {
         .unwrap();
     }
 
+    pub fn set_revision_blame_for_repo(
+        &self,
+        dot_git: &Path,
+        revision: &str,
+        blames: Vec<(RepoPath, git::blame::Blame)>,
+    ) {
+        self.with_git_state(dot_git, true, |state| {
+            state.revision_blames.extend(
+                blames
+                    .into_iter()
+                    .map(|(path, blame)| ((revision.to_string(), path), blame)),
+            );
+        })
+        .unwrap();
+    }
+
+    pub fn set_revision_contents_for_repo(
+        &self,
+        dot_git: &Path,
+        revision: &str,
+        contents_by_path: &[(RepoPath, String)],
+    ) {
+        self.with_git_state(dot_git, true, |state| {
+            state.revision_contents.extend(
+                contents_by_path.iter().map(|(path, contents)| {
+                    ((revision.to_string(), path.clone()), contents.clone())
+                }),
+            );
+        })
+        .unwrap();
+    }
+
+    pub fn set_log_for_repo(
+        &self,
+        dot_git: &Path,
+        log_entries: Vec<(git::repository::LogEntry, Vec<RepoPath>)>,
+    ) {
+        self.with_git_state(dot_git, true, |state| {
+            state.log_entries = log_entries;
+        })
+        .unwrap();
+    }
+
     /// Put the given git repository into a state with the given status,
     /// by mutating the head, index, and unmerged state.
     pub fn set_status_for_repo(&self, dot_git: &Path, statuses: &[(&Path, FileStatus)]) {
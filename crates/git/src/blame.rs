@@ -38,7 +38,28 @@ impl Blame {
         remote_url: Option<String>,
     ) -> Result<Self> {
         let output = run_git_blame(git_binary, working_directory, path, content).await?;
-        let mut entries = parse_git_blame(&output)?;
+        Self::from_output(&output, working_directory, remote_url).await
+    }
+
+    /// Blames the file as it was at the given revision, rather than its working copy.
+    pub async fn for_path_at_revision(
+        git_binary: &Path,
+        working_directory: &Path,
+        path: &Path,
+        revision: &str,
+        remote_url: Option<String>,
+    ) -> Result<Self> {
+        let output =
+            run_git_blame_at_revision(git_binary, working_directory, path, revision).await?;
+        Self::from_output(&output, working_directory, remote_url).await
+    }
+
+    async fn from_output(
+        output: &str,
+        working_directory: &Path,
+        remote_url: Option<String>,
+    ) -> Result<Self> {
+        let mut entries = parse_git_blame(output)?;
         entries.sort_unstable_by(|a, b| a.range.start.cmp(&b.range.start));
 
         let mut unique_shas = HashSet::default();
@@ -109,6 +130,31 @@ async fn run_git_blame(
     Ok(String::from_utf8(output.stdout)?)
 }
 
+async fn run_git_blame_at_revision(
+    git_binary: &Path,
+    working_directory: &Path,
+    path: &Path,
+    revision: &str,
+) -> Result<String> {
+    if revision.starts_with('-') {
+        return Err(anyhow!("invalid revision {revision:?}"));
+    }
+    let output = util::command::new_smol_command(git_binary)
+        .current_dir(working_directory)
+        .args(["blame", "--incremental", revision, "--"])
+        .arg(path.as_os_str())
+        .output()
+        .await
+        .map_err(|e| anyhow!("Failed to start git blame process: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("git blame process failed: {}", stderr));
+    }
+
+    Ok(String::from_utf8(output.stdout)?)
+}
+
 #[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
 pub struct BlameEntry {
     pub sha: Oid,
@@ -172,6 +218,12 @@ impl BlameEntry {
         })
     }
 
+    /// The parent of the entry's commit and the path the file had there, when the lines existed
+    /// before that commit.
+    pub fn previous_revision(&self) -> Option<(&str, &str)> {
+        self.previous.as_deref()?.split_once(' ')
+    }
+
     pub fn author_offset_date_time(&self) -> Result<time::OffsetDateTime> {
         if let (Some(author_time), Some(author_tz)) = (self.author_time, &self.author_tz) {
             let format = format_description!("[offset_hour][offset_minute]");
@@ -365,6 +417,22 @@ mod tests {
         assert_eq_golden(&entries, "blame_incremental_simple");
     }
 
+    #[test]
+    fn test_previous_revision() {
+        let output = read_test_data("blame_incremental_simple");
+        let entries = parse_git_blame(&output).unwrap();
+        for entry in entries {
+            match entry.previous.as_deref() {
+                Some(previous) => {
+                    let (sha, path) = entry.previous_revision().unwrap();
+                    assert_eq!(format!("{sha} {path}"), previous);
+                    assert_eq!(sha.len(), 40);
+                }
+                None => assert_eq!(entry.previous_revision(), None),
+            }
+        }
+    }
+
     #[test]
     fn test_parse_git_blame_complex() {
         let output = read_test_data("blame_incremental_complex");
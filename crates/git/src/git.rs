@@ -12,11 +12,13 @@ pub use git2 as libgit;
 use gpui::action_with_deprecated_aliases;
 use gpui::actions;
 use gpui::impl_action_with_deprecated_aliases;
+use gpui::impl_actions;
 pub use repository::WORK_DIRECTORY_REPO_PATH;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::ffi::OsStr;
 use std::fmt;
+use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::LazyLock;
 
@@ -49,6 +51,16 @@ actions!(
         ForcePush,
         Pull,
         Fetch,
+        StashAll,
+        StashAllIncludingUntracked,
+        StashPop,
+        ViewHistory,
+        RebaseInteractive,
+        RebaseContinue,
+        RebaseSkip,
+        RebaseAbort,
+        InitSubmodules,
+        UpdateSubmodules,
         Commit,
         ExpandCommitEditor,
         GenerateCommitMessage,
@@ -62,6 +74,15 @@ pub struct RestoreFile {
     pub skip_prompt: bool,
 }
 
+/// Opens the commit history of a file or directory, or of the active file when no path is given.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct FileHistory {
+    #[serde(default)]
+    pub abs_path: Option<PathBuf>,
+}
+
+impl_actions!(git, [FileHistory]);
 impl_action_with_deprecated_aliases!(git, RestoreFile, ["editor::RevertFile"]);
 action_with_deprecated_aliases!(git, Restore, ["editor::RevertSelectedHunks"]);
 action_with_deprecated_aliases!(git, Blame, ["editor::ToggleGitBlame"]);
@@ -155,6 +155,134 @@ impl CommitDetails {
     }
 }
 
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct StashEntry {
+    /// The position of the entry in the stash, where `0` is the most recent one (`stash@{0}`).
+    pub index: usize,
+    pub sha: SharedString,
+    pub message: SharedString,
+    /// This is a unix timestamp
+    pub commit_timestamp: i64,
+}
+
+impl StashEntry {
+    pub fn name(&self) -> String {
+        stash_ref(self.index)
+    }
+}
+
+/// Selects the commits returned by [`GitRepository::log`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LogOptions {
+    /// A revision or revision range, such as `main` or `v1.0..HEAD`. Defaults to `HEAD`.
+    pub revision: Option<String>,
+    /// Only include commits that changed this path.
+    pub path: Option<RepoPath>,
+    /// Only include commits whose author name or email matches this pattern.
+    pub author: Option<String>,
+    /// Only include commits whose message matches this pattern, ignoring case.
+    pub message: Option<String>,
+    /// The number of matching commits to skip, for pagination.
+    pub skip: usize,
+    /// The maximum number of commits to return.
+    pub limit: usize,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct LogEntry {
+    pub sha: SharedString,
+    /// The parent commits, which are rewritten to the nearest matching ancestors when the log
+    /// is limited to a path.
+    pub parent_shas: Vec<SharedString>,
+    pub subject: SharedString,
+    pub author_name: SharedString,
+    pub author_email: SharedString,
+    /// This is a unix timestamp
+    pub commit_timestamp: i64,
+    /// The branches and tags pointing at this commit.
+    pub refs: Vec<SharedString>,
+}
+
+/// What an interactive rebase does with a commit.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum RebaseAction {
+    Pick,
+    /// Keeps the commit with a new message.
+    Reword,
+    /// Melds the commit into the previous one, keeping both messages.
+    Squash,
+    /// Melds the commit into the previous one, discarding its message.
+    Fixup,
+    Drop,
+}
+
+impl RebaseAction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            RebaseAction::Pick => "pick",
+            RebaseAction::Reword => "reword",
+            RebaseAction::Squash => "squash",
+            RebaseAction::Fixup => "fixup",
+            RebaseAction::Drop => "drop",
+        }
+    }
+}
+
+/// A line of an interactive rebase's todo list.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct RebaseStep {
+    pub action: RebaseAction,
+    pub sha: SharedString,
+    /// The new message of a reworded commit.
+    pub message: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub enum RebaseStatus {
+    /// No rebase is in progress.
+    #[default]
+    Idle,
+    /// The rebase stopped, usually because of conflicts, and waits to be continued, skipped
+    /// or aborted.
+    Stopped {
+        /// The commit that couldn't be applied, if known.
+        commit: Option<SharedString>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum SubmoduleStatus {
+    /// The submodule is registered but hasn't been initialized and checked out.
+    Uninitialized,
+    /// The submodule has the commit recorded in the superproject checked out.
+    UpToDate,
+    /// The submodule has a different commit than the recorded one checked out.
+    Modified,
+    /// The submodule's recorded commit has merge conflicts.
+    Conflicted,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Submodule {
+    pub path: RepoPath,
+    /// The commit checked out in the submodule, or the recorded commit when it isn't checked out.
+    pub sha: SharedString,
+    pub status: SubmoduleStatus,
+    /// Whether the submodule's working tree has changes not committed in it, untracked files included.
+    pub has_changes: bool,
+}
+
+/// A working tree of the repository, either the main one or one linked with `git worktree add`.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct GitWorktree {
+    pub path: PathBuf,
+    /// The commit checked out in the worktree, `None` for a bare repository.
+    pub sha: Option<SharedString>,
+    /// The branch checked out in the worktree, `None` when its HEAD is detached.
+    pub branch: Option<SharedString>,
+    pub is_main: bool,
+}
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub struct Remote {
     pub name: SharedString,
@@ -182,6 +310,16 @@ pub trait GitRepository: Send + Sync {
     /// Also returns `None` for symlinks.
     fn load_committed_text(&self, path: RepoPath) -> BoxFuture<Option<String>>;
 
+    /// Returns the contents of an entry in the given revision, or None if the revision has no
+    /// entry for the given path.
+    ///
+    /// Also returns `None` for symlinks.
+    fn load_text_at_revision(
+        &self,
+        path: RepoPath,
+        revision: String,
+    ) -> BoxFuture<Result<Option<String>>>;
+
     fn set_index_text(
         &self,
         path: RepoPath,
@@ -223,8 +361,20 @@ pub trait GitRepository: Send + Sync {
     fn show(&self, commit: String) -> BoxFuture<Result<CommitDetails>>;
 
     fn load_commit(&self, commit: String, cx: AsyncApp) -> BoxFuture<Result<CommitDiff>>;
+
+    /// Returns the commits selected by the options, in topological order with the most recent
+    /// commits first.
+    fn log(&self, options: LogOptions) -> BoxFuture<Result<Vec<LogEntry>>>;
+
     fn blame(&self, path: RepoPath, content: Rope) -> BoxFuture<Result<crate::blame::Blame>>;
 
+    /// Blames the file as it was at the given revision.
+    fn blame_at_revision(
+        &self,
+        path: RepoPath,
+        revision: String,
+    ) -> BoxFuture<Result<crate::blame::Blame>>;
+
     /// Returns the absolute path to the repository. For worktrees, this will be the path to the
     /// worktree's gitdir within the main repository (typically `.git/worktrees/<name>`).
     fn path(&self) -> PathBuf;
@@ -254,10 +404,15 @@ pub trait GitRepository: Send + Sync {
         env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<Result<()>>;
 
+    /// Commits the index. Signing follows the repository's configuration (`commit.gpgSign`,
+    /// `gpg.format`), with passphrase prompts of SSH signing keys sent to `askpass`; GPG keys are
+    /// unlocked by the pinentry program configured for gpg-agent.
     fn commit(
         &self,
         message: SharedString,
         name_and_email: Option<(SharedString, SharedString)>,
+        options: CommitOptions,
+        askpass: AskPassDelegate,
         env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<Result<()>>;
 
@@ -301,6 +456,84 @@ pub trait GitRepository: Send + Sync {
     /// Run git diff
     fn diff(&self, diff: DiffType) -> BoxFuture<Result<String>>;
 
+    /// Saves the local changes to a new stash entry and reverts them in the working tree.
+    fn stash_push(
+        &self,
+        message: Option<String>,
+        include_untracked: bool,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>>;
+
+    /// Returns the stash entries, the most recent one first.
+    fn stash_list(&self) -> BoxFuture<Result<Vec<StashEntry>>>;
+
+    /// Applies the changes of a stash entry to the working tree, keeping the entry.
+    fn stash_apply(&self, index: usize, env: Arc<HashMap<String, String>>)
+    -> BoxFuture<Result<()>>;
+
+    /// Applies the changes of a stash entry to the working tree and removes the entry.
+    fn stash_pop(&self, index: usize, env: Arc<HashMap<String, String>>) -> BoxFuture<Result<()>>;
+
+    fn stash_drop(&self, index: usize, env: Arc<HashMap<String, String>>) -> BoxFuture<Result<()>>;
+
+    /// Returns the changes recorded in a stash entry, including its untracked files, relative to
+    /// the commit the entry was created on.
+    fn stash_show(&self, index: usize) -> BoxFuture<Result<CommitDiff>>;
+
+    /// Rebases the commits between `base` and HEAD by applying the steps in order, oldest commit
+    /// first. Commits of the range that have no step are dropped.
+    fn rebase_interactive(
+        &self,
+        base: String,
+        steps: Vec<RebaseStep>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<RebaseStatus>>;
+
+    /// Resumes a stopped rebase once its conflicts have been resolved and staged.
+    fn rebase_continue(&self, env: Arc<HashMap<String, String>>)
+    -> BoxFuture<Result<RebaseStatus>>;
+
+    /// Resumes a stopped rebase without the commit it stopped at.
+    fn rebase_skip(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<Result<RebaseStatus>>;
+
+    /// Cancels the rebase in progress and restores the branch to its original state.
+    fn rebase_abort(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<Result<()>>;
+
+    fn rebase_status(&self) -> BoxFuture<Result<RebaseStatus>>;
+
+    /// Returns the submodules of the repository, excluding nested ones.
+    fn submodules(&self) -> BoxFuture<Result<Vec<Submodule>>>;
+
+    /// Checks out the recorded commits of the given submodules, or of all of them when `paths`
+    /// is empty, along with their nested submodules. Uninitialized submodules are only checked
+    /// out when `init` is set.
+    fn submodule_update(
+        &self,
+        paths: Vec<RepoPath>,
+        init: bool,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>>;
+
+    /// Returns the working trees of the repository, the main one first.
+    fn worktrees(&self) -> BoxFuture<Result<Vec<GitWorktree>>>;
+
+    /// Creates a linked working tree at `path`. Branches are checked out in the new worktree,
+    /// other revisions with a detached HEAD.
+    fn worktree_add(
+        &self,
+        path: PathBuf,
+        revision: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>>;
+
+    /// Removes a linked working tree, failing if it has local changes unless `force` is set.
+    fn worktree_remove(
+        &self,
+        path: PathBuf,
+        force: bool,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>>;
+
     /// Creates a checkpoint for the repository.
     fn checkpoint(&self) -> BoxFuture<'static, Result<GitRepositoryCheckpoint>>;
 
@@ -330,6 +563,16 @@ pub enum DiffType {
     HeadToWorktree,
 }
 
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommitOptions {
+    /// Replaces the tip of the current branch instead of creating a new commit on top of it.
+    pub amend: bool,
+    /// Adds a `Signed-off-by` trailer for the committer.
+    pub signoff: bool,
+    /// Additional trailers as key and value, e.g. `Co-authored-by` and `Name <email>`.
+    pub trailers: Vec<(SharedString, SharedString)>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema)]
 pub enum PushOptions {
     SetUpstream,
@@ -370,6 +613,110 @@ impl RealGitRepository {
             .context("failed to read git work directory")
             .map(Path::to_path_buf)
     }
+
+    fn run_stash_command(
+        &self,
+        args: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .envs(env.iter())
+                    .args(["stash"])
+                    .args(&args)
+                    .output()
+                    .await?;
+
+                if !output.status.success() {
+                    return Err(anyhow!(
+                        "Failed to run git stash {}:\n{}",
+                        args.first().map(String::as_str).unwrap_or_default(),
+                        String::from_utf8_lossy(&output.stderr)
+                    ));
+                }
+                Ok(())
+            })
+            .boxed()
+    }
+
+    /// Runs a git command in the working directory, returning its output.
+    fn run_command(
+        &self,
+        args: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<String>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .envs(env.iter())
+                    .args(&args)
+                    .output()
+                    .await?;
+
+                if !output.status.success() {
+                    return Err(anyhow!(
+                        "Failed to run git {}:\n{}",
+                        args.iter()
+                            .take_while(|arg| !arg.starts_with('-'))
+                            .map(String::as_str)
+                            .collect::<Vec<_>>()
+                            .join(" "),
+                        String::from_utf8_lossy(&output.stderr)
+                    ));
+                }
+                Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+            })
+            .boxed()
+    }
+
+    /// Runs a `git rebase` command without opening any editor, and reports whether it stopped.
+    fn run_rebase_command(
+        &self,
+        args: Vec<String>,
+        sequence_editor: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<RebaseStatus>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        let dot_git_path = self.path();
+        self.executor
+            .spawn(async move {
+                let mut command = new_smol_command(&git_binary_path);
+                command
+                    .current_dir(&working_directory?)
+                    .envs(env.iter())
+                    .env("GIT_EDITOR", "true")
+                    .args(["rebase"])
+                    .args(&args);
+                if let Some(sequence_editor) = sequence_editor {
+                    command.env("GIT_SEQUENCE_EDITOR", sequence_editor);
+                }
+                let output = command.output().await?;
+
+                let status = read_rebase_status(&dot_git_path).await;
+                if status == RebaseStatus::Idle {
+                    smol::fs::remove_dir_all(dot_git_path.join(REBASE_MESSAGES_DIR))
+                        .await
+                        .ok();
+                    if !output.status.success() {
+                        return Err(anyhow!(
+                            "Failed to run git rebase {}:\n{}",
+                            args.first().map(String::as_str).unwrap_or_default(),
+                            String::from_utf8_lossy(&output.stderr)
+                        ));
+                    }
+                }
+                Ok(status)
+            })
+            .boxed()
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -449,78 +796,67 @@ impl GitRepository for RealGitRepository {
             let parent_sha = lines.next().unwrap().trim().trim_end_matches('\0');
             let changes = parse_git_diff_name_status(lines.next().unwrap_or(""));
 
-            let mut cat_file_process = util::command::new_std_command("git")
-                .current_dir(&working_directory)
-                .args(["--no-optional-locks", "cat-file", "--batch=%(objectsize)"])
-                .stdin(Stdio::piped())
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped())
-                .spawn()
-                .map_err(|e| anyhow!("Failed to start git cat-file process: {e}"))?;
-
-            use std::io::Write as _;
-            let mut files = Vec::<CommitFile>::new();
-            let mut stdin = BufWriter::with_capacity(512, cat_file_process.stdin.take().unwrap());
-            let mut stdout = BufReader::new(cat_file_process.stdout.take().unwrap());
-            let mut info_line = String::new();
-            let mut newline = [b'\0'];
-            for (path, status_code) in changes {
-                match status_code {
-                    StatusCode::Modified => {
-                        writeln!(&mut stdin, "{commit}:{}", path.display())?;
-                        writeln!(&mut stdin, "{parent_sha}:{}", path.display())?;
-                    }
-                    StatusCode::Added => {
-                        writeln!(&mut stdin, "{commit}:{}", path.display())?;
-                    }
-                    StatusCode::Deleted => {
-                        writeln!(&mut stdin, "{parent_sha}:{}", path.display())?;
-                    }
-                    _ => continue,
-                }
-                stdin.flush()?;
+            let files = load_changed_files(&working_directory, parent_sha, &commit, changes)?;
+            Ok(CommitDiff { files })
+        })
+        .boxed()
+    }
 
-                info_line.clear();
-                stdout.read_line(&mut info_line)?;
+    fn log(&self, options: LogOptions) -> BoxFuture<Result<Vec<LogEntry>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let revision = options.revision.as_deref().unwrap_or("HEAD");
+                if revision.starts_with('-') {
+                    return Err(anyhow!("invalid revision {revision:?}"));
+                }
 
-                let len = info_line.trim_end().parse().with_context(|| {
-                    format!("invalid object size output from cat-file {info_line}")
-                })?;
-                let mut text = vec![0; len];
-                stdout.read_exact(&mut text)?;
-                stdout.read_exact(&mut newline)?;
-                let text = String::from_utf8_lossy(&text).to_string();
-
-                let mut old_text = None;
-                let mut new_text = None;
-                match status_code {
-                    StatusCode::Modified => {
-                        info_line.clear();
-                        stdout.read_line(&mut info_line)?;
-                        let len = info_line.trim_end().parse().with_context(|| {
-                            format!("invalid object size output from cat-file {}", info_line)
-                        })?;
-                        let mut parent_text = vec![0; len];
-                        stdout.read_exact(&mut parent_text)?;
-                        stdout.read_exact(&mut newline)?;
-                        old_text = Some(String::from_utf8_lossy(&parent_text).to_string());
-                        new_text = Some(text);
-                    }
-                    StatusCode::Added => new_text = Some(text),
-                    StatusCode::Deleted => old_text = Some(text),
-                    _ => continue,
+                let mut args = vec![
+                    "--no-optional-locks".to_string(),
+                    "log".to_string(),
+                    "--topo-order".to_string(),
+                    format!("--format={LOG_FORMAT}"),
+                    format!("--skip={}", options.skip),
+                    format!("--max-count={}", options.limit),
+                ];
+                if let Some(author) = &options.author {
+                    args.push(format!("--author={author}"));
+                }
+                if let Some(message) = &options.message {
+                    args.push(format!("--grep={message}"));
+                    args.push("--regexp-ignore-case".to_string());
+                }
+                if options.path.is_some() {
+                    // Rewrites the parents to the nearest commits that changed the path, so that
+                    // the history forms a connected graph.
+                    args.push("--parents".to_string());
+                }
+                args.push(revision.to_string());
+                args.push("--".to_string());
+                if let Some(path) = &options.path {
+                    args.push(path.to_str().context("invalid path")?.to_string());
                 }
 
-                files.push(CommitFile {
-                    path: path.into(),
-                    old_text,
-                    new_text,
-                })
-            }
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .args(args)
+                    .output()
+                    .await?;
+                if !output.status.success() {
+                    return Err(anyhow!(
+                        "Failed to load the commit history:\n{}",
+                        String::from_utf8_lossy(&output.stderr)
+                    ));
+                }
 
-            Ok(CommitDiff { files })
-        })
-        .boxed()
+                String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .filter(|line| !line.is_empty())
+                    .map(parse_log_entry)
+                    .collect()
+            })
+            .boxed()
     }
 
     fn reset(
@@ -635,6 +971,31 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
+    fn load_text_at_revision(
+        &self,
+        path: RepoPath,
+        revision: String,
+    ) -> BoxFuture<Result<Option<String>>> {
+        let repo = self.repository.clone();
+        self.executor
+            .spawn(async move {
+                let repo = repo.lock();
+                let tree = repo
+                    .revparse_single(&revision)
+                    .with_context(|| format!("resolving revision {revision:?}"))?
+                    .peel_to_tree()?;
+                let Ok(entry) = tree.get_path(&path) else {
+                    return Ok(None);
+                };
+                if entry.filemode() == i32::from(git2::FileMode::Link) {
+                    return Ok(None);
+                }
+                let content = repo.find_blob(entry.id())?.content().to_owned();
+                Ok(Some(String::from_utf8(content)?))
+            })
+            .boxed()
+    }
+
     fn set_index_text(
         &self,
         path: RepoPath,
@@ -871,6 +1232,31 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
+    fn blame_at_revision(
+        &self,
+        path: RepoPath,
+        revision: String,
+    ) -> BoxFuture<Result<crate::blame::Blame>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+
+        const REMOTE_NAME: &str = "origin";
+        let remote_url = self.remote_url(REMOTE_NAME);
+
+        self.executor
+            .spawn(async move {
+                crate::blame::Blame::for_path_at_revision(
+                    &git_binary_path,
+                    &working_directory?,
+                    &path,
+                    &revision,
+                    remote_url,
+                )
+                .await
+            })
+            .boxed()
+    }
+
     fn diff(&self, diff: DiffType) -> BoxFuture<Result<String>> {
         let working_directory = self.working_directory();
         let git_binary_path = self.git_binary_path.clone();
@@ -899,6 +1285,150 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
+    fn stash_push(
+        &self,
+        message: Option<String>,
+        include_untracked: bool,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        let mut args = vec!["push".to_string(), "--quiet".to_string()];
+        if include_untracked {
+            args.push("--include-untracked".to_string());
+        }
+        if let Some(message) = message {
+            args.push("--message".to_string());
+            args.push(message);
+        }
+        self.run_stash_command(args, env)
+    }
+
+    fn stash_list(&self) -> BoxFuture<Result<Vec<StashEntry>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .args(["stash", "list", "--format=%H%x00%ct%x00%gs"])
+                    .output()
+                    .await?;
+
+                if !output.status.success() {
+                    return Err(anyhow!(
+                        "Failed to list stashes:\n{}",
+                        String::from_utf8_lossy(&output.stderr)
+                    ));
+                }
+
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                stdout
+                    .lines()
+                    .enumerate()
+                    .map(|(index, line)| {
+                        let mut fields = line.splitn(3, '\0');
+                        let (Some(sha), Some(timestamp), Some(message)) =
+                            (fields.next(), fields.next(), fields.next())
+                        else {
+                            return Err(anyhow!("invalid stash list entry: {line}"));
+                        };
+                        Ok(StashEntry {
+                            index,
+                            sha: sha.to_string().into(),
+                            message: message.to_string().into(),
+                            commit_timestamp: timestamp.parse()?,
+                        })
+                    })
+                    .collect()
+            })
+            .boxed()
+    }
+
+    fn stash_apply(
+        &self,
+        index: usize,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        self.run_stash_command(
+            vec!["apply".to_string(), "--quiet".to_string(), stash_ref(index)],
+            env,
+        )
+    }
+
+    fn stash_pop(&self, index: usize, env: Arc<HashMap<String, String>>) -> BoxFuture<Result<()>> {
+        self.run_stash_command(
+            vec!["pop".to_string(), "--quiet".to_string(), stash_ref(index)],
+            env,
+        )
+    }
+
+    fn stash_drop(&self, index: usize, env: Arc<HashMap<String, String>>) -> BoxFuture<Result<()>> {
+        self.run_stash_command(
+            vec!["drop".to_string(), "--quiet".to_string(), stash_ref(index)],
+            env,
+        )
+    }
+
+    fn stash_show(&self, index: usize) -> BoxFuture<Result<CommitDiff>> {
+        let working_directory = self.working_directory();
+        self.executor
+            .spawn(async move {
+                let working_directory = working_directory?;
+                let stash = stash_ref(index);
+                let base = format!("{stash}^1");
+
+                let diff_output = new_std_command("git")
+                    .current_dir(&working_directory)
+                    .args([
+                        "--no-optional-locks",
+                        "diff",
+                        "--no-renames",
+                        "--name-status",
+                        "-z",
+                    ])
+                    .args([&base, &stash])
+                    .stdin(Stdio::null())
+                    .output()
+                    .map_err(|e| anyhow!("Failed to start git diff process: {e}"))?;
+                if !diff_output.status.success() {
+                    return Err(anyhow!(
+                        "Failed to show stash:\n{}",
+                        String::from_utf8_lossy(&diff_output.stderr)
+                    ));
+                }
+                let diff_stdout = String::from_utf8_lossy(&diff_output.stdout);
+                let changes = parse_git_diff_name_status(&diff_stdout);
+                let mut files = load_changed_files(&working_directory, &base, &stash, changes)?;
+
+                // Untracked files are recorded in the third parent of the stash commit, which
+                // only exists when the stash was created with `--include-untracked`.
+                let untracked = format!("{stash}^3");
+                let untracked_output = new_std_command("git")
+                    .current_dir(&working_directory)
+                    .args(["--no-optional-locks", "ls-tree", "-r", "-z", "--name-only"])
+                    .arg(&untracked)
+                    .stdin(Stdio::null())
+                    .output()
+                    .map_err(|e| anyhow!("Failed to start git ls-tree process: {e}"))?;
+                if untracked_output.status.success() {
+                    let untracked_stdout = String::from_utf8_lossy(&untracked_output.stdout);
+                    let changes = untracked_stdout
+                        .split('\0')
+                        .filter(|path| !path.is_empty())
+                        .map(|path| (Path::new(path), StatusCode::Added));
+                    files.extend(load_changed_files(
+                        &working_directory,
+                        &base,
+                        &untracked,
+                        changes,
+                    )?);
+                }
+
+                files.sort_by(|a, b| a.path.cmp(&b.path));
+                Ok(CommitDiff { files })
+            })
+            .boxed()
+    }
+
     fn stage_paths(
         &self,
         paths: Vec<RepoPath>,
@@ -964,33 +1494,68 @@ impl GitRepository for RealGitRepository {
         &self,
         message: SharedString,
         name_and_email: Option<(SharedString, SharedString)>,
+        options: CommitOptions,
+        ask_pass: AskPassDelegate,
         env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<Result<()>> {
         let working_directory = self.working_directory();
-        self.executor
-            .spawn(async move {
-                let mut cmd = new_smol_command("git");
-                cmd.current_dir(&working_directory?)
-                    .envs(env.iter())
-                    .args(["commit", "--quiet", "-m"])
-                    .arg(&message.to_string())
-                    .arg("--cleanup=strip");
+        let git_binary_path = self.git_binary_path.clone();
+        let executor = self.executor.clone();
+        async move {
+            let working_directory = working_directory?;
+            let signs_with_ssh_key = {
+                let git =
+                    GitBinary::new(git_binary_path, working_directory.clone(), executor.clone())
+                        .envs(HashMap::clone(&env));
+                git.run(["config", "--bool", "commit.gpgSign"])
+                    .await
+                    .is_ok_and(|gpg_sign| gpg_sign == "true")
+                    && git
+                        .run(["config", "gpg.format"])
+                        .await
+                        .is_ok_and(|format| format == "ssh")
+            };
 
-                if let Some((name, email)) = name_and_email {
-                    cmd.arg("--author").arg(&format!("{name} <{email}>"));
-                }
+            let mut cmd = new_smol_command("git");
+            cmd.current_dir(&working_directory)
+                .envs(env.iter())
+                .args(["commit", "--quiet", "-m"])
+                .arg(&message.to_string())
+                .arg("--cleanup=strip")
+                .args(commit_option_args(&options))
+                .stdin(smol::process::Stdio::null())
+                .stdout(smol::process::Stdio::piped())
+                .stderr(smol::process::Stdio::piped());
+
+            if let Some((name, email)) = name_and_email {
+                cmd.arg("--author").arg(&format!("{name} <{email}>"));
+            }
 
+            // Only SSH signing keys ask for their passphrases through askpass, GPG keys are unlocked with the pinentry
+            // program of gpg-agent. Hooks may run for a while before signing, so the prompt is awaited for as long
+            // as the commit runs.
+            if signs_with_ssh_key && !env.contains_key("GIT_ASKPASS") {
+                let ask_pass = AskPassSession::new(&executor, ask_pass)
+                    .await?
+                    .without_connection_timeout();
+                cmd.env("SSH_ASKPASS", ask_pass.script_path())
+                    .env("SSH_ASKPASS_REQUIRE", "force");
+                let git_process = cmd.spawn()?;
+                run_askpass_command(ask_pass, git_process)
+                    .await
+                    .map_err(|error| anyhow!("Failed to commit:\n{error}"))?;
+            } else {
                 let output = cmd.output().await?;
-
                 if !output.status.success() {
                     return Err(anyhow!(
                         "Failed to commit:\n{}",
                         String::from_utf8_lossy(&output.stderr)
                     ));
                 }
-                Ok(())
-            })
-            .boxed()
+            }
+            Ok(())
+        }
+        .boxed()
     }
 
     fn push(
@@ -1186,6 +1751,196 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
+    fn rebase_interactive(
+        &self,
+        base: String,
+        steps: Vec<RebaseStep>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<RebaseStatus>> {
+        if base.starts_with('-') {
+            return future::ready(Err(anyhow!("invalid revision {base:?}"))).boxed();
+        }
+        let messages_dir = self.path().join(REBASE_MESSAGES_DIR);
+        let todo_path = messages_dir.join("git-rebase-todo");
+        let todo = rebase_todo(&steps, &self.git_binary_path, &messages_dir);
+        let write_files = self.executor.spawn({
+            let todo_path = todo_path.clone();
+            async move {
+                smol::fs::create_dir_all(&messages_dir).await?;
+                for (ix, step) in steps.iter().enumerate() {
+                    if let Some(message) = &step.message {
+                        smol::fs::write(messages_dir.join(rebase_message_file_name(ix)), message)
+                            .await?;
+                    }
+                }
+                smol::fs::write(&todo_path, todo).await?;
+                anyhow::Ok(())
+            }
+        });
+        async move {
+            write_files.await?;
+            self.run_rebase_command(
+                vec![
+                    "--interactive".to_string(),
+                    "--no-autosquash".to_string(),
+                    base,
+                ],
+                Some(format!("cp {}", shell_quote(&todo_path))),
+                env,
+            )
+            .await
+        }
+        .boxed()
+    }
+
+    fn rebase_continue(
+        &self,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<RebaseStatus>> {
+        self.run_rebase_command(vec!["--continue".to_string()], None, env)
+    }
+
+    fn rebase_skip(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<Result<RebaseStatus>> {
+        self.run_rebase_command(vec!["--skip".to_string()], None, env)
+    }
+
+    fn rebase_abort(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<Result<()>> {
+        let rebase = self.run_rebase_command(vec!["--abort".to_string()], None, env);
+        async move {
+            rebase.await?;
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn rebase_status(&self) -> BoxFuture<Result<RebaseStatus>> {
+        let dot_git_path = self.path();
+        self.executor
+            .spawn(async move { Ok(read_rebase_status(&dot_git_path).await) })
+            .boxed()
+    }
+
+    fn submodules(&self) -> BoxFuture<Result<Vec<Submodule>>> {
+        let status = self.run_command(
+            vec!["submodule".to_string(), "status".to_string()],
+            Arc::default(),
+        );
+        async move {
+            let mut submodules = status
+                .await?
+                .lines()
+                .map(parse_submodule_status)
+                .collect::<Result<Vec<_>>>()?;
+            // `git submodule status` only compares the commits, the changes are found in each submodule.
+            for submodule in &mut submodules {
+                if submodule.status == SubmoduleStatus::Uninitialized {
+                    continue;
+                }
+                let changes = self
+                    .run_command(
+                        vec![
+                            "-C".to_string(),
+                            submodule
+                                .path
+                                .to_unix_style()
+                                .to_string_lossy()
+                                .into_owned(),
+                            "status".to_string(),
+                            "--porcelain".to_string(),
+                        ],
+                        Arc::default(),
+                    )
+                    .await?;
+                submodule.has_changes = !changes.is_empty();
+            }
+            Ok(submodules)
+        }
+        .boxed()
+    }
+
+    fn submodule_update(
+        &self,
+        paths: Vec<RepoPath>,
+        init: bool,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        let mut args = vec!["submodule".to_string(), "update".to_string()];
+        if init {
+            args.push("--init".to_string());
+        }
+        args.push("--recursive".to_string());
+        args.push("--".to_string());
+        args.extend(
+            paths
+                .iter()
+                .map(|path| path.to_unix_style().to_string_lossy().into_owned()),
+        );
+        let update = self.run_command(args, env);
+        async move {
+            update.await?;
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn worktrees(&self) -> BoxFuture<Result<Vec<GitWorktree>>> {
+        let list = self.run_command(
+            vec![
+                "worktree".to_string(),
+                "list".to_string(),
+                "--porcelain".to_string(),
+            ],
+            Arc::default(),
+        );
+        async move { parse_worktree_list(&list.await?) }.boxed()
+    }
+
+    fn worktree_add(
+        &self,
+        path: PathBuf,
+        revision: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        if revision.starts_with('-') {
+            return future::ready(Err(anyhow!("invalid revision {revision:?}"))).boxed();
+        }
+        let add = self.run_command(
+            vec![
+                "worktree".to_string(),
+                "add".to_string(),
+                "--".to_string(),
+                path.to_string_lossy().into_owned(),
+                revision,
+            ],
+            env,
+        );
+        async move {
+            add.await?;
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn worktree_remove(
+        &self,
+        path: PathBuf,
+        force: bool,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        let mut args = vec!["worktree".to_string(), "remove".to_string()];
+        if force {
+            args.push("--force".to_string());
+        }
+        args.push("--".to_string());
+        args.push(path.to_string_lossy().into_owned());
+        let remove = self.run_command(args, env);
+        async move {
+            remove.await?;
+            Ok(())
+        }
+        .boxed()
+    }
+
     fn checkpoint(&self) -> BoxFuture<'static, Result<GitRepositoryCheckpoint>> {
         let working_directory = self.working_directory();
         let git_binary_path = self.git_binary_path.clone();
@@ -1329,6 +2084,249 @@ impl GitRepository for RealGitRepository {
     }
 }
 
+/// The fields of a [`LogEntry`], separated by NUL bytes.
+const LOG_FORMAT: &str = "%H%x00%P%x00%ct%x00%an%x00%ae%x00%D%x00%s";
+
+fn parse_log_entry(line: &str) -> Result<LogEntry> {
+    let mut fields = line.splitn(7, '\0');
+    let (
+        Some(sha),
+        Some(parent_shas),
+        Some(timestamp),
+        Some(author_name),
+        Some(author_email),
+        Some(refs),
+        Some(subject),
+    ) = (
+        fields.next(),
+        fields.next(),
+        fields.next(),
+        fields.next(),
+        fields.next(),
+        fields.next(),
+        fields.next(),
+    )
+    else {
+        return Err(anyhow!("invalid log entry: {line}"));
+    };
+    Ok(LogEntry {
+        sha: sha.to_string().into(),
+        parent_shas: parent_shas
+            .split_whitespace()
+            .map(|sha| sha.to_string().into())
+            .collect(),
+        subject: subject.to_string().into(),
+        author_name: author_name.to_string().into(),
+        author_email: author_email.to_string().into(),
+        commit_timestamp: timestamp.parse()?,
+        refs: refs
+            .split(", ")
+            .filter_map(|decoration| {
+                let name = decoration
+                    .strip_prefix("HEAD -> ")
+                    .or_else(|| decoration.strip_prefix("tag: "))
+                    .unwrap_or(decoration);
+                (!name.is_empty() && name != "HEAD").then(|| name.to_string().into())
+            })
+            .collect(),
+    })
+}
+
+/// The directory within the git dir holding the todo list and the reworded messages of an
+/// interactive rebase started by Zed.
+const REBASE_MESSAGES_DIR: &str = "zed-rebase";
+
+/// Builds the todo list of an interactive rebase. Rewords are expressed as a pick followed by an
+/// amend, so that git doesn't need to open an editor for the new message.
+fn rebase_todo(steps: &[RebaseStep], git_binary_path: &Path, messages_dir: &Path) -> String {
+    let mut todo = String::new();
+    for (ix, step) in steps.iter().enumerate() {
+        match step.action {
+            RebaseAction::Reword => {
+                todo.push_str(&format!("pick {}\n", step.sha));
+                if step.message.is_some() {
+                    todo.push_str(&format!(
+                        "exec {} commit --amend --only --allow-empty --no-verify --quiet -F {}\n",
+                        shell_quote(git_binary_path),
+                        shell_quote(&messages_dir.join(rebase_message_file_name(ix)))
+                    ));
+                }
+            }
+            action => todo.push_str(&format!("{} {}\n", action.name(), step.sha)),
+        }
+    }
+    todo
+}
+
+fn rebase_message_file_name(step_ix: usize) -> String {
+    format!("message-{step_ix}")
+}
+
+/// Quotes a path for the shell that git uses to run editors and `exec` commands.
+fn shell_quote(path: &Path) -> String {
+    format!("'{}'", path.to_string_lossy().replace('\'', "'\\''"))
+}
+
+async fn read_rebase_status(dot_git_path: &Path) -> RebaseStatus {
+    let rebase_merge_path = dot_git_path.join("rebase-merge");
+    if smol::fs::metadata(&rebase_merge_path).await.is_ok() {
+        let commit = smol::fs::read_to_string(rebase_merge_path.join("stopped-sha"))
+            .await
+            .ok()
+            .map(|sha| sha.trim().to_string())
+            .filter(|sha| !sha.is_empty())
+            .map(SharedString::from);
+        RebaseStatus::Stopped { commit }
+    } else if smol::fs::metadata(dot_git_path.join("rebase-apply"))
+        .await
+        .is_ok()
+    {
+        RebaseStatus::Stopped { commit: None }
+    } else {
+        RebaseStatus::Idle
+    }
+}
+
+/// Parses a line of `git submodule status`, such as `+<sha> path/to/submodule (v1.0-2-g<sha>)`.
+fn parse_submodule_status(line: &str) -> Result<Submodule> {
+    let status = match line.chars().next() {
+        Some(' ') => SubmoduleStatus::UpToDate,
+        Some('-') => SubmoduleStatus::Uninitialized,
+        Some('+') => SubmoduleStatus::Modified,
+        Some('U') => SubmoduleStatus::Conflicted,
+        _ => return Err(anyhow!("invalid submodule status: {line}")),
+    };
+    let (sha, path) = line[1..]
+        .split_once(' ')
+        .with_context(|| format!("invalid submodule status: {line}"))?;
+    // Checked out submodules are followed by the description of their commit.
+    let path = match path.rfind(" (") {
+        Some(ix) if path.ends_with(')') => &path[..ix],
+        _ => path,
+    };
+    Ok(Submodule {
+        path: RepoPath::from_str(path),
+        sha: sha.to_string().into(),
+        status,
+        has_changes: false,
+    })
+}
+
+/// Parses the output of `git worktree list --porcelain`, where each worktree is a block of
+/// attribute lines and the main worktree comes first.
+fn parse_worktree_list(output: &str) -> Result<Vec<GitWorktree>> {
+    let mut worktrees = Vec::new();
+    for block in output.split("\n\n") {
+        let mut lines = block.lines();
+        let Some(first_line) = lines.next().filter(|line| !line.is_empty()) else {
+            continue;
+        };
+        let path = first_line
+            .strip_prefix("worktree ")
+            .with_context(|| format!("invalid worktree list entry: {first_line}"))?;
+        let mut worktree = GitWorktree {
+            path: PathBuf::from(path),
+            sha: None,
+            branch: None,
+            is_main: worktrees.is_empty(),
+        };
+        for line in lines {
+            if let Some(sha) = line.strip_prefix("HEAD ") {
+                worktree.sha = Some(sha.to_string().into());
+            } else if let Some(branch) = line.strip_prefix("branch ") {
+                let branch = branch.strip_prefix("refs/heads/").unwrap_or(branch);
+                worktree.branch = Some(branch.to_string().into());
+            }
+        }
+        worktrees.push(worktree);
+    }
+    Ok(worktrees)
+}
+
+fn stash_ref(index: usize) -> String {
+    format!("stash@{{{index}}}")
+}
+
+/// Reads the old and new contents of the given changes between two revisions.
+fn load_changed_files<'a>(
+    working_directory: &Path,
+    old_rev: &str,
+    new_rev: &str,
+    changes: impl IntoIterator<Item = (&'a Path, StatusCode)>,
+) -> Result<Vec<CommitFile>> {
+    let mut cat_file_process = util::command::new_std_command("git")
+        .current_dir(&working_directory)
+        .args(["--no-optional-locks", "cat-file", "--batch=%(objectsize)"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| anyhow!("Failed to start git cat-file process: {e}"))?;
+
+    use std::io::Write as _;
+    let mut files = Vec::<CommitFile>::new();
+    let mut stdin = BufWriter::with_capacity(512, cat_file_process.stdin.take().unwrap());
+    let mut stdout = BufReader::new(cat_file_process.stdout.take().unwrap());
+    let mut info_line = String::new();
+    let mut newline = [b'\0'];
+    for (path, status_code) in changes {
+        match status_code {
+            StatusCode::Modified => {
+                writeln!(&mut stdin, "{new_rev}:{}", path.display())?;
+                writeln!(&mut stdin, "{old_rev}:{}", path.display())?;
+            }
+            StatusCode::Added => {
+                writeln!(&mut stdin, "{new_rev}:{}", path.display())?;
+            }
+            StatusCode::Deleted => {
+                writeln!(&mut stdin, "{old_rev}:{}", path.display())?;
+            }
+            _ => continue,
+        }
+        stdin.flush()?;
+
+        info_line.clear();
+        stdout.read_line(&mut info_line)?;
+
+        let len = info_line
+            .trim_end()
+            .parse()
+            .with_context(|| format!("invalid object size output from cat-file {info_line}"))?;
+        let mut text = vec![0; len];
+        stdout.read_exact(&mut text)?;
+        stdout.read_exact(&mut newline)?;
+        let text = String::from_utf8_lossy(&text).to_string();
+
+        let mut old_text = None;
+        let mut new_text = None;
+        match status_code {
+            StatusCode::Modified => {
+                info_line.clear();
+                stdout.read_line(&mut info_line)?;
+                let len = info_line.trim_end().parse().with_context(|| {
+                    format!("invalid object size output from cat-file {}", info_line)
+                })?;
+                let mut parent_text = vec![0; len];
+                stdout.read_exact(&mut parent_text)?;
+                stdout.read_exact(&mut newline)?;
+                old_text = Some(String::from_utf8_lossy(&parent_text).to_string());
+                new_text = Some(text);
+            }
+            StatusCode::Added => new_text = Some(text),
+            StatusCode::Deleted => old_text = Some(text),
+            _ => continue,
+        }
+
+        files.push(CommitFile {
+            path: path.into(),
+            old_text,
+            new_text,
+        })
+    }
+
+    Ok(files)
+}
+
 fn git_status_args(path_prefixes: &[RepoPath]) -> Vec<OsString> {
     let mut args = vec![
         OsString::from("--no-optional-locks"),
@@ -1461,6 +2459,20 @@ struct GitBinaryCommandError {
     status: ExitStatus,
 }
 
+fn commit_option_args(options: &CommitOptions) -> Vec<String> {
+    let mut args = Vec::new();
+    if options.amend {
+        args.push("--amend".to_string());
+    }
+    if options.signoff {
+        args.push("--signoff".to_string());
+    }
+    for (key, value) in &options.trailers {
+        args.push(format!("--trailer={key}: {value}"));
+    }
+    args
+}
+
 async fn run_git_command(
     env: Arc<HashMap<String, String>>,
     ask_pass: AskPassDelegate,
@@ -1772,6 +2784,8 @@ mod tests {
         repo.commit(
             "Initial commit".into(),
             None,
+            CommitOptions::default(),
+            AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
             Arc::new(checkpoint_author_envs()),
         )
         .await
@@ -1800,6 +2814,8 @@ mod tests {
         repo.commit(
             "Commit after checkpoint".into(),
             None,
+            CommitOptions::default(),
+            AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
             Arc::new(checkpoint_author_envs()),
         )
         .await
@@ -1841,6 +2857,90 @@ mod tests {
             .unwrap_err();
     }
 
+    #[gpui::test]
+    async fn test_commit_with_options(cx: &mut TestAppContext) {
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        smol::fs::write(repo_dir.path().join("file"), "initial")
+            .await
+            .unwrap();
+
+        let repo =
+            RealGitRepository::new(&repo_dir.path().join(".git"), None, cx.executor()).unwrap();
+        repo.stage_paths(
+            vec![RepoPath::from_str("file")],
+            Arc::new(HashMap::default()),
+        )
+        .await
+        .unwrap();
+        repo.commit(
+            "Initial commit".into(),
+            None,
+            CommitOptions::default(),
+            AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
+            Arc::new(checkpoint_author_envs()),
+        )
+        .await
+        .unwrap();
+
+        smol::fs::write(repo_dir.path().join("file"), "amended")
+            .await
+            .unwrap();
+        repo.stage_paths(
+            vec![RepoPath::from_str("file")],
+            Arc::new(HashMap::default()),
+        )
+        .await
+        .unwrap();
+        repo.commit(
+            "Amended commit".into(),
+            None,
+            CommitOptions {
+                amend: true,
+                signoff: true,
+                trailers: vec![("Co-authored-by".into(), "Someone <someone@zed.dev>".into())],
+            },
+            AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {
+                panic!("committing without signing should not ask for a password")
+            }),
+            Arc::new(checkpoint_author_envs()),
+        )
+        .await
+        .unwrap();
+
+        let git_repo = git2::Repository::open(repo_dir.path()).unwrap();
+        let head = git_repo.head().unwrap().peel_to_commit().unwrap();
+        let message = head.message().unwrap();
+        assert!(message.starts_with("Amended commit\n\n"), "{message:?}");
+        assert!(
+            message.contains("\nCo-authored-by: Someone <someone@zed.dev>\n"),
+            "{message:?}"
+        );
+        assert!(
+            message.contains("\nSigned-off-by: Zed <hi@zed.dev>\n"),
+            "{message:?}"
+        );
+        assert_eq!(
+            head.parent_count(),
+            0,
+            "The initial commit should be amended"
+        );
+        assert_eq!(
+            head.tree()
+                .unwrap()
+                .get_name("file")
+                .unwrap()
+                .to_object(&git_repo)
+                .unwrap()
+                .peel_to_blob()
+                .unwrap()
+                .content(),
+            b"amended"
+        );
+    }
+
     #[gpui::test]
     async fn test_checkpoint_empty_repo(cx: &mut TestAppContext) {
         cx.executor().allow_parking();
@@ -1913,6 +3013,60 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_submodules_with_changes(cx: &mut TestAppContext) {
+        cx.executor().allow_parking();
+
+        let git = |dir: &Path, args: &[&str]| {
+            let output = std::process::Command::new("git")
+                .current_dir(dir)
+                .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+                .args(["-c", "protocol.file.allow=always"])
+                .args(args)
+                .output()
+                .unwrap();
+            assert!(
+                output.status.success(),
+                "git {args:?} failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        };
+        let library_dir = tempfile::tempdir().unwrap();
+        git(library_dir.path(), &["init"]);
+        std::fs::write(library_dir.path().join("lib.txt"), "lib").unwrap();
+        git(library_dir.path(), &["add", "."]);
+        git(library_dir.path(), &["commit", "-m", "Initial commit"]);
+        let library_url = library_dir.path().to_str().unwrap();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git(repo_dir.path(), &["init"]);
+        for path in ["changed", "clean", "untracked"] {
+            git(repo_dir.path(), &["submodule", "add", library_url, path]);
+        }
+        git(repo_dir.path(), &["commit", "-m", "Add submodules"]);
+        std::fs::write(repo_dir.path().join("changed/lib.txt"), "changed").unwrap();
+        std::fs::write(repo_dir.path().join("untracked/new.txt"), "new").unwrap();
+
+        let repo =
+            RealGitRepository::new(&repo_dir.path().join(".git"), None, cx.executor()).unwrap();
+        let submodules = repo.submodules().await.unwrap();
+        assert_eq!(
+            submodules
+                .iter()
+                .map(|submodule| (
+                    submodule.path.to_string_lossy().into_owned(),
+                    submodule.status,
+                    submodule.has_changes
+                ))
+                .collect::<Vec<_>>(),
+            [
+                ("changed".to_string(), SubmoduleStatus::UpToDate, true),
+                ("clean".to_string(), SubmoduleStatus::UpToDate, false),
+                ("untracked".to_string(), SubmoduleStatus::UpToDate, true),
+            ]
+        );
+    }
+
     #[test]
     fn test_branches_parsing() {
         // suppress "help: octal escapes are not supported, `\0` is always null"
@@ -1940,6 +3094,174 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_log_entry_parsing() {
+        let entry = parse_log_entry(
+            "1111111111111111111111111111111111111111\x002222222222222222222222222222222222222222 3333333333333333333333333333333333333333\x001733187470\x00Jane Doe\x00jane@example.com\x00HEAD -> main, origin/main, tag: v1.0\x00Merge branch 'feature'",
+        )
+        .unwrap();
+        assert_eq!(
+            entry,
+            LogEntry {
+                sha: "1111111111111111111111111111111111111111".into(),
+                parent_shas: vec![
+                    "2222222222222222222222222222222222222222".into(),
+                    "3333333333333333333333333333333333333333".into(),
+                ],
+                subject: "Merge branch 'feature'".into(),
+                author_name: "Jane Doe".into(),
+                author_email: "jane@example.com".into(),
+                commit_timestamp: 1733187470,
+                refs: vec!["main".into(), "origin/main".into(), "v1.0".into()],
+            }
+        );
+
+        let root = parse_log_entry(
+            "1111111111111111111111111111111111111111\x00\x001733187470\x00Jane Doe\x00jane@example.com\x00\x00Initial commit",
+        )
+        .unwrap();
+        assert!(root.parent_shas.is_empty());
+        assert!(root.refs.is_empty());
+        assert!(parse_log_entry("1111111111111111111111111111111111111111").is_err());
+    }
+
+    #[test]
+    fn test_rebase_todo() {
+        let todo = rebase_todo(
+            &[
+                RebaseStep {
+                    action: RebaseAction::Pick,
+                    sha: "1111111".into(),
+                    message: None,
+                },
+                RebaseStep {
+                    action: RebaseAction::Fixup,
+                    sha: "2222222".into(),
+                    message: None,
+                },
+                RebaseStep {
+                    action: RebaseAction::Reword,
+                    sha: "3333333".into(),
+                    message: Some("New message".into()),
+                },
+                RebaseStep {
+                    action: RebaseAction::Drop,
+                    sha: "4444444".into(),
+                    message: None,
+                },
+            ],
+            Path::new("/usr/bin/git"),
+            Path::new("/repo/.git/it's"),
+        );
+        assert_eq!(
+            todo,
+            concat!(
+                "pick 1111111\n",
+                "fixup 2222222\n",
+                "pick 3333333\n",
+                "exec '/usr/bin/git' commit --amend --only --allow-empty --no-verify --quiet -F '/repo/.git/it'\\''s/message-2'\n",
+                "drop 4444444\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_commit_option_args() {
+        assert!(commit_option_args(&CommitOptions::default()).is_empty());
+        assert_eq!(
+            commit_option_args(&CommitOptions {
+                amend: true,
+                signoff: true,
+                trailers: vec![(
+                    "Co-authored-by".into(),
+                    "Jane Doe <jane@example.com>".into()
+                )],
+            }),
+            [
+                "--amend",
+                "--signoff",
+                "--trailer=Co-authored-by: Jane Doe <jane@example.com>",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_submodule_status_parsing() {
+        let output = concat!(
+            " 1111111111111111111111111111111111111111 vendor/lib (v1.0)\n",
+            "-2222222222222222222222222222222222222222 docs/theme\n",
+            "+3333333333333333333333333333333333333333 path with spaces (heads/main)\n",
+            "U4444444444444444444444444444444444444444 conflicted\n",
+        );
+        let submodules = output
+            .lines()
+            .map(parse_submodule_status)
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(
+            submodules,
+            [
+                Submodule {
+                    path: RepoPath::from_str("vendor/lib"),
+                    sha: "1111111111111111111111111111111111111111".into(),
+                    status: SubmoduleStatus::UpToDate,
+                    has_changes: false,
+                },
+                Submodule {
+                    path: RepoPath::from_str("docs/theme"),
+                    sha: "2222222222222222222222222222222222222222".into(),
+                    status: SubmoduleStatus::Uninitialized,
+                    has_changes: false,
+                },
+                Submodule {
+                    path: RepoPath::from_str("path with spaces"),
+                    sha: "3333333333333333333333333333333333333333".into(),
+                    status: SubmoduleStatus::Modified,
+                    has_changes: false,
+                },
+                Submodule {
+                    path: RepoPath::from_str("conflicted"),
+                    sha: "4444444444444444444444444444444444444444".into(),
+                    status: SubmoduleStatus::Conflicted,
+                    has_changes: false,
+                },
+            ]
+        );
+        assert!(parse_submodule_status("?1111 unknown").is_err());
+    }
+
+    #[test]
+    fn test_worktree_list_parsing() {
+        let output = concat!(
+            "worktree /repo\n",
+            "HEAD 1111111111111111111111111111111111111111\n",
+            "branch refs/heads/main\n",
+            "\n",
+            "worktree /repo-review\n",
+            "HEAD 2222222222222222222222222222222222222222\n",
+            "detached\n",
+            "locked\n",
+            "\n",
+        );
+        assert_eq!(
+            parse_worktree_list(output).unwrap(),
+            [
+                GitWorktree {
+                    path: PathBuf::from("/repo"),
+                    sha: Some("1111111111111111111111111111111111111111".into()),
+                    branch: Some("main".into()),
+                    is_main: true,
+                },
+                GitWorktree {
+                    path: PathBuf::from("/repo-review"),
+                    sha: Some("2222222222222222222222222222222222222222".into()),
+                    branch: None,
+                    is_main: false,
+                },
+            ]
+        );
+    }
+
     impl RealGitRepository {
         /// Force a Git garbage collection on the repository.
         fn gc(&self) -> BoxFuture<Result<()>> {
@@ -1,8 +1,10 @@
-use crate::{commit_tooltip::CommitTooltip, commit_view::CommitView};
-use editor::{BlameRenderer, Editor};
+use crate::{
+    commit_tooltip::CommitTooltip, commit_view::CommitView, file_revision_view::FileRevisionView,
+};
+use editor::{BlameRenderer, BlameRevision, Editor};
 use git::{
     blame::{BlameEntry, ParsedCommitMessage},
-    repository::CommitSummary,
+    repository::{CommitSummary, RepoPath},
 };
 use gpui::{
     AnyElement, App, AppContext as _, ClipboardItem, Element as _, Entity, Hsla,
@@ -65,10 +67,14 @@ impl BlameRenderer for GitBlameRenderer {
                 .on_mouse_down(MouseButton::Right, {
                     let blame_entry = blame_entry.clone();
                     let details = details.clone();
+                    let repository = repository.clone();
+                    let workspace = workspace.clone();
                     move |event, window, cx| {
                         deploy_blame_entry_context_menu(
                             &blame_entry,
                             details.as_ref(),
+                            repository.clone(),
+                            workspace.clone(),
                             editor.clone(),
                             event.position,
                             window,
@@ -186,30 +192,82 @@ impl BlameRenderer for GitBlameRenderer {
             cx,
         )
     }
+
+    fn open_blame_revision(
+        &self,
+        revision: BlameRevision,
+        row: u32,
+        editor: Entity<Editor>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        // Deferred, as this is called while the editor is being updated.
+        window.defer(cx, move |window, cx| {
+            // Step through the history in place when already viewing a revision of the file.
+            let revision_view = workspace
+                .read_with(cx, |workspace, cx| {
+                    workspace
+                        .items_of_type::<FileRevisionView>(cx)
+                        .find(|view| view.read(cx).editor() == &editor)
+                })
+                .ok()
+                .flatten();
+            match revision_view {
+                Some(revision_view) => revision_view
+                    .update(cx, |view, cx| view.open_revision(revision, row, window, cx)),
+                None => FileRevisionView::open(revision, row, workspace, window, cx),
+            }
+        });
+    }
 }
 
 fn deploy_blame_entry_context_menu(
     blame_entry: &BlameEntry,
     details: Option<&ParsedCommitMessage>,
+    repository: Entity<Repository>,
+    workspace: WeakEntity<Workspace>,
     editor: Entity<Editor>,
     position: gpui::Point<Pixels>,
     window: &mut Window,
     cx: &mut App,
 ) {
-    let context_menu = ContextMenu::build(window, cx, move |menu, _, _| {
-        let sha = format!("{}", blame_entry.sha);
-        menu.on_blur_subscription(Subscription::new(|| {}))
-            .entry("Copy commit SHA", None, move |_, cx| {
-                cx.write_to_clipboard(ClipboardItem::new_string(sha.clone()));
-            })
-            .when_some(
-                details.and_then(|details| details.permalink.clone()),
-                |this, url| {
-                    this.entry("Open permalink", None, move |_, cx| {
-                        cx.open_url(url.as_str())
+    let context_menu = ContextMenu::build(window, cx, {
+        let editor = editor.clone();
+        move |menu, _, _| {
+            let sha = format!("{}", blame_entry.sha);
+            let has_previous_revision = blame_entry.previous_revision().is_some();
+            let revision = BlameRevision {
+                repository,
+                path: RepoPath::from_str(&blame_entry.filename),
+                sha: sha.clone().into(),
+            };
+            let row = blame_entry.original_line_number.saturating_sub(1);
+            let blame_entry = blame_entry.clone();
+            menu.on_blur_subscription(Subscription::new(|| {}))
+                .entry("Copy commit SHA", None, move |_, cx| {
+                    cx.write_to_clipboard(ClipboardItem::new_string(sha.clone()));
+                })
+                .when_some(
+                    details.and_then(|details| details.permalink.clone()),
+                    |this, url| {
+                        this.entry("Open permalink", None, move |_, cx| {
+                            cx.open_url(url.as_str())
+                        })
+                    },
+                )
+                .separator()
+                .when(has_previous_revision, |this| {
+                    this.entry("Blame previous revision", None, move |window, cx| {
+                        editor.update(cx, |editor, cx| {
+                            editor.open_blame_previous_revision(blame_entry.clone(), window, cx);
+                        });
                     })
-                },
-            )
+                })
+                .entry("Open file at this commit", None, move |window, cx| {
+                    FileRevisionView::open(revision.clone(), row, workspace.clone(), window, cx)
+                })
+        }
     });
 
     editor.update(cx, move |editor, cx| {
@@ -17,6 +17,8 @@ use util::ResultExt;
 use workspace::notifications::DetachAndPromptErr;
 use workspace::{ModalView, Workspace};
 
+use crate::rebase_view::RebaseModal;
+
 pub fn register(workspace: &mut Workspace) {
     workspace.register_action(open);
     workspace.register_action(switch);
@@ -54,6 +56,22 @@ pub fn open(
     })
 }
 
+/// Opens a branch picker whose confirmation starts an interactive rebase onto the branch.
+pub fn rebase_interactive(
+    workspace: &mut Workspace,
+    _: &git::RebaseInteractive,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let repository = workspace.project().read(cx).active_repository(cx).clone();
+    workspace.toggle_modal(window, cx, |window, cx| {
+        let list = BranchList::new(repository, BranchListStyle::Modal, rems(34.), window, cx);
+        list.picker
+            .update(cx, |picker, _| picker.delegate.rebase_onto_branch = true);
+        list
+    })
+}
+
 pub fn popover(
     repository: Option<Entity<Repository>>,
     window: &mut Window,
@@ -177,6 +195,8 @@ pub struct BranchListDelegate {
     selected_index: usize,
     last_query: String,
     modifiers: Modifiers,
+    /// Whether confirming a branch rebases onto it instead of checking it out.
+    rebase_onto_branch: bool,
 }
 
 impl BranchListDelegate {
@@ -189,6 +209,7 @@ impl BranchListDelegate {
             selected_index: 0,
             last_query: Default::default(),
             modifiers: Default::default(),
+            rebase_onto_branch: false,
         }
     }
 
@@ -213,13 +234,37 @@ impl BranchListDelegate {
         });
         cx.emit(DismissEvent);
     }
+
+    fn rebase_onto(
+        &self,
+        branch_name: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        let Some(workspace) = window.root::<Workspace>().flatten() else {
+            return;
+        };
+        cx.emit(DismissEvent);
+        window.defer(cx, move |window, cx| {
+            workspace.update(cx, |workspace, cx| {
+                RebaseModal::open(repo, branch_name, workspace, window, cx);
+            });
+        });
+    }
 }
 
 impl PickerDelegate for BranchListDelegate {
     type ListItem = ListItem;
 
     fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
-        "Select branch...".into()
+        if self.rebase_onto_branch {
+            "Select branch to rebase onto...".into()
+        } else {
+            "Select branch...".into()
+        }
     }
 
     fn editor_position(&self) -> PickerEditorPosition {
@@ -325,10 +370,16 @@ impl PickerDelegate for BranchListDelegate {
         })
     }
 
-    fn confirm(&mut self, _secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+    fn confirm(&mut self, secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
         let Some(entry) = self.matches.get(self.selected_index()) else {
             return;
         };
+        if self.rebase_onto_branch || secondary {
+            if !entry.is_new {
+                self.rebase_onto(entry.branch.name.clone(), window, cx);
+            }
+            return;
+        }
         if entry.is_new {
             self.create_branch(entry.branch.name.clone(), window, cx);
             return;
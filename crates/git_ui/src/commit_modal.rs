@@ -1,5 +1,5 @@
 use crate::branch_picker::{self, BranchList};
-use crate::git_panel::{GitPanel, commit_message_editor};
+use crate::git_panel::{GitPanel, ToggleAmend, ToggleSignoff, commit_message_editor};
 use git::{Commit, GenerateCommitMessage};
 use panel::{panel_button, panel_editor_style, panel_filled_button};
 use ui::{KeybindingHint, PopoverMenu, PopoverMenuHandle, Tooltip, prelude::*};
@@ -215,22 +215,31 @@ impl CommitModal {
     }
 
     pub fn render_footer(&self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let (can_commit, tooltip, commit_label, co_authors, generate_commit_message, active_repo) =
-            self.git_panel.update(cx, |git_panel, cx| {
-                let (can_commit, tooltip) = git_panel.configure_commit_button(cx);
-                let title = git_panel.commit_button_title();
-                let co_authors = git_panel.render_co_authors(cx);
-                let generate_commit_message = git_panel.render_generate_commit_message_button(cx);
-                let active_repo = git_panel.active_repository.clone();
-                (
-                    can_commit,
-                    tooltip,
-                    title,
-                    co_authors,
-                    generate_commit_message,
-                    active_repo,
-                )
-            });
+        let (
+            can_commit,
+            tooltip,
+            commit_label,
+            co_authors,
+            commit_options,
+            generate_commit_message,
+            active_repo,
+        ) = self.git_panel.update(cx, |git_panel, cx| {
+            let (can_commit, tooltip) = git_panel.configure_commit_button(cx);
+            let title = git_panel.commit_button_title();
+            let co_authors = git_panel.render_co_authors(cx);
+            let commit_options = git_panel.render_commit_options(cx);
+            let generate_commit_message = git_panel.render_generate_commit_message_button(cx);
+            let active_repo = git_panel.active_repository.clone();
+            (
+                can_commit,
+                tooltip,
+                title,
+                co_authors,
+                commit_options,
+                generate_commit_message,
+                active_repo,
+            )
+        });
 
         let branch = active_repo
             .as_ref()
@@ -313,7 +322,8 @@ impl CommitModal {
                             .child(branch_picker),
                     )
                     .children(generate_commit_message)
-                    .children(co_authors),
+                    .children(co_authors)
+                    .child(commit_options),
             )
             .child(div().flex_1())
             .child(
@@ -366,6 +376,16 @@ impl Render for CommitModal {
                     panel.generate_commit_message(cx);
                 })
             }))
+            .on_action(cx.listener(|this, _: &ToggleAmend, window, cx| {
+                this.git_panel.update(cx, |panel, cx| {
+                    panel.toggle_amend(&ToggleAmend, window, cx);
+                })
+            }))
+            .on_action(cx.listener(|this, _: &ToggleSignoff, window, cx| {
+                this.git_panel.update(cx, |panel, cx| {
+                    panel.toggle_signoff(&ToggleSignoff, window, cx);
+                })
+            }))
             .on_action(
                 cx.listener(|this, _: &zed_actions::git::Branch, window, cx| {
                     this.toggle_branch_selector(window, cx);
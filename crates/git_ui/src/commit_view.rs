@@ -1,7 +1,8 @@
 use anyhow::{Result, anyhow};
 use buffer_diff::{BufferDiff, BufferDiffSnapshot};
 use editor::{Editor, EditorEvent, MultiBuffer};
-use git::repository::{CommitDetails, CommitDiff, CommitSummary, RepoPath};
+use futures::channel::oneshot;
+use git::repository::{CommitDetails, CommitDiff, CommitSummary, RepoPath, StashEntry};
 use gpui::{
     AnyElement, AnyView, App, AppContext as _, AsyncApp, Context, Entity, EventEmitter,
     FocusHandle, Focusable, IntoElement, Render, WeakEntity, Window,
@@ -33,10 +34,10 @@ pub struct CommitView {
     multibuffer: Entity<MultiBuffer>,
 }
 
-struct GitBlob {
-    path: RepoPath,
-    worktree_id: WorktreeId,
-    is_deleted: bool,
+pub(crate) struct GitBlob {
+    pub(crate) path: RepoPath,
+    pub(crate) worktree_id: WorktreeId,
+    pub(crate) is_deleted: bool,
 }
 
 struct CommitMetadataFile {
@@ -58,15 +59,51 @@ impl CommitView {
         let commit_diff = repo
             .update(cx, |repo, _| repo.load_commit_diff(commit.sha.to_string()))
             .ok();
-        let commit_details = repo
-            .update(cx, |repo, _| repo.show(commit.sha.to_string()))
-            .ok();
+        Self::open_with_diff(
+            commit.sha.to_string(),
+            commit_diff,
+            repo,
+            workspace,
+            window,
+            cx,
+        );
+    }
+
+    /// Opens the changes recorded in a stash entry, relative to the commit it was created on.
+    pub fn open_stash(
+        stash: StashEntry,
+        repo: WeakEntity<Repository>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let stash_diff = repo.update(cx, |repo, _| repo.stash_show(stash.index)).ok();
+        Self::open_with_diff(
+            stash.sha.to_string(),
+            stash_diff,
+            repo,
+            workspace,
+            window,
+            cx,
+        );
+    }
+
+    fn open_with_diff(
+        sha: String,
+        commit_diff: Option<oneshot::Receiver<Result<CommitDiff>>>,
+        repo: WeakEntity<Repository>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let commit_details = repo.update(cx, |repo, _| repo.show(sha)).ok();
 
         window
             .spawn(cx, async move |cx| {
                 let (commit_diff, commit_details) = futures::join!(commit_diff?, commit_details?);
                 let commit_diff = commit_diff.log_err()?.log_err()?;
                 let commit_details = commit_details.log_err()?.log_err()?;
+                let sha = commit_details.sha.clone();
                 let repo = repo.upgrade()?;
 
                 workspace
@@ -87,8 +124,7 @@ impl CommitView {
                         pane.update(cx, |pane, cx| {
                             let ix = pane.items().position(|item| {
                                 let commit_view = item.downcast::<CommitView>();
-                                commit_view
-                                    .map_or(false, |view| view.read(cx).commit.sha == commit.sha)
+                                commit_view.map_or(false, |view| view.read(cx).commit.sha == sha)
                             });
                             if let Some(ix) = ix {
                                 pane.activate_item(ix, true, true, window, cx);
@@ -295,7 +331,7 @@ impl language::File for CommitMetadataFile {
     }
 }
 
-async fn build_buffer(
+pub(crate) async fn build_buffer(
     mut text: String,
     blob: Arc<dyn File>,
     language_registry: &Arc<language::LanguageRegistry>,
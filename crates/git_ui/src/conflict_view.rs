@@ -0,0 +1,421 @@
+use std::{any::Any, mem, ops::Range, sync::Arc};
+
+use collections::{HashMap, HashSet};
+use editor::{
+    Addon, Anchor, Direction, Editor, EditorMode,
+    display_map::{BlockPlacement, BlockProperties, BlockStyle, CustomBlockId, RenderBlock},
+    scroll::Autoscroll,
+};
+use gpui::{Action, Context, Entity, Subscription, WeakEntity, Window, actions};
+use language::{Buffer, BufferId};
+use project::git_store::conflict_set::{ConflictRegion, ConflictSet, ConflictSetEvent};
+use theme::ActiveTheme as _;
+use ui::{Tooltip, prelude::*};
+
+actions!(
+    git,
+    [
+        GoToNextConflict,
+        GoToPreviousConflict,
+        AcceptOurs,
+        AcceptTheirs,
+        AcceptBoth
+    ]
+);
+
+/// Marker types for the row highlights of the different parts of a conflict region.
+enum ConflictMarkerRows {}
+enum ConflictOursRows {}
+enum ConflictBaseRows {}
+enum ConflictTheirsRows {}
+
+/// Tracks the merge conflicts of the buffers shown in an editor, highlighting them and
+/// rendering a row of resolution buttons above each one.
+struct ConflictAddon {
+    buffers: HashMap<BufferId, BufferConflicts>,
+    block_ids: HashSet<CustomBlockId>,
+    _subscriptions: Vec<Subscription>,
+}
+
+struct BufferConflicts {
+    conflict_set: Entity<ConflictSet>,
+    _subscription: Subscription,
+}
+
+impl Addon for ConflictAddon {
+    fn to_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn to_any_mut(&mut self) -> Option<&mut dyn Any> {
+        Some(self)
+    }
+}
+
+pub(crate) fn register_editor(editor: &mut Editor, cx: &mut Context<Editor>) {
+    if editor.mode() != EditorMode::Full || editor.project.is_none() {
+        return;
+    }
+
+    let handle = cx.entity().downgrade();
+    let multi_buffer = editor.buffer().clone();
+    let subscriptions = vec![
+        cx.subscribe(&multi_buffer, |editor, _, event, cx| match event {
+            multi_buffer::Event::ExcerptsAdded { buffer, .. } => {
+                track_buffer(editor, buffer.clone(), cx);
+                refresh_conflicts(editor, cx);
+            }
+            multi_buffer::Event::ExcerptsRemoved { .. } => {
+                let multi_buffer = editor.buffer().read(cx);
+                if let Some(addon) = editor.addon_mut::<ConflictAddon>() {
+                    addon
+                        .buffers
+                        .retain(|buffer_id, _| multi_buffer.buffer(*buffer_id).is_some());
+                }
+                refresh_conflicts(editor, cx);
+            }
+            _ => {}
+        }),
+        register_conflict_action::<GoToNextConflict>(
+            editor,
+            handle.clone(),
+            |editor, window, cx| go_to_conflict(editor, Direction::Next, window, cx),
+        ),
+        register_conflict_action::<GoToPreviousConflict>(
+            editor,
+            handle.clone(),
+            |editor, window, cx| go_to_conflict(editor, Direction::Prev, window, cx),
+        ),
+        register_conflict_action::<AcceptOurs>(editor, handle.clone(), |editor, _, cx| {
+            accept_conflict_at_cursor(editor, |conflict| vec![conflict.ours.clone()], cx)
+        }),
+        register_conflict_action::<AcceptTheirs>(editor, handle.clone(), |editor, _, cx| {
+            accept_conflict_at_cursor(editor, |conflict| vec![conflict.theirs.clone()], cx)
+        }),
+        register_conflict_action::<AcceptBoth>(editor, handle, |editor, _, cx| {
+            accept_conflict_at_cursor(
+                editor,
+                |conflict| vec![conflict.ours.clone(), conflict.theirs.clone()],
+                cx,
+            )
+        }),
+    ];
+    editor.register_addon(ConflictAddon {
+        buffers: HashMap::default(),
+        block_ids: HashSet::default(),
+        _subscriptions: subscriptions,
+    });
+
+    for buffer in multi_buffer.read(cx).all_buffers() {
+        track_buffer(editor, buffer, cx);
+    }
+    refresh_conflicts(editor, cx);
+}
+
+fn register_conflict_action<A: Action>(
+    editor: &mut Editor,
+    handle: WeakEntity<Editor>,
+    callback: fn(&mut Editor, &mut Window, &mut Context<Editor>),
+) -> Subscription {
+    editor.register_action(move |_: &A, window, cx| {
+        handle
+            .update(cx, |editor, cx| callback(editor, window, cx))
+            .ok();
+    })
+}
+
+fn track_buffer(editor: &mut Editor, buffer: Entity<Buffer>, cx: &mut Context<Editor>) {
+    let Some(project) = editor.project.clone() else {
+        return;
+    };
+    let buffer_id = buffer.read(cx).remote_id();
+    if editor
+        .addon::<ConflictAddon>()
+        .is_none_or(|addon| addon.buffers.contains_key(&buffer_id))
+    {
+        return;
+    }
+
+    let conflict_set = project
+        .read(cx)
+        .git_store()
+        .update(cx, |git_store, cx| git_store.open_conflict_set(buffer, cx));
+    let subscription = cx.subscribe(&conflict_set, |editor, _, event, cx| {
+        if *event == ConflictSetEvent::ConflictsChanged {
+            refresh_conflicts(editor, cx);
+        }
+    });
+    if let Some(addon) = editor.addon_mut::<ConflictAddon>() {
+        addon.buffers.insert(
+            buffer_id,
+            BufferConflicts {
+                conflict_set,
+                _subscription: subscription,
+            },
+        );
+    }
+}
+
+/// Replaces the highlights and resolution buttons of every conflict in the editor.
+fn refresh_conflicts(editor: &mut Editor, cx: &mut Context<Editor>) {
+    let Some(addon) = editor.addon_mut::<ConflictAddon>() else {
+        return;
+    };
+    let old_block_ids = mem::take(&mut addon.block_ids);
+    let conflict_sets = addon
+        .buffers
+        .values()
+        .map(|buffer_conflicts| buffer_conflicts.conflict_set.read(cx).snapshot())
+        .collect::<Vec<_>>();
+
+    editor.remove_blocks(old_block_ids, None, cx);
+    editor.clear_row_highlights::<ConflictMarkerRows>();
+    editor.clear_row_highlights::<ConflictOursRows>();
+    editor.clear_row_highlights::<ConflictBaseRows>();
+    editor.clear_row_highlights::<ConflictTheirsRows>();
+
+    let show_buttons = !editor.read_only(cx);
+    let multi_buffer = editor.buffer().read(cx);
+    let snapshot = multi_buffer.snapshot(cx);
+    let mut marker_rows = Vec::new();
+    let mut ours_rows = Vec::new();
+    let mut base_rows = Vec::new();
+    let mut theirs_rows = Vec::new();
+    let mut blocks = Vec::new();
+    for conflict_set in &conflict_sets {
+        let Some(buffer) = multi_buffer.buffer(conflict_set.buffer_id) else {
+            continue;
+        };
+        let buffer_snapshot = buffer.read(cx).text_snapshot();
+        let excerpts = multi_buffer.excerpts_for_buffer(conflict_set.buffer_id, cx);
+        for conflict in conflict_set.conflicts.iter() {
+            for (excerpt_id, excerpt_range) in &excerpts {
+                if conflict
+                    .range
+                    .end
+                    .cmp(&excerpt_range.context.start, &buffer_snapshot)
+                    .is_lt()
+                    || conflict
+                        .range
+                        .start
+                        .cmp(&excerpt_range.context.end, &buffer_snapshot)
+                        .is_gt()
+                {
+                    continue;
+                }
+
+                // Empty sections are skipped, as they'd highlight the following marker row.
+                let push_rows = |rows: &mut Vec<Range<Anchor>>,
+                                 start: language::Anchor,
+                                 end: language::Anchor| {
+                    if start.cmp(&end, &buffer_snapshot).is_lt() {
+                        if let Some((start, end)) = snapshot
+                            .anchor_in_excerpt(*excerpt_id, start)
+                            .zip(snapshot.anchor_in_excerpt(*excerpt_id, end))
+                        {
+                            rows.push(start..end);
+                        }
+                    }
+                };
+                push_rows(&mut marker_rows, conflict.range.start, conflict.ours.start);
+                push_rows(&mut ours_rows, conflict.ours.start, conflict.ours.end);
+                match &conflict.base {
+                    Some(base) => {
+                        push_rows(&mut marker_rows, conflict.ours.end, base.start);
+                        push_rows(&mut base_rows, base.start, base.end);
+                        push_rows(&mut marker_rows, base.end, conflict.theirs.start);
+                    }
+                    None => push_rows(&mut marker_rows, conflict.ours.end, conflict.theirs.start),
+                }
+                push_rows(&mut theirs_rows, conflict.theirs.start, conflict.theirs.end);
+                push_rows(&mut marker_rows, conflict.theirs.end, conflict.range.end);
+
+                if show_buttons {
+                    if let Some(position) =
+                        snapshot.anchor_in_excerpt(*excerpt_id, conflict.range.start)
+                    {
+                        blocks.push(BlockProperties {
+                            placement: BlockPlacement::Above(position),
+                            height: 1,
+                            style: BlockStyle::Sticky,
+                            render: conflict_buttons_renderer(conflict.clone(), buffer.downgrade()),
+                            priority: 0,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    let marker_color = cx.theme().colors().version_control_conflict.opacity(0.3);
+    let status = cx.theme().status();
+    let (ours_color, base_color, theirs_color) = (
+        status.info_background,
+        status.hint_background,
+        status.success_background,
+    );
+    for range in marker_rows {
+        editor.highlight_rows::<ConflictMarkerRows>(range, marker_color, false, cx);
+    }
+    for range in ours_rows {
+        editor.highlight_rows::<ConflictOursRows>(range, ours_color, false, cx);
+    }
+    for range in base_rows {
+        editor.highlight_rows::<ConflictBaseRows>(range, base_color, false, cx);
+    }
+    for range in theirs_rows {
+        editor.highlight_rows::<ConflictTheirsRows>(range, theirs_color, false, cx);
+    }
+
+    let block_ids = editor.insert_blocks(blocks, None, cx);
+    if let Some(addon) = editor.addon_mut::<ConflictAddon>() {
+        addon.block_ids = block_ids.into_iter().collect();
+    }
+    cx.notify();
+}
+
+fn conflict_buttons_renderer(conflict: ConflictRegion, buffer: WeakEntity<Buffer>) -> RenderBlock {
+    Arc::new(move |cx| {
+        let resolve_button = |id: &'static str,
+                              label: &'static str,
+                              tooltip: String,
+                              sections: Vec<Range<language::Anchor>>| {
+            let conflict = conflict.clone();
+            let buffer = buffer.clone();
+            Button::new(id, label)
+                .label_size(LabelSize::Small)
+                .tooltip(Tooltip::text(tooltip))
+                .on_click(move |_, _, cx| {
+                    if let Some(buffer) = buffer.upgrade() {
+                        conflict.resolve(buffer, &sections, cx);
+                    }
+                })
+        };
+
+        h_flex()
+            .id(cx.block_id)
+            .h(cx.line_height)
+            .pl(cx.anchor_x)
+            .gap_1()
+            .block_mouse_down()
+            .child(resolve_button(
+                "use-ours",
+                "Use Ours",
+                format!("Keep the changes from {}", conflict.ours_branch_name),
+                vec![conflict.ours.clone()],
+            ))
+            .child(resolve_button(
+                "use-theirs",
+                "Use Theirs",
+                format!("Keep the changes from {}", conflict.theirs_branch_name),
+                vec![conflict.theirs.clone()],
+            ))
+            .child(resolve_button(
+                "use-both",
+                "Use Both",
+                "Keep both changes, ours first".to_string(),
+                vec![conflict.ours.clone(), conflict.theirs.clone()],
+            ))
+            .into_any_element()
+    })
+}
+
+fn go_to_conflict(
+    editor: &mut Editor,
+    direction: Direction,
+    window: &mut Window,
+    cx: &mut Context<Editor>,
+) {
+    let Some(addon) = editor.addon::<ConflictAddon>() else {
+        return;
+    };
+    let multi_buffer = editor.buffer().read(cx);
+    let snapshot = multi_buffer.snapshot(cx);
+    let mut starts = Vec::new();
+    for buffer_conflicts in addon.buffers.values() {
+        let conflict_set = buffer_conflicts.conflict_set.read(cx).snapshot();
+        let Some(buffer) = multi_buffer.buffer(conflict_set.buffer_id) else {
+            continue;
+        };
+        let buffer_snapshot = buffer.read(cx).text_snapshot();
+        for (excerpt_id, excerpt_range) in
+            multi_buffer.excerpts_for_buffer(conflict_set.buffer_id, cx)
+        {
+            starts.extend(
+                conflict_set
+                    .conflicts
+                    .iter()
+                    .filter(|conflict| {
+                        conflict
+                            .range
+                            .start
+                            .cmp(&excerpt_range.context.start, &buffer_snapshot)
+                            .is_ge()
+                            && conflict
+                                .range
+                                .start
+                                .cmp(&excerpt_range.context.end, &buffer_snapshot)
+                                .is_le()
+                    })
+                    .filter_map(|conflict| {
+                        snapshot.anchor_in_excerpt(excerpt_id, conflict.range.start)
+                    }),
+            );
+        }
+    }
+    starts.sort_by(|a, b| a.cmp(b, &snapshot));
+
+    let head = editor.selections.newest_anchor().head();
+    let target = match direction {
+        Direction::Next => starts
+            .iter()
+            .find(|start| start.cmp(&head, &snapshot).is_gt())
+            .or(starts.first()),
+        Direction::Prev => starts
+            .iter()
+            .rev()
+            .find(|start| start.cmp(&head, &snapshot).is_lt())
+            .or(starts.last()),
+    };
+    let Some(target) = target.copied() else {
+        return;
+    };
+    editor.change_selections(Some(Autoscroll::center()), window, cx, |selections| {
+        selections.select_anchor_ranges([target..target])
+    });
+}
+
+/// Resolves the conflict containing the newest cursor with the given sections of it.
+fn accept_conflict_at_cursor(
+    editor: &mut Editor,
+    sections: fn(&ConflictRegion) -> Vec<Range<language::Anchor>>,
+    cx: &mut Context<Editor>,
+) {
+    if editor.read_only(cx) {
+        return;
+    }
+    let Some(addon) = editor.addon::<ConflictAddon>() else {
+        return;
+    };
+    let head = editor.selections.newest_anchor().head();
+    let Some((buffer, position)) = editor.buffer().read(cx).text_anchor_for_position(head, cx)
+    else {
+        return;
+    };
+    let Some(buffer_conflicts) = addon.buffers.get(&buffer.read(cx).remote_id()) else {
+        return;
+    };
+    let conflict_set = buffer_conflicts.conflict_set.read(cx).snapshot();
+    let buffer_snapshot = buffer.read(cx).text_snapshot();
+    let Some(conflict) = conflict_set.conflicts.iter().find(|conflict| {
+        conflict
+            .range
+            .start
+            .cmp(&position, &buffer_snapshot)
+            .is_le()
+            && conflict.range.end.cmp(&position, &buffer_snapshot).is_gt()
+    }) else {
+        return;
+    };
+    conflict.resolve(buffer, &sections(conflict), cx);
+}
@@ -0,0 +1,346 @@
+use anyhow::{Context as _, Result};
+use editor::{BlameRevision, Editor, EditorEvent, scroll::Autoscroll};
+use gpui::{
+    AnyElement, AnyView, App, AsyncApp, Context, Entity, EventEmitter, FocusHandle, Focusable,
+    IntoElement, Render, Subscription, Task, WeakEntity, Window, actions,
+};
+use language::{Buffer, Point};
+use project::Project;
+use std::{any::TypeId, sync::Arc};
+use ui::prelude::*;
+use workspace::{
+    Item, ItemHandle as _, ToolbarItemLocation, Workspace,
+    item::{BreadcrumbText, ItemEvent, TabContentParams},
+    notifications::{DetachAndPromptErr as _, NotifyResultExt as _},
+    searchable::SearchableItemHandle,
+};
+
+use crate::commit_view::{GitBlob, build_buffer};
+
+actions!(file_revision_view, [NavigateBack, NavigateForward]);
+
+/// A read-only view of a file as it was at a past commit, with the blame of that revision in the
+/// gutter.
+///
+/// Blaming the previous revision of a line from the gutter replaces the view's contents, and the
+/// revisions visited that way can be stepped through with `NavigateBack` and `NavigateForward`.
+pub struct FileRevisionView {
+    project: Entity<Project>,
+    revision: BlameRevision,
+    editor: Entity<Editor>,
+    /// The revisions navigated away from, with the row the cursor was on, the most recent last.
+    back_stack: Vec<(BlameRevision, u32)>,
+    forward_stack: Vec<(BlameRevision, u32)>,
+    navigate_task: Option<Task<()>>,
+    _editor_subscription: Subscription,
+}
+
+impl FileRevisionView {
+    /// Opens the file at the given revision in a new view, with the cursor on the given row.
+    pub fn open(
+        revision: BlameRevision,
+        row: u32,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let Ok(project) = workspace.read_with(cx, |workspace, _| workspace.project().clone())
+        else {
+            return;
+        };
+        window
+            .spawn(cx, async move |cx| {
+                let buffer = load_buffer(&revision, &project, cx).await?;
+                workspace.update_in(cx, |workspace, window, cx| {
+                    let view = cx.new(|cx| Self::new(revision, row, buffer, project, window, cx));
+                    workspace.add_item_to_active_pane(Box::new(view), None, true, window, cx);
+                })
+            })
+            .detach_and_prompt_err("Failed to open file revision", window, cx, |_, _, _| None);
+    }
+
+    fn new(
+        revision: BlameRevision,
+        row: u32,
+        buffer: Entity<Buffer>,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let editor = build_editor(&revision, row, buffer, &project, window, cx);
+        Self {
+            _editor_subscription: subscribe_to_editor(&editor, cx),
+            project,
+            revision,
+            editor,
+            back_stack: Vec::new(),
+            forward_stack: Vec::new(),
+            navigate_task: None,
+        }
+    }
+
+    pub fn editor(&self) -> &Entity<Editor> {
+        &self.editor
+    }
+
+    /// Shows the given revision in place of the current one, which can be returned to with
+    /// `NavigateBack`.
+    pub fn open_revision(
+        &mut self,
+        revision: BlameRevision,
+        row: u32,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let current = (self.revision.clone(), self.cursor_row(cx));
+        self.navigate(revision, row, window, cx, move |this| {
+            this.back_stack.push(current);
+            this.forward_stack.clear();
+        });
+    }
+
+    fn navigate_back(&mut self, _: &NavigateBack, window: &mut Window, cx: &mut Context<Self>) {
+        let Some((revision, row)) = self.back_stack.last().cloned() else {
+            return;
+        };
+        let current = (self.revision.clone(), self.cursor_row(cx));
+        self.navigate(revision, row, window, cx, move |this| {
+            this.back_stack.pop();
+            this.forward_stack.push(current);
+        });
+    }
+
+    fn navigate_forward(
+        &mut self,
+        _: &NavigateForward,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some((revision, row)) = self.forward_stack.last().cloned() else {
+            return;
+        };
+        let current = (self.revision.clone(), self.cursor_row(cx));
+        self.navigate(revision, row, window, cx, move |this| {
+            this.forward_stack.pop();
+            this.back_stack.push(current);
+        });
+    }
+
+    /// Loads the revision and shows it, updating the stacks with `update_stacks` only once it
+    /// is loaded, so that a revision which fails to load doesn't lose its place in them.
+    fn navigate(
+        &mut self,
+        revision: BlameRevision,
+        row: u32,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        update_stacks: impl FnOnce(&mut Self) + 'static,
+    ) {
+        let project = self.project.clone();
+        let task = cx.spawn_in(window, async move |this, cx| {
+            let buffer = load_buffer(&revision, &project, cx).await?;
+            this.update_in(cx, |this, window, cx| {
+                update_stacks(this);
+
+                let was_focused = this.editor.focus_handle(cx).contains_focused(window, cx);
+                let editor = build_editor(&revision, row, buffer, &project, window, cx);
+                if was_focused {
+                    window.focus(&editor.focus_handle(cx));
+                }
+                this._editor_subscription = subscribe_to_editor(&editor, cx);
+                this.editor = editor;
+                this.revision = revision;
+                cx.emit(EditorEvent::TitleChanged);
+                cx.notify();
+            })
+        });
+        self.navigate_task = Some(cx.spawn_in(window, async move |_, cx| {
+            task.await
+                .context("failed to open file revision")
+                .notify_async_err(cx);
+        }));
+    }
+
+    fn cursor_row(&self, cx: &mut Context<Self>) -> u32 {
+        self.editor.update(cx, |editor, cx| {
+            editor.selections.newest::<Point>(cx).head().row
+        })
+    }
+
+    fn title(&self) -> String {
+        let file_name = self
+            .revision
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let short_sha = self
+            .revision
+            .sha
+            .get(..git::SHORT_SHA_LENGTH)
+            .unwrap_or(&self.revision.sha);
+        format!("{file_name} @ {short_sha}")
+    }
+}
+
+async fn load_buffer(
+    revision: &BlameRevision,
+    project: &Entity<Project>,
+    cx: &mut AsyncApp,
+) -> Result<Entity<Buffer>> {
+    let text = revision
+        .repository
+        .update(cx, |repository, _| {
+            repository.load_text_at_revision(revision.path.clone(), revision.sha.to_string())
+        })?
+        .await??
+        .with_context(|| {
+            format!(
+                "{} does not exist at {}",
+                revision.path.display(),
+                revision.sha
+            )
+        })?;
+    let (worktree_id, language_registry) = cx.update(|cx| {
+        let project = project.read(cx);
+        let worktree_id = revision
+            .repository
+            .read(cx)
+            .repo_path_to_project_path(&revision.path, cx)
+            .map(|path| path.worktree_id)
+            .or_else(|| {
+                project
+                    .worktrees(cx)
+                    .next()
+                    .map(|worktree| worktree.read(cx).id())
+            });
+        (worktree_id, project.languages().clone())
+    })?;
+    let file = Arc::new(GitBlob {
+        path: revision.path.clone(),
+        worktree_id: worktree_id.context("project has no worktrees")?,
+        is_deleted: false,
+    }) as Arc<dyn language::File>;
+    build_buffer(text, file, &language_registry, cx).await
+}
+
+fn build_editor(
+    revision: &BlameRevision,
+    row: u32,
+    buffer: Entity<Buffer>,
+    project: &Entity<Project>,
+    window: &mut Window,
+    cx: &mut Context<FileRevisionView>,
+) -> Entity<Editor> {
+    cx.new(|cx| {
+        let mut editor = Editor::for_buffer(buffer, Some(project.clone()), window, cx);
+        editor.set_read_only(true);
+        editor.set_blame_revision(revision.clone(), window, cx);
+        let point = Point::new(row, 0);
+        editor.change_selections(Some(Autoscroll::center()), window, cx, |selections| {
+            selections.select_ranges([point..point]);
+        });
+        editor
+    })
+}
+
+fn subscribe_to_editor(
+    editor: &Entity<Editor>,
+    cx: &mut Context<FileRevisionView>,
+) -> Subscription {
+    cx.subscribe(editor, |_, _, event: &EditorEvent, cx| {
+        cx.emit(event.clone())
+    })
+}
+
+impl EventEmitter<EditorEvent> for FileRevisionView {}
+
+impl Focusable for FileRevisionView {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl Item for FileRevisionView {
+    type Event = EditorEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::FileGit).color(Color::Muted))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, _: &App) -> AnyElement {
+        Label::new(self.title())
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_tooltip_text(&self, _: &App) -> Option<SharedString> {
+        Some(format!("{} @ {}", self.revision.path.display(), self.revision.sha).into())
+    }
+
+    fn to_item_events(event: &EditorEvent, f: impl FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("File Revision View Opened")
+    }
+
+    fn deactivated(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor
+            .update(cx, |editor, cx| editor.deactivated(window, cx));
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<AnyView> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.to_any())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.editor.to_any())
+        } else {
+            None
+        }
+    }
+
+    fn as_searchable(&self, _: &Entity<Self>) -> Option<Box<dyn SearchableItemHandle>> {
+        Some(Box::new(self.editor.clone()))
+    }
+
+    fn breadcrumb_location(&self, _: &App) -> ToolbarItemLocation {
+        ToolbarItemLocation::PrimaryLeft
+    }
+
+    fn breadcrumbs(&self, theme: &theme::Theme, cx: &App) -> Option<Vec<BreadcrumbText>> {
+        self.editor.breadcrumbs(theme, cx)
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, cx| {
+            editor.added_to_workspace(workspace, window, cx)
+        });
+    }
+}
+
+impl Render for FileRevisionView {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .key_context("FileRevisionView")
+            .size_full()
+            .on_action(cx.listener(Self::navigate_back))
+            .on_action(cx.listener(Self::navigate_forward))
+            .child(self.editor.clone())
+    }
+}
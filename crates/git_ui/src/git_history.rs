@@ -0,0 +1,795 @@
+use std::time::Duration;
+
+use editor::{BlameRevision, Editor, EditorEvent};
+use git::repository::{CommitSummary, LogEntry, LogOptions, RepoPath};
+use gpui::{
+    App, Bounds, Corner, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, MouseButton,
+    MouseDownEvent, PathBuilder, Pixels, Point, ScrollStrategy, SharedString, Subscription, Task,
+    UniformListScrollHandle, WeakEntity, anchored, canvas, deferred, fill, point, size,
+    uniform_list,
+};
+use menu::{Confirm, SelectNext, SelectPrevious};
+use project::git_store::{Repository, RepositoryEvent};
+use theme::ActiveTheme as _;
+use time::OffsetDateTime;
+use ui::{ContextMenu, prelude::*};
+use workspace::{
+    Item, Workspace,
+    item::{ItemEvent, TabContentParams},
+};
+
+use crate::commit_view::CommitView;
+use crate::file_revision_view::FileRevisionView;
+use crate::rebase_view::RebaseModal;
+
+/// The number of commits loaded at a time.
+const PAGE_SIZE: usize = 200;
+const FILTER_DEBOUNCE: Duration = Duration::from_millis(300);
+const ROW_HEIGHT: Pixels = px(28.);
+const LANE_WIDTH: Pixels = px(14.);
+const NODE_SIZE: Pixels = px(8.);
+
+/// A browsable commit history of a repository, or of a path within it, with a branch graph.
+pub struct GitHistory {
+    repository: Entity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    path: Option<RepoPath>,
+    revision_editor: Entity<Editor>,
+    author_editor: Entity<Editor>,
+    message_editor: Entity<Editor>,
+    entries: Vec<LogEntry>,
+    graph: Vec<GraphRow>,
+    graph_width: usize,
+    all_loaded: bool,
+    load_task: Option<Task<()>>,
+    error: Option<SharedString>,
+    head_sha: Option<SharedString>,
+    selected_index: Option<usize>,
+    scroll_handle: UniformListScrollHandle,
+    focus_handle: FocusHandle,
+    context_menu: Option<(Entity<ContextMenu>, Point<Pixels>, Subscription)>,
+    _subscriptions: Vec<Subscription>,
+}
+
+/// How a commit is drawn in the graph, in terms of lanes: the columns in which lines run
+/// from each commit to its parents.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct GraphRow {
+    /// The lane of the commit's node.
+    pub lane: usize,
+    /// The lanes entering the node from the row above.
+    pub incoming: Vec<usize>,
+    /// The lanes leaving the node towards its parents in the rows below.
+    pub outgoing: Vec<usize>,
+    /// The lanes running past the node.
+    pub passing: Vec<usize>,
+}
+
+impl GitHistory {
+    /// Opens the history of the repository, or of the given path within it, reusing an
+    /// existing history view for the same path.
+    pub fn open(
+        repository: Entity<Repository>,
+        path: Option<RepoPath>,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let existing = workspace
+            .active_pane()
+            .read(cx)
+            .items()
+            .filter_map(|item| item.downcast::<GitHistory>())
+            .find(|history| {
+                let history = history.read(cx);
+                history.repository == repository && history.path == path
+            });
+        if let Some(existing) = existing {
+            workspace.activate_item(&existing, true, true, window, cx);
+            return;
+        }
+
+        let workspace_handle = cx.entity().downgrade();
+        let history = cx.new(|cx| GitHistory::new(repository, path, workspace_handle, window, cx));
+        workspace.add_item_to_active_pane(Box::new(history), None, true, window, cx);
+    }
+
+    fn new(
+        repository: Entity<Repository>,
+        path: Option<RepoPath>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let revision_editor = filter_editor("Revision or range, e.g. main..HEAD", window, cx);
+        let author_editor = filter_editor("Author", window, cx);
+        let message_editor = filter_editor("Message", window, cx);
+
+        let mut subscriptions = [&revision_editor, &author_editor, &message_editor]
+            .into_iter()
+            .map(|editor| {
+                cx.subscribe(editor, |this, _, event: &EditorEvent, cx| {
+                    if let EditorEvent::BufferEdited = event {
+                        this.reload(true, cx);
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        subscriptions.push(cx.subscribe(
+            &repository,
+            |this, repository, event: &RepositoryEvent, cx| {
+                if let RepositoryEvent::Updated { .. } = event {
+                    let head_sha = head_sha(&repository, cx);
+                    if head_sha != this.head_sha {
+                        this.head_sha = head_sha;
+                        this.reload(false, cx);
+                    }
+                }
+            },
+        ));
+
+        let mut this = Self {
+            head_sha: head_sha(&repository, cx),
+            repository,
+            workspace,
+            path,
+            revision_editor,
+            author_editor,
+            message_editor,
+            entries: Vec::new(),
+            graph: Vec::new(),
+            graph_width: 0,
+            all_loaded: false,
+            load_task: None,
+            error: None,
+            selected_index: None,
+            scroll_handle: UniformListScrollHandle::new(),
+            focus_handle: cx.focus_handle(),
+            context_menu: None,
+            _subscriptions: subscriptions,
+        };
+        this.reload(false, cx);
+        this
+    }
+
+    fn reload(&mut self, debounce: bool, cx: &mut Context<Self>) {
+        self.entries.clear();
+        self.graph.clear();
+        self.graph_width = 0;
+        self.all_loaded = false;
+        self.error = None;
+        self.selected_index = None;
+        self.load_page(debounce, cx);
+        cx.notify();
+    }
+
+    fn load_page(&mut self, debounce: bool, cx: &mut Context<Self>) {
+        self.load_task = Some(cx.spawn(async move |this, cx| {
+            if debounce {
+                cx.background_executor().timer(FILTER_DEBOUNCE).await;
+            }
+            let Ok(log) = this.update(cx, |this, cx| {
+                let options = this.log_options(cx);
+                this.repository
+                    .update(cx, |repository, _| repository.log(options))
+            }) else {
+                return;
+            };
+            let Ok(result) = log.await else {
+                return;
+            };
+            this.update(cx, |this, cx| {
+                this.load_task = None;
+                match result {
+                    Ok(entries) => {
+                        this.all_loaded = entries.len() < PAGE_SIZE;
+                        this.entries.extend(entries);
+                        this.graph = compute_graph(&this.entries);
+                        this.graph_width = this
+                            .graph
+                            .iter()
+                            .flat_map(|row| {
+                                [row.lane]
+                                    .into_iter()
+                                    .chain(row.incoming.iter().copied())
+                                    .chain(row.outgoing.iter().copied())
+                                    .chain(row.passing.iter().copied())
+                            })
+                            .max()
+                            .map_or(0, |lane| lane + 1);
+                    }
+                    Err(error) => {
+                        this.all_loaded = true;
+                        this.error = Some(error.to_string().into());
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    fn load_more(&mut self, cx: &mut Context<Self>) {
+        if !self.all_loaded && self.load_task.is_none() {
+            self.load_page(false, cx);
+        }
+    }
+
+    fn log_options(&self, cx: &App) -> LogOptions {
+        let filter = |editor: &Entity<Editor>| {
+            let text = editor.read(cx).text(cx);
+            let text = text.trim();
+            (!text.is_empty()).then(|| text.to_string())
+        };
+        LogOptions {
+            revision: filter(&self.revision_editor),
+            path: self.path.clone(),
+            author: filter(&self.author_editor),
+            message: filter(&self.message_editor),
+            skip: self.entries.len(),
+            limit: PAGE_SIZE,
+        }
+    }
+
+    fn select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let ix = self
+            .selected_index
+            .map_or(0, |ix| (ix + 1).min(self.entries.len() - 1));
+        self.select_entry(ix, cx);
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, _: &mut Window, cx: &mut Context<Self>) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let ix = self.selected_index.map_or(0, |ix| ix.saturating_sub(1));
+        self.select_entry(ix, cx);
+    }
+
+    fn select_entry(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.selected_index = Some(ix);
+        self.scroll_handle
+            .scroll_to_item(ix, ScrollStrategy::Center);
+        cx.notify();
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.selected_index {
+            self.open_commit(ix, window, cx);
+        }
+    }
+
+    fn open_commit(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get(ix) else {
+            return;
+        };
+        CommitView::open(
+            CommitSummary {
+                sha: entry.sha.clone(),
+                subject: entry.subject.clone(),
+                commit_timestamp: entry.commit_timestamp,
+                has_parent: !entry.parent_shas.is_empty(),
+            },
+            self.repository.downgrade(),
+            self.workspace.clone(),
+            window,
+            cx,
+        );
+    }
+
+    /// Opens the file whose history is shown as it was at the commit, with that revision's blame.
+    fn open_file_at_commit(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let (Some(entry), Some(path)) = (self.entries.get(ix), self.path.clone()) else {
+            return;
+        };
+        let revision = BlameRevision {
+            repository: self.repository.clone(),
+            path,
+            sha: entry.sha.clone(),
+        };
+        FileRevisionView::open(revision, 0, self.workspace.clone(), window, cx);
+    }
+
+    fn rebase_interactive(
+        &mut self,
+        _: &git::RebaseInteractive,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(ix) = self.selected_index {
+            self.rebase_onto(ix, window, cx);
+        }
+    }
+
+    /// Opens an interactive rebase of the commits that follow the entry.
+    fn rebase_onto(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get(ix) else {
+            return;
+        };
+        let repository = self.repository.clone();
+        let base = entry.sha.clone();
+        self.workspace
+            .update(cx, |workspace, cx| {
+                RebaseModal::open(repository, base, workspace, window, cx)
+            })
+            .ok();
+    }
+
+    fn deploy_context_menu(
+        &mut self,
+        position: Point<Pixels>,
+        ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let this = cx.entity();
+        let is_file_history = self.path.is_some();
+        let context_menu = ContextMenu::build(window, cx, |menu, window, _| {
+            menu.entry(
+                "View Commit",
+                None,
+                window.handler_for(&this, move |this, window, cx| {
+                    this.open_commit(ix, window, cx)
+                }),
+            )
+            .when(is_file_history, |menu| {
+                menu.entry(
+                    "Open File at This Revision",
+                    None,
+                    window.handler_for(&this, move |this, window, cx| {
+                        this.open_file_at_commit(ix, window, cx)
+                    }),
+                )
+            })
+            .entry(
+                "Rebase Interactively onto This Commit",
+                None,
+                window.handler_for(&this, move |this, window, cx| {
+                    this.rebase_onto(ix, window, cx)
+                }),
+            )
+        });
+        let subscription = cx.subscribe_in(
+            &context_menu,
+            window,
+            |this, _, _: &DismissEvent, window, cx| {
+                if this.context_menu.as_ref().is_some_and(|context_menu| {
+                    context_menu.0.focus_handle(cx).contains_focused(window, cx)
+                }) {
+                    cx.focus_self(window);
+                }
+                this.context_menu.take();
+                cx.notify();
+            },
+        );
+        self.selected_index = Some(ix);
+        self.context_menu = Some((context_menu, position, subscription));
+        cx.notify();
+    }
+
+    fn title(&self) -> SharedString {
+        match &self.path {
+            Some(path) => format!("History: {}", path.display()).into(),
+            None => "History".into(),
+        }
+    }
+
+    fn render_entry(&self, ix: usize, cx: &mut Context<Self>) -> AnyElement {
+        let entry = &self.entries[ix];
+        let colors = cx.theme().colors();
+        let short_sha = entry.sha.get(..7).unwrap_or(&entry.sha).to_string();
+        let date = time_format::format_local_timestamp(
+            OffsetDateTime::from_unix_timestamp(entry.commit_timestamp)
+                .unwrap_or(OffsetDateTime::UNIX_EPOCH),
+            OffsetDateTime::now_utc(),
+            time_format::TimestampFormat::Relative,
+        );
+
+        h_flex()
+            .id(("history-entry", ix))
+            .h(ROW_HEIGHT)
+            .w_full()
+            .px_2()
+            .gap_2()
+            .cursor_pointer()
+            .when(self.selected_index == Some(ix), |this| {
+                this.bg(colors.ghost_element_selected)
+            })
+            .hover(|this| this.bg(colors.ghost_element_hover))
+            .on_click(cx.listener(move |this, _, window, cx| {
+                this.selected_index = Some(ix);
+                this.open_commit(ix, window, cx);
+                cx.notify();
+            }))
+            .on_mouse_down(
+                MouseButton::Right,
+                cx.listener(move |this, event: &MouseDownEvent, window, cx| {
+                    this.deploy_context_menu(event.position, ix, window, cx);
+                    cx.stop_propagation();
+                }),
+            )
+            .children(
+                self.graph
+                    .get(ix)
+                    .map(|row| render_graph_row(row.clone(), self.graph_width, cx)),
+            )
+            .children(entry.refs.iter().map(|name| {
+                div()
+                    .px_1()
+                    .rounded_sm()
+                    .border_1()
+                    .border_color(colors.border)
+                    .child(
+                        Label::new(name.clone())
+                            .size(LabelSize::XSmall)
+                            .color(Color::Accent),
+                    )
+            }))
+            .child(
+                div()
+                    .flex_1()
+                    .min_w_0()
+                    .overflow_hidden()
+                    .child(Label::new(entry.subject.clone()).truncate()),
+            )
+            .child(
+                Label::new(entry.author_name.clone())
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+                    .single_line(),
+            )
+            .child(
+                Label::new(date)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+                    .single_line(),
+            )
+            .child(
+                Label::new(short_sha)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+                    .buffer_font(cx),
+            )
+            .into_any_element()
+    }
+}
+
+/// Opens the history of the path given by the action, or of the active item's file.
+pub(crate) fn open_file_history(
+    workspace: &mut Workspace,
+    action: &git::FileHistory,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let project = workspace.project().read(cx);
+    let project_path = match &action.abs_path {
+        Some(abs_path) => project.find_project_path(abs_path, cx),
+        None => workspace
+            .active_item(cx)
+            .and_then(|item| item.project_path(cx)),
+    };
+    let Some((repository, path)) = project_path.and_then(|project_path| {
+        project
+            .git_store()
+            .read(cx)
+            .repository_and_path_for_project_path(&project_path, cx)
+    }) else {
+        return;
+    };
+    // The repository's root directory has an empty path, and its history is the whole history.
+    let path = (!path.as_os_str().is_empty()).then_some(path);
+    GitHistory::open(repository, path, workspace, window, cx);
+}
+
+fn filter_editor(
+    placeholder: &'static str,
+    window: &mut Window,
+    cx: &mut Context<GitHistory>,
+) -> Entity<Editor> {
+    cx.new(|cx| {
+        let mut editor = Editor::single_line(window, cx);
+        editor.set_placeholder_text(placeholder, cx);
+        editor
+    })
+}
+
+fn head_sha(repository: &Entity<Repository>, cx: &App) -> Option<SharedString> {
+    let branch = repository.read(cx).branch.as_ref()?;
+    Some(branch.most_recent_commit.as_ref()?.sha.clone())
+}
+
+/// Assigns the commits, given in topological order, to lanes. Each lane holds the commit it
+/// expects next; a commit continues the first lane expecting it, and its first parent takes
+/// over its lane unless another lane already expects that parent.
+pub(crate) fn compute_graph(entries: &[LogEntry]) -> Vec<GraphRow> {
+    let mut lanes: Vec<Option<SharedString>> = Vec::new();
+    entries
+        .iter()
+        .map(|entry| {
+            let incoming = lanes
+                .iter()
+                .enumerate()
+                .filter(|(_, sha)| sha.as_ref() == Some(&entry.sha))
+                .map(|(lane, _)| lane)
+                .collect::<Vec<_>>();
+            let lane = match incoming.first() {
+                Some(lane) => *lane,
+                None => free_lane(&mut lanes),
+            };
+            for lane in &incoming {
+                lanes[*lane] = None;
+            }
+            let passing = lanes
+                .iter()
+                .enumerate()
+                .filter(|(_, sha)| sha.is_some())
+                .map(|(lane, _)| lane)
+                .collect();
+
+            let mut outgoing = Vec::new();
+            for parent_sha in &entry.parent_shas {
+                let target = match lanes
+                    .iter()
+                    .position(|sha| sha.as_ref() == Some(parent_sha))
+                {
+                    Some(target) => target,
+                    None => {
+                        let target = if lanes[lane].is_none() {
+                            lane
+                        } else {
+                            free_lane(&mut lanes)
+                        };
+                        lanes[target] = Some(parent_sha.clone());
+                        target
+                    }
+                };
+                if !outgoing.contains(&target) {
+                    outgoing.push(target);
+                }
+            }
+            while lanes.last().is_some_and(|sha| sha.is_none()) {
+                lanes.pop();
+            }
+
+            GraphRow {
+                lane,
+                incoming,
+                outgoing,
+                passing,
+            }
+        })
+        .collect()
+}
+
+fn free_lane(lanes: &mut Vec<Option<SharedString>>) -> usize {
+    lanes.iter().position(Option::is_none).unwrap_or_else(|| {
+        lanes.push(None);
+        lanes.len() - 1
+    })
+}
+
+fn render_graph_row(row: GraphRow, graph_width: usize, cx: &App) -> impl IntoElement {
+    let accents = cx.theme().accents().clone();
+    canvas(
+        |_, _, _| {},
+        move |bounds, _, window, _| {
+            let lane_x = |lane: usize| bounds.left() + LANE_WIDTH * (lane as f32 + 0.5);
+            let node = point(lane_x(row.lane), bounds.center().y);
+            let mut paint_edge = |from: Point<Pixels>, to: Point<Pixels>, lane: usize| {
+                let mut builder = PathBuilder::stroke(px(1.5));
+                builder.move_to(from);
+                if from.x == to.x {
+                    builder.line_to(to);
+                } else {
+                    let middle_y = (from.y + to.y) / 2.;
+                    builder.cubic_bezier_to(to, point(from.x, middle_y), point(to.x, middle_y));
+                }
+                if let Ok(path) = builder.build() {
+                    window.paint_path(path, accents.color_for_index(lane as u32));
+                }
+            };
+            for lane in &row.passing {
+                let x = lane_x(*lane);
+                paint_edge(point(x, bounds.top()), point(x, bounds.bottom()), *lane);
+            }
+            for lane in &row.incoming {
+                paint_edge(point(lane_x(*lane), bounds.top()), node, *lane);
+            }
+            for lane in &row.outgoing {
+                paint_edge(node, point(lane_x(*lane), bounds.bottom()), *lane);
+            }
+            window.paint_quad(
+                fill(
+                    Bounds::centered_at(node, size(NODE_SIZE, NODE_SIZE)),
+                    accents.color_for_index(row.lane as u32),
+                )
+                .corner_radii(NODE_SIZE / 2.),
+            );
+        },
+    )
+    .flex_none()
+    .w(LANE_WIDTH * graph_width as f32)
+    .h_full()
+}
+
+impl EventEmitter<ItemEvent> for GitHistory {}
+
+impl Focusable for GitHistory {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for GitHistory {
+    type Event = ItemEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::HistoryRerun).color(Color::Muted))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, _: &App) -> AnyElement {
+        Label::new(self.title())
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Git History Opened")
+    }
+
+    fn show_toolbar(&self) -> bool {
+        false
+    }
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+}
+
+impl Render for GitHistory {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let colors = cx.theme().colors();
+        let filter = |editor: &Entity<Editor>| {
+            div()
+                .flex_1()
+                .px_2()
+                .py_1()
+                .rounded_sm()
+                .border_1()
+                .border_color(colors.border)
+                .child(editor.clone())
+        };
+
+        let content = if !self.entries.is_empty() {
+            uniform_list(
+                cx.entity().clone(),
+                "history-entries",
+                self.entries.len(),
+                |this, range, _, cx| {
+                    if range.end + PAGE_SIZE / 4 >= this.entries.len() {
+                        this.load_more(cx);
+                    }
+                    range.map(|ix| this.render_entry(ix, cx)).collect()
+                },
+            )
+            .size_full()
+            .track_scroll(self.scroll_handle.clone())
+            .into_any_element()
+        } else {
+            let message = if let Some(error) = &self.error {
+                error.clone()
+            } else if self.load_task.is_some() {
+                "Loading…".into()
+            } else {
+                "No commits".into()
+            };
+            h_flex()
+                .size_full()
+                .justify_center()
+                .child(Label::new(message).color(Color::Muted))
+                .into_any_element()
+        };
+
+        v_flex()
+            .key_context("GitHistory")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::rebase_interactive))
+            .size_full()
+            .bg(colors.editor_background)
+            .child(
+                h_flex()
+                    .p_2()
+                    .gap_2()
+                    .border_b_1()
+                    .border_color(colors.border)
+                    .child(filter(&self.revision_editor))
+                    .child(filter(&self.author_editor))
+                    .child(filter(&self.message_editor)),
+            )
+            .child(div().flex_1().min_h_0().child(content))
+            .children(self.context_menu.as_ref().map(|(menu, position, _)| {
+                deferred(
+                    anchored()
+                        .position(*position)
+                        .anchor(Corner::TopLeft)
+                        .child(menu.clone()),
+                )
+                .with_priority(1)
+            }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(sha: &str, parent_shas: &[&str]) -> LogEntry {
+        LogEntry {
+            sha: sha.to_string().into(),
+            parent_shas: parent_shas
+                .iter()
+                .map(|sha| sha.to_string().into())
+                .collect(),
+            subject: SharedString::default(),
+            author_name: SharedString::default(),
+            author_email: SharedString::default(),
+            commit_timestamp: 0,
+            refs: Vec::new(),
+        }
+    }
+
+    fn row(lane: usize, incoming: &[usize], outgoing: &[usize], passing: &[usize]) -> GraphRow {
+        GraphRow {
+            lane,
+            incoming: incoming.to_vec(),
+            outgoing: outgoing.to_vec(),
+            passing: passing.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_compute_graph() {
+        // Linear history.
+        assert_eq!(
+            compute_graph(&[entry("c", &["b"]), entry("b", &["a"]), entry("a", &[])]),
+            [
+                row(0, &[], &[0], &[]),
+                row(0, &[0], &[0], &[]),
+                row(0, &[0], &[], &[])
+            ]
+        );
+
+        // A merge of a feature branch that forked off from "a".
+        //
+        // m
+        // |\
+        // | f
+        // b |
+        // |/
+        // a
+        assert_eq!(
+            compute_graph(&[
+                entry("m", &["b", "f"]),
+                entry("f", &["a"]),
+                entry("b", &["a"]),
+                entry("a", &[]),
+            ]),
+            [
+                row(0, &[], &[0, 1], &[]),
+                row(1, &[1], &[1], &[0]),
+                row(0, &[0], &[1], &[1]),
+                row(1, &[1], &[], &[]),
+            ]
+        );
+    }
+}
@@ -19,14 +19,19 @@ use editor::{
     scroll::ScrollbarAutoHide,
 };
 use futures::StreamExt as _;
+use futures::channel::oneshot;
 use git::blame::ParsedCommitMessage;
 use git::repository::{
-    Branch, CommitDetails, CommitSummary, DiffType, PushOptions, Remote, RemoteCommandOutput,
-    ResetMode, Upstream, UpstreamTracking, UpstreamTrackingStatus,
+    Branch, CommitDetails, CommitOptions, CommitSummary, DiffType, PushOptions, RebaseStatus,
+    Remote, RemoteCommandOutput, ResetMode, StashEntry, Submodule, SubmoduleStatus, Upstream,
+    UpstreamTracking, UpstreamTrackingStatus,
 };
 use git::status::StageStatus;
 use git::{Commit, ToggleStaged, repository::RepoPath, status::FileStatus};
-use git::{ExpandCommitEditor, RestoreTrackedFiles, StageAll, TrashUntrackedFiles, UnstageAll};
+use git::{
+    ExpandCommitEditor, RestoreTrackedFiles, StageAll, StashAll, StashAllIncludingUntracked,
+    StashPop, TrashUntrackedFiles, UnstageAll,
+};
 use gpui::{
     Action, Animation, AnimationExt as _, Axis, ClickEvent, Corner, DismissEvent, Entity,
     EventEmitter, FocusHandle, Focusable, KeyContext, ListHorizontalSizingBehavior,
@@ -35,7 +40,7 @@ use gpui::{
     WeakEntity, actions, anchored, deferred, percentage, uniform_list,
 };
 use itertools::Itertools;
-use language::{Buffer, File};
+use language::{Buffer, BufferId, File};
 use language_model::{
     LanguageModel, LanguageModelRegistry, LanguageModelRequest, LanguageModelRequestMessage, Role,
 };
@@ -60,8 +65,8 @@ use std::{collections::HashSet, sync::Arc, time::Duration, usize};
 use strum::{IntoEnumIterator, VariantNames};
 use time::OffsetDateTime;
 use ui::{
-    Checkbox, ContextMenu, ElevationIndex, PopoverMenu, Scrollbar, ScrollbarState, Tooltip,
-    prelude::*,
+    Checkbox, ContextMenu, Disclosure, ElevationIndex, PopoverMenu, Scrollbar, ScrollbarState,
+    Tooltip, prelude::*,
 };
 use util::{ResultExt, TryFutureExt, maybe, post_inc};
 use workspace::AppState;
@@ -82,6 +87,8 @@ actions!(
         FocusEditor,
         FocusChanges,
         ToggleFillCoAuthors,
+        ToggleAmend,
+        ToggleSignoff,
         GenerateCommitMessage
     ]
 );
@@ -106,6 +113,13 @@ enum TrashCancel {
     Cancel,
 }
 
+#[derive(strum::EnumIter, strum::VariantNames)]
+#[strum(serialize_all = "title_case")]
+enum DropCancel {
+    Drop,
+    Cancel,
+}
+
 fn git_panel_context_menu(
     focus_handle: FocusHandle,
     window: &mut Window,
@@ -119,6 +133,13 @@ fn git_panel_context_menu(
             .separator()
             .action("Open Diff", project_diff::Diff.boxed_clone())
             .separator()
+            .action("Stash All", StashAll.boxed_clone())
+            .action(
+                "Stash All Including Untracked",
+                StashAllIncludingUntracked.boxed_clone(),
+            )
+            .action("Pop Latest Stash", StashPop.boxed_clone())
+            .separator()
             .action("Discard Tracked Changes", RestoreTrackedFiles.boxed_clone())
             .action("Trash Untracked Files", TrashUntrackedFiles.boxed_clone())
     })
@@ -297,8 +318,22 @@ pub struct GitPanel {
     conflicted_staged_count: usize,
     current_modifiers: Modifiers,
     add_coauthors: bool,
+    /// Whether the next commit amends the last one instead of creating a new commit.
+    amend: bool,
+    /// The last commit's message, when it was filled into the commit editor for amending.
+    amend_message: Option<String>,
+    load_amend_message_task: Task<()>,
+    signoff: bool,
     generate_commit_message_task: Option<Task<Option<()>>>,
     entries: Vec<GitListEntry>,
+    stash_entries: Vec<StashEntry>,
+    stash_list_expanded: bool,
+    load_stash_entries_task: Task<()>,
+    rebase_status: RebaseStatus,
+    load_rebase_status_task: Task<()>,
+    submodules: Vec<Submodule>,
+    submodule_list_expanded: bool,
+    load_submodules_task: Task<()>,
     single_staged_entry: Option<GitStatusEntry>,
     single_tracked_entry: Option<GitStatusEntry>,
     focus_handle: FocusHandle,
@@ -426,6 +461,9 @@ impl GitPanel {
                         })
                         .ok();
                 }
+                GitStoreEvent::ConflictsResolved(buffer_id) => {
+                    this.mark_resolved(*buffer_id, cx);
+                }
             },
         )
         .detach();
@@ -465,8 +503,20 @@ impl GitPanel {
             conflicted_staged_count: 0,
             current_modifiers: window.modifiers(),
             add_coauthors: true,
+            amend: false,
+            amend_message: None,
+            load_amend_message_task: Task::ready(()),
+            signoff: false,
             generate_commit_message_task: None,
             entries: Vec::new(),
+            stash_entries: Vec::new(),
+            stash_list_expanded: true,
+            load_stash_entries_task: Task::ready(()),
+            rebase_status: RebaseStatus::Idle,
+            load_rebase_status_task: Task::ready(()),
+            submodules: Vec::new(),
+            submodule_list_expanded: true,
+            load_submodules_task: Task::ready(()),
             focus_handle: cx.focus_handle(),
             fs,
             new_count: 0,
@@ -1454,19 +1504,28 @@ impl GitPanel {
 
         let commit_message = self.custom_or_suggested_commit_message(cx);
 
-        let Some(mut message) = commit_message else {
+        let Some(message) = commit_message else {
             self.commit_editor.read(cx).focus_handle(cx).focus(window);
             return;
         };
 
-        if self.add_coauthors {
-            self.fill_co_authors(&mut message, cx);
-        }
+        let options = CommitOptions {
+            amend: self.amend,
+            signoff: self.signoff,
+            trailers: if self.add_coauthors {
+                self.co_author_trailers(&message, cx)
+            } else {
+                Vec::new()
+            },
+        };
+        let askpass = self.askpass_delegate("git commit", window, cx);
 
-        let task = if self.has_staged_changes() {
+        // Amending may only reword the last commit, so it doesn't need anything staged.
+        let task = if self.has_staged_changes() || self.amend {
             // Repository serializes all git operations, so we can just send a commit immediately
-            let commit_task =
-                active_repository.update(cx, |repo, cx| repo.commit(message.into(), None, cx));
+            let commit_task = active_repository.update(cx, |repo, cx| {
+                repo.commit(message.into(), None, options, askpass, cx)
+            });
             cx.background_spawn(async move { commit_task.await? })
         } else {
             let changed_files = self
@@ -1486,8 +1545,9 @@ impl GitPanel {
                 active_repository.update(cx, |repo, cx| repo.stage_entries(changed_files, cx));
             cx.spawn(async move |_, cx| {
                 stage_task.await?;
-                let commit_task = active_repository
-                    .update(cx, |repo, cx| repo.commit(message.into(), None, cx))?;
+                let commit_task = active_repository.update(cx, |repo, cx| {
+                    repo.commit(message.into(), None, options, askpass, cx)
+                })?;
                 commit_task.await?
             })
         };
@@ -1499,6 +1559,8 @@ impl GitPanel {
                     Ok(()) => {
                         this.commit_editor
                             .update(cx, |editor, cx| editor.clear(window, cx));
+                        this.amend = false;
+                        this.amend_message = None;
                     }
                     Err(e) => this.show_error_toast("commit", e, cx),
                 }
@@ -1769,6 +1831,226 @@ impl GitPanel {
             .detach_and_log_err(cx);
     }
 
+    /// Stages a conflicted file once the last of its conflict markers was removed.
+    fn mark_resolved(&mut self, buffer_id: BufferId, cx: &mut Context<Self>) {
+        let Some((repo, path)) = self
+            .project
+            .read(cx)
+            .git_store()
+            .read(cx)
+            .repository_and_path_for_buffer_id(buffer_id, cx)
+        else {
+            return;
+        };
+        repo.update(cx, |repo, cx| repo.stage_entries(vec![path], cx))
+            .detach_and_log_err(cx);
+    }
+
+    fn load_stash_entries(&mut self, cx: &mut Context<Self>) {
+        let Some(repo) = self.active_repository.clone() else {
+            self.stash_entries.clear();
+            self.load_stash_entries_task = Task::ready(());
+            return;
+        };
+        let stash_list = repo.update(cx, |repo, _| repo.stash_list());
+        self.load_stash_entries_task = cx.spawn(async move |this, cx| {
+            let Some(stash_entries) = stash_list.await.ok().and_then(|result| result.log_err())
+            else {
+                return;
+            };
+            this.update(cx, |this, cx| {
+                this.stash_entries = stash_entries;
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    pub(crate) fn stash_all(
+        &mut self,
+        include_untracked: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        let stash = repo.update(cx, |repo, _| repo.stash_push(None, include_untracked));
+        self.run_stash_operation("stash", stash, window, cx);
+    }
+
+    pub(crate) fn stash_pop(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        let pop = repo.update(cx, |repo, _| repo.stash_pop(index));
+        self.run_stash_operation("stash pop", pop, window, cx);
+    }
+
+    fn stash_apply(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        let apply = repo.update(cx, |repo, _| repo.stash_apply(index));
+        self.run_stash_operation("stash apply", apply, window, cx);
+    }
+
+    fn stash_drop(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        let Some(stash) = self.stash_entries.get(index) else {
+            return;
+        };
+        let prompt = prompt(
+            &format!("Drop {}?", stash.name()),
+            Some(stash.message.as_ref()),
+            window,
+            cx,
+        );
+        cx.spawn_in(window, async move |this, cx| {
+            match prompt.await? {
+                DropCancel::Drop => {}
+                DropCancel::Cancel => return Ok(()),
+            }
+            this.update_in(cx, |this, window, cx| {
+                let drop = repo.update(cx, |repo, _| repo.stash_drop(index));
+                this.run_stash_operation("stash drop", drop, window, cx);
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn run_stash_operation(
+        &mut self,
+        action: &'static str,
+        operation: oneshot::Receiver<Result<()>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        cx.spawn_in(window, async move |this, cx| {
+            let result = operation.await?;
+            this.update(cx, |this, cx| match result {
+                Ok(()) => this.load_stash_entries(cx),
+                Err(e) => this.show_error_toast(action, e, cx),
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn load_submodules(&mut self, cx: &mut Context<Self>) {
+        let Some(repo) = self.active_repository.clone() else {
+            self.submodules.clear();
+            self.load_submodules_task = Task::ready(());
+            return;
+        };
+        let submodules = repo.update(cx, |repo, _| repo.submodules());
+        self.load_submodules_task = cx.spawn(async move |this, cx| {
+            let Some(submodules) = submodules.await.ok().and_then(|result| result.log_err()) else {
+                return;
+            };
+            this.update(cx, |this, cx| {
+                this.submodules = submodules;
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    /// Checks out the recorded commits of the given submodules, or of all of them when `paths`
+    /// is empty. Uninitialized submodules are only checked out when `init` is set.
+    pub(crate) fn update_submodules(
+        &mut self,
+        paths: Vec<RepoPath>,
+        init: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        let update = repo.update(cx, |repo, _| repo.submodule_update(paths, init));
+        cx.spawn_in(window, async move |this, cx| {
+            let result = update.await?;
+            this.update(cx, |this, cx| match result {
+                Ok(()) => this.load_submodules(cx),
+                Err(e) => this.show_error_toast("submodule update", e, cx),
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn load_rebase_status(&mut self, cx: &mut Context<Self>) {
+        let Some(repo) = self.active_repository.clone() else {
+            self.rebase_status = RebaseStatus::Idle;
+            self.load_rebase_status_task = Task::ready(());
+            return;
+        };
+        let rebase_status = repo.update(cx, |repo, _| repo.rebase_status());
+        self.load_rebase_status_task = cx.spawn(async move |this, cx| {
+            let Some(rebase_status) = rebase_status.await.ok().and_then(|result| result.log_err())
+            else {
+                return;
+            };
+            this.update(cx, |this, cx| {
+                this.rebase_status = rebase_status;
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    pub(crate) fn rebase_continue(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        let operation = repo.update(cx, |repo, _| repo.rebase_continue());
+        self.run_rebase_operation("rebase --continue", operation, window, cx);
+    }
+
+    pub(crate) fn rebase_skip(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        let operation = repo.update(cx, |repo, _| repo.rebase_skip());
+        self.run_rebase_operation("rebase --skip", operation, window, cx);
+    }
+
+    pub(crate) fn rebase_abort(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        let abort = repo.update(cx, |repo, _| repo.rebase_abort());
+        cx.spawn_in(window, async move |this, cx| {
+            let result = abort.await?;
+            this.update(cx, |this, cx| match result {
+                Ok(()) => this.load_rebase_status(cx),
+                Err(e) => this.show_error_toast("rebase --abort", e, cx),
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn run_rebase_operation(
+        &mut self,
+        action: &'static str,
+        operation: oneshot::Receiver<Result<RebaseStatus>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        cx.spawn_in(window, async move |this, cx| {
+            let result = operation.await?;
+            this.update(cx, |this, cx| {
+                match result {
+                    Ok(rebase_status) => this.rebase_status = rebase_status,
+                    Err(e) => this.show_error_toast(action, e, cx),
+                }
+                cx.notify();
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
     pub(crate) fn git_init(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let worktrees = self
             .project
@@ -2124,52 +2406,83 @@ impl GitPanel {
         cx.notify();
     }
 
-    fn fill_co_authors(&mut self, message: &mut String, cx: &mut Context<Self>) {
-        const CO_AUTHOR_PREFIX: &str = "Co-authored-by: ";
+    pub(crate) fn toggle_amend(
+        &mut self,
+        _: &ToggleAmend,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.set_amend(!self.amend, window, cx);
+    }
+
+    /// Switches between creating a new commit and amending the last one. Amending with an empty
+    /// commit message fills in the last commit's message, which is cleared again when switching
+    /// back, unless it was edited.
+    fn set_amend(&mut self, amend: bool, window: &mut Window, cx: &mut Context<Self>) {
+        self.amend = amend;
+        if amend {
+            if self.commit_editor.read(cx).text(cx).trim().is_empty() {
+                let head = self.load_commit_details("HEAD".to_string(), cx);
+                self.load_amend_message_task = cx.spawn_in(window, async move |this, cx| {
+                    let Some(head) = head.await.log_err() else {
+                        return;
+                    };
+                    this.update_in(cx, |this, window, cx| {
+                        if !this.amend || !this.commit_editor.read(cx).text(cx).trim().is_empty() {
+                            return;
+                        }
+                        let message = head.message.trim_end().to_string();
+                        this.commit_editor.update(cx, |editor, cx| {
+                            editor.set_text(message.clone(), window, cx)
+                        });
+                        this.amend_message = Some(message);
+                    })
+                    .ok();
+                });
+            }
+        } else {
+            self.load_amend_message_task = Task::ready(());
+            if let Some(message) = self.amend_message.take() {
+                if self.commit_editor.read(cx).text(cx) == message {
+                    self.commit_editor
+                        .update(cx, |editor, cx| editor.clear(window, cx));
+                }
+            }
+        }
+        cx.notify();
+    }
+
+    pub(crate) fn toggle_signoff(
+        &mut self,
+        _: &ToggleSignoff,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.signoff = !self.signoff;
+        cx.notify();
+    }
+
+    /// The `Co-authored-by` trailers for the collaborators not already credited in the message.
+    fn co_author_trailers(&self, message: &str, cx: &App) -> Vec<(SharedString, SharedString)> {
+        const CO_AUTHOR_KEY: &str = "Co-authored-by";
 
         let existing_text = message.to_ascii_lowercase();
-        let lowercase_co_author_prefix = CO_AUTHOR_PREFIX.to_lowercase();
-        let mut ends_with_co_authors = false;
+        let lowercase_co_author_prefix = format!("{}:", CO_AUTHOR_KEY.to_lowercase());
         let existing_co_authors = existing_text
             .lines()
-            .filter_map(|line| {
-                let line = line.trim();
-                if line.starts_with(&lowercase_co_author_prefix) {
-                    ends_with_co_authors = true;
-                    Some(line)
-                } else {
-                    ends_with_co_authors = false;
-                    None
-                }
-            })
+            .map(|line| line.trim())
+            .filter(|line| line.starts_with(&lowercase_co_author_prefix))
             .collect::<HashSet<_>>();
 
-        let new_co_authors = self
-            .potential_co_authors(cx)
+        self.potential_co_authors(cx)
             .into_iter()
             .filter(|(_, email)| {
                 !existing_co_authors
                     .iter()
                     .any(|existing| existing.contains(email.as_str()))
             })
-            .collect::<Vec<_>>();
-
-        if new_co_authors.is_empty() {
-            return;
-        }
-
-        if !ends_with_co_authors {
-            message.push('\n');
-        }
-        for (name, email) in new_co_authors {
-            message.push('\n');
-            message.push_str(CO_AUTHOR_PREFIX);
-            message.push_str(&name);
-            message.push_str(" <");
-            message.push_str(&email);
-            message.push('>');
-        }
-        message.push('\n');
+            .map(|(name, email)| (CO_AUTHOR_KEY.into(), format!("{name} <{email}>").into()))
+            .collect()
     }
 
     fn schedule_update(
@@ -2189,6 +2502,9 @@ impl GitPanel {
                             git_panel.clear_pending();
                         }
                         git_panel.update_visible_entries(cx);
+                        git_panel.load_stash_entries(cx);
+                        git_panel.load_rebase_status(cx);
+                        git_panel.load_submodules(cx);
                         git_panel.update_scrollbar_properties(window, cx);
                     })
                     .ok();
@@ -2722,10 +3038,61 @@ impl GitPanel {
         }
     }
 
+    pub(crate) fn render_commit_options(&self, cx: &Context<Self>) -> AnyElement {
+        let git_panel = cx.weak_entity();
+        let amend = self.amend;
+        let signoff = self.signoff;
+        PopoverMenu::new("commit-options")
+            .trigger_with_tooltip(
+                IconButton::new("commit-options-trigger", IconName::ChevronDown)
+                    .shape(ui::IconButtonShape::Square)
+                    .icon_color(Color::Muted)
+                    .selected_icon_color(Color::Selected)
+                    .toggle_state(amend || signoff),
+                Tooltip::text("Commit Options"),
+            )
+            .menu(move |window, cx| {
+                let git_panel = git_panel.clone();
+                Some(ContextMenu::build(window, cx, move |menu, _, _| {
+                    menu.toggleable_entry(
+                        "Amend Last Commit",
+                        amend,
+                        IconPosition::Start,
+                        Some(ToggleAmend.boxed_clone()),
+                        {
+                            let git_panel = git_panel.clone();
+                            move |window, cx| {
+                                git_panel
+                                    .update(cx, |git_panel, cx| {
+                                        git_panel.toggle_amend(&ToggleAmend, window, cx)
+                                    })
+                                    .ok();
+                            }
+                        },
+                    )
+                    .toggleable_entry(
+                        "Add Signed-off-by",
+                        signoff,
+                        IconPosition::Start,
+                        Some(ToggleSignoff.boxed_clone()),
+                        move |window, cx| {
+                            git_panel
+                                .update(cx, |git_panel, cx| {
+                                    git_panel.toggle_signoff(&ToggleSignoff, window, cx)
+                                })
+                                .ok();
+                        },
+                    )
+                }))
+            })
+            .anchor(Corner::BottomRight)
+            .into_any_element()
+    }
+
     pub fn configure_commit_button(&self, cx: &mut Context<Self>) -> (bool, &'static str) {
         if self.has_unstaged_conflicts() {
             (false, "You must resolve conflicts before committing")
-        } else if !self.has_staged_changes() && !self.has_tracked_changes() {
+        } else if !self.has_staged_changes() && !self.has_tracked_changes() && !self.amend {
             (false, "No changes to commit")
         } else if self.pending_commit.is_some() {
             (false, "Commit in progress")
@@ -2739,7 +3106,9 @@ impl GitPanel {
     }
 
     pub fn commit_button_title(&self) -> &'static str {
-        if self.has_staged_changes() {
+        if self.amend {
+            "Amend"
+        } else if self.has_staged_changes() {
             "Commit"
         } else {
             "Commit Tracked"
@@ -2861,6 +3230,7 @@ impl GitPanel {
         let panel_editor_style = panel_editor_style(true, window, cx);
 
         let enable_coauthors = self.render_co_authors(cx);
+        let commit_options = self.render_commit_options(cx);
         let title = self.commit_button_title();
 
         let editor_focus_handle = self.commit_editor.focus_handle(cx);
@@ -2922,32 +3292,38 @@ impl GitPanel {
                                     .unwrap_or_else(|| div().into_any_element()),
                             )
                             .child(
-                                h_flex().gap_0p5().children(enable_coauthors).child(
-                                    panel_filled_button(title)
-                                        .tooltip(move |window, cx| {
-                                            if can_commit {
-                                                Tooltip::for_action_in(
-                                                    tooltip,
-                                                    &Commit,
-                                                    &commit_tooltip_focus_handle,
-                                                    window,
-                                                    cx,
-                                                )
-                                            } else {
-                                                Tooltip::simple(tooltip, cx)
-                                            }
-                                        })
-                                        .disabled(!can_commit || self.modal_open)
-                                        .on_click({
-                                            cx.listener(move |this, _: &ClickEvent, window, cx| {
-                                                telemetry::event!(
-                                                    "Git Committed",
-                                                    source = "Git Panel"
-                                                );
-                                                this.commit_changes(window, cx)
+                                h_flex()
+                                    .gap_0p5()
+                                    .children(enable_coauthors)
+                                    .child(commit_options)
+                                    .child(
+                                        panel_filled_button(title)
+                                            .tooltip(move |window, cx| {
+                                                if can_commit {
+                                                    Tooltip::for_action_in(
+                                                        tooltip,
+                                                        &Commit,
+                                                        &commit_tooltip_focus_handle,
+                                                        window,
+                                                        cx,
+                                                    )
+                                                } else {
+                                                    Tooltip::simple(tooltip, cx)
+                                                }
                                             })
-                                        }),
-                                ),
+                                            .disabled(!can_commit || self.modal_open)
+                                            .on_click({
+                                                cx.listener(
+                                                    move |this, _: &ClickEvent, window, cx| {
+                                                        telemetry::event!(
+                                                            "Git Committed",
+                                                            source = "Git Panel"
+                                                        );
+                                                        this.commit_changes(window, cx)
+                                                    },
+                                                )
+                                            }),
+                                    ),
                             ),
                     )
                     .child(
@@ -3076,6 +3452,363 @@ impl GitPanel {
         )
     }
 
+    fn render_rebase_banner(
+        &self,
+        has_write_access: bool,
+        cx: &mut Context<Self>,
+    ) -> Option<impl IntoElement> {
+        let RebaseStatus::Stopped { commit } = &self.rebase_status else {
+            return None;
+        };
+        let message = match commit {
+            Some(sha) => format!(
+                "Rebase stopped at {}",
+                sha.get(..git::SHORT_SHA_LENGTH).unwrap_or(sha)
+            ),
+            None => "Rebase in progress".to_string(),
+        };
+        let detail = if self.conflicted_count > 0 {
+            "Resolve and stage the conflicts to continue"
+        } else {
+            "Stage your changes to continue"
+        };
+
+        Some(
+            v_flex()
+                .px(rems(0.75))
+                .py_2()
+                .gap_1()
+                .border_b_1()
+                .border_color(cx.theme().colors().border)
+                .child(
+                    h_flex()
+                        .gap_1p5()
+                        .child(
+                            Icon::new(IconName::Warning)
+                                .size(IconSize::Small)
+                                .color(Color::Warning),
+                        )
+                        .child(Label::new(message).size(LabelSize::Small)),
+                )
+                .child(
+                    Label::new(detail)
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+                .when(has_write_access, |this| {
+                    this.child(
+                        h_flex()
+                            .gap_1()
+                            .justify_end()
+                            .child(
+                                panel_button("Abort")
+                                    .tooltip(Tooltip::for_action_title_in(
+                                        "git rebase --abort",
+                                        &git::RebaseAbort,
+                                        &self.focus_handle,
+                                    ))
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.rebase_abort(window, cx)
+                                    })),
+                            )
+                            .child(
+                                panel_button("Skip")
+                                    .tooltip(Tooltip::for_action_title_in(
+                                        "git rebase --skip",
+                                        &git::RebaseSkip,
+                                        &self.focus_handle,
+                                    ))
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.rebase_skip(window, cx)
+                                    })),
+                            )
+                            .child(
+                                panel_filled_button("Continue")
+                                    .tooltip(Tooltip::for_action_title_in(
+                                        "git rebase --continue",
+                                        &git::RebaseContinue,
+                                        &self.focus_handle,
+                                    ))
+                                    .disabled(self.has_unstaged_conflicts())
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.rebase_continue(window, cx)
+                                    })),
+                            ),
+                    )
+                }),
+        )
+    }
+
+    fn render_stash_list(
+        &self,
+        has_write_access: bool,
+        cx: &mut Context<Self>,
+    ) -> Option<impl IntoElement> {
+        if self.stash_entries.is_empty() {
+            return None;
+        }
+        let active_repository = self.active_repository.as_ref()?;
+
+        Some(
+            v_flex()
+                .border_t_1()
+                .border_color(cx.theme().colors().border)
+                .child(
+                    h_flex()
+                        .id("stash-list-header")
+                        .h(self.list_item_height())
+                        .w_full()
+                        .px(rems(0.75))
+                        .gap_1()
+                        .cursor_pointer()
+                        .child(Disclosure::new(
+                            "stash-list-disclosure",
+                            self.stash_list_expanded,
+                        ))
+                        .child(
+                            Label::new(format!("Stashes ({})", self.stash_entries.len()))
+                                .color(Color::Muted)
+                                .size(LabelSize::Small)
+                                .line_height_style(LineHeightStyle::UiLabel)
+                                .single_line(),
+                        )
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.stash_list_expanded = !this.stash_list_expanded;
+                            cx.notify();
+                        })),
+                )
+                .when(self.stash_list_expanded, |this| {
+                    this.child(
+                        v_flex()
+                            .id("stash-list")
+                            .max_h(rems(10.))
+                            .overflow_y_scroll()
+                            .children(self.stash_entries.iter().map(|stash| {
+                                self.render_stash_entry(
+                                    stash,
+                                    active_repository,
+                                    has_write_access,
+                                    cx,
+                                )
+                            })),
+                    )
+                }),
+        )
+    }
+
+    fn render_submodule_list(
+        &self,
+        has_write_access: bool,
+        cx: &mut Context<Self>,
+    ) -> Option<impl IntoElement> {
+        if self.submodules.is_empty() {
+            return None;
+        }
+
+        Some(
+            v_flex()
+                .border_t_1()
+                .border_color(cx.theme().colors().border)
+                .child(
+                    h_flex()
+                        .id("submodule-list-header")
+                        .h(self.list_item_height())
+                        .w_full()
+                        .px(rems(0.75))
+                        .gap_1()
+                        .cursor_pointer()
+                        .child(Disclosure::new(
+                            "submodule-list-disclosure",
+                            self.submodule_list_expanded,
+                        ))
+                        .child(
+                            Label::new(format!("Submodules ({})", self.submodules.len()))
+                                .color(Color::Muted)
+                                .size(LabelSize::Small)
+                                .line_height_style(LineHeightStyle::UiLabel)
+                                .single_line(),
+                        )
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.submodule_list_expanded = !this.submodule_list_expanded;
+                            cx.notify();
+                        })),
+                )
+                .when(self.submodule_list_expanded, |this| {
+                    this.child(
+                        v_flex()
+                            .id("submodule-list")
+                            .max_h(rems(10.))
+                            .overflow_y_scroll()
+                            .children(self.submodules.iter().enumerate().map(|(ix, submodule)| {
+                                self.render_submodule(ix, submodule, has_write_access, cx)
+                            })),
+                    )
+                }),
+        )
+    }
+
+    fn render_submodule(
+        &self,
+        ix: usize,
+        submodule: &Submodule,
+        has_write_access: bool,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let (status_label, status_color) = match submodule.status {
+            SubmoduleStatus::Uninitialized => ("Not initialized", Color::Disabled),
+            SubmoduleStatus::UpToDate if submodule.has_changes => {
+                ("Uncommitted changes", Color::VersionControlModified)
+            }
+            SubmoduleStatus::UpToDate => ("Up to date", Color::Muted),
+            SubmoduleStatus::Modified if submodule.has_changes => (
+                "Modified, uncommitted changes",
+                Color::VersionControlModified,
+            ),
+            SubmoduleStatus::Modified => ("Modified", Color::VersionControlModified),
+            SubmoduleStatus::Conflicted => ("Conflict", Color::VersionControlConflict),
+        };
+        let init = submodule.status == SubmoduleStatus::Uninitialized;
+        let (icon, tooltip) = if init {
+            (IconName::Download, "Initialize Submodule")
+        } else {
+            (IconName::ArrowCircle, "Update Submodule")
+        };
+        let path = submodule.path.clone();
+
+        h_flex()
+            .id(ElementId::NamedInteger("submodule".into(), ix))
+            .group("submodule-entry")
+            .h(self.list_item_height())
+            .w_full()
+            .px(rems(0.75))
+            .gap_1p5()
+            .hover(|this| this.bg(cx.theme().colors().ghost_element_hover))
+            .child(
+                div().flex_grow().overflow_hidden().child(
+                    Label::new(submodule.path.to_string_lossy().into_owned())
+                        .size(LabelSize::Small)
+                        .truncate(),
+                ),
+            )
+            .child(
+                Label::new(
+                    submodule
+                        .sha
+                        .get(..git::SHORT_SHA_LENGTH)
+                        .unwrap_or(&submodule.sha)
+                        .to_string(),
+                )
+                .color(Color::Muted)
+                .size(LabelSize::Small)
+                .single_line(),
+            )
+            .child(
+                Label::new(status_label)
+                    .color(status_color)
+                    .size(LabelSize::Small)
+                    .single_line(),
+            )
+            .when(
+                has_write_access && submodule.status != SubmoduleStatus::Conflicted,
+                |this| {
+                    this.child(
+                        panel_icon_button(format!("submodule-update-{ix}"), icon)
+                            .visible_on_hover("submodule-entry")
+                            .icon_size(IconSize::Small)
+                            .icon_color(Color::Muted)
+                            .tooltip(Tooltip::text(tooltip))
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                this.update_submodules(vec![path.clone()], init, window, cx)
+                            })),
+                    )
+                },
+            )
+    }
+
+    fn render_stash_entry(
+        &self,
+        stash: &StashEntry,
+        active_repository: &Entity<Repository>,
+        has_write_access: bool,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let index = stash.index;
+        let workspace = self.workspace.clone();
+        let repo = active_repository.downgrade();
+
+        h_flex()
+            .id(ElementId::NamedInteger("stash".into(), index))
+            .group("stash-entry")
+            .h(self.list_item_height())
+            .w_full()
+            .px(rems(0.75))
+            .gap_1p5()
+            .cursor_pointer()
+            .hover(|this| this.bg(cx.theme().colors().ghost_element_hover))
+            .child(
+                Label::new(stash.name())
+                    .color(Color::Muted)
+                    .size(LabelSize::Small)
+                    .single_line(),
+            )
+            .child(
+                div().flex_grow().overflow_hidden().child(
+                    Label::new(stash.message.clone())
+                        .size(LabelSize::Small)
+                        .truncate(),
+                ),
+            )
+            .when(has_write_access, |this| {
+                this.child(
+                    h_flex()
+                        .gap_0p5()
+                        .visible_on_hover("stash-entry")
+                        .child(
+                            panel_icon_button(format!("stash-apply-{index}"), IconName::Check)
+                                .icon_size(IconSize::Small)
+                                .icon_color(Color::Muted)
+                                .tooltip(Tooltip::text("Apply Stash"))
+                                .on_click(cx.listener(move |this, _, window, cx| {
+                                    this.stash_apply(index, window, cx)
+                                })),
+                        )
+                        .child(
+                            panel_icon_button(
+                                format!("stash-pop-{index}"),
+                                IconName::ArrowUpFromLine,
+                            )
+                            .icon_size(IconSize::Small)
+                            .icon_color(Color::Muted)
+                            .tooltip(Tooltip::text("Pop Stash"))
+                            .on_click(cx.listener(
+                                move |this, _, window, cx| this.stash_pop(index, window, cx),
+                            )),
+                        )
+                        .child(
+                            panel_icon_button(format!("stash-drop-{index}"), IconName::Trash)
+                                .icon_size(IconSize::Small)
+                                .icon_color(Color::Muted)
+                                .tooltip(Tooltip::text("Drop Stash"))
+                                .on_click(cx.listener(move |this, _, window, cx| {
+                                    this.stash_drop(index, window, cx)
+                                })),
+                        ),
+                )
+            })
+            .on_click({
+                let stash = stash.clone();
+                move |_, window, cx| {
+                    CommitView::open_stash(
+                        stash.clone(),
+                        repo.clone(),
+                        workspace.clone(),
+                        window,
+                        cx,
+                    );
+                }
+            })
+    }
+
     fn render_empty_state(&self, cx: &mut Context<Self>) -> impl IntoElement {
         h_flex()
             .h_full()
@@ -3819,6 +4552,11 @@ impl Render for GitPanel {
             .when(has_write_access && has_co_authors, |git_panel| {
                 git_panel.on_action(cx.listener(Self::toggle_fill_co_authors))
             })
+            .when(has_write_access, |git_panel| {
+                git_panel
+                    .on_action(cx.listener(Self::toggle_amend))
+                    .on_action(cx.listener(Self::toggle_signoff))
+            })
             .on_hover(cx.listener(move |this, hovered, window, cx| {
                 if *hovered {
                     this.horizontal_scrollbar.show(cx);
@@ -3835,6 +4573,7 @@ impl Render for GitPanel {
                 v_flex()
                     .size_full()
                     .children(self.render_panel_header(window, cx))
+                    .children(self.render_rebase_banner(has_write_access, cx))
                     .map(|this| {
                         if has_entries {
                             this.child(self.render_entries(has_write_access, window, cx))
@@ -3842,6 +4581,8 @@ impl Render for GitPanel {
                             this.child(self.render_empty_state(cx).into_any_element())
                         }
                     })
+                    .children(self.render_submodule_list(has_write_access, cx))
+                    .children(self.render_stash_list(has_write_access, cx))
                     .children(self.render_footer(window, cx))
                     .children(self.render_previous_commit(cx))
                     .into_any_element(),
@@ -4451,7 +5192,7 @@ impl ComponentPreview for PanelRepoFooter {
 
 #[cfg(test)]
 mod tests {
-    use git::status::StatusCode;
+    use git::status::{StatusCode, UnmergedStatus, UnmergedStatusCode};
     use gpui::TestAppContext;
     use project::{FakeFs, WorktreeSettings};
     use serde_json::json;
@@ -4644,4 +5385,73 @@ mod tests {
             ],
         );
     }
+
+    #[gpui::test]
+    async fn test_stage_resolved_conflicts(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "a.txt": "one\n<<<<<<< HEAD\ntwo\n=======\nthree\n>>>>>>> feature\nfour\n",
+            }),
+        )
+        .await;
+        let dot_git = Path::new(path!("/project/.git"));
+        fs.set_status_for_repo(
+            dot_git,
+            &[(
+                Path::new("a.txt"),
+                FileStatus::Unmerged(UnmergedStatus {
+                    first_head: UnmergedStatusCode::Updated,
+                    second_head: UnmergedStatusCode::Updated,
+                }),
+            )],
+        );
+        let index_text = |fs: &FakeFs| {
+            fs.with_git_state(dot_git, false, |state| {
+                state.index_contents.get(Path::new("a.txt")).cloned()
+            })
+            .unwrap()
+        };
+        let conflicted_index_text = index_text(&fs);
+
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        cx.executor().run_until_parked();
+        let app_state = workspace.update(cx, |workspace, _| workspace.app_state().clone());
+        let _panel = cx.new_window_entity(|window, cx| {
+            GitPanel::new(workspace.clone(), project.clone(), app_state, window, cx)
+        });
+
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/project/a.txt"), cx)
+            })
+            .await
+            .unwrap();
+        let _conflict_set = project.update(cx, |project, cx| {
+            project.git_store().update(cx, |git_store, cx| {
+                git_store.open_conflict_set(buffer.clone(), cx)
+            })
+        });
+        cx.executor().run_until_parked();
+
+        // Deleting a single marker leaves an incomplete region, which isn't a resolution.
+        buffer.update(cx, |buffer, cx| {
+            let start = buffer.text().find(">>>>>>> feature\n").unwrap();
+            buffer.edit([(start..start + ">>>>>>> feature\n".len(), "")], None, cx);
+        });
+        cx.executor().run_until_parked();
+        assert_eq!(index_text(&fs), conflicted_index_text);
+        assert!(buffer.read_with(cx, |buffer, _| buffer.is_dirty()));
+
+        buffer.update(cx, |buffer, cx| {
+            buffer.set_text("one\ntwo\nfour\n", cx);
+        });
+        cx.executor().run_until_parked();
+        assert_eq!(index_text(&fs).as_deref(), Some("one\ntwo\nfour\n"));
+    }
 }
@@ -8,6 +8,7 @@ use git::{
     repository::{Branch, Upstream, UpstreamTracking, UpstreamTrackingStatus},
     status::{FileStatus, StatusCode, UnmergedStatus, UnmergedStatusCode},
 };
+use git_history::GitHistory;
 use git_panel_settings::GitPanelSettings;
 use gpui::{App, FocusHandle, actions};
 use onboarding::GitOnboardingModal;
@@ -20,11 +21,15 @@ pub mod branch_picker;
 mod commit_modal;
 pub mod commit_tooltip;
 mod commit_view;
+mod conflict_view;
+pub mod file_revision_view;
+pub mod git_history;
 pub mod git_panel;
 mod git_panel_settings;
 pub mod onboarding;
 pub mod picker_prompt;
 pub mod project_diff;
+pub mod rebase_view;
 pub(crate) mod remote_output;
 pub mod repository_selector;
 
@@ -35,6 +40,11 @@ pub fn init(cx: &mut App) {
 
     editor::set_blame_renderer(blame_ui::GitBlameRenderer, cx);
 
+    cx.observe_new(|editor: &mut editor::Editor, _, cx| {
+        conflict_view::register_editor(editor, cx);
+    })
+    .detach();
+
     cx.observe_new(|workspace: &mut Workspace, _, cx| {
         ProjectDiff::register(workspace, cx);
         CommitModal::register(workspace);
@@ -96,6 +106,80 @@ pub fn init(cx: &mut App) {
                 panel.unstage_all(action, window, cx);
             });
         });
+        workspace.register_action(|workspace, _: &git::ViewHistory, window, cx| {
+            let Some(repository) = workspace.project().read(cx).active_repository(cx) else {
+                return;
+            };
+            GitHistory::open(repository, None, workspace, window, cx);
+        });
+        workspace.register_action(git_history::open_file_history);
+        workspace.register_action(branch_picker::rebase_interactive);
+        workspace.register_action(|workspace, _: &git::RebaseContinue, window, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.rebase_continue(window, cx);
+            });
+        });
+        workspace.register_action(|workspace, _: &git::RebaseSkip, window, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.rebase_skip(window, cx);
+            });
+        });
+        workspace.register_action(|workspace, _: &git::RebaseAbort, window, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.rebase_abort(window, cx);
+            });
+        });
+        workspace.register_action(|workspace, _: &git::InitSubmodules, window, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.update_submodules(Vec::new(), true, window, cx);
+            });
+        });
+        workspace.register_action(|workspace, _: &git::UpdateSubmodules, window, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.update_submodules(Vec::new(), false, window, cx);
+            });
+        });
+        workspace.register_action(|workspace, _: &git::StashAll, window, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.stash_all(false, window, cx);
+            });
+        });
+        workspace.register_action(
+            |workspace, _: &git::StashAllIncludingUntracked, window, cx| {
+                let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                    return;
+                };
+                panel.update(cx, |panel, cx| {
+                    panel.stash_all(true, window, cx);
+                });
+            },
+        );
+        workspace.register_action(|workspace, _: &git::StashPop, window, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.stash_pop(0, window, cx);
+            });
+        });
         CommandPaletteFilter::update_global(cx, |filter, _cx| {
             filter.hide_action_types(&[
                 zed_actions::OpenGitIntegrationOnboarding.type_id(),
@@ -0,0 +1,560 @@
+use editor::Editor;
+use git::repository::{LogEntry, LogOptions, RebaseAction, RebaseStatus, RebaseStep};
+use gpui::{
+    DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Pixels, ScrollStrategy,
+    SharedString, Task, UniformListScrollHandle, WeakEntity, actions, uniform_list,
+};
+use menu::{Cancel, Confirm, SelectNext, SelectPrevious};
+use project::git_store::Repository;
+use theme::ActiveTheme as _;
+use ui::{Tooltip, prelude::*};
+use workspace::{ModalView, Workspace};
+
+use crate::git_panel::GitPanel;
+
+actions!(
+    git_rebase,
+    [
+        MoveCommitUp,
+        MoveCommitDown,
+        PickCommit,
+        RewordCommit,
+        SquashCommit,
+        FixupCommit,
+        DropCommit
+    ]
+);
+
+/// The maximum number of commits that can be rebased at once. Larger ranges are rejected
+/// rather than truncated, as commits missing from the todo list would be dropped.
+const MAX_COMMITS: usize = 500;
+const ROW_HEIGHT: Pixels = px(28.);
+
+/// Lets the user edit the todo list of an interactive rebase onto `base` and starts it.
+///
+/// Merge commits of the range are left out of the list, as `git rebase` linearizes the
+/// history unless asked to rebase merges.
+pub struct RebaseModal {
+    repository: Entity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    base: SharedString,
+    rows: Vec<RebaseRow>,
+    selected_index: usize,
+    loading: bool,
+    error: Option<SharedString>,
+    rebase_task: Option<Task<()>>,
+    scroll_handle: UniformListScrollHandle,
+    focus_handle: FocusHandle,
+    _load_task: Task<()>,
+}
+
+struct RebaseRow {
+    entry: LogEntry,
+    action: RebaseAction,
+    /// The new message of the commit, created the first time it is reworded.
+    message_editor: Option<Entity<Editor>>,
+}
+
+impl RebaseModal {
+    pub fn open(
+        repository: Entity<Repository>,
+        base: SharedString,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let workspace_handle = cx.entity().downgrade();
+        workspace.toggle_modal(window, cx, |window, cx| {
+            Self::new(repository, base, workspace_handle, window, cx)
+        });
+    }
+
+    fn new(
+        repository: Entity<Repository>,
+        base: SharedString,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let log = repository.read(cx).log(LogOptions {
+            revision: Some(format!("{base}..HEAD")),
+            limit: MAX_COMMITS + 1,
+            ..Default::default()
+        });
+        let load_task = cx.spawn(async move |this, cx| {
+            let result = log.await.map_err(anyhow::Error::from).and_then(|log| log);
+            this.update(cx, |this, cx| {
+                this.loading = false;
+                match result {
+                    Ok(entries) if entries.len() > MAX_COMMITS => {
+                        this.error = Some(
+                            format!("Can't rebase more than {MAX_COMMITS} commits at once").into(),
+                        );
+                    }
+                    Ok(entries) => {
+                        // The log lists the most recent commits first, while the todo list
+                        // applies the oldest ones first.
+                        this.rows = entries
+                            .into_iter()
+                            .rev()
+                            .filter(|entry| entry.parent_shas.len() <= 1)
+                            .map(|entry| RebaseRow {
+                                entry,
+                                action: RebaseAction::Pick,
+                                message_editor: None,
+                            })
+                            .collect();
+                    }
+                    Err(error) => this.error = Some(error.to_string().into()),
+                }
+                cx.notify();
+            })
+            .ok();
+        });
+
+        let focus_handle = cx.focus_handle();
+        focus_handle.focus(window);
+        Self {
+            repository,
+            workspace,
+            base,
+            rows: Vec::new(),
+            selected_index: 0,
+            loading: true,
+            error: None,
+            rebase_task: None,
+            scroll_handle: UniformListScrollHandle::new(),
+            focus_handle,
+            _load_task: load_task,
+        }
+    }
+
+    fn select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        if self.selected_index + 1 < self.rows.len() {
+            self.select(self.selected_index + 1, cx);
+        }
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, _: &mut Window, cx: &mut Context<Self>) {
+        if self.selected_index > 0 {
+            self.select(self.selected_index - 1, cx);
+        }
+    }
+
+    fn select(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.selected_index = ix;
+        self.scroll_handle
+            .scroll_to_item(ix, ScrollStrategy::Center);
+        cx.notify();
+    }
+
+    fn move_commit_up(&mut self, _: &MoveCommitUp, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_commit(self.selected_index, -1, cx);
+    }
+
+    fn move_commit_down(&mut self, _: &MoveCommitDown, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_commit(self.selected_index, 1, cx);
+    }
+
+    fn move_commit(&mut self, ix: usize, delta: isize, cx: &mut Context<Self>) {
+        let Some(target_ix) = ix.checked_add_signed(delta) else {
+            return;
+        };
+        if target_ix >= self.rows.len() || ix >= self.rows.len() {
+            return;
+        }
+        self.rows.swap(ix, target_ix);
+        self.error = None;
+        self.select(target_ix, cx);
+    }
+
+    fn pick_commit(&mut self, _: &PickCommit, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(self.selected_index, RebaseAction::Pick, window, cx);
+    }
+
+    fn reword_commit(&mut self, _: &RewordCommit, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(self.selected_index, RebaseAction::Reword, window, cx);
+    }
+
+    fn squash_commit(&mut self, _: &SquashCommit, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(self.selected_index, RebaseAction::Squash, window, cx);
+    }
+
+    fn fixup_commit(&mut self, _: &FixupCommit, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(self.selected_index, RebaseAction::Fixup, window, cx);
+    }
+
+    fn drop_commit(&mut self, _: &DropCommit, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(self.selected_index, RebaseAction::Drop, window, cx);
+    }
+
+    fn cycle_action(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(row) = self.rows.get(ix) else {
+            return;
+        };
+        let action = match row.action {
+            RebaseAction::Pick => RebaseAction::Reword,
+            RebaseAction::Reword => RebaseAction::Squash,
+            RebaseAction::Squash => RebaseAction::Fixup,
+            RebaseAction::Fixup => RebaseAction::Drop,
+            RebaseAction::Drop => RebaseAction::Pick,
+        };
+        self.selected_index = ix;
+        self.set_action(ix, action, window, cx);
+    }
+
+    fn set_action(
+        &mut self,
+        ix: usize,
+        action: RebaseAction,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(row) = self.rows.get_mut(ix) else {
+            return;
+        };
+        row.action = action;
+        if action == RebaseAction::Reword && row.message_editor.is_none() {
+            let editor = cx.new(|cx| {
+                let mut editor = Editor::auto_height(6, window, cx);
+                editor.set_text(row.entry.subject.clone(), window, cx);
+                editor
+            });
+            // Replace the subject with the full message once it is loaded, unless the user
+            // started editing it already.
+            let show = self.repository.read(cx).show(row.entry.sha.to_string());
+            let subject = row.entry.subject.clone();
+            cx.spawn_in(window, {
+                let editor = editor.clone();
+                async move |_, cx| {
+                    let details = show.await??;
+                    editor.update_in(cx, |editor, window, cx| {
+                        if editor.text(cx) == subject.as_ref() {
+                            editor.set_text(details.message.trim_end(), window, cx);
+                        }
+                    })
+                }
+            })
+            .detach_and_log_err(cx);
+            row.message_editor = Some(editor);
+        }
+        self.error = None;
+        cx.notify();
+    }
+
+    fn steps(&self, cx: &App) -> Result<Vec<RebaseStep>, SharedString> {
+        let first_kept = self
+            .rows
+            .iter()
+            .find(|row| row.action != RebaseAction::Drop);
+        if first_kept
+            .is_some_and(|row| matches!(row.action, RebaseAction::Squash | RebaseAction::Fixup))
+        {
+            return Err("The first commit can't be squashed into a previous one".into());
+        }
+
+        self.rows
+            .iter()
+            .map(|row| {
+                let message = match (&row.action, &row.message_editor) {
+                    (RebaseAction::Reword, Some(editor)) => {
+                        let message = editor.read(cx).text(cx);
+                        if message.trim().is_empty() {
+                            return Err(format!(
+                                "The new message of {} is empty",
+                                short_sha(&row.entry)
+                            )
+                            .into());
+                        }
+                        Some(message)
+                    }
+                    _ => None,
+                };
+                Ok(RebaseStep {
+                    action: row.action,
+                    sha: row.entry.sha.clone(),
+                    message,
+                })
+            })
+            .collect()
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if self.rebase_task.is_some() || self.rows.is_empty() {
+            return;
+        }
+        let steps = match self.steps(cx) {
+            Ok(steps) => steps,
+            Err(error) => {
+                self.error = Some(error);
+                cx.notify();
+                return;
+            }
+        };
+
+        let rebase = self
+            .repository
+            .read(cx)
+            .rebase_interactive(self.base.to_string(), steps);
+        self.error = None;
+        self.rebase_task = Some(cx.spawn_in(window, async move |this, cx| {
+            let result = rebase
+                .await
+                .map_err(anyhow::Error::from)
+                .and_then(|result| result);
+            this.update_in(cx, |this, window, cx| {
+                this.rebase_task = None;
+                match result {
+                    Ok(RebaseStatus::Idle) => cx.emit(DismissEvent),
+                    Ok(RebaseStatus::Stopped { .. }) => {
+                        // The Git panel offers to continue, skip or abort the stopped rebase.
+                        this.workspace
+                            .update(cx, |workspace, cx| {
+                                workspace.focus_panel::<GitPanel>(window, cx);
+                            })
+                            .ok();
+                        cx.emit(DismissEvent);
+                    }
+                    Err(error) => {
+                        this.error = Some(error.to_string().trim().to_string().into());
+                        cx.notify();
+                    }
+                }
+            })
+            .ok();
+        }));
+        cx.notify();
+    }
+
+    fn cancel(&mut self, _: &Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_row(&self, ix: usize, cx: &mut Context<Self>) -> AnyElement {
+        let row = &self.rows[ix];
+        let colors = cx.theme().colors();
+        let is_dropped = row.action == RebaseAction::Drop;
+        let action_color = match row.action {
+            RebaseAction::Pick => Color::Default,
+            RebaseAction::Reword => Color::Accent,
+            RebaseAction::Squash | RebaseAction::Fixup => Color::Warning,
+            RebaseAction::Drop => Color::Error,
+        };
+        let subject = match &row.message_editor {
+            Some(editor) if row.action == RebaseAction::Reword => editor
+                .read(cx)
+                .text(cx)
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .to_string()
+                .into(),
+            _ => row.entry.subject.clone(),
+        };
+
+        h_flex()
+            .id(("rebase-row", ix))
+            .group("rebase-row")
+            .h(ROW_HEIGHT)
+            .w_full()
+            .px_2()
+            .gap_2()
+            .when(self.selected_index == ix, |this| {
+                this.bg(colors.ghost_element_selected)
+            })
+            .hover(|this| this.bg(colors.ghost_element_hover))
+            .on_click(cx.listener(move |this, _, _, cx| this.select(ix, cx)))
+            .child(
+                div().w(rems(4.5)).child(
+                    Button::new(("rebase-action", ix), row.action.name())
+                        .label_size(LabelSize::Small)
+                        .color(action_color)
+                        .tooltip(Tooltip::text("Change Action"))
+                        .on_click(cx.listener(move |this, _, window, cx| {
+                            this.cycle_action(ix, window, cx)
+                        })),
+                ),
+            )
+            .child(
+                Label::new(short_sha(&row.entry))
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+                    .buffer_font(cx),
+            )
+            .child(
+                div().flex_1().min_w_0().overflow_hidden().child(
+                    Label::new(subject)
+                        .color(if is_dropped {
+                            Color::Disabled
+                        } else {
+                            Color::Default
+                        })
+                        .when(is_dropped, |label| label.strikethrough())
+                        .truncate(),
+                ),
+            )
+            .child(
+                h_flex()
+                    .visible_on_hover("rebase-row")
+                    .child(
+                        IconButton::new(("move-up", ix), IconName::ArrowUp)
+                            .icon_size(IconSize::Small)
+                            .disabled(ix == 0)
+                            .tooltip(Tooltip::text("Move Up"))
+                            .on_click(
+                                cx.listener(move |this, _, _, cx| this.move_commit(ix, -1, cx)),
+                            ),
+                    )
+                    .child(
+                        IconButton::new(("move-down", ix), IconName::ArrowDown)
+                            .icon_size(IconSize::Small)
+                            .disabled(ix + 1 == self.rows.len())
+                            .tooltip(Tooltip::text("Move Down"))
+                            .on_click(
+                                cx.listener(move |this, _, _, cx| this.move_commit(ix, 1, cx)),
+                            ),
+                    ),
+            )
+            .into_any_element()
+    }
+}
+
+fn short_sha(entry: &LogEntry) -> SharedString {
+    entry
+        .sha
+        .get(..git::SHORT_SHA_LENGTH)
+        .unwrap_or(&entry.sha)
+        .to_string()
+        .into()
+}
+
+impl EventEmitter<DismissEvent> for RebaseModal {}
+impl ModalView for RebaseModal {}
+
+impl Focusable for RebaseModal {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for RebaseModal {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let colors = cx.theme().colors();
+        let message_editor = self
+            .rows
+            .get(self.selected_index)
+            .filter(|row| row.action == RebaseAction::Reword)
+            .and_then(|row| row.message_editor.clone());
+        let is_rebasing = self.rebase_task.is_some();
+
+        let content = if !self.rows.is_empty() {
+            uniform_list(
+                cx.entity().clone(),
+                "rebase-rows",
+                self.rows.len(),
+                |this, range, _, cx| range.map(|ix| this.render_row(ix, cx)).collect(),
+            )
+            .h(ROW_HEIGHT * self.rows.len().min(12) as f32)
+            .track_scroll(self.scroll_handle.clone())
+            .into_any_element()
+        } else {
+            let message = if self.loading {
+                "Loading…"
+            } else if self.error.is_some() {
+                "No commits can be rebased"
+            } else {
+                "No commits to rebase"
+            };
+            h_flex()
+                .h(ROW_HEIGHT)
+                .justify_center()
+                .child(Label::new(message).color(Color::Muted))
+                .into_any_element()
+        };
+
+        v_flex()
+            .key_context("RebaseModal")
+            .on_action(cx.listener(Self::cancel))
+            .elevation_3(cx)
+            .w(rems(40.))
+            .overflow_hidden()
+            .child(
+                h_flex()
+                    .px_3()
+                    .py_2()
+                    .gap_1p5()
+                    .child(Icon::new(IconName::GitBranch).size(IconSize::Small))
+                    .child(
+                        Headline::new(format!("Rebase onto {}", self.base))
+                            .size(HeadlineSize::XSmall),
+                    ),
+            )
+            .child(
+                v_flex()
+                    .key_context("RebaseTodo")
+                    .track_focus(&self.focus_handle)
+                    .on_action(cx.listener(Self::select_next))
+                    .on_action(cx.listener(Self::select_previous))
+                    .on_action(cx.listener(Self::move_commit_up))
+                    .on_action(cx.listener(Self::move_commit_down))
+                    .on_action(cx.listener(Self::pick_commit))
+                    .on_action(cx.listener(Self::reword_commit))
+                    .on_action(cx.listener(Self::squash_commit))
+                    .on_action(cx.listener(Self::fixup_commit))
+                    .on_action(cx.listener(Self::drop_commit))
+                    .on_action(cx.listener(Self::confirm))
+                    .border_y_1()
+                    .border_color(colors.border_variant)
+                    .bg(colors.editor_background)
+                    .child(content),
+            )
+            .children(message_editor.map(|editor| {
+                v_flex()
+                    .px_3()
+                    .py_2()
+                    .gap_1()
+                    .border_b_1()
+                    .border_color(colors.border_variant)
+                    .child(
+                        Label::new("New Message")
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .child(editor)
+            }))
+            .child(
+                h_flex()
+                    .px_3()
+                    .py_2()
+                    .gap_2()
+                    .justify_between()
+                    .child(div().flex_1().min_w_0().children(
+                        self.error.clone().map(|error| {
+                            Label::new(error).size(LabelSize::Small).color(Color::Error)
+                        }),
+                    ))
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .child(Button::new("cancel-rebase", "Cancel").on_click(
+                                cx.listener(|this, _, window, cx| this.cancel(&Cancel, window, cx)),
+                            ))
+                            .child(
+                                Button::new(
+                                    "start-rebase",
+                                    if is_rebasing {
+                                        "Rebasing…"
+                                    } else {
+                                        "Start Rebase"
+                                    },
+                                )
+                                .style(ButtonStyle::Filled)
+                                .disabled(is_rebasing || self.rows.is_empty())
+                                .on_click(cx.listener(
+                                    |this, _, window, cx| this.confirm(&Confirm, window, cx),
+                                )),
+                            ),
+                    ),
+            )
+    }
+}
@@ -60,9 +60,9 @@ use text::operation_queue::OperationQueue;
 use text::*;
 pub use text::{
     Anchor, Bias, Buffer as TextBuffer, BufferId, BufferSnapshot as TextBufferSnapshot, Edit,
-    OffsetRangeExt, OffsetUtf16, Patch, Point, PointUtf16, Rope, Selection, SelectionGoal,
-    Subscription, TextDimension, TextSummary, ToOffset, ToOffsetUtf16, ToPoint, ToPointUtf16,
-    Transaction, TransactionId, Unclipped,
+    HistorySnapshot, OffsetRangeExt, OffsetUtf16, Patch, Point, PointUtf16, Rope, Selection,
+    SelectionGoal, SerializedHistory, Subscription, TextDimension, TextSummary, ToOffset,
+    ToOffsetUtf16, ToPoint, ToPointUtf16, Transaction, TransactionId, Unclipped,
 };
 use theme::{ActiveTheme as _, SyntaxTheme};
 #[cfg(any(test, feature = "test-support"))]
@@ -227,10 +227,24 @@ pub struct Diagnostic {
     pub is_disk_based: bool,
     /// Whether this diagnostic marks unnecessary code.
     pub is_unnecessary: bool,
+    /// How the language server reported this diagnostic.
+    pub source_kind: DiagnosticSourceKind,
     /// Data from language server that produced this diagnostic. Passed back to the LS when we request code actions for this diagnostic.
     pub data: Option<Value>,
 }
 
+/// The way a diagnostic was obtained from its language server.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DiagnosticSourceKind {
+    /// Requested by the editor through `textDocument/diagnostic` or `workspace/diagnostic`.
+    Pulled,
+    /// Published by the server through `textDocument/publishDiagnostics`.
+    Pushed,
+    /// Produced in any other way, e.g. by a language server extension.
+    #[default]
+    Other,
+}
+
 /// An operation used to synchronize this buffer with its other replicas.
 #[derive(Clone, Debug, PartialEq)]
 pub enum Operation {
@@ -2067,6 +2081,32 @@ impl Buffer {
         self.text.merge_transactions(transaction, destination);
     }
 
+    /// Replays a history taken with [`TextBuffer::serialize_history`] onto a buffer with the text
+    /// the history was taken at, so that its transactions can be undone and redone. The buffer
+    /// must not have a history of its own, and ends up with the same text and unchanged since it
+    /// was last saved.
+    ///
+    /// The history is rebuilt in the text buffer alone: as the text doesn't change, no edits are
+    /// reported and nothing is reparsed. The operations are still sent, so that replicas of the
+    /// buffer can undo the restored transactions too.
+    pub fn restore_history(
+        &mut self,
+        history: SerializedHistory,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        anyhow::ensure!(!self.is_dirty(), "buffer has unsaved edits");
+        let operations = self.text.restore_history(&history)?;
+        for operation in operations {
+            self.send_operation(Operation::Buffer(operation), true, cx);
+        }
+
+        self.saved_version = self.version();
+        self.has_unsaved_edits
+            .set((self.saved_version.clone(), false));
+        cx.notify();
+        Ok(())
+    }
+
     /// Waits for the buffer to receive operations with the given timestamps.
     pub fn wait_for_edits<It: IntoIterator<Item = clock::Lamport>>(
         &mut self,
@@ -4544,6 +4584,7 @@ impl Default for Diagnostic {
             is_primary: false,
             is_disk_based: false,
             is_unnecessary: false,
+            source_kind: DiagnosticSourceKind::Other,
             data: None,
         }
     }
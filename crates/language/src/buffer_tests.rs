@@ -357,6 +357,71 @@ fn test_edit_events(cx: &mut gpui::App) {
     );
 }
 
+#[gpui::test]
+fn test_serialize_and_restore_history(cx: &mut gpui::App) {
+    let buffer = cx.new(|cx| {
+        let mut buffer = Buffer::local("one two three", cx);
+        for (range, text) in [(0..3, "ONE"), (4..7, "TWO"), (8..13, "THREE")] {
+            buffer.start_transaction();
+            buffer.edit([(range, text)], None, cx);
+            buffer.end_transaction(cx);
+            buffer.finalize_last_transaction();
+        }
+        buffer.undo(cx);
+        buffer
+    });
+    let text = buffer.read(cx).text();
+    assert_eq!(text, "ONE TWO three");
+    let history = buffer.read(cx).serialize_history(usize::MAX);
+    assert_eq!(history.undo_stack.len(), 2);
+    assert_eq!(history.redo_stack.len(), 1);
+
+    assert_eq!(
+        buffer.read(cx).history_snapshot().serialize(usize::MAX),
+        history
+    );
+
+    let restored = cx.new(|cx| Buffer::local(text, cx));
+    let restored_events = Arc::new(Mutex::new(Vec::new()));
+    restored.update(cx, |_, cx| {
+        let restored_events = restored_events.clone();
+        cx.subscribe(&restored, move |_, _, event, _| match event.clone() {
+            BufferEvent::Operation { .. } => {}
+            event => restored_events.lock().push(event),
+        })
+        .detach();
+    });
+    restored.update(cx, |buffer, cx| {
+        buffer.restore_history(history.clone(), cx).unwrap();
+        assert_eq!(buffer.text(), "ONE TWO three");
+        assert!(!buffer.is_dirty());
+    });
+    // The text is unchanged, so restoring the history isn't reported as an edit.
+    assert!(restored_events.lock().is_empty());
+    restored.update(cx, |buffer, cx| {
+        buffer.redo(cx);
+        assert_eq!(buffer.text(), "ONE TWO THREE");
+        buffer.undo(cx);
+        buffer.undo(cx);
+        buffer.undo(cx);
+        assert_eq!(buffer.text(), "one two three");
+        assert!(buffer.undo(cx).is_none());
+    });
+
+    // A history taken at a different text is rejected, leaving the buffer untouched.
+    let changed = cx.new(|cx| Buffer::local("ONE TWO four", cx));
+    changed.update(cx, |buffer, cx| {
+        assert!(buffer.restore_history(history, cx).is_err());
+        assert_eq!(buffer.text(), "ONE TWO four");
+        assert!(buffer.peek_undo_stack().is_none());
+    });
+
+    // Only the most recent transactions that fit within the limit are kept.
+    let history = buffer.read(cx).serialize_history(6);
+    assert_eq!(history.undo_stack.len(), 1);
+    assert!(history.redo_stack.is_empty());
+}
+
 #[gpui::test]
 async fn test_apply_diff(cx: &mut TestAppContext) {
     let (text, offsets) = marked_text_offsets(
@@ -72,6 +72,12 @@ enum BreakpointStoreMode {
 }
 pub struct BreakpointStore {
     breakpoints: BTreeMap<Arc<Path>, BreakpointsInFile>,
+    /// User settings for exception filters, keyed by the filter id reported by the adapter.
+    /// Filters without an entry fall back to the adapter's default.
+    exception_breakpoints: BTreeMap<Arc<str>, ExceptionBreakpoint>,
+    function_breakpoints: BTreeMap<Arc<str>, FunctionBreakpoint>,
+    /// Keyed by the `dataId` the adapter handed out for the watched data.
+    data_breakpoints: BTreeMap<Arc<str>, DataBreakpoint>,
     downstream_client: Option<(AnyProtoClient, u64)>,
     active_stack_frame: Option<(SessionId, Arc<Path>, text::Anchor)>,
     // E.g ssh
@@ -81,11 +87,20 @@ pub struct BreakpointStore {
 impl BreakpointStore {
     pub fn init(client: &AnyProtoClient) {
         client.add_entity_request_handler(Self::handle_toggle_breakpoint);
+        client.add_entity_request_handler(Self::handle_update_exception_breakpoint);
+        client.add_entity_request_handler(Self::handle_update_function_breakpoint);
+        client.add_entity_request_handler(Self::handle_update_data_breakpoint);
         client.add_entity_message_handler(Self::handle_breakpoints_for_file);
+        client.add_entity_message_handler(Self::handle_exception_breakpoints);
+        client.add_entity_message_handler(Self::handle_function_breakpoints);
+        client.add_entity_message_handler(Self::handle_data_breakpoints);
     }
     pub fn local(worktree_store: Entity<WorktreeStore>, buffer_store: Entity<BufferStore>) -> Self {
         BreakpointStore {
             breakpoints: BTreeMap::new(),
+            exception_breakpoints: BTreeMap::new(),
+            function_breakpoints: BTreeMap::new(),
+            data_breakpoints: BTreeMap::new(),
             mode: BreakpointStoreMode::Local(LocalBreakpointStore {
                 worktree_store,
                 buffer_store,
@@ -98,6 +113,9 @@ impl BreakpointStore {
     pub(crate) fn remote(upstream_project_id: u64, upstream_client: AnyProtoClient) -> Self {
         BreakpointStore {
             breakpoints: BTreeMap::new(),
+            exception_breakpoints: BTreeMap::new(),
+            function_breakpoints: BTreeMap::new(),
+            data_breakpoints: BTreeMap::new(),
             mode: BreakpointStoreMode::Remote(RemoteBreakpointStore {
                 upstream_client,
                 _upstream_project_id: upstream_project_id,
@@ -202,6 +220,126 @@ impl BreakpointStore {
         Ok(proto::Ack {})
     }
 
+    async fn handle_exception_breakpoints(
+        this: Entity<Project>,
+        message: TypedEnvelope<proto::ExceptionBreakpoints>,
+        mut cx: AsyncApp,
+    ) -> Result<()> {
+        let breakpoints = cx.update(|cx| this.read(cx).breakpoint_store())?;
+
+        breakpoints.update(&mut cx, |this, cx| {
+            this.exception_breakpoints = message
+                .payload
+                .breakpoints
+                .into_iter()
+                .map(ExceptionBreakpoint::from_proto)
+                .map(|breakpoint| (breakpoint.id.clone(), breakpoint))
+                .collect();
+
+            cx.emit(BreakpointStoreEvent::ExceptionBreakpointsUpdated);
+            cx.notify();
+        })?;
+
+        Ok(())
+    }
+
+    async fn handle_update_exception_breakpoint(
+        this: Entity<Project>,
+        message: TypedEnvelope<proto::UpdateExceptionBreakpoint>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let breakpoints = this.update(&mut cx, |this, _| this.breakpoint_store())?;
+        let breakpoint = message
+            .payload
+            .breakpoint
+            .map(ExceptionBreakpoint::from_proto)
+            .ok_or_else(|| anyhow!("Exception breakpoint not present in RPC payload"))?;
+
+        breakpoints.update(&mut cx, |this, cx| {
+            this.update_exception_breakpoint(breakpoint, cx);
+        })?;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_function_breakpoints(
+        this: Entity<Project>,
+        message: TypedEnvelope<proto::FunctionBreakpoints>,
+        mut cx: AsyncApp,
+    ) -> Result<()> {
+        let breakpoints = cx.update(|cx| this.read(cx).breakpoint_store())?;
+
+        breakpoints.update(&mut cx, |this, cx| {
+            this.function_breakpoints = message
+                .payload
+                .breakpoints
+                .into_iter()
+                .map(FunctionBreakpoint::from_proto)
+                .map(|breakpoint| (breakpoint.name.clone(), breakpoint))
+                .collect();
+
+            cx.emit(BreakpointStoreEvent::FunctionBreakpointsUpdated);
+            cx.notify();
+        })?;
+
+        Ok(())
+    }
+
+    async fn handle_update_function_breakpoint(
+        this: Entity<Project>,
+        message: TypedEnvelope<proto::UpdateFunctionBreakpoint>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let breakpoints = this.update(&mut cx, |this, _| this.breakpoint_store())?;
+        let name = Arc::<str>::from(message.payload.name);
+        let breakpoint = message
+            .payload
+            .breakpoint
+            .map(FunctionBreakpoint::from_proto);
+
+        breakpoints.update(&mut cx, |this, cx| {
+            this.set_function_breakpoint(name, breakpoint, cx);
+        })?;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_data_breakpoints(
+        this: Entity<Project>,
+        message: TypedEnvelope<proto::DataBreakpoints>,
+        mut cx: AsyncApp,
+    ) -> Result<()> {
+        let breakpoints = cx.update(|cx| this.read(cx).breakpoint_store())?;
+
+        breakpoints.update(&mut cx, |this, cx| {
+            this.data_breakpoints = message
+                .payload
+                .breakpoints
+                .into_iter()
+                .map(DataBreakpoint::from_proto)
+                .map(|breakpoint| (breakpoint.data_id.clone(), breakpoint))
+                .collect();
+
+            cx.emit(BreakpointStoreEvent::DataBreakpointsUpdated);
+            cx.notify();
+        })?;
+
+        Ok(())
+    }
+
+    async fn handle_update_data_breakpoint(
+        this: Entity<Project>,
+        message: TypedEnvelope<proto::UpdateDataBreakpoint>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let breakpoints = this.update(&mut cx, |this, _| this.breakpoint_store())?;
+        let data_id = Arc::<str>::from(message.payload.data_id);
+        let breakpoint = message.payload.breakpoint.map(DataBreakpoint::from_proto);
+
+        breakpoints.update(&mut cx, |this, cx| {
+            this.set_data_breakpoint(data_id, breakpoint, cx);
+        })?;
+        Ok(proto::Ack {})
+    }
+
     pub(crate) fn broadcast(&self) {
         if let Some((client, project_id)) = &self.downstream_client {
             for (path, breakpoint_set) in &self.breakpoints {
@@ -215,10 +353,47 @@ impl BreakpointStore {
                         .collect(),
                 });
             }
+
+            self.broadcast_exception_breakpoints(client, *project_id);
+            self.broadcast_function_breakpoints(client, *project_id);
+            self.broadcast_data_breakpoints(client, *project_id);
         }
     }
 
-    fn abs_path_from_buffer(buffer: &Entity<Buffer>, cx: &App) -> Option<Arc<Path>> {
+    fn broadcast_exception_breakpoints(&self, client: &AnyProtoClient, project_id: u64) {
+        let _ = client.send(proto::ExceptionBreakpoints {
+            project_id,
+            breakpoints: self
+                .exception_breakpoints
+                .values()
+                .map(ExceptionBreakpoint::to_proto)
+                .collect(),
+        });
+    }
+
+    fn broadcast_function_breakpoints(&self, client: &AnyProtoClient, project_id: u64) {
+        let _ = client.send(proto::FunctionBreakpoints {
+            project_id,
+            breakpoints: self
+                .function_breakpoints
+                .values()
+                .map(FunctionBreakpoint::to_proto)
+                .collect(),
+        });
+    }
+
+    fn broadcast_data_breakpoints(&self, client: &AnyProtoClient, project_id: u64) {
+        let _ = client.send(proto::DataBreakpoints {
+            project_id,
+            breakpoints: self
+                .data_breakpoints
+                .values()
+                .map(DataBreakpoint::to_proto)
+                .collect(),
+        });
+    }
+
+    pub fn abs_path_from_buffer(buffer: &Entity<Buffer>, cx: &App) -> Option<Arc<Path>> {
         worktree::File::from_dyn(buffer.read(cx).file())
             .and_then(|file| file.worktree.read(cx).absolutize(&file.path).ok())
             .map(Arc::<Path>::from)
@@ -434,6 +609,226 @@ impl BreakpointStore {
             })
     }
 
+    /// Returns the user's setting for an exception filter reported by a debug adapter.
+    ///
+    /// Filters the user never touched are enabled according to the adapter's `default`.
+    pub fn exception_breakpoint_for_filter(
+        &self,
+        filter: &dap::ExceptionBreakpointsFilter,
+    ) -> ExceptionBreakpoint {
+        self.exception_breakpoints
+            .get(filter.filter.as_str())
+            .cloned()
+            .unwrap_or_else(|| ExceptionBreakpoint {
+                id: filter.filter.as_str().into(),
+                condition: None,
+                state: if filter.default.unwrap_or_default() {
+                    BreakpointState::Enabled
+                } else {
+                    BreakpointState::Disabled
+                },
+            })
+    }
+
+    pub fn toggle_exception_breakpoint(
+        &mut self,
+        filter: &dap::ExceptionBreakpointsFilter,
+        cx: &mut Context<Self>,
+    ) {
+        let mut breakpoint = self.exception_breakpoint_for_filter(filter);
+        breakpoint.state = if breakpoint.state.is_enabled() {
+            BreakpointState::Disabled
+        } else {
+            BreakpointState::Enabled
+        };
+        self.update_exception_breakpoint(breakpoint, cx);
+    }
+
+    pub fn update_exception_breakpoint(
+        &mut self,
+        breakpoint: ExceptionBreakpoint,
+        cx: &mut Context<Self>,
+    ) {
+        if let BreakpointStoreMode::Remote(remote) = &self.mode {
+            cx.background_spawn(
+                remote
+                    .upstream_client
+                    .request(proto::UpdateExceptionBreakpoint {
+                        project_id: remote._upstream_project_id,
+                        breakpoint: Some(breakpoint.to_proto()),
+                    }),
+            )
+            .detach();
+        }
+
+        self.exception_breakpoints
+            .insert(breakpoint.id.clone(), breakpoint);
+
+        if let Some((client, project_id)) = &self.downstream_client {
+            self.broadcast_exception_breakpoints(client, *project_id);
+        }
+
+        cx.emit(BreakpointStoreEvent::ExceptionBreakpointsUpdated);
+        cx.notify();
+    }
+
+    pub fn all_exception_breakpoints(&self) -> Vec<ExceptionBreakpoint> {
+        self.exception_breakpoints.values().cloned().collect()
+    }
+
+    pub fn with_serialized_exception_breakpoints(
+        &mut self,
+        breakpoints: Vec<ExceptionBreakpoint>,
+        cx: &mut Context<Self>,
+    ) {
+        if let BreakpointStoreMode::Local(_) = &self.mode {
+            self.exception_breakpoints = breakpoints
+                .into_iter()
+                .map(|breakpoint| (breakpoint.id.clone(), breakpoint))
+                .collect();
+            cx.notify();
+        }
+    }
+
+    /// Adds the function breakpoint, replacing any existing one for the same function name.
+    pub fn update_function_breakpoint(
+        &mut self,
+        breakpoint: FunctionBreakpoint,
+        cx: &mut Context<Self>,
+    ) {
+        self.set_function_breakpoint(breakpoint.name.clone(), Some(breakpoint), cx);
+    }
+
+    pub fn remove_function_breakpoint(&mut self, name: &str, cx: &mut Context<Self>) {
+        self.set_function_breakpoint(name.into(), None, cx);
+    }
+
+    fn set_function_breakpoint(
+        &mut self,
+        name: Arc<str>,
+        breakpoint: Option<FunctionBreakpoint>,
+        cx: &mut Context<Self>,
+    ) {
+        if let BreakpointStoreMode::Remote(remote) = &self.mode {
+            cx.background_spawn(
+                remote
+                    .upstream_client
+                    .request(proto::UpdateFunctionBreakpoint {
+                        project_id: remote._upstream_project_id,
+                        name: name.to_string(),
+                        breakpoint: breakpoint.as_ref().map(FunctionBreakpoint::to_proto),
+                    }),
+            )
+            .detach();
+        }
+
+        match breakpoint {
+            Some(breakpoint) => {
+                self.function_breakpoints.insert(name, breakpoint);
+            }
+            None => {
+                self.function_breakpoints.remove(&name);
+            }
+        }
+
+        if let Some((client, project_id)) = &self.downstream_client {
+            self.broadcast_function_breakpoints(client, *project_id);
+        }
+
+        cx.emit(BreakpointStoreEvent::FunctionBreakpointsUpdated);
+        cx.notify();
+    }
+
+    pub fn all_function_breakpoints(&self) -> Vec<FunctionBreakpoint> {
+        self.function_breakpoints.values().cloned().collect()
+    }
+
+    pub fn with_serialized_function_breakpoints(
+        &mut self,
+        breakpoints: Vec<FunctionBreakpoint>,
+        cx: &mut Context<Self>,
+    ) {
+        if let BreakpointStoreMode::Local(_) = &self.mode {
+            self.function_breakpoints = breakpoints
+                .into_iter()
+                .map(|breakpoint| (breakpoint.name.clone(), breakpoint))
+                .collect();
+            cx.notify();
+        }
+    }
+
+    /// Adds the data breakpoint, replacing any existing one watching the same `dataId`.
+    pub fn update_data_breakpoint(&mut self, breakpoint: DataBreakpoint, cx: &mut Context<Self>) {
+        self.set_data_breakpoint(breakpoint.data_id.clone(), Some(breakpoint), cx);
+    }
+
+    pub fn remove_data_breakpoint(&mut self, data_id: &str, cx: &mut Context<Self>) {
+        self.set_data_breakpoint(data_id.into(), None, cx);
+    }
+
+    fn set_data_breakpoint(
+        &mut self,
+        data_id: Arc<str>,
+        breakpoint: Option<DataBreakpoint>,
+        cx: &mut Context<Self>,
+    ) {
+        if let BreakpointStoreMode::Remote(remote) = &self.mode {
+            cx.background_spawn(remote.upstream_client.request(proto::UpdateDataBreakpoint {
+                project_id: remote._upstream_project_id,
+                data_id: data_id.to_string(),
+                breakpoint: breakpoint.as_ref().map(DataBreakpoint::to_proto),
+            }))
+            .detach();
+        }
+
+        match breakpoint {
+            Some(breakpoint) => {
+                self.data_breakpoints.insert(data_id, breakpoint);
+            }
+            None => {
+                self.data_breakpoints.remove(&data_id);
+            }
+        }
+
+        if let Some((client, project_id)) = &self.downstream_client {
+            self.broadcast_data_breakpoints(client, *project_id);
+        }
+
+        cx.emit(BreakpointStoreEvent::DataBreakpointsUpdated);
+        cx.notify();
+    }
+
+    pub fn all_data_breakpoints(&self) -> Vec<DataBreakpoint> {
+        self.data_breakpoints.values().cloned().collect()
+    }
+
+    /// Removes the data breakpoints whose `data_id` is only valid for the session that created it.
+    pub fn remove_session_data_breakpoints(&mut self, cx: &mut Context<Self>) {
+        let data_ids = self
+            .data_breakpoints
+            .values()
+            .filter(|breakpoint| !breakpoint.can_persist)
+            .map(|breakpoint| breakpoint.data_id.clone())
+            .collect::<Vec<_>>();
+        for data_id in data_ids {
+            self.set_data_breakpoint(data_id, None, cx);
+        }
+    }
+
+    pub fn with_serialized_data_breakpoints(
+        &mut self,
+        breakpoints: Vec<DataBreakpoint>,
+        cx: &mut Context<Self>,
+    ) {
+        if let BreakpointStoreMode::Local(_) = &self.mode {
+            self.data_breakpoints = breakpoints
+                .into_iter()
+                .map(|breakpoint| (breakpoint.data_id.clone(), breakpoint))
+                .collect();
+            cx.notify();
+        }
+    }
+
     pub fn active_position(&self) -> Option<&(SessionId, Arc<Path>, text::Anchor)> {
         self.active_stack_frame.as_ref()
     }
@@ -606,6 +1001,9 @@ pub enum BreakpointStoreEvent {
     ActiveDebugLineChanged,
     BreakpointsUpdated(Arc<Path>, BreakpointUpdatedReason),
     BreakpointsCleared(Vec<Arc<Path>>),
+    ExceptionBreakpointsUpdated,
+    FunctionBreakpointsUpdated,
+    DataBreakpointsUpdated,
 }
 
 impl EventEmitter<BreakpointStoreEvent> for BreakpointStore {}
@@ -645,6 +1043,20 @@ impl BreakpointState {
             BreakpointState::Disabled => 1,
         }
     }
+
+    fn to_proto(self) -> i32 {
+        match self {
+            BreakpointState::Enabled => proto::BreakpointState::Enabled.into(),
+            BreakpointState::Disabled => proto::BreakpointState::Disabled.into(),
+        }
+    }
+
+    fn from_proto(state: i32) -> Self {
+        match proto::BreakpointState::from_i32(state) {
+            Some(proto::BreakpointState::Disabled) => BreakpointState::Disabled,
+            None | Some(proto::BreakpointState::Enabled) => BreakpointState::Enabled,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
@@ -687,10 +1099,7 @@ impl Breakpoint {
     fn to_proto(&self, _path: &Path, position: &text::Anchor) -> Option<client::proto::Breakpoint> {
         Some(client::proto::Breakpoint {
             position: Some(serialize_text_anchor(position)),
-            state: match self.state {
-                BreakpointState::Enabled => proto::BreakpointState::Enabled.into(),
-                BreakpointState::Disabled => proto::BreakpointState::Disabled.into(),
-            },
+            state: self.state.to_proto(),
             message: self.message.as_ref().map(|s| String::from(s.as_ref())),
             condition: self.condition.as_ref().map(|s| String::from(s.as_ref())),
             hit_condition: self
@@ -702,10 +1111,7 @@ impl Breakpoint {
 
     fn from_proto(breakpoint: client::proto::Breakpoint) -> Option<Self> {
         Some(Self {
-            state: match proto::BreakpointState::from_i32(breakpoint.state) {
-                Some(proto::BreakpointState::Disabled) => BreakpointState::Disabled,
-                None | Some(proto::BreakpointState::Enabled) => BreakpointState::Enabled,
-            },
+            state: BreakpointState::from_proto(breakpoint.state),
             message: breakpoint.message.map(Into::into),
             condition: breakpoint.condition.map(Into::into),
             hit_condition: breakpoint.hit_condition.map(Into::into),
@@ -750,3 +1156,240 @@ impl From<SourceBreakpoint> for dap::SourceBreakpoint {
         }
     }
 }
+
+/// Breakpoint that stops on exceptions matched by one of the debug adapter's `exceptionBreakpointFilters`.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct ExceptionBreakpoint {
+    /// The `filter` id of the adapter's exception filter.
+    pub id: Arc<str>,
+    /// Only honored by adapters whose filter declares `supportsCondition`.
+    pub condition: Option<BreakpointMessage>,
+    pub state: BreakpointState,
+}
+
+impl ExceptionBreakpoint {
+    fn to_proto(&self) -> proto::ExceptionBreakpoint {
+        proto::ExceptionBreakpoint {
+            id: self.id.to_string(),
+            state: self.state.to_proto(),
+            condition: self.condition.as_ref().map(|s| String::from(s.as_ref())),
+        }
+    }
+
+    fn from_proto(breakpoint: proto::ExceptionBreakpoint) -> Self {
+        Self {
+            id: breakpoint.id.into(),
+            state: BreakpointState::from_proto(breakpoint.state),
+            condition: breakpoint.condition.map(Into::into),
+        }
+    }
+}
+
+/// Breakpoint that stops whenever a function with the given name is entered.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct FunctionBreakpoint {
+    pub name: Arc<str>,
+    pub condition: Option<BreakpointMessage>,
+    pub hit_condition: Option<BreakpointMessage>,
+    pub state: BreakpointState,
+}
+
+impl FunctionBreakpoint {
+    pub fn new(name: impl Into<Arc<str>>) -> Self {
+        Self {
+            name: name.into(),
+            condition: None,
+            hit_condition: None,
+            state: BreakpointState::Enabled,
+        }
+    }
+
+    fn to_proto(&self) -> proto::FunctionBreakpoint {
+        proto::FunctionBreakpoint {
+            name: self.name.to_string(),
+            state: self.state.to_proto(),
+            condition: self.condition.as_ref().map(|s| String::from(s.as_ref())),
+            hit_condition: self
+                .hit_condition
+                .as_ref()
+                .map(|s| String::from(s.as_ref())),
+        }
+    }
+
+    fn from_proto(breakpoint: proto::FunctionBreakpoint) -> Self {
+        Self {
+            name: breakpoint.name.into(),
+            state: BreakpointState::from_proto(breakpoint.state),
+            condition: breakpoint.condition.map(Into::into),
+            hit_condition: breakpoint.hit_condition.map(Into::into),
+        }
+    }
+}
+
+impl From<FunctionBreakpoint> for dap::FunctionBreakpoint {
+    fn from(bp: FunctionBreakpoint) -> Self {
+        Self {
+            name: String::from(bp.name.as_ref()),
+            condition: bp
+                .condition
+                .map(|condition| String::from(condition.as_ref())),
+            hit_condition: bp
+                .hit_condition
+                .map(|hit_condition| String::from(hit_condition.as_ref())),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub enum DataBreakpointAccessType {
+    Read,
+    #[default]
+    Write,
+    ReadWrite,
+}
+
+impl DataBreakpointAccessType {
+    #[inline]
+    pub fn to_int(&self) -> i32 {
+        match self {
+            DataBreakpointAccessType::Read => 0,
+            DataBreakpointAccessType::Write => 1,
+            DataBreakpointAccessType::ReadWrite => 2,
+        }
+    }
+
+    pub fn from_int(value: i32) -> Option<Self> {
+        match value {
+            0 => Some(DataBreakpointAccessType::Read),
+            1 => Some(DataBreakpointAccessType::Write),
+            2 => Some(DataBreakpointAccessType::ReadWrite),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DataBreakpointAccessType::Read => "Read",
+            DataBreakpointAccessType::Write => "Write",
+            DataBreakpointAccessType::ReadWrite => "Read/Write",
+        }
+    }
+
+    fn to_proto(self) -> i32 {
+        match self {
+            DataBreakpointAccessType::Read => proto::DapDataBreakpointAccessType::Read.into(),
+            DataBreakpointAccessType::Write => proto::DapDataBreakpointAccessType::Write.into(),
+            DataBreakpointAccessType::ReadWrite => {
+                proto::DapDataBreakpointAccessType::ReadWrite.into()
+            }
+        }
+    }
+
+    fn from_proto(access_type: i32) -> Self {
+        match proto::DapDataBreakpointAccessType::from_i32(access_type) {
+            Some(proto::DapDataBreakpointAccessType::Read) => DataBreakpointAccessType::Read,
+            Some(proto::DapDataBreakpointAccessType::ReadWrite) => {
+                DataBreakpointAccessType::ReadWrite
+            }
+            None | Some(proto::DapDataBreakpointAccessType::Write) => {
+                DataBreakpointAccessType::Write
+            }
+        }
+    }
+}
+
+impl From<dap::DataBreakpointAccessType> for DataBreakpointAccessType {
+    fn from(access_type: dap::DataBreakpointAccessType) -> Self {
+        match access_type {
+            dap::DataBreakpointAccessType::Read => DataBreakpointAccessType::Read,
+            dap::DataBreakpointAccessType::Write => DataBreakpointAccessType::Write,
+            dap::DataBreakpointAccessType::ReadWrite => DataBreakpointAccessType::ReadWrite,
+        }
+    }
+}
+
+impl From<DataBreakpointAccessType> for dap::DataBreakpointAccessType {
+    fn from(access_type: DataBreakpointAccessType) -> Self {
+        match access_type {
+            DataBreakpointAccessType::Read => dap::DataBreakpointAccessType::Read,
+            DataBreakpointAccessType::Write => dap::DataBreakpointAccessType::Write,
+            DataBreakpointAccessType::ReadWrite => dap::DataBreakpointAccessType::ReadWrite,
+        }
+    }
+}
+
+/// Breakpoint that stops when the data behind an adapter's `dataId` is accessed, e.g. a watchpoint on a struct field.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct DataBreakpoint {
+    /// Identifier returned by the adapter's `dataBreakpointInfo` response.
+    pub data_id: Arc<str>,
+    /// Adapter-provided description of the watched data, shown in the UI.
+    pub description: Arc<str>,
+    pub access_type: DataBreakpointAccessType,
+    pub condition: Option<BreakpointMessage>,
+    pub hit_condition: Option<BreakpointMessage>,
+    pub state: BreakpointState,
+    /// Whether the adapter reported that the `data_id` stays valid across sessions. Other data
+    /// breakpoints are dropped once their session ends and are never serialized.
+    pub can_persist: bool,
+}
+
+impl DataBreakpoint {
+    pub fn new(
+        data_id: impl Into<Arc<str>>,
+        description: impl Into<Arc<str>>,
+        access_type: DataBreakpointAccessType,
+    ) -> Self {
+        Self {
+            data_id: data_id.into(),
+            description: description.into(),
+            access_type,
+            condition: None,
+            hit_condition: None,
+            state: BreakpointState::Enabled,
+            can_persist: false,
+        }
+    }
+
+    fn to_proto(&self) -> proto::DataBreakpoint {
+        proto::DataBreakpoint {
+            data_id: self.data_id.to_string(),
+            description: self.description.to_string(),
+            access_type: self.access_type.to_proto(),
+            state: self.state.to_proto(),
+            condition: self.condition.as_ref().map(|s| String::from(s.as_ref())),
+            hit_condition: self
+                .hit_condition
+                .as_ref()
+                .map(|s| String::from(s.as_ref())),
+            can_persist: self.can_persist,
+        }
+    }
+
+    fn from_proto(breakpoint: proto::DataBreakpoint) -> Self {
+        Self {
+            data_id: breakpoint.data_id.into(),
+            description: breakpoint.description.into(),
+            access_type: DataBreakpointAccessType::from_proto(breakpoint.access_type),
+            state: BreakpointState::from_proto(breakpoint.state),
+            condition: breakpoint.condition.map(Into::into),
+            hit_condition: breakpoint.hit_condition.map(Into::into),
+            can_persist: breakpoint.can_persist,
+        }
+    }
+}
+
+impl From<DataBreakpoint> for dap::DataBreakpoint {
+    fn from(bp: DataBreakpoint) -> Self {
+        Self {
+            data_id: String::from(bp.data_id.as_ref()),
+            access_type: Some(bp.access_type.into()),
+            condition: bp
+                .condition
+                .map(|condition| String::from(condition.as_ref())),
+            hit_condition: bp
+                .hit_condition
+                .map(|hit_condition| String::from(hit_condition.as_ref())),
+        }
+    }
+}
@@ -113,6 +113,7 @@ impl DapStore {
         // todo(debugger): Reenable these after we finish handle_dap_command refactor
         // client.add_entity_request_handler(Self::handle_dap_command::<NextCommand>);
         // client.add_entity_request_handler(Self::handle_dap_command::<StepInCommand>);
+        // client.add_entity_request_handler(Self::handle_dap_command::<StepInTargetsCommand>);
         // client.add_entity_request_handler(Self::handle_dap_command::<StepOutCommand>);
         // client.add_entity_request_handler(Self::handle_dap_command::<StepBackCommand>);
         // client.add_entity_request_handler(Self::handle_dap_command::<ContinueCommand>);
@@ -123,6 +124,8 @@ impl DapStore {
         // client.add_entity_request_handler(Self::handle_dap_command::<RestartCommand>);
         // client.add_entity_request_handler(Self::handle_dap_command::<VariablesCommand>);
         // client.add_entity_request_handler(Self::handle_dap_command::<RestartStackFrameCommand>);
+        // client.add_entity_request_handler(Self::handle_dap_command::<GotoTargetsCommand>);
+        // client.add_entity_request_handler(Self::handle_dap_command::<GotoCommand>);
     }
 
     #[expect(clippy::too_many_arguments)]
@@ -783,6 +786,10 @@ impl DapStore {
         };
 
         let shutdown_task = session.update(cx, |this, cx| this.shutdown(cx));
+        // Data ids that the adapter can't persist are only valid in the session that returned them.
+        self.breakpoint_store.update(cx, |breakpoint_store, cx| {
+            breakpoint_store.remove_session_data_breakpoints(cx)
+        });
 
         cx.background_spawn(async move {
             if shutdown_children.len() > 0 {
@@ -2,15 +2,17 @@ use crate::project_settings::ProjectSettings;
 
 use super::breakpoint_store::{BreakpointStore, BreakpointStoreEvent, BreakpointUpdatedReason};
 use super::dap_command::{
-    self, Attach, ConfigurationDone, ContinueCommand, DapCommand, DisconnectCommand,
-    EvaluateCommand, Initialize, Launch, LoadedSourcesCommand, LocalDapCommand, LocationsCommand,
-    ModulesCommand, NextCommand, PauseCommand, RestartCommand, RestartStackFrameCommand,
+    self, Attach, ConfigurationDone, ContinueCommand, DapCommand, DataBreakpointInfoCommand,
+    DisassembleCommand, DisconnectCommand, EvaluateCommand, GotoCommand, GotoTargetsCommand,
+    Initialize, Launch, LoadedSourcesCommand, LocalDapCommand, LocationsCommand, ModulesCommand,
+    NextCommand, PauseCommand, ReadMemoryCommand, RestartCommand, RestartStackFrameCommand,
     ScopesCommand, SetVariableValueCommand, StackTraceCommand, StepBackCommand, StepCommand,
-    StepInCommand, StepOutCommand, TerminateCommand, TerminateThreadsCommand, ThreadsCommand,
-    VariablesCommand,
+    StepInCommand, StepInTargetsCommand, StepOutCommand, TerminateCommand, TerminateThreadsCommand,
+    ThreadsCommand, VariablesCommand, WriteMemoryCommand,
 };
 use super::dap_store::DapAdapterDelegate;
 use anyhow::{Result, anyhow};
+use base64::Engine as _;
 use collections::{HashMap, HashSet, IndexMap, IndexSet};
 use dap::adapters::{DebugAdapter, DebugAdapterBinary};
 use dap::messages::Response;
@@ -75,6 +77,32 @@ impl From<dap::StackFrame> for StackFrame {
     }
 }
 
+/// Bytes read from the debuggee's memory.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Memory {
+    /// Address of the first byte, as reported by the adapter.
+    pub address: String,
+    pub data: Vec<u8>,
+    /// Number of bytes following `data` that couldn't be read.
+    pub unreadable_bytes: u64,
+}
+
+impl Memory {
+    fn from_dap(response: dap::ReadMemoryResponse) -> Result<Self> {
+        let data = response
+            .data
+            .map(|data| base64::engine::general_purpose::STANDARD.decode(data))
+            .transpose()?
+            .unwrap_or_default();
+
+        Ok(Self {
+            address: response.address,
+            data,
+            unreadable_bytes: response.unreadable_bytes.unwrap_or_default(),
+        })
+    }
+}
+
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub enum ThreadStatus {
     #[default]
@@ -165,7 +193,7 @@ pub struct LocalMode {
     breakpoint_store: Entity<BreakpointStore>,
 }
 
-fn client_source(abs_path: &Path) -> dap::Source {
+pub(super) fn client_source(abs_path: &Path) -> dap::Source {
     dap::Source {
         name: abs_path
             .file_name()
@@ -494,6 +522,132 @@ impl LocalMode {
         })
     }
 
+    fn send_exception_breakpoints(
+        &self,
+        capabilities: &Capabilities,
+        ignore_breakpoints: bool,
+        cx: &App,
+    ) -> Task<()> {
+        let Some(filters) = capabilities
+            .exception_breakpoint_filters
+            .as_ref()
+            .filter(|filters| !filters.is_empty())
+        else {
+            return Task::ready(());
+        };
+        let supports_filter_options = capabilities
+            .supports_exception_filter_options
+            .unwrap_or_default();
+
+        let mut filter_ids = Vec::new();
+        let mut filter_options = Vec::new();
+        if !ignore_breakpoints {
+            let store = self.breakpoint_store.read(cx);
+            for filter in filters {
+                let breakpoint = store.exception_breakpoint_for_filter(filter);
+                if breakpoint.state.is_disabled() {
+                    continue;
+                }
+
+                match breakpoint.condition.filter(|_| {
+                    supports_filter_options && filter.supports_condition.unwrap_or_default()
+                }) {
+                    Some(condition) => filter_options.push(dap::ExceptionFilterOptions {
+                        filter_id: filter.filter.clone(),
+                        condition: Some(String::from(condition.as_ref())),
+                        mode: None,
+                    }),
+                    None => filter_ids.push(filter.filter.clone()),
+                }
+            }
+        }
+
+        let task = self.request(
+            dap_command::SetExceptionBreakpoints {
+                filters: filter_ids,
+                filter_options: (!filter_options.is_empty()).then_some(filter_options),
+            },
+            cx.background_executor().clone(),
+        );
+
+        cx.background_spawn(async move {
+            match task.await {
+                Ok(_) => {}
+                Err(err) => log::warn!("Set exception breakpoints request failed: {}", err),
+            }
+        })
+    }
+
+    fn send_function_breakpoints(
+        &self,
+        capabilities: &Capabilities,
+        ignore_breakpoints: bool,
+        cx: &App,
+    ) -> Task<()> {
+        if !dap_command::SetFunctionBreakpoints::is_supported(capabilities) {
+            return Task::ready(());
+        }
+
+        let breakpoints = if ignore_breakpoints {
+            vec![]
+        } else {
+            self.breakpoint_store
+                .read(cx)
+                .all_function_breakpoints()
+                .into_iter()
+                .filter(|bp| bp.state.is_enabled())
+                .map(Into::into)
+                .collect()
+        };
+
+        let task = self.request(
+            dap_command::SetFunctionBreakpoints { breakpoints },
+            cx.background_executor().clone(),
+        );
+
+        cx.background_spawn(async move {
+            match task.await {
+                Ok(_) => {}
+                Err(err) => log::warn!("Set function breakpoints request failed: {}", err),
+            }
+        })
+    }
+
+    fn send_data_breakpoints(
+        &self,
+        capabilities: &Capabilities,
+        ignore_breakpoints: bool,
+        cx: &App,
+    ) -> Task<()> {
+        if !dap_command::SetDataBreakpoints::is_supported(capabilities) {
+            return Task::ready(());
+        }
+
+        let breakpoints = if ignore_breakpoints {
+            vec![]
+        } else {
+            self.breakpoint_store
+                .read(cx)
+                .all_data_breakpoints()
+                .into_iter()
+                .filter(|bp| bp.state.is_enabled())
+                .map(Into::into)
+                .collect()
+        };
+
+        let task = self.request(
+            dap_command::SetDataBreakpoints { breakpoints },
+            cx.background_executor().clone(),
+        );
+
+        cx.background_spawn(async move {
+            match task.await {
+                Ok(_) => {}
+                Err(err) => log::warn!("Set data breakpoints request failed: {}", err),
+            }
+        })
+    }
+
     async fn get_adapter_binary(
         registry: &Arc<DapRegistry>,
         config: &DebugAdapterConfig,
@@ -579,11 +733,18 @@ impl LocalMode {
 
         let configuration_sequence = cx.spawn({
             let this = self.clone();
+            let capabilities = capabilities.clone();
             async move |cx| {
                 initialized_rx.await?;
                 // todo(debugger) figure out if we want to handle a breakpoint response error
                 // This will probably consist of letting a user know that breakpoints failed to be set
                 cx.update(|cx| this.send_all_breakpoints(false, cx))?.await;
+                cx.update(|cx| this.send_exception_breakpoints(&capabilities, false, cx))?
+                    .await;
+                cx.update(|cx| this.send_function_breakpoints(&capabilities, false, cx))?
+                    .await;
+                cx.update(|cx| this.send_data_breakpoints(&capabilities, false, cx))?
+                    .await;
 
                 if configuration_done_supported {
                     this.request(ConfigurationDone, cx.background_executor().clone())
@@ -823,6 +984,7 @@ pub enum SessionEvent {
     StackTrace,
     Variables,
     Threads,
+    Memory,
 }
 
 impl EventEmitter<SessionEvent> for Session {}
@@ -1391,7 +1553,15 @@ impl Session {
         self.ignore_breakpoints = ignore;
 
         if let Some(local) = self.as_local() {
-            local.send_all_breakpoints(ignore, cx)
+            let tasks = [
+                local.send_all_breakpoints(ignore, cx),
+                local.send_exception_breakpoints(&self.capabilities, ignore, cx),
+                local.send_function_breakpoints(&self.capabilities, ignore, cx),
+                local.send_data_breakpoints(&self.capabilities, ignore, cx),
+            ];
+            cx.background_spawn(async move {
+                futures::future::join_all(tasks).await;
+            })
         } else {
             // todo(debugger): We need to propagate this change to downstream sessions and send a message to upstream sessions
             unimplemented!()
@@ -1607,6 +1777,18 @@ impl Session {
         thread_id: ThreadId,
         granularity: SteppingGranularity,
         cx: &mut Context<Self>,
+    ) {
+        self.step_in_target(thread_id, None, granularity, cx);
+    }
+
+    /// Steps into the given target, as returned by [`Self::step_in_targets`],
+    /// or into the first call on the line when no target is given.
+    pub fn step_in_target(
+        &mut self,
+        thread_id: ThreadId,
+        target_id: Option<u64>,
+        granularity: SteppingGranularity,
+        cx: &mut Context<Self>,
     ) {
         let supports_single_thread_execution_requests =
             self.capabilities.supports_single_thread_execution_requests;
@@ -1621,6 +1803,7 @@ impl Session {
                 granularity: supports_stepping_granularity.then(|| granularity),
                 single_thread: supports_single_thread_execution_requests,
             },
+            target_id,
         };
 
         self.thread_states.process_step(thread_id);
@@ -1632,6 +1815,53 @@ impl Session {
         .detach();
     }
 
+    pub fn step_in_targets(
+        &mut self,
+        stack_frame_id: StackFrameId,
+        cx: &mut Context<Self>,
+    ) -> Task<Option<Vec<dap::StepInTarget>>> {
+        self.request(
+            StepInTargetsCommand {
+                frame_id: stack_frame_id,
+            },
+            |_, response, _| response.log_err(),
+            cx,
+        )
+    }
+
+    /// Returns the locations in the given line that the execution point can be moved to.
+    pub fn goto_targets(
+        &mut self,
+        abs_path: Arc<Path>,
+        line: u64,
+        cx: &mut Context<Self>,
+    ) -> Task<Option<Vec<dap::GotoTarget>>> {
+        self.request(
+            GotoTargetsCommand {
+                path: abs_path,
+                line,
+                column: None,
+            },
+            |_, response, _| response.log_err(),
+            cx,
+        )
+    }
+
+    pub fn goto(&mut self, thread_id: ThreadId, target_id: u64, cx: &mut Context<Self>) {
+        let command = GotoCommand {
+            thread_id: thread_id.0,
+            target_id,
+        };
+
+        self.thread_states.process_step(thread_id);
+        self.request(
+            command,
+            Self::on_step_response::<GotoCommand>(thread_id),
+            cx,
+        )
+        .detach();
+    }
+
     pub fn step_out(
         &mut self,
         thread_id: ThreadId,
@@ -1850,6 +2080,90 @@ impl Session {
         }
     }
 
+    /// Disassembles `instruction_count` instructions, starting `instruction_offset` instructions
+    /// away from `memory_reference` (usually a stack frame's `instructionPointerReference`).
+    pub fn disassemble(
+        &mut self,
+        memory_reference: String,
+        instruction_offset: i64,
+        instruction_count: u64,
+        cx: &mut Context<Self>,
+    ) -> Task<Option<Vec<dap::DisassembledInstruction>>> {
+        self.request(
+            DisassembleCommand {
+                memory_reference,
+                offset: None,
+                instruction_offset: Some(instruction_offset),
+                instruction_count,
+                resolve_symbols: true,
+            },
+            |_, response, _| response.log_err(),
+            cx,
+        )
+    }
+
+    pub fn read_memory(
+        &mut self,
+        memory_reference: String,
+        offset: i64,
+        count: u64,
+        cx: &mut Context<Self>,
+    ) -> Task<Option<Memory>> {
+        let response = self.request(
+            ReadMemoryCommand {
+                memory_reference,
+                offset: Some(offset),
+                count,
+            },
+            |_, response, _| response.log_err(),
+            cx,
+        );
+        cx.background_spawn(async move { Memory::from_dap(response.await?).log_err() })
+    }
+
+    pub fn write_memory(
+        &mut self,
+        memory_reference: String,
+        offset: i64,
+        data: &[u8],
+        cx: &mut Context<Self>,
+    ) -> Task<Option<dap::WriteMemoryResponse>> {
+        self.request(
+            WriteMemoryCommand {
+                memory_reference,
+                offset: Some(offset),
+                data: base64::engine::general_purpose::STANDARD.encode(data),
+            },
+            |this, response, cx| {
+                let response = response.log_err()?;
+                this.invalidate_command_type::<VariablesCommand>();
+                cx.emit(SessionEvent::Memory);
+                cx.notify();
+                Some(response)
+            },
+            cx,
+        )
+    }
+
+    /// Asks the adapter whether a data breakpoint can be set on the named variable or expression.
+    pub fn data_breakpoint_info(
+        &mut self,
+        variables_reference: Option<u64>,
+        name: String,
+        frame_id: Option<u64>,
+        cx: &mut Context<Self>,
+    ) -> Task<Option<dap::DataBreakpointInfoResponse>> {
+        self.request(
+            DataBreakpointInfoCommand {
+                variables_reference,
+                name,
+                frame_id,
+            },
+            |_, response, _| response.log_err(),
+            cx,
+        )
+    }
+
     pub fn evaluate(
         &mut self,
         expression: String,
@@ -1989,6 +2303,36 @@ fn create_local_session(
                 local.unset_breakpoints_from_paths(paths, cx).detach();
             }
         }
+        BreakpointStoreEvent::ExceptionBreakpointsUpdated => {
+            if let Some(local) = (!this.ignore_breakpoints)
+                .then(|| this.as_local())
+                .flatten()
+            {
+                local
+                    .send_exception_breakpoints(&this.capabilities, false, cx)
+                    .detach();
+            }
+        }
+        BreakpointStoreEvent::FunctionBreakpointsUpdated => {
+            if let Some(local) = (!this.ignore_breakpoints)
+                .then(|| this.as_local())
+                .flatten()
+            {
+                local
+                    .send_function_breakpoints(&this.capabilities, false, cx)
+                    .detach();
+            }
+        }
+        BreakpointStoreEvent::DataBreakpointsUpdated => {
+            if let Some(local) = (!this.ignore_breakpoints)
+                .then(|| this.as_local())
+                .flatten()
+            {
+                local
+                    .send_data_breakpoints(&this.capabilities, false, cx)
+                    .detach();
+            }
+        }
         BreakpointStoreEvent::ActiveDebugLineChanged => {}
     })
     .detach();
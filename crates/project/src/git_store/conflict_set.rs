@@ -0,0 +1,300 @@
+use gpui::{App, Context, Entity, EventEmitter, SharedString, Subscription, Task, WeakEntity};
+use language::{Buffer, BufferEvent};
+use std::{ops::Range, sync::Arc};
+use text::{Anchor, BufferId};
+
+const OURS_MARKER: &str = "<<<<<<<";
+const BASE_MARKER: &str = "|||||||";
+const SEPARATOR_MARKER: &str = "=======";
+const THEIRS_MARKER: &str = ">>>>>>>";
+
+/// The merge conflict regions of a buffer, kept up to date as the buffer changes.
+///
+/// Regions are only detected while the buffer's file is conflicted in its repository, so that
+/// files which merely contain conflict markers aren't affected.
+pub struct ConflictSet {
+    buffer: WeakEntity<Buffer>,
+    has_conflict: bool,
+    /// Whether no conflict markers were left in the buffer when it was last parsed, so that the
+    /// conflicts are only reported as resolved once markers were seen and then removed.
+    resolved: bool,
+    snapshot: ConflictSetSnapshot,
+    parse_task: Task<()>,
+    _buffer_subscription: Subscription,
+}
+
+#[derive(Clone, Debug)]
+pub struct ConflictSetSnapshot {
+    pub buffer_id: BufferId,
+    pub conflicts: Arc<[ConflictRegion]>,
+}
+
+/// A region delimited by `<<<<<<<`, `=======` and `>>>>>>>` marker lines, with an optional
+/// `|||||||` section holding the merge base in the diff3 conflict style.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConflictRegion {
+    /// The whole region, marker lines included.
+    pub range: Range<Anchor>,
+    pub ours: Range<Anchor>,
+    pub base: Option<Range<Anchor>>,
+    pub theirs: Range<Anchor>,
+    pub ours_branch_name: SharedString,
+    pub theirs_branch_name: SharedString,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConflictSetEvent {
+    ConflictsChanged,
+    /// The last conflict marker of a conflicted file was removed.
+    ConflictsResolved,
+}
+
+impl EventEmitter<ConflictSetEvent> for ConflictSet {}
+
+impl ConflictSet {
+    pub fn new(buffer: Entity<Buffer>, has_conflict: bool, cx: &mut Context<Self>) -> Self {
+        let _buffer_subscription = cx.subscribe(&buffer, |this, buffer, event, cx| match event {
+            BufferEvent::Edited | BufferEvent::Reloaded => this.reparse(buffer, cx),
+            _ => {}
+        });
+        let mut this = Self {
+            buffer: buffer.downgrade(),
+            has_conflict,
+            resolved: true,
+            snapshot: ConflictSetSnapshot {
+                buffer_id: buffer.read(cx).remote_id(),
+                conflicts: Arc::default(),
+            },
+            parse_task: Task::ready(()),
+            _buffer_subscription,
+        };
+        this.reparse(buffer, cx);
+        this
+    }
+
+    pub fn has_conflict(&self) -> bool {
+        self.has_conflict
+    }
+
+    pub fn snapshot(&self) -> ConflictSetSnapshot {
+        self.snapshot.clone()
+    }
+
+    pub(crate) fn set_has_conflict(&mut self, has_conflict: bool, cx: &mut Context<Self>) {
+        if self.has_conflict == has_conflict {
+            return;
+        }
+        self.has_conflict = has_conflict;
+        if let Some(buffer) = self.buffer.upgrade() {
+            self.reparse(buffer, cx);
+        }
+    }
+
+    fn reparse(&mut self, buffer: Entity<Buffer>, cx: &mut Context<Self>) {
+        if !self.has_conflict {
+            self.parse_task = Task::ready(());
+            self.resolved = true;
+            self.set_conflicts(Arc::default(), cx);
+            return;
+        }
+
+        let buffer = buffer.read(cx).text_snapshot();
+        self.parse_task = cx.spawn(async move |this, cx| {
+            let (conflicts, has_markers) = cx
+                .background_spawn(async move {
+                    (parse_conflicts(&buffer), has_conflict_markers(&buffer))
+                })
+                .await;
+            this.update(cx, |this, cx| {
+                this.set_conflicts(conflicts.into(), cx);
+                // Incomplete regions aren't parsed as conflicts, so only the absence of any marker
+                // line shows that the conflicts were resolved.
+                let resolved = !has_markers;
+                if resolved && !this.resolved {
+                    cx.emit(ConflictSetEvent::ConflictsResolved);
+                }
+                this.resolved = resolved;
+            })
+            .ok();
+        });
+    }
+
+    fn set_conflicts(&mut self, conflicts: Arc<[ConflictRegion]>, cx: &mut Context<Self>) {
+        if self.snapshot.conflicts == conflicts {
+            return;
+        }
+        self.snapshot.conflicts = conflicts;
+        cx.emit(ConflictSetEvent::ConflictsChanged);
+    }
+}
+
+impl ConflictRegion {
+    /// Replaces the whole region with the concatenated contents of the given sections.
+    pub fn resolve(&self, buffer: Entity<Buffer>, sections: &[Range<Anchor>], cx: &mut App) {
+        let snapshot = buffer.read(cx).snapshot();
+        let mut new_text = String::new();
+        for section in sections {
+            new_text.extend(snapshot.text_for_range(section.clone()));
+        }
+        buffer.update(cx, |buffer, cx| {
+            buffer.edit([(self.range.clone(), new_text)], None, cx);
+        });
+    }
+}
+
+/// Whether any line of the buffer is a conflict marker, including those of incomplete regions.
+pub fn has_conflict_markers(buffer: &text::BufferSnapshot) -> bool {
+    let mut lines = buffer.as_rope().chunks().lines();
+    while let Some(line) = lines.next() {
+        if [OURS_MARKER, BASE_MARKER, SEPARATOR_MARKER, THEIRS_MARKER]
+            .iter()
+            .any(|marker| line.starts_with(marker))
+        {
+            return true;
+        }
+    }
+    false
+}
+
+/// Finds the conflict regions in the buffer. Incomplete regions are ignored.
+pub fn parse_conflicts(buffer: &text::BufferSnapshot) -> Vec<ConflictRegion> {
+    struct PendingRegion {
+        start: usize,
+        ours: Range<usize>,
+        base: Option<Range<usize>>,
+        theirs_start: Option<usize>,
+        ours_branch_name: SharedString,
+    }
+
+    let mut conflicts = Vec::new();
+    let mut pending: Option<PendingRegion> = None;
+    let len = buffer.len();
+    let mut lines = buffer.as_rope().chunks().lines();
+    let mut line_start = 0;
+    while let Some(line) = lines.next() {
+        let next_line_start = (line_start + line.len() + 1).min(len);
+        if let Some(branch_name) = line.strip_prefix(OURS_MARKER) {
+            pending = Some(PendingRegion {
+                start: line_start,
+                ours: next_line_start..next_line_start,
+                base: None,
+                theirs_start: None,
+                ours_branch_name: branch_name.trim().to_string().into(),
+            });
+        } else if let Some(region) = pending
+            .as_mut()
+            .filter(|region| region.theirs_start.is_none())
+        {
+            if line.starts_with(BASE_MARKER) && region.base.is_none() {
+                region.ours.end = line_start;
+                region.base = Some(next_line_start..next_line_start);
+            } else if line.starts_with(SEPARATOR_MARKER) {
+                match region.base.as_mut() {
+                    Some(base) => base.end = line_start,
+                    None => region.ours.end = line_start,
+                }
+                region.theirs_start = Some(next_line_start);
+            }
+        } else if let Some(branch_name) = line.strip_prefix(THEIRS_MARKER) {
+            if let Some(region) = pending.take() {
+                let theirs_start = region.theirs_start.unwrap_or(line_start);
+                let anchor_range = |range: Range<usize>| {
+                    buffer.anchor_before(range.start)..buffer.anchor_after(range.end)
+                };
+                conflicts.push(ConflictRegion {
+                    range: anchor_range(region.start..next_line_start),
+                    ours: anchor_range(region.ours),
+                    base: region.base.map(anchor_range),
+                    theirs: anchor_range(theirs_start..line_start),
+                    ours_branch_name: region.ours_branch_name,
+                    theirs_branch_name: branch_name.trim().to_string().into(),
+                });
+            }
+        }
+        line_start = next_line_start;
+    }
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use text::{Buffer, ToOffset as _};
+
+    fn parse(text: &str) -> (text::BufferSnapshot, Vec<ConflictRegion>) {
+        let buffer = Buffer::new(0, BufferId::new(1).unwrap(), text.to_string());
+        let snapshot = buffer.snapshot();
+        let conflicts = parse_conflicts(&snapshot);
+        (snapshot, conflicts)
+    }
+
+    fn text(snapshot: &text::BufferSnapshot, range: &Range<Anchor>) -> String {
+        snapshot.text_for_range(range.clone()).collect()
+    }
+
+    #[test]
+    fn test_parse_conflicts() {
+        let (snapshot, conflicts) = parse(concat!(
+            "fn main() {\n",
+            "<<<<<<< HEAD\n",
+            "    ours();\n",
+            "=======\n",
+            "    theirs();\n",
+            ">>>>>>> feature\n",
+            "}\n",
+            "<<<<<<< HEAD\n",
+            "ours\n",
+            "||||||| base\n",
+            "base\n",
+            "=======\n",
+            "theirs\n",
+            ">>>>>>> other",
+        ));
+        assert_eq!(conflicts.len(), 2);
+
+        let first = &conflicts[0];
+        assert_eq!(
+            text(&snapshot, &first.range),
+            "<<<<<<< HEAD\n    ours();\n=======\n    theirs();\n>>>>>>> feature\n"
+        );
+        assert_eq!(text(&snapshot, &first.ours), "    ours();\n");
+        assert_eq!(first.base, None);
+        assert_eq!(text(&snapshot, &first.theirs), "    theirs();\n");
+        assert_eq!(first.ours_branch_name.as_ref(), "HEAD");
+        assert_eq!(first.theirs_branch_name.as_ref(), "feature");
+
+        let second = &conflicts[1];
+        assert_eq!(text(&snapshot, &second.ours), "ours\n");
+        assert_eq!(
+            second.base.as_ref().map(|base| text(&snapshot, base)),
+            Some("base\n".to_string())
+        );
+        assert_eq!(text(&snapshot, &second.theirs), "theirs\n");
+        assert_eq!(second.range.end.to_offset(&snapshot), snapshot.len());
+    }
+
+    #[test]
+    fn test_parse_incomplete_conflicts() {
+        let (_, conflicts) = parse(concat!(
+            "<<<<<<< HEAD\n",
+            "ours\n",
+            ">>>>>>> feature\n",
+            "=======\n",
+            "<<<<<<< HEAD\n",
+            "ours\n",
+            "=======\n",
+            "theirs\n",
+        ));
+        assert!(conflicts.is_empty());
+    }
+
+    #[test]
+    fn test_has_conflict_markers() {
+        let (snapshot, conflicts) = parse("fn main() {\n<<<<<<< HEAD\n    ours();\n}\n");
+        assert!(conflicts.is_empty());
+        assert!(has_conflict_markers(&snapshot));
+
+        let (snapshot, _) = parse("fn main() {\n    ours();\n}\n");
+        assert!(!has_conflict_markers(&snapshot));
+    }
+}
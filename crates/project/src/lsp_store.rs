@@ -1,10 +1,12 @@
 pub mod clangd_ext;
 pub mod lsp_ext_command;
 pub mod rust_analyzer_ext;
+pub mod semantic_tokens;
 
 use crate::{
-    CodeAction, Completion, CompletionSource, CoreCompletion, Hover, InlayHint, LspAction,
-    ProjectItem, ProjectPath, ProjectTransaction, ResolveState, Symbol, ToolchainStore,
+    CallHierarchyItem, CodeAction, Completion, CompletionSource, CoreCompletion, Hover, InlayHint,
+    LspAction, ProjectItem, ProjectPath, ProjectTransaction, ResolveState, Symbol, ToolchainStore,
+    TypeHierarchyItem,
     buffer_store::{BufferStore, BufferStoreEvent},
     environment::ProjectEnvironment,
     lsp_command::{self, *},
@@ -35,9 +37,9 @@ use http_client::HttpClient;
 use itertools::Itertools as _;
 use language::{
     Bias, BinaryStatus, Buffer, BufferSnapshot, CachedLspAdapter, CodeLabel, Diagnostic,
-    DiagnosticEntry, DiagnosticSet, Diff, File as _, Language, LanguageRegistry,
-    LanguageToolchainStore, LocalFile, LspAdapter, LspAdapterDelegate, Patch, PointUtf16,
-    TextBufferSnapshot, ToOffset, ToPointUtf16, Transaction, Unclipped,
+    DiagnosticEntry, DiagnosticSet, DiagnosticSourceKind, Diff, File as _, Language,
+    LanguageRegistry, LanguageToolchainStore, LocalFile, LspAdapter, LspAdapterDelegate, Patch,
+    PointUtf16, TextBufferSnapshot, ToOffset, ToPointUtf16, Transaction, Unclipped,
     language_settings::{
         AllLanguageSettings, FormatOnSave, Formatter, LanguageSettings, LspInsertMode,
         SelectedFormatter, language_settings,
@@ -59,6 +61,7 @@ use node_runtime::read_package_installed_version;
 use parking_lot::Mutex;
 use postage::watch;
 use rand::prelude::*;
+use semantic_tokens::BufferSemanticTokens;
 
 use rpc::{
     AnyProtoClient,
@@ -83,6 +86,7 @@ use std::{
     sync::Arc,
     time::{Duration, Instant},
 };
+use task::{ProblemSeverity, TaskId, TaskProblem};
 use text::{Anchor, BufferId, LineEnding, OffsetRangeExt};
 use url::Url;
 use util::{
@@ -101,6 +105,7 @@ pub use worktree::{
 
 const SERVER_LAUNCHING_BEFORE_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
 pub const SERVER_PROGRESS_THROTTLE_TIMEOUT: Duration = Duration::from_millis(100);
+const PULL_DIAGNOSTICS_DEBOUNCE: Duration = Duration::from_millis(125);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FormatTrigger {
@@ -161,6 +166,21 @@ pub struct LocalLspStore {
     _subscription: gpui::Subscription,
     lsp_tree: Entity<LanguageServerTree>,
     registered_buffers: HashMap<BufferId, usize>,
+    semantic_tokens: HashMap<BufferId, CachedSemanticTokens>,
+    /// The `resultId` of the last diagnostics pulled from each server, by document.
+    diagnostic_result_ids: HashMap<LanguageServerId, HashMap<lsp::Url, String>>,
+    pull_diagnostics_tasks: HashMap<BufferId, Task<()>>,
+    workspace_diagnostics_tasks: HashMap<LanguageServerId, Task<()>>,
+    /// Diagnostics found in the tasks' output are reported under a server id allocated per task,
+    /// along with the paths that have them, to clear those on the task rerun.
+    task_diagnostics: HashMap<TaskId, (LanguageServerId, HashSet<PathBuf>)>,
+}
+
+/// The last semantic tokens reported for a buffer, kept to request deltas against them.
+struct CachedSemanticTokens {
+    server_id: LanguageServerId,
+    result_id: Option<String>,
+    data: Arc<[lsp::SemanticToken]>,
 }
 
 impl LocalLspStore {
@@ -844,6 +864,44 @@ impl LocalLspStore {
             })
             .detach();
 
+        language_server
+            .on_request::<lsp::request::WorkspaceDiagnosticRefresh, _, _>({
+                let this = this.clone();
+                move |(), cx| {
+                    let this = this.clone();
+                    let mut cx = cx.clone();
+                    async move {
+                        this.update(&mut cx, |this, cx| {
+                            this.refresh_pulled_diagnostics(server_id, cx);
+                        })?;
+                        Ok(())
+                    }
+                }
+            })
+            .detach();
+
+        language_server
+            .on_request::<lsp::request::SemanticTokensRefresh, _, _>({
+                let this = this.clone();
+                move |(), cx| {
+                    let this = this.clone();
+                    let mut cx = cx.clone();
+                    async move {
+                        this.update(&mut cx, |this, cx| {
+                            cx.emit(LspStoreEvent::RefreshSemanticTokens);
+                            this.downstream_client.as_ref().map(|(client, project_id)| {
+                                client.send(proto::RefreshSemanticTokens {
+                                    project_id: *project_id,
+                                })
+                            })
+                        })?
+                        .transpose()?;
+                        Ok(())
+                    }
+                }
+            })
+            .detach();
+
         language_server
             .on_request::<lsp::request::ShowMessageRequest, _, _>({
                 let this = this.clone();
@@ -2434,6 +2492,8 @@ impl LocalLspStore {
     ) {
         buffer.update(cx, |buffer, cx| {
             let _ = self.buffer_snapshots.remove(&buffer.remote_id());
+            self.semantic_tokens.remove(&buffer.remote_id());
+            self.pull_diagnostics_tasks.remove(&buffer.remote_id());
 
             for (_, language_server) in self.language_servers_for_buffer(buffer, cx) {
                 language_server.unregister_buffer(file_url.clone());
@@ -3350,6 +3410,7 @@ pub enum LspStoreEvent {
     Notification(String),
     RefreshInlayHints,
     RefreshCodeLens,
+    RefreshSemanticTokens,
     DiagnosticsUpdated {
         language_server_id: LanguageServerId,
         path: ProjectPath,
@@ -3406,6 +3467,8 @@ impl LspStore {
         client.add_entity_request_handler(Self::handle_open_buffer_for_symbol);
         client.add_entity_request_handler(Self::handle_refresh_inlay_hints);
         client.add_entity_request_handler(Self::handle_refresh_code_lens);
+        client.add_entity_request_handler(Self::handle_refresh_semantic_tokens);
+        client.add_entity_request_handler(Self::handle_get_semantic_tokens);
         client.add_entity_request_handler(Self::handle_on_type_formatting);
         client.add_entity_request_handler(Self::handle_apply_additional_edits_for_completion);
         client.add_entity_request_handler(Self::handle_register_buffer_with_language_servers);
@@ -3419,6 +3482,12 @@ impl LspStore {
         client.add_entity_request_handler(Self::handle_lsp_command::<GetTypeDefinition>);
         client.add_entity_request_handler(Self::handle_lsp_command::<GetDocumentHighlights>);
         client.add_entity_request_handler(Self::handle_lsp_command::<GetDocumentSymbols>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<PrepareCallHierarchy>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetIncomingCalls>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetOutgoingCalls>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<PrepareTypeHierarchy>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetSupertypes>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetSubtypes>);
         client.add_entity_request_handler(Self::handle_lsp_command::<GetReferences>);
         client.add_entity_request_handler(Self::handle_lsp_command::<PrepareRename>);
         client.add_entity_request_handler(Self::handle_lsp_command::<PerformRename>);
@@ -3535,6 +3604,11 @@ impl LspStore {
                 }),
                 lsp_tree: LanguageServerTree::new(manifest_tree, languages.clone(), cx),
                 registered_buffers: Default::default(),
+                semantic_tokens: Default::default(),
+                diagnostic_result_ids: Default::default(),
+                pull_diagnostics_tasks: Default::default(),
+                workspace_diagnostics_tasks: Default::default(),
+                task_diagnostics: Default::default(),
             }),
             last_formatting_failure: None,
             downstream_client: None,
@@ -3728,7 +3802,8 @@ impl LspStore {
     ) {
         match event {
             language::BufferEvent::Edited { .. } => {
-                self.on_buffer_edited(buffer, cx);
+                self.on_buffer_edited(buffer.clone(), cx);
+                self.pull_diagnostics_for_buffer(buffer, cx);
             }
 
             language::BufferEvent::Saved => {
@@ -3883,6 +3958,7 @@ impl LspStore {
 
             if ignore_refcounts || *refcount == 1 {
                 local.register_buffer_with_language_servers(buffer, cx);
+                self.pull_diagnostics_for_buffer(buffer.clone(), cx);
             }
             if !ignore_refcounts {
                 cx.observe_release(&handle, move |this, buffer, cx| {
@@ -5585,6 +5661,151 @@ impl LspStore {
         }
     }
 
+    /// Fetches the semantic tokens of the buffer from the first of its language servers that
+    /// provides them. Deltas are requested when the server supports them and has already
+    /// reported tokens for the buffer.
+    pub fn semantic_tokens(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Option<BufferSemanticTokens>>> {
+        let version = buffer.read(cx).version();
+        if let Some((client, project_id)) = self.upstream_client() {
+            let request = proto::GetSemanticTokens {
+                project_id,
+                buffer_id: buffer.read(cx).remote_id().into(),
+                version: serialize_version(&version),
+            };
+            return cx.background_spawn(async move {
+                let response = client
+                    .request(request)
+                    .await
+                    .context("semantic tokens proto request")?;
+                response
+                    .tokens
+                    .map(BufferSemanticTokens::from_proto)
+                    .transpose()
+            });
+        }
+
+        let Some((language_server, options)) = buffer.update(cx, |buffer, cx| {
+            self.as_local()?
+                .language_servers_for_buffer(buffer, cx)
+                .find_map(|(_, server)| {
+                    let options = match server.capabilities().semantic_tokens_provider? {
+                        lsp::SemanticTokensServerCapabilities::SemanticTokensOptions(options) => {
+                            options
+                        }
+                        lsp::SemanticTokensServerCapabilities::SemanticTokensRegistrationOptions(
+                            options,
+                        ) => options.semantic_tokens_options,
+                    };
+                    Some((server.clone(), options))
+                })
+        }) else {
+            return Task::ready(Ok(None));
+        };
+        let Some(abs_path) = File::from_dyn(buffer.read(cx).file())
+            .and_then(|file| Some(file.as_local()?.abs_path(cx)))
+        else {
+            return Task::ready(Ok(None));
+        };
+        let Some(uri) = lsp::Url::from_file_path(&abs_path).log_err() else {
+            return Task::ready(Ok(None));
+        };
+
+        let buffer_id = buffer.read(cx).remote_id();
+        let server_id = language_server.server_id();
+        let legend = Arc::new(options.legend);
+        let supports_delta = matches!(
+            options.full,
+            Some(lsp::SemanticTokensFullOptions::Delta { delta: Some(true) })
+        );
+        let previous = self
+            .as_local()
+            .and_then(|local| local.semantic_tokens.get(&buffer_id))
+            .filter(|cached| supports_delta && cached.server_id == server_id)
+            .and_then(|cached| Some((cached.result_id.clone()?, cached.data.clone())));
+        let text_document = lsp::TextDocumentIdentifier::new(uri);
+
+        cx.spawn(async move |this, cx| {
+            let response = if let Some((previous_result_id, previous_data)) = previous {
+                language_server
+                    .request::<lsp::request::SemanticTokensFullDeltaRequest>(
+                        lsp::SemanticTokensDeltaParams {
+                            text_document,
+                            previous_result_id,
+                            work_done_progress_params: Default::default(),
+                            partial_result_params: Default::default(),
+                        },
+                    )
+                    .await
+                    .and_then(|response| match response {
+                        Some(lsp::SemanticTokensFullDeltaResult::Tokens(tokens)) => {
+                            Ok((tokens.result_id, tokens.data))
+                        }
+                        Some(lsp::SemanticTokensFullDeltaResult::TokensDelta(delta)) => {
+                            let mut data = previous_data.to_vec();
+                            semantic_tokens::apply_semantic_token_edits(&mut data, delta.edits)?;
+                            Ok((delta.result_id, data))
+                        }
+                        Some(lsp::SemanticTokensFullDeltaResult::PartialTokensDelta { edits }) => {
+                            let mut data = previous_data.to_vec();
+                            semantic_tokens::apply_semantic_token_edits(&mut data, edits)?;
+                            Ok((None, data))
+                        }
+                        None => Ok((None, Vec::new())),
+                    })
+            } else {
+                language_server
+                    .request::<lsp::request::SemanticTokensFullRequest>(lsp::SemanticTokensParams {
+                        text_document,
+                        work_done_progress_params: Default::default(),
+                        partial_result_params: Default::default(),
+                    })
+                    .await
+                    .map(|response| match response {
+                        Some(lsp::SemanticTokensResult::Tokens(tokens)) => {
+                            (tokens.result_id, tokens.data)
+                        }
+                        Some(lsp::SemanticTokensResult::Partial(partial)) => (None, partial.data),
+                        None => (None, Vec::new()),
+                    })
+            };
+
+            let (result_id, data) = match response {
+                Ok(response) => response,
+                Err(error) => {
+                    // Start over with a full request next time, in case the server lost track
+                    // of the result the delta was based on.
+                    this.update(cx, |this, _| {
+                        if let Some(local) = this.as_local_mut() {
+                            local.semantic_tokens.remove(&buffer_id);
+                        }
+                    })?;
+                    return Err(error.context(format!(
+                        "semantic tokens via {} failed",
+                        language_server.name()
+                    )));
+                }
+            };
+            let data = Arc::<[lsp::SemanticToken]>::from(data);
+            this.update(cx, |this, _| {
+                if let Some(local) = this.as_local_mut() {
+                    local.semantic_tokens.insert(
+                        buffer_id,
+                        CachedSemanticTokens {
+                            server_id,
+                            result_id,
+                            data: data.clone(),
+                        },
+                    );
+                }
+            })?;
+            Ok(Some(BufferSemanticTokens::new(version, legend, data)))
+        })
+    }
+
     pub fn signature_help<T: ToPointUtf16>(
         &mut self,
         buffer: &Entity<Buffer>,
@@ -7871,6 +8092,40 @@ impl LspStore {
         Ok(proto::Ack {})
     }
 
+    async fn handle_refresh_semantic_tokens(
+        this: Entity<Self>,
+        _: TypedEnvelope<proto::RefreshSemanticTokens>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        this.update(&mut cx, |_, cx| {
+            cx.emit(LspStoreEvent::RefreshSemanticTokens);
+        })?;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_get_semantic_tokens(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GetSemanticTokens>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GetSemanticTokensResponse> {
+        let buffer_id = BufferId::new(envelope.payload.buffer_id)?;
+        let buffer = this.update(&mut cx, |this, cx| {
+            this.buffer_store.read(cx).get_existing(buffer_id)
+        })??;
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&envelope.payload.version))
+            })?
+            .await
+            .with_context(|| format!("waiting for version for buffer {}", buffer.entity_id()))?;
+        let tokens = this
+            .update(&mut cx, |this, cx| this.semantic_tokens(&buffer, cx))?
+            .await?;
+        Ok(proto::GetSemanticTokensResponse {
+            tokens: tokens.as_ref().map(BufferSemanticTokens::to_proto),
+        })
+    }
+
     async fn handle_open_buffer_for_symbol(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::OpenBufferForSymbol>,
@@ -8394,6 +8649,8 @@ impl LspStore {
             });
         }
         local.language_server_watched_paths.remove(&server_id);
+        local.diagnostic_result_ids.remove(&server_id);
+        local.workspace_diagnostics_tasks.remove(&server_id);
         let server_state = local.language_servers.remove(&server_id);
         cx.notify();
         cx.emit(LspStoreEvent::LanguageServerRemoved(server_id));
@@ -8484,8 +8741,9 @@ impl LspStore {
         self.merge_diagnostics(
             language_server_id,
             params,
+            DiagnosticSourceKind::Pushed,
             disk_based_sources,
-            |_| false,
+            |diagnostic| diagnostic.source_kind == DiagnosticSourceKind::Pulled,
             cx,
         )
     }
@@ -8494,6 +8752,7 @@ impl LspStore {
         &mut self,
         language_server_id: LanguageServerId,
         mut params: lsp::PublishDiagnosticsParams,
+        source_kind: DiagnosticSourceKind,
         disk_based_sources: &[String],
         filter: F,
         cx: &mut Context<Self>,
@@ -8558,6 +8817,7 @@ impl LspStore {
                         is_primary: true,
                         is_disk_based,
                         is_unnecessary,
+                        source_kind,
                         data: diagnostic.data.clone(),
                     },
                 });
@@ -8576,6 +8836,7 @@ impl LspStore {
                                     is_primary: false,
                                     is_disk_based,
                                     is_unnecessary: false,
+                                    source_kind,
                                     data: diagnostic.data.clone(),
                                 },
                             });
@@ -8613,6 +8874,357 @@ impl LspStore {
         Ok(())
     }
 
+    /// Replaces the diagnostics found in the output of the task given with the problems given,
+    /// clearing them in the files that no longer have any.
+    ///
+    /// Only local projects get these diagnostics. In remote and shared projects, the diagnostics are
+    /// owned by the host, which no message forwards the problems to yet, so they are dropped.
+    pub fn update_task_diagnostics(
+        &mut self,
+        task_id: &TaskId,
+        problems: Vec<TaskProblem>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(local) = self.as_local_mut() else {
+            return;
+        };
+        let languages = local.languages.clone();
+        let (server_id, previous_paths) = local
+            .task_diagnostics
+            .entry(task_id.clone())
+            .or_insert_with(|| (languages.next_language_server_id(), HashSet::default()));
+        let server_id = *server_id;
+        let previous_paths = mem::take(previous_paths);
+
+        let mut problems_by_path = HashMap::<PathBuf, Vec<TaskProblem>>::default();
+        for path in previous_paths {
+            problems_by_path.entry(path).or_default();
+        }
+        for problem in problems {
+            if problem.path.is_absolute() {
+                problems_by_path
+                    .entry(problem.path.clone())
+                    .or_default()
+                    .push(problem);
+            }
+        }
+
+        let mut paths_with_problems = HashSet::default();
+        for (path, problems) in problems_by_path {
+            let Ok(uri) = lsp::Url::from_file_path(&path) else {
+                continue;
+            };
+            if !problems.is_empty() {
+                paths_with_problems.insert(path);
+            }
+            // Task output refers to the files saved on disk, so adjust the problems for unsaved edits.
+            let disk_based_sources = problems
+                .iter()
+                .filter_map(|problem| problem.source.clone())
+                .collect::<Vec<_>>();
+            let diagnostics = problems
+                .into_iter()
+                .map(|problem| lsp::Diagnostic {
+                    range: lsp::Range::new(
+                        lsp::Position::new(problem.start_line, problem.start_column),
+                        lsp::Position::new(problem.end_line, problem.end_column),
+                    ),
+                    severity: Some(match problem.severity {
+                        ProblemSeverity::Error => DiagnosticSeverity::ERROR,
+                        ProblemSeverity::Warning => DiagnosticSeverity::WARNING,
+                        ProblemSeverity::Info => DiagnosticSeverity::INFORMATION,
+                        ProblemSeverity::Hint => DiagnosticSeverity::HINT,
+                    }),
+                    code: problem.code.map(lsp::NumberOrString::String),
+                    source: problem.source,
+                    message: problem.message,
+                    ..lsp::Diagnostic::default()
+                })
+                .collect();
+            self.merge_diagnostics(
+                server_id,
+                lsp::PublishDiagnosticsParams {
+                    uri,
+                    diagnostics,
+                    version: None,
+                },
+                DiagnosticSourceKind::Other,
+                &disk_based_sources,
+                |_| false,
+                cx,
+            )
+            .log_err();
+        }
+
+        if let Some((_, paths)) = self
+            .as_local_mut()
+            .and_then(|local| local.task_diagnostics.get_mut(task_id))
+        {
+            *paths = paths_with_problems;
+        }
+    }
+
+    /// Pulls document diagnostics for the buffer from its language servers that support the pull
+    /// model, once the buffer has stopped changing for a moment.
+    pub fn pull_diagnostics_for_buffer(&mut self, buffer: Entity<Buffer>, cx: &mut Context<Self>) {
+        let Some(local) = self.as_local_mut() else {
+            return;
+        };
+        let buffer_id = buffer.read(cx).remote_id();
+        let task = cx.spawn(async move |this, cx| {
+            cx.background_executor()
+                .timer(PULL_DIAGNOSTICS_DEBOUNCE)
+                .await;
+            let Ok(pull) = this.update(cx, |this, cx| this.pull_document_diagnostics(&buffer, cx))
+            else {
+                return;
+            };
+            pull.await;
+        });
+        local.pull_diagnostics_tasks.insert(buffer_id, task);
+    }
+
+    fn pull_document_diagnostics(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<()> {
+        let Some(local) = self.as_local() else {
+            return Task::ready(());
+        };
+        let Some(uri) = File::from_dyn(buffer.read(cx).file())
+            .and_then(|file| file.as_local())
+            .and_then(|file| lsp::Url::from_file_path(file.abs_path(cx)).ok())
+        else {
+            return Task::ready(());
+        };
+        let buffer_id = buffer.read(cx).remote_id();
+        let servers = buffer.update(cx, |buffer, cx| {
+            local
+                .language_servers_for_buffer(buffer, cx)
+                .filter_map(|(_, server)| Some((server.clone(), diagnostic_options(server)?)))
+                .collect::<Vec<_>>()
+        });
+
+        let requests = servers
+            .into_iter()
+            .map(|(server, options)| {
+                let server_id = server.server_id();
+                let version = local
+                    .buffer_snapshots
+                    .get(&buffer_id)
+                    .and_then(|snapshots| snapshots.get(&server_id)?.last())
+                    .map(|snapshot| snapshot.version);
+                let previous_result_id = local
+                    .diagnostic_result_ids
+                    .get(&server_id)
+                    .and_then(|result_ids| result_ids.get(&uri))
+                    .cloned();
+                let request = server.request::<lsp::request::DocumentDiagnosticRequest>(
+                    lsp::DocumentDiagnosticParams {
+                        text_document: lsp::TextDocumentIdentifier::new(uri.clone()),
+                        identifier: options.identifier,
+                        previous_result_id,
+                        work_done_progress_params: Default::default(),
+                        partial_result_params: Default::default(),
+                    },
+                );
+                let uri = uri.clone();
+                async move { (server_id, uri, version, request.await) }
+            })
+            .collect::<Vec<_>>();
+
+        cx.spawn(async move |this, cx| {
+            let responses = join_all(requests).await;
+            this.update(cx, |this, cx| {
+                for (server_id, uri, version, response) in responses {
+                    let Some(response) = response.log_err() else {
+                        continue;
+                    };
+                    let (report, related_documents) = match response {
+                        lsp::DocumentDiagnosticReportResult::Report(
+                            lsp::DocumentDiagnosticReport::Full(report),
+                        ) => (
+                            Some(lsp::DocumentDiagnosticReportKind::Full(
+                                report.full_document_diagnostic_report,
+                            )),
+                            report.related_documents,
+                        ),
+                        lsp::DocumentDiagnosticReportResult::Report(
+                            lsp::DocumentDiagnosticReport::Unchanged(report),
+                        ) => (
+                            Some(lsp::DocumentDiagnosticReportKind::Unchanged(
+                                report.unchanged_document_diagnostic_report,
+                            )),
+                            report.related_documents,
+                        ),
+                        lsp::DocumentDiagnosticReportResult::Partial(report) => {
+                            (None, report.related_documents)
+                        }
+                    };
+                    if let Some(report) = report {
+                        this.update_pulled_diagnostics(server_id, uri, version, report, cx)
+                            .log_err();
+                    }
+                    for (uri, report) in related_documents.into_iter().flatten() {
+                        this.update_pulled_diagnostics(server_id, uri, None, report, cx)
+                            .log_err();
+                    }
+                }
+            })
+            .ok();
+        })
+    }
+
+    /// Pulls the diagnostics of the whole workspace from the given server, if it supports them.
+    fn pull_workspace_diagnostics(&mut self, server_id: LanguageServerId, cx: &mut Context<Self>) {
+        let Some(local) = self.as_local_mut() else {
+            return;
+        };
+        let Some(server) = local.running_language_server_for_id(server_id).cloned() else {
+            return;
+        };
+        let Some(options) =
+            diagnostic_options(&server).filter(|options| options.workspace_diagnostics)
+        else {
+            return;
+        };
+        let previous_result_ids = local
+            .diagnostic_result_ids
+            .get(&server_id)
+            .into_iter()
+            .flatten()
+            .map(|(uri, result_id)| lsp::PreviousResultId {
+                uri: uri.clone(),
+                value: result_id.clone(),
+            })
+            .collect();
+        let request = server.request::<lsp::request::WorkspaceDiagnosticRequest>(
+            lsp::WorkspaceDiagnosticParams {
+                identifier: options.identifier,
+                previous_result_ids,
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            },
+        );
+
+        let task = cx.spawn(async move |this, cx| {
+            let Some(response) = request.await.log_err() else {
+                return;
+            };
+            let items = match response {
+                lsp::WorkspaceDiagnosticReportResult::Report(report) => report.items,
+                lsp::WorkspaceDiagnosticReportResult::Partial(report) => report.items,
+            };
+            this.update(cx, |this, cx| {
+                for item in items {
+                    let (uri, report) = match item {
+                        lsp::WorkspaceDocumentDiagnosticReport::Full(report) => (
+                            report.uri,
+                            lsp::DocumentDiagnosticReportKind::Full(
+                                report.full_document_diagnostic_report,
+                            ),
+                        ),
+                        lsp::WorkspaceDocumentDiagnosticReport::Unchanged(report) => (
+                            report.uri,
+                            lsp::DocumentDiagnosticReportKind::Unchanged(
+                                report.unchanged_document_diagnostic_report,
+                            ),
+                        ),
+                    };
+                    // Open buffers are kept up to date by document pulls, which know the
+                    // buffer version the diagnostics were computed for.
+                    let is_open = uri
+                        .to_file_path()
+                        .is_ok_and(|path| this.get_buffer(&path, cx).is_some());
+                    if !is_open {
+                        this.update_pulled_diagnostics(server_id, uri, None, report, cx)
+                            .log_err();
+                    }
+                }
+            })
+            .ok();
+        });
+        local.workspace_diagnostics_tasks.insert(server_id, task);
+    }
+
+    /// Pulls the diagnostics of every buffer the given server has open and of its workspace, e.g.
+    /// when the server asks for it through `workspace/diagnostic/refresh`.
+    fn refresh_pulled_diagnostics(&mut self, server_id: LanguageServerId, cx: &mut Context<Self>) {
+        let Some(local) = self.as_local() else {
+            return;
+        };
+        let buffers = self
+            .buffer_store
+            .read(cx)
+            .buffers()
+            .filter(|buffer| {
+                local
+                    .buffer_snapshots
+                    .get(&buffer.read(cx).remote_id())
+                    .is_some_and(|snapshots| snapshots.contains_key(&server_id))
+            })
+            .collect::<Vec<_>>();
+        for buffer in buffers {
+            self.pull_diagnostics_for_buffer(buffer, cx);
+        }
+        self.pull_workspace_diagnostics(server_id, cx);
+    }
+
+    fn update_pulled_diagnostics(
+        &mut self,
+        server_id: LanguageServerId,
+        uri: lsp::Url,
+        version: Option<i32>,
+        report: lsp::DocumentDiagnosticReportKind,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        let result_id = match report {
+            lsp::DocumentDiagnosticReportKind::Full(report) => {
+                let adapter = self
+                    .language_server_adapter_for_id(server_id)
+                    .ok_or_else(|| anyhow!("no running language server {server_id}"))?;
+                let mut params = lsp::PublishDiagnosticsParams {
+                    uri: uri.clone(),
+                    diagnostics: report.items,
+                    version,
+                };
+                let buffer = params
+                    .uri
+                    .to_file_path()
+                    .ok()
+                    .and_then(|path| self.get_buffer(&path, cx));
+                adapter.process_diagnostics(&mut params, server_id, buffer);
+                self.merge_diagnostics(
+                    server_id,
+                    params,
+                    DiagnosticSourceKind::Pulled,
+                    &adapter.disk_based_diagnostic_sources,
+                    |diagnostic| diagnostic.source_kind != DiagnosticSourceKind::Pulled,
+                    cx,
+                )?;
+                report.result_id
+            }
+            lsp::DocumentDiagnosticReportKind::Unchanged(report) => Some(report.result_id),
+        };
+
+        let result_ids = self
+            .as_local_mut()
+            .context("pulled diagnostics on remote")?
+            .diagnostic_result_ids
+            .entry(server_id)
+            .or_default();
+        match result_id {
+            Some(result_id) => {
+                result_ids.insert(uri, result_id);
+            }
+            None => {
+                result_ids.remove(&uri);
+            }
+        }
+        Ok(())
+    }
+
     fn insert_newly_running_language_server(
         &mut self,
         adapter: Arc<CachedLspAdapter>,
@@ -8682,6 +9294,7 @@ impl LspStore {
             Some(key.0),
         ));
         cx.emit(LspStoreEvent::RefreshInlayHints);
+        cx.emit(LspStoreEvent::RefreshSemanticTokens);
 
         if let Some((downstream_client, project_id)) = self.downstream_client.as_ref() {
             downstream_client
@@ -8775,6 +9388,7 @@ impl LspStore {
                 });
             }
         });
+        self.refresh_pulled_diagnostics(server_id, cx);
 
         cx.notify();
     }
@@ -9053,6 +9667,184 @@ impl LspStore {
         })
     }
 
+    /// Builds a [`Symbol`] for an item of a call or type hierarchy, so that it can be opened like a workspace symbol.
+    /// Items outside of the project's worktrees are attributed to `source_worktree`.
+    fn hierarchy_symbol_from_lsp(
+        &self,
+        uri: &lsp::Url,
+        name: &str,
+        kind: lsp::SymbolKind,
+        range: lsp::Range,
+        server_id: LanguageServerId,
+        source_worktree: &Entity<Worktree>,
+        cx: &App,
+    ) -> Option<Symbol> {
+        let abs_path = uri.to_file_path().ok()?;
+        let source_worktree_id = source_worktree.read(cx).id();
+        let (worktree_id, path) = match self.worktree_store.read(cx).find_worktree(&abs_path, cx) {
+            Some((worktree, path)) => (worktree.read(cx).id(), path),
+            None => (
+                source_worktree_id,
+                relativize_path(&source_worktree.read(cx).abs_path(), &abs_path),
+            ),
+        };
+        let project_path = ProjectPath {
+            worktree_id,
+            path: path.into(),
+        };
+        let signature = self.symbol_signature(&project_path);
+        let language_server_name = self.language_server_adapter_for_id(server_id)?.name.clone();
+
+        Some(Symbol {
+            language_server_name,
+            source_worktree_id,
+            source_language_server_id: server_id,
+            path: project_path,
+            label: CodeLabel::plain(name.to_string(), None),
+            name: name.to_string(),
+            kind,
+            range: range_from_lsp(range),
+            signature,
+        })
+    }
+
+    fn hierarchy_symbol_from_proto(symbol: Option<proto::Symbol>) -> Result<Symbol> {
+        let symbol = Self::deserialize_symbol(symbol.context("invalid symbol")?)?;
+        Ok(Symbol {
+            language_server_name: symbol.language_server_name,
+            source_worktree_id: symbol.source_worktree_id,
+            source_language_server_id: symbol.source_language_server_id,
+            path: symbol.path,
+            label: CodeLabel::plain(symbol.name.clone(), None),
+            name: symbol.name,
+            kind: symbol.kind,
+            range: symbol.range,
+            signature: symbol.signature,
+        })
+    }
+
+    pub(crate) fn call_hierarchy_item_from_lsp(
+        &self,
+        lsp_item: lsp::CallHierarchyItem,
+        server_id: LanguageServerId,
+        source_worktree: &Entity<Worktree>,
+        cx: &App,
+    ) -> Option<CallHierarchyItem> {
+        let symbol = self.hierarchy_symbol_from_lsp(
+            &lsp_item.uri,
+            &lsp_item.name,
+            lsp_item.kind,
+            lsp_item.range,
+            server_id,
+            source_worktree,
+            cx,
+        )?;
+        Some(CallHierarchyItem {
+            symbol,
+            detail: lsp_item.detail.clone(),
+            selection_range: range_from_lsp(lsp_item.selection_range),
+            lsp_item,
+        })
+    }
+
+    pub(crate) fn serialize_call_hierarchy_item(
+        item: &CallHierarchyItem,
+    ) -> proto::CallHierarchyItem {
+        proto::CallHierarchyItem {
+            symbol: Some(Self::serialize_symbol(&item.symbol)),
+            detail: item.detail.clone(),
+            selection_start: Some(proto::PointUtf16 {
+                row: item.selection_range.start.0.row,
+                column: item.selection_range.start.0.column,
+            }),
+            selection_end: Some(proto::PointUtf16 {
+                row: item.selection_range.end.0.row,
+                column: item.selection_range.end.0.column,
+            }),
+            lsp_item: serde_json::to_vec(&item.lsp_item).unwrap(),
+        }
+    }
+
+    pub(crate) fn deserialize_call_hierarchy_item(
+        item: proto::CallHierarchyItem,
+    ) -> Result<CallHierarchyItem> {
+        let start = item
+            .selection_start
+            .ok_or_else(|| anyhow!("invalid selection start"))?;
+        let end = item
+            .selection_end
+            .ok_or_else(|| anyhow!("invalid selection end"))?;
+
+        Ok(CallHierarchyItem {
+            symbol: Self::hierarchy_symbol_from_proto(item.symbol)?,
+            detail: item.detail,
+            selection_range: Unclipped(PointUtf16::new(start.row, start.column))
+                ..Unclipped(PointUtf16::new(end.row, end.column)),
+            lsp_item: serde_json::from_slice(&item.lsp_item)?,
+        })
+    }
+
+    pub(crate) fn type_hierarchy_item_from_lsp(
+        &self,
+        lsp_item: lsp::TypeHierarchyItem,
+        server_id: LanguageServerId,
+        source_worktree: &Entity<Worktree>,
+        cx: &App,
+    ) -> Option<TypeHierarchyItem> {
+        let symbol = self.hierarchy_symbol_from_lsp(
+            &lsp_item.uri,
+            &lsp_item.name,
+            lsp_item.kind,
+            lsp_item.range,
+            server_id,
+            source_worktree,
+            cx,
+        )?;
+        Some(TypeHierarchyItem {
+            symbol,
+            detail: lsp_item.detail.clone(),
+            selection_range: range_from_lsp(lsp_item.selection_range),
+            lsp_item,
+        })
+    }
+
+    pub(crate) fn serialize_type_hierarchy_item(
+        item: &TypeHierarchyItem,
+    ) -> proto::TypeHierarchyItem {
+        proto::TypeHierarchyItem {
+            symbol: Some(Self::serialize_symbol(&item.symbol)),
+            detail: item.detail.clone(),
+            selection_start: Some(proto::PointUtf16 {
+                row: item.selection_range.start.0.row,
+                column: item.selection_range.start.0.column,
+            }),
+            selection_end: Some(proto::PointUtf16 {
+                row: item.selection_range.end.0.row,
+                column: item.selection_range.end.0.column,
+            }),
+            lsp_item: serde_json::to_vec(&item.lsp_item).unwrap(),
+        }
+    }
+
+    pub(crate) fn deserialize_type_hierarchy_item(
+        item: proto::TypeHierarchyItem,
+    ) -> Result<TypeHierarchyItem> {
+        let start = item
+            .selection_start
+            .ok_or_else(|| anyhow!("invalid selection start"))?;
+        let end = item
+            .selection_end
+            .ok_or_else(|| anyhow!("invalid selection end"))?;
+
+        Ok(TypeHierarchyItem {
+            symbol: Self::hierarchy_symbol_from_proto(item.symbol)?,
+            detail: item.detail,
+            selection_range: Unclipped(PointUtf16::new(start.row, start.column))
+                ..Unclipped(PointUtf16::new(end.row, end.column)),
+            lsp_item: serde_json::from_slice(&item.lsp_item)?,
+        })
+    }
+
     pub(crate) fn serialize_completion(completion: &CoreCompletion) -> proto::Completion {
         let mut serialized_completion = proto::Completion {
             old_start: Some(serialize_anchor(&completion.old_range.start)),
@@ -10121,6 +10913,15 @@ async fn populate_labels_for_symbols(
     }
 }
 
+fn diagnostic_options(server: &lsp::LanguageServer) -> Option<lsp::DiagnosticOptions> {
+    match server.capabilities().diagnostic_provider? {
+        lsp::DiagnosticServerCapabilities::Options(options) => Some(options),
+        lsp::DiagnosticServerCapabilities::RegistrationOptions(options) => {
+            Some(options.diagnostic_options)
+        }
+    }
+}
+
 fn include_text(server: &lsp::LanguageServer) -> Option<bool> {
     match server.capabilities().text_document_sync.as_ref()? {
         lsp::TextDocumentSyncCapability::Kind(kind) => match *kind {
@@ -0,0 +1,163 @@
+use std::{cmp::Reverse, ops::Range, sync::Arc};
+
+use anyhow::{Result, anyhow};
+use client::proto;
+use language::{
+    PointUtf16,
+    proto::{deserialize_version, serialize_version},
+};
+
+/// Semantic tokens reported by a language server for a buffer, as of `version`.
+#[derive(Clone, Debug)]
+pub struct BufferSemanticTokens {
+    pub version: clock::Global,
+    legend: Arc<lsp::SemanticTokensLegend>,
+    data: Arc<[lsp::SemanticToken]>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SemanticToken<'a> {
+    pub range: Range<PointUtf16>,
+    pub token_type: &'a str,
+    pub modifiers: Vec<&'a str>,
+}
+
+impl BufferSemanticTokens {
+    pub(crate) fn new(
+        version: clock::Global,
+        legend: Arc<lsp::SemanticTokensLegend>,
+        data: Arc<[lsp::SemanticToken]>,
+    ) -> Self {
+        Self {
+            version,
+            legend,
+            data,
+        }
+    }
+
+    /// Decodes the tokens into absolute ranges, resolving their types and modifiers through the
+    /// server's legend. Tokens whose type is not part of the legend are skipped.
+    pub fn tokens(&self) -> impl Iterator<Item = SemanticToken<'_>> {
+        let mut line = 0;
+        let mut start = 0;
+        self.data.iter().filter_map(move |token| {
+            if token.delta_line > 0 {
+                line += token.delta_line;
+                start = token.delta_start;
+            } else {
+                start += token.delta_start;
+            }
+
+            let token_type = self.legend.token_types.get(token.token_type as usize)?;
+            let modifiers = self
+                .legend
+                .token_modifiers
+                .iter()
+                .take(u32::BITS as usize)
+                .enumerate()
+                .filter(|(ix, _)| token.token_modifiers_bitset & (1 << ix) != 0)
+                .map(|(_, modifier)| modifier.as_str())
+                .collect();
+            Some(SemanticToken {
+                range: PointUtf16::new(line, start)..PointUtf16::new(line, start + token.length),
+                token_type: token_type.as_str(),
+                modifiers,
+            })
+        })
+    }
+
+    pub(crate) fn to_proto(&self) -> proto::SemanticTokens {
+        proto::SemanticTokens {
+            token_types: self
+                .legend
+                .token_types
+                .iter()
+                .map(|token_type| token_type.as_str().to_string())
+                .collect(),
+            token_modifiers: self
+                .legend
+                .token_modifiers
+                .iter()
+                .map(|modifier| modifier.as_str().to_string())
+                .collect(),
+            data: self
+                .data
+                .iter()
+                .flat_map(|token| {
+                    [
+                        token.delta_line,
+                        token.delta_start,
+                        token.length,
+                        token.token_type,
+                        token.token_modifiers_bitset,
+                    ]
+                })
+                .collect(),
+            version: serialize_version(&self.version),
+        }
+    }
+
+    pub(crate) fn from_proto(message: proto::SemanticTokens) -> Result<Self> {
+        if message.data.len() % 5 != 0 {
+            return Err(anyhow!(
+                "invalid semantic tokens data length {}",
+                message.data.len()
+            ));
+        }
+        Ok(Self {
+            version: deserialize_version(&message.version),
+            legend: Arc::new(lsp::SemanticTokensLegend {
+                token_types: message
+                    .token_types
+                    .into_iter()
+                    .map(lsp::SemanticTokenType::from)
+                    .collect(),
+                token_modifiers: message
+                    .token_modifiers
+                    .into_iter()
+                    .map(lsp::SemanticTokenModifier::from)
+                    .collect(),
+            }),
+            data: message
+                .data
+                .chunks_exact(5)
+                .map(|chunk| lsp::SemanticToken {
+                    delta_line: chunk[0],
+                    delta_start: chunk[1],
+                    length: chunk[2],
+                    token_type: chunk[3],
+                    token_modifiers_bitset: chunk[4],
+                })
+                .collect(),
+        })
+    }
+}
+
+/// Applies a `textDocument/semanticTokens/full/delta` response to the previously reported tokens.
+///
+/// Edit offsets are expressed in integers of the LSP encoding, so they must fall on token boundaries.
+pub(crate) fn apply_semantic_token_edits(
+    data: &mut Vec<lsp::SemanticToken>,
+    mut edits: Vec<lsp::SemanticTokensEdit>,
+) -> Result<()> {
+    edits.sort_by_key(|edit| Reverse(edit.start));
+    for edit in edits {
+        if edit.start % 5 != 0 || edit.delete_count % 5 != 0 {
+            return Err(anyhow!(
+                "semantic tokens edit {}..+{} is not aligned to token boundaries",
+                edit.start,
+                edit.delete_count
+            ));
+        }
+        let start = (edit.start / 5) as usize;
+        let end = start + (edit.delete_count / 5) as usize;
+        if end > data.len() {
+            return Err(anyhow!(
+                "semantic tokens edit {start}..{end} is out of bounds for {} tokens",
+                data.len()
+            ));
+        }
+        data.splice(start..end, edit.data.unwrap_or_default());
+    }
+    Ok(())
+}
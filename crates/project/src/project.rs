@@ -127,6 +127,7 @@ pub use lsp_store::{
     DiagnosticSummary, LanguageServerLogType, LanguageServerProgress, LanguageServerPromptRequest,
     LanguageServerStatus, LanguageServerToQuery, LspStore, LspStoreEvent,
     SERVER_PROGRESS_THROTTLE_TIMEOUT,
+    semantic_tokens::{BufferSemanticTokens, SemanticToken},
 };
 pub use toolchain_store::ToolchainStore;
 const MAX_PROJECT_SEARCH_HISTORY_SIZE: usize = 500;
@@ -297,6 +298,7 @@ pub enum Event {
     Rejoined,
     RefreshInlayHints,
     RefreshCodeLens,
+    RefreshSemanticTokens,
     RevealInProjectPanel(ProjectEntryId),
     SnippetEdit(BufferId, Vec<(lsp::Range, Snippet)>),
     ExpandedAllForEntry(WorktreeId, ProjectEntryId),
@@ -668,6 +670,34 @@ pub struct DocumentSymbol {
     pub children: Vec<DocumentSymbol>,
 }
 
+/// A function, method or constructor in a call hierarchy, as returned by `textDocument/prepareCallHierarchy`.
+#[derive(Clone, Debug)]
+pub struct CallHierarchyItem {
+    /// Location of the item, which can be opened with [`Project::open_buffer_for_symbol`].
+    pub symbol: Symbol,
+    pub detail: Option<String>,
+    pub selection_range: Range<Unclipped<PointUtf16>>,
+    pub(crate) lsp_item: lsp::CallHierarchyItem,
+}
+
+#[derive(Clone, Debug)]
+pub struct CallHierarchyCall {
+    pub item: CallHierarchyItem,
+    /// For incoming calls, the ranges of the calls within the caller `item`.
+    /// For outgoing calls, the ranges of the calls within the item they were requested for.
+    pub ranges: Vec<Range<Unclipped<PointUtf16>>>,
+}
+
+/// A class, interface or trait in a type hierarchy, as returned by `textDocument/prepareTypeHierarchy`.
+#[derive(Clone, Debug)]
+pub struct TypeHierarchyItem {
+    /// Location of the item, which can be opened with [`Project::open_buffer_for_symbol`].
+    pub symbol: Symbol,
+    pub detail: Option<String>,
+    pub selection_range: Range<Unclipped<PointUtf16>>,
+    pub(crate) lsp_item: lsp::TypeHierarchyItem,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct HoverBlock {
     pub text: String,
@@ -2708,6 +2738,7 @@ impl Project {
             }
             LspStoreEvent::RefreshInlayHints => cx.emit(Event::RefreshInlayHints),
             LspStoreEvent::RefreshCodeLens => cx.emit(Event::RefreshCodeLens),
+            LspStoreEvent::RefreshSemanticTokens => cx.emit(Event::RefreshSemanticTokens),
             LspStoreEvent::LanguageServerPrompt(prompt) => {
                 cx.emit(Event::LanguageServerPrompt(prompt.clone()))
             }
@@ -3287,6 +3318,100 @@ impl Project {
         )
     }
 
+    pub fn prepare_call_hierarchy<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: T,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<CallHierarchyItem>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            PrepareCallHierarchy { position },
+            cx,
+        )
+    }
+
+    /// Returns the callers of an `item` returned by [`Project::prepare_call_hierarchy`] for `buffer`.
+    pub fn incoming_calls(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        item: CallHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<CallHierarchyCall>>> {
+        let server_id = item.symbol.source_language_server_id;
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::Other(server_id),
+            GetIncomingCalls { item },
+            cx,
+        )
+    }
+
+    /// Returns the calls made from an `item` returned by [`Project::prepare_call_hierarchy`] for `buffer`.
+    pub fn outgoing_calls(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        item: CallHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<CallHierarchyCall>>> {
+        let server_id = item.symbol.source_language_server_id;
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::Other(server_id),
+            GetOutgoingCalls { item },
+            cx,
+        )
+    }
+
+    pub fn prepare_type_hierarchy<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: T,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<TypeHierarchyItem>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            PrepareTypeHierarchy { position },
+            cx,
+        )
+    }
+
+    /// Returns the direct supertypes of an `item` returned by [`Project::prepare_type_hierarchy`] for `buffer`.
+    pub fn supertypes(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        item: TypeHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<TypeHierarchyItem>>> {
+        let server_id = item.symbol.source_language_server_id;
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::Other(server_id),
+            GetSupertypes { item },
+            cx,
+        )
+    }
+
+    /// Returns the direct subtypes of an `item` returned by [`Project::prepare_type_hierarchy`] for `buffer`.
+    pub fn subtypes(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        item: TypeHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<TypeHierarchyItem>>> {
+        let server_id = item.symbol.source_language_server_id;
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::Other(server_id),
+            GetSubtypes { item },
+            cx,
+        )
+    }
+
     pub fn symbols(&self, query: &str, cx: &mut Context<Self>) -> Task<Result<Vec<Symbol>>> {
         self.lsp_store
             .update(cx, |lsp_store, cx| lsp_store.symbols(query, cx))
@@ -3532,6 +3657,15 @@ impl Project {
         })
     }
 
+    pub fn semantic_tokens(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<Option<BufferSemanticTokens>>> {
+        self.lsp_store
+            .update(cx, |lsp_store, cx| lsp_store.semantic_tokens(buffer, cx))
+    }
+
     pub fn resolve_inlay_hint(
         &self,
         hint: InlayHint,
@@ -3552,6 +3686,7 @@ impl Project {
         } else {
             self.find_search_candidate_buffers(&query, MAX_SEARCH_RESULT_FILES + 1, cx)
         };
+        let languages = self.languages.clone();
 
         cx.spawn(async move |_, cx| {
             let mut range_count = 0;
@@ -3570,10 +3705,25 @@ impl Project {
                 for buffer in matching_buffer_chunk {
                     let buffer = buffer.clone();
                     let query = query.clone();
-                    let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot())?;
+                    let (snapshot, language) = buffer.read_with(cx, |buffer, cx| {
+                        let snapshot = buffer.snapshot();
+                        // Structural queries need a grammar even for buffers whose language is not detected yet.
+                        let language = (query.is_structural() && snapshot.language().is_none())
+                            .then(|| buffer.file())
+                            .flatten()
+                            .and_then(|file| {
+                                languages.language_for_file(file, Some(snapshot.as_rope()), cx)
+                            })
+                            .map(|language| languages.load_language(&language));
+                        (snapshot, language)
+                    })?;
                     chunk_results.push(cx.background_spawn(async move {
+                        let language = match language {
+                            Some(language) => language.await.ok().and_then(Result::ok),
+                            None => None,
+                        };
                         let ranges = query
-                            .search(&snapshot, None)
+                            .search_with_language(&snapshot, language, None)
                             .await
                             .iter()
                             .map(|range| {
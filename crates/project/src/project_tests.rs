@@ -10,15 +10,20 @@ use buffer_diff::{
 use fs::FakeFs;
 use futures::{StreamExt, future};
 use git::{
-    repository::RepoPath,
+    Oid,
+    blame::{Blame, BlameEntry},
+    repository::{
+        GitWorktree, LogEntry, LogOptions, RebaseAction, RebaseStatus, RebaseStep, RepoPath,
+        Submodule, SubmoduleStatus,
+    },
     status::{StatusCode, TrackedStatus},
 };
 use git2::RepositoryInitOptions;
 use gpui::{App, BackgroundExecutor, SemanticVersion, UpdateGlobal};
 use http_client::Url;
 use language::{
-    Diagnostic, DiagnosticEntry, DiagnosticSet, DiskState, FakeLspAdapter, LanguageConfig,
-    LanguageMatcher, LanguageName, LineEnding, OffsetRangeExt, Point, ToPoint,
+    Diagnostic, DiagnosticEntry, DiagnosticSet, DiagnosticSourceKind, DiskState, FakeLspAdapter,
+    LanguageConfig, LanguageMatcher, LanguageName, LineEnding, OffsetRangeExt, Point, ToPoint,
     language_settings::{AllLanguageSettings, LanguageSettingsContent, language_settings},
     tree_sitter_rust, tree_sitter_typescript,
 };
@@ -34,7 +39,7 @@ use serde_json::json;
 #[cfg(not(windows))]
 use std::os;
 use std::{mem, num::NonZeroU32, ops::Range, str::FromStr, sync::OnceLock, task::Poll};
-use task::{ResolvedTask, TaskContext};
+use task::{ProblemSeverity, ResolvedTask, TaskContext, TaskId, TaskProblem};
 use unindent::Unindent as _;
 use util::{
     TryFutureExt as _, assert_set_eq, path,
@@ -1509,6 +1514,7 @@ async fn test_disk_based_diagnostics_progress(cx: &mut gpui::TestAppContext) {
         .start_progress(format!("{}/0", progress_token))
         .await;
     assert_eq!(events.next().await.unwrap(), Event::RefreshInlayHints);
+    assert_eq!(events.next().await.unwrap(), Event::RefreshSemanticTokens);
     assert_eq!(
         events.next().await.unwrap(),
         Event::DiskBasedDiagnosticsStarted {
@@ -1561,6 +1567,7 @@ async fn test_disk_based_diagnostics_progress(cx: &mut gpui::TestAppContext) {
                     message: "undefined variable 'A'".to_string(),
                     group_id: 0,
                     is_primary: true,
+                    source_kind: DiagnosticSourceKind::Pushed,
                     ..Default::default()
                 }
             }]
@@ -1642,6 +1649,7 @@ async fn test_restarting_server_with_diagnostics_running(cx: &mut gpui::TestAppC
         )
     );
     assert_eq!(events.next().await.unwrap(), Event::RefreshInlayHints);
+    assert_eq!(events.next().await.unwrap(), Event::RefreshSemanticTokens);
     fake_server.start_progress(progress_token).await;
     assert_eq!(
         events.next().await.unwrap(),
@@ -2077,6 +2085,7 @@ async fn test_transforming_diagnostics(cx: &mut gpui::TestAppContext) {
                         is_disk_based: true,
                         group_id: 1,
                         is_primary: true,
+                        source_kind: DiagnosticSourceKind::Pushed,
                         ..Default::default()
                     },
                 },
@@ -2089,6 +2098,7 @@ async fn test_transforming_diagnostics(cx: &mut gpui::TestAppContext) {
                         is_disk_based: true,
                         group_id: 2,
                         is_primary: true,
+                        source_kind: DiagnosticSourceKind::Pushed,
                         ..Default::default()
                     }
                 }
@@ -2155,6 +2165,7 @@ async fn test_transforming_diagnostics(cx: &mut gpui::TestAppContext) {
                         is_disk_based: true,
                         group_id: 4,
                         is_primary: true,
+                        source_kind: DiagnosticSourceKind::Pushed,
                         ..Default::default()
                     }
                 },
@@ -2167,6 +2178,7 @@ async fn test_transforming_diagnostics(cx: &mut gpui::TestAppContext) {
                         is_disk_based: true,
                         group_id: 3,
                         is_primary: true,
+                        source_kind: DiagnosticSourceKind::Pushed,
                         ..Default::default()
                     },
                 }
@@ -2247,6 +2259,7 @@ async fn test_transforming_diagnostics(cx: &mut gpui::TestAppContext) {
                         is_disk_based: true,
                         group_id: 6,
                         is_primary: true,
+                        source_kind: DiagnosticSourceKind::Pushed,
                         ..Default::default()
                     }
                 },
@@ -2259,6 +2272,7 @@ async fn test_transforming_diagnostics(cx: &mut gpui::TestAppContext) {
                         is_disk_based: true,
                         group_id: 5,
                         is_primary: true,
+                        source_kind: DiagnosticSourceKind::Pushed,
                         ..Default::default()
                     },
                 }
@@ -2397,6 +2411,244 @@ async fn test_diagnostics_from_multiple_language_servers(cx: &mut gpui::TestAppC
     });
 }
 
+#[gpui::test]
+async fn test_task_diagnostics(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({ "a.rs": "let a = 1;\nlet b = 2;", "b.rs": "let c = 3;" }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+    let lsp_store = project.read_with(cx, |project, _| project.lsp_store.clone());
+    let problem = |file: &str, line: u32, severity: ProblemSeverity| TaskProblem {
+        owner: "rustc".to_string(),
+        path: Path::new(path!("/dir")).join(file),
+        start_line: line,
+        start_column: 4,
+        end_line: line,
+        end_column: u32::MAX,
+        severity,
+        code: None,
+        message: format!("problem in {file}"),
+        source: Some("rustc".to_string()),
+    };
+    let task_id = TaskId("check".to_string());
+
+    lsp_store.update(cx, |lsp_store, cx| {
+        lsp_store.update_task_diagnostics(
+            &task_id,
+            vec![
+                problem("a.rs", 0, ProblemSeverity::Error),
+                problem("a.rs", 1, ProblemSeverity::Warning),
+                problem("b.rs", 0, ProblemSeverity::Error),
+            ],
+            cx,
+        );
+        assert_eq!(
+            lsp_store.diagnostic_summary(false, cx),
+            DiagnosticSummary {
+                error_count: 2,
+                warning_count: 1,
+            }
+        );
+
+        lsp_store.update_task_diagnostics(
+            &task_id,
+            vec![problem("a.rs", 1, ProblemSeverity::Warning)],
+            cx,
+        );
+        assert_eq!(
+            lsp_store.diagnostic_summary(false, cx),
+            DiagnosticSummary {
+                error_count: 0,
+                warning_count: 1,
+            },
+            "Problems no longer reported by the task should be cleared"
+        );
+    });
+
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+    buffer.update(cx, |buffer, _| {
+        let chunks = chunks_with_diagnostics(buffer, 0..buffer.len());
+        assert_eq!(
+            chunks
+                .iter()
+                .map(|(s, d)| (s.as_str(), *d))
+                .collect::<Vec<_>>(),
+            &[
+                ("let a = 1;\nlet ", None),
+                ("b = 2;", Some(DiagnosticSeverity::WARNING)),
+            ]
+        );
+    });
+
+    lsp_store.update(cx, |lsp_store, cx| {
+        lsp_store.update_task_diagnostics(&task_id, Vec::new(), cx);
+        assert_eq!(
+            lsp_store.diagnostic_summary(false, cx),
+            DiagnosticSummary::default()
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_pulled_diagnostics(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(path!("/dir"), json!({ "a.rs": "let a = 1;" }))
+        .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                diagnostic_provider: Some(lsp::DiagnosticServerCapabilities::Options(
+                    lsp::DiagnosticOptions {
+                        identifier: Some("rust".into()),
+                        ..Default::default()
+                    },
+                )),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let fake_server = fake_servers.next().await.unwrap();
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    fake_server.set_request_handler::<lsp::request::DocumentDiagnosticRequest, _, _>({
+        let requests = requests.clone();
+        move |params, _| {
+            let requests = requests.clone();
+            async move {
+                assert_eq!(params.identifier.as_deref(), Some("rust"));
+                let report = if params.previous_result_id.is_none() {
+                    lsp::DocumentDiagnosticReport::Full(lsp::RelatedFullDocumentDiagnosticReport {
+                        related_documents: None,
+                        full_document_diagnostic_report: lsp::FullDocumentDiagnosticReport {
+                            result_id: Some("1".into()),
+                            items: vec![lsp::Diagnostic {
+                                range: lsp::Range::new(
+                                    lsp::Position::new(1, 4),
+                                    lsp::Position::new(1, 5),
+                                ),
+                                severity: Some(lsp::DiagnosticSeverity::ERROR),
+                                message: "unused variable 'a'".to_string(),
+                                ..Default::default()
+                            }],
+                        },
+                    })
+                } else {
+                    lsp::DocumentDiagnosticReport::Unchanged(
+                        lsp::RelatedUnchangedDocumentDiagnosticReport {
+                            related_documents: None,
+                            unchanged_document_diagnostic_report:
+                                lsp::UnchangedDocumentDiagnosticReport {
+                                    result_id: "1".into(),
+                                },
+                        },
+                    )
+                };
+                requests.lock().push(params.previous_result_id);
+                Ok(lsp::DocumentDiagnosticReportResult::Report(report))
+            }
+        }
+    });
+
+    // Diagnostics are pulled when the buffer changes.
+    buffer.update(cx, |buffer, cx| buffer.edit([(0..0, "\n")], None, cx));
+    cx.executor().advance_clock(Duration::from_secs(1));
+    cx.executor().run_until_parked();
+    assert_eq!(requests.lock().drain(..).collect::<Vec<_>>(), [None]);
+
+    // Diagnostics published by the same server are kept alongside the pulled ones.
+    fake_server.notify::<lsp::notification::PublishDiagnostics>(&lsp::PublishDiagnosticsParams {
+        uri: Url::from_file_path(path!("/dir/a.rs")).unwrap(),
+        version: None,
+        diagnostics: vec![lsp::Diagnostic {
+            range: lsp::Range::new(lsp::Position::new(1, 8), lsp::Position::new(1, 9)),
+            severity: Some(lsp::DiagnosticSeverity::WARNING),
+            message: "literal out of range".to_string(),
+            ..Default::default()
+        }],
+    });
+    cx.executor().run_until_parked();
+
+    let expected_diagnostics = [
+        DiagnosticEntry {
+            range: Point::new(1, 4)..Point::new(1, 5),
+            diagnostic: Diagnostic {
+                severity: DiagnosticSeverity::ERROR,
+                message: "unused variable 'a'".to_string(),
+                group_id: 0,
+                is_primary: true,
+                source_kind: DiagnosticSourceKind::Pulled,
+                ..Default::default()
+            },
+        },
+        DiagnosticEntry {
+            range: Point::new(1, 8)..Point::new(1, 9),
+            diagnostic: Diagnostic {
+                severity: DiagnosticSeverity::WARNING,
+                message: "literal out of range".to_string(),
+                group_id: 1,
+                is_primary: true,
+                source_kind: DiagnosticSourceKind::Pushed,
+                ..Default::default()
+            },
+        },
+    ];
+    buffer.update(cx, |buffer, _| {
+        assert_eq!(
+            buffer
+                .snapshot()
+                .diagnostics_in_range::<_, Point>(0..buffer.len(), false)
+                .collect::<Vec<_>>(),
+            expected_diagnostics,
+        );
+    });
+
+    // The next pull refers to the previous result, which the server reports as unchanged.
+    buffer.update(cx, |buffer, cx| buffer.edit([(0..0, " ")], None, cx));
+    cx.executor().advance_clock(Duration::from_secs(1));
+    cx.executor().run_until_parked();
+    assert_eq!(
+        requests.lock().drain(..).collect::<Vec<_>>(),
+        [Some("1".to_string())]
+    );
+    buffer.update(cx, |buffer, _| {
+        assert_eq!(
+            buffer
+                .snapshot()
+                .diagnostics_in_range::<_, Point>(0..buffer.len(), false)
+                .collect::<Vec<_>>()
+                .len(),
+            expected_diagnostics.len(),
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_edits_from_lsp2_with_past_version(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -2886,14 +3138,15 @@ async fn test_definition(cx: &mut gpui::TestAppContext) {
 }
 
 #[gpui::test]
-async fn test_completions_with_text_edit(cx: &mut gpui::TestAppContext) {
+async fn test_call_hierarchy(cx: &mut gpui::TestAppContext) {
     init_test(cx);
 
     let fs = FakeFs::new(cx.executor());
     fs.insert_tree(
         path!("/dir"),
         json!({
-            "a.ts": "",
+            "a.rs": "fn a() { b() }",
+            "b.rs": "pub fn b() {}",
         }),
     )
     .await;
@@ -2901,15 +3154,12 @@ async fn test_completions_with_text_edit(cx: &mut gpui::TestAppContext) {
     let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
 
     let language_registry = project.read_with(cx, |project, _| project.languages().clone());
-    language_registry.add(typescript_lang());
-    let mut fake_language_servers = language_registry.register_fake_lsp(
-        "TypeScript",
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
         FakeLspAdapter {
             capabilities: lsp::ServerCapabilities {
-                completion_provider: Some(lsp::CompletionOptions {
-                    trigger_characters: Some(vec![".".to_string()]),
-                    ..Default::default()
-                }),
+                call_hierarchy_provider: Some(lsp::CallHierarchyServerCapability::Simple(true)),
                 ..Default::default()
             },
             ..Default::default()
@@ -2917,61 +3167,98 @@ async fn test_completions_with_text_edit(cx: &mut gpui::TestAppContext) {
     );
 
     let (buffer, _handle) = project
-        .update(cx, |p, cx| {
-            p.open_local_buffer_with_lsp(path!("/dir/a.ts"), cx)
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/b.rs"), cx)
         })
         .await
         .unwrap();
 
-    let fake_server = fake_language_servers.next().await.unwrap();
+    fn lsp_item(name: &str, path: &str, line: u32) -> lsp::CallHierarchyItem {
+        lsp::CallHierarchyItem {
+            name: name.to_string(),
+            kind: lsp::SymbolKind::FUNCTION,
+            tags: None,
+            detail: Some(format!("fn {name}()")),
+            uri: lsp::Url::from_file_path(path).unwrap(),
+            range: lsp::Range::new(lsp::Position::new(line, 0), lsp::Position::new(line, 14)),
+            selection_range: lsp::Range::new(
+                lsp::Position::new(line, 3),
+                lsp::Position::new(line, 4),
+            ),
+            data: Some(json!({ "id": name })),
+        }
+    }
 
-    // When text_edit exists, it takes precedence over insert_text and label
-    let text = "let a = obj.fqn";
-    buffer.update(cx, |buffer, cx| buffer.set_text(text, cx));
-    let completions = project.update(cx, |project, cx| {
-        project.completions(&buffer, text.len(), DEFAULT_COMPLETION_CONTEXT, cx)
-    });
+    let fake_server = fake_servers.next().await.unwrap();
+    fake_server.set_request_handler::<lsp::request::CallHierarchyPrepare, _, _>(
+        |params, _| async move {
+            let params = params.text_document_position_params;
+            assert_eq!(
+                params.text_document.uri.to_file_path().unwrap(),
+                Path::new(path!("/dir/b.rs")),
+            );
+            assert_eq!(params.position, lsp::Position::new(0, 7));
+            Ok(Some(vec![lsp_item("b", path!("/dir/b.rs"), 0)]))
+        },
+    );
+    fake_server.set_request_handler::<lsp::request::CallHierarchyIncomingCalls, _, _>(
+        |params, _| async move {
+            assert_eq!(params.item.data, Some(json!({ "id": "b" })));
+            Ok(Some(vec![lsp::CallHierarchyIncomingCall {
+                from: lsp_item("a", path!("/dir/a.rs"), 0),
+                from_ranges: vec![lsp::Range::new(
+                    lsp::Position::new(0, 9),
+                    lsp::Position::new(0, 10),
+                )],
+            }]))
+        },
+    );
 
-    fake_server
-        .set_request_handler::<lsp::request::Completion, _, _>(|_, _| async {
-            Ok(Some(lsp::CompletionResponse::Array(vec![
-                lsp::CompletionItem {
-                    label: "labelText".into(),
-                    insert_text: Some("insertText".into()),
-                    text_edit: Some(lsp::CompletionTextEdit::Edit(lsp::TextEdit {
-                        range: lsp::Range::new(
-                            lsp::Position::new(0, text.len() as u32 - 3),
-                            lsp::Position::new(0, text.len() as u32),
-                        ),
-                        new_text: "textEditText".into(),
-                    })),
-                    ..Default::default()
-                },
-            ])))
+    let items = project
+        .update(cx, |project, cx| {
+            project.prepare_call_hierarchy(&buffer, 7, cx)
         })
-        .next()
-        .await;
-
-    let completions = completions.await.unwrap().unwrap();
-    let snapshot = buffer.update(cx, |buffer, _| buffer.snapshot());
-
-    assert_eq!(completions.len(), 1);
-    assert_eq!(completions[0].new_text, "textEditText");
+        .await
+        .unwrap();
+    assert_eq!(items.len(), 1);
+    let item = items[0].clone();
+    assert_eq!(item.symbol.name, "b");
+    assert_eq!(item.detail.as_deref(), Some("fn b()"));
+    assert_eq!(item.symbol.path.path.as_ref(), Path::new("b.rs"));
+
+    let calls = project
+        .update(cx, |project, cx| project.incoming_calls(&buffer, item, cx))
+        .await
+        .unwrap();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].item.symbol.name, "a");
+    assert_eq!(calls[0].item.symbol.path.path.as_ref(), Path::new("a.rs"));
     assert_eq!(
-        completions[0].old_range.to_offset(&snapshot),
-        text.len() - 3..text.len()
+        calls[0].ranges,
+        vec![Unclipped(PointUtf16::new(0, 9))..Unclipped(PointUtf16::new(0, 10))]
     );
+
+    let caller_buffer = project
+        .update(cx, |project, cx| {
+            project.open_buffer_for_symbol(&calls[0].item.symbol, cx)
+        })
+        .await
+        .unwrap();
+    caller_buffer.read_with(cx, |buffer, _| {
+        assert_eq!(buffer.text(), "fn a() { b() }");
+    });
 }
 
 #[gpui::test]
-async fn test_completions_with_edit_ranges(cx: &mut gpui::TestAppContext) {
+async fn test_type_hierarchy(cx: &mut gpui::TestAppContext) {
     init_test(cx);
 
     let fs = FakeFs::new(cx.executor());
     fs.insert_tree(
         path!("/dir"),
         json!({
-            "a.ts": "",
+            "shape.rs": "pub trait Shape {}",
+            "circle.rs": "struct Circle;\nimpl Shape for Circle {}",
         }),
     )
     .await;
@@ -2979,42 +3266,358 @@ async fn test_completions_with_edit_ranges(cx: &mut gpui::TestAppContext) {
     let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
 
     let language_registry = project.read_with(cx, |project, _| project.languages().clone());
-    language_registry.add(typescript_lang());
-    let mut fake_language_servers = language_registry.register_fake_lsp(
-        "TypeScript",
-        FakeLspAdapter {
-            capabilities: lsp::ServerCapabilities {
-                completion_provider: Some(lsp::CompletionOptions {
-                    trigger_characters: Some(vec![".".to_string()]),
-                    ..Default::default()
-                }),
-                ..Default::default()
-            },
-            ..Default::default()
-        },
-    );
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp("Rust", FakeLspAdapter::default());
 
     let (buffer, _handle) = project
-        .update(cx, |p, cx| {
-            p.open_local_buffer_with_lsp(path!("/dir/a.ts"), cx)
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/circle.rs"), cx)
         })
         .await
         .unwrap();
 
-    let fake_server = fake_language_servers.next().await.unwrap();
-    let text = "let a = obj.fqn";
+    fn lsp_item(
+        name: &str,
+        kind: lsp::SymbolKind,
+        path: &str,
+        line: u32,
+    ) -> lsp::TypeHierarchyItem {
+        lsp::TypeHierarchyItem {
+            name: name.to_string(),
+            kind,
+            tags: None,
+            detail: None,
+            uri: lsp::Url::from_file_path(path).unwrap(),
+            range: lsp::Range::new(lsp::Position::new(line, 0), lsp::Position::new(line, 14)),
+            selection_range: lsp::Range::new(
+                lsp::Position::new(line, 0),
+                lsp::Position::new(line, 6),
+            ),
+            data: Some(json!({ "id": name })),
+        }
+    }
 
-    // Test 1: When text_edit is None but insert_text exists with default edit_range
-    {
-        buffer.update(cx, |buffer, cx| buffer.set_text(text, cx));
-        let completions = project.update(cx, |project, cx| {
-            project.completions(&buffer, text.len(), DEFAULT_COMPLETION_CONTEXT, cx)
-        });
+    let fake_server = fake_servers.next().await.unwrap();
+    fake_server.set_request_handler::<lsp::request::TypeHierarchyPrepare, _, _>(
+        |params, _| async move {
+            let params = params.text_document_position_params;
+            assert_eq!(
+                params.text_document.uri.to_file_path().unwrap(),
+                Path::new(path!("/dir/circle.rs")),
+            );
+            assert_eq!(params.position, lsp::Position::new(0, 7));
+            Ok(Some(vec![lsp_item(
+                "Circle",
+                lsp::SymbolKind::STRUCT,
+                path!("/dir/circle.rs"),
+                0,
+            )]))
+        },
+    );
+    fake_server.set_request_handler::<lsp::request::TypeHierarchySupertypes, _, _>(
+        |params, _| async move {
+            assert_eq!(params.item.data, Some(json!({ "id": "Circle" })));
+            Ok(Some(vec![lsp_item(
+                "Shape",
+                lsp::SymbolKind::INTERFACE,
+                path!("/dir/shape.rs"),
+                0,
+            )]))
+        },
+    );
 
-        fake_server
-            .set_request_handler::<lsp::request::Completion, _, _>(|_, _| async {
-                Ok(Some(lsp::CompletionResponse::List(lsp::CompletionList {
-                    is_incomplete: false,
+    let items = project
+        .update(cx, |project, cx| {
+            project.prepare_type_hierarchy(&buffer, 7, cx)
+        })
+        .await
+        .unwrap();
+    assert_eq!(items.len(), 1);
+    let item = items[0].clone();
+    assert_eq!(item.symbol.name, "Circle");
+    assert_eq!(item.symbol.kind, lsp::SymbolKind::STRUCT);
+
+    let supertypes = project
+        .update(cx, |project, cx| project.supertypes(&buffer, item, cx))
+        .await
+        .unwrap();
+    assert_eq!(supertypes.len(), 1);
+    assert_eq!(supertypes[0].symbol.name, "Shape");
+    assert_eq!(
+        supertypes[0].symbol.path.path.as_ref(),
+        Path::new("shape.rs")
+    );
+    assert_eq!(
+        supertypes[0].selection_range,
+        Unclipped(PointUtf16::new(0, 0))..Unclipped(PointUtf16::new(0, 6))
+    );
+}
+
+#[gpui::test]
+async fn test_semantic_tokens(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.rs": "let mut a = 1;\nfn b() {}",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                semantic_tokens_provider: Some(
+                    lsp::SemanticTokensServerCapabilities::SemanticTokensOptions(
+                        lsp::SemanticTokensOptions {
+                            legend: lsp::SemanticTokensLegend {
+                                token_types: vec![
+                                    lsp::SemanticTokenType::VARIABLE,
+                                    lsp::SemanticTokenType::FUNCTION,
+                                ],
+                                token_modifiers: vec![
+                                    lsp::SemanticTokenModifier::DECLARATION,
+                                    lsp::SemanticTokenModifier::new("mutable"),
+                                ],
+                            },
+                            full: Some(lsp::SemanticTokensFullOptions::Delta { delta: Some(true) }),
+                            ..Default::default()
+                        },
+                    ),
+                ),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let variable = lsp::SemanticToken {
+        delta_line: 0,
+        delta_start: 8,
+        length: 1,
+        token_type: 0,
+        token_modifiers_bitset: 0b11,
+    };
+    let function = lsp::SemanticToken {
+        delta_line: 1,
+        delta_start: 3,
+        length: 1,
+        token_type: 1,
+        token_modifiers_bitset: 0,
+    };
+
+    let fake_server = fake_servers.next().await.unwrap();
+    fake_server.set_request_handler::<lsp::request::SemanticTokensFullRequest, _, _>(
+        move |params, _| async move {
+            assert_eq!(
+                params.text_document.uri.to_file_path().unwrap(),
+                Path::new(path!("/dir/a.rs")),
+            );
+            Ok(Some(lsp::SemanticTokensResult::Tokens(
+                lsp::SemanticTokens {
+                    result_id: Some("1".into()),
+                    data: vec![variable],
+                },
+            )))
+        },
+    );
+    fake_server.set_request_handler::<lsp::request::SemanticTokensFullDeltaRequest, _, _>(
+        move |params, _| async move {
+            assert_eq!(params.previous_result_id, "1");
+            Ok(Some(lsp::SemanticTokensFullDeltaResult::TokensDelta(
+                lsp::SemanticTokensDelta {
+                    result_id: Some("2".into()),
+                    edits: vec![lsp::SemanticTokensEdit {
+                        start: 5,
+                        delete_count: 0,
+                        data: Some(vec![function]),
+                    }],
+                },
+            )))
+        },
+    );
+
+    let tokens = project
+        .update(cx, |project, cx| project.semantic_tokens(&buffer, cx))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        tokens.tokens().collect::<Vec<_>>(),
+        [SemanticToken {
+            range: PointUtf16::new(0, 8)..PointUtf16::new(0, 9),
+            token_type: "variable",
+            modifiers: vec!["declaration", "mutable"],
+        }]
+    );
+
+    // The second request only fetches the changes since the first one.
+    let tokens = project
+        .update(cx, |project, cx| project.semantic_tokens(&buffer, cx))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        tokens.tokens().collect::<Vec<_>>(),
+        [
+            SemanticToken {
+                range: PointUtf16::new(0, 8)..PointUtf16::new(0, 9),
+                token_type: "variable",
+                modifiers: vec!["declaration", "mutable"],
+            },
+            SemanticToken {
+                range: PointUtf16::new(1, 3)..PointUtf16::new(1, 4),
+                token_type: "function",
+                modifiers: Vec::new(),
+            },
+        ]
+    );
+}
+
+#[gpui::test]
+async fn test_completions_with_text_edit(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.ts": "",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(typescript_lang());
+    let mut fake_language_servers = language_registry.register_fake_lsp(
+        "TypeScript",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                completion_provider: Some(lsp::CompletionOptions {
+                    trigger_characters: Some(vec![".".to_string()]),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |p, cx| {
+            p.open_local_buffer_with_lsp(path!("/dir/a.ts"), cx)
+        })
+        .await
+        .unwrap();
+
+    let fake_server = fake_language_servers.next().await.unwrap();
+
+    // When text_edit exists, it takes precedence over insert_text and label
+    let text = "let a = obj.fqn";
+    buffer.update(cx, |buffer, cx| buffer.set_text(text, cx));
+    let completions = project.update(cx, |project, cx| {
+        project.completions(&buffer, text.len(), DEFAULT_COMPLETION_CONTEXT, cx)
+    });
+
+    fake_server
+        .set_request_handler::<lsp::request::Completion, _, _>(|_, _| async {
+            Ok(Some(lsp::CompletionResponse::Array(vec![
+                lsp::CompletionItem {
+                    label: "labelText".into(),
+                    insert_text: Some("insertText".into()),
+                    text_edit: Some(lsp::CompletionTextEdit::Edit(lsp::TextEdit {
+                        range: lsp::Range::new(
+                            lsp::Position::new(0, text.len() as u32 - 3),
+                            lsp::Position::new(0, text.len() as u32),
+                        ),
+                        new_text: "textEditText".into(),
+                    })),
+                    ..Default::default()
+                },
+            ])))
+        })
+        .next()
+        .await;
+
+    let completions = completions.await.unwrap().unwrap();
+    let snapshot = buffer.update(cx, |buffer, _| buffer.snapshot());
+
+    assert_eq!(completions.len(), 1);
+    assert_eq!(completions[0].new_text, "textEditText");
+    assert_eq!(
+        completions[0].old_range.to_offset(&snapshot),
+        text.len() - 3..text.len()
+    );
+}
+
+#[gpui::test]
+async fn test_completions_with_edit_ranges(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.ts": "",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(typescript_lang());
+    let mut fake_language_servers = language_registry.register_fake_lsp(
+        "TypeScript",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                completion_provider: Some(lsp::CompletionOptions {
+                    trigger_characters: Some(vec![".".to_string()]),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |p, cx| {
+            p.open_local_buffer_with_lsp(path!("/dir/a.ts"), cx)
+        })
+        .await
+        .unwrap();
+
+    let fake_server = fake_language_servers.next().await.unwrap();
+    let text = "let a = obj.fqn";
+
+    // Test 1: When text_edit is None but insert_text exists with default edit_range
+    {
+        buffer.update(cx, |buffer, cx| buffer.set_text(text, cx));
+        let completions = project.update(cx, |project, cx| {
+            project.completions(&buffer, text.len(), DEFAULT_COMPLETION_CONTEXT, cx)
+        });
+
+        fake_server
+            .set_request_handler::<lsp::request::Completion, _, _>(|_, _| async {
+                Ok(Some(lsp::CompletionResponse::List(lsp::CompletionList {
+                    is_incomplete: false,
                     item_defaults: Some(lsp::CompletionListItemDefaults {
                         edit_range: Some(lsp::CompletionListItemDefaultsEditRange::Range(
                             lsp::Range::new(
@@ -4356,6 +4959,7 @@ async fn test_grouped_diagnostics(cx: &mut gpui::TestAppContext) {
                     message: "error 1".to_string(),
                     group_id: 1,
                     is_primary: true,
+                    source_kind: DiagnosticSourceKind::Pushed,
                     ..Default::default()
                 }
             },
@@ -4366,6 +4970,7 @@ async fn test_grouped_diagnostics(cx: &mut gpui::TestAppContext) {
                     message: "error 1 hint 1".to_string(),
                     group_id: 1,
                     is_primary: false,
+                    source_kind: DiagnosticSourceKind::Pushed,
                     ..Default::default()
                 }
             },
@@ -4376,6 +4981,7 @@ async fn test_grouped_diagnostics(cx: &mut gpui::TestAppContext) {
                     message: "error 2 hint 1".to_string(),
                     group_id: 0,
                     is_primary: false,
+                    source_kind: DiagnosticSourceKind::Pushed,
                     ..Default::default()
                 }
             },
@@ -4386,6 +4992,7 @@ async fn test_grouped_diagnostics(cx: &mut gpui::TestAppContext) {
                     message: "error 2 hint 2".to_string(),
                     group_id: 0,
                     is_primary: false,
+                    source_kind: DiagnosticSourceKind::Pushed,
                     ..Default::default()
                 }
             },
@@ -4396,6 +5003,7 @@ async fn test_grouped_diagnostics(cx: &mut gpui::TestAppContext) {
                     message: "error 2".to_string(),
                     group_id: 0,
                     is_primary: true,
+                    source_kind: DiagnosticSourceKind::Pushed,
                     ..Default::default()
                 }
             }
@@ -4412,6 +5020,7 @@ async fn test_grouped_diagnostics(cx: &mut gpui::TestAppContext) {
                     message: "error 2 hint 1".to_string(),
                     group_id: 0,
                     is_primary: false,
+                    source_kind: DiagnosticSourceKind::Pushed,
                     ..Default::default()
                 }
             },
@@ -4422,6 +5031,7 @@ async fn test_grouped_diagnostics(cx: &mut gpui::TestAppContext) {
                     message: "error 2 hint 2".to_string(),
                     group_id: 0,
                     is_primary: false,
+                    source_kind: DiagnosticSourceKind::Pushed,
                     ..Default::default()
                 }
             },
@@ -4432,6 +5042,7 @@ async fn test_grouped_diagnostics(cx: &mut gpui::TestAppContext) {
                     message: "error 2".to_string(),
                     group_id: 0,
                     is_primary: true,
+                    source_kind: DiagnosticSourceKind::Pushed,
                     ..Default::default()
                 }
             }
@@ -4448,6 +5059,7 @@ async fn test_grouped_diagnostics(cx: &mut gpui::TestAppContext) {
                     message: "error 1".to_string(),
                     group_id: 1,
                     is_primary: true,
+                    source_kind: DiagnosticSourceKind::Pushed,
                     ..Default::default()
                 }
             },
@@ -4458,6 +5070,7 @@ async fn test_grouped_diagnostics(cx: &mut gpui::TestAppContext) {
                     message: "error 1 hint 1".to_string(),
                     group_id: 1,
                     is_primary: false,
+                    source_kind: DiagnosticSourceKind::Pushed,
                     ..Default::default()
                 }
             },
@@ -5363,6 +5976,65 @@ async fn test_search_in_gitignored_dirs(cx: &mut gpui::TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_structural_search(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "one.rs": "fn main() {\n    let a = foo(1).unwrap();\n    let b = bar . unwrap ( /* ok */ );\n    let c = a.unwrap_or(b);\n}\n",
+            "two.rs": "fn two() {}",
+            "three.txt": "foo(1).unwrap()",
+        }),
+    )
+    .await;
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+    let rust = rust_lang();
+    project.read_with(cx, |project, _| project.languages().add(rust.clone()));
+
+    assert!(
+        SearchQuery::structural("$X", false, Default::default(), Default::default(), None).is_err(),
+        "A pattern of metavariables only should be rejected"
+    );
+
+    let query = SearchQuery::structural(
+        "$X.unwrap()",
+        false,
+        Default::default(),
+        Default::default(),
+        None,
+    )
+    .unwrap()
+    .with_replacement("$X.expect(\"checked\")".to_string());
+    assert_eq!(
+        search(&project, query.clone(), cx).await.unwrap(),
+        HashMap::from_iter([
+            (separator!("dir/one.rs").to_string(), vec![24..39, 53..78]),
+            (separator!("dir/three.txt").to_string(), vec![]),
+        ]),
+        "Matches should ignore formatting and comments, and skip files without a grammar"
+    );
+    assert_eq!(
+        query
+            .replacement_for("foo(1).unwrap()", Some(&rust))
+            .as_deref(),
+        Some("foo(1).expect(\"checked\")")
+    );
+    assert_eq!(
+        query
+            .replacement_for("bar . unwrap ( /* ok */ )", Some(&rust))
+            .as_deref(),
+        Some("bar.expect(\"checked\")")
+    );
+    assert_eq!(
+        query.replacement_for("foo(1).unwrap()", None),
+        None,
+        "Structural replacements need the language of the match"
+    );
+}
+
 #[gpui::test]
 async fn test_create_entry(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -7952,23 +8624,651 @@ async fn test_file_status(cx: &mut gpui::TestAppContext) {
 }
 
 #[gpui::test]
-async fn test_repos_in_invisible_worktrees(
-    executor: BackgroundExecutor,
-    cx: &mut gpui::TestAppContext,
-) {
+async fn test_git_stash(cx: &mut gpui::TestAppContext) {
     init_test(cx);
-    let fs = FakeFs::new(executor);
+    let fs = FakeFs::new(cx.background_executor.clone());
     fs.insert_tree(
         path!("/root"),
         json!({
-            "dir1": {
-                ".git": {},
-                "dep1": {
-                    ".git": {},
-                    "src": {
-                        "a.txt": "",
-                    },
-                },
+            ".git": {},
+            "a.txt": "a modified\n",
+            "b.txt": "b\n",
+            "c.txt": "untracked\n",
+        }),
+    )
+    .await;
+    fs.set_head_and_index_for_repo(
+        path!("/root/.git").as_ref(),
+        &[
+            ("a.txt".into(), "a\n".into()),
+            ("b.txt".into(), "b\n".into()),
+        ],
+    );
+
+    let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+    cx.executor().run_until_parked();
+    let repository = project.read_with(cx, |project, cx| {
+        project.repositories(cx).values().next().unwrap().clone()
+    });
+
+    // Untracked files are kept in the working tree unless requested.
+    repository
+        .update(cx, |repository, _| {
+            repository.stash_push(Some("first".into()), false)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(fs.load(path!("/root/a.txt").as_ref()).await.unwrap(), "a\n");
+    assert!(fs.is_file(path!("/root/c.txt").as_ref()).await);
+
+    let stash_diff = repository
+        .update(cx, |repository, _| repository.stash_show(0))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        stash_diff
+            .files
+            .iter()
+            .map(|file| (
+                file.path.to_string(),
+                file.old_text.as_deref(),
+                file.new_text.as_deref()
+            ))
+            .collect::<Vec<_>>(),
+        [("a.txt".to_string(), Some("a\n"), Some("a modified\n"))]
+    );
+
+    repository
+        .update(cx, |repository, _| repository.stash_push(None, true))
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!fs.is_file(path!("/root/c.txt").as_ref()).await);
+
+    let stash_messages = |cx: &mut gpui::TestAppContext| {
+        let stash_list = repository.update(cx, |repository, _| repository.stash_list());
+        async move {
+            stash_list
+                .await
+                .unwrap()
+                .unwrap()
+                .into_iter()
+                .map(|entry| (entry.index, entry.message.to_string()))
+                .collect::<Vec<_>>()
+        }
+    };
+    assert_eq!(
+        stash_messages(cx).await,
+        [
+            (0, "WIP on (no branch)".to_string()),
+            (1, "On (no branch): first".to_string())
+        ]
+    );
+
+    // Popping an older entry restores its changes and shifts the entries after it.
+    repository
+        .update(cx, |repository, _| repository.stash_pop(1))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        fs.load(path!("/root/a.txt").as_ref()).await.unwrap(),
+        "a modified\n"
+    );
+    assert_eq!(
+        stash_messages(cx).await,
+        [(0, "WIP on (no branch)".to_string())]
+    );
+
+    repository
+        .update(cx, |repository, _| repository.stash_apply(0))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        fs.load(path!("/root/c.txt").as_ref()).await.unwrap(),
+        "untracked\n"
+    );
+    assert_eq!(stash_messages(cx).await.len(), 1);
+
+    repository
+        .update(cx, |repository, _| repository.stash_drop(0))
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(stash_messages(cx).await.is_empty());
+}
+
+#[gpui::test]
+async fn test_git_log(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+    let fs = FakeFs::new(cx.background_executor.clone());
+    fs.insert_tree(
+        path!("/root"),
+        json!({
+            ".git": {},
+            "a.txt": "a\n",
+            "b.txt": "b\n",
+        }),
+    )
+    .await;
+    fs.set_log_for_repo(
+        path!("/root/.git").as_ref(),
+        vec![
+            (
+                log_entry("c3", &["c2"], "Fix b", "Bob"),
+                vec!["b.txt".into()],
+            ),
+            (
+                log_entry("c2", &["c1"], "Update a", "Alice"),
+                vec!["a.txt".into()],
+            ),
+            (
+                log_entry("c1", &[], "Initial commit", "Alice"),
+                vec!["a.txt".into(), "b.txt".into()],
+            ),
+        ],
+    );
+
+    let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+    cx.executor().run_until_parked();
+    let repository = project.read_with(cx, |project, cx| {
+        project.repositories(cx).values().next().unwrap().clone()
+    });
+
+    let log = |options: LogOptions, cx: &mut gpui::TestAppContext| {
+        let log = repository.update(cx, |repository, _| repository.log(options));
+        async move {
+            log.await
+                .unwrap()
+                .unwrap()
+                .into_iter()
+                .map(|entry| entry.sha.to_string())
+                .collect::<Vec<_>>()
+        }
+    };
+    let options = LogOptions {
+        limit: 100,
+        ..Default::default()
+    };
+
+    assert_eq!(log(options.clone(), cx).await, ["c3", "c2", "c1"]);
+    assert_eq!(
+        log(
+            LogOptions {
+                skip: 1,
+                limit: 1,
+                ..options.clone()
+            },
+            cx
+        )
+        .await,
+        ["c2"]
+    );
+    assert_eq!(
+        log(
+            LogOptions {
+                path: Some("a.txt".into()),
+                ..options.clone()
+            },
+            cx
+        )
+        .await,
+        ["c2", "c1"]
+    );
+    assert_eq!(
+        log(
+            LogOptions {
+                author: Some("Bob".into()),
+                ..options.clone()
+            },
+            cx
+        )
+        .await,
+        ["c3"]
+    );
+    assert_eq!(
+        log(
+            LogOptions {
+                message: Some("update".into()),
+                ..options.clone()
+            },
+            cx
+        )
+        .await,
+        ["c2"]
+    );
+    assert_eq!(
+        log(
+            LogOptions {
+                revision: Some("c1..c3".into()),
+                ..options
+            },
+            cx
+        )
+        .await,
+        ["c3", "c2"]
+    );
+}
+
+#[gpui::test]
+async fn test_git_rebase(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+    let fs = FakeFs::new(cx.background_executor.clone());
+    fs.insert_tree(
+        path!("/root"),
+        json!({
+            ".git": {},
+            "a.txt": "a\n",
+        }),
+    )
+    .await;
+    let log_entries = vec![
+        (
+            log_entry("c4", &["c3"], "Fourth", "Alice"),
+            vec!["a.txt".into()],
+        ),
+        (
+            log_entry("c3", &["c2"], "Third", "Alice"),
+            vec!["a.txt".into()],
+        ),
+        (
+            log_entry("c2", &["c1"], "Second", "Alice"),
+            vec!["a.txt".into()],
+        ),
+        (log_entry("c1", &[], "First", "Alice"), vec!["a.txt".into()]),
+    ];
+    fs.set_log_for_repo(path!("/root/.git").as_ref(), log_entries.clone());
+
+    let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+    cx.executor().run_until_parked();
+    let repository = project.read_with(cx, |project, cx| {
+        project.repositories(cx).values().next().unwrap().clone()
+    });
+
+    let log = |cx: &mut gpui::TestAppContext| {
+        let log = repository.update(cx, |repository, _| {
+            repository.log(LogOptions {
+                limit: 100,
+                ..Default::default()
+            })
+        });
+        async move {
+            log.await
+                .unwrap()
+                .unwrap()
+                .into_iter()
+                .map(|entry| (entry.sha.to_string(), entry.subject.to_string()))
+                .collect::<Vec<_>>()
+        }
+    };
+    let step = |action: RebaseAction, sha: &str| RebaseStep {
+        action,
+        sha: sha.to_string().into(),
+        message: None,
+    };
+
+    // Reorder, reword, squash and drop commits.
+    let status = repository
+        .update(cx, |repository, _| {
+            repository.rebase_interactive(
+                "c1".into(),
+                vec![
+                    step(RebaseAction::Pick, "c3"),
+                    step(RebaseAction::Fixup, "c4"),
+                    RebaseStep {
+                        message: Some("Second, reworded\n\nWith a body".into()),
+                        ..step(RebaseAction::Reword, "c2")
+                    },
+                ],
+            )
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(status, RebaseStatus::Idle);
+    assert_eq!(
+        log(cx).await,
+        [
+            ("c2".to_string(), "Second, reworded".to_string()),
+            ("c3".to_string(), "Third".to_string()),
+            ("c1".to_string(), "First".to_string()),
+        ]
+    );
+
+    // Squashing the first commit is rejected.
+    fs.set_log_for_repo(path!("/root/.git").as_ref(), log_entries.clone());
+    let result = repository
+        .update(cx, |repository, _| {
+            repository.rebase_interactive(
+                "c1".into(),
+                vec![
+                    step(RebaseAction::Squash, "c2"),
+                    step(RebaseAction::Pick, "c3"),
+                ],
+            )
+        })
+        .await
+        .unwrap();
+    assert!(result.is_err());
+
+    // A conflicting commit stops the rebase, until it is skipped.
+    fs.with_git_state(path!("/root/.git").as_ref(), false, |state| {
+        state.rebase_conflicts.insert("c3".into());
+    })
+    .unwrap();
+    let rebase = |cx: &mut gpui::TestAppContext| {
+        repository.update(cx, |repository, _| {
+            repository.rebase_interactive(
+                "c1".into(),
+                vec![
+                    step(RebaseAction::Pick, "c2"),
+                    step(RebaseAction::Pick, "c3"),
+                    step(RebaseAction::Drop, "c4"),
+                ],
+            )
+        })
+    };
+    let stopped = RebaseStatus::Stopped {
+        commit: Some("c3".into()),
+    };
+    assert_eq!(rebase(cx).await.unwrap().unwrap(), stopped);
+    let status = repository
+        .update(cx, |repository, _| repository.rebase_status())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(status, stopped);
+    let status = repository
+        .update(cx, |repository, _| repository.rebase_skip())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(status, RebaseStatus::Idle);
+    assert_eq!(
+        log(cx).await,
+        [
+            ("c2".to_string(), "Second".to_string()),
+            ("c1".to_string(), "First".to_string()),
+        ]
+    );
+
+    // Continuing applies the conflicting commit.
+    fs.set_log_for_repo(path!("/root/.git").as_ref(), log_entries.clone());
+    assert_eq!(rebase(cx).await.unwrap().unwrap(), stopped);
+    let status = repository
+        .update(cx, |repository, _| repository.rebase_continue())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(status, RebaseStatus::Idle);
+    assert_eq!(
+        log(cx).await,
+        [
+            ("c3".to_string(), "Third".to_string()),
+            ("c2".to_string(), "Second".to_string()),
+            ("c1".to_string(), "First".to_string()),
+        ]
+    );
+
+    // Aborting restores the original history.
+    fs.set_log_for_repo(path!("/root/.git").as_ref(), log_entries);
+    assert_eq!(rebase(cx).await.unwrap().unwrap(), stopped);
+    repository
+        .update(cx, |repository, _| repository.rebase_abort())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(log(cx).await.len(), 4);
+    let status = repository
+        .update(cx, |repository, _| repository.rebase_status())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(status, RebaseStatus::Idle);
+}
+
+#[gpui::test]
+async fn test_git_submodules_and_worktrees(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+    let fs = FakeFs::new(cx.background_executor.clone());
+    fs.insert_tree(
+        path!("/code/root"),
+        json!({
+            ".git": {},
+            "a.txt": "a\n",
+        }),
+    )
+    .await;
+    let dot_git = Path::new(path!("/code/root/.git"));
+    fs.set_log_for_repo(
+        dot_git,
+        vec![(
+            LogEntry {
+                sha: "c1".into(),
+                parent_shas: Vec::new(),
+                subject: "First".into(),
+                author_name: "Alice".into(),
+                author_email: "alice@example.com".into(),
+                commit_timestamp: 0,
+                refs: Vec::new(),
+            },
+            vec!["a.txt".into()],
+        )],
+    );
+    fs.insert_branches(dot_git, &["main", "feature"]);
+    let submodule = |path: &str, status: SubmoduleStatus| Submodule {
+        path: RepoPath::from_str(path),
+        sha: "s1".into(),
+        status,
+        has_changes: false,
+    };
+    fs.with_git_state(dot_git, true, |state| {
+        state.submodules = vec![
+            submodule("vendor/a", SubmoduleStatus::Uninitialized),
+            submodule("vendor/b", SubmoduleStatus::Modified),
+        ];
+    })
+    .unwrap();
+
+    let project = Project::test(fs.clone(), [path!("/code/root").as_ref()], cx).await;
+    cx.executor().run_until_parked();
+    let repository = project.read_with(cx, |project, cx| {
+        project.repositories(cx).values().next().unwrap().clone()
+    });
+    let submodule_statuses = |cx: &mut gpui::TestAppContext| {
+        let submodules = repository.update(cx, |repository, _| repository.submodules());
+        async move {
+            submodules
+                .await
+                .unwrap()
+                .unwrap()
+                .into_iter()
+                .map(|submodule| submodule.status)
+                .collect::<Vec<_>>()
+        }
+    };
+
+    // Updating leaves uninitialized submodules alone, unless they're initialized too.
+    repository
+        .update(cx, |repository, _| {
+            repository.submodule_update(Vec::new(), false)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        submodule_statuses(cx).await,
+        [SubmoduleStatus::Uninitialized, SubmoduleStatus::UpToDate]
+    );
+    repository
+        .update(cx, |repository, _| {
+            repository.submodule_update(vec![RepoPath::from_str("vendor/a")], true)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        submodule_statuses(cx).await,
+        [SubmoduleStatus::UpToDate, SubmoduleStatus::UpToDate]
+    );
+
+    // Branches checked out elsewhere can't be added again.
+    let review_path = PathBuf::from(path!("/code/root-feature"));
+    repository
+        .update(cx, |repository, _| {
+            repository.worktree_add(review_path.clone(), "feature".to_string())
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(
+        repository
+            .update(cx, |repository, _| {
+                repository.worktree_add(PathBuf::from(path!("/code/other")), "main".to_string())
+            })
+            .await
+            .unwrap()
+            .is_err()
+    );
+    assert!(fs.is_dir(&review_path).await);
+    let worktrees = repository
+        .update(cx, |repository, _| repository.worktrees())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        worktrees,
+        [
+            GitWorktree {
+                path: PathBuf::from(path!("/code/root")),
+                sha: Some("c1".into()),
+                branch: Some("main".into()),
+                is_main: true,
+            },
+            GitWorktree {
+                path: review_path.clone(),
+                sha: Some("c1".into()),
+                branch: Some("feature".into()),
+                is_main: false,
+            },
+        ]
+    );
+
+    repository
+        .update(cx, |repository, _| {
+            repository.worktree_remove(review_path.clone(), false)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!fs.is_dir(&review_path).await);
+    let worktrees = repository
+        .update(cx, |repository, _| repository.worktrees())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(worktrees.len(), 1);
+}
+
+#[gpui::test]
+async fn test_git_blame_and_text_at_revision(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+    let fs = FakeFs::new(cx.background_executor.clone());
+    fs.insert_tree(
+        path!("/code/root"),
+        json!({
+            ".git": {},
+            "a.txt": "one\ntwo\nthree\n",
+        }),
+    )
+    .await;
+    let dot_git = Path::new(path!("/code/root/.git"));
+    fs.set_revision_contents_for_repo(
+        dot_git,
+        "c1",
+        &[(RepoPath::from_str("a.txt"), "one\n".to_string())],
+    );
+    fs.set_revision_blame_for_repo(
+        dot_git,
+        "c1",
+        vec![(
+            RepoPath::from_str("a.txt"),
+            Blame {
+                entries: vec![BlameEntry {
+                    sha: Oid::from_str("1b1a1b1a1b1a1b1a1b1a1b1a1b1a1b1a1b1a1b1a").unwrap(),
+                    range: 0..1,
+                    original_line_number: 1,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        )],
+    );
+
+    let project = Project::test(fs.clone(), [path!("/code/root").as_ref()], cx).await;
+    cx.executor().run_until_parked();
+    let repository = project.read_with(cx, |project, cx| {
+        project.repositories(cx).values().next().unwrap().clone()
+    });
+
+    let text = repository
+        .update(cx, |repository, _| {
+            repository.load_text_at_revision(RepoPath::from_str("a.txt"), "c1".into())
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(text.as_deref(), Some("one\n"));
+    let text = repository
+        .update(cx, |repository, _| {
+            repository.load_text_at_revision(RepoPath::from_str("b.txt"), "c1".into())
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(text, None);
+
+    let blame = repository
+        .update(cx, |repository, _| {
+            repository.blame_at_revision(RepoPath::from_str("a.txt"), "c1".into())
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(blame.entries.len(), 1);
+    assert_eq!(blame.entries[0].range, 0..1);
+    assert!(
+        repository
+            .update(cx, |repository, _| {
+                repository.blame_at_revision(RepoPath::from_str("a.txt"), "c2".into())
+            })
+            .await
+            .unwrap()
+            .is_err()
+    );
+}
+
+#[gpui::test]
+async fn test_repos_in_invisible_worktrees(
+    executor: BackgroundExecutor,
+    cx: &mut gpui::TestAppContext,
+) {
+    init_test(cx);
+    let fs = FakeFs::new(executor);
+    fs.insert_tree(
+        path!("/root"),
+        json!({
+            "dir1": {
+                ".git": {},
+                "dep1": {
+                    ".git": {},
+                    "src": {
+                        "a.txt": "",
+                    },
+                },
                 "b.txt": "",
             },
         }),
@@ -8332,6 +9632,21 @@ fn get_all_tasks(
 }
 
 #[track_caller]
+fn log_entry(sha: &str, parent_shas: &[&str], subject: &str, author: &str) -> LogEntry {
+    LogEntry {
+        sha: sha.to_string().into(),
+        parent_shas: parent_shas
+            .iter()
+            .map(|sha| sha.to_string().into())
+            .collect(),
+        subject: subject.to_string().into(),
+        author_name: author.to_string().into(),
+        author_email: format!("{}@example.com", author.to_lowercase()).into(),
+        commit_timestamp: 0,
+        refs: Vec::new(),
+    }
+}
+
 fn assert_entry_git_state(
     tree: &Worktree,
     repository: &Repository,
@@ -0,0 +1,465 @@
+//! Structural search: finds code by the shape of its syntax tree rather than by its text.
+//!
+//! A pattern is a snippet of code with metavariables in place of the parts that can vary:
+//! `$NAME` matches any single syntax node, `$$$NAME` matches any number of sibling nodes,
+//! and `$_` and `$$$` match without capturing. The pattern is parsed with the grammar of
+//! each buffer searched, and matches the nodes of the same shape, whatever their formatting and comments.
+//! Replacement templates refer to the captured nodes with the same syntax.
+
+use aho_corasick::{AhoCorasick, AhoCorasickBuilder};
+use anyhow::{Result, anyhow};
+use collections::HashMap;
+use language::{BufferSnapshot, Language, LanguageName, Node, Tree, with_parser};
+use parking_lot::Mutex;
+use std::{
+    fmt,
+    io::{BufReader, Read},
+    ops::Range,
+    sync::Arc,
+};
+
+const SINGLE_PLACEHOLDER_PREFIX: &str = "zed_metavar_";
+const MULTIPLE_PLACEHOLDER_PREFIX: &str = "zed_metavars_";
+
+/// A structural search pattern, parsed lazily for each language searched.
+pub struct StructuralPattern {
+    /// The pattern with its metavariables replaced by identifiers, so that grammars can parse it.
+    source: String,
+    /// The longest word of the pattern outside metavariables, which any match has to contain.
+    literal: Option<AhoCorasick>,
+    /// Patterns parsed so far, `None` for the languages the pattern is not valid in.
+    parsed: Mutex<HashMap<LanguageName, Option<Arc<ParsedPattern>>>>,
+}
+
+impl fmt::Debug for StructuralPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StructuralPattern")
+            .field("source", &self.source)
+            .finish_non_exhaustive()
+    }
+}
+
+struct ParsedPattern {
+    language: Arc<Language>,
+    tree: Tree,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token<'a> {
+    Text(&'a str),
+    Single(&'a str),
+    Multiple(&'a str),
+}
+
+/// Splits a pattern or a replacement template into text and metavariables.
+fn tokenize(pattern: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut text_start = 0;
+    let mut ix = 0;
+    while let Some(dollar_ix) = pattern[ix..].find('$') {
+        let start = ix + dollar_ix;
+        let (multiple, name_start) = if pattern[start..].starts_with("$$$") {
+            (true, start + 3)
+        } else {
+            (false, start + 1)
+        };
+        let name_len = pattern[name_start..]
+            .find(|c: char| !is_metavariable_char(c))
+            .unwrap_or(pattern.len() - name_start);
+        let name = &pattern[name_start..name_start + name_len];
+        if name.is_empty() && !multiple {
+            ix = start + 1;
+            continue;
+        }
+        if text_start < start {
+            tokens.push(Token::Text(&pattern[text_start..start]));
+        }
+        tokens.push(if multiple {
+            Token::Multiple(name)
+        } else {
+            Token::Single(name)
+        });
+        ix = name_start + name_len;
+        text_start = ix;
+    }
+    if text_start < pattern.len() {
+        tokens.push(Token::Text(&pattern[text_start..]));
+    }
+    tokens
+}
+
+enum Metavariable<'a> {
+    Single(Option<&'a str>),
+    Multiple(Option<&'a str>),
+}
+
+fn is_metavariable_char(c: char) -> bool {
+    c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_'
+}
+
+fn is_metavariable_name(name: &str) -> bool {
+    name.chars().all(is_metavariable_char)
+}
+
+/// Names starting with `_` match without capturing.
+fn capture_name(name: &str) -> Option<&str> {
+    (!name.is_empty() && !name.starts_with('_')).then_some(name)
+}
+
+/// Parses the text of a pattern node, if it is a metavariable placeholder.
+fn metavariable(text: &str) -> Option<Metavariable<'_>> {
+    if let Some(name) = text.strip_prefix(MULTIPLE_PLACEHOLDER_PREFIX) {
+        is_metavariable_name(name).then(|| Metavariable::Multiple(capture_name(name)))
+    } else {
+        let name = text.strip_prefix(SINGLE_PLACEHOLDER_PREFIX)?;
+        is_metavariable_name(name).then(|| Metavariable::Single(capture_name(name)))
+    }
+}
+
+fn trimmed_range(root: &Node, source: &str) -> Range<usize> {
+    let text = &source[root.byte_range()];
+    let start = root.start_byte() + (text.len() - text.trim_start().len());
+    let end = root.start_byte() + text.trim_end().len();
+    start..end.max(start)
+}
+
+/// Returns the nodes spanning exactly the range given, from the outermost one.
+fn spanning_nodes(root: Node, range: Range<usize>) -> Vec<Node> {
+    let mut nodes = Vec::new();
+    let mut node = root;
+    loop {
+        if node.byte_range() == range {
+            nodes.push(node);
+        }
+        let mut cursor = node.walk();
+        let Some(child) = node
+            .children(&mut cursor)
+            .find(|child| child.start_byte() <= range.start && range.end <= child.end_byte())
+        else {
+            break;
+        };
+        node = child;
+    }
+    nodes
+}
+
+impl StructuralPattern {
+    pub fn new(pattern: &str) -> Result<Self> {
+        let tokens = tokenize(pattern.trim());
+        let mut source = String::new();
+        let mut literal = "";
+        for token in &tokens {
+            match token {
+                Token::Text(text) => {
+                    source.push_str(text);
+                    for word in text.split(|c: char| !(c.is_alphanumeric() || c == '_')) {
+                        if word.len() > literal.len() {
+                            literal = word;
+                        }
+                    }
+                }
+                Token::Single(name) => {
+                    source.push_str(SINGLE_PLACEHOLDER_PREFIX);
+                    source.push_str(name);
+                }
+                Token::Multiple(name) => {
+                    source.push_str(MULTIPLE_PLACEHOLDER_PREFIX);
+                    source.push_str(name);
+                }
+            }
+        }
+        if !tokens
+            .iter()
+            .any(|token| matches!(token, Token::Text(text) if !text.trim().is_empty()))
+        {
+            return Err(anyhow!(
+                "structural search pattern should contain code besides metavariables"
+            ));
+        }
+        let literal = if literal.is_empty() {
+            None
+        } else {
+            Some(AhoCorasickBuilder::new().build([literal])?)
+        };
+        Ok(Self {
+            source,
+            literal,
+            parsed: Mutex::default(),
+        })
+    }
+
+    /// Whether a file may contain matches, judging by its text.
+    pub(crate) fn detect(&self, reader: BufReader<Box<dyn Read + Send + Sync>>) -> Result<bool> {
+        let Some(literal) = &self.literal else {
+            return Ok(true);
+        };
+        match literal.stream_find_iter(reader).next() {
+            Some(Ok(_)) => Ok(true),
+            Some(Err(err)) => Err(err.into()),
+            None => Ok(false),
+        }
+    }
+
+    fn parsed(&self, language: &Arc<Language>) -> Option<Arc<ParsedPattern>> {
+        self.parsed
+            .lock()
+            .entry(language.name())
+            .or_insert_with(|| {
+                let tree = parse(&self.source, language)?;
+                let range = trimmed_range(&tree.root_node(), &self.source);
+                let node = spanning_nodes(tree.root_node(), range).pop()?;
+                if node.has_error() {
+                    return None;
+                }
+                Some(Arc::new(ParsedPattern {
+                    language: language.clone(),
+                    tree,
+                }))
+            })
+            .clone()
+    }
+
+    fn pattern_node<'a>(&self, pattern: &'a ParsedPattern) -> Node<'a> {
+        let range = trimmed_range(&pattern.tree.root_node(), &self.source);
+        spanning_nodes(pattern.tree.root_node(), range)
+            .pop()
+            .unwrap_or_else(|| pattern.tree.root_node())
+    }
+
+    /// Finds the matches in the buffer, using its syntax trees, or parsing it with the language given if it has none.
+    /// Returns ranges relative to the start of the subrange.
+    pub(crate) fn find(
+        &self,
+        buffer: &BufferSnapshot,
+        language: Option<&Arc<Language>>,
+        subrange: Option<Range<usize>>,
+    ) -> Vec<Range<usize>> {
+        let range = subrange.unwrap_or(0..buffer.len());
+        let text = buffer.text();
+        let mut matches = Vec::new();
+        let mut has_layers = false;
+        for layer in buffer.syntax_layers() {
+            has_layers = true;
+            if let Some(pattern) = self.parsed(layer.language) {
+                self.find_in_tree(&pattern, layer.node(), &text, &range, &mut matches);
+            }
+        }
+        if !has_layers {
+            if let Some(language) = language.or(buffer.language()) {
+                if let Some((pattern, tree)) = self.parsed(language).zip(parse(&text, language)) {
+                    self.find_in_tree(&pattern, tree.root_node(), &text, &range, &mut matches);
+                }
+            }
+        }
+        matches.sort_by_key(|range| (range.start, range.end));
+        matches.dedup();
+        matches
+            .into_iter()
+            .map(|matched| matched.start - range.start..matched.end - range.start)
+            .collect()
+    }
+
+    fn find_in_tree(
+        &self,
+        pattern: &ParsedPattern,
+        root: Node,
+        text: &str,
+        range: &Range<usize>,
+        matches: &mut Vec<Range<usize>>,
+    ) {
+        let pattern_node = self.pattern_node(pattern);
+        let mut cursor = root.walk();
+        loop {
+            let node = cursor.node();
+            let node_range = node.byte_range();
+            let matched = node.is_named()
+                && node.kind_id() == pattern_node.kind_id()
+                && range.start <= node_range.start
+                && node_range.end <= range.end
+                && Matcher::new(&self.source, text).match_node(pattern_node, node);
+            if matched {
+                matches.push(node_range);
+            }
+            // Matches do not overlap: the nodes inside a match are not matched on their own.
+            let overlaps_range = node_range.start < range.end && range.start < node_range.end;
+            if !matched && overlaps_range && cursor.goto_first_child() {
+                continue;
+            }
+            while !cursor.goto_next_sibling() {
+                if !cursor.goto_parent() {
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Renders the replacement template for the text given, which should be a match of this pattern
+    /// in the language given.
+    pub(crate) fn replacement_for(
+        &self,
+        text: &str,
+        template: &str,
+        language: &Arc<Language>,
+    ) -> Option<String> {
+        let pattern = self.parsed(language)?;
+        let tree = parse(text, language)?;
+        let range = trimmed_range(&tree.root_node(), text);
+        let pattern_node = self.pattern_node(&pattern);
+        spanning_nodes(tree.root_node(), range)
+            .into_iter()
+            .find_map(|node| {
+                let mut matcher = Matcher::new(&self.source, text);
+                matcher
+                    .match_node(pattern_node, node)
+                    .then(|| matcher.render(template))
+            })
+    }
+}
+
+fn parse(text: &str, language: &Arc<Language>) -> Option<Tree> {
+    let grammar = language.grammar()?;
+    with_parser(|parser| {
+        parser.set_language(&grammar.ts_language).ok()?;
+        parser.parse(text, None)
+    })
+}
+
+/// Matches a pattern node against a node of the text searched, collecting the captures of metavariables.
+struct Matcher<'a> {
+    pattern_source: &'a str,
+    source: &'a str,
+    captures: HashMap<&'a str, Range<usize>>,
+}
+
+impl<'a> Matcher<'a> {
+    fn new(pattern_source: &'a str, source: &'a str) -> Self {
+        Self {
+            pattern_source,
+            source,
+            captures: HashMap::default(),
+        }
+    }
+
+    fn capture(&mut self, name: Option<&'a str>, range: Range<usize>) -> bool {
+        let Some(name) = name else {
+            return true;
+        };
+        match self.captures.get(name) {
+            // A metavariable used several times has to match the same text every time.
+            Some(captured) => self.source[captured.clone()] == self.source[range],
+            None => {
+                self.captures.insert(name, range);
+                true
+            }
+        }
+    }
+
+    fn pattern_text(&self, pattern: Node) -> &'a str {
+        &self.pattern_source[pattern.byte_range()]
+    }
+
+    fn match_node(&mut self, pattern: Node, node: Node) -> bool {
+        match metavariable(self.pattern_text(pattern)) {
+            Some(Metavariable::Single(name) | Metavariable::Multiple(name)) => {
+                return self.capture(name, node.byte_range());
+            }
+            None => {}
+        }
+        if pattern.kind_id() != node.kind_id() {
+            return false;
+        }
+        let pattern_children = significant_children(pattern);
+        let node_children = significant_children(node);
+        if pattern_children.is_empty() && node_children.is_empty() {
+            return self.pattern_text(pattern) == &self.source[node.byte_range()];
+        }
+        self.match_sequence(&pattern_children, &node_children)
+    }
+
+    fn match_sequence(&mut self, patterns: &[Node], nodes: &[Node]) -> bool {
+        let Some((pattern, patterns_rest)) = patterns.split_first() else {
+            return nodes.is_empty();
+        };
+        if let Some(Metavariable::Multiple(name)) = metavariable(self.pattern_text(*pattern)) {
+            for count in 0..=nodes.len() {
+                let captures = self.captures.clone();
+                let range = match (nodes.first(), nodes[..count].last()) {
+                    (Some(first), Some(last)) => first.start_byte()..last.end_byte(),
+                    (Some(first), None) => first.start_byte()..first.start_byte(),
+                    (None, _) => 0..0,
+                };
+                if self.capture(name, range) && self.match_sequence(patterns_rest, &nodes[count..])
+                {
+                    return true;
+                }
+                self.captures = captures;
+            }
+            return false;
+        }
+        let Some((node, nodes_rest)) = nodes.split_first() else {
+            return false;
+        };
+        let captures = self.captures.clone();
+        if self.match_node(*pattern, *node) && self.match_sequence(patterns_rest, nodes_rest) {
+            return true;
+        }
+        self.captures = captures;
+        false
+    }
+
+    fn render(&self, template: &str) -> String {
+        let mut rendered = String::new();
+        for token in tokenize(template) {
+            let (prefix, name) = match token {
+                Token::Text(text) => {
+                    rendered.push_str(text);
+                    continue;
+                }
+                Token::Single(name) => ("$", name),
+                Token::Multiple(name) => ("$$$", name),
+            };
+            match self.captures.get(name) {
+                Some(range) => rendered.push_str(&self.source[range.clone()]),
+                None => {
+                    rendered.push_str(prefix);
+                    rendered.push_str(name);
+                }
+            }
+        }
+        rendered
+    }
+}
+
+/// The children to compare: comments and other extras can be anywhere, and so are skipped,
+/// as are the zero-width nodes the parser inserts in place of missing ones.
+fn significant_children(node: Node) -> Vec<Node> {
+    let mut cursor = node.walk();
+    node.children(&mut cursor)
+        .filter(|child| !child.is_extra() && !child.is_missing())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize("foo($A, $$$REST) + $$$ + $_ + $lower + $"),
+            vec![
+                Token::Text("foo("),
+                Token::Single("A"),
+                Token::Text(", "),
+                Token::Multiple("REST"),
+                Token::Text(") + "),
+                Token::Multiple(""),
+                Token::Text(" + "),
+                Token::Single("_"),
+                Token::Text(" + $lower + $"),
+            ]
+        );
+        assert!(StructuralPattern::new("$A").is_err());
+        assert!(StructuralPattern::new("$$$ARGS").is_err());
+    }
+}
@@ -8,15 +8,15 @@ use std::{
     sync::Arc,
 };
 
-use anyhow::Result;
+use anyhow::{Result, bail};
 use collections::{HashMap, HashSet, VecDeque};
 use gpui::{App, AppContext as _, Entity, SharedString, Task};
 use itertools::Itertools;
 use language::{ContextProvider, File, Language, LanguageToolchainStore, Location};
 use settings::{InvalidSettingsError, TaskKind, parse_json_with_comments};
 use task::{
-    DebugTaskDefinition, ResolvedTask, TaskContext, TaskId, TaskTemplate, TaskTemplates,
-    TaskVariables, VariableName,
+    DebugTaskDefinition, ResolvedTask, SpawnInTerminal, TaskContext, TaskGraphStatus, TaskId,
+    TaskTemplate, TaskTemplates, TaskVariables, VariableName,
 };
 use text::{Point, ToPoint};
 use util::{NumericPrefixWithSuffix, ResultExt as _, paths::PathExt as _, post_inc};
@@ -29,6 +29,7 @@ use crate::{task_store::TaskSettingsLocation, worktree_store::WorktreeStore};
 pub struct Inventory {
     last_scheduled_tasks: VecDeque<(TaskSourceKind, ResolvedTask)>,
     templates_from_settings: ParsedTemplates,
+    task_graphs: Vec<TaskGraphStatus>,
 }
 
 #[derive(Debug, Default)]
@@ -313,6 +314,60 @@ impl Inventory {
         }
     }
 
+    /// Resolves every task the given one depends on, directly or not, with the same context the task was resolved with.
+    /// Dependencies are looked up by their labels among the tasks from the same worktree and the global ones,
+    /// preferring the tasks from the same source as the task depending on them.
+    /// Fails if any dependency cannot be found or resolved, or if the dependencies form a cycle.
+    pub fn resolve_dependencies(
+        &self,
+        task_source_kind: &TaskSourceKind,
+        resolved_task: &ResolvedTask,
+        cx: &App,
+    ) -> Result<Vec<SpawnInTerminal>> {
+        let worktree = match task_source_kind {
+            TaskSourceKind::Worktree { id, .. } => Some(*id),
+            _ => None,
+        };
+        let templates = self
+            .list_tasks(None, None, worktree, cx)
+            .into_iter()
+            .sorted_by_key(|(kind, _)| kind != task_source_kind)
+            .collect::<Vec<_>>();
+        let mut dependency_chain = vec![resolved_task.original_task().label.clone()];
+        resolve_dependencies(resolved_task, &templates, &mut dependency_chain)
+    }
+
+    /// Returns the status of the last run of the task with dependencies, by the id of the task.
+    pub fn task_graph(&self, task_id: &TaskId) -> Option<&TaskGraphStatus> {
+        self.task_graphs
+            .iter()
+            .find(|graph| graph.root() == task_id)
+    }
+
+    /// Returns the statuses of all task graph runs, in the order they were started.
+    pub fn task_graphs(&self) -> &[TaskGraphStatus] {
+        &self.task_graphs
+    }
+
+    /// Returns the status of the last run of the task with dependencies, for updating.
+    pub fn task_graph_mut(&mut self, task_id: &TaskId) -> Option<&mut TaskGraphStatus> {
+        self.task_graphs
+            .iter_mut()
+            .find(|graph| graph.root() == task_id)
+    }
+
+    /// Stores the status of a new task graph run, replacing the previous run of the same task.
+    pub fn start_task_graph(&mut self, status: TaskGraphStatus) {
+        self.task_graphs
+            .retain(|graph| graph.root() != status.root());
+        self.task_graphs.push(status);
+    }
+
+    /// Forgets the status of a task graph run, by the id of the task.
+    pub fn dismiss_task_graph(&mut self, task_id: &TaskId) {
+        self.task_graphs.retain(|graph| graph.root() != task_id);
+    }
+
     /// Deletes a resolved task from history, using its id.
     /// A similar may still resurface in `used_and_current_resolved_tasks` when its [`TaskTemplate`] is resolved again.
     pub fn delete_previously_used(&mut self, id: &TaskId) {
@@ -434,6 +489,45 @@ impl Inventory {
     }
 }
 
+fn resolve_dependencies(
+    resolved_task: &ResolvedTask,
+    templates: &[(TaskSourceKind, TaskTemplate)],
+    dependency_chain: &mut Vec<String>,
+) -> Result<Vec<SpawnInTerminal>> {
+    let mut dependencies = Vec::new();
+    for label in &resolved_task.original_task().depends_on {
+        if dependency_chain.contains(label) {
+            bail!(
+                "Task dependency cycle: {} -> {label}",
+                dependency_chain.join(" -> ")
+            );
+        }
+        let Some((kind, template)) = templates
+            .iter()
+            .find(|(_, template)| &template.label == label)
+        else {
+            bail!(
+                "Task `{}` depends on unknown task `{label}`",
+                resolved_task.original_task().label
+            );
+        };
+        let Some(mut dependency) = resolved_task.resolve_dependency(template, &kind.to_id_base())
+        else {
+            bail!("Failed to resolve task `{label}`");
+        };
+        dependency_chain.push(label.clone());
+        let nested_dependencies = resolve_dependencies(&dependency, templates, dependency_chain)?;
+        dependency_chain.pop();
+        if let Some(spawn_in_terminal) = dependency.resolved.take() {
+            dependencies.push(SpawnInTerminal {
+                dependencies: nested_dependencies,
+                ..spawn_in_terminal
+            });
+        }
+    }
+    Ok(dependencies)
+}
+
 fn task_lru_comparator(
     (kind_a, task_a, lru_score_a): &(TaskSourceKind, ResolvedTask, u32),
     (kind_b, task_b, lru_score_b): &(TaskSourceKind, ResolvedTask, u32),
@@ -964,6 +1058,113 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_resolving_task_dependencies(cx: &mut TestAppContext) {
+        init_test(cx);
+        let inventory = cx.update(Inventory::new);
+        let worktree = WorktreeId::from_usize(1);
+        inventory.update(cx, |inventory, _| {
+            inventory
+                .update_file_based_tasks(
+                    TaskSettingsLocation::Global(tasks_file()),
+                    Some(
+                        &json!([
+                            { "label": "codegen", "command": "make", "args": ["global"] },
+                            { "label": "cycle_a", "command": "echo", "depends_on": ["cycle_b"] },
+                            { "label": "cycle_b", "command": "echo", "depends_on": ["cycle_a"] },
+                        ])
+                        .to_string(),
+                    ),
+                    settings::TaskKind::Script,
+                )
+                .unwrap();
+            inventory
+                .update_file_based_tasks(
+                    TaskSettingsLocation::Worktree(SettingsLocation {
+                        worktree_id: worktree,
+                        path: Path::new(".zed"),
+                    }),
+                    Some(
+                        &json!([
+                            { "label": "codegen", "command": "make", "args": ["local"] },
+                            { "label": "api", "command": "serve", "depends_on": ["codegen"] },
+                            { "label": "web", "command": "serve" },
+                            {
+                                "label": "dev",
+                                "depends_on": ["codegen", "servers"],
+                                "depends_order": "sequence",
+                            },
+                            { "label": "servers", "depends_on": ["api", "web"] },
+                            { "label": "broken", "depends_on": ["missing"] },
+                        ])
+                        .to_string(),
+                    ),
+                    settings::TaskKind::Script,
+                )
+                .unwrap();
+        });
+
+        let resolve = |label: &str, cx: &mut TestAppContext| {
+            inventory.update(cx, |inventory, cx| {
+                let (kind, template) = inventory
+                    .list_tasks(None, None, Some(worktree), cx)
+                    .into_iter()
+                    .find(|(_, template)| template.label == label)
+                    .unwrap();
+                let resolved_task = template
+                    .resolve_task(&kind.to_id_base(), &TaskContext::default())
+                    .unwrap();
+                inventory.resolve_dependencies(&kind, &resolved_task, cx)
+            })
+        };
+        fn labels(dependencies: &[SpawnInTerminal]) -> Vec<(String, Vec<String>)> {
+            dependencies
+                .iter()
+                .map(|dependency| {
+                    (
+                        dependency.command_label.clone(),
+                        dependency
+                            .dependencies
+                            .iter()
+                            .map(|nested| nested.command_label.clone())
+                            .collect(),
+                    )
+                })
+                .collect()
+        }
+
+        let dependencies = resolve("dev", cx).unwrap();
+        assert_eq!(
+            labels(&dependencies),
+            vec![
+                ("make local".to_string(), Vec::new()),
+                (
+                    String::new(),
+                    vec!["serve".to_string(), "serve".to_string()]
+                ),
+            ],
+            "Dependencies from the same source should be preferred"
+        );
+        assert_eq!(
+            labels(&dependencies[1].dependencies),
+            vec![
+                ("serve".to_string(), vec!["make local".to_string()]),
+                ("serve".to_string(), Vec::new()),
+            ]
+        );
+
+        let error = resolve("broken", cx).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Task `broken` depends on unknown task `missing`"
+        );
+        let error = resolve("cycle_a", cx).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Task dependency cycle: cycle_a -> cycle_b -> cycle_a"
+        );
+    }
+
     fn init_test(_cx: &mut TestAppContext) {
         if std::env::var("RUST_LOG").is_ok() {
             env_logger::try_init().ok();
@@ -241,6 +241,14 @@ impl Project {
         let mut python_venv_activate_command = None;
         let debug_terminal = matches!(kind, TerminalKind::Debug { .. });
 
+        let task_problem_matchers = match &kind {
+            TerminalKind::Task(spawn_task) => Some((
+                spawn_task.id.clone(),
+                !spawn_task.problem_matchers.is_empty(),
+            )),
+            _ => None,
+        };
+
         let (spawn_task, shell) = match kind {
             TerminalKind::Shell(_) => {
                 if let Some(python_venv_directory) = &python_venv_directory {
@@ -285,6 +293,7 @@ impl Project {
                     show_summary: spawn_task.show_summary,
                     show_command: spawn_task.show_command,
                     show_rerun: spawn_task.show_rerun,
+                    problem_matchers: spawn_task.problem_matchers,
                     completion_rx,
                 });
 
@@ -366,6 +375,7 @@ impl Project {
             settings.cursor_shape.unwrap_or_default(),
             settings.alternate_scroll,
             settings.max_scroll_history_lines,
+            settings.shell_integration,
             ssh_details.is_some(),
             window,
             completion_tx,
@@ -396,6 +406,24 @@ impl Project {
             if let Some(activate_command) = python_venv_activate_command {
                 this.activate_python_virtual_environment(activate_command, &terminal_handle, cx);
             }
+
+            if let Some((task_id, has_problem_matchers)) = task_problem_matchers {
+                // Problems found during the previous run of the task are cleared when it reruns.
+                this.lsp_store.update(cx, |lsp_store, cx| {
+                    lsp_store.update_task_diagnostics(&task_id, Vec::new(), cx)
+                });
+                if has_problem_matchers {
+                    cx.subscribe(&terminal_handle, move |project, terminal, event, cx| {
+                        if let terminal::Event::TaskProblemsUpdated = event {
+                            let problems = terminal.read(cx).task_problems();
+                            project.lsp_store.update(cx, |lsp_store, cx| {
+                                lsp_store.update_task_diagnostics(&task_id, problems, cx)
+                            });
+                        }
+                    })
+                    .detach();
+                }
+            }
             terminal_handle
         })
     }
@@ -715,6 +715,18 @@ impl ProjectPanel {
             let is_read_only = project.is_read_only(cx);
             let is_remote = project.is_via_collab();
             let is_local = project.is_local();
+            let has_git_repo = project
+                .git_store()
+                .read(cx)
+                .repository_and_path_for_project_path(
+                    &ProjectPath {
+                        worktree_id,
+                        path: entry.path.clone(),
+                    },
+                    cx,
+                )
+                .is_some();
+            let abs_path = worktree.absolutize(&entry.path).ok();
 
             let context_menu = ContextMenu::build(window, cx, |menu, _, _| {
                 menu.context(self.focus_handle.clone()).map(|menu| {
@@ -736,6 +748,9 @@ impl ProjectPanel {
                                 menu.action("Open in Default App", Box::new(OpenWithSystem))
                             })
                             .action("Open in Terminal", Box::new(OpenInTerminal))
+                            .when(has_git_repo, |menu| {
+                                menu.action("File History", Box::new(git::FileHistory { abs_path }))
+                            })
                             .when(is_dir, |menu| {
                                 menu.separator()
                                     .action("Find in Folder…", Box::new(NewSearchInDirectory))
@@ -276,6 +276,18 @@ messages!(
     (GetDocumentHighlightsResponse, Background),
     (GetDocumentSymbols, Background),
     (GetDocumentSymbolsResponse, Background),
+    (PrepareCallHierarchy, Background),
+    (PrepareCallHierarchyResponse, Background),
+    (GetIncomingCalls, Background),
+    (GetIncomingCallsResponse, Background),
+    (GetOutgoingCalls, Background),
+    (GetOutgoingCallsResponse, Background),
+    (PrepareTypeHierarchy, Background),
+    (PrepareTypeHierarchyResponse, Background),
+    (GetSupertypes, Background),
+    (GetSupertypesResponse, Background),
+    (GetSubtypes, Background),
+    (GetSubtypesResponse, Background),
     (GetHover, Background),
     (GetHoverResponse, Background),
     (GetNotifications, Foreground),
@@ -395,6 +407,9 @@ messages!(
     (ResolveInlayHint, Background),
     (ResolveInlayHintResponse, Background),
     (RefreshCodeLens, Background),
+    (GetSemanticTokens, Background),
+    (GetSemanticTokensResponse, Background),
+    (RefreshSemanticTokens, Background),
     (GetCodeLens, Background),
     (GetCodeLensResponse, Background),
     (RespondToChannelInvite, Foreground),
@@ -418,6 +433,12 @@ messages!(
     (SyncExtensionsResponse, Background),
     (BreakpointsForFile, Background),
     (ToggleBreakpoint, Foreground),
+    (ExceptionBreakpoints, Background),
+    (UpdateExceptionBreakpoint, Foreground),
+    (FunctionBreakpoints, Background),
+    (UpdateFunctionBreakpoint, Foreground),
+    (DataBreakpoints, Background),
+    (UpdateDataBreakpoint, Foreground),
     (SynchronizeBuffers, Foreground),
     (SynchronizeBuffersResponse, Foreground),
     (SynchronizeContexts, Foreground),
@@ -474,6 +495,31 @@ messages!(
     (GitDiff, Background),
     (GitDiffResponse, Background),
     (GitInit, Background),
+    (GitStashPush, Background),
+    (GitStashList, Background),
+    (GitStashListResponse, Background),
+    (GitStashApply, Background),
+    (GitStashPop, Background),
+    (GitStashDrop, Background),
+    (GitStashShow, Background),
+    (GitLog, Background),
+    (GitLogResponse, Background),
+    (GitRebase, Background),
+    (GitRebaseContinue, Background),
+    (GitRebaseSkip, Background),
+    (GitRebaseAbort, Background),
+    (GitRebaseStatus, Background),
+    (GitRebaseStatusResponse, Background),
+    (GitSubmodules, Background),
+    (GitSubmodulesResponse, Background),
+    (GitSubmoduleUpdate, Background),
+    (GitWorktrees, Background),
+    (GitWorktreesResponse, Background),
+    (GitWorktreeAdd, Background),
+    (GitWorktreeRemove, Background),
+    (GitBlameAtRevision, Background),
+    (GitLoadTextAtRevision, Background),
+    (GitLoadTextAtRevisionResponse, Background),
 );
 
 request_messages!(
@@ -511,6 +557,12 @@ request_messages!(
     (GetImplementation, GetImplementationResponse),
     (GetDocumentHighlights, GetDocumentHighlightsResponse),
     (GetDocumentSymbols, GetDocumentSymbolsResponse),
+    (PrepareCallHierarchy, PrepareCallHierarchyResponse),
+    (GetIncomingCalls, GetIncomingCallsResponse),
+    (GetOutgoingCalls, GetOutgoingCallsResponse),
+    (PrepareTypeHierarchy, PrepareTypeHierarchyResponse),
+    (GetSupertypes, GetSupertypesResponse),
+    (GetSubtypes, GetSubtypesResponse),
     (GetHover, GetHoverResponse),
     (GetLlmToken, GetLlmTokenResponse),
     (GetNotifications, GetNotificationsResponse),
@@ -551,6 +603,8 @@ request_messages!(
     (CountLanguageModelTokens, CountLanguageModelTokensResponse),
     (RefreshInlayHints, Ack),
     (RefreshCodeLens, Ack),
+    (RefreshSemanticTokens, Ack),
+    (GetSemanticTokens, GetSemanticTokensResponse),
     (RejoinChannelBuffers, RejoinChannelBuffersResponse),
     (RejoinRoom, RejoinRoomResponse),
     (ReloadBuffers, ReloadBuffersResponse),
@@ -629,7 +683,29 @@ request_messages!(
     (CheckForPushedCommits, CheckForPushedCommitsResponse),
     (GitDiff, GitDiffResponse),
     (GitInit, Ack),
+    (GitStashPush, Ack),
+    (GitStashList, GitStashListResponse),
+    (GitStashApply, Ack),
+    (GitStashPop, Ack),
+    (GitStashDrop, Ack),
+    (GitStashShow, LoadCommitDiffResponse),
+    (GitLog, GitLogResponse),
+    (GitRebase, GitRebaseStatusResponse),
+    (GitRebaseContinue, GitRebaseStatusResponse),
+    (GitRebaseSkip, GitRebaseStatusResponse),
+    (GitRebaseAbort, Ack),
+    (GitRebaseStatus, GitRebaseStatusResponse),
+    (GitSubmodules, GitSubmodulesResponse),
+    (GitSubmoduleUpdate, Ack),
+    (GitWorktrees, GitWorktreesResponse),
+    (GitWorktreeAdd, Ack),
+    (GitWorktreeRemove, Ack),
+    (GitBlameAtRevision, BlameBufferResponse),
+    (GitLoadTextAtRevision, GitLoadTextAtRevisionResponse),
     (ToggleBreakpoint, Ack),
+    (UpdateExceptionBreakpoint, Ack),
+    (UpdateFunctionBreakpoint, Ack),
+    (UpdateDataBreakpoint, Ack),
 );
 
 entity_messages!(
@@ -660,6 +736,12 @@ entity_messages!(
     GetImplementation,
     GetDocumentHighlights,
     GetDocumentSymbols,
+    PrepareCallHierarchy,
+    GetIncomingCalls,
+    GetOutgoingCalls,
+    PrepareTypeHierarchy,
+    GetSupertypes,
+    GetSubtypes,
     GetHover,
     GetProjectSymbols,
     GetReferences,
@@ -684,6 +766,8 @@ entity_messages!(
     PrepareRename,
     RefreshInlayHints,
     RefreshCodeLens,
+    RefreshSemanticTokens,
+    GetSemanticTokens,
     ReloadBuffers,
     RemoveProjectCollaborator,
     RenameProjectEntry,
@@ -745,8 +829,33 @@ entity_messages!(
     CheckForPushedCommits,
     GitDiff,
     GitInit,
+    GitStashPush,
+    GitStashList,
+    GitStashApply,
+    GitStashPop,
+    GitStashDrop,
+    GitStashShow,
+    GitLog,
+    GitRebase,
+    GitRebaseContinue,
+    GitRebaseSkip,
+    GitRebaseAbort,
+    GitRebaseStatus,
+    GitSubmodules,
+    GitSubmoduleUpdate,
+    GitWorktrees,
+    GitWorktreeAdd,
+    GitWorktreeRemove,
+    GitBlameAtRevision,
+    GitLoadTextAtRevision,
     BreakpointsForFile,
     ToggleBreakpoint,
+    ExceptionBreakpoints,
+    UpdateExceptionBreakpoint,
+    FunctionBreakpoints,
+    UpdateFunctionBreakpoint,
+    DataBreakpoints,
+    UpdateDataBreakpoint,
 );
 
 entity_messages!(
@@ -0,0 +1,382 @@
+use fuzzy::{StringMatch, StringMatchCandidate};
+use git::repository::GitWorktree;
+use gpui::{
+    App, AsyncWindowContext, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
+    Subscription, Task, WeakEntity, Window,
+};
+use picker::{Picker, PickerDelegate};
+use project::git_store::Repository;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, Tooltip, prelude::*};
+use util::ResultExt as _;
+use workspace::{ModalView, Workspace, notifications::DetachAndPromptErr as _};
+use zed_actions::OpenWorktree;
+
+/// Lists the working trees of the active repository, opening the selected one as a new project.
+/// Typing a revision offers to check it out in a new linked worktree, e.g. to review a branch
+/// without touching the current checkout.
+pub struct WorktreePicker {
+    picker: Entity<Picker<WorktreePickerDelegate>>,
+    _subscription: Subscription,
+}
+
+impl ModalView for WorktreePicker {}
+
+impl WorktreePicker {
+    pub(crate) fn register(
+        workspace: &mut Workspace,
+        _window: Option<&mut Window>,
+        _cx: &mut Context<Workspace>,
+    ) {
+        workspace.register_action(|workspace, _: &OpenWorktree, window, cx| {
+            Self::open(workspace, window, cx);
+        });
+    }
+
+    pub fn open(workspace: &mut Workspace, window: &mut Window, cx: &mut Context<Workspace>) {
+        let project = workspace.project().read(cx);
+        // The worktrees of a remote project live on the host, where they can't be opened from.
+        if !project.is_local() {
+            return;
+        }
+        let Some(repository) = project.active_repository(cx) else {
+            return;
+        };
+        let weak = cx.entity().downgrade();
+        workspace.toggle_modal(window, cx, |window, cx| {
+            let delegate = WorktreePickerDelegate::new(weak, repository);
+            Self::new(delegate, window, cx)
+        })
+    }
+
+    fn new(delegate: WorktreePickerDelegate, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let picker = cx.new(|cx| {
+            let mut picker = Picker::uniform_list(delegate, window, cx);
+            WorktreePickerDelegate::reload_worktrees(&mut picker, window, cx);
+            picker
+        });
+        let _subscription = cx.subscribe(&picker, |_, _, _, cx| cx.emit(DismissEvent));
+        Self {
+            picker,
+            _subscription,
+        }
+    }
+}
+
+impl EventEmitter<DismissEvent> for WorktreePicker {}
+
+impl Focusable for WorktreePicker {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl Render for WorktreePicker {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+pub struct WorktreePickerDelegate {
+    workspace: WeakEntity<Workspace>,
+    repository: Entity<Repository>,
+    /// The worktrees of the repository, except for the one open in this project.
+    worktrees: Vec<GitWorktree>,
+    matches: Vec<StringMatch>,
+    /// The revision typed in the query, offered after the matches to create a new worktree.
+    new_worktree_revision: Option<String>,
+    selected_index: usize,
+}
+
+impl WorktreePickerDelegate {
+    fn new(workspace: WeakEntity<Workspace>, repository: Entity<Repository>) -> Self {
+        Self {
+            workspace,
+            repository,
+            worktrees: Vec::new(),
+            matches: Vec::new(),
+            new_worktree_revision: None,
+            selected_index: 0,
+        }
+    }
+
+    fn reload_worktrees(
+        picker: &mut Picker<Self>,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        let repository = picker.delegate.repository.read(cx);
+        let current_path = repository.work_directory_abs_path.clone();
+        let worktrees = repository.worktrees();
+        cx.spawn_in(window, async move |picker, cx| {
+            let mut worktrees = worktrees.await??;
+            worktrees.retain(|worktree| worktree.path.as_path() != current_path.as_ref());
+            picker.update_in(cx, |picker, window, cx| {
+                picker.delegate.worktrees = worktrees;
+                picker.refresh(window, cx);
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn remove_worktree(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(worktree) = self
+            .matches
+            .get(ix)
+            .and_then(|hit| self.worktrees.get(hit.candidate_id))
+        else {
+            return;
+        };
+        let remove = self
+            .repository
+            .read(cx)
+            .worktree_remove(worktree.path.clone(), false);
+        cx.spawn_in(window, async move |picker, cx| {
+            remove.await??;
+            picker.update_in(cx, |picker, window, cx| {
+                Self::reload_worktrees(picker, window, cx);
+            })
+        })
+        .detach_and_prompt_err("Failed to remove worktree", window, cx, |_, _, _| None);
+    }
+
+    fn open_worktree(
+        workspace: WeakEntity<Workspace>,
+        path: PathBuf,
+        cx: &mut AsyncWindowContext,
+    ) -> Task<anyhow::Result<()>> {
+        let open = workspace.update_in(cx, |workspace, window, cx| {
+            workspace.open_workspace_for_paths(false, vec![path], window, cx)
+        });
+        match open {
+            Ok(open) => open,
+            Err(error) => Task::ready(Err(error)),
+        }
+    }
+}
+
+impl EventEmitter<DismissEvent> for WorktreePickerDelegate {}
+
+impl PickerDelegate for WorktreePickerDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Select a worktree, or type a revision to check out in a new one…".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len() + self.new_worktree_revision.is_some() as usize
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let query = query.trim().to_string();
+        let candidates = self
+            .worktrees
+            .iter()
+            .enumerate()
+            .map(|(ix, worktree)| StringMatchCandidate::new(ix, &worktree.path.to_string_lossy()))
+            .collect::<Vec<_>>();
+        let executor = cx.background_executor().clone();
+        cx.spawn_in(window, async move |picker, cx| {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        score: 0.,
+                        positions: Vec::new(),
+                        string: candidate.string,
+                    })
+                    .collect()
+            } else {
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    100,
+                    &Default::default(),
+                    executor,
+                )
+                .await
+            };
+            picker
+                .update(cx, |picker, _| {
+                    let delegate = &mut picker.delegate;
+                    delegate.new_worktree_revision = (!query.is_empty()
+                        && !query.contains(char::is_whitespace))
+                    .then_some(query);
+                    delegate.matches = matches;
+                    delegate.selected_index = delegate
+                        .selected_index
+                        .min(delegate.match_count().saturating_sub(1));
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let workspace = self.workspace.clone();
+        if let Some(hit) = self.matches.get(self.selected_index) {
+            let Some(worktree) = self.worktrees.get(hit.candidate_id) else {
+                return;
+            };
+            let path = worktree.path.clone();
+            cx.spawn_in(window, async move |_, cx| {
+                Self::open_worktree(workspace, path, cx).await
+            })
+            .detach_and_prompt_err(
+                "Failed to open worktree",
+                window,
+                cx,
+                |_, _, _| None,
+            );
+        } else if let Some(revision) = self.new_worktree_revision.clone() {
+            let repository = self.repository.read(cx);
+            let path = new_worktree_path(&repository.work_directory_abs_path, &revision);
+            let add = repository.worktree_add(path.clone(), revision);
+            cx.spawn_in(window, async move |_, cx| {
+                add.await??;
+                Self::open_worktree(workspace, path, cx).await
+            })
+            .detach_and_prompt_err(
+                "Failed to create worktree",
+                window,
+                cx,
+                |_, _, _| None,
+            );
+        } else {
+            return;
+        }
+        cx.emit(DismissEvent);
+    }
+
+    fn dismissed(&mut self, _window: &mut Window, _cx: &mut Context<Picker<Self>>) {}
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No other worktrees".into())
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let Some(hit) = self.matches.get(ix) else {
+            let revision = self.new_worktree_revision.as_ref()?;
+            let path =
+                new_worktree_path(&self.repository.read(cx).work_directory_abs_path, revision);
+            return Some(
+                ListItem::new(ix)
+                    .inset(true)
+                    .spacing(ListItemSpacing::Sparse)
+                    .toggle_state(selected)
+                    .start_slot(Icon::new(IconName::Plus).color(Color::Muted))
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .child(Label::new(format!(
+                                "Check out \"{revision}\" in a new worktree"
+                            )))
+                            .child(
+                                Label::new(path.to_string_lossy().into_owned())
+                                    .color(Color::Muted)
+                                    .size(LabelSize::Small)
+                                    .truncate(),
+                            ),
+                    ),
+            );
+        };
+        let worktree = self.worktrees.get(hit.candidate_id)?;
+        let checkout = match (&worktree.branch, &worktree.sha) {
+            (Some(branch), _) => branch.clone(),
+            (None, Some(sha)) => sha
+                .get(..git::SHORT_SHA_LENGTH)
+                .unwrap_or(sha)
+                .to_string()
+                .into(),
+            (None, None) => "bare".into(),
+        };
+
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .start_slot(Icon::new(IconName::GitBranch).color(Color::Muted))
+                .child(
+                    h_flex()
+                        .gap_2()
+                        .child(HighlightedLabel::new(
+                            hit.string.clone(),
+                            hit.positions.clone(),
+                        ))
+                        .child(
+                            Label::new(checkout)
+                                .color(Color::Muted)
+                                .size(LabelSize::Small),
+                        ),
+                )
+                .when(!worktree.is_main, |this| {
+                    this.end_hover_slot(
+                        IconButton::new("remove-worktree", IconName::Trash)
+                            .icon_size(IconSize::Small)
+                            .tooltip(Tooltip::text("Remove Worktree"))
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                cx.stop_propagation();
+                                this.delegate.remove_worktree(ix, window, cx);
+                            })),
+                    )
+                }),
+        )
+    }
+}
+
+/// Places a new worktree next to the repository, named after the repository and the revision.
+fn new_worktree_path(work_directory: &Path, revision: &str) -> PathBuf {
+    let revision = revision.replace(['/', '\\', ':'], "-");
+    let name = match work_directory.file_name() {
+        Some(name) => format!("{}-{revision}", name.to_string_lossy()),
+        None => revision,
+    };
+    work_directory.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_worktree_path() {
+        assert_eq!(
+            new_worktree_path(Path::new("/code/zed"), "origin/pr/123"),
+            PathBuf::from("/code/zed-origin-pr-123")
+        );
+        assert_eq!(
+            new_worktree_path(Path::new("/code/zed"), "v1.0"),
+            PathBuf::from("/code/zed-v1.0")
+        );
+    }
+}
@@ -1,10 +1,12 @@
 pub mod disconnected_overlay;
+mod git_worktrees;
 mod remote_servers;
 mod ssh_connections;
 pub use ssh_connections::{is_connecting_over_ssh, open_ssh_project};
 
 use disconnected_overlay::DisconnectedOverlay;
 use fuzzy::{StringMatch, StringMatchCandidate};
+pub use git_worktrees::WorktreePicker;
 use gpui::{
     Action, AnyElement, App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
     Subscription, Task, WeakEntity, Window,
@@ -27,12 +29,13 @@ use workspace::{
     CloseIntent, ModalView, OpenOptions, SerializedWorkspaceLocation, WORKSPACE_DB, Workspace,
     WorkspaceId,
 };
-use zed_actions::{OpenRecent, OpenRemote};
+use zed_actions::{OpenRecent, OpenRemote, OpenWorktree};
 
 pub fn init(cx: &mut App) {
     SshSettings::register(cx);
     cx.observe_new(RecentProjects::register).detach();
     cx.observe_new(RemoteServerProjects::register).detach();
+    cx.observe_new(WorktreePicker::register).detach();
     cx.observe_new(DisconnectedOverlay::register).detach();
 }
 
@@ -464,6 +467,13 @@ impl PickerDelegate for RecentProjectsDelegate {
                 .justify_end()
                 .border_t_1()
                 .border_color(cx.theme().colors().border_variant)
+                .child(
+                    Button::new("worktree", "Open Git Worktree")
+                        .key_binding(KeyBinding::for_action(&OpenWorktree, window, cx))
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(OpenWorktree.boxed_clone(), cx)
+                        }),
+                )
                 .child(
                     Button::new("remote", "Open Remote Folder")
                         .key_binding(KeyBinding::for_action(&OpenRemote, window, cx))
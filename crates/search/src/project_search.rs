@@ -1,7 +1,9 @@
+mod replace_preview;
+
 use crate::{
     BufferSearchBar, FocusSearch, NextHistoryQuery, PreviousHistoryQuery, ReplaceAll, ReplaceNext,
     SearchOptions, SelectNextMatch, SelectPreviousMatch, ToggleCaseSensitive, ToggleIncludeIgnored,
-    ToggleRegex, ToggleReplace, ToggleWholeWord, buffer_search::Deploy,
+    ToggleRegex, ToggleReplace, ToggleStructural, ToggleWholeWord, buffer_search::Deploy,
 };
 use anyhow::Context as _;
 use collections::{HashMap, HashSet};
@@ -23,6 +25,7 @@ use project::{
     search::{SearchInputKind, SearchQuery},
     search_history::SearchHistoryCursor,
 };
+use replace_preview::{ReplacePreview, ReplacePreviewEvent, ReplacementScope};
 use settings::Settings;
 use std::{
     any::{Any, TypeId},
@@ -47,7 +50,19 @@ use workspace::{
 
 actions!(
     project_search,
-    [SearchInNew, ToggleFocus, NextField, ToggleFilters]
+    [
+        SearchInNew,
+        ToggleFocus,
+        NextField,
+        ToggleFilters,
+        ToggleReplacePreview,
+        AcceptReplacement,
+        RejectReplacement,
+        AcceptFileReplacements,
+        RejectFileReplacements,
+        AcceptAllReplacements,
+        RejectAllReplacements
+    ]
 );
 
 #[derive(Default)]
@@ -82,12 +97,21 @@ pub fn init(cx: &mut App) {
         register_workspace_action(workspace, move |search_bar, _: &ToggleRegex, _, cx| {
             search_bar.toggle_search_option(SearchOptions::REGEX, cx);
         });
+        register_workspace_action(workspace, move |search_bar, _: &ToggleStructural, _, cx| {
+            search_bar.toggle_search_option(SearchOptions::STRUCTURAL, cx);
+        });
         register_workspace_action(
             workspace,
             move |search_bar, action: &ToggleReplace, window, cx| {
                 search_bar.toggle_replace(action, window, cx)
             },
         );
+        register_workspace_action(
+            workspace,
+            move |search_bar, action: &ToggleReplacePreview, window, cx| {
+                search_bar.toggle_replace_preview(action, window, cx)
+            },
+        );
         register_workspace_action(
             workspace,
             move |search_bar, action: &SelectPreviousMatch, window, cx| {
@@ -170,6 +194,7 @@ pub struct ProjectSearchView {
     replace_enabled: bool,
     included_opened_only: bool,
     regex_language: Option<Arc<Language>>,
+    replace_preview: Option<(Entity<ReplacePreview>, Subscription)>,
     _subscriptions: Vec<Subscription>,
 }
 
@@ -344,11 +369,34 @@ impl EventEmitter<ViewEvent> for ProjectSearchView {}
 impl Render for ProjectSearchView {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         if self.has_matches() {
-            div()
+            let results = div()
                 .flex_1()
                 .size_full()
-                .track_focus(&self.focus_handle(cx))
-                .child(self.results_editor.clone())
+                .track_focus(&self.focus_handle(cx));
+            if let Some((replace_preview, _)) = self.replace_preview.as_ref() {
+                results
+                    .on_action(cx.listener(|this, _: &AcceptReplacement, _, cx| {
+                        this.accept_replacements(ReplacementScope::Selections, cx)
+                    }))
+                    .on_action(cx.listener(|this, _: &RejectReplacement, window, cx| {
+                        this.reject_replacements(ReplacementScope::Selections, window, cx)
+                    }))
+                    .on_action(cx.listener(|this, _: &AcceptFileReplacements, _, cx| {
+                        this.accept_replacements(ReplacementScope::Files, cx)
+                    }))
+                    .on_action(cx.listener(|this, _: &RejectFileReplacements, window, cx| {
+                        this.reject_replacements(ReplacementScope::Files, window, cx)
+                    }))
+                    .on_action(cx.listener(|this, _: &AcceptAllReplacements, _, cx| {
+                        this.accept_replacements(ReplacementScope::All, cx)
+                    }))
+                    .on_action(cx.listener(|this, _: &RejectAllReplacements, window, cx| {
+                        this.reject_replacements(ReplacementScope::All, window, cx)
+                    }))
+                    .child(replace_preview.read(cx).editor().clone())
+            } else {
+                results.child(self.results_editor.clone())
+            }
         } else {
             let model = self.entity.read(cx);
             let has_no_results = model.no_results.unwrap_or(false);
@@ -619,6 +667,18 @@ impl ProjectSearchView {
 
     fn toggle_search_option(&mut self, option: SearchOptions, cx: &mut Context<Self>) {
         self.search_options.toggle(option);
+        // A query is either a regex or a syntax pattern, never both.
+        if self
+            .search_options
+            .contains(SearchOptions::REGEX | SearchOptions::STRUCTURAL)
+        {
+            self.search_options
+                .remove(if option == SearchOptions::REGEX {
+                    SearchOptions::STRUCTURAL
+                } else {
+                    SearchOptions::REGEX
+                });
+        }
         ActiveSettings::update_global(cx, |settings, cx| {
             settings.0.insert(
                 self.entity.read(cx).project.downgrade(),
@@ -681,6 +741,99 @@ impl ProjectSearchView {
         });
     }
 
+    fn toggle_replace_preview(
+        &mut self,
+        _: &ToggleReplacePreview,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.replace_preview.take().is_some() {
+            self.focus_results_editor(window, cx);
+            cx.notify();
+            return;
+        }
+        if self.active_match_index.is_none() {
+            return;
+        }
+
+        let Some(query) = self.entity.read(cx).active_query.as_ref() else {
+            return;
+        };
+        let query = query.clone().with_replacement(self.replacement(cx));
+
+        let excerpts = self.entity.read(cx).excerpts.clone();
+        let snapshot = excerpts.read(cx).snapshot(cx);
+        let mut replacements =
+            Vec::<(Entity<Buffer>, Vec<(Range<language::Anchor>, String)>)>::new();
+        for range in &self.entity.read(cx).match_ranges {
+            let Some(buffer) = range
+                .start
+                .buffer_id
+                .and_then(|buffer_id| excerpts.read(cx).buffer(buffer_id))
+            else {
+                continue;
+            };
+            let text = snapshot.text_for_range(range.clone()).collect::<String>();
+            let language = snapshot.language_at(range.start);
+            let Some(replacement) = query.replacement_for(&text, language) else {
+                continue;
+            };
+            let edit = (
+                range.start.text_anchor..range.end.text_anchor,
+                replacement.into_owned(),
+            );
+            match replacements
+                .iter_mut()
+                .find(|(replaced_buffer, _)| *replaced_buffer == buffer)
+            {
+                Some((_, edits)) => edits.push(edit),
+                None => replacements.push((buffer, vec![edit])),
+            }
+        }
+        if replacements.is_empty() {
+            return;
+        }
+
+        let replace_preview = cx.new(|cx| ReplacePreview::new(excerpts, replacements, window, cx));
+        let subscription = cx.subscribe_in(
+            &replace_preview,
+            window,
+            |this, _, event, window, cx| match event {
+                ReplacePreviewEvent::Finished => {
+                    this.replace_preview = None;
+                    this.focus_results_editor(window, cx);
+                    cx.notify();
+                }
+            },
+        );
+        window.focus(&replace_preview.focus_handle(cx));
+        self.replace_preview = Some((replace_preview, subscription));
+        cx.notify();
+    }
+
+    fn accept_replacements(&mut self, scope: ReplacementScope, cx: &mut Context<Self>) {
+        if let Some((replace_preview, _)) = self.replace_preview.as_ref() {
+            replace_preview.update(cx, |replace_preview, cx| {
+                let hunks = replace_preview.hunks(scope, cx);
+                replace_preview.accept(hunks, cx);
+            });
+        }
+    }
+
+    fn reject_replacements(
+        &mut self,
+        scope: ReplacementScope,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some((replace_preview, _)) = self.replace_preview.as_ref() {
+            replace_preview.update(cx, |replace_preview, cx| {
+                let hunks = replace_preview.hunks(scope, cx);
+                replace_preview.reject(hunks, window, cx);
+            });
+        }
+    }
+
     pub fn new(
         workspace: WeakEntity<Workspace>,
         entity: Entity<ProjectSearch>,
@@ -839,6 +992,7 @@ impl ProjectSearchView {
             replace_enabled: false,
             included_opened_only: false,
             regex_language: None,
+            replace_preview: None,
             _subscriptions: subscriptions,
         };
         this.entity_changed(window, cx);
@@ -994,6 +1148,7 @@ impl ProjectSearchView {
     }
 
     fn search(&mut self, cx: &mut Context<Self>) {
+        self.replace_preview = None;
         if let Some(query) = self.build_search_query(cx) {
             self.entity.update(cx, |model, cx| model.search(query, cx));
         }
@@ -1047,7 +1202,32 @@ impl ProjectSearchView {
                 }
             };
 
-        let query = if self.search_options.contains(SearchOptions::REGEX) {
+        let query = if self.search_options.contains(SearchOptions::STRUCTURAL) {
+            match SearchQuery::structural(
+                text,
+                self.search_options.contains(SearchOptions::INCLUDE_IGNORED),
+                included_files,
+                excluded_files,
+                open_buffers,
+            ) {
+                Ok(query) => {
+                    let should_unmark_error = self.panels_with_errors.remove(&InputPanel::Query);
+                    if should_unmark_error {
+                        cx.notify();
+                    }
+
+                    Some(query)
+                }
+                Err(_e) => {
+                    let should_mark_error = self.panels_with_errors.insert(InputPanel::Query);
+                    if should_mark_error {
+                        cx.notify();
+                    }
+
+                    None
+                }
+            }
+        } else if self.search_options.contains(SearchOptions::REGEX) {
             match SearchQuery::regex(
                 text,
                 self.search_options.contains(SearchOptions::WHOLE_WORD),
@@ -1314,6 +1494,21 @@ impl ProjectSearchView {
                         window.dispatch_action(ToggleRegex.boxed_clone(), cx)
                     }),
             )
+            .child(
+                Button::new("structural", "Match syntax structure")
+                    .icon(IconName::ListTree)
+                    .icon_position(IconPosition::Start)
+                    .icon_size(IconSize::Small)
+                    .key_binding(KeyBinding::for_action_in(
+                        &ToggleStructural,
+                        &focus_handle,
+                        window,
+                        cx,
+                    ))
+                    .on_click(|_event, window, cx| {
+                        window.dispatch_action(ToggleStructural.boxed_clone(), cx)
+                    }),
+            )
             .child(
                 Button::new("match-case", "Match case")
                     .icon(IconName::CaseSensitive)
@@ -1534,6 +1729,20 @@ impl ProjectSearchBar {
         }
     }
 
+    fn toggle_replace_preview(
+        &mut self,
+        action: &ToggleReplacePreview,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(search) = &self.active_project_search {
+            search.update(cx, |this, cx| {
+                this.toggle_replace_preview(action, window, cx);
+            });
+            cx.notify();
+        }
+    }
+
     fn toggle_filters(&mut self, window: &mut Window, cx: &mut Context<Self>) -> bool {
         if let Some(search_view) = self.active_project_search.as_ref() {
             search_view.update(cx, |search_view, cx| {
@@ -1803,6 +2012,13 @@ impl Render for ProjectSearchBar {
                         cx.listener(|this, _, _, cx| {
                             this.toggle_search_option(SearchOptions::REGEX, cx);
                         }),
+                    ))
+                    .child(SearchOptions::STRUCTURAL.as_button(
+                        self.is_option_enabled(SearchOptions::STRUCTURAL, cx),
+                        focus_handle.clone(),
+                        cx.listener(|this, _, _, cx| {
+                            this.toggle_search_option(SearchOptions::STRUCTURAL, cx);
+                        }),
                     )),
             );
 
@@ -1965,58 +2181,125 @@ impl Render for ProjectSearchBar {
 
             let focus_handle = search.replacement_editor.read(cx).focus_handle(cx);
 
-            let replace_actions =
-                h_flex()
-                    .min_w_64()
-                    .gap_1()
-                    .when(search.replace_enabled, |this| {
-                        this.child(
-                            IconButton::new("project-search-replace-next", IconName::ReplaceNext)
-                                .shape(IconButtonShape::Square)
-                                .on_click(cx.listener(|this, _, window, cx| {
-                                    if let Some(search) = this.active_project_search.as_ref() {
-                                        search.update(cx, |this, cx| {
-                                            this.replace_next(&ReplaceNext, window, cx);
-                                        })
-                                    }
-                                }))
-                                .tooltip({
-                                    let focus_handle = focus_handle.clone();
-                                    move |window, cx| {
-                                        Tooltip::for_action_in(
-                                            "Replace Next Match",
-                                            &ReplaceNext,
-                                            &focus_handle,
-                                            window,
-                                            cx,
-                                        )
-                                    }
-                                }),
-                        )
-                        .child(
-                            IconButton::new("project-search-replace-all", IconName::ReplaceAll)
-                                .shape(IconButtonShape::Square)
-                                .on_click(cx.listener(|this, _, window, cx| {
-                                    if let Some(search) = this.active_project_search.as_ref() {
-                                        search.update(cx, |this, cx| {
-                                            this.replace_all(&ReplaceAll, window, cx);
-                                        })
-                                    }
-                                }))
-                                .tooltip({
-                                    let focus_handle = focus_handle.clone();
-                                    move |window, cx| {
-                                        Tooltip::for_action_in(
-                                            "Replace All Matches",
-                                            &ReplaceAll,
-                                            &focus_handle,
-                                            window,
-                                            cx,
-                                        )
-                                    }
-                                }),
-                        )
-                    });
+            let replace_actions = h_flex()
+                .min_w_64()
+                .gap_1()
+                .when(search.replace_enabled, |this| {
+                    this.child(
+                        IconButton::new("project-search-replace-next", IconName::ReplaceNext)
+                            .shape(IconButtonShape::Square)
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                if let Some(search) = this.active_project_search.as_ref() {
+                                    search.update(cx, |this, cx| {
+                                        this.replace_next(&ReplaceNext, window, cx);
+                                    })
+                                }
+                            }))
+                            .tooltip({
+                                let focus_handle = focus_handle.clone();
+                                move |window, cx| {
+                                    Tooltip::for_action_in(
+                                        "Replace Next Match",
+                                        &ReplaceNext,
+                                        &focus_handle,
+                                        window,
+                                        cx,
+                                    )
+                                }
+                            }),
+                    )
+                    .child(
+                        IconButton::new("project-search-replace-all", IconName::ReplaceAll)
+                            .shape(IconButtonShape::Square)
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                if let Some(search) = this.active_project_search.as_ref() {
+                                    search.update(cx, |this, cx| {
+                                        this.replace_all(&ReplaceAll, window, cx);
+                                    })
+                                }
+                            }))
+                            .tooltip({
+                                let focus_handle = focus_handle.clone();
+                                move |window, cx| {
+                                    Tooltip::for_action_in(
+                                        "Replace All Matches",
+                                        &ReplaceAll,
+                                        &focus_handle,
+                                        window,
+                                        cx,
+                                    )
+                                }
+                            }),
+                    )
+                    .child(
+                        IconButton::new("project-search-replace-preview", IconName::Diff)
+                            .shape(IconButtonShape::Square)
+                            .toggle_state(search.replace_preview.is_some())
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.toggle_replace_preview(&ToggleReplacePreview, window, cx);
+                            }))
+                            .tooltip({
+                                let focus_handle = focus_handle.clone();
+                                move |window, cx| {
+                                    Tooltip::for_action_in(
+                                        "Preview Replacements",
+                                        &ToggleReplacePreview,
+                                        &focus_handle,
+                                        window,
+                                        cx,
+                                    )
+                                }
+                            }),
+                    )
+                })
+                .when(search.replace_preview.is_some(), |this| {
+                    this.child(
+                        IconButton::new("project-search-accept-all", IconName::Check)
+                            .shape(IconButtonShape::Square)
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                if let Some(search) = this.active_project_search.as_ref() {
+                                    search.update(cx, |this, cx| {
+                                        this.accept_replacements(ReplacementScope::All, cx);
+                                    })
+                                }
+                            }))
+                            .tooltip({
+                                let focus_handle = focus_handle.clone();
+                                move |window, cx| {
+                                    Tooltip::for_action_in(
+                                        "Accept All Replacements",
+                                        &AcceptAllReplacements,
+                                        &focus_handle,
+                                        window,
+                                        cx,
+                                    )
+                                }
+                            }),
+                    )
+                    .child(
+                        IconButton::new("project-search-reject-all", IconName::Close)
+                            .shape(IconButtonShape::Square)
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                if let Some(search) = this.active_project_search.as_ref() {
+                                    search.update(cx, |this, cx| {
+                                        this.reject_replacements(ReplacementScope::All, window, cx);
+                                    })
+                                }
+                            }))
+                            .tooltip({
+                                let focus_handle = focus_handle.clone();
+                                move |window, cx| {
+                                    Tooltip::for_action_in(
+                                        "Reject All Replacements",
+                                        &RejectAllReplacements,
+                                        &focus_handle,
+                                        window,
+                                        cx,
+                                    )
+                                }
+                            }),
+                    )
+                });
 
             h_flex()
                 .w_full()
@@ -2238,7 +2521,7 @@ pub fn perform_project_search(
 
 #[cfg(test)]
 pub mod tests {
-    use std::{ops::Deref as _, sync::Arc};
+    use std::{cell::RefCell, ops::Deref as _, rc::Rc, sync::Arc};
 
     use super::*;
     use editor::{DisplayPoint, display_map::DisplayRow};
@@ -2376,6 +2659,240 @@ pub mod tests {
             .unwrap();
     }
 
+    #[gpui::test]
+    async fn test_replace_preview(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            path!("/dir"),
+            json!({
+                "one.rs": "const ONE: usize = 1;",
+                "two.rs": "const TWO: usize = one::ONE + one::ONE;",
+                "three.rs": "const THREE: usize = one::ONE + two::TWO;",
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+        let window = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let workspace = window.root(cx).unwrap();
+        let search = cx.new(|cx| ProjectSearch::new(project.clone(), cx));
+        let search_view = cx.add_window(|window, cx| {
+            ProjectSearchView::new(workspace.downgrade(), search.clone(), window, cx, None)
+        });
+
+        perform_search(search_view, "ONE", cx);
+        search_view
+            .update(cx, |search_view, window, cx| {
+                search_view.replacement_editor.update(cx, |editor, cx| {
+                    editor.set_text("UNO", window, cx);
+                });
+                search_view.toggle_replace_preview(&ToggleReplacePreview, window, cx);
+            })
+            .unwrap();
+        cx.run_until_parked();
+
+        let buffer_texts = |cx: &mut TestAppContext| {
+            search.read_with(cx, |search, cx| {
+                let mut texts = search
+                    .excerpts
+                    .read(cx)
+                    .all_buffers()
+                    .into_iter()
+                    .map(|buffer| buffer.read(cx).text())
+                    .collect::<Vec<_>>();
+                texts.sort();
+                texts
+            })
+        };
+
+        search_view
+            .update(cx, |search_view, window, cx| {
+                let (replace_preview, _) = search_view
+                    .replace_preview
+                    .as_ref()
+                    .expect("replace preview should be open");
+                replace_preview.update(cx, |replace_preview, cx| {
+                    let hunks = replace_preview.hunks(ReplacementScope::All, cx);
+                    assert_eq!(hunks.len(), 3, "Every file with matches should get a hunk");
+                    let three_hunks = hunks
+                        .into_iter()
+                        .filter(|hunk| {
+                            replace_preview
+                                .editor()
+                                .read(cx)
+                                .buffer()
+                                .read(cx)
+                                .buffer(hunk.buffer_id)
+                                .unwrap()
+                                .read(cx)
+                                .text()
+                                .starts_with("const THREE")
+                        })
+                        .collect::<Vec<_>>();
+                    replace_preview.reject(three_hunks, window, cx);
+                });
+            })
+            .unwrap();
+        cx.run_until_parked();
+        assert_eq!(
+            buffer_texts(cx),
+            [
+                "const ONE: usize = 1;",
+                "const THREE: usize = one::ONE + two::TWO;",
+                "const TWO: usize = one::ONE + one::ONE;",
+            ],
+            "Nothing should be replaced before the replacements are accepted"
+        );
+
+        search_view
+            .update(cx, |search_view, _, cx| {
+                search_view.accept_replacements(ReplacementScope::All, cx);
+            })
+            .unwrap();
+        cx.run_until_parked();
+        assert_eq!(
+            buffer_texts(cx),
+            [
+                "const THREE: usize = one::ONE + two::TWO;",
+                "const TWO: usize = UNO::UNO + UNO::UNO;",
+                "const UNO: usize = 1;",
+            ],
+            "Only the accepted replacements should be applied"
+        );
+        search_view
+            .update(cx, |search_view, _, _| {
+                assert!(
+                    search_view.replace_preview.is_none(),
+                    "Preview should be closed after all replacements are resolved"
+                );
+            })
+            .unwrap();
+
+        search_view
+            .update(cx, |search_view, window, cx| {
+                search_view.results_editor.update(cx, |editor, cx| {
+                    editor.undo(&editor::actions::Undo, window, cx);
+                });
+            })
+            .unwrap();
+        cx.run_until_parked();
+        assert_eq!(
+            buffer_texts(cx),
+            [
+                "const ONE: usize = 1;",
+                "const THREE: usize = one::ONE + two::TWO;",
+                "const TWO: usize = one::ONE + one::ONE;",
+            ],
+            "All accepted replacements should be undone at once"
+        );
+    }
+
+    #[gpui::test]
+    async fn test_replace_preview_key_bindings(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let dispatched = Rc::new(RefCell::new(Vec::<String>::new()));
+        let _subscription = cx.update(|cx| {
+            let dispatched = dispatched.clone();
+            cx.observe_keystrokes(move |event, _, _| {
+                if let Some(action) = event.action.as_ref() {
+                    dispatched.borrow_mut().push(action.name().to_string());
+                }
+            })
+        });
+
+        for (keymap, accept, reject) in [
+            ("keymaps/default-linux.json", "ctrl-y", "ctrl-k ctrl-r"),
+            ("keymaps/default-macos.json", "cmd-y", "cmd-alt-z"),
+        ] {
+            cx.update(|cx| {
+                cx.clear_key_bindings();
+                cx.bind_keys(
+                    settings::KeymapFile::load_asset_allow_partial_failure(keymap, cx).unwrap(),
+                );
+            });
+
+            let fs = FakeFs::new(cx.background_executor.clone());
+            fs.insert_tree(
+                path!("/dir"),
+                json!({
+                    "one.rs": "const ONE: usize = 1;",
+                    "two.rs": "const TWO: usize = one::ONE + one::ONE;",
+                    "three.rs": "const THREE: usize = one::ONE + two::TWO;",
+                }),
+            )
+            .await;
+            let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+            let window =
+                cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+            let workspace = window.root(cx).unwrap();
+            let search = cx.new(|cx| ProjectSearch::new(project.clone(), cx));
+            let search_view = cx.add_window(|window, cx| {
+                ProjectSearchView::new(workspace.downgrade(), search.clone(), window, cx, None)
+            });
+
+            perform_search(search_view, "ONE", cx);
+            search_view
+                .update(cx, |search_view, window, cx| {
+                    search_view.replacement_editor.update(cx, |editor, cx| {
+                        editor.set_text("UNO", window, cx);
+                    });
+                    search_view.toggle_replace_preview(&ToggleReplacePreview, window, cx);
+                })
+                .unwrap();
+            cx.run_until_parked();
+
+            let preview_hunks = |cx: &mut TestAppContext| {
+                search_view
+                    .update(cx, |search_view, window, cx| {
+                        let (replace_preview, _) = search_view
+                            .replace_preview
+                            .as_ref()
+                            .expect("replace preview should be open");
+                        window.focus(&replace_preview.focus_handle(cx));
+                        replace_preview
+                            .read(cx)
+                            .hunks(ReplacementScope::All, cx)
+                            .len()
+                    })
+                    .unwrap()
+            };
+            let replaced_buffers = |cx: &mut TestAppContext| {
+                search.read_with(cx, |search, cx| {
+                    search
+                        .excerpts
+                        .read(cx)
+                        .all_buffers()
+                        .into_iter()
+                        .filter(|buffer| buffer.read(cx).text().contains("UNO"))
+                        .count()
+                })
+            };
+            assert_eq!(preview_hunks(cx), 3, "{keymap}");
+
+            cx.simulate_keystrokes(search_view.into(), accept);
+            cx.run_until_parked();
+            assert_eq!(
+                dispatched.take(),
+                ["project_search::AcceptReplacement"],
+                "{keymap}: `{accept}` should accept the replacement"
+            );
+            assert_eq!(preview_hunks(cx), 2, "{keymap}");
+            assert_eq!(replaced_buffers(cx), 1, "{keymap}");
+
+            cx.simulate_keystrokes(search_view.into(), reject);
+            cx.run_until_parked();
+            assert_eq!(
+                dispatched.take(),
+                ["project_search::RejectReplacement"],
+                "{keymap}: `{reject}` should reject the replacement"
+            );
+            assert_eq!(preview_hunks(cx), 1, "{keymap}");
+            assert_eq!(replaced_buffers(cx), 1, "{keymap}");
+        }
+    }
+
     #[gpui::test]
     async fn test_deploy_project_search_focus(cx: &mut TestAppContext) {
         init_test(cx);
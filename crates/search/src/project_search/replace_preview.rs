@@ -0,0 +1,410 @@
+use std::{ops::Range, sync::Arc};
+
+use buffer_diff::{BufferDiff, BufferDiffEvent, DiffHunkStatus};
+use editor::{
+    Anchor, AnchorRangeExt as _, Editor, MultiBuffer, actions::GoToHunk, scroll::Autoscroll,
+};
+use gpui::{Entity, EventEmitter, FocusHandle, Focusable, Subscription};
+use language::{Buffer, BufferEvent, BufferId, Capability, OffsetRangeExt as _, TransactionId};
+use multi_buffer::{MultiBufferDiffHunk, PathKey};
+use ui::{KeyBinding, Tooltip, prelude::*};
+
+use super::{AcceptReplacement, RejectReplacement};
+
+/// Replacements of the project search matches, shown as diff hunks before they are applied.
+///
+/// Every buffer with matches is previewed by a branch of it with the replacements made, diffed against the buffer
+/// itself. Accepted hunks are merged into the buffers, rejected ones are reverted in the branches. All accepted
+/// hunks are applied in a single transaction of the search results, so that they are undone together.
+pub struct ReplacePreview {
+    results: Entity<MultiBuffer>,
+    multibuffer: Entity<MultiBuffer>,
+    editor: Entity<Editor>,
+    buffers: Vec<PreviewBuffer>,
+    transaction: Option<TransactionId>,
+}
+
+struct PreviewBuffer {
+    base: Entity<Buffer>,
+    branch: Entity<Buffer>,
+    diff: Entity<BufferDiff>,
+    diff_calculated: bool,
+    _subscriptions: [Subscription; 3],
+}
+
+/// Which replacements of the preview an action applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplacementScope {
+    /// The hunks intersecting the selections.
+    Selections,
+    /// All hunks of the files with selections.
+    Files,
+    /// Every hunk of the preview.
+    All,
+}
+
+pub enum ReplacePreviewEvent {
+    /// Every replacement has been either accepted or rejected.
+    Finished,
+}
+
+impl EventEmitter<ReplacePreviewEvent> for ReplacePreview {}
+
+impl ReplacePreview {
+    /// Creates a preview for the replacements given for each buffer of the search `results`.
+    pub fn new(
+        results: Entity<MultiBuffer>,
+        replacements: Vec<(Entity<Buffer>, Vec<(Range<language::Anchor>, String)>)>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let multibuffer = cx.new(|_| MultiBuffer::new(Capability::ReadWrite));
+        let render_diff_hunk_controls = Arc::new({
+            let preview = cx.entity();
+            move |row,
+                  _: &DiffHunkStatus,
+                  hunk_range,
+                  _,
+                  line_height,
+                  editor: &Entity<Editor>,
+                  window: &mut Window,
+                  cx: &mut App| {
+                render_diff_hunk_controls(
+                    row,
+                    hunk_range,
+                    line_height,
+                    &preview,
+                    editor,
+                    window,
+                    cx,
+                )
+            }
+        });
+        let editor = cx.new(|cx| {
+            // Branch buffers share the files of their base buffers, so they are kept away from the project and its language servers.
+            let mut editor = Editor::for_multibuffer(multibuffer.clone(), None, window, cx);
+            editor.disable_inline_diagnostics();
+            editor.set_expand_all_diff_hunks(cx);
+            editor.set_render_diff_hunk_controls(render_diff_hunk_controls, cx);
+            editor.register_addon(ReplacePreviewAddon);
+            editor
+        });
+
+        let mut buffers = Vec::with_capacity(replacements.len());
+        for (base, edits) in replacements {
+            let branch = base.update(cx, |base, cx| base.branch(cx));
+            branch.update(cx, |branch, cx| {
+                branch.edit(edits, None, cx);
+            });
+            let diff = cx.new(|cx| BufferDiff::new(&branch.read(cx).text_snapshot(), cx));
+            multibuffer.update(cx, |multibuffer, cx| multibuffer.add_diff(diff.clone(), cx));
+            buffers.push(PreviewBuffer {
+                _subscriptions: [
+                    cx.subscribe(&base, Self::on_buffer_event),
+                    cx.subscribe(&branch, Self::on_buffer_event),
+                    cx.subscribe_in(&diff, window, Self::on_diff_event),
+                ],
+                base,
+                branch,
+                diff,
+                diff_calculated: false,
+            });
+        }
+
+        let this = Self {
+            results,
+            multibuffer,
+            editor,
+            buffers,
+            transaction: None,
+        };
+        for buffer in &this.buffers {
+            this.recalculate_diff(buffer, cx);
+        }
+        this
+    }
+
+    pub fn editor(&self) -> &Entity<Editor> {
+        &self.editor
+    }
+
+    fn on_buffer_event(
+        &mut self,
+        buffer: Entity<Buffer>,
+        event: &BufferEvent,
+        cx: &mut Context<Self>,
+    ) {
+        if !matches!(event, BufferEvent::Edited) {
+            return;
+        }
+        if let Some(preview_buffer) = self
+            .buffers
+            .iter()
+            .find(|preview_buffer| preview_buffer.base == buffer || preview_buffer.branch == buffer)
+        {
+            self.recalculate_diff(preview_buffer, cx);
+        }
+    }
+
+    fn recalculate_diff(&self, preview_buffer: &PreviewBuffer, cx: &mut Context<Self>) {
+        let base = preview_buffer.base.clone();
+        let branch = preview_buffer.branch.read(cx).text_snapshot();
+        preview_buffer.diff.update(cx, |diff, cx| {
+            let _ = diff.set_base_text_buffer(base, branch, cx);
+        });
+    }
+
+    fn on_diff_event(
+        &mut self,
+        diff: &Entity<BufferDiff>,
+        event: &BufferDiffEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !matches!(event, BufferDiffEvent::DiffChanged { .. }) {
+            return;
+        }
+        let Some(preview_buffer) = self
+            .buffers
+            .iter_mut()
+            .find(|preview_buffer| &preview_buffer.diff == diff)
+        else {
+            return;
+        };
+        preview_buffer.diff_calculated = true;
+
+        let snapshot = preview_buffer.branch.read(cx).snapshot();
+        let hunk_ranges = diff
+            .read(cx)
+            .hunks_intersecting_range(language::Anchor::MIN..language::Anchor::MAX, &snapshot, cx)
+            .map(|hunk| hunk.range)
+            .collect::<Vec<_>>();
+        let path_key = PathKey::for_buffer(&preview_buffer.branch, cx);
+        let branch = preview_buffer.branch.clone();
+        let was_empty = self.multibuffer.update(cx, |multibuffer, cx| {
+            let was_empty = multibuffer.is_empty();
+            if hunk_ranges.is_empty() {
+                multibuffer.remove_excerpts_for_path(path_key, cx);
+            } else {
+                multibuffer.set_excerpts_for_path(
+                    path_key,
+                    branch,
+                    hunk_ranges,
+                    editor::DEFAULT_MULTIBUFFER_CONTEXT,
+                    cx,
+                );
+            }
+            was_empty
+        });
+
+        if self.multibuffer.read(cx).is_empty() {
+            if self
+                .buffers
+                .iter()
+                .all(|preview_buffer| preview_buffer.diff_calculated)
+            {
+                cx.emit(ReplacePreviewEvent::Finished);
+            }
+        } else if was_empty {
+            self.editor.update(cx, |editor, cx| {
+                editor.change_selections(Some(Autoscroll::fit()), window, cx, |selections| {
+                    selections.select_anchor_ranges([Anchor::min()..Anchor::min()]);
+                });
+            });
+        }
+        cx.notify();
+    }
+
+    /// The hunks intersecting the ranges given.
+    fn hunks_in_ranges(&self, ranges: &[Range<Anchor>], cx: &App) -> Vec<MultiBufferDiffHunk> {
+        let snapshot = self.multibuffer.read(cx).snapshot(cx);
+        self.editor
+            .read(cx)
+            .diff_hunks_in_ranges(ranges, &snapshot)
+            .collect()
+    }
+
+    /// The hunks of the scope given.
+    pub fn hunks(&self, scope: ReplacementScope, cx: &App) -> Vec<MultiBufferDiffHunk> {
+        let all_hunks = || self.hunks_in_ranges(&[Anchor::min()..Anchor::max()], cx);
+        let selections = self.editor.read(cx).selections.disjoint_anchors();
+        match scope {
+            ReplacementScope::Selections => {
+                let ranges = selections
+                    .iter()
+                    .map(|selection| selection.range())
+                    .collect::<Vec<_>>();
+                self.hunks_in_ranges(&ranges, cx)
+            }
+            ReplacementScope::Files => {
+                let buffer_ids = selections
+                    .iter()
+                    .filter_map(|selection| selection.head().buffer_id)
+                    .collect::<Vec<BufferId>>();
+                all_hunks()
+                    .into_iter()
+                    .filter(|hunk| buffer_ids.contains(&hunk.buffer_id))
+                    .collect()
+            }
+            ReplacementScope::All => all_hunks(),
+        }
+    }
+
+    /// Applies the hunks given to the buffers searched.
+    pub fn accept(&mut self, hunks: Vec<MultiBufferDiffHunk>, cx: &mut Context<Self>) {
+        let mut ranges_by_branch = Vec::<(Entity<Buffer>, Vec<Range<usize>>)>::new();
+        for hunk in hunks {
+            let Some(branch) = self.multibuffer.read(cx).buffer(hunk.buffer_id) else {
+                continue;
+            };
+            let range = hunk.buffer_range.to_offset(branch.read(cx));
+            match ranges_by_branch
+                .iter_mut()
+                .find(|(buffer, _)| *buffer == branch)
+            {
+                Some((_, ranges)) => ranges.push(range),
+                None => ranges_by_branch.push((branch, vec![range])),
+            }
+        }
+        if ranges_by_branch.is_empty() {
+            return;
+        }
+
+        let transaction = self.results.update(cx, |results, cx| {
+            // Extend the transaction of the previously accepted hunks, unless the search results were edited since.
+            let first_transaction = self
+                .transaction
+                .filter(|transaction| results.last_transaction_id() == Some(*transaction));
+            results.start_transaction(cx);
+            for (branch, ranges) in ranges_by_branch {
+                branch.update(cx, |branch, cx| branch.merge_into_base(ranges, cx));
+            }
+            let transaction = results.end_transaction(cx)?;
+            match first_transaction {
+                Some(first_transaction) if first_transaction != transaction => {
+                    results.merge_transactions(transaction, first_transaction, cx);
+                    Some(first_transaction)
+                }
+                _ => Some(transaction),
+            }
+        });
+        if transaction.is_some() {
+            self.transaction = transaction;
+        }
+    }
+
+    /// Drops the hunks given from the preview, leaving the buffers searched as they are.
+    pub fn reject(
+        &mut self,
+        hunks: Vec<MultiBufferDiffHunk>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let snapshot = self.multibuffer.read(cx).snapshot(cx);
+        let ranges = hunks
+            .iter()
+            .map(|hunk| hunk.multi_buffer_range().to_point(&snapshot))
+            .collect::<Vec<_>>();
+        if ranges.is_empty() {
+            return;
+        }
+        self.editor.update(cx, |editor, cx| {
+            editor.restore_hunks_in_ranges(ranges, window, cx);
+        });
+    }
+}
+
+struct ReplacePreviewAddon;
+
+impl editor::Addon for ReplacePreviewAddon {
+    fn to_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn extend_key_context(&self, key_context: &mut gpui::KeyContext, _: &App) {
+        key_context.add("replace_preview");
+    }
+}
+
+impl Focusable for ReplacePreview {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+fn render_diff_hunk_controls(
+    row: u32,
+    hunk_range: Range<Anchor>,
+    line_height: Pixels,
+    preview: &Entity<ReplacePreview>,
+    editor: &Entity<Editor>,
+    window: &mut Window,
+    cx: &mut App,
+) -> AnyElement {
+    let focus_handle = editor.read(cx).focus_handle(cx);
+    h_flex()
+        .h(line_height)
+        .mr_0p5()
+        .gap_1()
+        .px_0p5()
+        .pb_1()
+        .border_x_1()
+        .border_b_1()
+        .border_color(cx.theme().colors().border)
+        .rounded_b_md()
+        .bg(cx.theme().colors().editor_background)
+        .occlude()
+        .shadow_md()
+        .child(
+            Button::new(("reject", row as u64), "Reject")
+                .key_binding(
+                    KeyBinding::for_action_in(&RejectReplacement, &focus_handle, window, cx)
+                        .map(|kb| kb.size(rems_from_px(12.))),
+                )
+                .on_click({
+                    let preview = preview.clone();
+                    let hunk_range = hunk_range.clone();
+                    move |_event, window, cx| {
+                        preview.update(cx, |preview, cx| {
+                            let hunks =
+                                preview.hunks_in_ranges(&[hunk_range.start..hunk_range.start], cx);
+                            preview.reject(hunks, window, cx);
+                        });
+                    }
+                }),
+        )
+        .child(
+            Button::new(("accept", row as u64), "Accept")
+                .key_binding(
+                    KeyBinding::for_action_in(&AcceptReplacement, &focus_handle, window, cx)
+                        .map(|kb| kb.size(rems_from_px(12.))),
+                )
+                .on_click({
+                    let preview = preview.clone();
+                    move |_event, _window, cx| {
+                        preview.update(cx, |preview, cx| {
+                            let hunks =
+                                preview.hunks_in_ranges(&[hunk_range.start..hunk_range.start], cx);
+                            preview.accept(hunks, cx);
+                        });
+                    }
+                }),
+        )
+        .child(
+            IconButton::new(("next-hunk", row as u64), IconName::ArrowDown)
+                .shape(ui::IconButtonShape::Square)
+                .icon_size(IconSize::Small)
+                .tooltip(move |window, cx| {
+                    Tooltip::for_action_in("Next Hunk", &GoToHunk, &focus_handle, window, cx)
+                })
+                .on_click({
+                    let editor = editor.clone();
+                    move |_event, window, cx| {
+                        editor.update(cx, |editor, cx| {
+                            editor.go_to_next_hunk(&GoToHunk, window, cx);
+                        });
+                    }
+                }),
+        )
+        .into_any_element()
+}
@@ -2,7 +2,9 @@
 #![deny(missing_docs)]
 
 mod debug_format;
+mod problem_matcher;
 pub mod static_source;
+mod task_graph;
 mod task_template;
 mod vscode_format;
 
@@ -18,9 +20,15 @@ pub use debug_format::{
     AttachConfig, DebugAdapterConfig, DebugConnectionType, DebugRequestDisposition,
     DebugRequestType, DebugTaskDefinition, DebugTaskFile, LaunchConfig, TCPHost,
 };
+pub use problem_matcher::{
+    BackgroundMatcher, BackgroundPattern, FileLocation, FileLocationKind, ProblemMatcher,
+    ProblemMatcherDefinition, ProblemMatcherParser, ProblemPattern, ProblemPatterns,
+    ProblemSeverity, TaskProblem,
+};
+pub use task_graph::{TaskGraphNode, TaskGraphNodeStatus, TaskGraphStatus};
 pub use task_template::{
-    DebugArgs, DebugArgsRequest, HideStrategy, RevealStrategy, TaskModal, TaskTemplate,
-    TaskTemplates, TaskType,
+    DebugArgs, DebugArgsRequest, DependsOrder, HideStrategy, RevealStrategy, TaskModal,
+    TaskTemplate, TaskTemplates, TaskType,
 };
 pub use vscode_format::VsCodeTaskFile;
 pub use zed_actions::RevealTarget;
@@ -68,6 +76,12 @@ pub struct SpawnInTerminal {
     pub show_command: bool,
     /// Whether to show the rerun button in the terminal tab.
     pub show_rerun: bool,
+    /// Tasks to run before this one, resolved along with their own dependencies.
+    pub dependencies: Vec<SpawnInTerminal>,
+    /// Whether to run the `dependencies` all at once or one after another.
+    pub depends_order: DependsOrder,
+    /// Problem matchers to find diagnostics in the task output with.
+    pub problem_matchers: Vec<ProblemMatcher>,
 }
 
 /// A final form of the [`TaskTemplate`], that got resolved with a particular [`TaskContext`] and now is ready to spawn the actual task.
@@ -81,6 +95,8 @@ pub struct ResolvedTask {
     pub id: TaskId,
     /// A template the task got resolved from.
     original_task: TaskTemplate,
+    /// A context the task got resolved with, reused to resolve the task's dependencies.
+    task_context: TaskContext,
     /// Full, unshortened label of the task after all resolutions are made.
     pub resolved_label: String,
     /// Variables that were substituted during the task template resolution.
@@ -151,6 +167,11 @@ impl ResolvedTask {
         }
     }
 
+    /// Resolves a template this task depends on with the same [`TaskContext`] this task was resolved with.
+    pub fn resolve_dependency(&self, dependency: &TaskTemplate, id_base: &str) -> Option<Self> {
+        dependency.resolve_task(id_base, &self.task_context)
+    }
+
     /// Variables that were substituted during the task template resolution.
     pub fn substituted_variables(&self) -> &HashSet<VariableName> {
         &self.substituted_variables
@@ -0,0 +1,887 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context as _, bail};
+use regex::{Captures, Regex};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use util::ResultExt;
+
+/// A way to find problems in the task's output: either a name of a built-in matcher (e.g. `$rustc`), or a custom one.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum ProblemMatcherDefinition {
+    /// A name of a built-in problem matcher:
+    /// `$rustc`, `$tsc`, `$tsc-watch`, `$eslint-compact`, `$eslint-stylish`, `$gcc` or `$go`.
+    Named(String),
+    /// A problem matcher defined by the user.
+    Custom(ProblemMatcher),
+}
+
+impl ProblemMatcherDefinition {
+    /// Turns the definition into a matcher ready to use: looks up the built-in matchers and applies the `base` one, if any.
+    pub fn resolve(&self) -> anyhow::Result<ProblemMatcher> {
+        match self {
+            Self::Named(name) => builtin_problem_matcher(name)
+                .with_context(|| format!("Unknown problem matcher `{name}`")),
+            Self::Custom(matcher) => {
+                let Some(base) = &matcher.base else {
+                    if matcher.patterns().is_empty() {
+                        bail!("Problem matcher `{}` has no `pattern`", matcher.owner);
+                    }
+                    return Ok(matcher.clone());
+                };
+                let base = builtin_problem_matcher(base)
+                    .with_context(|| format!("Unknown base problem matcher `{base}`"))?;
+                Ok(ProblemMatcher {
+                    owner: if matcher.owner.is_empty() {
+                        base.owner
+                    } else {
+                        matcher.owner.clone()
+                    },
+                    source: matcher.source.clone().or(base.source),
+                    base: None,
+                    file_location: matcher.file_location.clone().or(base.file_location),
+                    severity: matcher.severity.or(base.severity),
+                    pattern: matcher.pattern.clone().or(base.pattern),
+                    background: matcher.background.clone().or(base.background),
+                })
+            }
+        }
+    }
+}
+
+/// A matcher that finds problems in the task output, compatible with VS Code's `problemMatcher`.
+#[derive(Clone, Default, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ProblemMatcher {
+    /// A name of the matcher, the problems found are grouped under it.
+    #[serde(default)]
+    pub owner: String,
+    /// A name to show as the source of the problems found, e.g. `rustc`.
+    #[serde(default)]
+    pub source: Option<String>,
+    /// A name of a built-in problem matcher to take the fields not set in this one from, e.g. `$tsc`.
+    #[serde(default)]
+    pub base: Option<String>,
+    /// How to resolve file paths found in the output:
+    /// * `relative` — relative to the task's working directory (default)
+    /// * `absolute` — the paths are absolute already
+    /// * `auto_detect` — use absolute paths as is, resolve the rest as relative ones
+    ///
+    /// A base directory to resolve the paths against may be given as the second array element: `["relative", "$ZED_WORKTREE_ROOT"]`.
+    #[serde(default, alias = "fileLocation")]
+    pub file_location: Option<FileLocation>,
+    /// Severity of the problems found, used when the pattern does not capture one; `error` by default.
+    #[serde(default)]
+    pub severity: Option<ProblemSeverity>,
+    /// A pattern to match a problem with, or a list of patterns to match consecutive lines of a multi-line problem with.
+    #[serde(default)]
+    pub pattern: Option<ProblemPatterns>,
+    /// Patterns that mark the start and the end of a compilation cycle in a long-running (watching) task.
+    /// Problems found during a cycle replace the ones found during the previous cycle.
+    #[serde(default, alias = "watching")]
+    pub background: Option<BackgroundMatcher>,
+}
+
+impl ProblemMatcher {
+    /// Patterns to match the output lines with, in order.
+    pub fn patterns(&self) -> &[ProblemPattern] {
+        match &self.pattern {
+            Some(ProblemPatterns::Single(pattern)) => std::slice::from_ref(pattern),
+            Some(ProblemPatterns::Multiple(patterns)) => patterns,
+            None => &[],
+        }
+    }
+}
+
+/// One or several patterns of a [`ProblemMatcher`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum ProblemPatterns {
+    /// A pattern matching a single line problem.
+    Single(ProblemPattern),
+    /// Patterns matching consecutive lines of a multi-line problem.
+    Multiple(Vec<ProblemPattern>),
+}
+
+/// A regular expression to match an output line with, and the numbers of its capture groups to take the problem data from.
+#[derive(Clone, Default, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ProblemPattern {
+    /// A regular expression to match a line of the output with.
+    pub regexp: String,
+    /// A group with the file path.
+    #[serde(default)]
+    pub file: Option<usize>,
+    /// A group with the problem location, as `line`, `line,column` or `line,column,end_line,end_column`.
+    #[serde(default)]
+    pub location: Option<usize>,
+    /// A group with the 1-based line number.
+    #[serde(default)]
+    pub line: Option<usize>,
+    /// A group with the 1-based column number.
+    #[serde(default)]
+    pub column: Option<usize>,
+    /// A group with the 1-based end line number.
+    #[serde(default, alias = "endLine")]
+    pub end_line: Option<usize>,
+    /// A group with the 1-based end column number.
+    #[serde(default, alias = "endColumn")]
+    pub end_column: Option<usize>,
+    /// A group with the problem severity: `error`, `warning`, `info` or `hint`.
+    #[serde(default)]
+    pub severity: Option<usize>,
+    /// A group with the problem code.
+    #[serde(default)]
+    pub code: Option<usize>,
+    /// A group with the problem message.
+    #[serde(default)]
+    pub message: Option<usize>,
+    /// Only for the last pattern of a multi-line matcher: whether to keep matching it on the lines that follow,
+    /// reporting a new problem for each of them.
+    #[serde(default, rename = "loop")]
+    pub repeat: bool,
+}
+
+/// How to resolve file paths found in the task output.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum FileLocation {
+    /// Resolve the paths against the task's working directory.
+    Kind(FileLocationKind),
+    /// Resolve the paths against the directory given.
+    WithBase(FileLocationKind, String),
+}
+
+/// A kind of the file paths found in the task output.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FileLocationKind {
+    /// Paths are absolute.
+    Absolute,
+    /// Paths are relative.
+    #[default]
+    Relative,
+    /// Paths may be either absolute or relative.
+    #[serde(alias = "autoDetect")]
+    AutoDetect,
+}
+
+/// Patterns that mark a compilation cycle of a long-running task.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct BackgroundMatcher {
+    /// Whether a cycle is considered started when the task starts, before the `begins_pattern` is matched.
+    #[serde(default, alias = "activeBegins")]
+    pub active_begins: bool,
+    /// A line that starts a new cycle.
+    #[serde(alias = "beginsPattern")]
+    pub begins_pattern: BackgroundPattern,
+    /// A line that finishes the current cycle.
+    #[serde(alias = "endsPattern")]
+    pub ends_pattern: BackgroundPattern,
+}
+
+/// A regular expression to match a cycle boundary with.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum BackgroundPattern {
+    /// A bare regular expression.
+    Regexp(String),
+    /// A regular expression in an object, as VS Code allows.
+    Pattern {
+        /// A regular expression to match a line of the output with.
+        regexp: String,
+    },
+}
+
+impl BackgroundPattern {
+    fn regexp(&self) -> &str {
+        match self {
+            Self::Regexp(regexp) | Self::Pattern { regexp } => regexp,
+        }
+    }
+}
+
+/// Severity of a problem found in the task output.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProblemSeverity {
+    /// An error.
+    #[default]
+    Error,
+    /// A warning.
+    Warning,
+    /// An informational message.
+    Info,
+    /// A hint.
+    Hint,
+}
+
+impl ProblemSeverity {
+    fn parse(severity: &str) -> Option<Self> {
+        match severity.trim().to_lowercase().as_str() {
+            "error" | "err" | "fatal" | "fatal error" => Some(Self::Error),
+            "warning" | "warn" => Some(Self::Warning),
+            "info" | "information" | "note" => Some(Self::Info),
+            "hint" | "help" => Some(Self::Hint),
+            _ => None,
+        }
+    }
+}
+
+/// A problem found in the task output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TaskProblem {
+    /// A name of the matcher that found the problem.
+    pub owner: String,
+    /// A path of the file with the problem; absolute, unless it could not be resolved.
+    pub path: PathBuf,
+    /// 0-based line the problem starts at.
+    pub start_line: u32,
+    /// 0-based column the problem starts at.
+    pub start_column: u32,
+    /// 0-based line the problem ends at.
+    pub end_line: u32,
+    /// 0-based column the problem ends at; [`u32::MAX`] if the problem spans till the end of the line.
+    pub end_column: u32,
+    /// Severity of the problem.
+    pub severity: ProblemSeverity,
+    /// Code of the problem, e.g. `E0308`.
+    pub code: Option<String>,
+    /// Message describing the problem.
+    pub message: String,
+    /// A name to show as the source of the problem.
+    pub source: Option<String>,
+}
+
+/// Finds problems in the task output, fed to it line by line.
+pub struct ProblemMatcherParser {
+    matchers: Vec<CompiledProblemMatcher>,
+}
+
+struct CompiledProblemMatcher {
+    matcher: ProblemMatcher,
+    patterns: Vec<(Regex, ProblemPattern)>,
+    background: Option<(Regex, Regex)>,
+    base_dir: Option<PathBuf>,
+    /// The next pattern to match, with the data of the problem matched so far.
+    partial_match: Option<(usize, ProblemData)>,
+    cycle_active: bool,
+    /// Whether the end of a cycle has been matched at least once.
+    cycle_finished: bool,
+    cycle_problems: Vec<TaskProblem>,
+    problems: Vec<TaskProblem>,
+}
+
+#[derive(Clone, Default)]
+struct ProblemData {
+    file: Option<String>,
+    line: Option<u32>,
+    column: Option<u32>,
+    end_line: Option<u32>,
+    end_column: Option<u32>,
+    severity: Option<String>,
+    code: Option<String>,
+    message: Option<String>,
+}
+
+impl ProblemMatcherParser {
+    /// Prepares the matchers given to parse the output of the task running in `cwd`.
+    /// Matchers with invalid regular expressions are logged and skipped.
+    pub fn new(matchers: &[ProblemMatcher], cwd: Option<&Path>) -> Self {
+        let matchers = matchers
+            .iter()
+            .filter_map(|matcher| CompiledProblemMatcher::new(matcher, cwd).log_err())
+            .collect();
+        Self { matchers }
+    }
+
+    /// Matches the next line of the output, returns whether the problems found have changed.
+    pub fn process_line(&mut self, line: &str) -> bool {
+        let line = line.trim_end();
+        let mut changed = false;
+        for matcher in &mut self.matchers {
+            changed |= matcher.process_line(line);
+        }
+        changed
+    }
+
+    /// Whether the task is a background one, e.g. watching for changes, that has finished its first cycle:
+    /// all matchers with background patterns have matched the end of a cycle.
+    pub fn is_ready(&self) -> bool {
+        let mut background_matchers = self
+            .matchers
+            .iter()
+            .filter(|matcher| matcher.background.is_some())
+            .peekable();
+        background_matchers.peek().is_some()
+            && background_matchers.all(|matcher| matcher.cycle_finished)
+    }
+
+    /// All problems found so far.
+    pub fn problems(&self) -> Vec<TaskProblem> {
+        self.matchers
+            .iter()
+            .flat_map(|matcher| matcher.problems.iter().cloned())
+            .collect()
+    }
+}
+
+impl CompiledProblemMatcher {
+    fn new(matcher: &ProblemMatcher, cwd: Option<&Path>) -> anyhow::Result<Self> {
+        let patterns = matcher
+            .patterns()
+            .iter()
+            .map(|pattern| {
+                let regex = Regex::new(&pattern.regexp).with_context(|| {
+                    format!(
+                        "Invalid pattern `{}` of problem matcher `{}`",
+                        pattern.regexp, matcher.owner
+                    )
+                })?;
+                anyhow::Ok((regex, pattern.clone()))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        anyhow::ensure!(
+            !patterns.is_empty(),
+            "Problem matcher `{}` has no `pattern`",
+            matcher.owner
+        );
+        let background = match &matcher.background {
+            Some(background) => Some((
+                Regex::new(background.begins_pattern.regexp())
+                    .context("Invalid `begins_pattern` of a problem matcher")?,
+                Regex::new(background.ends_pattern.regexp())
+                    .context("Invalid `ends_pattern` of a problem matcher")?,
+            )),
+            None => None,
+        };
+        let base_dir = match &matcher.file_location {
+            Some(FileLocation::WithBase(_, base_dir)) => Some(PathBuf::from(base_dir)),
+            _ => cwd.map(Path::to_path_buf),
+        };
+        Ok(Self {
+            cycle_active: matcher
+                .background
+                .as_ref()
+                .is_some_and(|background| background.active_begins),
+            matcher: matcher.clone(),
+            patterns,
+            background,
+            base_dir,
+            partial_match: None,
+            cycle_finished: false,
+            cycle_problems: Vec::new(),
+            problems: Vec::new(),
+        })
+    }
+
+    fn process_line(&mut self, line: &str) -> bool {
+        if let Some((begins, ends)) = &self.background {
+            if begins.is_match(line) {
+                self.cycle_active = true;
+                self.cycle_problems.clear();
+                self.partial_match = None;
+                return false;
+            }
+            if ends.is_match(line) {
+                self.cycle_active = false;
+                self.cycle_finished = true;
+                self.partial_match = None;
+                self.problems = std::mem::take(&mut self.cycle_problems);
+                return true;
+            }
+        }
+
+        if let Some((index, data)) = self.partial_match.take() {
+            let (regex, pattern) = &self.patterns[index];
+            if let Some(captures) = regex.captures(line) {
+                let mut matched_data = data.clone();
+                matched_data.fill(pattern, &captures);
+                if index + 1 < self.patterns.len() {
+                    self.partial_match = Some((index + 1, matched_data));
+                    return false;
+                }
+                if pattern.repeat {
+                    self.partial_match = Some((index, data));
+                }
+                return self.report(matched_data);
+            }
+        }
+
+        let (regex, pattern) = &self.patterns[0];
+        let Some(captures) = regex.captures(line) else {
+            return false;
+        };
+        let mut data = ProblemData::default();
+        data.fill(pattern, &captures);
+        if self.patterns.len() > 1 {
+            self.partial_match = Some((1, data));
+            false
+        } else {
+            self.report(data)
+        }
+    }
+
+    fn report(&mut self, data: ProblemData) -> bool {
+        let Some(problem) = self.problem(data) else {
+            return false;
+        };
+        if self.background.is_some() && self.cycle_active {
+            self.cycle_problems.push(problem);
+            false
+        } else {
+            self.problems.push(problem);
+            true
+        }
+    }
+
+    fn problem(&self, data: ProblemData) -> Option<TaskProblem> {
+        let file = data.file?;
+        let message = data.message?;
+        let line = data.line?.max(1);
+        let path = PathBuf::from(file.trim());
+        let kind = match &self.matcher.file_location {
+            Some(FileLocation::Kind(kind) | FileLocation::WithBase(kind, _)) => *kind,
+            None => FileLocationKind::default(),
+        };
+        let path = match (kind, &self.base_dir) {
+            (FileLocationKind::Absolute, _) => path,
+            (FileLocationKind::AutoDetect, _) if path.is_absolute() => path,
+            (_, Some(base_dir)) => base_dir.join(path),
+            (_, None) => path,
+        };
+        let start_column = data.column.map_or(0, |column| column.saturating_sub(1));
+        let end_line = data.end_line.unwrap_or(line).max(line);
+        let end_column = match (data.end_column, data.column) {
+            (Some(end_column), _) => end_column.saturating_sub(1),
+            (None, Some(_)) => start_column,
+            (None, None) => u32::MAX,
+        };
+        let severity = data
+            .severity
+            .as_deref()
+            .and_then(ProblemSeverity::parse)
+            .or(self.matcher.severity)
+            .unwrap_or_default();
+        Some(TaskProblem {
+            owner: self.matcher.owner.clone(),
+            path,
+            start_line: line - 1,
+            start_column,
+            end_line: end_line - 1,
+            end_column,
+            severity,
+            code: data.code,
+            message,
+            source: self.matcher.source.clone(),
+        })
+    }
+}
+
+impl ProblemData {
+    fn fill(&mut self, pattern: &ProblemPattern, captures: &Captures) {
+        let group = |index: Option<usize>| {
+            captures
+                .get(index?)
+                .map(|group| group.as_str())
+                .filter(|group| !group.is_empty())
+        };
+        let number = |index: Option<usize>| group(index)?.parse::<u32>().ok();
+
+        if let Some(file) = group(pattern.file) {
+            self.file = Some(file.to_string());
+        }
+        if let Some(location) = group(pattern.location) {
+            let numbers = location
+                .split(|c: char| !c.is_ascii_digit())
+                .filter_map(|number| number.parse::<u32>().ok())
+                .collect::<Vec<_>>();
+            match numbers.as_slice() {
+                [line] => self.line = Some(*line),
+                [line, column] | [line, column, _] => {
+                    self.line = Some(*line);
+                    self.column = Some(*column);
+                }
+                [line, column, end_line, end_column, ..] => {
+                    self.line = Some(*line);
+                    self.column = Some(*column);
+                    self.end_line = Some(*end_line);
+                    self.end_column = Some(*end_column);
+                }
+                [] => {}
+            }
+        }
+        if let Some(line) = number(pattern.line) {
+            self.line = Some(line);
+        }
+        if let Some(column) = number(pattern.column) {
+            self.column = Some(column);
+        }
+        if let Some(end_line) = number(pattern.end_line) {
+            self.end_line = Some(end_line);
+        }
+        if let Some(end_column) = number(pattern.end_column) {
+            self.end_column = Some(end_column);
+        }
+        if let Some(severity) = group(pattern.severity) {
+            self.severity = Some(severity.to_string());
+        }
+        if let Some(code) = group(pattern.code) {
+            self.code = Some(code.to_string());
+        }
+        if let Some(message) = group(pattern.message) {
+            self.message = Some(message.to_string());
+        }
+    }
+}
+
+fn builtin_problem_matcher(name: &str) -> Option<ProblemMatcher> {
+    let matcher = |owner: &str, file_location: FileLocationKind, patterns: Vec<ProblemPattern>| {
+        ProblemMatcher {
+            owner: owner.to_string(),
+            source: Some(owner.to_string()),
+            base: None,
+            file_location: Some(FileLocation::Kind(file_location)),
+            severity: None,
+            pattern: Some(ProblemPatterns::Multiple(patterns)),
+            background: None,
+        }
+    };
+    let tsc_pattern = ProblemPattern {
+        regexp: r"^([^\s].*)[\(:](\d+)[,:](\d+)(?:\):\s+|\s+-\s+)(error|warning|info)\s+TS(\d+)\s*:\s*(.*)$".to_string(),
+        file: Some(1),
+        line: Some(2),
+        column: Some(3),
+        severity: Some(4),
+        code: Some(5),
+        message: Some(6),
+        ..ProblemPattern::default()
+    };
+    let matcher = match name {
+        "$rustc" => matcher(
+            "rustc",
+            FileLocationKind::AutoDetect,
+            vec![
+                ProblemPattern {
+                    regexp: r"^(warning|error)(?:\[(\w+)\])?: (.*)$".to_string(),
+                    severity: Some(1),
+                    code: Some(2),
+                    message: Some(3),
+                    ..ProblemPattern::default()
+                },
+                ProblemPattern {
+                    regexp: r"^\s*-->\s+(.+?):(\d+):(\d+)$".to_string(),
+                    file: Some(1),
+                    line: Some(2),
+                    column: Some(3),
+                    ..ProblemPattern::default()
+                },
+            ],
+        ),
+        "$tsc" => matcher("tsc", FileLocationKind::Relative, vec![tsc_pattern]),
+        "$tsc-watch" => ProblemMatcher {
+            background: Some(BackgroundMatcher {
+                active_begins: true,
+                begins_pattern: BackgroundPattern::Regexp(
+                    r"(?:Starting compilation in watch mode|File change detected\. Starting incremental compilation)\.\.\.".to_string(),
+                ),
+                ends_pattern: BackgroundPattern::Regexp(
+                    r"(?:Compilation complete\.|Found \d+ errors?\.) Watching for file changes\."
+                        .to_string(),
+                ),
+            }),
+            ..matcher("tsc", FileLocationKind::Relative, vec![tsc_pattern])
+        },
+        "$eslint-compact" => matcher(
+            "eslint",
+            FileLocationKind::AutoDetect,
+            vec![ProblemPattern {
+                regexp: r"^(.+):\sline\s(\d+),\scol\s(\d+),\s(Error|Warning|Info)\s-\s(.+)\s\((.+)\)$"
+                    .to_string(),
+                file: Some(1),
+                line: Some(2),
+                column: Some(3),
+                severity: Some(4),
+                message: Some(5),
+                code: Some(6),
+                ..ProblemPattern::default()
+            }],
+        ),
+        "$eslint-stylish" => matcher(
+            "eslint",
+            FileLocationKind::AutoDetect,
+            vec![
+                ProblemPattern {
+                    regexp: r"^([^\s].*)$".to_string(),
+                    file: Some(1),
+                    ..ProblemPattern::default()
+                },
+                ProblemPattern {
+                    regexp: r"^\s+(\d+):(\d+)\s+(error|warning|info)\s+(.*?)(?:\s\s+(.*))?$"
+                        .to_string(),
+                    line: Some(1),
+                    column: Some(2),
+                    severity: Some(3),
+                    message: Some(4),
+                    code: Some(5),
+                    repeat: true,
+                    ..ProblemPattern::default()
+                },
+            ],
+        ),
+        "$gcc" => matcher(
+            "gcc",
+            FileLocationKind::AutoDetect,
+            vec![ProblemPattern {
+                regexp: r"^(.*?):(\d+):(\d*):?\s+(?:fatal\s+)?(warning|error):\s+(.*)$".to_string(),
+                file: Some(1),
+                line: Some(2),
+                column: Some(3),
+                severity: Some(4),
+                message: Some(5),
+                ..ProblemPattern::default()
+            }],
+        ),
+        "$go" => matcher(
+            "go",
+            FileLocationKind::AutoDetect,
+            vec![ProblemPattern {
+                regexp: r"^([^:\s]+\.go):(\d+):(?:(\d+):)?\s*(.*)$".to_string(),
+                file: Some(1),
+                line: Some(2),
+                column: Some(3),
+                message: Some(4),
+                ..ProblemPattern::default()
+            }],
+        ),
+        _ => return None,
+    };
+    Some(matcher)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(matcher: &str, output: &str) -> Vec<TaskProblem> {
+        let matcher = ProblemMatcherDefinition::Named(matcher.to_string())
+            .resolve()
+            .unwrap();
+        let mut parser = ProblemMatcherParser::new(&[matcher], Some(Path::new("/project")));
+        for line in output.lines() {
+            parser.process_line(line);
+        }
+        parser.problems()
+    }
+
+    fn summary(problems: &[TaskProblem]) -> Vec<(String, (u32, u32), ProblemSeverity, &str)> {
+        problems
+            .iter()
+            .map(|problem| {
+                (
+                    problem.path.to_string_lossy().into_owned(),
+                    (problem.start_line, problem.start_column),
+                    problem.severity,
+                    problem.message.as_str(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_builtin_problem_matchers() {
+        let problems = parse(
+            "$rustc",
+            r#"   Compiling foo v0.1.0 (/project)
+warning: unused variable: `x`
+ --> src/main.rs:2:9
+  |
+2 |     let x = 5;
+  |         ^ help: if this is intentional, prefix it with an underscore: `_x`
+error[E0308]: mismatched types
+  --> src/lib.rs:10:18
+warning: `foo` (bin "foo") generated 1 warning
+error: could not compile `foo` (bin "foo") due to 1 previous error"#,
+        );
+        assert_eq!(
+            summary(&problems),
+            vec![
+                (
+                    "/project/src/main.rs".to_string(),
+                    (1, 8),
+                    ProblemSeverity::Warning,
+                    "unused variable: `x`"
+                ),
+                (
+                    "/project/src/lib.rs".to_string(),
+                    (9, 17),
+                    ProblemSeverity::Error,
+                    "mismatched types"
+                ),
+            ]
+        );
+        assert_eq!(problems[1].code.as_deref(), Some("E0308"));
+        assert_eq!(problems[1].source.as_deref(), Some("rustc"));
+
+        let problems = parse(
+            "$eslint-stylish",
+            r#"/project/src/index.js
+  1:10  error    'foo' is defined but never used  no-unused-vars
+  3:1   warning  Unexpected console statement     no-console
+
+✖ 2 problems (1 error, 1 warning)"#,
+        );
+        assert_eq!(
+            summary(&problems),
+            vec![
+                (
+                    "/project/src/index.js".to_string(),
+                    (0, 9),
+                    ProblemSeverity::Error,
+                    "'foo' is defined but never used"
+                ),
+                (
+                    "/project/src/index.js".to_string(),
+                    (2, 0),
+                    ProblemSeverity::Warning,
+                    "Unexpected console statement"
+                ),
+            ],
+            "Looping pattern should report a problem per matching line"
+        );
+        assert_eq!(problems[1].code.as_deref(), Some("no-console"));
+
+        let problems = parse(
+            "$gcc",
+            "main.c:3:5: error: unknown type name 'foo'\nmain.c:7: warning: no newline",
+        );
+        assert_eq!(
+            summary(&problems),
+            vec![
+                (
+                    "/project/main.c".to_string(),
+                    (2, 4),
+                    ProblemSeverity::Error,
+                    "unknown type name 'foo'"
+                ),
+                (
+                    "/project/main.c".to_string(),
+                    (6, 0),
+                    ProblemSeverity::Warning,
+                    "no newline"
+                ),
+            ]
+        );
+        assert_eq!(
+            problems[1].end_column,
+            u32::MAX,
+            "Problems without a column should span the whole line"
+        );
+
+        assert!(
+            ProblemMatcherDefinition::Named("$unknown".to_string())
+                .resolve()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_custom_problem_matcher() {
+        let matcher = serde_json::from_str::<ProblemMatcherDefinition>(
+            r#"{
+                "owner": "lint",
+                "fileLocation": ["relative", "/project/web"],
+                "severity": "warning",
+                "pattern": {
+                    "regexp": "^(.*):(\\d+,\\d+,\\d+,\\d+) (.*)$",
+                    "file": 1,
+                    "location": 2,
+                    "message": 3
+                }
+            }"#,
+        )
+        .unwrap()
+        .resolve()
+        .unwrap();
+        let mut parser = ProblemMatcherParser::new(&[matcher], Some(Path::new("/project")));
+        assert!(!parser.process_line("all good"));
+        assert!(parser.process_line("app.ts:2,3,4,5 something is off"));
+        assert!(
+            !parser.is_ready(),
+            "Tasks without background patterns are never ready"
+        );
+        assert_eq!(
+            parser.problems(),
+            vec![TaskProblem {
+                owner: "lint".to_string(),
+                path: PathBuf::from("/project/web/app.ts"),
+                start_line: 1,
+                start_column: 2,
+                end_line: 3,
+                end_column: 4,
+                severity: ProblemSeverity::Warning,
+                code: None,
+                message: "something is off".to_string(),
+                source: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_background_problem_matcher() {
+        let matcher = serde_json::from_str::<ProblemMatcherDefinition>(
+            r#"{
+                "base": "$tsc",
+                "background": {
+                    "activeBegins": true,
+                    "beginsPattern": "Starting compilation",
+                    "endsPattern": { "regexp": "Watching for file changes" }
+                }
+            }"#,
+        )
+        .unwrap()
+        .resolve()
+        .unwrap();
+        assert_eq!(matcher.owner, "tsc");
+        let mut parser = ProblemMatcherParser::new(&[matcher], Some(Path::new("/project")));
+
+        assert!(!parser.process_line("Starting compilation in watch mode..."));
+        assert!(!parser.process_line(
+            "src/a.ts(1,7): error TS2322: Type 'string' is not assignable to type 'number'."
+        ));
+        assert!(
+            parser.problems().is_empty(),
+            "Problems should not be reported until the cycle ends"
+        );
+        assert!(!parser.is_ready());
+        assert!(parser.process_line("Found 1 error. Watching for file changes."));
+        assert!(
+            parser.is_ready(),
+            "The task should be ready after its first cycle"
+        );
+        assert_eq!(
+            summary(&parser.problems()),
+            vec![(
+                "/project/src/a.ts".to_string(),
+                (0, 6),
+                ProblemSeverity::Error,
+                "Type 'string' is not assignable to type 'number'."
+            )]
+        );
+        assert_eq!(parser.problems()[0].code.as_deref(), Some("2322"));
+
+        assert!(!parser.process_line("File change detected. Starting compilation..."));
+        assert_eq!(
+            parser.problems().len(),
+            1,
+            "Previous cycle problems should be kept while the new cycle runs"
+        );
+        assert!(parser.process_line("Found 0 errors. Watching for file changes."));
+        assert!(
+            parser.problems().is_empty(),
+            "A new cycle should replace the problems of the previous one"
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use crate::{SpawnInTerminal, TaskId};
+
+/// A state of a single task, spawned as a part of a task with dependencies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskGraphNodeStatus {
+    /// The task waits for its dependencies to finish.
+    Pending,
+    /// The task is started and running currently.
+    Running,
+    /// The task has finished successfully.
+    Succeeded,
+    /// The task has failed, or did not report its exit code.
+    Failed,
+    /// The task was never started, as some of the tasks it depends on have failed.
+    Skipped,
+}
+
+/// A single task of a [`TaskGraphStatus`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskGraphNode {
+    /// Id of the task.
+    pub id: TaskId,
+    /// Human readable name of the task.
+    pub label: String,
+    /// How far the task is from the one spawned: its direct dependencies have depth 1, theirs have depth 2, etc.
+    pub depth: usize,
+    /// The current state of the task.
+    pub status: TaskGraphNodeStatus,
+}
+
+/// Progress of a task with dependencies: the task itself and every task it depends on, directly or not.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskGraphStatus {
+    /// All tasks of the graph in the depth-first order, each listed once; the task spawned is listed first.
+    pub nodes: Vec<TaskGraphNode>,
+}
+
+impl TaskGraphStatus {
+    /// Collects the task given and all of its dependencies, with all tasks pending.
+    pub fn new(root: &SpawnInTerminal) -> Self {
+        let mut nodes = Vec::<TaskGraphNode>::new();
+        let mut stack = vec![(root, 0)];
+        while let Some((task, depth)) = stack.pop() {
+            if nodes.iter().any(|node| node.id == task.id) {
+                continue;
+            }
+            nodes.push(TaskGraphNode {
+                id: task.id.clone(),
+                label: task.label.clone(),
+                depth,
+                status: TaskGraphNodeStatus::Pending,
+            });
+            stack.extend(
+                task.dependencies
+                    .iter()
+                    .rev()
+                    .map(|dependency| (dependency, depth + 1)),
+            );
+        }
+        Self { nodes }
+    }
+
+    /// Id of the task spawned.
+    pub fn root(&self) -> &TaskId {
+        &self.nodes[0].id
+    }
+
+    /// The current state of the task with the id given, if it belongs to the graph.
+    pub fn status(&self, id: &TaskId) -> Option<TaskGraphNodeStatus> {
+        self.nodes
+            .iter()
+            .find(|node| &node.id == id)
+            .map(|node| node.status)
+    }
+
+    /// Updates the state of the task with the id given.
+    pub fn set_status(&mut self, id: &TaskId, status: TaskGraphNodeStatus) {
+        if let Some(node) = self.nodes.iter_mut().find(|node| &node.id == id) {
+            node.status = status;
+        }
+    }
+
+    /// Marks all tasks that were not started as skipped, to be called after the graph has stopped running.
+    pub fn finish(&mut self) {
+        for node in &mut self.nodes {
+            if node.status == TaskGraphNodeStatus::Pending {
+                node.status = TaskGraphNodeStatus::Skipped;
+            }
+        }
+    }
+
+    /// Whether any task of the graph is still pending or running.
+    pub fn is_running(&self) -> bool {
+        self.nodes.iter().any(|node| {
+            matches!(
+                node.status,
+                TaskGraphNodeStatus::Pending | TaskGraphNodeStatus::Running
+            )
+        })
+    }
+
+    /// A state of the graph as a whole: failed if any of its tasks have failed, running if any are yet to finish,
+    /// otherwise the state of the task spawned.
+    pub fn summary(&self) -> TaskGraphNodeStatus {
+        if self
+            .nodes
+            .iter()
+            .any(|node| node.status == TaskGraphNodeStatus::Failed)
+        {
+            TaskGraphNodeStatus::Failed
+        } else if self.is_running() {
+            TaskGraphNodeStatus::Running
+        } else {
+            self.nodes[0].status
+        }
+    }
+
+    /// How many tasks of the graph have succeeded, out of all of them.
+    pub fn progress(&self) -> (usize, usize) {
+        let succeeded = self
+            .nodes
+            .iter()
+            .filter(|node| node.status == TaskGraphNodeStatus::Succeeded)
+            .count();
+        (succeeded, self.nodes.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DependsOrder, TaskContext, TaskTemplate};
+
+    use super::*;
+
+    fn spawn(label: &str, dependencies: Vec<SpawnInTerminal>) -> SpawnInTerminal {
+        let mut spawn_in_terminal = TaskTemplate {
+            label: label.to_string(),
+            command: "echo".to_string(),
+            ..TaskTemplate::default()
+        }
+        .resolve_task("test", &TaskContext::default())
+        .unwrap()
+        .resolved
+        .unwrap();
+        spawn_in_terminal.dependencies = dependencies;
+        spawn_in_terminal.depends_order = DependsOrder::Sequence;
+        spawn_in_terminal
+    }
+
+    #[test]
+    fn test_task_graph_status() {
+        let codegen = spawn("codegen", Vec::new());
+        let dev = spawn(
+            "dev",
+            vec![
+                codegen.clone(),
+                spawn("servers", vec![spawn("api", vec![codegen.clone()])]),
+            ],
+        );
+        let mut graph = TaskGraphStatus::new(&dev);
+        assert_eq!(graph.root(), &dev.id);
+        assert_eq!(
+            graph
+                .nodes
+                .iter()
+                .map(|node| (node.label.as_str(), node.depth))
+                .collect::<Vec<_>>(),
+            vec![("dev", 0), ("codegen", 1), ("servers", 1), ("api", 2)],
+            "Shared dependencies should be listed once, at their first occurrence"
+        );
+        assert!(graph.is_running());
+        assert_eq!(graph.summary(), TaskGraphNodeStatus::Running);
+
+        graph.set_status(&codegen.id, TaskGraphNodeStatus::Succeeded);
+        assert_eq!(graph.progress(), (1, 4));
+        assert_eq!(graph.summary(), TaskGraphNodeStatus::Running);
+
+        let api_id = graph.nodes[3].id.clone();
+        graph.set_status(&api_id, TaskGraphNodeStatus::Failed);
+        graph.finish();
+        assert!(!graph.is_running());
+        assert_eq!(graph.status(&dev.id), Some(TaskGraphNodeStatus::Skipped));
+        assert_eq!(graph.summary(), TaskGraphNodeStatus::Failed);
+    }
+}
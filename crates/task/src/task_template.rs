@@ -9,8 +9,8 @@ use sha2::{Digest, Sha256};
 use util::{ResultExt, truncate_and_remove_front};
 
 use crate::{
-    AttachConfig, ResolvedTask, RevealTarget, Shell, SpawnInTerminal, TCPHost, TaskContext, TaskId,
-    VariableName, ZED_VARIABLE_NAME_PREFIX,
+    AttachConfig, FileLocation, ProblemMatcherDefinition, ResolvedTask, RevealTarget, Shell,
+    SpawnInTerminal, TCPHost, TaskContext, TaskId, VariableName, ZED_VARIABLE_NAME_PREFIX,
 };
 
 /// A template definition of a Zed task to run.
@@ -73,6 +73,21 @@ pub struct TaskTemplate {
     /// Whether to show the command line in the task output.
     #[serde(default = "default_true")]
     pub show_command: bool,
+    /// Labels of the tasks to run before this one; this task is not started if any of them fails.
+    /// A task with dependencies may have no `command`, then it only runs its dependencies.
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    /// How to run the tasks from `depends_on`:
+    /// * `parallel` — start all of them at once (default)
+    /// * `sequence` — start them one after another, in the order listed, stopping at the first failure
+    #[serde(default)]
+    pub depends_order: DependsOrder,
+    /// Problem matchers to turn the task output into diagnostics with: names of the built-in matchers
+    /// (`$rustc`, `$tsc`, `$tsc-watch`, `$eslint-compact`, `$eslint-stylish`, `$gcc`, `$go`)
+    /// and custom matchers in the format of VS Code's `problemMatcher`.
+    /// Diagnostics found are cleared when the task is rerun.
+    #[serde(default)]
+    pub problem_matchers: Vec<ProblemMatcherDefinition>,
 }
 
 #[derive(Deserialize, Eq, PartialEq, Clone, Debug)]
@@ -147,6 +162,17 @@ pub enum HideStrategy {
     OnSuccess,
 }
 
+/// How to run the dependencies of a task.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DependsOrder {
+    /// Start all dependencies at once.
+    #[default]
+    Parallel,
+    /// Start dependencies one after another, in the order they are listed, stopping at the first failure.
+    Sequence,
+}
+
 /// A group of Tasks defined in a JSON file.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct TaskTemplates(pub Vec<TaskTemplate>);
@@ -172,7 +198,9 @@ impl TaskTemplate {
     /// and hashes of its template and [`TaskContext`], see [`ResolvedTask`] fields' documentation for more details.
     pub fn resolve_task(&self, id_base: &str, cx: &TaskContext) -> Option<ResolvedTask> {
         if self.label.trim().is_empty()
-            || (self.command.trim().is_empty() && matches!(self.task_type, TaskType::Script))
+            || (self.command.trim().is_empty()
+                && self.depends_on.is_empty()
+                && matches!(self.task_type, TaskType::Script))
         {
             return None;
         }
@@ -248,6 +276,22 @@ impl TaskTemplate {
             &variable_names,
             &mut substituted_variables,
         )?;
+        let problem_matchers = self
+            .problem_matchers
+            .iter()
+            .filter_map(|definition| {
+                let mut matcher = definition.resolve().log_err()?;
+                if let Some(FileLocation::WithBase(_, base_dir)) = &mut matcher.file_location {
+                    *base_dir = substitute_all_template_variables_in_str(
+                        base_dir,
+                        &task_variables,
+                        &variable_names,
+                        &mut substituted_variables,
+                    )?;
+                }
+                Some(matcher)
+            })
+            .collect();
 
         let task_hash = to_hex_hash(self)
             .context("hashing task template")
@@ -281,6 +325,7 @@ impl TaskTemplate {
             id: id.clone(),
             substituted_variables,
             original_task: self.clone(),
+            task_context: cx.clone(),
             resolved_label: full_label.clone(),
             resolved: Some(SpawnInTerminal {
                 id,
@@ -307,6 +352,9 @@ impl TaskTemplate {
                 show_summary: self.show_summary,
                 show_command: self.show_command,
                 show_rerun: true,
+                dependencies: Vec::new(),
+                depends_order: self.depends_order,
+                problem_matchers,
             }),
         })
     }
@@ -418,7 +466,10 @@ fn substitute_all_template_variables_in_map(
 mod tests {
     use std::{borrow::Cow, path::Path};
 
-    use crate::{TaskVariables, VariableName};
+    use crate::{
+        FileLocationKind, ProblemMatcher, ProblemPattern, ProblemPatterns, TaskVariables,
+        VariableName,
+    };
 
     use super::*;
 
@@ -890,4 +941,104 @@ mod tests {
             "overwritten"
         );
     }
+
+    #[test]
+    fn test_resolving_tasks_with_dependencies() {
+        let compound_task = TaskTemplate {
+            label: "dev".to_string(),
+            depends_on: vec!["codegen".to_string(), "servers".to_string()],
+            depends_order: DependsOrder::Sequence,
+            ..TaskTemplate::default()
+        };
+        let context = TaskContext {
+            cwd: None,
+            task_variables: TaskVariables::from_iter([(
+                VariableName::WorktreeRoot,
+                "/dir".to_string(),
+            )]),
+            project_env: HashMap::default(),
+        };
+        let resolved_compound_task = compound_task
+            .resolve_task(TEST_ID_BASE, &context)
+            .expect("should resolve a task without command, if it has dependencies");
+        assert_eq!(
+            resolved_compound_task
+                .resolved
+                .as_ref()
+                .unwrap()
+                .depends_order,
+            DependsOrder::Sequence
+        );
+
+        let dependency = TaskTemplate {
+            label: "codegen".to_string(),
+            command: format!("{}/codegen.sh", VariableName::WorktreeRoot.template_value()),
+            ..TaskTemplate::default()
+        };
+        let resolved_dependency = resolved_compound_task
+            .resolve_dependency(&dependency, TEST_ID_BASE)
+            .unwrap();
+        assert_eq!(
+            resolved_dependency.resolved.unwrap().command,
+            "/dir/codegen.sh",
+            "Dependencies should be resolved with the context of the task depending on them"
+        );
+    }
+
+    #[test]
+    fn test_resolving_problem_matchers() {
+        let task = TaskTemplate {
+            label: "check".to_string(),
+            command: "cargo".to_string(),
+            problem_matchers: vec![
+                ProblemMatcherDefinition::Named("$rustc".to_string()),
+                ProblemMatcherDefinition::Named("$unknown".to_string()),
+                ProblemMatcherDefinition::Custom(ProblemMatcher {
+                    owner: "lint".to_string(),
+                    file_location: Some(FileLocation::WithBase(
+                        FileLocationKind::Relative,
+                        format!("{}/web", VariableName::WorktreeRoot.template_value()),
+                    )),
+                    pattern: Some(ProblemPatterns::Single(ProblemPattern {
+                        regexp: "^(.*):(\\d+) (.*)$".to_string(),
+                        file: Some(1),
+                        line: Some(2),
+                        message: Some(3),
+                        ..ProblemPattern::default()
+                    })),
+                    ..ProblemMatcher::default()
+                }),
+            ],
+            ..TaskTemplate::default()
+        };
+        let context = TaskContext {
+            cwd: None,
+            task_variables: TaskVariables::from_iter([(
+                VariableName::WorktreeRoot,
+                "/dir".to_string(),
+            )]),
+            project_env: HashMap::default(),
+        };
+        let problem_matchers = task
+            .resolve_task(TEST_ID_BASE, &context)
+            .unwrap()
+            .resolved
+            .unwrap()
+            .problem_matchers;
+        assert_eq!(
+            problem_matchers
+                .iter()
+                .map(|matcher| matcher.owner.as_str())
+                .collect::<Vec<_>>(),
+            vec!["rustc", "lint"],
+            "Unknown problem matchers should be skipped"
+        );
+        assert_eq!(
+            problem_matchers[1].file_location,
+            Some(FileLocation::WithBase(
+                FileLocationKind::Relative,
+                "/dir/web".to_string()
+            ))
+        );
+    }
 }
@@ -1,9 +1,11 @@
-use anyhow::bail;
+use anyhow::{Context as _, bail};
 use collections::HashMap;
 use serde::Deserialize;
 use util::ResultExt;
 
-use crate::{TaskTemplate, TaskTemplates, VariableName};
+use crate::{
+    DependsOrder, FileLocation, ProblemMatcherDefinition, TaskTemplate, TaskTemplates, VariableName,
+};
 
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -24,6 +26,20 @@ struct VsCodeTaskDefinition {
     options: Option<TaskOptions>,
 }
 
+#[derive(Clone, Deserialize, PartialEq, Debug)]
+#[serde(untagged)]
+enum DependsOn {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+#[derive(Clone, Deserialize, PartialEq, Debug)]
+#[serde(untagged)]
+enum ProblemMatchers {
+    Single(ProblemMatcherDefinition),
+    Multiple(Vec<ProblemMatcherDefinition>),
+}
+
 #[derive(Clone, Deserialize, PartialEq, Debug)]
 #[serde(tag = "type")]
 #[serde(rename_all = "camelCase")]
@@ -85,21 +101,58 @@ impl EnvVariableReplacer {
 
 impl VsCodeTaskDefinition {
     fn into_zed_format(self, replacer: &EnvVariableReplacer) -> anyhow::Result<TaskTemplate> {
-        if self.other_attributes.contains_key("dependsOn") {
-            bail!("Encountered unsupported `dependsOn` key during deserialization");
-        }
-        // `type` might not be set in e.g. tasks that use `dependsOn`; we still want to deserialize the whole object though (hence command is an Option),
-        // as that way we can provide more specific description of why deserialization failed.
-        // E.g. if the command is missing due to `dependsOn` presence, we can check other_attributes first before doing this (and provide nice error message)
-        // catch-all if on value.command presence.
-        let Some(command) = self.command else {
-            bail!("Missing `type` field in task");
+        let depends_on = match self.other_attributes.get("dependsOn") {
+            Some(depends_on) => {
+                match serde_json_lenient::from_value::<DependsOn>(depends_on.clone()) {
+                    Ok(DependsOn::Single(label)) => vec![label],
+                    Ok(DependsOn::Multiple(labels)) => labels,
+                    Err(_) => bail!("Only task labels are supported in `dependsOn`"),
+                }
+            }
+            None => Vec::new(),
         };
-
-        let (command, args) = match command {
-            Command::Npm { script } => ("npm".to_owned(), vec!["run".to_string(), script]),
-            Command::Shell { command, args } => (command, args),
-            Command::Gulp { task } => ("gulp".to_owned(), vec![task]),
+        let depends_order = match self
+            .other_attributes
+            .get("dependsOrder")
+            .and_then(|order| order.as_str())
+        {
+            Some("sequence") => DependsOrder::Sequence,
+            _ => DependsOrder::Parallel,
+        };
+        // Problem matchers are optional, so the task is kept even if they fail to parse.
+        let problem_matchers = self
+            .other_attributes
+            .get("problemMatcher")
+            .and_then(|problem_matchers| {
+                serde_json_lenient::from_value::<ProblemMatchers>(problem_matchers.clone())
+                    .with_context(|| {
+                        format!("Unsupported `problemMatcher` in task `{}`", self.label)
+                    })
+                    .log_err()
+            })
+            .map(|problem_matchers| match problem_matchers {
+                ProblemMatchers::Single(problem_matcher) => vec![problem_matcher],
+                ProblemMatchers::Multiple(problem_matchers) => problem_matchers,
+            })
+            .unwrap_or_default()
+            .into_iter()
+            .map(|mut problem_matcher| {
+                if let ProblemMatcherDefinition::Custom(matcher) = &mut problem_matcher {
+                    if let Some(FileLocation::WithBase(_, base_dir)) = &mut matcher.file_location {
+                        *base_dir = replacer.replace(base_dir);
+                    }
+                }
+                problem_matcher
+            })
+            .collect();
+        // `type` might not be set in e.g. tasks that use `dependsOn`; we still want to deserialize the whole object though (hence command is an Option),
+        // such tasks only run their dependencies.
+        let (command, args) = match self.command {
+            Some(Command::Npm { script }) => ("npm".to_owned(), vec!["run".to_string(), script]),
+            Some(Command::Shell { command, args }) => (command, args),
+            Some(Command::Gulp { task }) => ("gulp".to_owned(), vec![task]),
+            None if !depends_on.is_empty() => (String::new(), Vec::new()),
+            None => bail!("Missing `type` field in task"),
         };
         // Per VSC docs, only `command`, `args` and `options` support variable substitution.
         let command = replacer.replace(&command);
@@ -108,6 +161,9 @@ impl VsCodeTaskDefinition {
             label: self.label,
             command,
             args,
+            depends_on,
+            depends_order,
+            problem_matchers,
             ..Default::default()
         };
         if let Some(options) = self.options {
@@ -154,6 +210,7 @@ mod tests {
     use std::collections::HashMap;
 
     use crate::{
+        DependsOrder, FileLocation, FileLocationKind, ProblemMatcher, ProblemMatcherDefinition,
         TaskTemplate, TaskTemplates, VsCodeTaskFile,
         vscode_format::{Command, VsCodeTaskDefinition},
     };
@@ -257,6 +314,7 @@ mod tests {
                 label: "gulp: tests".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build:tests:notypecheck".to_string()],
+                problem_matchers: vec![ProblemMatcherDefinition::Named("$tsc".to_string())],
                 ..Default::default()
             },
             TaskTemplate {
@@ -268,18 +326,21 @@ mod tests {
                     "${ZED_WORKTREE_ROOT}/src".to_string(),
                     "--watch".to_string(),
                 ],
+                problem_matchers: vec![ProblemMatcherDefinition::Named("$tsc-watch".to_string())],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "npm: build:compiler".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build:compiler".to_string()],
+                problem_matchers: vec![ProblemMatcherDefinition::Named("$tsc".to_string())],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "npm: build:tests".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build:tests:notypecheck".to_string()],
+                problem_matchers: vec![ProblemMatcherDefinition::Named("$tsc".to_string())],
                 ..Default::default()
             },
         ];
@@ -355,37 +416,124 @@ mod tests {
             .iter()
             .zip(expected)
             .for_each(|(lhs, rhs)| compare_without_other_attributes(lhs.clone(), rhs));
+        let extension_problem_matcher = |base: &str| {
+            ProblemMatcherDefinition::Custom(ProblemMatcher {
+                base: Some(base.to_string()),
+                file_location: Some(FileLocation::WithBase(
+                    FileLocationKind::Relative,
+                    "${ZED_WORKTREE_ROOT}/editors/code/".to_string(),
+                )),
+                ..ProblemMatcher::default()
+            })
+        };
+        let rustc_problem_matcher = ProblemMatcherDefinition::Named("$rustc".to_string());
         let expected = vec![
             TaskTemplate {
                 label: "Build Extension in Background".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "watch".to_string()],
+                problem_matchers: vec![extension_problem_matcher("$tsc-watch")],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Build Extension".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build".to_string()],
+                problem_matchers: vec![extension_problem_matcher("$tsc")],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Build Server".to_string(),
                 command: "cargo build --package rust-analyzer".to_string(),
+                problem_matchers: vec![rustc_problem_matcher.clone()],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Build Server (Release)".to_string(),
                 command: "cargo build --release --package rust-analyzer".to_string(),
+                problem_matchers: vec![rustc_problem_matcher.clone()],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Pretest".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "pretest".to_string()],
+                problem_matchers: vec![extension_problem_matcher("$tsc")],
+                ..Default::default()
+            },
+            TaskTemplate {
+                label: "Build Server and Extension".to_string(),
+                depends_on: vec!["Build Server".to_string(), "Build Extension".to_string()],
+                problem_matchers: vec![rustc_problem_matcher.clone()],
+                ..Default::default()
+            },
+            TaskTemplate {
+                label: "Build Server (Release) and Extension".to_string(),
+                depends_on: vec![
+                    "Build Server (Release)".to_string(),
+                    "Build Extension".to_string(),
+                ],
+                problem_matchers: vec![rustc_problem_matcher],
                 ..Default::default()
             },
         ];
         let tasks: TaskTemplates = vscode_definitions.try_into().unwrap();
         assert_eq!(tasks.0, expected);
     }
+
+    #[test]
+    fn can_deserialize_depends_on() {
+        let vscode_definitions: VsCodeTaskFile = serde_json_lenient::from_str(
+            r#"{
+                "version": "2.0.0",
+                "tasks": [
+                    {
+                        "label": "dev",
+                        "dependsOn": ["codegen", "servers"],
+                        "dependsOrder": "sequence"
+                    },
+                    {
+                        "label": "servers",
+                        "dependsOn": ["api", "web"]
+                    },
+                    {
+                        "label": "codegen",
+                        "type": "shell",
+                        "command": "make",
+                        "dependsOn": "fetch schema"
+                    },
+                    {
+                        "label": "task identifiers",
+                        "dependsOn": [{ "type": "npm", "script": "watch" }]
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let tasks: TaskTemplates = vscode_definitions.try_into().unwrap();
+        assert_eq!(
+            tasks.0,
+            vec![
+                TaskTemplate {
+                    label: "dev".to_string(),
+                    depends_on: vec!["codegen".to_string(), "servers".to_string()],
+                    depends_order: DependsOrder::Sequence,
+                    ..Default::default()
+                },
+                TaskTemplate {
+                    label: "servers".to_string(),
+                    depends_on: vec!["api".to_string(), "web".to_string()],
+                    ..Default::default()
+                },
+                TaskTemplate {
+                    label: "codegen".to_string(),
+                    command: "make".to_string(),
+                    depends_on: vec!["fetch schema".to_string()],
+                    ..Default::default()
+                },
+            ],
+            "Tasks that refer to their dependencies by anything but a label should be skipped"
+        );
+    }
 }
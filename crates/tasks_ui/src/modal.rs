@@ -10,13 +10,13 @@ use gpui::{
 use picker::{Picker, PickerDelegate, highlighted_match_with_paths::HighlightedMatch};
 use project::{TaskSourceKind, task_store::TaskStore};
 use task::{
-    DebugRequestType, DebugTaskDefinition, ResolvedTask, RevealTarget, TaskContext, TaskModal,
-    TaskTemplate, TaskType,
+    DebugRequestType, DebugTaskDefinition, ResolvedTask, RevealTarget, TaskContext,
+    TaskGraphNodeStatus, TaskModal, TaskTemplate, TaskType,
 };
 use ui::{
     ActiveTheme, Button, ButtonCommon, ButtonSize, Clickable, Color, FluentBuilder as _, Icon,
-    IconButton, IconButtonShape, IconName, IconSize, IntoElement, KeyBinding, LabelSize, ListItem,
-    ListItemSpacing, RenderOnce, Toggleable, Tooltip, div, h_flex, v_flex,
+    IconButton, IconButtonShape, IconName, IconSize, IntoElement, KeyBinding, Label, LabelCommon,
+    LabelSize, ListItem, ListItemSpacing, RenderOnce, Toggleable, Tooltip, div, h_flex, v_flex,
 };
 use util::ResultExt;
 use workspace::{ModalView, Workspace, tasks::schedule_resolved_task};
@@ -397,6 +397,46 @@ impl PickerDelegate for TasksModalDelegate {
                 tooltip_label_text.push_str(&resolved.command_label);
             }
         }
+        let task_graph = self
+            .task_store
+            .read(cx)
+            .task_inventory()
+            .and_then(|inventory| inventory.read(cx).task_graph(&resolved_task.id).cloned());
+        if let Some(task_graph) = &task_graph {
+            for node in &task_graph.nodes {
+                if !tooltip_label_text.trim().is_empty() {
+                    tooltip_label_text.push('\n');
+                }
+                let status = match node.status {
+                    TaskGraphNodeStatus::Pending => "pending",
+                    TaskGraphNodeStatus::Running => "running",
+                    TaskGraphNodeStatus::Succeeded => "succeeded",
+                    TaskGraphNodeStatus::Failed => "failed",
+                    TaskGraphNodeStatus::Skipped => "skipped",
+                };
+                tooltip_label_text.push_str(&"  ".repeat(node.depth));
+                tooltip_label_text.push_str(&format!("{}: {status}", node.label));
+            }
+        }
+        let task_graph_status = task_graph.map(|task_graph| {
+            let (succeeded, total) = task_graph.progress();
+            let (icon, color) = match task_graph.summary() {
+                TaskGraphNodeStatus::Pending | TaskGraphNodeStatus::Running => {
+                    (IconName::Play, Color::Disabled)
+                }
+                TaskGraphNodeStatus::Succeeded => (IconName::Check, Color::Success),
+                TaskGraphNodeStatus::Failed => (IconName::XCircle, Color::Error),
+                TaskGraphNodeStatus::Skipped => (IconName::Dash, Color::Muted),
+            };
+            h_flex()
+                .gap_1()
+                .child(Icon::new(icon).color(color).size(IconSize::XSmall))
+                .child(
+                    Label::new(format!("{succeeded}/{total}"))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+        });
         let tooltip_label = if tooltip_label_text.trim().is_empty() {
             None
         } else {
@@ -477,7 +517,12 @@ impl PickerDelegate for TasksModalDelegate {
                     item
                 })
                 .toggle_state(selected)
-                .child(highlighted_location.render(window, cx)),
+                .child(
+                    h_flex()
+                        .gap_2()
+                        .child(highlighted_location.render(window, cx))
+                        .children(task_graph_status),
+                ),
         )
     }
 
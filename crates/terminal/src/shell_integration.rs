@@ -0,0 +1,775 @@
+//! Shell integration: the marks a shell emits around its prompts and commands
+//! (OSC 133 "semantic prompts", and their OSC 633 flavor used by VS Code),
+//! and the working directory it reports.
+//!
+//! Alacritty ignores these sequences, so the pty output is scanned before Alacritty sees it:
+//! the zone marks are rewritten into hyperlinks with a special URI, which tag the grid cells
+//! of each prompt, command line and command output, and keep tagging them as they move into the scrollback.
+//! The rest of the data reported (command lines, exit codes and working directories) is kept in [`ShellIntegration`].
+
+use std::{
+    borrow::Cow,
+    collections::VecDeque,
+    fs,
+    io::{self, Read},
+    mem,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use alacritty_terminal::{
+    Term,
+    event::{OnResize, WindowSize},
+    grid::Dimensions,
+    index::{Column, Line, Point as AlacPoint},
+    tty::{self, ChildEvent, EventedPty, EventedReadWrite},
+};
+use anyhow::{Context as _, Result};
+use collections::HashMap;
+use parking_lot::Mutex;
+use polling::{Event as PollingEvent, PollMode, Poller};
+use task::Shell;
+
+use crate::task_output::TaskOutputLines;
+
+const MARK_URI_PREFIX: &str = "zed-shell-integration:";
+const MARK_PREFIXES: [&[u8]; 4] = [b"\x1b]133;", b"\x1b]633;", b"\x1b]7;", b"\x1b]8;"];
+/// Longer sequences are not marks, and are passed to Alacritty as is.
+const MAX_SEQUENCE_LEN: usize = 4096;
+/// Marks are rewritten into longer sequences, so less than the caller asks for is read from the pty.
+const MAX_MARK_EXPANSION: usize = 8;
+const MAX_COMMANDS: usize = 1000;
+
+const BASH_SCRIPT: &str = include_str!("shell_integration/zed.bash");
+const ZSH_ENV_SCRIPT: &str = include_str!("shell_integration/zsh/.zshenv");
+const ZSH_PROFILE_SCRIPT: &str = include_str!("shell_integration/zsh/.zprofile");
+const ZSH_RC_SCRIPT: &str = include_str!("shell_integration/zsh/.zshrc");
+const FISH_SCRIPT: &str = include_str!("shell_integration/zed.fish");
+
+/// A command run in the terminal, as reported by the shell.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShellCommand {
+    pub id: usize,
+    /// The command line, if the shell reported it.
+    pub command_line: Option<String>,
+    /// The working directory the command was started in.
+    pub cwd: Option<PathBuf>,
+    /// The exit code of the command, if it has finished and the shell reported it.
+    pub exit_code: Option<i32>,
+    /// Whether the command has started running, as opposed to still being typed in the prompt.
+    pub executed: bool,
+    pub finished: bool,
+}
+
+impl ShellCommand {
+    pub fn failed(&self) -> bool {
+        self.exit_code.is_some_and(|exit_code| exit_code != 0)
+    }
+}
+
+/// The part of the terminal output, marked by the shell.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum CommandZone {
+    Prompt,
+    Input,
+    Output,
+}
+
+impl CommandZone {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Prompt => "prompt",
+            Self::Input => "input",
+            Self::Output => "output",
+        }
+    }
+}
+
+/// Parses the URI of a hyperlink, tagging a zone of the command with the id given.
+pub(crate) fn parse_mark_uri(uri: &str) -> Option<(usize, CommandZone)> {
+    let (id, zone) = uri.strip_prefix(MARK_URI_PREFIX)?.split_once('/')?;
+    let zone = match zone {
+        "prompt" => CommandZone::Prompt,
+        "input" => CommandZone::Input,
+        "output" => CommandZone::Output,
+        _ => return None,
+    };
+    Some((id.parse().ok()?, zone))
+}
+
+pub(crate) fn is_mark_uri(uri: &str) -> bool {
+    uri.starts_with(MARK_URI_PREFIX)
+}
+
+/// Where a command is in the terminal grid.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct CommandBounds {
+    pub id: usize,
+    /// The first cell of the command still in the grid, usually the start of its prompt.
+    pub start: AlacPoint,
+    pub output: Option<RangeInclusive<AlacPoint>>,
+}
+
+/// Finds all commands in the terminal grid, scrollback included, from the topmost one.
+pub(crate) fn command_bounds<T>(term: &Term<T>) -> Vec<CommandBounds> {
+    let grid = term.grid();
+    let mut bounds = Vec::<CommandBounds>::new();
+    for line in grid.topmost_line().0..=grid.bottommost_line().0 {
+        let row = &grid[Line(line)];
+        for column in 0..grid.columns() {
+            let Some((id, zone)) = row[Column(column)]
+                .hyperlink()
+                .and_then(|hyperlink| parse_mark_uri(hyperlink.uri()))
+            else {
+                continue;
+            };
+            let point = AlacPoint::new(Line(line), Column(column));
+            let ix = match bounds.iter().rposition(|command| command.id == id) {
+                Some(ix) => ix,
+                None => {
+                    bounds.push(CommandBounds {
+                        id,
+                        start: point,
+                        output: None,
+                    });
+                    bounds.len() - 1
+                }
+            };
+            if zone == CommandZone::Output {
+                let output = &mut bounds[ix].output;
+                *output = Some(match output.take() {
+                    Some(output) => *output.start()..=point,
+                    None => point..=point,
+                });
+            }
+        }
+    }
+    bounds
+}
+
+/// Everything the shell has reported about its commands.
+#[derive(Debug, Default)]
+pub(crate) struct ShellIntegration {
+    commands: VecDeque<ShellCommand>,
+    next_command_id: usize,
+    cwd: Option<PathBuf>,
+    /// The zone the output is written to, which is tagged again after a program ends a hyperlink of its own.
+    zone: Option<(usize, CommandZone)>,
+}
+
+impl ShellIntegration {
+    pub fn command(&self, id: usize) -> Option<&ShellCommand> {
+        self.commands.iter().rev().find(|command| command.id == id)
+    }
+
+    pub fn commands(&self) -> impl DoubleEndedIterator<Item = &ShellCommand> {
+        self.commands.iter()
+    }
+
+    /// The working directory the shell has reported last.
+    pub fn cwd(&self) -> Option<&Path> {
+        self.cwd.as_deref()
+    }
+
+    fn current_command(&mut self) -> Option<&mut ShellCommand> {
+        self.commands.back_mut().filter(|command| !command.finished)
+    }
+
+    fn current_or_new_command(&mut self) -> &mut ShellCommand {
+        if self.current_command().is_none() {
+            let id = self.next_command_id;
+            self.next_command_id += 1;
+            if self.commands.len() == MAX_COMMANDS {
+                self.commands.pop_front();
+            }
+            self.commands.push_back(ShellCommand {
+                id,
+                command_line: None,
+                cwd: self.cwd.clone(),
+                exit_code: None,
+                executed: false,
+                finished: false,
+            });
+        }
+        self.commands.back_mut().unwrap()
+    }
+
+    fn start_zone(&mut self, id: usize, zone: CommandZone) -> Cow<'static, [u8]> {
+        self.zone = Some((id, zone));
+        zone_start(id, zone)
+    }
+
+    /// Records the mark, returning what to pass to Alacritty in its place.
+    fn apply(&mut self, mark: Mark) -> Cow<'static, [u8]> {
+        match mark {
+            Mark::PromptStart => {
+                // A new prompt means the shell is done with the previous command, whether it reported that or not.
+                if let Some(command) = self.current_command() {
+                    command.finished = true;
+                }
+                let id = self.current_or_new_command().id;
+                self.start_zone(id, CommandZone::Prompt)
+            }
+            Mark::CommandStart => {
+                let id = self.current_or_new_command().id;
+                self.start_zone(id, CommandZone::Input)
+            }
+            Mark::CommandExecuted => {
+                let command = self.current_or_new_command();
+                command.executed = true;
+                let id = command.id;
+                self.start_zone(id, CommandZone::Output)
+            }
+            Mark::CommandFinished(exit_code) => {
+                if let Some(command) = self.current_command() {
+                    command.exit_code = exit_code;
+                    command.finished = true;
+                }
+                self.zone = None;
+                Cow::Borrowed(b"\x1b]8;;\x1b\\")
+            }
+            // Starting the zone's link again ends the program's one.
+            Mark::HyperlinkEnd => match self.zone {
+                Some((id, zone)) => zone_start(id, zone),
+                None => Cow::Borrowed(b"\x1b]8;;\x1b\\"),
+            },
+            Mark::CommandLine(command_line) => {
+                if let Some(command) = self.current_command() {
+                    command.command_line = Some(command_line);
+                }
+                Cow::Borrowed(b"")
+            }
+            Mark::Cwd(cwd) => {
+                if let Some(command) = self.current_command().filter(|command| !command.executed) {
+                    command.cwd = Some(cwd.clone());
+                }
+                self.cwd = Some(cwd);
+                Cow::Borrowed(b"")
+            }
+        }
+    }
+}
+
+fn zone_start(id: usize, zone: CommandZone) -> Cow<'static, [u8]> {
+    Cow::Owned(format!("\x1b]8;;{MARK_URI_PREFIX}{id}/{}\x1b\\", zone.name()).into_bytes())
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Mark {
+    PromptStart,
+    CommandStart,
+    CommandExecuted,
+    CommandFinished(Option<i32>),
+    CommandLine(String),
+    Cwd(PathBuf),
+    /// The end of a hyperlink printed by a program (OSC 8 with no URI).
+    HyperlinkEnd,
+}
+
+impl Mark {
+    /// Parses the contents of an OSC sequence, between `ESC ]` and its terminator.
+    fn parse(payload: &str) -> Option<Self> {
+        let (code, rest) = payload.split_once(';')?;
+        match code {
+            "133" | "633" => {
+                let mut params = rest.split(';');
+                match params.next()? {
+                    "A" => Some(Self::PromptStart),
+                    "B" => Some(Self::CommandStart),
+                    "C" => Some(Self::CommandExecuted),
+                    "D" => Some(Self::CommandFinished(
+                        params.next().and_then(|exit_code| exit_code.parse().ok()),
+                    )),
+                    "E" if code == "633" => Some(Self::CommandLine(unescape(
+                        params.next().unwrap_or_default(),
+                    ))),
+                    "P" if code == "633" => {
+                        let (key, value) = params.next()?.split_once('=')?;
+                        (key == "Cwd").then(|| Self::Cwd(PathBuf::from(unescape(value))))
+                    }
+                    _ => None,
+                }
+            }
+            "8" => {
+                let (_params, uri) = rest.split_once(';')?;
+                uri.is_empty().then_some(Self::HyperlinkEnd)
+            }
+            "7" => {
+                let location = rest.strip_prefix("file://")?;
+                let path = &location[location.find('/')?..];
+                let path = urlencoding::decode(path).ok()?;
+                Some(Self::Cwd(PathBuf::from(path.as_ref())))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Reverts the escaping of OSC 633 values: `\\` for a backslash and `\xAB` for any other byte.
+fn unescape(value: &str) -> String {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut rest = value.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        rest = tail;
+        if byte == b'\\' {
+            if let Some((b'\\', tail)) = rest.split_first() {
+                bytes.push(b'\\');
+                rest = tail;
+                continue;
+            }
+            if let Some(escaped) = rest
+                .strip_prefix(b"x")
+                .and_then(|hex| hex.get(..2))
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                bytes.push(escaped);
+                rest = &rest[3..];
+                continue;
+            }
+        }
+        bytes.push(byte);
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+enum Scan {
+    /// The sequence might be a mark, but has not been received fully yet.
+    Incomplete,
+    NotAMark,
+    Mark {
+        payload_end: usize,
+        len: usize,
+    },
+}
+
+fn scan_sequence(bytes: &[u8]) -> Scan {
+    if !MARK_PREFIXES.iter().any(|prefix| bytes.starts_with(prefix)) {
+        return if MARK_PREFIXES.iter().any(|prefix| prefix.starts_with(bytes)) {
+            Scan::Incomplete
+        } else {
+            Scan::NotAMark
+        };
+    }
+    for (ix, &byte) in bytes.iter().enumerate().take(MAX_SEQUENCE_LEN).skip(2) {
+        match byte {
+            0x07 => {
+                return Scan::Mark {
+                    payload_end: ix,
+                    len: ix + 1,
+                };
+            }
+            0x1b => {
+                return match bytes.get(ix + 1) {
+                    Some(b'\\') => Scan::Mark {
+                        payload_end: ix,
+                        len: ix + 2,
+                    },
+                    Some(_) => Scan::NotAMark,
+                    None => Scan::Incomplete,
+                };
+            }
+            _ => {}
+        }
+    }
+    if bytes.len() < MAX_SEQUENCE_LEN {
+        Scan::Incomplete
+    } else {
+        Scan::NotAMark
+    }
+}
+
+/// Finds the marks in the pty output, which may be split across reads.
+#[derive(Default)]
+struct MarkScanner {
+    /// The start of a possible mark, left unprocessed until the rest of it is read.
+    pending: Vec<u8>,
+}
+
+impl MarkScanner {
+    fn process(&mut self, input: &[u8], output: &mut Vec<u8>, integration: &mut ShellIntegration) {
+        let data = if self.pending.is_empty() {
+            Cow::Borrowed(input)
+        } else {
+            let mut data = mem::take(&mut self.pending);
+            data.extend_from_slice(input);
+            Cow::Owned(data)
+        };
+
+        let mut ix = 0;
+        while ix < data.len() {
+            let Some(escape_ix) = data[ix..].iter().position(|&byte| byte == 0x1b) else {
+                output.extend_from_slice(&data[ix..]);
+                break;
+            };
+            let start = ix + escape_ix;
+            output.extend_from_slice(&data[ix..start]);
+            let sequence = &data[start..];
+            match scan_sequence(sequence) {
+                Scan::Incomplete => {
+                    self.pending = sequence.to_vec();
+                    break;
+                }
+                Scan::NotAMark => {
+                    output.push(0x1b);
+                    ix = start + 1;
+                }
+                Scan::Mark { payload_end, len } => {
+                    let payload = String::from_utf8_lossy(&sequence[2..payload_end]);
+                    match Mark::parse(&payload) {
+                        // OSC 7 is left for Alacritty, to keep working with it.
+                        Some(mark @ Mark::Cwd(_)) if sequence.starts_with(b"\x1b]7;") => {
+                            integration.apply(mark);
+                            output.extend_from_slice(&sequence[..len]);
+                        }
+                        Some(mark) => output.extend_from_slice(&integration.apply(mark)),
+                        None => output.extend_from_slice(&sequence[..len]),
+                    }
+                    ix = start + len;
+                }
+            }
+        }
+    }
+
+    /// Returns the pending data, to be called when there is nothing more to read.
+    fn flush(&mut self) -> Vec<u8> {
+        mem::take(&mut self.pending)
+    }
+}
+
+/// Reads the pty output, with the marks in it rewritten for Alacritty.
+pub(crate) struct ShellIntegrationReader {
+    pty: tty::Pty,
+    /// Where to record the marks; the output is read as is when the shell integration is disabled.
+    integration: Option<Arc<Mutex<ShellIntegration>>>,
+    /// Where to split the output into lines, for the problem matchers of a task.
+    task_output: Option<Arc<Mutex<TaskOutputLines>>>,
+    scanner: MarkScanner,
+    read_buffer: Vec<u8>,
+    output: Vec<u8>,
+    output_start: usize,
+}
+
+impl io::Read for ShellIntegrationReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let Some(integration) = &self.integration else {
+            let read = self.pty.reader().read(buf)?;
+            if let Some(task_output) = &self.task_output {
+                task_output.lock().process(&buf[..read]);
+            }
+            return Ok(read);
+        };
+        if self.output_start == self.output.len() {
+            self.output.clear();
+            self.output_start = 0;
+            while self.output.is_empty() {
+                self.read_buffer
+                    .resize((buf.len() / MAX_MARK_EXPANSION).max(1), 0);
+                let read = self.pty.reader().read(&mut self.read_buffer)?;
+                if read == 0 {
+                    self.output = self.scanner.flush();
+                    break;
+                }
+                if let Some(task_output) = &self.task_output {
+                    task_output.lock().process(&self.read_buffer[..read]);
+                }
+                self.scanner.process(
+                    &self.read_buffer[..read],
+                    &mut self.output,
+                    &mut integration.lock(),
+                );
+            }
+        }
+
+        let len = buf.len().min(self.output.len() - self.output_start);
+        buf[..len].copy_from_slice(&self.output[self.output_start..self.output_start + len]);
+        self.output_start += len;
+        Ok(len)
+    }
+}
+
+/// A pty that passes its output through the shell integration, before Alacritty reads it.
+pub(crate) struct ShellIntegrationPty {
+    reader: ShellIntegrationReader,
+}
+
+impl ShellIntegrationPty {
+    /// Wraps the pty, passing its output through the shell integration given, if any,
+    /// and splitting it into lines for the task output given, if any.
+    pub(crate) fn new(
+        pty: tty::Pty,
+        integration: Option<Arc<Mutex<ShellIntegration>>>,
+        task_output: Option<Arc<Mutex<TaskOutputLines>>>,
+    ) -> Self {
+        Self {
+            reader: ShellIntegrationReader {
+                pty,
+                integration,
+                task_output,
+                scanner: MarkScanner::default(),
+                read_buffer: Vec::new(),
+                output: Vec::new(),
+                output_start: 0,
+            },
+        }
+    }
+}
+
+impl EventedReadWrite for ShellIntegrationPty {
+    type Reader = ShellIntegrationReader;
+    type Writer = <tty::Pty as EventedReadWrite>::Writer;
+
+    unsafe fn register(
+        &mut self,
+        poll: &Arc<Poller>,
+        interest: PollingEvent,
+        mode: PollMode,
+    ) -> io::Result<()> {
+        unsafe { self.reader.pty.register(poll, interest, mode) }
+    }
+
+    fn reregister(
+        &mut self,
+        poll: &Arc<Poller>,
+        interest: PollingEvent,
+        mode: PollMode,
+    ) -> io::Result<()> {
+        self.reader.pty.reregister(poll, interest, mode)
+    }
+
+    fn deregister(&mut self, poll: &Arc<Poller>) -> io::Result<()> {
+        self.reader.pty.deregister(poll)
+    }
+
+    fn reader(&mut self) -> &mut Self::Reader {
+        &mut self.reader
+    }
+
+    fn writer(&mut self) -> &mut Self::Writer {
+        self.reader.pty.writer()
+    }
+}
+
+impl EventedPty for ShellIntegrationPty {
+    fn next_child_event(&mut self) -> Option<ChildEvent> {
+        self.reader.pty.next_child_event()
+    }
+}
+
+impl OnResize for ShellIntegrationPty {
+    fn on_resize(&mut self, window_size: WindowSize) {
+        self.reader.pty.on_resize(window_size)
+    }
+}
+
+/// Adjusts the shell to load the shell integration script for it, if there is one.
+/// Returns the shell to run instead, or `None` if the shell is not supported.
+pub(crate) fn inject(shell: &Shell, env: &mut HashMap<String, String>) -> Result<Option<Shell>> {
+    let (program, login) = match shell {
+        // Alacritty runs the system shell as a login shell on macOS, keep doing so.
+        Shell::System => match std::env::var("SHELL") {
+            Ok(program) => (program, cfg!(target_os = "macos")),
+            Err(_) => return Ok(None),
+        },
+        Shell::Program(program) => (program.clone(), false),
+        Shell::WithArguments { .. } => return Ok(None),
+    };
+    let Some(shell_name) = Path::new(&program)
+        .file_name()
+        .and_then(|name| name.to_str())
+    else {
+        return Ok(None);
+    };
+
+    let dir = paths::shell_integration_dir();
+    let mut args = Vec::new();
+    match shell_name {
+        "bash" => {
+            // Login shells ignore `--init-file`, the script loads the login files itself.
+            let script = write_script(&dir.join("zed.bash"), BASH_SCRIPT)?;
+            if login {
+                env.insert("ZED_SHELL_INTEGRATION_LOGIN".to_string(), "1".to_string());
+            }
+            args.push("--init-file".to_string());
+            args.push(script.to_string_lossy().into_owned());
+        }
+        "zsh" => {
+            let zsh_dir = dir.join("zsh");
+            write_script(&zsh_dir.join(".zshenv"), ZSH_ENV_SCRIPT)?;
+            write_script(&zsh_dir.join(".zprofile"), ZSH_PROFILE_SCRIPT)?;
+            write_script(&zsh_dir.join(".zshrc"), ZSH_RC_SCRIPT)?;
+            if let Some(zdotdir) = env
+                .get("ZDOTDIR")
+                .cloned()
+                .or_else(|| std::env::var("ZDOTDIR").ok())
+            {
+                env.insert("ZED_USER_ZDOTDIR".to_string(), zdotdir);
+            }
+            env.insert(
+                "ZDOTDIR".to_string(),
+                zsh_dir.to_string_lossy().into_owned(),
+            );
+            if login {
+                args.push("-l".to_string());
+            }
+        }
+        "fish" => {
+            let script = write_script(&dir.join("zed.fish"), FISH_SCRIPT)?;
+            if login {
+                args.push("-l".to_string());
+            }
+            args.push("--init-command".to_string());
+            args.push(format!(
+                "source {}",
+                shlex::try_quote(&script.to_string_lossy())?
+            ));
+        }
+        _ => return Ok(None),
+    }
+
+    Ok(Some(Shell::WithArguments {
+        program,
+        args,
+        title_override: None,
+    }))
+}
+
+fn write_script(path: &Path, contents: &str) -> Result<PathBuf> {
+    if fs::read_to_string(path).ok().as_deref() != Some(contents) {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("creating shell integration directory {dir:?}"))?;
+        }
+        fs::write(path, contents)
+            .with_context(|| format!("writing shell integration script {path:?}"))?;
+    }
+    Ok(path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use alacritty_terminal::{event::VoidListener, term::Config, vte::ansi::Processor};
+
+    use crate::TerminalBounds;
+
+    use super::*;
+
+    fn process(chunks: &[&[u8]], integration: &mut ShellIntegration) -> Vec<u8> {
+        let mut scanner = MarkScanner::default();
+        let mut output = Vec::new();
+        for chunk in chunks {
+            scanner.process(chunk, &mut output, integration);
+        }
+        output.extend(scanner.flush());
+        output
+    }
+
+    #[test]
+    fn test_marks_are_rewritten_into_links() {
+        let mut integration = ShellIntegration::default();
+        let output = process(
+            &[
+                b"\x1b]633;P;Cwd=/home/user\x07\x1b]133;A\x07$ \x1b]13",
+                b"3;B\x07ls\r\n\x1b]633;E;ls \\x3b echo\\\\ok\x07\x1b]133;C\x1b\\",
+                b"\x1b[1mfile.rs\x1b[0m\r\n\x1b]133;D;2\x07\x1b]133;A\x07$ \x1b",
+            ],
+            &mut integration,
+        );
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "\x1b]8;;zed-shell-integration:0/prompt\x1b\\$ \
+            \x1b]8;;zed-shell-integration:0/input\x1b\\ls\r\n\
+            \x1b]8;;zed-shell-integration:0/output\x1b\\\x1b[1mfile.rs\x1b[0m\r\n\
+            \x1b]8;;\x1b\\\
+            \x1b]8;;zed-shell-integration:1/prompt\x1b\\$ \x1b",
+            "Marks split across reads should be rewritten, other sequences should be kept as is"
+        );
+        assert_eq!(
+            integration.commands().cloned().collect::<Vec<_>>(),
+            vec![
+                ShellCommand {
+                    id: 0,
+                    command_line: Some("ls ; echo\\ok".to_string()),
+                    cwd: Some(PathBuf::from("/home/user")),
+                    exit_code: Some(2),
+                    executed: true,
+                    finished: true,
+                },
+                ShellCommand {
+                    id: 1,
+                    command_line: None,
+                    cwd: Some(PathBuf::from("/home/user")),
+                    exit_code: None,
+                    executed: false,
+                    finished: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_zone_is_tagged_again_after_program_links() {
+        let mut integration = ShellIntegration::default();
+        let output = process(
+            &[
+                b"\x1b]133;C\x07\x1b]8;;https://zed.dev\x1b\\zed\x1b]8;;\x1b\\ ok\r\n",
+                b"\x1b]133;D;0\x07\x1b]8;id=1;https://zed.dev\x07zed\x1b]8;id=1;\x07",
+            ],
+            &mut integration,
+        );
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "\x1b]8;;zed-shell-integration:0/output\x1b\\\
+            \x1b]8;;https://zed.dev\x1b\\zed\
+            \x1b]8;;zed-shell-integration:0/output\x1b\\ ok\r\n\
+            \x1b]8;;\x1b\\\
+            \x1b]8;id=1;https://zed.dev\x07zed\x1b]8;;\x1b\\",
+            "Links ended by a program should end in the zone's link, if the output is in a zone"
+        );
+    }
+
+    #[test]
+    fn test_cwd_reports() {
+        let mut integration = ShellIntegration::default();
+        let output = process(
+            &[b"\x1b]7;file://host/tmp/with%20space\x07\x1b]2;title\x07"],
+            &mut integration,
+        );
+        assert_eq!(
+            output, b"\x1b]7;file://host/tmp/with%20space\x07\x1b]2;title\x07",
+            "OSC 7 and other sequences should be passed through"
+        );
+        assert_eq!(integration.cwd(), Some(Path::new("/tmp/with space")));
+    }
+
+    #[test]
+    fn test_command_bounds() {
+        let mut integration = ShellIntegration::default();
+        let output = process(
+            &[
+                b"\x1b]133;A\x07$ \x1b]133;B\x07true\r\n\x1b]133;C\x07\x1b]133;D;0\x07",
+                b"\x1b]133;A\x07$ \x1b]133;B\x07cat\r\n\x1b]133;C\x07one\r\ntwo\r\n\x1b]133;D;1\x07",
+                b"\x1b]133;A\x07$ ",
+            ],
+            &mut integration,
+        );
+        let mut term = Term::new(Config::default(), &TerminalBounds::default(), VoidListener);
+        let mut processor: Processor = Processor::new();
+        processor.advance(&mut term, &output);
+
+        let bounds = command_bounds(&term);
+        assert_eq!(
+            bounds.iter().map(|command| command.id).collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+        assert_eq!(bounds[0].output, None, "`true` prints nothing");
+        let output = bounds[1].output.clone().unwrap();
+        assert_eq!(
+            term.bounds_to_string(*output.start(), *output.end())
+                .trim_end(),
+            "one\ntwo"
+        );
+        assert!(integration.command(1).unwrap().failed());
+    }
+}
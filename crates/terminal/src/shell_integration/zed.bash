@@ -0,0 +1,66 @@
+# Zed shell integration for bash, loaded with `bash --init-file`.
+# Marks prompts and commands with OSC 133 sequences, and reports command lines and the working directory with OSC 633.
+
+if [[ -n "$ZED_SHELL_INTEGRATION_LOGIN" ]]; then
+    unset ZED_SHELL_INTEGRATION_LOGIN
+    [[ -r /etc/profile ]] && . /etc/profile
+    if [[ -r ~/.bash_profile ]]; then
+        . ~/.bash_profile
+    elif [[ -r ~/.bash_login ]]; then
+        . ~/.bash_login
+    elif [[ -r ~/.profile ]]; then
+        . ~/.profile
+    fi
+else
+    [[ -r ~/.bashrc ]] && . ~/.bashrc
+fi
+
+if [[ -n "$__zed_shell_integration" ]]; then
+    return
+fi
+__zed_shell_integration=1
+
+__zed_escape() {
+    local value="${1//\\/\\\\}"
+    value="${value//;/\\x3b}"
+    value="${value//$'\n'/\\x0a}"
+    builtin printf '%s' "$value"
+}
+
+__zed_at_prompt=0
+__zed_in_command=0
+__zed_status=0
+
+__zed_save_status() {
+    __zed_status=$?
+}
+
+__zed_precmd() {
+    if [[ "$__zed_in_command" == 1 ]]; then
+        builtin printf '\e]133;D;%s\a' "$__zed_status"
+    fi
+    __zed_in_command=0
+    builtin printf '\e]633;P;Cwd=%s\a' "$(__zed_escape "$PWD")"
+    if [[ "$PS1" != *'133;A'* ]]; then
+        PS1="\[\e]133;A\a\]$PS1\[\e]133;B\a\]"
+    fi
+    __zed_at_prompt=1
+}
+
+__zed_preexec() {
+    # The DEBUG trap runs before every simple command, only the first one after the prompt starts the command.
+    if [[ "$__zed_at_prompt" != 1 || -n "$COMP_LINE" ]]; then
+        return
+    fi
+    __zed_at_prompt=0
+    __zed_in_command=1
+    local command_line
+    command_line="$(HISTTIMEFORMAT= builtin history 1)"
+    command_line="${command_line#*[0-9] }"
+    command_line="${command_line#"${command_line%%[![:space:]]*}"}"
+    builtin printf '\e]633;E;%s\a\e]133;C\a' "$(__zed_escape "$command_line")"
+}
+
+# Saving the exit status has to go first, and marking the prompt has to go last, around other prompt commands.
+PROMPT_COMMAND="__zed_save_status${PROMPT_COMMAND:+; $PROMPT_COMMAND}; __zed_precmd"
+trap '__zed_preexec' DEBUG
@@ -0,0 +1,42 @@
+# Zed shell integration for fish, loaded with `fish --init-command` after the user's configuration.
+# Marks prompts and commands with OSC 133 sequences, and reports command lines and the working directory with OSC 633.
+
+status is-interactive; or exit
+set --query __zed_shell_integration; and exit
+set --global __zed_shell_integration 1
+
+function __zed_escape
+    string replace --all '\\' '\\\\' -- $argv | string replace --all ';' '\\x3b' | string join '\\x0a'
+end
+
+function __zed_restore_status
+    return $argv[1]
+end
+
+function __zed_preexec --on-event fish_preexec
+    set --global __zed_in_command 1
+    printf '\e]633;E;%s\a\e]133;C\a' (__zed_escape $argv)
+end
+
+function __zed_postexec --on-event fish_postexec
+    set --local last_status $status
+    printf '\e]133;D;%s\a' $last_status
+    set --global __zed_in_command 0
+    __zed_restore_status $last_status
+end
+
+if functions --query fish_prompt
+    functions --copy fish_prompt __zed_user_fish_prompt
+else
+    function __zed_user_fish_prompt
+        printf '%s> ' (prompt_pwd)
+    end
+end
+
+function fish_prompt
+    set --local last_status $status
+    printf '\e]633;P;Cwd=%s\a\e]133;A\a' (__zed_escape $PWD)
+    __zed_restore_status $last_status
+    __zed_user_fish_prompt
+    printf '\e]133;B\a'
+end
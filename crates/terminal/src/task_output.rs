@@ -0,0 +1,145 @@
+//! The output of a task, split into lines as it is read from the pty, for the task's problem matchers.
+//!
+//! Reading the lines from the terminal grid would lose them once the scrollback is full and drops its oldest lines,
+//! so the pty output is followed as it arrives instead, keeping only the text printed on each line.
+
+use alacritty_terminal::vte::ansi::{Handler, LineClearMode, Processor};
+
+/// Longer lines are cut, so that output with no line breaks does not grow without bounds.
+const MAX_LINE_LEN: usize = 64 * 1024;
+const TAB_WIDTH: usize = 8;
+
+/// The lines of the task output read so far, until they are taken by the problem matchers.
+pub(crate) struct TaskOutputLines {
+    processor: Processor,
+    line: LineBuilder,
+}
+
+impl TaskOutputLines {
+    pub fn new() -> Self {
+        Self {
+            processor: Processor::new(),
+            line: LineBuilder::default(),
+        }
+    }
+
+    pub fn process(&mut self, bytes: &[u8]) {
+        self.processor.advance(&mut self.line, bytes);
+    }
+
+    /// Takes the lines completed since the last call, and the line still being printed if `flush` is set.
+    pub fn take_lines(&mut self, flush: bool) -> Vec<String> {
+        if flush && !self.line.cells.is_empty() {
+            self.line.finish_line();
+        }
+        std::mem::take(&mut self.line.lines)
+    }
+}
+
+/// Applies the printing and line editing control sequences, ignoring all others.
+#[derive(Default)]
+struct LineBuilder {
+    cells: Vec<char>,
+    column: usize,
+    lines: Vec<String>,
+}
+
+impl LineBuilder {
+    fn finish_line(&mut self) {
+        self.lines.push(self.cells.drain(..).collect());
+        self.column = 0;
+    }
+}
+
+impl Handler for LineBuilder {
+    fn input(&mut self, c: char) {
+        if self.column >= MAX_LINE_LEN {
+            return;
+        }
+        if self.column < self.cells.len() {
+            self.cells[self.column] = c;
+        } else {
+            self.cells.resize(self.column, ' ');
+            self.cells.push(c);
+        }
+        self.column += 1;
+    }
+
+    fn put_tab(&mut self, count: u16) {
+        for _ in 0..count {
+            self.column = ((self.column / TAB_WIDTH + 1) * TAB_WIDTH).min(MAX_LINE_LEN);
+        }
+    }
+
+    fn backspace(&mut self) {
+        self.column = self.column.saturating_sub(1);
+    }
+
+    fn carriage_return(&mut self) {
+        self.column = 0;
+    }
+
+    fn linefeed(&mut self) {
+        self.finish_line();
+    }
+
+    fn newline(&mut self) {
+        self.finish_line();
+    }
+
+    fn goto_col(&mut self, column: usize) {
+        self.column = column.min(MAX_LINE_LEN);
+    }
+
+    fn clear_line(&mut self, mode: LineClearMode) {
+        match mode {
+            LineClearMode::Right => self.cells.truncate(self.column),
+            LineClearMode::Left => {
+                let end = (self.column + 1).min(self.cells.len());
+                self.cells[..end].fill(' ');
+            }
+            LineClearMode::All => self.cells.clear(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TaskOutputLines;
+
+    fn lines(chunks: &[&str], flush: bool) -> Vec<String> {
+        let mut output = TaskOutputLines::new();
+        for chunk in chunks {
+            output.process(chunk.as_bytes());
+        }
+        output.take_lines(flush)
+    }
+
+    #[test]
+    fn test_task_output_lines() {
+        assert_eq!(
+            lines(&["one\r\ntw", "o\r\nthree"], false),
+            vec!["one", "two"],
+            "The line still being printed is kept until the output is flushed"
+        );
+        assert_eq!(
+            lines(&["one\r\ntwo\r\nthree"], true),
+            vec!["one", "two", "three"]
+        );
+        assert_eq!(
+            lines(
+                &["\x1b[1;31merror\x1b[0m: oops \x1b]8;;file:///a\x1b\\link\x1b]8;;\x1b\\\r\n"],
+                false
+            ),
+            vec!["error: oops link"],
+            "Styles and links are not part of the text"
+        );
+        assert_eq!(
+            lines(&["Compiling 1/3\rCompiling 2/3\r\x1b[KDone\r\n"], false),
+            vec!["Done"],
+            "Progress overwritten in place only leaves the last text"
+        );
+        assert_eq!(lines(&["ab\x08c\r\n"], false), vec!["ac"]);
+        assert_eq!(lines(&["a\tb\r\n"], false), vec!["a       b"]);
+    }
+}
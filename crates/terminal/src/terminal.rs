@@ -3,6 +3,8 @@ pub mod mappings;
 pub use alacritty_terminal;
 
 mod pty_info;
+mod shell_integration;
+mod task_output;
 pub mod terminal_settings;
 
 use alacritty_terminal::{
@@ -38,15 +40,18 @@ use mappings::mouse::{
 
 use collections::{HashMap, VecDeque};
 use futures::StreamExt;
+use parking_lot::Mutex;
 use pty_info::PtyProcessInfo;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use settings::Settings;
+use shell_integration::{CommandZone, ShellIntegration, ShellIntegrationPty};
 use smol::channel::{Receiver, Sender};
-use task::{HideStrategy, Shell, TaskId};
+use task::{HideStrategy, ProblemMatcher, ProblemMatcherParser, Shell, TaskId, TaskProblem};
+use task_output::TaskOutputLines;
 use terminal_settings::{AlternateScroll, CursorShape, TerminalSettings};
 use theme::{ActiveTheme, Theme};
-use util::{paths::home_dir, truncate_and_trailoff};
+use util::{ResultExt, paths::home_dir, truncate_and_trailoff};
 
 use std::{
     cmp::{self, min},
@@ -66,6 +71,8 @@ use gpui::{
 
 use crate::mappings::{colors::to_alac_rgb, keys::to_esc_str};
 
+pub use shell_integration::ShellCommand;
+
 actions!(
     terminal,
     [
@@ -80,6 +87,9 @@ actions!(
         ScrollPageDown,
         ScrollToTop,
         ScrollToBottom,
+        ScrollToPreviousCommand,
+        ScrollToNextCommand,
+        CopyLastCommandOutput,
         ToggleViMode,
     ]
 );
@@ -109,7 +119,14 @@ pub enum Event {
     SelectionsChanged,
     NewNavigationTarget(Option<MaybeNavigationTarget>),
     Open(MaybeNavigationTarget),
-    TaskLocatorReady { task_id: TaskId, success: bool },
+    TaskLocatorReady {
+        task_id: TaskId,
+        success: bool,
+    },
+    /// The task's problem matchers found new problems in its output, see [`Terminal::task_problems`].
+    TaskProblemsUpdated,
+    /// The task keeps running in the background and has finished its first cycle, see [`Terminal::task_ready`].
+    TaskReady,
 }
 
 #[derive(Clone, Debug)]
@@ -143,6 +160,10 @@ enum InternalEvent {
     // Adjusted mouse position, should open
     FindHyperlink(Point<Pixels>, bool),
     Copy,
+    // Shell integration events
+    ScrollToPreviousCommand,
+    ScrollToNextCommand,
+    CopyLastCommandOutput,
     // Vi mode events
     ToggleViMode,
     ViMotion(ViMotion),
@@ -349,6 +370,7 @@ impl TerminalBuilder {
         cursor_shape: CursorShape,
         alternate_scroll: AlternateScroll,
         max_scroll_history_lines: Option<usize>,
+        shell_integration: bool,
         is_ssh_terminal: bool,
         window: AnyWindowHandle,
         completion_tx: Sender<()>,
@@ -374,8 +396,20 @@ impl TerminalBuilder {
 
         let mut terminal_title_override = None;
 
+        // Tasks and debug terminals do not run interactive shells, and ssh terminals run them on the remote host.
+        let integration_shell =
+            if shell_integration && task.is_none() && !is_ssh_terminal && !debug_terminal {
+                shell_integration::inject(&shell, &mut env)
+                    .log_err()
+                    .flatten()
+            } else {
+                None
+            };
+        let shell_integration = integration_shell.is_some();
+        let pty_shell = integration_shell.unwrap_or_else(|| shell.clone());
+
         let pty_options = {
-            let alac_shell = match shell.clone() {
+            let alac_shell = match pty_shell {
                 Shell::System => {
                     #[cfg(target_os = "windows")]
                     {
@@ -426,6 +460,15 @@ impl TerminalBuilder {
                 .unwrap_or(DEFAULT_SCROLL_HISTORY_LINES)
                 .min(MAX_SCROLL_HISTORY_LINES)
         };
+        let task_output_scanner = task
+            .as_ref()
+            .filter(|task| !task.problem_matchers.is_empty())
+            .map(|task| {
+                TaskOutputScanner::new(ProblemMatcherParser::new(
+                    &task.problem_matchers,
+                    working_directory.as_deref(),
+                ))
+            });
         let config = Config {
             scrolling_history,
             default_cursor_style,
@@ -466,6 +509,14 @@ impl TerminalBuilder {
         };
 
         let pty_info = PtyProcessInfo::new(&pty);
+        let integration = Arc::new(Mutex::new(ShellIntegration::default()));
+        let pty = ShellIntegrationPty::new(
+            pty,
+            shell_integration.then(|| integration.clone()),
+            task_output_scanner
+                .as_ref()
+                .map(|scanner| scanner.output.clone()),
+        );
 
         //And connect them together
         let event_loop = EventLoop::new(
@@ -482,6 +533,8 @@ impl TerminalBuilder {
 
         let terminal = Terminal {
             task,
+            task_output_scanner,
+            shell_integration: integration,
             pty_tx: Notifier(pty_tx),
             completion_tx,
             term,
@@ -603,6 +656,15 @@ pub struct TerminalContent {
     pub cursor_char: char,
     pub terminal_bounds: TerminalBounds,
     pub last_hovered_word: Option<HoveredWord>,
+    /// Commands with their prompts on the lines displayed, if the shell reports its commands.
+    pub command_marks: Vec<CommandMark>,
+}
+
+/// A command, reported by the shell, and the line its prompt starts on.
+#[derive(Clone, Debug)]
+pub struct CommandMark {
+    pub line: Line,
+    pub command: ShellCommand,
 }
 
 #[derive(Clone)]
@@ -627,6 +689,7 @@ impl Default for TerminalContent {
             cursor_char: Default::default(),
             terminal_bounds: Default::default(),
             last_hovered_word: None,
+            command_marks: Vec::new(),
         }
     }
 }
@@ -659,6 +722,8 @@ pub struct Terminal {
     word_regex: RegexSearch,
     python_file_line_regex: RegexSearch,
     task: Option<TaskState>,
+    task_output_scanner: Option<TaskOutputScanner>,
+    shell_integration: Arc<Mutex<ShellIntegration>>,
     vi_mode_enabled: bool,
     debug_terminal: bool,
     is_ssh_terminal: bool,
@@ -675,6 +740,34 @@ pub struct TaskState {
     pub show_summary: bool,
     pub show_command: bool,
     pub show_rerun: bool,
+    pub problem_matchers: Vec<ProblemMatcher>,
+}
+
+/// Feeds the task output to the task's problem matchers, line by line, as the output arrives.
+struct TaskOutputScanner {
+    parser: ProblemMatcherParser,
+    /// The lines read from the pty, which the problem matchers have not seen yet.
+    output: Arc<Mutex<TaskOutputLines>>,
+}
+
+impl TaskOutputScanner {
+    fn new(parser: ProblemMatcherParser) -> Self {
+        Self {
+            parser,
+            output: Arc::new(Mutex::new(TaskOutputLines::new())),
+        }
+    }
+
+    /// Matches the lines output since the last scan, returns whether the problems found have changed.
+    /// The line being printed may still get more output, so it is only scanned after the task has finished.
+    fn scan(&mut self, task_finished: bool) -> bool {
+        let lines = self.output.lock().take_lines(task_finished);
+        let mut changed = false;
+        for line in &lines {
+            changed |= self.parser.process_line(line);
+        }
+        changed
+    }
 }
 
 /// A status of the current terminal tab's task.
@@ -745,6 +838,20 @@ impl Terminal {
             AlacTermEvent::Wakeup => {
                 cx.emit(Event::Wakeup);
 
+                let task_running = self
+                    .task
+                    .as_ref()
+                    .is_some_and(|task| task.status == TaskStatus::Running);
+                if let Some(scanner) = self.task_output_scanner.as_mut().filter(|_| task_running) {
+                    let was_ready = scanner.parser.is_ready();
+                    if scanner.scan(false) {
+                        cx.emit(Event::TaskProblemsUpdated);
+                        if !was_ready && scanner.parser.is_ready() {
+                            cx.emit(Event::TaskReady);
+                        }
+                    }
+                }
+
                 if self.pty_info.has_changed() {
                     cx.emit(Event::TitleChanged);
                 }
@@ -908,6 +1015,40 @@ impl Terminal {
                 term.scroll_to_point(*point);
                 self.refresh_hovered_word(window);
             }
+            InternalEvent::ScrollToPreviousCommand | InternalEvent::ScrollToNextCommand => {
+                let top_line = -(term.grid().display_offset() as i32);
+                let mut command_lines = shell_integration::command_bounds(term)
+                    .into_iter()
+                    .map(|command| command.start.line.0);
+                let target_line = if matches!(event, InternalEvent::ScrollToPreviousCommand) {
+                    command_lines.rev().find(|line| *line < top_line)
+                } else {
+                    command_lines.find(|line| *line > top_line)
+                };
+                let scroll = match target_line {
+                    Some(line) => AlacScroll::Delta(top_line - line),
+                    None if matches!(event, InternalEvent::ScrollToNextCommand) => {
+                        AlacScroll::Bottom
+                    }
+                    None => return,
+                };
+                self.process_terminal_event(&InternalEvent::Scroll(scroll), term, window, cx);
+            }
+            InternalEvent::CopyLastCommandOutput => {
+                let integration = self.shell_integration.lock();
+                let last_finished_command = shell_integration::command_bounds(term)
+                    .into_iter()
+                    .rev()
+                    .find(|command| {
+                        integration
+                            .command(command.id)
+                            .is_some_and(|command| command.finished)
+                    });
+                if let Some(output) = last_finished_command.and_then(|command| command.output) {
+                    let output = term.bounds_to_string(*output.start(), *output.end());
+                    cx.write_to_clipboard(ClipboardItem::new_string(output.trim_end().to_string()))
+                }
+            }
             InternalEvent::ToggleViMode => {
                 self.vi_mode_enabled = !self.vi_mode_enabled;
                 term.toggle_vi_mode();
@@ -925,7 +1066,11 @@ impl Terminal {
                 )
                 .grid_clamp(term, Boundary::Grid);
 
-                let link = term.grid().index(point).hyperlink();
+                let link = term
+                    .grid()
+                    .index(point)
+                    .hyperlink()
+                    .filter(|link| !shell_integration::is_mark_uri(link.uri()));
                 let found_word = if link.is_some() {
                     let mut min_index = point;
                     loop {
@@ -1036,7 +1181,7 @@ impl Terminal {
                             if let Some(path) = maybe_url_or_path.strip_prefix("file://") {
                                 MaybeNavigationTarget::PathLike(PathLikeTarget {
                                     maybe_path: path.to_string(),
-                                    terminal_dir: self.working_directory(),
+                                    terminal_dir: self.working_directory_at(term, point),
                                 })
                             } else {
                                 MaybeNavigationTarget::Url(maybe_url_or_path.clone())
@@ -1044,7 +1189,7 @@ impl Terminal {
                         } else {
                             MaybeNavigationTarget::PathLike(PathLikeTarget {
                                 maybe_path: maybe_url_or_path.clone(),
-                                terminal_dir: self.working_directory(),
+                                terminal_dir: self.working_directory_at(term, point),
                             })
                         };
                         if *open {
@@ -1211,6 +1356,27 @@ impl Terminal {
             .push_back(InternalEvent::Scroll(AlacScroll::Bottom));
     }
 
+    /// Scrolls to the closest command above the top of the viewport, if the shell reports its commands.
+    pub fn scroll_to_previous_command(&mut self) {
+        self.events
+            .push_back(InternalEvent::ScrollToPreviousCommand);
+    }
+
+    /// Scrolls to the closest command below the top of the viewport, or to the bottom if there is none.
+    pub fn scroll_to_next_command(&mut self) {
+        self.events.push_back(InternalEvent::ScrollToNextCommand);
+    }
+
+    /// Copies the output of the last finished command, if the shell reports its commands.
+    pub fn copy_last_command_output(&mut self) {
+        self.events.push_back(InternalEvent::CopyLastCommandOutput);
+    }
+
+    /// Commands run in the terminal, as reported by the shell integration, oldest first.
+    pub fn shell_commands(&self) -> Vec<ShellCommand> {
+        self.shell_integration.lock().commands().cloned().collect()
+    }
+
     ///Resize the terminal and the PTY.
     pub fn set_size(&mut self, new_bounds: TerminalBounds) {
         if self.last_content.terminal_bounds != new_bounds {
@@ -1392,26 +1558,58 @@ impl Terminal {
             self.process_terminal_event(&e, &mut terminal, window, cx)
         }
 
-        self.last_content = Self::make_content(&terminal, &self.last_content);
+        self.last_content = Self::make_content(
+            &terminal,
+            &self.last_content,
+            &self.shell_integration.lock(),
+        );
     }
 
-    fn make_content(term: &Term<ZedListener>, last_content: &TerminalContent) -> TerminalContent {
+    fn make_content(
+        term: &Term<ZedListener>,
+        last_content: &TerminalContent,
+        integration: &ShellIntegration,
+    ) -> TerminalContent {
         let content = term.renderable_content();
-        TerminalContent {
-            cells: content
-                .display_iter
-                //TODO: Add this once there's a way to retain empty lines
-                // .filter(|ic| {
-                //     !ic.flags.contains(Flags::HIDDEN)
-                //         && !(ic.bg == Named(NamedColor::Background)
-                //             && ic.c == ' '
-                //             && !ic.flags.contains(Flags::INVERSE))
-                // })
-                .map(|ic| IndexedCell {
+        let mut command_marks = Vec::<CommandMark>::new();
+        let cells = content
+            .display_iter
+            //TODO: Add this once there's a way to retain empty lines
+            // .filter(|ic| {
+            //     !ic.flags.contains(Flags::HIDDEN)
+            //         && !(ic.bg == Named(NamedColor::Background)
+            //             && ic.c == ' '
+            //             && !ic.flags.contains(Flags::INVERSE))
+            // })
+            .map(|ic| {
+                let mut cell = ic.cell.clone();
+                // Shell integration marks are links only to tag the cells, not to be shown or opened.
+                if let Some((id, zone)) = cell
+                    .hyperlink()
+                    .and_then(|link| shell_integration::parse_mark_uri(link.uri()))
+                {
+                    cell.set_hyperlink(None);
+                    if zone != CommandZone::Output
+                        && command_marks
+                            .last()
+                            .is_none_or(|mark| mark.command.id != id)
+                    {
+                        if let Some(command) = integration.command(id) {
+                            command_marks.push(CommandMark {
+                                line: ic.point.line,
+                                command: command.clone(),
+                            });
+                        }
+                    }
+                }
+                IndexedCell {
                     point: ic.point,
-                    cell: ic.cell.clone(),
-                })
-                .collect::<Vec<IndexedCell>>(),
+                    cell,
+                }
+            })
+            .collect::<Vec<IndexedCell>>();
+        TerminalContent {
+            cells,
             mode: content.mode,
             display_offset: content.display_offset,
             selection_text: term.selection_to_string(),
@@ -1420,6 +1618,7 @@ impl Terminal {
             cursor_char: term.grid()[content.cursor.point].c,
             terminal_bounds: last_content.terminal_bounds,
             last_hovered_word: last_content.last_hovered_word.clone(),
+            command_marks,
         }
     }
 
@@ -1771,11 +1970,30 @@ impl Terminal {
             // SSH host. Until we can do that, it doesn't make sense to display
             // the working directory on the client and persist that.
             None
+        } else if let Some(cwd) = self.shell_integration.lock().cwd() {
+            Some(cwd.to_path_buf())
         } else {
             self.client_side_working_directory()
         }
     }
 
+    /// Returns the working directory of the command whose output is at the point given,
+    /// if the shell reports its commands, and the current working directory otherwise.
+    fn working_directory_at(&self, term: &Term<ZedListener>, point: AlacPoint) -> Option<PathBuf> {
+        if !self.is_ssh_terminal {
+            let command_cwd = term
+                .grid()
+                .index(point)
+                .hyperlink()
+                .and_then(|link| shell_integration::parse_mark_uri(link.uri()))
+                .and_then(|(id, _)| self.shell_integration.lock().command(id)?.cwd.clone());
+            if command_cwd.is_some() {
+                return command_cwd;
+            }
+        }
+        self.working_directory()
+    }
+
     /// Returns the working directory of the process that's connected to the PTY.
     /// That means it returns the working directory of the local shell or program
     /// that's running inside the terminal.
@@ -1843,6 +2061,22 @@ impl Terminal {
         self.task.as_ref()
     }
 
+    /// Problems found in the task output by the task's problem matchers.
+    pub fn task_problems(&self) -> Vec<TaskProblem> {
+        self.task_output_scanner
+            .as_ref()
+            .map(|scanner| scanner.parser.problems())
+            .unwrap_or_default()
+    }
+
+    /// Whether the task keeps running in the background, e.g. watching for changes, and its problem matchers
+    /// have matched the end of its first cycle.
+    pub fn task_ready(&self) -> bool {
+        self.task_output_scanner
+            .as_ref()
+            .is_some_and(|scanner| scanner.parser.is_ready())
+    }
+
     pub fn debug_terminal(&self) -> bool {
         self.debug_terminal
     }
@@ -1873,6 +2107,11 @@ impl Terminal {
         if task.status != TaskStatus::Running {
             return;
         }
+        if let Some(scanner) = &mut self.task_output_scanner {
+            if scanner.scan(true) {
+                cx.emit(Event::TaskProblemsUpdated);
+            }
+        }
         match error_code {
             Some(error_code) => {
                 task.status.register_task_exit(error_code);
@@ -2141,15 +2380,18 @@ pub fn rgba_color(r: u8, g: u8, b: u8) -> Hsla {
 
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
+
     use alacritty_terminal::{
         index::{Column, Line, Point as AlacPoint},
         term::cell::Cell,
     };
     use gpui::{Pixels, Point, bounds, point, size};
     use rand::{Rng, distributions::Alphanumeric, rngs::ThreadRng, thread_rng};
+    use task::{ProblemMatcherDefinition, ProblemMatcherParser};
 
     use crate::{
-        IndexedCell, TerminalBounds, TerminalContent, content_index_for_mouse,
+        IndexedCell, TaskOutputScanner, TerminalBounds, TerminalContent, content_index_for_mouse,
         python_extract_path_and_line, rgb_for_index,
     };
 
@@ -2162,6 +2404,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_task_output_scanner_with_repetitive_output() {
+        let matcher = ProblemMatcherDefinition::Named("$gcc".to_string())
+            .resolve()
+            .unwrap();
+        let mut scanner = TaskOutputScanner::new(ProblemMatcherParser::new(
+            &[matcher],
+            Some(Path::new("/project")),
+        ));
+        // The same lines over and over, in chunks that split them, as a watcher or a flaky test would print them.
+        let output = "main.c:1:1: error: oops\r\n".repeat(100);
+        for chunk in output.as_bytes().chunks(7) {
+            scanner.output.lock().process(chunk);
+            scanner.scan(false);
+        }
+        scanner.output.lock().process(b"main.c:2:1: error: last");
+        scanner.scan(false);
+        assert_eq!(scanner.parser.problems().len(), 100);
+        scanner.scan(true);
+
+        let problems = scanner.parser.problems();
+        assert_eq!(
+            problems.len(),
+            101,
+            "Every line should be scanned exactly once"
+        );
+        assert!(
+            problems[..100]
+                .iter()
+                .all(|problem| problem.start_line == 0)
+        );
+        assert_eq!(problems[100].start_line, 1);
+    }
+
     #[test]
     fn test_mouse_to_cell_test() {
         let mut rng = thread_rng();
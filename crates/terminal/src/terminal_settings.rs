@@ -46,6 +46,7 @@ pub struct TerminalSettings {
     pub default_height: Pixels,
     pub detect_venv: VenvSettings,
     pub max_scroll_history_lines: Option<usize>,
+    pub shell_integration: bool,
     pub toolbar: Toolbar,
     pub scrollbar: ScrollbarSettings,
 }
@@ -219,6 +220,14 @@ pub struct TerminalSettingsContent {
     ///
     /// Default: 10_000
     pub max_scroll_history_lines: Option<usize>,
+    /// Whether to load Zed's shell integration into bash, zsh and fish terminals.
+    /// The shell integration marks prompts and commands in the terminal output,
+    /// which allows jumping between commands, copying the last command's output,
+    /// showing failed commands in the gutter and tracking the shell's working directory.
+    /// Existing terminals will not pick up this change until they are recreated.
+    ///
+    /// Default: false
+    pub shell_integration: Option<bool>,
     /// Toolbar related settings
     pub toolbar: Option<ToolbarContent>,
     /// Scrollbar-related settings
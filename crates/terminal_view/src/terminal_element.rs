@@ -47,6 +47,8 @@ pub struct LayoutState {
     hyperlink_tooltip: Option<AnyElement>,
     gutter: Pixels,
     block_below_cursor_element: Option<AnyElement>,
+    /// Displayed lines with prompts of the commands that have failed, marked in the gutter.
+    failed_command_lines: Vec<i32>,
 }
 
 /// Helper struct for converting data between Alacritty's cursor points, and displayed cursor points.
@@ -728,10 +730,16 @@ impl Element for TerminalElement {
                     cursor_char,
                     selection,
                     cursor,
+                    command_marks,
                     ..
                 } = &self.terminal.read(cx).last_content;
                 let mode = *mode;
                 let display_offset = *display_offset;
+                let failed_command_lines = command_marks
+                    .iter()
+                    .filter(|mark| mark.command.failed())
+                    .map(|mark| mark.line.0 + display_offset as i32)
+                    .collect::<Vec<_>>();
 
                 // searches, highlights to a single range representations
                 let mut relative_highlighted_ranges = Vec::new();
@@ -852,6 +860,7 @@ impl Element for TerminalElement {
                     hyperlink_tooltip,
                     gutter,
                     block_below_cursor_element,
+                    failed_command_lines,
                 }
             },
         )
@@ -921,6 +930,18 @@ impl Element for TerminalElement {
                         rect.paint(origin, &layout.dimensions, window);
                     }
 
+                    let failed_command_color = cx.theme().status().error;
+                    for line in &layout.failed_command_lines {
+                        let mark_bounds = Bounds::new(
+                            point(
+                                bounds.origin.x + layout.gutter * 0.25,
+                                origin.y + *line as f32 * layout.dimensions.line_height,
+                            ),
+                            size(layout.gutter * 0.25, layout.dimensions.line_height),
+                        );
+                        window.paint_quad(fill(mark_bounds, failed_command_color));
+                    }
+
                     for (relative_highlighted_range, color) in
                         layout.relative_highlighted_ranges.iter()
                     {
@@ -1,4 +1,4 @@
-use std::{cmp, ops::ControlFlow, path::PathBuf, sync::Arc, time::Duration};
+use std::{cell::RefCell, cmp, ops::ControlFlow, path::PathBuf, rc::Rc, sync::Arc, time::Duration};
 
 use crate::{
     TerminalView, default_working_directory,
@@ -9,19 +9,26 @@ use crate::{
 use breadcrumbs::Breadcrumbs;
 use collections::HashMap;
 use db::kvp::KEY_VALUE_STORE;
-use futures::future::join_all;
+use futures::{
+    FutureExt as _,
+    channel::oneshot,
+    future::{self, Either, LocalBoxFuture, Shared, join_all},
+};
 use gpui::{
     Action, AnyView, App, AsyncApp, AsyncWindowContext, Context, Corner, Entity, EventEmitter,
     ExternalPaths, FocusHandle, Focusable, IntoElement, ParentElement, Pixels, Render, Styled,
     Task, WeakEntity, Window, actions,
 };
 use itertools::Itertools;
-use project::{Fs, Project, ProjectEntryId, terminals::TerminalKind};
+use project::{Fs, Inventory, Project, ProjectEntryId, terminals::TerminalKind};
 use search::{BufferSearchBar, buffer_search::DivRegistrar};
 use settings::Settings;
-use task::{RevealStrategy, RevealTarget, ShellBuilder, SpawnInTerminal, TaskId};
+use task::{
+    DependsOrder, RevealStrategy, RevealTarget, ShellBuilder, SpawnInTerminal, TaskGraphNodeStatus,
+    TaskGraphStatus, TaskId,
+};
 use terminal::{
-    Terminal,
+    Event as TerminalEvent, TaskStatus, Terminal,
     terminal_settings::{TerminalDockPosition, TerminalSettings},
 };
 use ui::{
@@ -73,6 +80,7 @@ pub struct TerminalPanel {
     pending_serialization: Task<Option<()>>,
     pending_terminals_to_add: usize,
     deferred_tasks: HashMap<TaskId, Task<()>>,
+    task_graphs: HashMap<TaskId, Task<()>>,
     assistant_enabled: bool,
     assistant_tab_bar_button: Option<AnyView>,
     active: bool,
@@ -93,6 +101,7 @@ impl TerminalPanel {
             height: None,
             pending_terminals_to_add: 0,
             deferred_tasks: HashMap::default(),
+            task_graphs: HashMap::default(),
             assistant_enabled: false,
             assistant_tab_bar_button: None,
             active: false,
@@ -487,11 +496,25 @@ impl TerminalPanel {
     }
 
     fn spawn_task(&mut self, task: &SpawnInTerminal, window: &mut Window, cx: &mut Context<Self>) {
+        if task.dependencies.is_empty() {
+            self.start_task(task, window, cx).detach();
+        } else {
+            self.spawn_task_graph(task.clone(), window, cx);
+        }
+    }
+
+    /// Starts the task in a terminal, returning the terminal once the task is started.
+    fn start_task(
+        &mut self,
+        task: &SpawnInTerminal,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Option<Entity<Terminal>>> {
         let Ok(is_local) = self
             .workspace
             .update(cx, |workspace, cx| workspace.project().read(cx).is_local())
         else {
-            return;
+            return Task::ready(None);
         };
 
         let builder = ShellBuilder::new(is_local, &task.shell);
@@ -502,62 +525,184 @@ impl TerminalPanel {
             command_label,
             command,
             args,
+            dependencies: Vec::new(),
             ..task.clone()
         };
 
         if task.allow_concurrent_runs && task.use_new_terminal {
-            self.spawn_in_new_terminal(task, window, cx)
-                .detach_and_log_err(cx);
-            return;
+            let new_terminal = self.spawn_in_new_terminal(task, window, cx);
+            return cx.spawn(async move |_, _| new_terminal.await.log_err());
         }
 
         let mut terminals_for_task = self.terminals_for_task(&task.full_label, cx);
         let Some(existing) = terminals_for_task.pop() else {
-            self.spawn_in_new_terminal(task, window, cx)
-                .detach_and_log_err(cx);
-            return;
+            let new_terminal = self.spawn_in_new_terminal(task, window, cx);
+            return cx.spawn(async move |_, _| new_terminal.await.log_err());
         };
 
         let (existing_item_index, task_pane, existing_terminal) = existing;
         if task.allow_concurrent_runs {
-            self.replace_terminal(
+            return self.replace_terminal(
                 task,
                 task_pane,
                 existing_item_index,
                 existing_terminal,
                 window,
                 cx,
-            )
-            .detach();
-            return;
+            );
         }
 
+        let (started_tx, started_rx) = oneshot::channel();
         self.deferred_tasks.insert(
             task.id.clone(),
             cx.spawn_in(window, async move |terminal_panel, cx| {
                 wait_for_terminals_tasks(terminals_for_task, cx).await;
                 let task = terminal_panel.update_in(cx, |terminal_panel, window, cx| {
                     if task.use_new_terminal {
-                        terminal_panel
-                            .spawn_in_new_terminal(task, window, cx)
-                            .detach_and_log_err(cx);
-                        None
+                        let new_terminal = terminal_panel.spawn_in_new_terminal(task, window, cx);
+                        cx.spawn(async move |_, _| new_terminal.await.log_err())
                     } else {
-                        Some(terminal_panel.replace_terminal(
+                        terminal_panel.replace_terminal(
                             task,
                             task_pane,
                             existing_item_index,
                             existing_terminal,
                             window,
                             cx,
-                        ))
+                        )
                     }
                 });
-                if let Ok(Some(task)) = task {
-                    task.await;
+                if let Ok(task) = task {
+                    started_tx.send(task.await).ok();
                 }
             }),
         );
+        cx.spawn(async move |_, _| started_rx.await.ok().flatten())
+    }
+
+    /// Runs the task after all of its dependencies, reporting the progress into the task inventory.
+    fn spawn_task_graph(
+        &mut self,
+        task: SpawnInTerminal,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let graph_id = task.id.clone();
+        if let Some(inventory) = self.task_inventory(cx) {
+            inventory.update(cx, |inventory, _| {
+                inventory.start_task_graph(TaskGraphStatus::new(&task));
+            });
+        }
+        cx.notify();
+        self.task_graphs.insert(
+            graph_id.clone(),
+            cx.spawn_in(window, async move |terminal_panel, cx| {
+                let started_tasks = Rc::new(RefCell::new(HashMap::default()));
+                run_task_graph_node(
+                    terminal_panel.clone(),
+                    graph_id.clone(),
+                    task,
+                    started_tasks,
+                    cx.clone(),
+                )
+                .await;
+                terminal_panel
+                    .update(cx, |terminal_panel, cx| {
+                        terminal_panel.update_task_graph(&graph_id, cx, |graph| graph.finish());
+                    })
+                    .ok();
+            }),
+        );
+    }
+
+    fn render_task_graphs(&self, cx: &Context<Self>) -> Option<AnyElement> {
+        let inventory = self.task_inventory(cx)?;
+        let task_graphs = inventory.read(cx).task_graphs();
+        if task_graphs.is_empty() {
+            return None;
+        }
+
+        let graph_rows = task_graphs.iter().map(|graph| {
+            let root = &graph.nodes[0];
+            let (succeeded, total) = graph.progress();
+            let graph_id = root.id.clone();
+            let dismiss_button = (!graph.is_running()).then(|| {
+                let inventory = inventory.clone();
+                IconButton::new(
+                    SharedString::from(format!("dismiss-{}", root.id.0)),
+                    IconName::Close,
+                )
+                .icon_size(IconSize::XSmall)
+                .icon_color(Color::Muted)
+                .tooltip(Tooltip::text("Dismiss"))
+                .on_click(cx.listener(move |_, _, _, cx| {
+                    inventory.update(cx, |inventory, _| inventory.dismiss_task_graph(&graph_id));
+                    cx.notify();
+                }))
+            });
+            v_flex()
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .child(task_graph_status_icon(graph.summary()))
+                        .child(Label::new(root.label.clone()).size(LabelSize::Small))
+                        .child(
+                            Label::new(format!("{succeeded}/{total}"))
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                        .child(div().flex_1())
+                        .children(dismiss_button),
+                )
+                .children(graph.nodes.iter().skip(1).map(|node| {
+                    h_flex()
+                        .gap_1()
+                        .pl(px(12. * node.depth as f32))
+                        .child(task_graph_status_icon(node.status))
+                        .child(
+                            Label::new(node.label.clone())
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                }))
+        });
+
+        Some(
+            v_flex()
+                .id("task-graphs")
+                .max_h_40()
+                .overflow_y_scroll()
+                .px_2()
+                .py_1()
+                .gap_1()
+                .border_b_1()
+                .border_color(cx.theme().colors().border)
+                .children(graph_rows)
+                .into_any_element(),
+        )
+    }
+
+    fn task_inventory(&self, cx: &App) -> Option<Entity<Inventory>> {
+        let workspace = self.workspace.upgrade()?;
+        let project = workspace.read(cx).project().read(cx);
+        project.task_store().read(cx).task_inventory().cloned()
+    }
+
+    fn update_task_graph(
+        &mut self,
+        graph_id: &TaskId,
+        cx: &mut Context<Self>,
+        update: impl FnOnce(&mut TaskGraphStatus),
+    ) {
+        let Some(inventory) = self.task_inventory(cx) else {
+            return;
+        };
+        inventory.update(cx, |inventory, _| {
+            if let Some(graph) = inventory.task_graph_mut(graph_id) {
+                update(graph);
+            }
+        });
+        cx.notify();
     }
 
     pub fn spawn_in_new_terminal(
@@ -810,7 +955,7 @@ impl TerminalPanel {
         terminal_to_replace: Entity<TerminalView>,
         window: &mut Window,
         cx: &mut Context<Self>,
-    ) -> Task<Option<()>> {
+    ) -> Task<Option<Entity<Terminal>>> {
         let reveal = spawn_task.reveal;
         let reveal_target = spawn_task.reveal_target;
         let window_handle = window.window_handle();
@@ -833,7 +978,7 @@ impl TerminalPanel {
                 .log_err()?;
             terminal_to_replace
                 .update_in(cx, |terminal_to_replace, window, cx| {
-                    terminal_to_replace.set_terminal(new_terminal, window, cx);
+                    terminal_to_replace.set_terminal(new_terminal.clone(), window, cx);
                 })
                 .ok()?;
 
@@ -909,7 +1054,7 @@ impl TerminalPanel {
                 RevealStrategy::Never => {}
             }
 
-            Some(())
+            Some(new_terminal)
         })
     }
 
@@ -1161,6 +1306,189 @@ async fn wait_for_terminals_tasks(
     let _: Vec<()> = join_all(pending_tasks).await;
 }
 
+fn task_graph_status_icon(status: TaskGraphNodeStatus) -> Icon {
+    let (icon, color) = match status {
+        TaskGraphNodeStatus::Pending => (IconName::Circle, Color::Muted),
+        TaskGraphNodeStatus::Running => (IconName::Play, Color::Disabled),
+        TaskGraphNodeStatus::Succeeded => (IconName::Check, Color::Success),
+        TaskGraphNodeStatus::Failed => (IconName::XCircle, Color::Error),
+        TaskGraphNodeStatus::Skipped => (IconName::Dash, Color::Muted),
+    };
+    Icon::new(icon).size(IconSize::Small).color(color)
+}
+
+type StartedGraphTasks = Rc<RefCell<HashMap<TaskId, Shared<LocalBoxFuture<'static, bool>>>>>;
+
+/// Runs the task once its dependencies have finished, resolving into whether the task and all of its dependencies have succeeded.
+/// A task that several other tasks of the graph depend on is run once.
+fn run_task_graph_node(
+    terminal_panel: WeakEntity<TerminalPanel>,
+    graph_id: TaskId,
+    task: SpawnInTerminal,
+    started_tasks: StartedGraphTasks,
+    mut cx: AsyncWindowContext,
+) -> Shared<LocalBoxFuture<'static, bool>> {
+    if let Some(started) = started_tasks.borrow().get(&task.id) {
+        return started.clone();
+    }
+
+    let task_id = task.id.clone();
+    let run = {
+        let started_tasks = started_tasks.clone();
+        async move {
+            let dependencies_succeeded = match task.depends_order {
+                DependsOrder::Parallel => join_all(task.dependencies.iter().map(|dependency| {
+                    run_task_graph_node(
+                        terminal_panel.clone(),
+                        graph_id.clone(),
+                        dependency.clone(),
+                        started_tasks.clone(),
+                        cx.clone(),
+                    )
+                }))
+                .await
+                .into_iter()
+                .all(|succeeded| succeeded),
+                DependsOrder::Sequence => {
+                    let mut succeeded = true;
+                    for dependency in &task.dependencies {
+                        succeeded = run_task_graph_node(
+                            terminal_panel.clone(),
+                            graph_id.clone(),
+                            dependency.clone(),
+                            started_tasks.clone(),
+                            cx.clone(),
+                        )
+                        .await;
+                        if !succeeded {
+                            break;
+                        }
+                    }
+                    succeeded
+                }
+            };
+
+            let status = if !dependencies_succeeded {
+                TaskGraphNodeStatus::Skipped
+            } else if task.command.trim().is_empty() {
+                TaskGraphNodeStatus::Succeeded
+            } else {
+                set_task_graph_node_status(
+                    &terminal_panel,
+                    &graph_id,
+                    &task.id,
+                    TaskGraphNodeStatus::Running,
+                    &mut cx,
+                );
+                let terminal = match terminal_panel
+                    .update_in(&mut cx, |terminal_panel, window, cx| {
+                        terminal_panel.start_task(&task, window, cx)
+                    }) {
+                    Ok(started) => started.await,
+                    Err(_) => None,
+                };
+                match terminal {
+                    Some(terminal) => {
+                        if wait_for_task_ready(&terminal, &mut cx).await {
+                            // The task keeps running, so its status is updated once it finishes,
+                            // while the tasks depending on it are started already.
+                            cx.spawn(async move |cx| {
+                                let status = task_graph_node_status(
+                                    wait_for_task_success(terminal, cx).await,
+                                );
+                                set_task_graph_node_status(
+                                    &terminal_panel,
+                                    &graph_id,
+                                    &task.id,
+                                    status,
+                                    cx,
+                                );
+                            })
+                            .detach();
+                            return true;
+                        }
+                        task_graph_node_status(wait_for_task_success(terminal, &mut cx).await)
+                    }
+                    None => TaskGraphNodeStatus::Failed,
+                }
+            };
+            set_task_graph_node_status(&terminal_panel, &graph_id, &task.id, status, &mut cx);
+            status == TaskGraphNodeStatus::Succeeded
+        }
+    }
+    .boxed_local()
+    .shared();
+
+    started_tasks.borrow_mut().insert(task_id, run.clone());
+    run
+}
+
+fn set_task_graph_node_status(
+    terminal_panel: &WeakEntity<TerminalPanel>,
+    graph_id: &TaskId,
+    task_id: &TaskId,
+    status: TaskGraphNodeStatus,
+    cx: &mut AsyncWindowContext,
+) {
+    terminal_panel
+        .update(cx, |terminal_panel, cx| {
+            terminal_panel
+                .update_task_graph(graph_id, cx, |graph| graph.set_status(task_id, status));
+        })
+        .ok();
+}
+
+fn task_graph_node_status(succeeded: bool) -> TaskGraphNodeStatus {
+    if succeeded {
+        TaskGraphNodeStatus::Succeeded
+    } else {
+        TaskGraphNodeStatus::Failed
+    }
+}
+
+/// Waits for the task to finish or to get ready, resolving into whether it is ready while still running.
+/// Only the tasks that keep running in the background get ready, once their problem matchers match the end of their first cycle.
+async fn wait_for_task_ready(terminal: &Entity<Terminal>, cx: &mut AsyncWindowContext) -> bool {
+    let (ready_tx, ready_rx) = oneshot::channel();
+    let mut ready_tx = Some(ready_tx);
+    let Ok(_subscription) = cx.update(|_, cx| {
+        cx.subscribe(terminal, move |_, event, _| {
+            if let TerminalEvent::TaskReady = event {
+                if let Some(ready_tx) = ready_tx.take() {
+                    ready_tx.send(()).ok();
+                }
+            }
+        })
+    }) else {
+        return false;
+    };
+    let Ok((ready, completion)) = terminal.update(cx, |terminal, cx| {
+        (terminal.task_ready(), terminal.wait_for_completed_task(cx))
+    }) else {
+        return false;
+    };
+    if ready {
+        return true;
+    }
+    match future::select(completion, ready_rx).await {
+        Either::Left(_) => false,
+        Either::Right((ready, _)) => ready.is_ok(),
+    }
+}
+
+async fn wait_for_task_success(terminal: Entity<Terminal>, cx: &mut AsyncWindowContext) -> bool {
+    let Ok(completion) = terminal.update(cx, |terminal, cx| terminal.wait_for_completed_task(cx))
+    else {
+        return false;
+    };
+    completion.await;
+    terminal
+        .read_with(&*cx, |terminal, _| {
+            terminal.task().map(|task| task.status) == Some(TaskStatus::Completed { success: true })
+        })
+        .unwrap_or(false)
+}
+
 fn add_paths_to_terminal(
     pane: &mut Pane,
     paths: &[PathBuf],
@@ -1199,18 +1527,24 @@ impl Render for TerminalPanel {
         );
         BufferSearchBar::register(&mut registrar);
         let registrar = registrar.into_div();
+        let task_graphs = self.render_task_graphs(cx);
         self.workspace
             .update(cx, |workspace, cx| {
-                registrar.size_full().child(self.center.render(
-                    workspace.project(),
-                    &HashMap::default(),
-                    None,
-                    &self.active_pane,
-                    workspace.zoomed_item(),
-                    workspace.app_state(),
-                    window,
-                    cx,
-                ))
+                registrar
+                    .size_full()
+                    .flex()
+                    .flex_col()
+                    .children(task_graphs)
+                    .child(div().flex_1().min_h_0().child(self.center.render(
+                        workspace.project(),
+                        &HashMap::default(),
+                        None,
+                        &self.active_pane,
+                        workspace.zoomed_item(),
+                        workspace.app_state(),
+                        window,
+                        cx,
+                    )))
             })
             .ok()
             .map(|div| {
@@ -23,6 +23,7 @@ use postage::{oneshot, prelude::*};
 use regex::Regex;
 pub use rope::*;
 pub use selection::*;
+use serde::{Deserialize, Serialize};
 use std::{
     borrow::Cow,
     cmp::{self, Ordering, Reverse},
@@ -136,6 +137,171 @@ impl HistoryEntry {
     }
 }
 
+/// A buffer's undo and redo stacks as the plain text edits of each transaction. Unlike the
+/// buffer's operations, these don't depend on the buffer's replica, so they can be replayed onto
+/// a new buffer with the same text, e.g. after the file is reopened.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SerializedHistory {
+    /// The transactions that can be undone, the most recent last.
+    pub undo_stack: Vec<SerializedTransaction>,
+    /// The transactions that can be redone, the next one to redo last.
+    pub redo_stack: Vec<SerializedTransaction>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SerializedTransaction {
+    /// The edits made by the transaction, ordered and non-overlapping.
+    pub edits: Vec<SerializedEdit>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SerializedEdit {
+    /// The range of the edit, in the text before the transaction.
+    pub old_range: Range<usize>,
+    pub old_text: String,
+    pub new_text: String,
+}
+
+impl SerializedHistory {
+    pub fn is_empty(&self) -> bool {
+        self.undo_stack.is_empty() && self.redo_stack.is_empty()
+    }
+
+    /// Checks that the history can be replayed onto the given text, i.e. that undoing every
+    /// transaction and redoing every transaction from it only replaces the text they expect.
+    pub fn validate(&self, text: &str) -> Result<()> {
+        let mut undone_text = text.to_string();
+        for transaction in self.undo_stack.iter().rev() {
+            apply_serialized_edits(&mut undone_text, transaction.inverted_edits())
+                .context("undo history doesn't match the text")?;
+        }
+        let mut redone_text = text.to_string();
+        for transaction in self.redo_stack.iter().rev() {
+            apply_serialized_edits(&mut redone_text, transaction.edits.iter().cloned())
+                .context("redo history doesn't match the text")?;
+        }
+        Ok(())
+    }
+}
+
+impl SerializedTransaction {
+    fn len(&self) -> usize {
+        self.edits
+            .iter()
+            .map(|edit| edit.old_text.len() + edit.new_text.len())
+            .sum()
+    }
+
+    /// The edits that revert the transaction, in the text after it.
+    pub fn inverted_edits(&self) -> impl Iterator<Item = SerializedEdit> + '_ {
+        let mut delta = 0_isize;
+        self.edits.iter().map(move |edit| {
+            let start = (edit.old_range.start as isize + delta) as usize;
+            delta += edit.new_text.len() as isize - edit.old_text.len() as isize;
+            SerializedEdit {
+                old_range: start..start + edit.new_text.len(),
+                old_text: edit.new_text.clone(),
+                new_text: edit.old_text.clone(),
+            }
+        })
+    }
+}
+
+/// Applies ordered, non-overlapping edits to the text, failing if any of them doesn't replace
+/// the text it expects.
+fn apply_serialized_edits(
+    text: &mut String,
+    edits: impl IntoIterator<Item = SerializedEdit>,
+) -> Result<()> {
+    let mut edits = edits.into_iter().collect::<Vec<_>>();
+    edits.reverse();
+    let mut last_start = text.len();
+    for edit in edits {
+        anyhow::ensure!(
+            edit.old_range.end <= last_start
+                && text.get(edit.old_range.clone()) == Some(edit.old_text.as_str()),
+            "edit at {:?} doesn't match the text",
+            edit.old_range
+        );
+        last_start = edit.old_range.start;
+        text.replace_range(edit.old_range, &edit.new_text);
+    }
+    Ok(())
+}
+
+/// A copy of a buffer's undo and redo stacks, taken with [`Buffer::history_snapshot`].
+pub struct HistorySnapshot {
+    /// A copy of the buffer, or `None` if the snapshot was taken during a transaction.
+    buffer: Option<Buffer>,
+}
+
+impl HistorySnapshot {
+    /// Returns the undo and redo stacks as plain text edits, keeping the most recent
+    /// transactions whose edits fit within `max_len` bytes of text.
+    pub fn serialize(&self, max_len: usize) -> SerializedHistory {
+        let mut history = SerializedHistory::default();
+        let Some(source) = self.buffer.as_ref() else {
+            return history;
+        };
+
+        // Undo and redo every transaction on a copy of the buffer, recording what changed.
+        let mut remaining_len = max_len;
+        let mut buffer = source.scratch_copy();
+        loop {
+            let before = buffer.snapshot.clone();
+            if buffer.undo().is_none() {
+                break;
+            }
+            let transaction = SerializedTransaction {
+                edits: buffer
+                    .edits_since::<usize>(&before.version)
+                    .map(|edit| SerializedEdit {
+                        old_range: edit.new.clone(),
+                        old_text: buffer.text_for_range(edit.new).collect(),
+                        new_text: before.text_for_range(edit.old).collect(),
+                    })
+                    .collect(),
+            };
+            if transaction.len() > remaining_len {
+                break;
+            }
+            remaining_len -= transaction.len();
+            if !transaction.edits.is_empty() {
+                history.undo_stack.push(transaction);
+            }
+        }
+        history.undo_stack.reverse();
+
+        let mut buffer = source.scratch_copy();
+        loop {
+            let before = buffer.snapshot.clone();
+            if buffer.redo().is_none() {
+                break;
+            }
+            let transaction = SerializedTransaction {
+                edits: buffer
+                    .edits_since::<usize>(&before.version)
+                    .map(|edit| SerializedEdit {
+                        old_range: edit.old.clone(),
+                        old_text: before.text_for_range(edit.old).collect(),
+                        new_text: buffer.text_for_range(edit.new).collect(),
+                    })
+                    .collect(),
+            };
+            if transaction.len() > remaining_len {
+                break;
+            }
+            remaining_len -= transaction.len();
+            if !transaction.edits.is_empty() {
+                history.redo_stack.push(transaction);
+            }
+        }
+        history.redo_stack.reverse();
+
+        history
+    }
+}
+
 struct History {
     base_text: Rope,
     operations: TreeMap<clock::Lamport, Operation>,
@@ -1389,6 +1555,95 @@ impl Buffer {
         &self.history.operations
     }
 
+    /// Returns the undo and redo stacks as plain text edits, keeping the most recent
+    /// transactions whose edits fit within `max_len` bytes of text.
+    pub fn serialize_history(&self, max_len: usize) -> SerializedHistory {
+        self.history_snapshot().serialize(max_len)
+    }
+
+    /// Copies the undo and redo stacks, so that they can be serialized without holding onto the
+    /// buffer, e.g. on a background thread.
+    pub fn history_snapshot(&self) -> HistorySnapshot {
+        HistorySnapshot {
+            buffer: (self.history.transaction_depth == 0).then(|| self.scratch_copy()),
+        }
+    }
+
+    /// Replays a history taken with [`Buffer::serialize_history`] onto a buffer with the text the
+    /// history was taken at, so that its transactions can be undone and redone, and returns the
+    /// operations that did so. The buffer must not have a history of its own, and ends up with
+    /// the same text.
+    pub fn restore_history(&mut self, history: &SerializedHistory) -> Result<Vec<Operation>> {
+        anyhow::ensure!(
+            self.history.transaction_depth == 0
+                && self.history.undo_stack.is_empty()
+                && self.history.redo_stack.is_empty(),
+            "buffer already has an undo history"
+        );
+        history.validate(&self.text())?;
+
+        // Rewind to the text before the oldest transaction, without recording it in the history.
+        let mut operations = Vec::new();
+        self.start_transaction();
+        for transaction in history.undo_stack.iter().rev() {
+            operations.push(
+                self.edit(
+                    transaction
+                        .inverted_edits()
+                        .map(|edit| (edit.old_range, edit.new_text))
+                        .collect::<Vec<_>>(),
+                ),
+            );
+        }
+        if let Some((transaction_id, _)) = self.end_transaction() {
+            self.forget_transaction(transaction_id);
+        }
+
+        for transaction in history
+            .undo_stack
+            .iter()
+            .chain(history.redo_stack.iter().rev())
+        {
+            self.start_transaction();
+            operations.push(
+                self.edit(
+                    transaction
+                        .edits
+                        .iter()
+                        .map(|edit| (edit.old_range.clone(), edit.new_text.clone()))
+                        .collect::<Vec<_>>(),
+                ),
+            );
+            self.end_transaction();
+            self.finalize_last_transaction();
+        }
+        for _ in &history.redo_stack {
+            operations.extend(self.undo().map(|(_, operation)| operation));
+        }
+        Ok(operations)
+    }
+
+    /// A copy of the buffer and its history that can be edited without affecting this one.
+    fn scratch_copy(&self) -> Self {
+        Self {
+            snapshot: self.snapshot.clone(),
+            history: History {
+                base_text: self.history.base_text.clone(),
+                operations: self.history.operations.clone(),
+                undo_stack: self.history.undo_stack.clone(),
+                redo_stack: self.history.redo_stack.clone(),
+                transaction_depth: 0,
+                group_interval: self.history.group_interval,
+            },
+            deferred_ops: OperationQueue::new(),
+            deferred_replicas: HashSet::default(),
+            lamport_clock: self.lamport_clock,
+            subscriptions: Default::default(),
+            edit_id_resolvers: Default::default(),
+            wait_for_version_txs: Default::default(),
+        }
+    }
+
     pub fn undo(&mut self) -> Option<(TransactionId, Operation)> {
         if let Some(entry) = self.history.pop_undo() {
             let transaction = entry.transaction.clone();
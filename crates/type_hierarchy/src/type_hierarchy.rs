@@ -0,0 +1,479 @@
+use editor::{Bias, Editor, scroll::Autoscroll};
+use fuzzy::{StringMatch, StringMatchCandidate};
+use gpui::{
+    App, Context, DismissEvent, Entity, FontWeight, HighlightStyle, ParentElement, SharedString,
+    StyledText, Task, WeakEntity, Window, actions, rems,
+};
+use language::Buffer;
+use picker::{Picker, PickerDelegate};
+use project::{Project, TypeHierarchyItem};
+use std::sync::Arc;
+use util::ResultExt;
+use workspace::{
+    Workspace,
+    ui::{Color, Label, LabelCommon, LabelLike, ListItem, ListItemSpacing, Toggleable, v_flex},
+};
+
+actions!(type_hierarchy, [ShowSupertypes, ShowSubtypes]);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TypeHierarchyDirection {
+    Supertypes,
+    Subtypes,
+}
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace
+            .register_action(|workspace, _: &ShowSupertypes, window, cx| {
+                show_type_hierarchy(workspace, TypeHierarchyDirection::Supertypes, window, cx);
+            })
+            .register_action(|workspace, _: &ShowSubtypes, window, cx| {
+                show_type_hierarchy(workspace, TypeHierarchyDirection::Subtypes, window, cx);
+            });
+    })
+    .detach();
+}
+
+fn show_type_hierarchy(
+    workspace: &mut Workspace,
+    direction: TypeHierarchyDirection,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+        return;
+    };
+    let Some((buffer, position)) = editor.update(cx, |editor, cx| {
+        let head = editor.selections.newest_anchor().head();
+        editor.buffer().read(cx).text_anchor_for_position(head, cx)
+    }) else {
+        return;
+    };
+    let project = workspace.project().clone();
+    let prepare = project.update(cx, |project, cx| {
+        project.prepare_type_hierarchy(&buffer, position, cx)
+    });
+    cx.spawn_in(window, async move |workspace, cx| {
+        let Some(root) = prepare.await?.into_iter().next() else {
+            return Ok(());
+        };
+        workspace.update_in(cx, |workspace, window, cx| {
+            let handle = cx.entity().downgrade();
+            workspace.toggle_modal(window, cx, move |window, cx| {
+                let delegate = TypeHierarchyDelegate::new(handle, project, buffer, root, direction);
+                Picker::uniform_list(delegate, window, cx).width(rems(34.))
+            });
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
+pub struct TypeHierarchyDelegate {
+    workspace: WeakEntity<Workspace>,
+    project: Entity<Project>,
+    /// The buffer the hierarchy was prepared in; follow-up requests are routed through it.
+    buffer: Entity<Buffer>,
+    direction: TypeHierarchyDirection,
+    /// The type whose supertypes or subtypes are listed.
+    root: TypeHierarchyItem,
+    /// `None` until the related types of `root` are fetched.
+    items: Option<Vec<TypeHierarchyItem>>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+}
+
+impl TypeHierarchyDelegate {
+    fn new(
+        workspace: WeakEntity<Workspace>,
+        project: Entity<Project>,
+        buffer: Entity<Buffer>,
+        root: TypeHierarchyItem,
+        direction: TypeHierarchyDirection,
+    ) -> Self {
+        Self {
+            workspace,
+            project,
+            buffer,
+            direction,
+            root,
+            items: None,
+            matches: Vec::new(),
+            selected_index: 0,
+        }
+    }
+
+    fn filter(&mut self, query: &str, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let items = self.items.as_deref().unwrap_or_default();
+        self.matches = if query.is_empty() {
+            items
+                .iter()
+                .enumerate()
+                .map(|(candidate_id, item)| StringMatch {
+                    candidate_id,
+                    score: 0.,
+                    positions: Vec::new(),
+                    string: item.symbol.name.clone(),
+                })
+                .collect()
+        } else {
+            let candidates = items
+                .iter()
+                .enumerate()
+                .map(|(id, item)| StringMatchCandidate::new(id, &item.symbol.name))
+                .collect::<Vec<_>>();
+            cx.background_executor().block(fuzzy::match_strings(
+                &candidates,
+                query,
+                false,
+                100,
+                &Default::default(),
+                cx.background_executor().clone(),
+            ))
+        };
+        self.set_selected_index(0, window, cx);
+    }
+
+    fn open_item(
+        &self,
+        item: TypeHierarchyItem,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        let buffer = self.project.update(cx, |project, cx| {
+            project.open_buffer_for_symbol(&item.symbol, cx)
+        });
+        let workspace = self.workspace.clone();
+        cx.spawn_in(window, async move |_, cx| {
+            let buffer = buffer.await?;
+            workspace.update_in(cx, |workspace, window, cx| {
+                let position = buffer
+                    .read(cx)
+                    .clip_point_utf16(item.selection_range.start, Bias::Left);
+                let pane = workspace.active_pane().clone();
+                let editor =
+                    workspace.open_project_item::<Editor>(pane, buffer, true, true, window, cx);
+                editor.update(cx, |editor, cx| {
+                    editor.change_selections(Some(Autoscroll::center()), window, cx, |s| {
+                        s.select_ranges([position..position])
+                    });
+                });
+            })?;
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+}
+
+impl PickerDelegate for TypeHierarchyDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        match self.direction {
+            TypeHierarchyDirection::Supertypes => {
+                format!("Supertypes of {}...", self.root.symbol.name).into()
+            }
+            TypeHierarchyDirection::Subtypes => {
+                format!("Subtypes of {}...", self.root.symbol.name).into()
+            }
+        }
+    }
+
+    /// Opens the selected type; the secondary confirm lists its own supertypes or subtypes instead.
+    fn confirm(&mut self, secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(item) = self
+            .matches
+            .get(self.selected_index)
+            .and_then(|mat| self.items.as_ref()?.get(mat.candidate_id))
+            .cloned()
+        else {
+            return;
+        };
+
+        if secondary {
+            self.root = item;
+            self.items = None;
+            self.matches.clear();
+            cx.defer_in(window, |picker, window, cx| {
+                picker.set_query("", window, cx);
+                picker.refresh_placeholder(window, cx);
+                picker.refresh(window, cx);
+            });
+        } else {
+            self.open_item(item, window, cx);
+            cx.emit(DismissEvent);
+        }
+    }
+
+    fn dismissed(&mut self, _window: &mut Window, _cx: &mut Context<Picker<Self>>) {}
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        if self.items.is_some() {
+            self.filter(&query, window, cx);
+            return Task::ready(());
+        }
+
+        let root = self.root.clone();
+        let items = self.project.update(cx, |project, cx| match self.direction {
+            TypeHierarchyDirection::Supertypes => project.supertypes(&self.buffer, root, cx),
+            TypeHierarchyDirection::Subtypes => project.subtypes(&self.buffer, root, cx),
+        });
+        cx.spawn_in(window, async move |picker, cx| {
+            let items = items.await.log_err().unwrap_or_default();
+            picker
+                .update_in(cx, |picker, window, cx| {
+                    picker.delegate.items = Some(items);
+                    picker.delegate.filter(&query, window, cx);
+                })
+                .log_err();
+        })
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        if self.items.is_none() {
+            return Some("Loading...".into());
+        }
+        Some(
+            match self.direction {
+                TypeHierarchyDirection::Supertypes => "No supertypes found",
+                TypeHierarchyDirection::Subtypes => "No subtypes found",
+            }
+            .into(),
+        )
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let string_match = self.matches.get(ix)?;
+        let item = self.items.as_ref()?.get(string_match.candidate_id)?;
+
+        let mut label = item.symbol.name.clone();
+        if let Some(detail) = &item.detail {
+            label.push(' ');
+            label.push_str(detail);
+        }
+        let highlights = string_match
+            .positions
+            .iter()
+            .map(|pos| {
+                (
+                    *pos..pos + 1,
+                    HighlightStyle {
+                        font_weight: Some(FontWeight::BOLD),
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(
+                    v_flex()
+                        .child(
+                            LabelLike::new().child(
+                                StyledText::new(label).with_default_highlights(
+                                    &window.text_style().clone(),
+                                    highlights,
+                                ),
+                            ),
+                        )
+                        .child(
+                            Label::new(item.symbol.path.path.to_string_lossy().to_string())
+                                .color(Color::Muted),
+                        ),
+                ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+    use gpui::{SemanticVersion, TestAppContext, VisualTestContext};
+    use language::{FakeLspAdapter, Language, LanguageConfig, LanguageMatcher, Point};
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use std::path::Path;
+    use util::path;
+    use workspace::OpenOptions;
+
+    #[gpui::test]
+    async fn test_type_hierarchy(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/dir"),
+            json!({
+                "main.rs": "trait Named {}\ntrait Shape: Named {}\nstruct Circle;\nimpl Shape for Circle {}\n",
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+        let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+        language_registry.add(Arc::new(Language::new(
+            LanguageConfig {
+                name: "Rust".into(),
+                matcher: LanguageMatcher {
+                    path_suffixes: vec!["rs".to_string()],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            None,
+        )));
+        let mut fake_servers =
+            language_registry.register_fake_lsp("Rust", FakeLspAdapter::default());
+
+        let window = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let workspace = window.root(cx).unwrap();
+        let cx = &mut VisualTestContext::from_window(*window, cx);
+
+        let editor = workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.open_abs_path(
+                    Path::new(path!("/dir/main.rs")).to_path_buf(),
+                    OpenOptions::default(),
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap()
+            .downcast::<Editor>()
+            .unwrap();
+
+        let fake_server = fake_servers.next().await.unwrap();
+        fake_server.set_request_handler::<lsp::request::TypeHierarchyPrepare, _, _>(
+            |params, _| async move {
+                assert_eq!(
+                    params.text_document_position_params.position,
+                    lsp::Position::new(2, 7)
+                );
+                Ok(Some(vec![lsp_item("Circle", 2)]))
+            },
+        );
+        fake_server.set_request_handler::<lsp::request::TypeHierarchySupertypes, _, _>(
+            |params, _| async move {
+                Ok(Some(match params.item.name.as_str() {
+                    "Circle" => vec![lsp_item("Shape", 1)],
+                    "Shape" => vec![lsp_item("Named", 0)],
+                    _ => Vec::new(),
+                }))
+            },
+        );
+
+        editor.update_in(cx, |editor, window, cx| {
+            editor.change_selections(None, window, cx, |s| {
+                s.select_ranges([Point::new(2, 7)..Point::new(2, 7)])
+            });
+        });
+        cx.dispatch_action(ShowSupertypes);
+        cx.run_until_parked();
+
+        let picker = workspace.update(cx, |workspace, cx| {
+            workspace
+                .active_modal::<Picker<TypeHierarchyDelegate>>(cx)
+                .unwrap()
+        });
+        picker.update_in(cx, |picker, window, cx| {
+            assert_eq!(match_names(&picker.delegate), vec!["Shape"]);
+            assert_eq!(
+                picker.delegate.placeholder_text(window, cx).as_ref(),
+                "Supertypes of Circle..."
+            );
+            picker.delegate.confirm(true, window, cx);
+        });
+        cx.run_until_parked();
+
+        picker.update_in(cx, |picker, window, cx| {
+            assert_eq!(match_names(&picker.delegate), vec!["Named"]);
+            assert_eq!(
+                picker.delegate.placeholder_text(window, cx).as_ref(),
+                "Supertypes of Shape..."
+            );
+            picker.delegate.confirm(false, window, cx);
+        });
+        cx.run_until_parked();
+
+        editor.update(cx, |editor, cx| {
+            assert_eq!(
+                editor.selections.newest::<Point>(cx).head(),
+                Point::new(0, 6)
+            );
+        });
+    }
+
+    fn match_names(delegate: &TypeHierarchyDelegate) -> Vec<&str> {
+        delegate
+            .matches
+            .iter()
+            .map(|mat| mat.string.as_str())
+            .collect()
+    }
+
+    fn lsp_item(name: &str, line: u32) -> lsp::TypeHierarchyItem {
+        let start = if name == "Circle" { 7 } else { 6 };
+        lsp::TypeHierarchyItem {
+            name: name.to_string(),
+            kind: lsp::SymbolKind::INTERFACE,
+            tags: None,
+            detail: None,
+            uri: lsp::Url::from_file_path(path!("/dir/main.rs")).unwrap(),
+            range: lsp::Range::new(lsp::Position::new(line, 0), lsp::Position::new(line + 1, 0)),
+            selection_range: lsp::Range::new(
+                lsp::Position::new(line, start),
+                lsp::Position::new(line, start + name.len() as u32),
+            ),
+            data: None,
+        }
+    }
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let store = SettingsStore::test(cx);
+            cx.set_global(store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            release_channel::init(SemanticVersion::default(), cx);
+            language::init(cx);
+            Project::init_settings(cx);
+            workspace::init_settings(cx);
+            editor::init(cx);
+            super::init(cx);
+        });
+    }
+}
@@ -7,7 +7,10 @@ use editor::{
     display_map::ToDisplayPoint,
     scroll::Autoscroll,
 };
-use gpui::{Action, App, AppContext as _, Context, Global, Window, actions, impl_internal_actions};
+use gpui::{
+    Action, App, AppContext as _, Context, Global, Keystroke, Window, actions,
+    impl_internal_actions,
+};
 use itertools::Itertools;
 use language::Point;
 use multi_buffer::MultiBufferRow;
@@ -15,6 +18,8 @@ use regex::Regex;
 use schemars::JsonSchema;
 use search::{BufferSearchBar, SearchOptions};
 use serde::Deserialize;
+use serde_json::Value;
+use settings::Settings;
 use std::{
     io::Write,
     iter::Peekable,
@@ -24,14 +29,15 @@ use std::{
     sync::OnceLock,
     time::Instant,
 };
-use task::{HideStrategy, RevealStrategy, SpawnInTerminal, TaskId};
+use task::{DependsOrder, HideStrategy, RevealStrategy, SpawnInTerminal, TaskId};
 use ui::ActiveTheme;
 use util::ResultExt;
 use workspace::{SaveIntent, notifications::NotifyResultExt};
 use zed_actions::RevealTarget;
 
 use crate::{
-    ToggleMarksView, ToggleRegistersView, Vim,
+    CustomCommand, ToggleMarksView, ToggleRegistersView, Vim, VimSettings,
+    insert::NormalBefore,
     motion::{EndOfDocument, Motion, MotionKind, StartOfDocument},
     normal::{
         JoinLines,
@@ -166,6 +172,7 @@ impl_internal_actions!(
         WithRange,
         WithCount,
         OnMatchingLines,
+        NormalCommand,
         ShellExec,
         VimSet,
     ]
@@ -351,6 +358,10 @@ pub fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
         action.run(vim, window, cx)
     });
 
+    Vim::action(editor, cx, |vim, action: &NormalCommand, window, cx| {
+        action.run(vim, window, cx)
+    });
+
     Vim::action(editor, cx, |vim, action: &ShellExec, window, cx| {
         action.run(vim, window, cx)
     })
@@ -445,6 +456,17 @@ impl VimCommand {
         }
     }
 
+    /// Whether `name` is the full name of this command.
+    fn has_full_name(&self, name: &str) -> bool {
+        name.strip_prefix(self.prefix) == Some(self.suffix)
+    }
+
+    /// Whether `name` is the name of this command or one of the abbreviations it accepts.
+    fn has_name(&self, name: &str) -> bool {
+        name.strip_prefix(self.prefix)
+            .is_some_and(|suffix| self.suffix.starts_with(suffix))
+    }
+
     // TODO: ranges with search queries
     fn parse_range(query: &str) -> (Option<CommandRange>, String) {
         let mut chars = query.chars().peekable();
@@ -946,6 +968,20 @@ pub fn command_interceptor(mut input: &str, cx: &App) -> Vec<CommandInterceptRes
     let range_prefix = input[0..(input.len() - query.len())].to_string();
     let query = query.as_str().trim();
 
+    if let Some((name, args, action)) = parse_custom_command(query, range.clone(), cx) {
+        let mut string = ":".to_owned() + &range_prefix + &name;
+        if !args.is_empty() {
+            string.push(' ');
+            string.push_str(args);
+        }
+        let positions = generate_positions(&string, &(range_prefix + query));
+        return vec![CommandInterceptResult {
+            action,
+            string,
+            positions,
+        }];
+    }
+
     let action = if range.is_some() && query.is_empty() {
         Some(
             GoToLine {
@@ -1007,6 +1043,8 @@ pub fn command_interceptor(mut input: &str, cx: &App) -> Vec<CommandInterceptRes
         } else {
             None
         }
+    } else if let Some(normal) = NormalCommand::parse(query, range.clone()) {
+        Some(normal.boxed_clone())
     } else if query.contains('!') {
         ShellExec::parse(query, range.clone())
     } else {
@@ -1040,6 +1078,163 @@ pub fn command_interceptor(mut input: &str, cx: &App) -> Vec<CommandInterceptRes
     return Vec::default();
 }
 
+/// Parses a command defined in the `custom_commands` setting into its name, its arguments and
+/// the action that runs it. As in Vim, these commands start with an uppercase letter, and can be
+/// shortened to any prefix that only one of them starts with, unless that prefix also names a
+/// built-in command, such as `:T` for `:Term`.
+fn parse_custom_command<'a>(
+    query: &'a str,
+    range: Option<CommandRange>,
+    cx: &App,
+) -> Option<(String, &'a str, Box<dyn Action>)> {
+    if !query.starts_with(|c: char| c.is_ascii_uppercase()) {
+        return None;
+    }
+    let (name, args) = match query.split_once(char::is_whitespace) {
+        Some((name, args)) => (name, args.trim_start()),
+        None => (query, ""),
+    };
+    let builtin_name = name.strip_suffix('!').unwrap_or(name);
+    let builtins = commands(cx);
+    if builtins
+        .iter()
+        .any(|command| command.has_full_name(builtin_name))
+    {
+        return None;
+    }
+    let commands = &VimSettings::get_global(cx).custom_commands;
+    let (name, command) = match commands.get_key_value(name) {
+        Some(command) => command,
+        None => {
+            if builtins
+                .iter()
+                .any(|command| command.has_name(builtin_name))
+            {
+                return None;
+            }
+            commands
+                .iter()
+                .filter(|(candidate, _)| candidate.starts_with(name))
+                .exactly_one()
+                .ok()?
+        }
+    };
+
+    let action = match command {
+        CustomCommand::Action(action) => {
+            let action = build_custom_action(action, args, cx)?;
+            match range {
+                Some(range) => act_on_range(action, &range)?,
+                None => action,
+            }
+        }
+        CustomCommand::Keys(keys) => NormalCommand {
+            range,
+            keystrokes: keystrokes_for(keys, true, args),
+        }
+        .boxed_clone(),
+    };
+    Some((name.clone(), args, action))
+}
+
+/// Builds an action written as in a keymap, either as its name or as its name and arguments,
+/// replacing `<args>` in the arguments with those of the command.
+fn build_custom_action(action: &Value, args: &str, cx: &App) -> Option<Box<dyn Action>> {
+    let (name, data) = match action {
+        Value::String(name) => (name, None),
+        Value::Array(items) => match items.as_slice() {
+            [Value::String(name), data] => (name, Some(substitute_args(data.clone(), args))),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    cx.build_action(name, data).log_err()
+}
+
+fn substitute_args(value: Value, args: &str) -> Value {
+    match value {
+        Value::String(string) => Value::String(string.replace("<args>", args)),
+        Value::Array(items) => Value::Array(
+            items
+                .into_iter()
+                .map(|item| substitute_args(item, args))
+                .collect(),
+        ),
+        Value::Object(fields) => Value::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key, substitute_args(value, args)))
+                .collect(),
+        ),
+        value => value,
+    }
+}
+
+/// Converts text into the keystrokes that type it. With `notation`, keys can also be written in
+/// Vim's key notation, like `<esc>`, `<cr>` or `<c-w>`, and `<args>` types `args`.
+fn keystrokes_for(text: &str, notation: bool, args: &str) -> Vec<Keystroke> {
+    let mut keystrokes = Vec::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if notation && c == '<' {
+            if let Some(end) = rest.find('>') {
+                let name = &rest[1..end];
+                if name.eq_ignore_ascii_case("args") {
+                    keystrokes.extend(keystrokes_for(args, false, ""));
+                    rest = &rest[end + 1..];
+                    continue;
+                }
+                if let Some(keystroke) = keystroke_for_notation(name) {
+                    keystrokes.push(keystroke);
+                    rest = &rest[end + 1..];
+                    continue;
+                }
+            }
+        }
+        let keystroke = match c {
+            ' ' => Keystroke::parse("space"),
+            '\t' => Keystroke::parse("tab"),
+            '\n' => Keystroke::parse("enter"),
+            c => Keystroke::parse(&c.to_string()),
+        };
+        keystrokes.extend(keystroke.log_err());
+        rest = &rest[c.len_utf8()..];
+    }
+    keystrokes
+}
+
+/// Parses the inside of a key written in Vim's key notation, like `esc` or `c-w`.
+fn keystroke_for_notation(name: &str) -> Option<Keystroke> {
+    let name = name.to_ascii_lowercase();
+    let mut key = name.as_str();
+    let mut modifiers = String::new();
+    while key.len() > 2 && key.as_bytes()[1] == b'-' {
+        modifiers.push_str(match key.as_bytes()[0] {
+            b'c' => "ctrl-",
+            b's' => "shift-",
+            b'a' | b'm' => "alt-",
+            b'd' => "cmd-",
+            _ => return None,
+        });
+        key = &key[2..];
+    }
+    let key = match key {
+        "esc" => "escape",
+        "cr" | "enter" | "return" => "enter",
+        "bs" => "backspace",
+        "del" => "delete",
+        "lt" => "<",
+        "bar" => "|",
+        "bslash" => "\\",
+        "space" | "tab" | "up" | "down" | "left" | "right" | "home" | "end" | "pageup"
+        | "pagedown" | "insert" => key,
+        key if key.starts_with('f') && key[1..].parse::<u8>().is_ok() => key,
+        key if key.chars().count() == 1 && !modifiers.is_empty() => key,
+        _ => return None,
+    };
+    Keystroke::parse(&(modifiers + key)).ok()
+}
+
 fn generate_positions(string: &str, query: &str) -> Vec<usize> {
     let mut positions = Vec::new();
     let mut chars = query.chars();
@@ -1254,6 +1449,122 @@ impl OnMatchingLines {
     }
 }
 
+/// Types keystrokes in normal mode, as `:normal` does: once at each cursor, or at the start of
+/// each line in a range.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct NormalCommand {
+    range: Option<CommandRange>,
+    keystrokes: Vec<Keystroke>,
+}
+
+impl NormalCommand {
+    /// Parses `norm[al][!] {keys}`. Zed has no remapping to bypass, so `!` changes nothing.
+    pub(crate) fn parse(query: &str, range: Option<CommandRange>) -> Option<Self> {
+        let rest = query.strip_prefix("norm")?;
+        let rest = ["al", "a", ""]
+            .iter()
+            .find_map(|suffix| rest.strip_prefix(suffix))?;
+        let rest = rest.strip_prefix('!').unwrap_or(rest);
+        if !rest.starts_with(char::is_whitespace) {
+            return None;
+        }
+        let keys = rest.trim_start();
+        if keys.is_empty() {
+            return None;
+        }
+        Some(Self {
+            range,
+            keystrokes: keystrokes_for(keys, false, ""),
+        })
+    }
+
+    pub fn run(&self, vim: &mut Vim, window: &mut Window, cx: &mut Context<Vim>) {
+        let result = vim.update_editor(window, cx, |vim, editor, window, cx| {
+            let Some(range) = self.range.as_ref() else {
+                let positions = editor
+                    .selections
+                    .disjoint_anchors()
+                    .iter()
+                    .map(|selection| selection.head())
+                    .collect::<Vec<_>>();
+                return anyhow::Ok(positions);
+            };
+            let range = range.buffer_range(vim, editor, window, cx)?;
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            anyhow::Ok(
+                (range.start.0..=range.end.0)
+                    .map(|row| snapshot.anchor_before(Point::new(row, 0)))
+                    .collect(),
+            )
+        });
+
+        let positions = match result {
+            None => return,
+            Some(e @ Err(_)) => {
+                let Some(workspace) = vim.workspace(window) else {
+                    return;
+                };
+                workspace.update(cx, |workspace, cx| {
+                    e.notify_err(workspace, cx);
+                });
+                return;
+            }
+            Some(Ok(result)) => result,
+        };
+        let Some(editor) = vim.editor() else {
+            return;
+        };
+        if vim.mode.is_visual() {
+            vim.switch_mode(Mode::Normal, false, window, cx);
+        }
+
+        let keystrokes = self.keystrokes.clone();
+        cx.spawn_in(window, async move |vim, cx| {
+            editor.update_in(cx, |editor, window, cx| {
+                editor.start_transaction_at(Instant::now(), window, cx);
+            })?;
+            for position in positions {
+                editor.update_in(cx, |editor, window, cx| {
+                    editor.change_selections(None, window, cx, |s| {
+                        s.select_anchor_ranges([position..position]);
+                    });
+                })?;
+                for keystroke in &keystrokes {
+                    cx.update(|window, cx| {
+                        let focused = window.focused(cx);
+                        window.dispatch_keystroke(keystroke.clone(), cx);
+                        // As with `SendKeystrokes`, focus changes need to be applied before the
+                        // next keystroke for vim mode to see them.
+                        if window.focused(cx) != focused {
+                            window.draw(cx);
+                        }
+                    })?;
+                }
+                vim.update_in(cx, |vim, window, cx| vim.finish_normal_command(window, cx))?;
+            }
+            editor.update(cx, |editor, cx| {
+                editor.end_transaction_at(Instant::now(), cx);
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+}
+
+impl Vim {
+    /// Completes or abandons whatever the keys of a `:normal` command left unfinished, as if
+    /// escape was pressed after them.
+    fn finish_normal_command(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.clear_operator(window, cx);
+        match self.mode {
+            Mode::Insert | Mode::Replace => self.normal_before(&NormalBefore, window, cx),
+            Mode::Visual | Mode::VisualLine | Mode::VisualBlock => {
+                self.switch_mode(Mode::Normal, false, window, cx)
+            }
+            Mode::Normal | Mode::HelixNormal | Mode::HelixSelect => {}
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct ShellExec {
     command: String,
@@ -1449,6 +1760,9 @@ impl ShellExec {
                         show_summary: false,
                         show_command: false,
                         show_rerun: false,
+                        dependencies: Vec::new(),
+                        depends_order: DependsOrder::default(),
+                        problem_matchers: Vec::new(),
                     }),
                 });
             });
@@ -1592,12 +1906,15 @@ mod test {
     use std::path::Path;
 
     use crate::{
-        state::Mode,
+        CustomCommand, VimSettings,
+        state::{Mode, VimGlobals},
         test::{NeovimBackedTestContext, VimTestContext},
     };
+    use collections::HashMap;
     use editor::Editor;
     use gpui::{Context, TestAppContext};
     use indoc::indoc;
+    use settings::SettingsStore;
     use util::path;
     use workspace::Workspace;
 
@@ -1910,4 +2227,162 @@ mod test {
             a
             ˇa"});
     }
+
+    #[gpui::test]
+    async fn test_command_normal(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state(
+            indoc! {"
+            ˇa
+            b
+            c"},
+            Mode::Normal,
+        );
+        cx.simulate_keystrokes(": % n o r m space A x enter");
+        cx.assert_state(
+            indoc! {"
+            ax
+            bx
+            cˇx"},
+            Mode::Normal,
+        );
+
+        cx.set_state("one ˇtwo three", Mode::Normal);
+        cx.simulate_keystrokes(": n o r m a l ! space d w enter");
+        cx.assert_state("one ˇthree", Mode::Normal);
+
+        cx.set_state(
+            indoc! {"
+            ˇa
+            b
+            a"},
+            Mode::Normal,
+        );
+        cx.simulate_keystrokes(": g / a / n o r m space A ! enter");
+        cx.assert_state(
+            indoc! {"
+            a!
+            b
+            aˇ!"},
+            Mode::Normal,
+        );
+    }
+
+    #[gpui::test]
+    async fn test_custom_commands(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.update_global(|store: &mut SettingsStore, cx| {
+            store.update_user_settings::<VimSettings>(cx, |s| {
+                let mut custom_commands = HashMap::default();
+                custom_commands.insert(
+                    "Todo".into(),
+                    CustomCommand::Keys("OTODO: <args><esc>".into()),
+                );
+                custom_commands.insert(
+                    "Send".into(),
+                    CustomCommand::Action(serde_json::json!([
+                        "workspace::SendKeystrokes",
+                        "<args>"
+                    ])),
+                );
+                s.custom_commands = Some(custom_commands);
+            });
+        });
+
+        cx.set_state(
+            indoc! {"
+            ˇa
+            b"},
+            Mode::Normal,
+        );
+        cx.simulate_keystrokes(": T o d o space f i x enter");
+        cx.assert_state(
+            indoc! {"
+            TODO: fiˇx
+            a
+            b"},
+            Mode::Normal,
+        );
+
+        cx.simulate_keystrokes(": S e n space d d enter");
+        cx.assert_state(
+            indoc! {"
+            ˇa
+            b"},
+            Mode::Normal,
+        );
+    }
+
+    #[gpui::test]
+    async fn test_custom_commands_sharing_builtin_prefix(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.update_global(|store: &mut SettingsStore, cx| {
+            store.update_user_settings::<VimSettings>(cx, |s| {
+                let mut custom_commands = HashMap::default();
+                custom_commands
+                    .insert("Sign".into(), CustomCommand::Keys("O-- <args><esc>".into()));
+                custom_commands.insert("Ex".into(), CustomCommand::Keys("dd".into()));
+                custom_commands.insert("Explore".into(), CustomCommand::Keys("dd".into()));
+                s.custom_commands = Some(custom_commands);
+            });
+        });
+
+        // The command each input runs, and the name of its action.
+        let command_for = |cx: &mut VimTestContext, input: &str| {
+            cx.update(|_, cx| {
+                let result = command_interceptor(input, cx).into_iter().next()?;
+                Some((result.string, result.action.name().to_string()))
+            })
+        };
+        let builtin = |string: &str, action: &str| Some((string.to_string(), action.to_string()));
+        let custom = |string: &str| Some((string.to_string(), "vim::NormalCommand".to_string()));
+
+        // Abbreviations of built-in commands are not taken over by custom commands.
+        assert_eq!(
+            command_for(&mut cx, "S"),
+            builtin(":Sexplore", "project_panel::ToggleFocus")
+        );
+        assert_eq!(
+            command_for(&mut cx, "E"),
+            builtin(":Explore", "project_panel::ToggleFocus")
+        );
+        // Custom commands can still be shortened where no built-in command matches.
+        assert_eq!(command_for(&mut cx, "Si"), custom(":Sign"));
+        assert_eq!(command_for(&mut cx, "Si me"), custom(":Sign me"));
+        // A custom command's full name is used over a built-in abbreviation, but not over the
+        // full name of a built-in command.
+        assert_eq!(command_for(&mut cx, "Ex"), custom(":Ex"));
+        assert_eq!(
+            command_for(&mut cx, "Explore"),
+            builtin(":Explore", "project_panel::ToggleFocus")
+        );
+
+        cx.set_state("ˇa\nb", Mode::Normal);
+        cx.simulate_keystrokes(": S i space m e enter");
+        cx.assert_state("-- mˇe\na\nb", Mode::Normal);
+    }
+
+    #[gpui::test]
+    async fn test_command_history(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.update_global(|store: &mut SettingsStore, cx| {
+            store.update_user_settings::<VimSettings>(cx, |s| s.history_size = Some(2));
+        });
+
+        cx.set_state("ˇa\nb\nc\nd", Mode::Normal);
+        cx.simulate_keystrokes(": 2 enter");
+        cx.simulate_keystrokes(": 3 enter");
+        cx.simulate_keystrokes(": 2 enter");
+        cx.simulate_keystrokes(": 4 enter");
+        cx.assert_state("a\nb\nc\nˇd", Mode::Normal);
+        cx.read(|cx| {
+            assert_eq!(cx.global::<VimGlobals>().command_history, vec!["2", "4"]);
+        });
+
+        cx.simulate_keystrokes("\" : p");
+        cx.assert_state("a\nb\nc\ndˇ4", Mode::Normal);
+    }
 }
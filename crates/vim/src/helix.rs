@@ -1,16 +1,106 @@
-use editor::{DisplayPoint, Editor, movement, scroll::Autoscroll};
-use gpui::{Action, actions};
+mod select;
+
+use editor::{
+    ClipboardSelection, DisplayPoint, Editor, display_map::DisplaySnapshot, movement,
+    scroll::Autoscroll,
+};
+use gpui::{Action, actions, impl_actions};
 use gpui::{Context, Window};
-use language::{CharClassifier, CharKind};
+use language::{CharClassifier, CharKind, Point, Selection};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::{ops::Range, sync::Arc};
 
 use crate::motion::MotionKind;
+use crate::object::Object;
+use crate::state::{Operator, Register};
+use crate::surrounds::SurroundsType;
 use crate::{Vim, motion::Motion, state::Mode};
 
-actions!(vim, [HelixNormalAfter, HelixDelete]);
+pub use select::RegexSelection;
+
+actions!(
+    vim,
+    [
+        HelixNormalAfter,
+        HelixDelete,
+        HelixChange,
+        HelixYank,
+        HelixReplaceWithRegister,
+        HelixSurroundAdd,
+    ]
+);
+
+/// Pastes the selected register after each selection, or before it, and selects what was
+/// pasted.
+#[derive(Clone, Deserialize, JsonSchema, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct HelixPaste {
+    #[serde(default)]
+    before: bool,
+}
+
+impl_actions!(vim, [HelixPaste]);
 
 pub fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
     Vim::action(editor, cx, Vim::helix_normal_after);
     Vim::action(editor, cx, Vim::helix_delete);
+    Vim::action(editor, cx, Vim::helix_change);
+    Vim::action(editor, cx, Vim::helix_yank);
+    Vim::action(editor, cx, Vim::helix_paste);
+    Vim::action(editor, cx, Vim::helix_replace_with_register);
+    Vim::action(editor, cx, Vim::helix_surround_add);
+    select::register(editor, cx);
+}
+
+/// The character under the block cursor of a selection in helix mode. An empty selection is a
+/// cursor on the character after it; otherwise it's the last character selected, or the first
+/// one when the selection is reversed.
+fn helix_cursor(map: &DisplaySnapshot, selection: &Selection<DisplayPoint>) -> DisplayPoint {
+    if selection.is_empty() || selection.reversed {
+        selection.head()
+    } else {
+        movement::left(map, selection.head())
+    }
+}
+
+/// The character at the other end of a selection from its cursor.
+fn helix_anchor(map: &DisplaySnapshot, selection: &Selection<DisplayPoint>) -> DisplayPoint {
+    if selection.is_empty() || !selection.reversed {
+        selection.tail()
+    } else {
+        movement::left(map, selection.tail())
+    }
+}
+
+/// The range a selection acts on, in which a cursor covers the character under it.
+fn helix_char_range(
+    map: &DisplaySnapshot,
+    selection: &Selection<DisplayPoint>,
+) -> Range<DisplayPoint> {
+    if selection.is_empty() {
+        selection.start..movement::right(map, selection.start)
+    } else {
+        selection.range()
+    }
+}
+
+/// Selects the characters from `anchor` to `cursor`, both included.
+fn select_inclusive(
+    map: &DisplaySnapshot,
+    selection: &mut Selection<DisplayPoint>,
+    anchor: DisplayPoint,
+    cursor: DisplayPoint,
+) {
+    if cursor < anchor {
+        selection.start = cursor;
+        selection.end = movement::right(map, anchor);
+        selection.reversed = true;
+    } else {
+        selection.start = anchor;
+        selection.end = movement::right(map, cursor);
+        selection.reversed = false;
+    }
 }
 
 impl Vim {
@@ -47,6 +137,7 @@ impl Vim {
         cx: &mut Context<Self>,
         mut is_boundary: impl FnMut(char, char, &CharClassifier) -> bool,
     ) {
+        let extend = self.mode == Mode::HelixSelect;
         self.update_editor(window, cx, |_, editor, window, cx| {
             editor.change_selections(Some(Autoscroll::fit()), window, cx, |s| {
                 s.move_with(|map, selection| {
@@ -56,6 +147,26 @@ impl Vim {
                         return;
                     }
 
+                    if extend {
+                        let classifier = map
+                            .buffer_snapshot
+                            .char_classifier_at(selection.head().to_point(map));
+                        let anchor = helix_anchor(map, selection);
+                        let mut head = movement::right(map, helix_cursor(map, selection));
+                        for _ in 0..times {
+                            let (_, new_head) =
+                                movement::find_boundary_trail(map, head, |left, right| {
+                                    is_boundary(left, right, &classifier)
+                                });
+                            if new_head == head {
+                                break;
+                            }
+                            head = new_head;
+                        }
+                        select_inclusive(map, selection, anchor, movement::left(map, head));
+                        return;
+                    }
+
                     // collapse to block cursor
                     if selection.tail() < selection.head() {
                         selection.set_tail(movement::left(map, selection.head()), selection.goal);
@@ -100,6 +211,7 @@ impl Vim {
         cx: &mut Context<Self>,
         mut is_boundary: impl FnMut(char, char, &CharClassifier) -> bool,
     ) {
+        let extend = self.mode == Mode::HelixSelect;
         self.update_editor(window, cx, |_, editor, window, cx| {
             editor.change_selections(Some(Autoscroll::fit()), window, cx, |s| {
                 s.move_with(|map, selection| {
@@ -109,6 +221,27 @@ impl Vim {
                         return;
                     }
 
+                    if extend {
+                        let classifier = map
+                            .buffer_snapshot
+                            .char_classifier_at(selection.head().to_point(map));
+                        let anchor = helix_anchor(map, selection);
+                        let mut head = helix_cursor(map, selection);
+                        for _ in 0..times {
+                            let (_, new_head) = movement::find_preceding_boundary_trail(
+                                map,
+                                head,
+                                |left, right| is_boundary(left, right, &classifier),
+                            );
+                            if new_head == head {
+                                break;
+                            }
+                            head = new_head;
+                        }
+                        select_inclusive(map, selection, anchor, head);
+                        return;
+                    }
+
                     // collapse to block cursor
                     if selection.tail() < selection.head() {
                         selection.set_tail(movement::left(map, selection.head()), selection.goal);
@@ -162,22 +295,56 @@ impl Vim {
             editor.change_selections(Some(Autoscroll::fit()), window, cx, |s| {
                 s.move_with(|map, selection| {
                     let goal = selection.goal;
-                    let cursor = if selection.is_empty() || selection.reversed {
-                        selection.head()
-                    } else {
-                        movement::left(map, selection.head())
-                    };
+                    let cursor = helix_cursor(map, selection);
 
-                    let (point, goal) = motion
+                    let (mut point, goal) = motion
                         .move_point(map, cursor, selection.goal, times, &text_layout_details)
                         .unwrap_or((cursor, goal));
 
+                    // Helix doesn't clip at line ends, so the end of the line is past its last
+                    // character.
+                    if matches!(motion, Motion::EndOfLine { .. }) && point.column() > 0 {
+                        point = movement::left(map, point);
+                    }
+
                     selection.collapse_to(point, goal)
                 })
             });
         });
     }
 
+    /// Selects from the cursor to where the motion moves it, both included. When `extend` is
+    /// set, the other end of the selection stays where it is instead.
+    fn helix_select_motion(
+        &mut self,
+        motion: Motion,
+        times: Option<usize>,
+        extend: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.update_editor(window, cx, |_, editor, window, cx| {
+            let text_layout_details = editor.text_layout_details(window);
+            editor.change_selections(Some(Autoscroll::fit()), window, cx, |s| {
+                s.move_with(|map, selection| {
+                    let cursor = helix_cursor(map, selection);
+                    let anchor = if extend {
+                        helix_anchor(map, selection)
+                    } else {
+                        cursor
+                    };
+                    let Some((point, goal)) =
+                        motion.move_point(map, cursor, selection.goal, times, &text_layout_details)
+                    else {
+                        return;
+                    };
+                    select_inclusive(map, selection, anchor, point);
+                    selection.goal = goal;
+                })
+            });
+        });
+    }
+
     pub fn helix_move_cursor(
         &mut self,
         motion: Motion,
@@ -235,10 +402,58 @@ impl Vim {
                     found
                 })
             }
+            Motion::FindForward { .. } | Motion::FindBackward { .. } => {
+                let extend = self.mode == Mode::HelixSelect;
+                self.helix_select_motion(motion, times, extend, window, cx)
+            }
+            _ if self.mode == Mode::HelixSelect => {
+                self.helix_select_motion(motion, times, true, window, cx)
+            }
             _ => self.helix_move_and_collapse(motion, times, window, cx),
         }
     }
 
+    /// Selects the text object around the cursor of each selection, or starts changing the
+    /// surrounding pair it names.
+    pub(crate) fn helix_object(
+        &mut self,
+        object: Object,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let mut waiting_operator = None;
+        match self.active_operator() {
+            Some(Operator::Object { around }) => {
+                self.update_editor(window, cx, |_, editor, window, cx| {
+                    editor.change_selections(Some(Autoscroll::fit()), window, cx, |s| {
+                        s.move_with(|map, selection| {
+                            let mut object_selection = selection.clone();
+                            object_selection
+                                .collapse_to(helix_cursor(map, selection), selection.goal);
+                            if object.expand_selection(map, &mut object_selection, around)
+                                && !object_selection.is_empty()
+                            {
+                                *selection = object_selection;
+                            }
+                        });
+                    });
+                });
+            }
+            Some(Operator::ChangeSurrounds { target: None }) => {
+                if self.check_and_move_to_valid_bracket_pair(object, window, cx) {
+                    waiting_operator = Some(Operator::ChangeSurrounds {
+                        target: Some(object),
+                    });
+                }
+            }
+            _ => {}
+        }
+        self.clear_operator(window, cx);
+        if let Some(operator) = waiting_operator {
+            self.push_operator(operator, window, cx);
+        }
+    }
+
     pub fn helix_delete(&mut self, _: &HelixDelete, window: &mut Window, cx: &mut Context<Self>) {
         self.store_visual_marks(window, cx);
         self.update_editor(window, cx, |vim, editor, window, cx| {
@@ -259,10 +474,240 @@ impl Vim {
             editor.insert("", window, cx);
         });
     }
+
+    fn helix_change(&mut self, _: &HelixChange, window: &mut Window, cx: &mut Context<Self>) {
+        self.helix_delete(&HelixDelete, window, cx);
+        self.switch_mode(Mode::Insert, true, window, cx);
+    }
+
+    fn helix_yank(&mut self, _: &HelixYank, window: &mut Window, cx: &mut Context<Self>) {
+        self.update_editor(window, cx, |vim, editor, window, cx| {
+            let (map, selections) = editor.selections.all_display(cx);
+            let ranges = selections
+                .iter()
+                .map(|selection| {
+                    let range = helix_char_range(&map, selection);
+                    range.start.to_point(&map)..range.end.to_point(&map)
+                })
+                .collect();
+            vim.copy_ranges(editor, MotionKind::Exclusive, true, ranges, window, cx);
+        });
+    }
+
+    fn helix_paste(&mut self, action: &HelixPaste, window: &mut Window, cx: &mut Context<Self>) {
+        let position = if action.before {
+            PastePosition::Before
+        } else {
+            PastePosition::After
+        };
+        self.helix_paste_register(position, window, cx);
+    }
+
+    fn helix_replace_with_register(
+        &mut self,
+        _: &HelixReplaceWithRegister,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.helix_paste_register(PastePosition::Replace, window, cx);
+    }
+
+    fn helix_paste_register(
+        &mut self,
+        position: PastePosition,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let selected_register = self.selected_register.take();
+        self.update_editor(window, cx, |_, editor, window, cx| {
+            let Some(Register {
+                text,
+                clipboard_selections,
+            }) = Vim::update_globals(cx, |globals, cx| {
+                globals.read_register(selected_register, Some(editor), cx)
+            })
+            .filter(|register| !register.text.is_empty())
+            else {
+                return;
+            };
+
+            let (map, selections) = editor.selections.all_display(cx);
+            let buffer = &map.buffer_snapshot;
+            let pieces = register_pieces(&text, clipboard_selections, selections.len());
+            let mut edits = Vec::new();
+            for (selection, piece) in selections.iter().zip(pieces) {
+                let range = helix_char_range(&map, selection);
+                let range = range.start.to_point(&map)..range.end.to_point(&map);
+                let linewise = piece.ends_with('\n');
+                let (point, text, selected) = match position {
+                    PastePosition::Replace => {
+                        let len = piece.len();
+                        edits.push((range, piece, 0..len));
+                        continue;
+                    }
+                    PastePosition::Before if linewise => {
+                        let len = piece.len();
+                        (Point::new(range.start.row, 0), piece, 0..len)
+                    }
+                    PastePosition::Before => {
+                        let len = piece.len();
+                        (range.start, piece, 0..len)
+                    }
+                    PastePosition::After if linewise => {
+                        if range.end.column == 0 && range.end > range.start {
+                            let len = piece.len();
+                            (range.end, piece, 0..len)
+                        } else if range.end.row < buffer.max_point().row {
+                            let len = piece.len();
+                            (Point::new(range.end.row + 1, 0), piece, 0..len)
+                        } else {
+                            // There's no next line to paste before, so start one.
+                            let text = format!("\n{}", &piece[..piece.len() - 1]);
+                            let len = text.len();
+                            (buffer.max_point(), text, 1..len)
+                        }
+                    }
+                    PastePosition::After => {
+                        let len = piece.len();
+                        (range.end, piece, 0..len)
+                    }
+                };
+                edits.push((point..point, text, selected));
+            }
+            edit_and_select(editor, edits, window, cx);
+        });
+    }
+
+    /// Replaces each character of the selections with the typed one.
+    pub(crate) fn helix_replace(
+        &mut self,
+        text: Arc<str>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.stop_recording(cx);
+        self.update_editor(window, cx, |_, editor, window, cx| {
+            let (map, selections) = editor.selections.all_display(cx);
+            let mut edits = Vec::new();
+            for selection in &selections {
+                let range = helix_char_range(&map, selection);
+                let range = range.start.to_point(&map)..range.end.to_point(&map);
+                let mut replacement = String::new();
+                let old_text = map
+                    .buffer_snapshot
+                    .text_for_range(range.clone())
+                    .collect::<String>();
+                for char in old_text.chars() {
+                    if char == '\n' {
+                        replacement.push('\n');
+                    } else {
+                        replacement.push_str(&text);
+                    }
+                }
+                let len = replacement.len();
+                let selected = if selection.is_empty() {
+                    0..0
+                } else if selection.reversed {
+                    len..0
+                } else {
+                    0..len
+                };
+                edits.push((range, replacement, selected));
+            }
+            edit_and_select(editor, edits, window, cx);
+        });
+        self.clear_operator(window, cx);
+    }
+
+    fn helix_surround_add(
+        &mut self,
+        _: &HelixSurroundAdd,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        // Make the character under each cursor part of its selection, as it's what gets surrounded.
+        self.update_editor(window, cx, |_, editor, window, cx| {
+            editor.change_selections(None, window, cx, |s| {
+                s.move_with(|map, selection| {
+                    let range = helix_char_range(map, selection);
+                    selection.start = range.start;
+                    selection.end = range.end;
+                });
+            });
+        });
+        self.push_operator(
+            Operator::AddSurrounds {
+                target: Some(SurroundsType::Selection),
+            },
+            window,
+            cx,
+        );
+    }
+}
+
+#[derive(Clone, Copy)]
+enum PastePosition {
+    Before,
+    After,
+    Replace,
+}
+
+/// Splits a register into one piece of text per selection when it was yanked from as many
+/// selections, and otherwise pastes all of it at each one.
+fn register_pieces(
+    text: &str,
+    clipboard_selections: Option<Vec<ClipboardSelection>>,
+    selection_count: usize,
+) -> Vec<String> {
+    match clipboard_selections {
+        Some(clipboard_selections) if clipboard_selections.len() == selection_count => {
+            let mut start = 0;
+            clipboard_selections
+                .iter()
+                .map(|clipboard_selection| {
+                    let end = start + clipboard_selection.len;
+                    let piece = text.get(start..end).unwrap_or_default().to_string();
+                    start = end + 1;
+                    piece
+                })
+                .collect()
+        }
+        _ => vec![text.to_string(); selection_count],
+    }
+}
+
+/// Applies edits, sorted by position, then selects the given range of each one's new text,
+/// relative to where it starts. A range whose start is after its end is selected reversed.
+fn edit_and_select(
+    editor: &mut Editor,
+    edits: Vec<(Range<Point>, String, Range<usize>)>,
+    window: &mut Window,
+    cx: &mut Context<Editor>,
+) {
+    let buffer = editor.buffer().read(cx).snapshot(cx);
+    let mut delta = 0isize;
+    let mut new_ranges = Vec::new();
+    for (range, text, selected) in &edits {
+        let old_start = buffer.point_to_offset(range.start);
+        let old_len = buffer.point_to_offset(range.end) - old_start;
+        let start = (old_start as isize + delta) as usize;
+        new_ranges.push(start + selected.start..start + selected.end);
+        delta += text.len() as isize - old_len as isize;
+    }
+    editor.transact(window, cx, |editor, window, cx| {
+        editor.edit(edits.into_iter().map(|(range, text, _)| (range, text)), cx);
+        editor.change_selections(Some(Autoscroll::fit()), window, cx, |s| {
+            s.select_ranges(new_ranges)
+        });
+    });
 }
 
 #[cfg(test)]
 mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use editor::actions;
+    use gpui::{Action, Focusable};
     use indoc::indoc;
 
     use crate::{state::Mode, test::VimTestContext};
@@ -381,4 +826,347 @@ mod test {
             Mode::HelixNormal,
         );
     }
+
+    #[gpui::test]
+    async fn test_select_mode(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.set_state("The quˇick brown", Mode::HelixNormal);
+
+        cx.simulate_keystrokes("v l l");
+        cx.assert_state("The qu«ickˇ» brown", Mode::HelixSelect);
+
+        cx.simulate_keystrokes("w");
+        cx.assert_state("The qu«ick ˇ»brown", Mode::HelixSelect);
+
+        cx.simulate_keystrokes("escape");
+        cx.assert_state("The qu«ick ˇ»brown", Mode::HelixNormal);
+
+        cx.simulate_keystrokes("l");
+        cx.assert_state("The quick ˇbrown", Mode::HelixNormal);
+
+        cx.simulate_keystrokes("v b");
+        cx.assert_state("The «ˇquick b»rown", Mode::HelixSelect);
+    }
+
+    #[gpui::test]
+    async fn test_find(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.set_state("ˇThe quick brown", Mode::HelixNormal);
+
+        cx.simulate_keystrokes("f k");
+        cx.assert_state("«The quickˇ» brown", Mode::HelixNormal);
+
+        cx.simulate_keystrokes("t n");
+        cx.assert_state("The quic«k browˇ»n", Mode::HelixNormal);
+
+        cx.simulate_keystrokes("shift-f q");
+        cx.assert_state("The «ˇquick brow»n", Mode::HelixNormal);
+    }
+
+    #[gpui::test]
+    async fn test_match_mode(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.set_state("fn aˇ(b) {}", Mode::HelixNormal);
+        cx.simulate_keystrokes("m m");
+        cx.assert_state("fn a(bˇ) {}", Mode::HelixNormal);
+
+        cx.set_state("The quˇick brown", Mode::HelixNormal);
+        cx.simulate_keystrokes("m i w");
+        cx.assert_state("The «quickˇ» brown", Mode::HelixNormal);
+
+        cx.set_state("The quˇick brown", Mode::HelixNormal);
+        cx.simulate_keystrokes("m a w");
+        cx.assert_state("The «quick ˇ»brown", Mode::HelixNormal);
+
+        cx.set_state("The «quickˇ» brown", Mode::HelixNormal);
+        cx.simulate_keystrokes("m s (");
+        cx.assert_state("The ˇ(quick) brown", Mode::HelixNormal);
+
+        cx.simulate_keystrokes("m r ( [");
+        cx.assert_state("The ˇ[quick] brown", Mode::HelixNormal);
+
+        cx.set_state("The [quˇick] brown", Mode::HelixNormal);
+        cx.simulate_keystrokes("m d [");
+        cx.assert_state("The ˇquick brown", Mode::HelixNormal);
+    }
+
+    #[gpui::test]
+    async fn test_change(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.set_state("The «quickˇ» brown", Mode::HelixNormal);
+
+        cx.simulate_keystrokes("c");
+        cx.assert_state("The ˇ brown", Mode::Insert);
+
+        cx.simulate_keystrokes("s l o w escape");
+        cx.assert_state("The slowˇ brown", Mode::HelixNormal);
+    }
+
+    #[gpui::test]
+    async fn test_insert(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.set_state("The «quickˇ» brown", Mode::HelixNormal);
+        cx.simulate_keystrokes("i");
+        cx.assert_state("The ˇquick brown", Mode::Insert);
+        cx.simulate_keystrokes("escape");
+        cx.assert_state("The ˇquick brown", Mode::HelixNormal);
+
+        cx.set_state("The «quickˇ» brown", Mode::HelixNormal);
+        cx.simulate_keystrokes("a");
+        cx.assert_state("The quickˇ brown", Mode::Insert);
+
+        cx.set_state("The ˇquick brown", Mode::HelixNormal);
+        cx.simulate_keystrokes("a");
+        cx.assert_state("The qˇuick brown", Mode::Insert);
+    }
+
+    #[gpui::test]
+    async fn test_yank_and_paste(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.set_state("The «quickˇ» brown", Mode::HelixNormal);
+
+        cx.simulate_keystrokes("y p");
+        cx.assert_state("The quick«quickˇ» brown", Mode::HelixNormal);
+
+        cx.simulate_keystrokes("shift-p");
+        cx.assert_state("The quick«quickˇ»quick brown", Mode::HelixNormal);
+
+        cx.set_state("The quick «brownˇ»", Mode::HelixNormal);
+        cx.simulate_keystrokes("shift-r");
+        cx.assert_state("The quick «quickˇ»", Mode::HelixNormal);
+
+        // Each selection gets the text yanked from the matching one.
+        cx.set_state("«oneˇ» «twoˇ»", Mode::HelixNormal);
+        cx.simulate_keystrokes("y p");
+        cx.assert_state("one«oneˇ» two«twoˇ»", Mode::HelixNormal);
+
+        // Whole lines are pasted on the next line.
+        cx.set_state(
+            indoc! {"
+            one
+            ˇtwo
+            three"},
+            Mode::HelixNormal,
+        );
+        cx.simulate_keystrokes("x y p");
+        cx.assert_state(
+            indoc! {"
+            one
+            two
+            «two
+            ˇ»three"},
+            Mode::HelixNormal,
+        );
+    }
+
+    #[gpui::test]
+    async fn test_paste_from_register(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.set_state("The «quickˇ» brown", Mode::HelixNormal);
+
+        cx.simulate_keystrokes("\" a y l");
+        cx.assert_state("The quickˇ brown", Mode::HelixNormal);
+
+        cx.simulate_keystrokes("\" a p");
+        cx.assert_state("The quick «quickˇ»brown", Mode::HelixNormal);
+    }
+
+    #[gpui::test]
+    async fn test_replace(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.set_state("The «quickˇ» brown", Mode::HelixNormal);
+        cx.simulate_keystrokes("r x");
+        cx.assert_state("The «xxxxxˇ» brown", Mode::HelixNormal);
+
+        cx.set_state("The ˇquick brown", Mode::HelixNormal);
+        cx.simulate_keystrokes("r x");
+        cx.assert_state("The ˇxuick brown", Mode::HelixNormal);
+    }
+
+    #[gpui::test]
+    async fn test_change_case(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.set_state("The «quickˇ» brown", Mode::HelixNormal);
+        cx.simulate_keystrokes("~");
+        cx.assert_state("The «QUICKˇ» brown", Mode::HelixNormal);
+
+        cx.simulate_keystrokes("`");
+        cx.assert_state("The «quickˇ» brown", Mode::HelixNormal);
+
+        cx.set_state("ˇthe quick brown", Mode::HelixNormal);
+        cx.simulate_keystrokes("~");
+        cx.assert_state("ˇThe quick brown", Mode::HelixNormal);
+    }
+
+    #[gpui::test]
+    async fn test_goto(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.set_state(
+            indoc! {"
+            one
+              two thˇree"},
+            Mode::HelixNormal,
+        );
+
+        cx.simulate_keystrokes("g s");
+        cx.assert_state(
+            indoc! {"
+            one
+              ˇtwo three"},
+            Mode::HelixNormal,
+        );
+
+        cx.simulate_keystrokes("g h");
+        cx.assert_state(
+            indoc! {"
+            one
+            ˇ  two three"},
+            Mode::HelixNormal,
+        );
+
+        cx.simulate_keystrokes("g g");
+        cx.assert_state(
+            indoc! {"
+            ˇone
+              two three"},
+            Mode::HelixNormal,
+        );
+    }
+
+    #[gpui::test]
+    async fn test_goto_line_and_document_end(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.set_state(
+            indoc! {"
+            ˇone
+            two"},
+            Mode::HelixNormal,
+        );
+
+        cx.simulate_keystrokes("g l");
+        cx.assert_state(
+            indoc! {"
+            onˇe
+            two"},
+            Mode::HelixNormal,
+        );
+
+        cx.simulate_keystrokes("g e");
+        cx.assert_state(
+            indoc! {"
+            one
+            twˇo"},
+            Mode::HelixNormal,
+        );
+    }
+
+    #[gpui::test]
+    async fn test_goto_window(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.set_state(
+            indoc! {"
+            one
+            two
+            three
+            fˇour
+            five"},
+            Mode::HelixNormal,
+        );
+
+        cx.simulate_keystrokes("g t");
+        cx.assert_state(
+            indoc! {"
+            oˇne
+            two
+            three
+            four
+            five"},
+            Mode::HelixNormal,
+        );
+
+        cx.simulate_keystrokes("g c");
+        cx.assert_state(
+            indoc! {"
+            one
+            two
+            tˇhree
+            four
+            five"},
+            Mode::HelixNormal,
+        );
+
+        cx.simulate_keystrokes("g b");
+        cx.assert_state(
+            indoc! {"
+            one
+            two
+            three
+            four
+            fˇive"},
+            Mode::HelixNormal,
+        );
+    }
+
+    #[gpui::test]
+    async fn test_goto_and_space_mode_bindings(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        // The pickers and the diagnostics view aren't initialized in tests, so their actions
+        // need a handler for the keystrokes to count as handled.
+        cx.update_workspace(|workspace, _, cx| {
+            workspace
+                .register_action(|_, _: &workspace::ToggleFileFinder, _, _| {})
+                .register_action(|_, _: &workspace::ToggleProjectSymbols, _, _| {})
+                .register_action(|_, _: &zed_actions::outline::ToggleOutline, _, _| {})
+                .register_action(|_, _: &tab_switcher::Toggle, _, _| {})
+                .register_action(|_, _: &diagnostics::Deploy, _, _| {});
+            cx.notify();
+        });
+
+        let dispatched = Rc::new(RefCell::new(None::<Box<dyn Action>>));
+        let _subscription = cx.update(|_, cx| {
+            let dispatched = dispatched.clone();
+            cx.observe_keystrokes(move |event, _, _| {
+                if let Some(action) = event.action.as_ref() {
+                    dispatched.replace(Some(action.boxed_clone()));
+                }
+            })
+        });
+
+        let mut assert_binding = |keystrokes: &str, expected: &dyn Action| {
+            cx.set_state("ˇone two", Mode::HelixNormal);
+            cx.update_editor(|editor, window, cx| window.focus(&editor.focus_handle(cx)));
+            cx.simulate_keystrokes(keystrokes);
+            let action = dispatched
+                .take()
+                .unwrap_or_else(|| panic!("`{keystrokes}` didn't dispatch any action"));
+            assert!(
+                action.partial_eq(expected),
+                "`{keystrokes}` dispatched {} instead of {}",
+                action.name(),
+                expected.name()
+            );
+        };
+
+        assert_binding("g d", &actions::GoToDefinition);
+        assert_binding("g y", &actions::GoToTypeDefinition);
+        assert_binding("g r", &actions::FindAllReferences);
+        assert_binding("g i", &actions::GoToImplementation);
+        assert_binding("g a", &workspace::pane::AlternateFile);
+        assert_binding("g n", &workspace::pane::ActivateNextItem);
+        assert_binding("g p", &workspace::pane::ActivatePreviousItem);
+
+        assert_binding("space f", &workspace::ToggleFileFinder::default());
+        assert_binding("space b", &tab_switcher::Toggle::default());
+        assert_binding("space s", &zed_actions::outline::ToggleOutline);
+        assert_binding("space shift-s", &workspace::ToggleProjectSymbols);
+        assert_binding("space d", &diagnostics::Deploy);
+        assert_binding("space a", &actions::ToggleCodeActions::default());
+        assert_binding("space r", &actions::Rename);
+        assert_binding("space k", &actions::Hover);
+        assert_binding("space y", &actions::Copy);
+        assert_binding("space p", &actions::Paste);
+        assert_binding("space c", &actions::ToggleComments::default());
+        // Project search takes focus, so it goes last.
+        assert_binding("space /", &workspace::DeploySearch::default());
+    }
 }
@@ -0,0 +1,482 @@
+use editor::{Bias, Editor, display_map::ToDisplayPoint, scroll::Autoscroll};
+use gpui::{Context, Window, actions};
+use language::{Point, SelectionGoal};
+use regex::Regex;
+
+use crate::{Vim, state::Operator};
+
+use super::{helix_char_range, helix_cursor};
+
+actions!(
+    vim,
+    [
+        HelixSelectLine,
+        HelixExtendToLineBounds,
+        HelixCollapseSelection,
+        HelixFlipSelections,
+        HelixKeepPrimarySelection,
+        HelixSelectRegex,
+        HelixSplitSelection,
+        HelixSplitSelectionOnNewline,
+        HelixKeepSelections,
+        HelixRemoveSelections,
+        HelixRegexBackspace,
+    ]
+);
+
+/// What to do with the selections once the regex typed after `s`, `S`, `K` or `Alt-K` is
+/// confirmed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RegexSelection {
+    /// Select the matches within each selection.
+    Select,
+    /// Split each selection at the matches, selecting the text between them.
+    Split,
+    /// Keep only the selections that contain a match.
+    Keep,
+    /// Remove the selections that contain a match.
+    Remove,
+}
+
+impl RegexSelection {
+    pub(crate) fn prompt(&self) -> &'static str {
+        match self {
+            RegexSelection::Select => "select",
+            RegexSelection::Split => "split",
+            RegexSelection::Keep => "keep",
+            RegexSelection::Remove => "remove",
+        }
+    }
+}
+
+pub fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
+    Vim::action(editor, cx, |vim, _: &HelixSelectLine, window, cx| {
+        let count = Vim::take_count(cx).unwrap_or(1) as u32;
+        vim.helix_select_lines(count, window, cx)
+    });
+    Vim::action(
+        editor,
+        cx,
+        |vim, _: &HelixExtendToLineBounds, window, cx| {
+            Vim::take_count(cx);
+            vim.helix_select_lines(0, window, cx)
+        },
+    );
+    Vim::action(editor, cx, Vim::helix_collapse_selection);
+    Vim::action(editor, cx, Vim::helix_flip_selections);
+    Vim::action(editor, cx, Vim::helix_keep_primary_selection);
+    Vim::action(editor, cx, |vim, _: &HelixSelectRegex, window, cx| {
+        vim.push_regex_operator(RegexSelection::Select, window, cx)
+    });
+    Vim::action(editor, cx, |vim, _: &HelixSplitSelection, window, cx| {
+        vim.push_regex_operator(RegexSelection::Split, window, cx)
+    });
+    Vim::action(editor, cx, |vim, _: &HelixKeepSelections, window, cx| {
+        vim.push_regex_operator(RegexSelection::Keep, window, cx)
+    });
+    Vim::action(editor, cx, |vim, _: &HelixRemoveSelections, window, cx| {
+        vim.push_regex_operator(RegexSelection::Remove, window, cx)
+    });
+    Vim::action(
+        editor,
+        cx,
+        |vim, _: &HelixSplitSelectionOnNewline, window, cx| {
+            vim.helix_select_regex(RegexSelection::Split, "\n", window, cx)
+        },
+    );
+    Vim::action(editor, cx, Vim::helix_regex_backspace);
+}
+
+impl Vim {
+    /// Selects whole lines. Selections that already cover whole lines are extended by
+    /// `extend_by` lines, others by `extend_by - 1` lines beyond the last line they touch.
+    fn helix_select_lines(&mut self, extend_by: u32, window: &mut Window, cx: &mut Context<Self>) {
+        self.update_editor(window, cx, |_, editor, window, cx| {
+            editor.change_selections(Some(Autoscroll::fit()), window, cx, |s| {
+                s.move_with(|map, selection| {
+                    let start = selection.start.to_point(map);
+                    let end = selection.end.to_point(map);
+                    let max_point = map.buffer_snapshot.max_point();
+
+                    let is_line_selection = start.column == 0
+                        && !selection.is_empty()
+                        && (end.column == 0 || end == max_point);
+                    let mut last_row = if end.column == 0 && end.row > start.row {
+                        end.row - 1
+                    } else {
+                        end.row
+                    };
+                    if is_line_selection {
+                        last_row += extend_by;
+                    } else {
+                        last_row += extend_by.saturating_sub(1);
+                    }
+
+                    let end = if last_row >= max_point.row {
+                        max_point
+                    } else {
+                        Point::new(last_row + 1, 0)
+                    };
+                    selection.start = Point::new(start.row, 0).to_display_point(map);
+                    selection.end = end.to_display_point(map);
+                    selection.reversed = false;
+                    selection.goal = SelectionGoal::None;
+                });
+            });
+        });
+    }
+
+    fn helix_collapse_selection(
+        &mut self,
+        _: &HelixCollapseSelection,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.update_editor(window, cx, |_, editor, window, cx| {
+            editor.change_selections(Some(Autoscroll::fit()), window, cx, |s| {
+                s.move_with(|map, selection| {
+                    let cursor = helix_cursor(map, selection);
+                    selection.collapse_to(cursor, selection.goal);
+                });
+            });
+        });
+    }
+
+    fn helix_flip_selections(
+        &mut self,
+        _: &HelixFlipSelections,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.update_editor(window, cx, |_, editor, window, cx| {
+            editor.change_selections(Some(Autoscroll::fit()), window, cx, |s| {
+                s.move_with(|_, selection| {
+                    if !selection.is_empty() {
+                        selection.reversed = !selection.reversed;
+                    }
+                });
+            });
+        });
+    }
+
+    fn helix_keep_primary_selection(
+        &mut self,
+        _: &HelixKeepPrimarySelection,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.update_editor(window, cx, |_, editor, window, cx| {
+            editor.change_selections(Some(Autoscroll::fit()), window, cx, |s| {
+                let primary = s.newest_anchor().clone();
+                s.select_anchors(vec![primary]);
+            });
+        });
+    }
+
+    fn push_regex_operator(
+        &mut self,
+        kind: RegexSelection,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        Vim::take_count(cx);
+        self.push_operator(
+            Operator::HelixSelectRegex {
+                kind,
+                query: String::new(),
+            },
+            window,
+            cx,
+        );
+    }
+
+    /// Handles text typed while a regex is being entered, applying it once enter is pressed.
+    pub(crate) fn helix_regex_input(
+        &mut self,
+        kind: RegexSelection,
+        mut query: String,
+        text: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.pop_operator(window, cx);
+        if text == "\n" {
+            self.helix_select_regex(kind, &query, window, cx);
+            return;
+        }
+        query.push_str(text);
+        self.push_operator(Operator::HelixSelectRegex { kind, query }, window, cx);
+    }
+
+    fn helix_regex_backspace(
+        &mut self,
+        _: &HelixRegexBackspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(Operator::HelixSelectRegex { kind, mut query }) = self.active_operator() else {
+            return;
+        };
+        self.pop_operator(window, cx);
+        // Like Helix's prompt, deleting past the start of the regex cancels it.
+        if query.pop().is_some() {
+            self.push_operator(Operator::HelixSelectRegex { kind, query }, window, cx);
+        }
+    }
+
+    fn helix_select_regex(
+        &mut self,
+        kind: RegexSelection,
+        query: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if query.is_empty() {
+            return;
+        }
+        let regex = match Regex::new(query) {
+            Ok(regex) => regex,
+            Err(error) => {
+                self.status_label = Some(format!("Invalid regex: {error}").into());
+                cx.notify();
+                return;
+            }
+        };
+
+        self.update_editor(window, cx, |_, editor, window, cx| {
+            let (map, selections) = editor.selections.all_display(cx);
+            let mut new_ranges = Vec::new();
+            for selection in &selections {
+                let range = helix_char_range(&map, selection);
+                let range =
+                    range.start.to_offset(&map, Bias::Left)..range.end.to_offset(&map, Bias::Left);
+                let text = map
+                    .buffer_snapshot
+                    .text_for_range(range.clone())
+                    .collect::<String>();
+                let mut select = |start: usize, end: usize| {
+                    new_ranges.push(range.start + start..range.start + end)
+                };
+                match kind {
+                    RegexSelection::Select => {
+                        for found in regex.find_iter(&text).filter(|found| !found.is_empty()) {
+                            select(found.start(), found.end());
+                        }
+                    }
+                    RegexSelection::Split => {
+                        let mut start = 0;
+                        for found in regex.find_iter(&text) {
+                            if found.start() > start {
+                                select(start, found.start());
+                            }
+                            start = found.end();
+                        }
+                        if start < text.len() {
+                            select(start, text.len());
+                        }
+                    }
+                    RegexSelection::Keep | RegexSelection::Remove => {
+                        if regex.is_match(&text) == (kind == RegexSelection::Keep) {
+                            let start = selection.start.to_offset(&map, Bias::Left);
+                            let end = selection.end.to_offset(&map, Bias::Left);
+                            new_ranges.push(if selection.reversed {
+                                end..start
+                            } else {
+                                start..end
+                            });
+                        }
+                    }
+                }
+            }
+
+            // Like Helix, leave the selections alone rather than removing all of them.
+            if new_ranges.is_empty() {
+                return;
+            }
+            editor.change_selections(Some(Autoscroll::fit()), window, cx, |s| {
+                s.select_ranges(new_ranges);
+            });
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use indoc::indoc;
+
+    use crate::{state::Mode, test::VimTestContext};
+
+    #[gpui::test]
+    async fn test_select_line(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state(
+            indoc! {"
+            The quˇick brown
+            fox jumps over
+            the lazy dog."},
+            Mode::HelixNormal,
+        );
+        cx.simulate_keystrokes("x");
+        cx.assert_state(
+            indoc! {"
+            «The quick brown
+            ˇ»fox jumps over
+            the lazy dog."},
+            Mode::HelixNormal,
+        );
+
+        // A line selection is extended by another line.
+        cx.simulate_keystrokes("x");
+        cx.assert_state(
+            indoc! {"
+            «The quick brown
+            fox jumps over
+            ˇ»the lazy dog."},
+            Mode::HelixNormal,
+        );
+
+        // The last line has no newline to select.
+        cx.simulate_keystrokes("x");
+        cx.assert_state(
+            indoc! {"
+            «The quick brown
+            fox jumps over
+            the lazy dog.ˇ»"},
+            Mode::HelixNormal,
+        );
+
+        cx.set_state(
+            indoc! {"
+            The quˇick brown
+            fox jumps over
+            the lazy dog."},
+            Mode::HelixNormal,
+        );
+        cx.simulate_keystrokes("2 x");
+        cx.assert_state(
+            indoc! {"
+            «The quick brown
+            fox jumps over
+            ˇ»the lazy dog."},
+            Mode::HelixNormal,
+        );
+    }
+
+    #[gpui::test]
+    async fn test_extend_to_line_bounds(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state(
+            indoc! {"
+            The qu«ick brown
+            fox ˇ»jumps over
+            the lazy dog."},
+            Mode::HelixNormal,
+        );
+        cx.simulate_keystrokes("shift-x");
+        cx.assert_state(
+            indoc! {"
+            «The quick brown
+            fox jumps over
+            ˇ»the lazy dog."},
+            Mode::HelixNormal,
+        );
+
+        // Unlike `x`, a line selection is left as it is.
+        cx.simulate_keystrokes("shift-x");
+        cx.assert_state(
+            indoc! {"
+            «The quick brown
+            fox jumps over
+            ˇ»the lazy dog."},
+            Mode::HelixNormal,
+        );
+    }
+
+    #[gpui::test]
+    async fn test_collapse_flip_and_keep_primary(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state("The «quickˇ» brown «foxˇ»", Mode::HelixNormal);
+        cx.simulate_keystrokes("alt-;");
+        cx.assert_state("The «ˇquick» brown «ˇfox»", Mode::HelixNormal);
+        cx.simulate_keystrokes(",");
+        cx.assert_state("The quick brown «ˇfox»", Mode::HelixNormal);
+        cx.simulate_keystrokes(";");
+        cx.assert_state("The quick brown ˇfox", Mode::HelixNormal);
+
+        cx.set_state("The «quickˇ» brown", Mode::HelixNormal);
+        cx.simulate_keystrokes(";");
+        cx.assert_state("The quicˇk brown", Mode::HelixNormal);
+    }
+
+    #[gpui::test]
+    async fn test_select_regex(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state(
+            indoc! {"
+            «one two one
+            three oneˇ» four"},
+            Mode::HelixNormal,
+        );
+        cx.simulate_keystrokes("s o n e enter");
+        cx.assert_state(
+            indoc! {"
+            «oneˇ» two «oneˇ»
+            three «oneˇ» four"},
+            Mode::HelixNormal,
+        );
+
+        // Backspace edits the regex, and escape cancels it.
+        cx.set_state("«one two threeˇ»", Mode::HelixNormal);
+        cx.simulate_keystrokes("s t x backspace w o enter");
+        cx.assert_state("one «twoˇ» three", Mode::HelixNormal);
+        cx.set_state("«one two threeˇ»", Mode::HelixNormal);
+        cx.simulate_keystrokes("s t w o escape");
+        cx.assert_state("«one two threeˇ»", Mode::HelixNormal);
+
+        // Without a match the selections are left alone.
+        cx.simulate_keystrokes("s f o u r enter");
+        cx.assert_state("«one two threeˇ»", Mode::HelixNormal);
+    }
+
+    #[gpui::test]
+    async fn test_split_selection(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state("«one,two,threeˇ»", Mode::HelixNormal);
+        cx.simulate_keystrokes("shift-s , enter");
+        cx.assert_state("«oneˇ»,«twoˇ»,«threeˇ»", Mode::HelixNormal);
+
+        cx.set_state(
+            indoc! {"
+            «one
+            two
+            threeˇ»"},
+            Mode::HelixNormal,
+        );
+        cx.simulate_keystrokes("alt-s");
+        cx.assert_state(
+            indoc! {"
+            «oneˇ»
+            «twoˇ»
+            «threeˇ»"},
+            Mode::HelixNormal,
+        );
+    }
+
+    #[gpui::test]
+    async fn test_keep_and_remove_selections(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state("«oneˇ» «twoˇ» «threeˇ»", Mode::HelixNormal);
+        cx.simulate_keystrokes("shift-k t enter");
+        cx.assert_state("one «twoˇ» «threeˇ»", Mode::HelixNormal);
+
+        cx.set_state("«oneˇ» «twoˇ» «threeˇ»", Mode::HelixNormal);
+        cx.simulate_keystrokes("alt-shift-k t enter");
+        cx.assert_state("«oneˇ» two three", Mode::HelixNormal);
+    }
+}
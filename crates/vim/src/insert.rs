@@ -11,7 +11,7 @@ pub fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
 }
 
 impl Vim {
-    fn normal_before(
+    pub(crate) fn normal_before(
         &mut self,
         action: &NormalBefore,
         window: &mut Window,
@@ -24,6 +24,14 @@ impl Vim {
         }
         let count = Vim::take_count(cx).unwrap_or(1);
         self.stop_recording_immediately(action.boxed_clone(), cx);
+        // Helix leaves the cursor where it is when returning to normal mode.
+        if self.last_mode.is_helix() {
+            self.update_editor(window, cx, |_, editor, window, cx| {
+                editor.dismiss_menus_and_popups(false, window, cx);
+            });
+            self.switch_mode(Mode::HelixNormal, false, window, cx);
+            return;
+        }
         if count <= 1 || Vim::globals(cx).dot_replaying {
             self.create_mark("^".into(), window, cx);
             self.update_editor(window, cx, |_, editor, window, cx| {
@@ -81,6 +81,8 @@ pub(crate) fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
     Vim::action(editor, cx, Vim::toggle_comments);
     Vim::action(editor, cx, Vim::paste);
     Vim::action(editor, cx, Vim::show_location);
+    Vim::action(editor, cx, Vim::go_back);
+    Vim::action(editor, cx, Vim::go_forward);
 
     Vim::action(editor, cx, |vim, _: &DeleteLeft, window, cx| {
         vim.record_current_action(cx);
@@ -297,17 +299,33 @@ impl Vim {
 
     fn insert_after(&mut self, _: &InsertAfter, window: &mut Window, cx: &mut Context<Self>) {
         self.start_recording(cx);
+        let was_helix = self.mode.is_helix();
         self.switch_mode(Mode::Insert, false, window, cx);
         self.update_editor(window, cx, |_, editor, window, cx| {
             editor.change_selections(Some(Autoscroll::fit()), window, cx, |s| {
-                s.move_cursors_with(|map, cursor, _| (right(map, cursor, 1), SelectionGoal::None));
+                if was_helix {
+                    // Helix appends after the selection, which includes the character under the
+                    // cursor.
+                    s.move_with(|map, selection| {
+                        let end = if selection.is_empty() {
+                            right(map, selection.end, 1)
+                        } else {
+                            selection.end
+                        };
+                        selection.collapse_to(end, SelectionGoal::None)
+                    });
+                } else {
+                    s.move_cursors_with(|map, cursor, _| {
+                        (right(map, cursor, 1), SelectionGoal::None)
+                    });
+                }
             });
         });
     }
 
     fn insert_before(&mut self, _: &InsertBefore, window: &mut Window, cx: &mut Context<Self>) {
         self.start_recording(cx);
-        if self.mode.is_visual() {
+        if self.mode.is_visual() || self.mode.is_helix() {
             let current_mode = self.mode;
             self.update_editor(window, cx, |_, editor, window, cx| {
                 editor.change_selections(Some(Autoscroll::fit()), window, cx, |s| {
@@ -202,8 +202,15 @@ impl Vim {
                             cursor_positions.push(selection.start..selection.start);
                         }
                     }
-
-                    Mode::HelixNormal => {}
+                    Mode::HelixNormal | Mode::HelixSelect => {
+                        let end = if selection.is_empty() {
+                            snapshot.clip_point(selection.start + Point::new(0, 1), Bias::Right)
+                        } else {
+                            selection.end
+                        };
+                        ranges.push(selection.start..end);
+                        cursor_positions.push(selection.start..selection.end);
+                    }
                     Mode::Insert | Mode::Normal | Mode::Replace => {
                         let start = selection.start;
                         let mut end = start;
@@ -234,7 +241,9 @@ impl Vim {
                 })
             });
         });
-        self.switch_mode(Mode::Normal, true, window, cx)
+        if !self.mode.is_helix() {
+            self.switch_mode(Mode::Normal, true, window, cx)
+        }
     }
 }
 
@@ -1,7 +1,7 @@
 use std::{ops::Range, path::Path, sync::Arc};
 
 use editor::{
-    Anchor, Bias, DisplayPoint, Editor, MultiBuffer,
+    Anchor, Bias, DisplayPoint, Editor, MultiBuffer, ToPoint,
     display_map::{DisplaySnapshot, ToDisplayPoint},
     movement,
     scroll::Autoscroll,
@@ -15,7 +15,7 @@ use workspace::OpenOptions;
 use crate::{
     Vim,
     motion::{self, Motion},
-    state::{Mark, Mode, VimGlobals},
+    state::{Mark, MarksState, Mode, VimGlobals},
 };
 
 impl Vim {
@@ -124,7 +124,7 @@ impl Vim {
         return;
     }
 
-    fn open_path_mark(
+    pub(crate) fn open_path_mark(
         &mut self,
         line: bool,
         path: Arc<Path>,
@@ -290,6 +290,84 @@ impl Vim {
         });
     }
 
+    /// Records a jump from the given position in the workspace's jumplist.
+    pub fn push_jump(
+        &mut self,
+        anchor: Anchor,
+        buffer_entity: &Entity<MultiBuffer>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let Some(workspace) = self.workspace(window) else {
+            return;
+        };
+        let multibuffer = buffer_entity.read(cx);
+        let point = anchor.to_point(&multibuffer.snapshot(cx));
+        let Some((buffer, point, _)) = multibuffer.point_to_buffer_point(point, cx) else {
+            return;
+        };
+        let entity_id = workspace.entity_id();
+        Vim::update_globals(cx, |vim_globals, cx| {
+            let Some(marks_state) = vim_globals.marks.get(&entity_id) else {
+                return;
+            };
+            marks_state.update(cx, |ms, cx| ms.push_jump(&buffer, point, cx));
+        });
+    }
+
+    /// Goes back in the pane's navigation history, continuing into the jumps of previous sessions
+    /// once the history is exhausted.
+    pub(crate) fn go_back(
+        &mut self,
+        _: &workspace::GoBack,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let can_navigate_backward = self
+            .pane(window, cx)
+            .is_some_and(|pane| pane.read(cx).can_navigate_backward());
+        let jump = self.update_marks_state(window, cx, |marks_state| {
+            if can_navigate_backward && !marks_state.is_navigating_restored_jumps() {
+                None
+            } else {
+                marks_state.previous_restored_jump()
+            }
+        });
+        match jump {
+            Some((path, point)) => self.open_path_mark(false, path, vec![point], window, cx),
+            None => cx.propagate(),
+        }
+    }
+
+    /// Goes forward through the jumps of previous sessions that [`Vim::go_back`] stepped into,
+    /// and then in the pane's navigation history.
+    pub(crate) fn go_forward(
+        &mut self,
+        _: &workspace::GoForward,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match self.update_marks_state(window, cx, |marks_state| marks_state.next_restored_jump()) {
+            Some((path, point)) => self.open_path_mark(false, path, vec![point], window, cx),
+            None => cx.propagate(),
+        }
+    }
+
+    fn update_marks_state<R>(
+        &self,
+        window: &mut Window,
+        cx: &mut App,
+        f: impl FnOnce(&mut MarksState) -> Option<R>,
+    ) -> Option<R> {
+        let entity_id = self.workspace(window)?.entity_id();
+        Vim::update_globals(cx, |vim_globals, cx| {
+            vim_globals
+                .marks
+                .get(&entity_id)?
+                .update(cx, |marks_state, _| f(marks_state))
+        })
+    }
+
     pub fn get_mark(
         &self,
         mut name: &str,
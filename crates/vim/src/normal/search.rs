@@ -12,7 +12,7 @@ use crate::{
     Vim,
     command::CommandRange,
     motion::Motion,
-    state::{Mode, SearchState},
+    state::{HistoryKind, Mode, SearchState},
 };
 
 #[derive(Clone, Debug, Deserialize, JsonSchema, PartialEq)]
@@ -212,8 +212,13 @@ impl Vim {
                 let prior_mode = self.search.prior_mode;
                 let prior_operator = self.search.prior_operator.take();
 
-                let query = search_bar.query(cx).into();
-                Vim::globals(cx).registers.insert('/', query);
+                let query = search_bar.query(cx);
+                Vim::update_globals(cx, |globals, cx| {
+                    globals.registers.insert('/', query.clone().into());
+                    if !query.is_empty() {
+                        globals.push_history(HistoryKind::Search, query, cx);
+                    }
+                });
                 Some((prior_selections, prior_mode, prior_operator))
             })
         });
@@ -586,7 +591,8 @@ mod test {
     use std::time::Duration;
 
     use crate::{
-        state::Mode,
+        VimSettings,
+        state::{Mode, VimGlobals},
         test::{NeovimBackedTestContext, VimTestContext},
     };
     use editor::EditorSettings;
@@ -991,4 +997,25 @@ mod test {
                  "
         });
     }
+
+    #[gpui::test]
+    async fn test_search_history(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.update_global(|store: &mut SettingsStore, cx| {
+            store.update_user_settings::<VimSettings>(cx, |s| s.history_size = Some(2));
+        });
+
+        cx.set_state("ˇone two three", Mode::Normal);
+        cx.simulate_keystrokes("/ o n e enter");
+        cx.simulate_keystrokes("/ t w o enter");
+        cx.simulate_keystrokes("/ o n e enter");
+        cx.simulate_keystrokes("/ t h r e e enter");
+        cx.assert_state("one two ˇthree", Mode::Normal);
+        cx.read(|cx| {
+            assert_eq!(
+                cx.global::<VimGlobals>().search_history,
+                vec!["one", "three"]
+            );
+        });
+    }
 }
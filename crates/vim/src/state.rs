@@ -1,4 +1,5 @@
 use crate::command::command_interceptor;
+use crate::helix::RegexSelection;
 use crate::motion::MotionKind;
 use crate::normal::repeat::Replayer;
 use crate::surrounds::SurroundsType;
@@ -24,6 +25,8 @@ use settings::{Settings, SettingsStore};
 use std::borrow::BorrowMut;
 use std::collections::HashSet;
 use std::path::Path;
+use std::rc::Rc;
+use std::time::Duration;
 use std::{fmt::Display, ops::Range, sync::Arc};
 use text::{Bias, ToPoint};
 use theme::ThemeSettings;
@@ -44,6 +47,7 @@ pub enum Mode {
     VisualLine,
     VisualBlock,
     HelixNormal,
+    HelixSelect,
 }
 
 impl Display for Mode {
@@ -56,6 +60,7 @@ impl Display for Mode {
             Mode::VisualLine => write!(f, "VISUAL LINE"),
             Mode::VisualBlock => write!(f, "VISUAL BLOCK"),
             Mode::HelixNormal => write!(f, "HELIX NORMAL"),
+            Mode::HelixSelect => write!(f, "HELIX SELECT"),
         }
     }
 }
@@ -64,7 +69,21 @@ impl Mode {
     pub fn is_visual(&self) -> bool {
         match self {
             Self::Visual | Self::VisualLine | Self::VisualBlock => true,
-            Self::Normal | Self::Insert | Self::Replace | Self::HelixNormal => false,
+            Self::Normal | Self::Insert | Self::Replace | Self::HelixNormal | Self::HelixSelect => {
+                false
+            }
+        }
+    }
+
+    pub fn is_helix(&self) -> bool {
+        match self {
+            Self::HelixNormal | Self::HelixSelect => true,
+            Self::Normal
+            | Self::Insert
+            | Self::Replace
+            | Self::Visual
+            | Self::VisualLine
+            | Self::VisualBlock => false,
         }
     }
 }
@@ -130,6 +149,10 @@ pub enum Operator {
     ToggleComments,
     ReplaceWithRegister,
     Exchange,
+    HelixSelectRegex {
+        kind: RegexSelection,
+        query: String,
+    },
 }
 
 #[derive(Default, Clone, Debug)]
@@ -222,6 +245,42 @@ pub struct VimGlobals {
     pub focused_vim: Option<WeakEntity<Vim>>,
 
     pub marks: HashMap<EntityId, Entity<MarksState>>,
+
+    /// The commands run from `:`, the oldest first.
+    pub command_history: Vec<String>,
+    /// The searches made with `/` and `?`, the oldest first.
+    pub search_history: Vec<String>,
+
+    serialize_registers_task: Option<Task<()>>,
+}
+
+/// Like viminfo's default `s10`, registers larger than this are not kept across restarts.
+const MAX_PERSISTED_REGISTER_LEN: usize = 10 * 1024;
+const SERIALIZE_REGISTERS_DEBOUNCE: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HistoryKind {
+    Command,
+    Search,
+}
+
+impl HistoryKind {
+    /// The character typed to start an entry of this history, which also names it in the
+    /// database.
+    fn prefix(self) -> char {
+        match self {
+            HistoryKind::Command => ':',
+            HistoryKind::Search => '/',
+        }
+    }
+
+    fn from_prefix(prefix: &str) -> Option<Self> {
+        match prefix {
+            ":" => Some(HistoryKind::Command),
+            "/" => Some(HistoryKind::Search),
+            _ => None,
+        }
+    }
 }
 
 pub struct MarksState {
@@ -234,6 +293,15 @@ pub struct MarksState {
     serialized_marks: HashMap<Arc<Path>, HashMap<String, Vec<Point>>>,
     global_marks: HashMap<String, MarkLocation>,
 
+    /// The positions jumped from, the oldest first.
+    jumps: Vec<(Arc<Path>, Point)>,
+    /// The jumps of previous sessions, which `ctrl-o` and `ctrl-i` step through once the
+    /// navigation history of this session is exhausted.
+    restored_jumps: Vec<(Arc<Path>, Point)>,
+    /// The index of the restored jump last navigated to, `restored_jumps.len()` until `ctrl-o`
+    /// steps into them.
+    restored_jump_ix: usize,
+
     _subscription: Subscription,
 }
 
@@ -271,6 +339,9 @@ impl MarksState {
                 watched_buffers: HashMap::default(),
                 serialized_marks: HashMap::default(),
                 global_marks: HashMap::default(),
+                jumps: Vec::new(),
+                restored_jumps: Vec::new(),
+                restored_jump_ix: 0,
                 _subscription: subscription,
             };
 
@@ -297,14 +368,15 @@ impl MarksState {
             let Some(workspace_id) = this.update(cx, |this, cx| this.workspace_id(cx))? else {
                 return Ok(());
             };
-            let (marks, paths) = cx
+            let (marks, paths, jumps) = cx
                 .background_spawn(async move {
                     let marks = DB.get_marks(workspace_id)?;
                     let paths = DB.get_global_marks_paths(workspace_id)?;
-                    anyhow::Ok((marks, paths))
+                    let jumps = DB.get_jumps(workspace_id)?;
+                    anyhow::Ok((marks, paths, jumps))
                 })
                 .await?;
-            this.update(cx, |this, cx| this.loaded(marks, paths, cx))
+            this.update(cx, |this, cx| this.loaded(marks, paths, jumps, cx))
         })
         .detach_and_log_err(cx);
     }
@@ -313,8 +385,15 @@ impl MarksState {
         &mut self,
         marks: Vec<SerializedMark>,
         global_mark_paths: Vec<(String, Arc<Path>)>,
+        jumps: Vec<(Arc<Path>, Point)>,
         cx: &mut Context<Self>,
     ) {
+        // Keep any jumps made while loading after those of the previous session.
+        self.restored_jump_ix = jumps.len();
+        self.restored_jumps = jumps.clone();
+        let recent_jumps = std::mem::replace(&mut self.jumps, jumps);
+        self.jumps.extend(recent_jumps);
+
         let Some(project) = self.project(cx) else {
             return;
         };
@@ -441,6 +520,52 @@ impl MarksState {
         }
     }
 
+    /// Adds a position to the end of the jumplist, replacing any earlier jump from the same line
+    /// and dropping the oldest jumps past `vim.jumplist_size`.
+    pub fn push_jump(&mut self, buffer: &Entity<Buffer>, point: Point, cx: &mut Context<Self>) {
+        let Some(path) = self.path_for_buffer(buffer, cx) else {
+            return;
+        };
+        let size = VimSettings::get_global(cx).jumplist_size;
+        self.jumps
+            .retain(|(jump_path, jump_point)| *jump_path != path || jump_point.row != point.row);
+        self.jumps.push((path, point));
+        let excess = self.jumps.len().saturating_sub(size);
+        self.jumps.drain(..excess);
+
+        if let Some(workspace_id) = self.workspace_id(cx) {
+            let jumps = self.jumps.clone();
+            cx.background_spawn(async move { DB.set_jumps(workspace_id, jumps).await })
+                .detach_and_log_err(cx);
+        }
+    }
+
+    pub fn jumps(&self) -> &[(Arc<Path>, Point)] {
+        &self.jumps
+    }
+
+    /// Whether `ctrl-o` stepped into the jumps of previous sessions, so that navigation keeps
+    /// following them rather than this session's navigation history.
+    pub fn is_navigating_restored_jumps(&self) -> bool {
+        self.restored_jump_ix < self.restored_jumps.len()
+    }
+
+    /// Steps back to the previous jump of previous sessions.
+    pub fn previous_restored_jump(&mut self) -> Option<(Arc<Path>, Point)> {
+        self.restored_jump_ix = self.restored_jump_ix.checked_sub(1)?;
+        self.restored_jumps.get(self.restored_jump_ix).cloned()
+    }
+
+    /// Steps forward to the next jump of previous sessions, returning `None` once the most recent
+    /// of them was left.
+    pub fn next_restored_jump(&mut self) -> Option<(Arc<Path>, Point)> {
+        if !self.is_navigating_restored_jumps() {
+            return None;
+        }
+        self.restored_jump_ix += 1;
+        self.restored_jumps.get(self.restored_jump_ix).cloned()
+    }
+
     fn is_global_mark(&self, key: &str) -> bool {
         key.chars()
             .next()
@@ -652,7 +777,16 @@ impl VimGlobals {
                 });
                 CommandPaletteInterceptor::update_global(cx, |interceptor, _| {
                     interceptor.set(Box::new(command_interceptor));
+                    interceptor.set_confirm_observer(Rc::new(|query: &str, cx: &mut App| {
+                        let command = query.trim_start_matches(':').trim();
+                        if !command.is_empty() {
+                            Vim::update_globals(cx, |globals, cx| {
+                                globals.push_history(HistoryKind::Command, command.to_string(), cx)
+                            });
+                        }
+                    }));
                 });
+                VimGlobals::load_persisted(cx);
                 for window in cx.windows() {
                     if let Some(workspace) = window.downcast::<Workspace>() {
                         workspace
@@ -693,6 +827,99 @@ impl VimGlobals {
         .detach();
     }
 
+    /// Loads the registers and histories saved by previous sessions.
+    fn load_persisted(cx: &mut App) {
+        // Tests share one database, in which they would see each other's registers.
+        if cfg!(any(test, feature = "test-support")) {
+            return;
+        }
+        cx.spawn(async move |cx| {
+            let (registers, history) = cx
+                .background_spawn(async move {
+                    let registers = DB.get_registers()?;
+                    let history = DB.get_history()?;
+                    anyhow::Ok((registers, history))
+                })
+                .await?;
+            cx.update(|cx| Vim::update_globals(cx, |globals, _| globals.loaded(registers, history)))
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn loaded(&mut self, registers: Vec<(char, Register)>, history: Vec<(HistoryKind, String)>) {
+        // Anything written while loading is more recent than what was saved.
+        for (name, register) in registers {
+            if !name.is_ascii_lowercase() {
+                continue;
+            }
+            self.registers.entry(name).or_insert(register);
+        }
+        for kind in [HistoryKind::Command, HistoryKind::Search] {
+            let recent = std::mem::take(self.history_mut(kind));
+            let mut entries: Vec<String> = history
+                .iter()
+                .filter(|(entry_kind, entry)| *entry_kind == kind && !recent.contains(entry))
+                .map(|(_, entry)| entry.clone())
+                .collect();
+            entries.extend(recent);
+            *self.history_mut(kind) = entries;
+        }
+    }
+
+    pub fn history(&self, kind: HistoryKind) -> &[String] {
+        match kind {
+            HistoryKind::Command => &self.command_history,
+            HistoryKind::Search => &self.search_history,
+        }
+    }
+
+    fn history_mut(&mut self, kind: HistoryKind) -> &mut Vec<String> {
+        match kind {
+            HistoryKind::Command => &mut self.command_history,
+            HistoryKind::Search => &mut self.search_history,
+        }
+    }
+
+    /// Adds an entry to the end of a history, removing any earlier copy of it and dropping the
+    /// oldest entries past `vim.history_size`.
+    pub(crate) fn push_history(&mut self, kind: HistoryKind, entry: String, cx: &mut App) {
+        let size = VimSettings::get_global(cx).history_size;
+        let history = self.history_mut(kind);
+        history.retain(|existing| *existing != entry);
+        history.push(entry);
+        let excess = history.len().saturating_sub(size);
+        history.drain(..excess);
+
+        let entries = history.clone();
+        cx.background_spawn(async move { DB.set_history(kind.prefix(), entries).await })
+            .detach_and_log_err(cx);
+    }
+
+    /// The named registers, `a` to `z`, that are kept across restarts.
+    fn persisted_registers(&self) -> Vec<(char, Register)> {
+        self.registers
+            .iter()
+            .filter(|(name, register)| {
+                name.is_ascii_lowercase() && register.text.len() <= MAX_PERSISTED_REGISTER_LEN
+            })
+            .map(|(name, register)| (*name, register.clone()))
+            .collect()
+    }
+
+    /// Saves the named registers once they have stopped changing, so that a run of yanks is
+    /// written once.
+    fn serialize_registers(&mut self, cx: &mut App) {
+        self.serialize_registers_task = Some(cx.spawn(async move |cx| {
+            cx.background_executor()
+                .timer(SERIALIZE_REGISTERS_DEBOUNCE)
+                .await;
+            let Ok(registers) = cx.update(|cx| Vim::globals(cx).persisted_registers()) else {
+                return;
+            };
+            DB.set_registers(registers).await.log_err();
+        }));
+    }
+
     pub(crate) fn write_registers(
         &mut self,
         content: Register,
@@ -767,6 +994,9 @@ impl VimGlobals {
                 }
             }
         }
+        if register.is_some_and(|register| register.is_ascii_alphabetic()) {
+            self.serialize_registers(cx);
+        }
     }
 
     pub(crate) fn read_register(
@@ -787,7 +1017,11 @@ impl VimGlobals {
         };
         let lower = register.to_lowercase().next().unwrap_or(register);
         match lower {
-            '_' | ':' | '.' | '#' | '=' => None,
+            '_' | '.' | '#' | '=' => None,
+            ':' => self
+                .command_history
+                .last()
+                .map(|command| command.clone().into()),
             '+' => cx.read_from_clipboard().map(|item| item.into()),
             '*' => {
                 #[cfg(any(target_os = "linux", target_os = "freebsd"))]
@@ -966,6 +1200,7 @@ impl Operator {
             Operator::RecordRegister => "q",
             Operator::ReplayRegister => "@",
             Operator::ToggleComments => "gc",
+            Operator::HelixSelectRegex { .. } => "regex",
         }
     }
 
@@ -979,6 +1214,7 @@ impl Operator {
             } => format!("^V{prefix}"),
             Operator::AutoIndent => "=".to_string(),
             Operator::ShellCommand => "=".to_string(),
+            Operator::HelixSelectRegex { kind, query } => format!("{}:{query}", kind.prompt()),
             _ => self.id().to_string(),
         }
     }
@@ -999,7 +1235,8 @@ impl Operator {
             | Operator::Digraph { .. }
             | Operator::Literal { .. }
             | Operator::ChangeSurrounds { target: Some(_) }
-            | Operator::DeleteSurrounds => true,
+            | Operator::DeleteSurrounds
+            | Operator::HelixSelectRegex { .. } => true,
             Operator::Change
             | Operator::Delete
             | Operator::Yank
@@ -1056,13 +1293,15 @@ impl Operator {
             | Operator::Jump { .. }
             | Operator::Register
             | Operator::RecordRegister
-            | Operator::ReplayRegister => false,
+            | Operator::ReplayRegister
+            | Operator::HelixSelectRegex { .. } => false,
         }
     }
 }
 
 struct RegisterMatch {
-    name: char,
+    /// The register, e.g. `"a`, or the history entry, e.g. `:3`.
+    label: String,
     contents: SharedString,
 }
 
@@ -1118,8 +1357,7 @@ impl PickerDelegate for RegistersViewDelegate {
 
         let mut output = String::new();
         let mut runs = Vec::new();
-        output.push('"');
-        output.push(register_match.name);
+        output.push_str(&register_match.label);
         runs.push((
             0..output.len(),
             HighlightStyle::color(cx.theme().colors().text_accent),
@@ -1210,7 +1448,7 @@ impl RegistersView {
             for name in ['"', '+', '*'] {
                 if let Some(register) = globals.read_register(Some(name), None, cx) {
                     matches.push(RegisterMatch {
-                        name,
+                        label: format!("\"{name}"),
                         contents: register.text.clone(),
                     })
                 }
@@ -1221,7 +1459,7 @@ impl RegistersView {
                 });
                 if let Some(register) = register {
                     matches.push(RegisterMatch {
-                        name: '%',
+                        label: "\"%".to_string(),
                         contents: register.text.clone(),
                     })
                 }
@@ -1231,12 +1469,31 @@ impl RegistersView {
                     continue;
                 };
                 matches.push(RegisterMatch {
-                    name: *name,
+                    label: format!("\"{name}"),
                     contents: register.text.clone(),
                 })
             }
+            matches.sort_by(|a, b| a.label.cmp(&b.label));
+
+            // Follow the registers with the histories, the most recent entries first.
+            for kind in [HistoryKind::Command, HistoryKind::Search] {
+                for (ix, entry) in globals.history(kind).iter().enumerate().rev() {
+                    matches.push(RegisterMatch {
+                        label: format!("{}{}", kind.prefix(), ix + 1),
+                        contents: entry.clone().into(),
+                    })
+                }
+            }
         });
-        matches.sort_by(|a, b| a.name.cmp(&b.name));
+        let label_width = matches
+            .iter()
+            .map(|register_match| register_match.label.len())
+            .max()
+            .unwrap_or_default();
+        for register_match in &mut matches {
+            let padding = label_width - register_match.label.len();
+            register_match.label.push_str(&" ".repeat(padding));
+        }
         let delegate = RegistersViewDelegate {
             selected_index: 0,
             matches,
@@ -1276,9 +1533,11 @@ impl MarksMatchInfo {
 }
 
 struct MarksMatch {
+    /// The name of the mark, or the number of the jump counting back from the most recent.
     name: String,
     position: Point,
     info: MarksMatchInfo,
+    is_jump: bool,
 }
 
 pub struct MarksViewDelegate {
@@ -1319,6 +1578,7 @@ impl PickerDelegate for MarksViewDelegate {
         };
         cx.spawn(async move |picker, cx| {
             let mut matches = Vec::new();
+            let mut jumps = Vec::new();
             let _ = workspace.update(cx, |workspace, cx| {
                 let entity_id = cx.entity_id();
                 let Some(editor) = workspace
@@ -1362,6 +1622,7 @@ impl PickerDelegate for MarksViewDelegate {
                             name: name.clone(),
                             position,
                             info: MarksMatchInfo::from_chunks(chunks, cx),
+                            is_jump: false,
                         })
                     }
                 }
@@ -1389,6 +1650,7 @@ impl PickerDelegate for MarksViewDelegate {
                                 name: name.clone(),
                                 position,
                                 info: MarksMatchInfo::from_chunks(chunks, cx),
+                                is_jump: false,
                             })
                         }
                     }
@@ -1428,6 +1690,7 @@ impl PickerDelegate for MarksViewDelegate {
                                     name: name.clone(),
                                     position: anchor.to_point(&snapshot),
                                     info,
+                                    is_jump: false,
                                 });
                             }
                         }
@@ -1443,11 +1706,21 @@ impl PickerDelegate for MarksViewDelegate {
                                     name: name.clone(),
                                     position,
                                     info,
+                                    is_jump: false,
                                 });
                             }
                         }
                     }
                 }
+
+                for (ix, (path, position)) in marks_state.jumps().iter().rev().enumerate() {
+                    jumps.push(MarksMatch {
+                        name: (ix + 1).to_string(),
+                        position: *position,
+                        info: MarksMatchInfo::Path(path.clone()),
+                        is_jump: true,
+                    });
+                }
             });
             let _ = picker.update(cx, |picker, cx| {
                 matches.sort_by_key(|a| {
@@ -1456,6 +1729,7 @@ impl PickerDelegate for MarksViewDelegate {
                         a.name.clone(),
                     )
                 });
+                matches.extend(jumps);
                 let digits = matches
                     .iter()
                     .map(|m| (m.position.row + 1).ilog10() + (m.position.column + 1).ilog10())
@@ -1480,16 +1754,23 @@ impl PickerDelegate for MarksViewDelegate {
         else {
             return;
         };
-        let Some(text): Option<Arc<str>> = self
-            .matches
-            .get(self.selected_index)
-            .map(|m| Arc::from(m.name.to_string().into_boxed_str()))
-        else {
+        let Some(mark_match) = self.matches.get(self.selected_index) else {
             return;
         };
-        vim.update(cx, |vim, cx| {
-            vim.jump(text, false, false, window, cx);
-        });
+        if mark_match.is_jump {
+            let MarksMatchInfo::Path(path) = &mark_match.info else {
+                return;
+            };
+            let (path, position) = (path.clone(), mark_match.position);
+            vim.update(cx, |vim, cx| {
+                vim.open_path_mark(false, path, vec![position], window, cx);
+            });
+        } else {
+            let text: Arc<str> = Arc::from(mark_match.name.to_string().into_boxed_str());
+            vim.update(cx, |vim, cx| {
+                vim.jump(text, false, false, window, cx);
+            });
+        }
 
         cx.emit(DismissEvent);
     }
@@ -1510,7 +1791,9 @@ impl PickerDelegate for MarksViewDelegate {
 
         let mut left_output = String::new();
         let mut left_runs = Vec::new();
-        left_output.push('`');
+        if !mark_match.is_jump {
+            left_output.push('`');
+        }
         left_output.push_str(&mark_match.name);
         left_runs.push((
             0..left_output.len(),
@@ -1628,6 +1911,27 @@ define_connection! (
             CREATE UNIQUE INDEX idx_vim_global_marks_paths
             ON vim_global_marks_paths(workspace_id, mark_name);
         ),
+        sql! (
+            CREATE TABLE vim_registers(
+                register_name TEXT PRIMARY KEY,
+                contents TEXT,
+                clipboard_selections TEXT
+            );
+            CREATE TABLE vim_history(
+                kind TEXT,
+                ix INTEGER,
+                entry TEXT
+            );
+            CREATE UNIQUE INDEX idx_vim_history ON vim_history(kind, ix);
+            CREATE TABLE vim_jumps(
+                workspace_id INTEGER,
+                ix INTEGER,
+                path BLOB,
+                point_row INTEGER,
+                point_column INTEGER
+            );
+            CREATE UNIQUE INDEX idx_vim_jumps ON vim_jumps(workspace_id, ix);
+        ),
     ];
 );
 
@@ -1714,4 +2018,168 @@ impl VimDb {
             WHERE workspace_id = ?
         ))?(workspace_id)
     }
+
+    pub(crate) async fn set_registers(&self, registers: Vec<(char, Register)>) -> Result<()> {
+        self.write(move |conn| {
+            conn.exec(sql!(DELETE FROM vim_registers))?()?;
+            let mut query = conn.exec_bound(sql!(
+                INSERT INTO vim_registers
+                    (register_name, contents, clipboard_selections)
+                VALUES
+                    (?, ?, ?)
+            ))?;
+            for (name, register) in registers {
+                let clipboard_selections = register
+                    .clipboard_selections
+                    .map(|selections| serde_json::to_string(&selections))
+                    .transpose()?;
+                query((
+                    name.to_string(),
+                    register.text.to_string(),
+                    clipboard_selections,
+                ))?;
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    fn get_registers(&self) -> Result<Vec<(char, Register)>> {
+        let result: Vec<(String, String, Option<String>)> = self.select(sql!(
+            SELECT register_name, contents, clipboard_selections FROM vim_registers
+        ))?()?;
+
+        Ok(result
+            .into_iter()
+            .filter_map(|(name, contents, clipboard_selections)| {
+                let register = Register {
+                    text: contents.into(),
+                    clipboard_selections: clipboard_selections
+                        .and_then(|selections| serde_json::from_str(&selections).log_err()),
+                };
+                Some((name.chars().next()?, register))
+            })
+            .collect())
+    }
+
+    pub(crate) async fn set_history(&self, kind: char, entries: Vec<String>) -> Result<()> {
+        self.write(move |conn| {
+            let kind = kind.to_string();
+            conn.exec_bound(sql!(DELETE FROM vim_history WHERE kind = ?))?(kind.clone())?;
+            let mut query = conn.exec_bound(sql!(
+                INSERT INTO vim_history
+                    (kind, ix, entry)
+                VALUES
+                    (?, ?, ?)
+            ))?;
+            for (ix, entry) in entries.into_iter().enumerate() {
+                query((kind.clone(), ix as i64, entry))?;
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    fn get_history(&self) -> Result<Vec<(HistoryKind, String)>> {
+        let result: Vec<(String, String)> = self.select(sql!(
+            SELECT kind, entry FROM vim_history
+                ORDER BY ix
+        ))?()?;
+
+        Ok(result
+            .into_iter()
+            .filter_map(|(kind, entry)| Some((HistoryKind::from_prefix(&kind)?, entry)))
+            .collect())
+    }
+
+    pub(crate) async fn set_jumps(
+        &self,
+        workspace_id: WorkspaceId,
+        jumps: Vec<(Arc<Path>, Point)>,
+    ) -> Result<()> {
+        self.write(move |conn| {
+            conn.exec_bound(sql!(DELETE FROM vim_jumps WHERE workspace_id = ?))?(workspace_id)?;
+            let mut query = conn.exec_bound(sql!(
+                INSERT INTO vim_jumps
+                    (workspace_id, ix, path, point_row, point_column)
+                VALUES
+                    (?, ?, ?, ?, ?)
+            ))?;
+            for (ix, (path, point)) in jumps.into_iter().enumerate() {
+                query((workspace_id, ix as i64, path, point.row, point.column))?;
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    fn get_jumps(&self, workspace_id: WorkspaceId) -> Result<Vec<(Arc<Path>, Point)>> {
+        let result: Vec<(Arc<Path>, u32, u32)> = self.select_bound(sql!(
+            SELECT path, point_row, point_column FROM vim_jumps
+                WHERE workspace_id = ?
+                ORDER BY ix
+        ))?(workspace_id)?;
+
+        Ok(result
+            .into_iter()
+            .map(|(path, row, column)| (path, Point { row, column }))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[gpui::test]
+    async fn test_persisted_registers_and_jumps() {
+        let db = VimDb(db::open_test_db("test_persisted_registers_and_jumps").await);
+
+        let mut globals = VimGlobals::default();
+        globals.registers.insert('a', "named".to_string().into());
+        globals.registers.insert('"', "unnamed".to_string().into());
+        globals.registers.insert('0', "yanked".to_string().into());
+        globals.registers.insert('-', "deleted".to_string().into());
+        globals
+            .registers
+            .insert('b', "x".repeat(MAX_PERSISTED_REGISTER_LEN + 1).into());
+        db.set_registers(globals.persisted_registers())
+            .await
+            .unwrap();
+
+        let registers = db.get_registers().unwrap();
+        let registers: Vec<(char, String)> = registers
+            .into_iter()
+            .map(|(name, register)| (name, register.text.to_string()))
+            .collect();
+        assert_eq!(registers, vec![('a', "named".to_string())]);
+
+        let mut globals = VimGlobals::default();
+        globals.registers.insert('a', "newer".to_string().into());
+        globals.loaded(
+            vec![
+                ('a', "named".to_string().into()),
+                ('c', "other".to_string().into()),
+                ('"', "unnamed".to_string().into()),
+            ],
+            Vec::new(),
+        );
+        assert_eq!(globals.registers.len(), 2);
+        assert_eq!(globals.registers[&'a'].text.to_string(), "newer");
+        assert_eq!(globals.registers[&'c'].text.to_string(), "other");
+
+        let workspace_id = WorkspaceId::default();
+        let jumps: Vec<(Arc<Path>, Point)> = vec![
+            (Path::new("/root/a.rs").into(), Point::new(1, 2)),
+            (Path::new("/root/b.rs").into(), Point::new(3, 0)),
+            (Path::new("/root/a.rs").into(), Point::new(10, 4)),
+        ];
+        db.set_jumps(workspace_id, jumps.clone()).await.unwrap();
+        assert_eq!(db.get_jumps(workspace_id).unwrap(), jumps);
+
+        db.set_jumps(workspace_id, jumps[1..].to_vec())
+            .await
+            .unwrap();
+        assert_eq!(db.get_jumps(workspace_id).unwrap(), jumps[1..].to_vec());
+    }
 }
@@ -43,7 +43,8 @@ impl Vim {
                         newline: false,
                     },
                 };
-                let surround = pair.end != surround_alias((*text).as_ref());
+                // Helix surrounds with the pair as typed, without vim-surround's inner spaces.
+                let surround = pair.end != surround_alias((*text).as_ref()) && !mode.is_helix();
                 let (display_map, display_selections) = editor.selections.all_adjusted_display(cx);
                 let mut edits = Vec::new();
                 let mut anchors = Vec::new();
@@ -111,7 +112,9 @@ impl Vim {
                 });
             });
         });
-        self.switch_mode(Mode::Normal, false, window, cx);
+        if !mode.is_helix() {
+            self.switch_mode(Mode::Normal, false, window, cx);
+        }
     }
 
     pub fn delete_surrounds(
@@ -219,6 +222,7 @@ impl Vim {
     ) {
         if let Some(will_replace_pair) = object_to_bracket_pair(target) {
             self.stop_recording(cx);
+            let mode = self.mode;
             self.update_editor(window, cx, |_, editor, window, cx| {
                 editor.transact(window, cx, |editor, window, cx| {
                     editor.set_clip_at_line_ends(false, cx);
@@ -233,7 +237,7 @@ impl Vim {
                             newline: false,
                         },
                     };
-                    let surround = pair.end != surround_alias((*text).as_ref());
+                    let surround = pair.end != surround_alias((*text).as_ref()) && !mode.is_helix();
                     let (display_map, selections) = editor.selections.all_adjusted_display(cx);
                     let mut edits = Vec::new();
                     let mut anchors = Vec::new();
@@ -125,6 +125,7 @@ actions!(
         SwitchToVisualLineMode,
         SwitchToVisualBlockMode,
         SwitchToHelixNormalMode,
+        SwitchToHelixSelectMode,
         ClearOperators,
         ClearExchange,
         Tab,
@@ -473,6 +474,14 @@ impl Vim {
                 },
             );
 
+            Vim::action(
+                editor,
+                cx,
+                |vim, _: &SwitchToHelixSelectMode, window, cx| {
+                    vim.switch_mode(Mode::HelixSelect, false, window, cx)
+                },
+            );
+
             Vim::action(editor, cx, |vim, action: &PushObject, window, cx| {
                 vim.push_operator(
                     Operator::Object {
@@ -840,12 +849,12 @@ impl Vim {
                 is_deactivate,
             } => {
                 self.update_editor(window, cx, |vim, editor, window, cx| {
-                    let mark = if *is_deactivate {
-                        "\"".to_string()
+                    if *is_deactivate {
+                        vim.set_mark("\"".to_string(), vec![*anchor], editor.buffer(), window, cx);
                     } else {
-                        "'".to_string()
-                    };
-                    vim.set_mark(mark, vec![*anchor], editor.buffer(), window, cx);
+                        vim.set_mark("'".to_string(), vec![*anchor], editor.buffer(), window, cx);
+                        vim.push_jump(*anchor, editor.buffer(), window, cx);
+                    }
                 });
             }
             _ => {}
@@ -1029,9 +1038,11 @@ impl Vim {
                 }
             }
             Mode::Replace => CursorShape::Underline,
-            Mode::HelixNormal | Mode::Visual | Mode::VisualLine | Mode::VisualBlock => {
-                CursorShape::Block
-            }
+            Mode::HelixNormal
+            | Mode::HelixSelect
+            | Mode::Visual
+            | Mode::VisualLine
+            | Mode::VisualBlock => CursorShape::Block,
             Mode::Insert => {
                 let editor_settings = EditorSettings::get_global(cx);
                 editor_settings.cursor_shape.unwrap_or_default()
@@ -1050,6 +1061,7 @@ impl Vim {
             }
             Mode::Normal
             | Mode::HelixNormal
+            | Mode::HelixSelect
             | Mode::Replace
             | Mode::Visual
             | Mode::VisualLine
@@ -1068,7 +1080,8 @@ impl Vim {
             | Mode::VisualLine
             | Mode::VisualBlock
             | Mode::Replace
-            | Mode::HelixNormal => false,
+            | Mode::HelixNormal
+            | Mode::HelixSelect => false,
             Mode::Normal => true,
         }
     }
@@ -1080,6 +1093,7 @@ impl Vim {
             Mode::Insert => "insert",
             Mode::Replace => "replace",
             Mode::HelixNormal => "helix_normal",
+            Mode::HelixSelect => "helix_select",
         }
         .to_string();
 
@@ -1096,6 +1110,8 @@ impl Vim {
             if active_operator.is_waiting(self.mode) {
                 if matches!(active_operator, Operator::Literal { .. }) {
                     mode = "literal".to_string();
+                } else if matches!(active_operator, Operator::HelixSelectRegex { .. }) {
+                    mode = "helix_regex".to_string();
                 } else {
                     mode = "waiting".to_string();
                 }
@@ -1443,7 +1459,7 @@ impl Vim {
                     })
                 });
             }
-            Mode::Insert | Mode::Replace | Mode::HelixNormal => {}
+            Mode::Insert | Mode::Replace | Mode::HelixNormal | Mode::HelixSelect => {}
         }
     }
 
@@ -1553,6 +1569,7 @@ impl Vim {
                 Mode::Visual | Mode::VisualLine | Mode::VisualBlock => {
                     self.visual_replace(text, window, cx)
                 }
+                Mode::HelixNormal | Mode::HelixSelect => self.helix_replace(text, window, cx),
                 _ => self.clear_operator(window, cx),
             },
             Some(Operator::Digraph { first_char }) => {
@@ -1570,7 +1587,7 @@ impl Vim {
                 self.handle_literal_input(prefix.unwrap_or_default(), &text, window, cx)
             }
             Some(Operator::AddSurrounds { target }) => match self.mode {
-                Mode::Normal => {
+                Mode::Normal | Mode::HelixNormal | Mode::HelixSelect => {
                     if let Some(target) = target {
                         self.add_surrounds(text, target, window, cx);
                         self.clear_operator(window, cx);
@@ -1583,7 +1600,7 @@ impl Vim {
                 _ => self.clear_operator(window, cx),
             },
             Some(Operator::ChangeSurrounds { target }) => match self.mode {
-                Mode::Normal => {
+                Mode::Normal | Mode::HelixNormal | Mode::HelixSelect => {
                     if let Some(target) = target {
                         self.change_surrounds(text, target, window, cx);
                         self.clear_operator(window, cx);
@@ -1592,7 +1609,7 @@ impl Vim {
                 _ => self.clear_operator(window, cx),
             },
             Some(Operator::DeleteSurrounds) => match self.mode {
-                Mode::Normal => {
+                Mode::Normal | Mode::HelixNormal | Mode::HelixSelect => {
                     self.delete_surrounds(text, window, cx);
                     self.clear_operator(window, cx);
                 }
@@ -1627,6 +1644,9 @@ impl Vim {
                 }
             },
             Some(Operator::Jump { line }) => self.jump(text, line, true, window, cx),
+            Some(Operator::HelixSelectRegex { kind, query }) => {
+                self.helix_regex_input(kind, query, &text, window, cx)
+            }
             _ => {
                 if self.mode == Mode::Replace {
                     self.multi_replace(text, window, cx)
@@ -1676,6 +1696,18 @@ pub enum UseSystemClipboard {
     OnYank,
 }
 
+/// An Ex command defined in the `custom_commands` setting.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CustomCommand {
+    /// Runs an action, written as in a keymap: either its name, or an array of its name and
+    /// arguments. `<args>` in the arguments is replaced by the arguments given to the command.
+    Action(serde_json::Value),
+    /// Types keys in normal mode, like `:normal`. Keys can be written in Vim's key notation, like
+    /// `<esc>` or `<c-w>`, and `<args>` types the arguments given to the command.
+    Keys(String),
+}
+
 #[derive(Deserialize)]
 struct VimSettings {
     pub default_mode: Mode,
@@ -1685,6 +1717,9 @@ struct VimSettings {
     pub use_smartcase_find: bool,
     pub custom_digraphs: HashMap<String, Arc<str>>,
     pub highlight_on_yank_duration: u64,
+    pub custom_commands: HashMap<String, CustomCommand>,
+    pub history_size: usize,
+    pub jumplist_size: usize,
 }
 
 #[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
@@ -1696,6 +1731,9 @@ struct VimSettingsContent {
     pub use_smartcase_find: Option<bool>,
     pub custom_digraphs: Option<HashMap<String, Arc<str>>>,
     pub highlight_on_yank_duration: Option<u64>,
+    pub custom_commands: Option<HashMap<String, CustomCommand>>,
+    pub history_size: Option<usize>,
+    pub jumplist_size: Option<usize>,
 }
 
 #[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
@@ -1754,6 +1792,9 @@ impl Settings for VimSettings {
             highlight_on_yank_duration: settings
                 .highlight_on_yank_duration
                 .ok_or_else(Self::missing_default)?,
+            custom_commands: settings.custom_commands.ok_or_else(Self::missing_default)?,
+            history_size: settings.history_size.ok_or_else(Self::missing_default)?,
+            jumplist_size: settings.jumplist_size.ok_or_else(Self::missing_default)?,
         })
     }
 }
@@ -13,7 +13,10 @@ use client::DevServerProjectId;
 use db::{define_connection, query, sqlez::connection::Connection, sqlez_macros::sql};
 use gpui::{Axis, Bounds, WindowBounds, WindowId, point, size};
 use itertools::Itertools;
-use project::debugger::breakpoint_store::{BreakpointState, SourceBreakpoint};
+use project::debugger::breakpoint_store::{
+    BreakpointState, DataBreakpoint, DataBreakpointAccessType, ExceptionBreakpoint,
+    FunctionBreakpoint, SourceBreakpoint,
+};
 
 use language::{LanguageName, Toolchain};
 use project::WorktreeId;
@@ -541,6 +544,44 @@ define_connection! {
         ALTER TABLE breakpoints ADD COLUMN condition TEXT;
         ALTER TABLE breakpoints ADD COLUMN hit_condition TEXT;
     ),
+    sql!(
+        CREATE TABLE exception_breakpoints (
+            workspace_id INTEGER NOT NULL,
+            filter_id TEXT NOT NULL,
+            condition TEXT,
+            state INTEGER NOT NULL,
+            PRIMARY KEY(workspace_id, filter_id),
+            FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+        );
+    ),
+    sql!(
+        CREATE TABLE function_breakpoints (
+            workspace_id INTEGER NOT NULL,
+            name TEXT NOT NULL,
+            condition TEXT,
+            hit_condition TEXT,
+            state INTEGER NOT NULL,
+            PRIMARY KEY(workspace_id, name),
+            FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+        );
+        CREATE TABLE data_breakpoints (
+            workspace_id INTEGER NOT NULL,
+            data_id TEXT NOT NULL,
+            description TEXT NOT NULL,
+            access_type INTEGER NOT NULL,
+            condition TEXT,
+            hit_condition TEXT,
+            state INTEGER NOT NULL,
+            PRIMARY KEY(workspace_id, data_id),
+            FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+        );
+    ),
     ];
 }
 
@@ -629,6 +670,9 @@ impl WorkspaceDb {
             docks,
             session_id: None,
             breakpoints: self.breakpoints(workspace_id),
+            exception_breakpoints: self.exception_breakpoints(workspace_id),
+            function_breakpoints: self.function_breakpoints(workspace_id),
+            data_breakpoints: self.data_breakpoints(workspace_id),
             window_id,
         })
     }
@@ -683,6 +727,9 @@ impl WorkspaceDb {
             window_bounds,
             centered_layout: centered_layout.unwrap_or(false),
             breakpoints: self.breakpoints(workspace_id),
+            exception_breakpoints: self.exception_breakpoints(workspace_id),
+            function_breakpoints: self.function_breakpoints(workspace_id),
+            data_breakpoints: self.data_breakpoints(workspace_id),
             display,
             docks,
             session_id: None,
@@ -736,6 +783,108 @@ impl WorkspaceDb {
         }
     }
 
+    fn exception_breakpoints(&self, workspace_id: WorkspaceId) -> Vec<ExceptionBreakpoint> {
+        let breakpoints: Result<Vec<(String, Option<String>, BreakpointStateWrapper)>> = self
+            .select_bound(sql! {
+                SELECT filter_id, condition, state
+                FROM exception_breakpoints
+                WHERE workspace_id = ?
+            })
+            .and_then(|mut prepared_statement| (prepared_statement)(workspace_id));
+
+        match breakpoints {
+            Ok(breakpoints) => breakpoints
+                .into_iter()
+                .map(|(id, condition, state)| ExceptionBreakpoint {
+                    id: id.into(),
+                    condition: condition.map(Arc::from),
+                    state: state.0.into_owned(),
+                })
+                .collect(),
+            Err(msg) => {
+                log::error!("Exception breakpoints query failed with msg: {msg}");
+                Default::default()
+            }
+        }
+    }
+
+    fn function_breakpoints(&self, workspace_id: WorkspaceId) -> Vec<FunctionBreakpoint> {
+        let breakpoints: Result<
+            Vec<(
+                String,
+                Option<String>,
+                Option<String>,
+                BreakpointStateWrapper,
+            )>,
+        > = self
+            .select_bound(sql! {
+                SELECT name, condition, hit_condition, state
+                FROM function_breakpoints
+                WHERE workspace_id = ?
+            })
+            .and_then(|mut prepared_statement| (prepared_statement)(workspace_id));
+
+        match breakpoints {
+            Ok(breakpoints) => breakpoints
+                .into_iter()
+                .map(
+                    |(name, condition, hit_condition, state)| FunctionBreakpoint {
+                        name: name.into(),
+                        condition: condition.map(Arc::from),
+                        hit_condition: hit_condition.map(Arc::from),
+                        state: state.0.into_owned(),
+                    },
+                )
+                .collect(),
+            Err(msg) => {
+                log::error!("Function breakpoints query failed with msg: {msg}");
+                Default::default()
+            }
+        }
+    }
+
+    fn data_breakpoints(&self, workspace_id: WorkspaceId) -> Vec<DataBreakpoint> {
+        let breakpoints: Result<
+            Vec<(
+                String,
+                String,
+                i32,
+                Option<String>,
+                Option<String>,
+                BreakpointStateWrapper,
+            )>,
+        > = self
+            .select_bound(sql! {
+                SELECT data_id, description, access_type, condition, hit_condition, state
+                FROM data_breakpoints
+                WHERE workspace_id = ?
+            })
+            .and_then(|mut prepared_statement| (prepared_statement)(workspace_id));
+
+        match breakpoints {
+            Ok(breakpoints) => breakpoints
+                .into_iter()
+                .filter_map(
+                    |(data_id, description, access_type, condition, hit_condition, state)| {
+                        Some(DataBreakpoint {
+                            data_id: data_id.into(),
+                            description: description.into(),
+                            access_type: DataBreakpointAccessType::from_int(access_type)?,
+                            condition: condition.map(Arc::from),
+                            hit_condition: hit_condition.map(Arc::from),
+                            state: state.0.into_owned(),
+                            can_persist: true,
+                        })
+                    },
+                )
+                .collect(),
+            Err(msg) => {
+                log::error!("Data breakpoints query failed with msg: {msg}");
+                Default::default()
+            }
+        }
+    }
+
     /// Saves a workspace using the worktree roots. Will garbage collect any workspaces
     /// that used this workspace previously
     pub(crate) async fn save_workspace(&self, workspace: SerializedWorkspace) {
@@ -777,6 +926,60 @@ impl WorkspaceDb {
 
                 }
 
+                conn.exec_bound(sql!(DELETE FROM exception_breakpoints WHERE workspace_id = ?1))?(workspace.id).context("Clearing old exception breakpoints")?;
+
+                for bp in workspace.exception_breakpoints {
+                    let state = BreakpointStateWrapper::from(bp.state);
+                    if let Err(err) = conn.exec_bound(sql!(
+                        INSERT INTO exception_breakpoints (workspace_id, filter_id, condition, state)
+                        VALUES (?1, ?2, ?3, ?4);))?
+                    ((
+                        workspace.id,
+                        bp.id.as_ref(),
+                        bp.condition,
+                        state,
+                    )) {
+                        log::error!("{err}");
+                    }
+                }
+
+                conn.exec_bound(sql!(DELETE FROM function_breakpoints WHERE workspace_id = ?1))?(workspace.id).context("Clearing old function breakpoints")?;
+
+                for bp in workspace.function_breakpoints {
+                    let state = BreakpointStateWrapper::from(bp.state);
+                    if let Err(err) = conn.exec_bound(sql!(
+                        INSERT INTO function_breakpoints (workspace_id, name, condition, hit_condition, state)
+                        VALUES (?1, ?2, ?3, ?4, ?5);))?
+                    ((
+                        workspace.id,
+                        bp.name.as_ref(),
+                        bp.condition,
+                        bp.hit_condition,
+                        state,
+                    )) {
+                        log::error!("{err}");
+                    }
+                }
+
+                conn.exec_bound(sql!(DELETE FROM data_breakpoints WHERE workspace_id = ?1))?(workspace.id).context("Clearing old data breakpoints")?;
+
+                for bp in workspace.data_breakpoints.into_iter().filter(|bp| bp.can_persist) {
+                    let state = BreakpointStateWrapper::from(bp.state);
+                    if let Err(err) = conn.exec_bound(sql!(
+                        INSERT INTO data_breakpoints (workspace_id, data_id, description, access_type, condition, hit_condition, state)
+                        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7);))?
+                    ((
+                        workspace.id,
+                        bp.data_id.as_ref(),
+                        bp.description.as_ref(),
+                        bp.access_type.to_int(),
+                        bp.condition,
+                        bp.hit_condition,
+                        state,
+                    )) {
+                        log::error!("{err}");
+                    }
+                }
 
                 match workspace.location {
                     SerializedWorkspaceLocation::Local(local_paths, local_paths_order) => {
@@ -1529,6 +1732,9 @@ mod tests {
                 );
                 map
             },
+            exception_breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: None,
             window_id: None,
         };
@@ -1608,6 +1814,110 @@ mod tests {
         assert_eq!(loaded_breakpoints[4].path, Arc::from(path));
     }
 
+    #[gpui::test]
+    async fn test_exception_breakpoints() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_exception_breakpoints").await);
+        let id = db.next_id().await.unwrap();
+
+        let uncaught = ExceptionBreakpoint {
+            id: "uncaught".into(),
+            condition: None,
+            state: BreakpointState::Enabled,
+        };
+        let raised = ExceptionBreakpoint {
+            id: "raised".into(),
+            condition: Some("isinstance(e, ValueError)".into()),
+            state: BreakpointState::Disabled,
+        };
+
+        let mut workspace = SerializedWorkspace {
+            id,
+            location: SerializedWorkspaceLocation::from_local_paths(["/tmp"]),
+            center_group: Default::default(),
+            window_bounds: Default::default(),
+            display: Default::default(),
+            docks: Default::default(),
+            centered_layout: false,
+            breakpoints: Default::default(),
+            exception_breakpoints: vec![uncaught.clone(), raised.clone()],
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
+            session_id: None,
+            window_id: None,
+        };
+
+        db.save_workspace(workspace.clone()).await;
+
+        let loaded = db.workspace_for_roots(&["/tmp"]).unwrap();
+        let mut loaded_breakpoints = loaded.exception_breakpoints;
+        loaded_breakpoints.sort_by(|a, b| a.id.cmp(&b.id));
+        assert_eq!(loaded_breakpoints, vec![raised, uncaught.clone()]);
+
+        workspace.exception_breakpoints = vec![uncaught.clone()];
+        db.save_workspace(workspace).await;
+
+        let loaded = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert_eq!(loaded.exception_breakpoints, vec![uncaught]);
+    }
+
+    #[gpui::test]
+    async fn test_function_and_data_breakpoints() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_function_and_data_breakpoints").await);
+        let id = db.next_id().await.unwrap();
+
+        let function_breakpoint = FunctionBreakpoint {
+            condition: Some("count > 2".into()),
+            ..FunctionBreakpoint::new("main::run")
+        };
+        let data_breakpoint = DataBreakpoint {
+            state: BreakpointState::Disabled,
+            hit_condition: Some("3".into()),
+            can_persist: true,
+            ..DataBreakpoint::new(
+                "0x7ffe1234",
+                "config.retries",
+                DataBreakpointAccessType::ReadWrite,
+            )
+        };
+        let session_data_breakpoint =
+            DataBreakpoint::new("1000", "config.timeout", DataBreakpointAccessType::Write);
+
+        let mut workspace = SerializedWorkspace {
+            id,
+            location: SerializedWorkspaceLocation::from_local_paths(["/tmp"]),
+            center_group: Default::default(),
+            window_bounds: Default::default(),
+            display: Default::default(),
+            docks: Default::default(),
+            centered_layout: false,
+            breakpoints: Default::default(),
+            exception_breakpoints: Default::default(),
+            function_breakpoints: vec![function_breakpoint.clone()],
+            data_breakpoints: vec![data_breakpoint.clone(), session_data_breakpoint],
+            session_id: None,
+            window_id: None,
+        };
+
+        db.save_workspace(workspace.clone()).await;
+
+        // Data ids that the adapter didn't mark as persistable are only valid in their session.
+        let loaded = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert_eq!(loaded.function_breakpoints, vec![function_breakpoint]);
+        assert_eq!(loaded.data_breakpoints, vec![data_breakpoint]);
+
+        workspace.function_breakpoints.clear();
+        workspace.data_breakpoints.clear();
+        db.save_workspace(workspace).await;
+
+        let loaded = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert!(loaded.function_breakpoints.is_empty());
+        assert!(loaded.data_breakpoints.is_empty());
+    }
+
     #[gpui::test]
     async fn test_remove_last_breakpoint() {
         env_logger::try_init().ok();
@@ -1648,6 +1958,9 @@ mod tests {
                 );
                 map
             },
+            exception_breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: None,
             window_id: None,
         };
@@ -1680,6 +1993,9 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: collections::BTreeMap::default(),
+            exception_breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: None,
             window_id: None,
         };
@@ -1774,6 +2090,9 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            exception_breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: None,
             window_id: None,
         };
@@ -1787,6 +2106,9 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            exception_breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: None,
             window_id: None,
         };
@@ -1892,6 +2214,9 @@ mod tests {
             center_group,
             window_bounds: Default::default(),
             breakpoints: Default::default(),
+            exception_breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
@@ -1927,6 +2252,9 @@ mod tests {
             center_group: Default::default(),
             window_bounds: Default::default(),
             breakpoints: Default::default(),
+            exception_breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
@@ -1943,6 +2271,9 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            exception_breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: None,
             window_id: Some(2),
         };
@@ -1983,6 +2314,9 @@ mod tests {
             center_group: Default::default(),
             window_bounds: Default::default(),
             breakpoints: Default::default(),
+            exception_breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
@@ -2023,6 +2357,9 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            exception_breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: Some("session-id-1".to_owned()),
             window_id: Some(10),
         };
@@ -2036,6 +2373,9 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            exception_breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: Some("session-id-1".to_owned()),
             window_id: Some(20),
         };
@@ -2049,6 +2389,9 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            exception_breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: Some("session-id-2".to_owned()),
             window_id: Some(30),
         };
@@ -2062,6 +2405,9 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            exception_breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: None,
             window_id: None,
         };
@@ -2080,6 +2426,9 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            exception_breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: Some("session-id-2".to_owned()),
             window_id: Some(50),
         };
@@ -2093,6 +2442,9 @@ mod tests {
             center_group: Default::default(),
             window_bounds: Default::default(),
             breakpoints: Default::default(),
+            exception_breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
@@ -2151,6 +2503,9 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             breakpoints: Default::default(),
+            exception_breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             centered_layout: false,
             session_id: None,
             window_id: None,
@@ -2199,6 +2554,9 @@ mod tests {
             centered_layout: false,
             session_id: Some("one-session".to_owned()),
             breakpoints: Default::default(),
+            exception_breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             window_id: Some(window_id),
         })
         .collect::<Vec<_>>();
@@ -2291,6 +2649,9 @@ mod tests {
             centered_layout: false,
             session_id: Some("one-session".to_owned()),
             breakpoints: Default::default(),
+            exception_breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             window_id: Some(window_id),
         })
         .collect::<Vec<_>>();
@@ -10,7 +10,12 @@ use db::sqlez::{
 };
 use gpui::{AsyncWindowContext, Entity, WeakEntity};
 use itertools::Itertools as _;
-use project::{Project, debugger::breakpoint_store::SourceBreakpoint};
+use project::{
+    Project,
+    debugger::breakpoint_store::{
+        DataBreakpoint, ExceptionBreakpoint, FunctionBreakpoint, SourceBreakpoint,
+    },
+};
 use remote::ssh_session::SshProjectId;
 use serde::{Deserialize, Serialize};
 use std::{
@@ -265,6 +270,9 @@ pub(crate) struct SerializedWorkspace {
     pub(crate) docks: DockStructure,
     pub(crate) session_id: Option<String>,
     pub(crate) breakpoints: BTreeMap<Arc<Path>, Vec<SourceBreakpoint>>,
+    pub(crate) exception_breakpoints: Vec<ExceptionBreakpoint>,
+    pub(crate) function_breakpoints: Vec<FunctionBreakpoint>,
+    pub(crate) data_breakpoints: Vec<DataBreakpoint>,
     pub(crate) window_id: Option<u64>,
 }
 
@@ -48,7 +48,33 @@ pub fn schedule_resolved_task(
 ) {
     let debug_config = resolved_task.resolved_debug_adapter_config();
 
-    if let Some(spawn_in_terminal) = resolved_task.resolved.take() {
+    if let Some(mut spawn_in_terminal) = resolved_task.resolved.take() {
+        if !resolved_task.original_task().depends_on.is_empty() {
+            let dependencies = workspace
+                .project()
+                .read(cx)
+                .task_store()
+                .read(cx)
+                .task_inventory()
+                .map(|inventory| {
+                    inventory
+                        .read(cx)
+                        .resolve_dependencies(&task_source_kind, &resolved_task, cx)
+                });
+            match dependencies {
+                Some(Ok(dependencies)) => spawn_in_terminal.dependencies = dependencies,
+                Some(Err(e)) => {
+                    log::error!("Failed to schedule task with dependencies: {e:#}");
+                    workspace.show_error(&e, cx);
+                    return;
+                }
+                None => {
+                    log::warn!("Cannot schedule tasks with dependencies without a task inventory");
+                    return;
+                }
+            }
+        }
+
         if let Some(debug_config) = debug_config {
             workspace
                 .debug_task_queue
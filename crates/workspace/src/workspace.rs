@@ -138,12 +138,16 @@ actions!(
         Pause,
         Restart,
         StepInto,
+        StepIntoInstruction,
+        StepIntoTarget,
         StepOver,
+        StepOverInstruction,
         StepOut,
         StepBack,
         Stop,
         ToggleIgnoreBreakpoints,
-        ClearAllBreakpoints
+        ClearAllBreakpoints,
+        JumpToCursor
     ]
 );
 
@@ -990,7 +994,10 @@ impl Workspace {
             window,
             |workspace, _, event, window, cx| match event {
                 BreakpointStoreEvent::BreakpointsUpdated(_, _)
-                | BreakpointStoreEvent::BreakpointsCleared(_) => {
+                | BreakpointStoreEvent::BreakpointsCleared(_)
+                | BreakpointStoreEvent::ExceptionBreakpointsUpdated
+                | BreakpointStoreEvent::FunctionBreakpointsUpdated
+                | BreakpointStoreEvent::DataBreakpointsUpdated => {
                     workspace.serialize_workspace(window, cx);
                 }
                 BreakpointStoreEvent::ActiveDebugLineChanged => {}
@@ -4729,9 +4736,16 @@ impl Workspace {
         };
 
         if let Some(location) = location {
-            let breakpoints = self.project.update(cx, |project, cx| {
-                project.breakpoint_store().read(cx).all_breakpoints(cx)
-            });
+            let (breakpoints, exception_breakpoints, function_breakpoints, data_breakpoints) =
+                self.project.update(cx, |project, cx| {
+                    let breakpoint_store = project.breakpoint_store().read(cx);
+                    (
+                        breakpoint_store.all_breakpoints(cx),
+                        breakpoint_store.all_exception_breakpoints(),
+                        breakpoint_store.all_function_breakpoints(),
+                        breakpoint_store.all_data_breakpoints(),
+                    )
+                });
 
             let center_group = build_serialized_pane_group(&self.center.root, window, cx);
             let docks = build_serialized_docks(self, window, cx);
@@ -4746,6 +4760,9 @@ impl Workspace {
                 centered_layout: self.centered_layout,
                 session_id: self.session_id.clone(),
                 breakpoints,
+                exception_breakpoints,
+                function_breakpoints,
+                data_breakpoints,
                 window_id: Some(window.window_handle().window_id().as_u64()),
             };
             return window.spawn(cx, async move |_| {
@@ -4888,6 +4905,18 @@ impl Workspace {
                     project
                         .breakpoint_store()
                         .update(cx, |breakpoint_store, cx| {
+                            breakpoint_store.with_serialized_exception_breakpoints(
+                                serialized_workspace.exception_breakpoints,
+                                cx,
+                            );
+                            breakpoint_store.with_serialized_function_breakpoints(
+                                serialized_workspace.function_breakpoints,
+                                cx,
+                            );
+                            breakpoint_store.with_serialized_data_breakpoints(
+                                serialized_workspace.data_breakpoints,
+                                cx,
+                            );
                             breakpoint_store
                                 .with_serialized_breakpoints(serialized_workspace.breakpoints, cx)
                         })
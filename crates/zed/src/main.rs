@@ -516,8 +516,10 @@ fn main() {
         tab_switcher::init(cx);
         outline::init(cx);
         project_symbols::init(cx);
+        type_hierarchy::init(cx);
         project_panel::init(cx);
         outline_panel::init(cx);
+        call_hierarchy_panel::init(cx);
         tasks_ui::init(cx);
         snippets_ui::init(cx);
         channel::init(&app_state.client.clone(), app_state.user_store.clone(), cx);
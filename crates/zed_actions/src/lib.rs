@@ -207,7 +207,7 @@ pub struct OpenRecent {
 }
 
 impl_actions!(projects, [OpenRecent]);
-actions!(projects, [OpenRemote]);
+actions!(projects, [OpenRemote, OpenWorktree]);
 
 /// Where to spawn the task in the UI.
 #[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]